@@ -638,8 +638,8 @@ fn extend_drives_from_csv(drives: &mut Vec<DriveLetter>, value: &str) {
 #[path = "args_help.rs"]
 mod help;
 pub(crate) use help::{
-    print_aggregate_help, print_daemon_help, print_deleted_help, print_help, print_snapshot_help,
-    print_stats_help, print_status_help, print_version,
+    print_aggregate_help, print_daemon_help, print_deleted_help, print_growth_help, print_help,
+    print_query_help, print_snapshot_help, print_stats_help, print_status_help, print_version,
 };
 
 #[cfg(test)]
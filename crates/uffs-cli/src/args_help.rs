@@ -24,6 +24,7 @@ EXAMPLES:
   uffs '*' --mft-file C.bin            Offline MFT search
   uffs --ext rs,toml                   Find Rust project files
   uffs --type picture --min-size 10MB  Large images
+  uffs @stale-vms --drive D            Run a saved query (see --query)
   uffs --update doctor                 Self-update health check
 
 COMMANDS:
@@ -31,9 +32,11 @@ COMMANDS:
   --stats [PATH]       Show filesystem statistics
   --agg <PRESET>       Run aggregate analytics
   --deleted            Forensic tombstone read: recently-deleted files from an MFT
+  --growth             Folder growth between two MFT captures (offline)
   --snapshot           Capture the live MFT to a baseline file (Windows, for --diff)
   --daemon <ACTION>    Manage the UFFS daemon (start/stop/load/status)
   --mcp <ACTION>       Manage the UFFS MCP server
+  --query <ACTION>     Manage saved queries (save/list/delete); run with @NAME
   --update [ACTION]    Self-update (snapshot/acquire/apply/doctor/recover)
   --status             Show combined system status
 
@@ -58,6 +61,25 @@ COMMON OPTIONS:
   --diff <BASELINE>       Search the DELETED set vs a baseline MFT capture
                           (combine with any filter: --diff C_old.bin --drive C
                           '*.txt' --newer 30d). Needs the drive loaded.
+                          @<TIME> (@yesterday, @2026-10-01) diffs against
+                          the daemon's scheduled snapshots or retained
+                          index history instead.
+  --as-of <TIME>          Search the index as it stood at TIME (daemon
+                          [history] must be enabled)
+  --hash-in <FILE>        Keep files whose content hash (SHA-256, SHA-1 or
+                          MD5) is on the list; only size-matched files are
+                          hashed, and digests are cached for later runs
+  --hash-mode <MODE>      match (default) or known_good: drop listed files
+                          instead (NSRL RDS subsets)
+  --patterns-from <FILE>  Match names against every entry of FILE (one name,
+                          glob or >regex per line) in a single pass; the
+                          matched_pattern column names the entry that hit
+  --field <NAME=EXPR>     Define a computed field for this query, e.g.
+                          avgsize=treesize/descendants (repeatable); usable
+                          in --where, --sort and stats:/hist:/range: specs.
+                          Saved ones live in daemon.toml [fields]
+  --where <FIELD OP N>    Compare any numeric or computed field with an
+                          integer (op: = != < <= > >=), e.g. 'avgsize>1MB'
   --min-size <SIZE>       Minimum file size (e.g. 100KB, 10MB)
   --max-size <SIZE>       Maximum file size
   --profile               Show timing breakdown
@@ -228,6 +250,39 @@ pub(crate) fn print_deleted_help() {
     print!("{DELETED_HELP}");
 }
 
+/// Help text for `uffs --growth`.
+const GROWTH_HELP: &str = "\
+uffs --growth — Folder growth between two MFT captures (offline)
+
+Compares every folder at a depth between an older capture and a newer one of
+the same volume and ranks them by how much they grew — the `growth:` aggregate
+a snapshot diff runs in the daemon (`uffs '*' --diff @yesterday --agg
+growth:path`), without a daemon or a stored snapshot.
+
+USAGE:  uffs --growth --baseline <OLD> --mft-file <NEW> [OPTIONS]
+
+SOURCES (both required):
+  --baseline <PATH>    The older MFT capture.
+  --mft-file <PATH>    The newer MFT capture.
+
+OPTIONS:
+  -d, --drive <VOL>    Volume both captures come from (`C`, `srv01\\C`);
+                       default: inferred from the newer capture's file name.
+  --depth <N>          Folder depth to compare (1 = the root's children).
+  --top <N>            Folders to show (default 30).
+  --rank <ORDER>       abs (bytes gained, default) or rel (percentage grown).
+  -f, --format <FMT>   table (default on a terminal), csv, tsv, json
+
+EXAMPLE:
+  uffs --growth --baseline C_monday.bin --mft-file C_friday.bin --depth 2
+";
+
+/// Print growth help.
+#[expect(clippy::print_stdout, reason = "intentional help output")]
+pub(crate) fn print_growth_help() {
+    print!("{GROWTH_HELP}");
+}
+
 /// Help text for `uffs --agg`.
 const AGGREGATE_HELP: &str = "\
 uffs --agg — Run aggregate analytics on the filesystem index
@@ -236,7 +291,7 @@ USAGE:  uffs --agg <PRESET> [OPTIONS]
 
 ARGUMENTS:
   <PRESET>             overview, by_type, by_extension, by_drive,
-                       by_size, by_age, count
+                       by_size, by_age, name_years, count
 
               Each preset has a built-in top-N cap (e.g. by_extension
               caps at 50 buckets). --agg-cursor/--agg-page-size page
@@ -244,6 +299,9 @@ ARGUMENTS:
               than a preset's cap, use the raw terms syntax instead:
                 uffs --agg 'terms:extension,top=2000' --format json
 
+              Raw terms can key on a regex capture group:
+                uffs --agg 'terms:regex(name, \"^(\\d{4})-\"),unmatched=true'
+
 OPTIONS:
   --format <FMT>       Output format: table (default), csv, json
   --data-dir <PATH>    Data directory with drive_* subdirs
@@ -279,3 +337,38 @@ OPTIONS:
 pub(crate) fn print_status_help() {
     print!("{STATUS_HELP}");
 }
+
+/// Help text for `uffs --query`.
+const QUERY_HELP: &str = "\
+uffs --query — Manage the daemon's saved queries (queries.toml)
+
+USAGE:  uffs --query save <NAME> [OPTIONS] -- <SEARCH ARGS>...
+        uffs --query list [--json]
+        uffs --query delete <NAME>
+        uffs @<NAME> [--param KEY=VALUE]... [SEARCH ARGS]...
+
+A saved query is a search stored under a name: pattern, filters, --sort,
+--columns, --agg, output options. Arguments after `@NAME` are appended, so
+they narrow or override the stored search (`uffs @big-logs --drive D`).
+
+PLACEHOLDERS:
+  `{name}` in a saved argument is filled from `--param name=VALUE` at run
+  time, falling back to the `--default` given at save time. Braces that are
+  not a name (regex quantifiers such as `{2,3}`) are left alone.
+
+SAVE OPTIONS:
+  --description <TEXT>  One-line summary (shown by list and in MCP clients)
+  --default <KEY=VALUE> Default for a placeholder (repeatable)
+  --replace             Overwrite an existing query of the same name
+
+EXAMPLE:
+  uffs --query save stale-vms --default age=90d -- \\
+       --ext vmdk,vhdx --older {age} --sort -size
+  uffs @stale-vms --drive D --param age=1y
+";
+
+/// Print saved-query help.
+#[expect(clippy::print_stdout, reason = "intentional help output")]
+pub(crate) fn print_query_help() {
+    print!("{QUERY_HELP}");
+}
@@ -31,6 +31,11 @@ pub(crate) mod deleted;
 /// admin-only work up front and decide once (elevate / continue-without /
 /// abort) instead of failing mid-flow. Keeps both flows' elevation UX aligned.
 pub(crate) mod elevation;
+/// `uffs --growth --baseline <OLD> --mft-file <NEW>` — offline folder growth
+/// between two MFT captures.
+pub(crate) mod growth;
+/// `uffs --query save|list|delete` and the `uffs @name` saved-query expansion.
+pub(crate) mod queries;
 /// `uffs --daemon resident` — permanent residency: per-user login item
 /// that starts `uffsd --no-retire` at login (Run key / LaunchAgent /
 /// systemd user unit), so the daemon is warm before the first search
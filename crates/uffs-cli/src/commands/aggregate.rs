@@ -19,7 +19,7 @@
 use std::io::Write;
 
 use anyhow::Result;
-use uffs_client::protocol::AggregateResultWire;
+use uffs_client::protocol::{AggregateResultWire, PivotWire};
 
 use super::{format_number, format_size};
 
@@ -53,6 +53,10 @@ pub(crate) fn print_table_results(results: &[AggregateResultWire]) -> Result<()>
                         "  Avg:    {}",
                         format_size(uffs_client::format::f64_to_u64(stats.avg))
                     )?;
+                    for pct in &stats.percentiles {
+                        let name = format!("{}:", pct.rank);
+                        writeln!(stdout, "  {name:<8}{}", format_size(pct.value))?;
+                    }
                     if stats.waste_bytes > 0 {
                         writeln!(
                             stdout,
@@ -77,6 +81,18 @@ pub(crate) fn print_table_results(results: &[AggregateResultWire]) -> Result<()>
                     print_duplicate_table(&mut stdout, result)?;
                 }
             }
+            "growth" => {
+                if result.buckets.is_empty() {
+                    writeln!(stdout, "  (no data)")?;
+                } else {
+                    print_growth_table(&mut stdout, result)?;
+                }
+            }
+            "pivot" => match &result.pivot {
+                Some(pivot) if pivot.heatmap => print_heatmap(&mut stdout, pivot)?,
+                Some(pivot) if !pivot.rows.is_empty() => print_pivot_table(&mut stdout, pivot)?,
+                _ => writeln!(stdout, "  (no data)")?,
+            },
             "missing" | "distinct" => {
                 if let Some(value) = result.value {
                     writeln!(stdout, "  {}: {}", result.kind, format_number(value))?;
@@ -140,6 +156,16 @@ pub(crate) fn print_csv_results(results: &[AggregateResultWire], tsv: bool) -> R
                 writeln!(stdout, "# {label}")?;
                 print_csv_duplicates(&mut stdout, result, sep)?;
             }
+            "growth" => {
+                writeln!(stdout, "# {label}")?;
+                print_csv_growth(&mut stdout, result, sep)?;
+            }
+            "pivot" => {
+                if let Some(pivot) = &result.pivot {
+                    writeln!(stdout, "# {label}")?;
+                    print_csv_pivot(&mut stdout, pivot, sep)?;
+                }
+            }
             "missing" | "distinct" => {
                 writeln!(stdout, "# {label}")?;
                 writeln!(stdout, "value")?;
@@ -179,6 +205,9 @@ fn print_table_buckets(stdout: &mut impl Write, result: &AggregateResultWire) ->
             share_c,
             share_b
         )?;
+        if !row.percentiles.is_empty() {
+            writeln!(stdout, "    {}", format_percentiles(&row.percentiles))?;
+        }
         // Sample rows (top-hits).
         for sr in &row.sample_rows {
             let name = sr.fields.get("name").map_or("?", |s| s.as_str());
@@ -193,20 +222,7 @@ fn print_table_buckets(stdout: &mut impl Write, result: &AggregateResultWire) ->
                 .map_or(String::new(), |s| format!(" mod:{s}"));
             writeln!(stdout, "    → {name}{size}{modified}")?;
         }
-        // Nested sub-aggregation buckets.
-        for sub in &row.sub_buckets {
-            let sc = sub.share_count.unwrap_or(0.0);
-            let sb = sub.share_bytes.unwrap_or(0.0);
-            writeln!(
-                stdout,
-                "    ├─ {:<26} {:>12} {:>14} {:>7.1}% {:>7.1}%",
-                sub.key,
-                format_number(sub.count),
-                format_size(sub.total_bytes),
-                sc,
-                sb
-            )?;
-        }
+        print_table_sub_buckets(stdout, &row.sub_buckets, 1)?;
     }
     if let Some(other) = result.other_count {
         if other > 0 {
@@ -230,6 +246,39 @@ fn print_table_buckets(stdout: &mut impl Write, result: &AggregateResultWire) ->
     Ok(())
 }
 
+/// One line of size percentiles: `p50 4.0 KB · p90 1.2 MB`.
+fn format_percentiles(percentiles: &[uffs_client::protocol::PercentileWire]) -> String {
+    percentiles
+        .iter()
+        .map(|pct| format!("{} {}", pct.rank, format_size(pct.value)))
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// Print nested sub-aggregation buckets under their parent row, two
+/// columns further in per level so the other columns stay aligned.
+fn print_table_sub_buckets(
+    stdout: &mut impl Write,
+    subs: &[uffs_client::protocol::BucketWire],
+    depth: usize,
+) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    let width = 28_usize.saturating_sub(2 * depth);
+    for sub in subs {
+        writeln!(
+            stdout,
+            "  {indent}├─ {:<width$} {:>12} {:>14} {:>7.1}% {:>7.1}%",
+            sub.key,
+            format_number(sub.count),
+            format_size(sub.total_bytes),
+            sub.share_count.unwrap_or(0.0),
+            sub.share_bytes.unwrap_or(0.0),
+        )?;
+        print_table_sub_buckets(stdout, &sub.sub_buckets, depth + 1)?;
+    }
+    Ok(())
+}
+
 /// Print dedicated duplicate-group table.
 ///
 /// Shows a summary header with total groups/files/reclaimable, then a
@@ -308,6 +357,181 @@ fn print_duplicate_table(stdout: &mut impl Write, result: &AggregateResultWire)
     Ok(())
 }
 
+/// Print the folder growth table: size before → after, the change, the
+/// relative change and how the folder was paired with the baseline.
+fn print_growth_table(stdout: &mut impl Write, result: &AggregateResultWire) -> Result<()> {
+    writeln!(
+        stdout,
+        "  {:<48} {:>12} {:>12} {:>13} {:>9} {:>8}",
+        "Folder", "Before", "After", "Change", "%", "Match"
+    )?;
+    writeln!(
+        stdout,
+        "  {:-<48} {:-<12} {:-<12} {:-<13} {:-<9} {:-<8}",
+        "", "", "", "", "", ""
+    )?;
+
+    for row in &result.buckets {
+        let Some(growth) = &row.growth else { continue };
+        let pct = growth
+            .growth_pct
+            .map_or_else(|| "new".to_owned(), |p| format!("{p:+.1}%"));
+        writeln!(
+            stdout,
+            "  {:<48} {:>12} {:>12} {:>13} {:>9} {:>8}",
+            truncate_str(&row.key, 48),
+            format_size(growth.before_bytes),
+            format_size(row.total_bytes),
+            format_signed_size(growth.delta_bytes),
+            pct,
+            growth.matched,
+        )?;
+    }
+
+    if let Some(total) = result.total_groups {
+        let shown = result.buckets.len();
+        if total > shown {
+            writeln!(
+                stdout,
+                "  ... and {} more folders",
+                format_number((total - shown) as u64), // usize→u64 lossless on 64-bit
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a pivot as a matrix: one line per row, a `Total` column on the
+/// right and a `Total` line at the bottom. Empty cells show `-`.
+fn print_pivot_table(stdout: &mut impl Write, pivot: &PivotWire) -> Result<()> {
+    let key_width = pivot
+        .rows
+        .iter()
+        .map(|row| row.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(12, 28);
+    let corner = format!("{} \\ {}", pivot.row_field, pivot.col_field);
+    write!(stdout, "  {:<key_width$}", truncate_str(&corner, key_width))?;
+    for column in &pivot.columns {
+        write!(stdout, " {:>12}", truncate_str(column, 12))?;
+    }
+    writeln!(stdout, " {:>12}", "Total")?;
+    writeln!(
+        stdout,
+        "  {:-<key_width$}{}",
+        "",
+        " ------------".repeat(pivot.columns.len() + 1)
+    )?;
+
+    let cell = |value: Option<f64>| {
+        value.map_or_else(|| "-".to_owned(), |v| format_pivot_value(&pivot.metric, v))
+    };
+    for row in &pivot.rows {
+        write!(
+            stdout,
+            "  {:<key_width$}",
+            truncate_str(&row.key, key_width)
+        )?;
+        for &value in &row.values {
+            write!(stdout, " {:>12}", cell(value))?;
+        }
+        writeln!(stdout, " {:>12}", cell(Some(row.total)))?;
+    }
+    write!(stdout, "  {:<key_width$}", "Total")?;
+    for &total in &pivot.column_totals {
+        write!(stdout, " {:>12}", cell(Some(total)))?;
+    }
+    writeln!(stdout, " {:>12}", cell(Some(pivot.grand_total)))?;
+    match &pivot.time_zone {
+        Some(zone) => writeln!(stdout, "  ({}, {zone})", pivot.metric)?,
+        None => writeln!(stdout, "  ({})", pivot.metric)?,
+    }
+    Ok(())
+}
+
+/// Print a weekday × hour-of-day pivot as a shaded grid: one line per
+/// weekday, two characters per hour, darker for hours nearer the
+/// busiest one.
+fn print_heatmap(stdout: &mut impl Write, pivot: &PivotWire) -> Result<()> {
+    let peak = pivot
+        .rows
+        .iter()
+        .flat_map(|row| {
+            row.values
+                .iter()
+                .zip(&pivot.columns)
+                .filter_map(move |(value, hour)| value.map(|v| (v, row.key.as_str(), hour)))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+    let zone = pivot.time_zone.as_deref().unwrap_or("UTC");
+    writeln!(stdout, "  {} ({}, {zone})", pivot.row_field, pivot.metric)?;
+    write!(stdout, "  {:<5}", "")?;
+    for hour in pivot.columns.iter().step_by(3) {
+        write!(stdout, "{hour:<6}")?;
+    }
+    writeln!(stdout, " {:>12}", "Total")?;
+    let top = peak.map_or(0.0, |(value, _, _)| value);
+    for row in &pivot.rows {
+        write!(stdout, "  {:<5}", row.key)?;
+        for value in &row.values {
+            write!(stdout, "{}", heat_shade(value.unwrap_or(0.0), top))?;
+        }
+        writeln!(
+            stdout,
+            " {:>12}",
+            format_pivot_value(&pivot.metric, row.total)
+        )?;
+    }
+    if let Some((value, day, hour)) = peak {
+        writeln!(
+            stdout,
+            "  Peak: {day} {hour}:00 ({})   ░ <25%  ▒ <50%  ▓ <75%  █ of peak",
+            format_pivot_value(&pivot.metric, value)
+        )?;
+    }
+    Ok(())
+}
+
+/// Two-character cell for `value` against the grid's `peak`.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "share of the peak picks one of four shades"
+)]
+fn heat_shade(value: f64, peak: f64) -> &'static str {
+    if value <= 0.0 || peak <= 0.0 {
+        return "  ";
+    }
+    let ratio = value / peak;
+    if ratio >= 0.75 {
+        "██"
+    } else if ratio >= 0.5 {
+        "▓▓"
+    } else if ratio >= 0.25 {
+        "▒▒"
+    } else {
+        "░░"
+    }
+}
+
+/// Render one pivot value for its metric: counts as numbers,
+/// percentages with one decimal, everything else as a size.
+fn format_pivot_value(metric: &str, value: f64) -> String {
+    let whole = uffs_client::format::f64_to_u64(value);
+    match metric {
+        "count" => format_number(whole),
+        "waste_pct" | "share_count" | "share_bytes" => format!("{value:.1}%"),
+        _ => format_size(whole),
+    }
+}
+
+/// `format_size` with an explicit `+` / `-` sign.
+fn format_signed_size(delta: i64) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    format!("{sign}{}", format_size(delta.unsigned_abs()))
+}
+
 /// Truncate a string to `max` chars, appending `…` if truncated.
 fn truncate_str(s: &str, max: usize) -> String {
     if s.chars().count() <= max {
@@ -360,21 +584,7 @@ fn print_csv_buckets(
             write!(stdout, "{sep}{json}")?;
         }
         writeln!(stdout)?;
-        // Nested sub-aggregation rows prefixed with parent key.
-        for sub in &row.sub_buckets {
-            writeln!(
-                stdout,
-                "{}/{}{sep}{}{sep}{}{sep}{}{sep}{:.2}{sep}{:.2}{sep}{:.2}",
-                row.key,
-                sub.key,
-                sub.count,
-                sub.total_bytes,
-                sub.total_allocated.unwrap_or(0),
-                sub.avg_size.unwrap_or(0.0),
-                sub.share_count.unwrap_or(0.0),
-                sub.share_bytes.unwrap_or(0.0),
-            )?;
-        }
+        print_csv_sub_buckets(stdout, &row.key, &row.sub_buckets, sep)?;
     }
     // CSV metadata comments.
     if let Some(other) = result.other_count {
@@ -391,6 +601,31 @@ fn print_csv_buckets(
     Ok(())
 }
 
+/// Render nested sub-aggregation rows keyed by their `/`-joined path
+/// from the top-level bucket (`C:/rs/...`).
+fn print_csv_sub_buckets(
+    stdout: &mut impl Write,
+    parent_key: &str,
+    subs: &[uffs_client::protocol::BucketWire],
+    sep: char,
+) -> Result<()> {
+    for sub in subs {
+        let key = format!("{parent_key}/{}", sub.key);
+        writeln!(
+            stdout,
+            "{key}{sep}{}{sep}{}{sep}{}{sep}{:.2}{sep}{:.2}{sep}{:.2}",
+            sub.count,
+            sub.total_bytes,
+            sub.total_allocated.unwrap_or(0),
+            sub.avg_size.unwrap_or(0.0),
+            sub.share_count.unwrap_or(0.0),
+            sub.share_bytes.unwrap_or(0.0),
+        )?;
+        print_csv_sub_buckets(stdout, &key, &sub.sub_buckets, sep)?;
+    }
+    Ok(())
+}
+
 /// Render duplicate groups in CSV/TSV format with dedicated columns.
 fn print_csv_duplicates(
     stdout: &mut impl Write,
@@ -446,6 +681,78 @@ fn print_csv_duplicates(
     Ok(())
 }
 
+/// Render folder growth rows in CSV/TSV format.
+fn print_csv_growth(
+    stdout: &mut impl Write,
+    result: &AggregateResultWire,
+    sep: char,
+) -> Result<()> {
+    writeln!(
+        stdout,
+        "key{sep}before_bytes{sep}after_bytes{sep}delta_bytes{sep}growth_pct{sep}\
+         before_allocated{sep}after_allocated{sep}delta_allocated{sep}\
+         before_descendants{sep}after_descendants{sep}delta_descendants{sep}matched"
+    )?;
+
+    for row in &result.buckets {
+        let Some(growth) = &row.growth else { continue };
+        let pct = growth
+            .growth_pct
+            .map_or_else(String::new, |p| format!("{p:.2}"));
+        writeln!(
+            stdout,
+            "{}{sep}{}{sep}{}{sep}{}{sep}{pct}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            row.key,
+            growth.before_bytes,
+            row.total_bytes,
+            growth.delta_bytes,
+            growth.before_allocated,
+            row.total_allocated.unwrap_or(0),
+            growth.delta_allocated,
+            growth.before_descendants,
+            row.count,
+            growth.delta_descendants,
+            growth.matched,
+        )?;
+    }
+
+    if let Some(total) = result.total_groups {
+        let shown = result.buckets.len();
+        if total > shown {
+            writeln!(stdout, "# remaining_folders={}", total - shown)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Render a pivot in CSV/TSV format: the matrix with a trailing `total`
+/// column and line. Empty cells stay blank.
+fn print_csv_pivot(stdout: &mut impl Write, pivot: &PivotWire, sep: char) -> Result<()> {
+    write!(stdout, "{}\\{}", pivot.row_field, pivot.col_field)?;
+    for column in &pivot.columns {
+        write!(stdout, "{sep}{column}")?;
+    }
+    writeln!(stdout, "{sep}total")?;
+    for row in &pivot.rows {
+        write!(stdout, "{}", row.key)?;
+        for value in &row.values {
+            write!(
+                stdout,
+                "{sep}{}",
+                value.map_or_else(String::new, |v| v.to_string())
+            )?;
+        }
+        writeln!(stdout, "{sep}{}", row.total)?;
+    }
+    write!(stdout, "total")?;
+    for total in &pivot.column_totals {
+        write!(stdout, "{sep}{total}")?;
+    }
+    writeln!(stdout, "{sep}{}", pivot.grand_total)?;
+    Ok(())
+}
+
 // ── Raw Value wrappers (thin-client path) ──────────────────────────────
 
 /// Print aggregate results from raw JSON values in table format.
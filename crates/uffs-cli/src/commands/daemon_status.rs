@@ -159,7 +159,7 @@ pub(crate) fn health(status: &DaemonStatus) -> (Glyph, String) {
         DaemonStatus::Refreshing { drives } => {
             let list: String = drives
                 .iter()
-                .map(|letter| format!("{letter}:"))
+                .map(|volume| volume.drive_label())
                 .collect::<Vec<_>>()
                 .join(", ");
             (Glyph::Warn, format!("refreshing ({list})"))
@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `uffs --growth --baseline <OLD> --mft-file <NEW>` — offline folder growth.
+//!
+//! The daemon answers `growth:` aggregates inside a snapshot diff
+//! (`uffs '*' --diff @yesterday --agg growth:path`). This command runs the
+//! same comparison between two MFT captures with no daemon and no stored
+//! snapshot: both captures are loaded into compact indexes, compared by
+//! [`compute_growth`], and the result goes through the `--agg` renderers,
+//! so `--format table|csv|tsv|json` prints exactly what the diff path does.
+
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use uffs_client::protocol::{AggregateResultWire, BucketWire, GrowthWire};
+use uffs_core::aggregate::growth::GrowthRow;
+use uffs_core::aggregate::{GrowthRank, GrowthResult, GrowthSpec, compute_growth};
+use uffs_core::compact::{MftSource, load_drive};
+use uffs_mft::platform::VolumeId;
+
+/// Parsed `uffs --growth` invocation.
+#[derive(Debug)]
+struct GrowthArgs {
+    /// The older capture.
+    baseline: PathBuf,
+    /// The newer capture.
+    current: PathBuf,
+    /// Volume both captures come from; inferred from the newer capture's
+    /// file name when absent.
+    volume: Option<VolumeId>,
+    /// Depth, top-N and rank of the comparison.
+    spec: GrowthSpec,
+}
+
+/// Run `uffs --growth --baseline <OLD> --mft-file <NEW> [--drive D]
+/// [--depth N] [--top N] [--rank abs|rel] [--format FMT]`.
+///
+/// # Errors
+///
+/// Returns an error on bad arguments or when a capture cannot be loaded.
+pub(crate) fn run_growth(args: &[String]) -> Result<()> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        crate::args::print_growth_help();
+        return Ok(());
+    }
+
+    let parsed = parse_growth_args(args)?;
+    // `no_cache`: a one-off comparison must not leave compact caches of
+    // two captures of the same volume behind.
+    let (current, _) = load_drive(
+        &MftSource::File(parsed.current.clone(), parsed.volume),
+        true,
+    )
+    .with_context(|| format!("failed to load capture '{}'", parsed.current.display()))?;
+    // Read the baseline as the same volume so both sides resolve paths under
+    // one root and match folders by path when a file reference was reused.
+    let (baseline, _) = load_drive(
+        &MftSource::File(parsed.baseline.clone(), Some(current.volume())),
+        true,
+    )
+    .with_context(|| format!("failed to load baseline '{}'", parsed.baseline.display()))?;
+
+    let result = compute_growth(&baseline, &current, &parsed.spec);
+    let wire = serde_json::to_value(growth_to_wire(&parsed.spec, &result))?;
+    crate::commands::search::dispatch::write_aggregations(&[wire], args)
+}
+
+/// Parse the `--growth` argument vector. Both captures are required;
+/// `--format` is left in `args` for the renderer.
+fn parse_growth_args(args: &[String]) -> Result<GrowthArgs> {
+    let mut baseline: Option<PathBuf> = None;
+    let mut current: Option<PathBuf> = None;
+    let mut volume: Option<VolumeId> = None;
+    let mut spec = GrowthSpec::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--baseline" => {
+                let val = iter
+                    .next()
+                    .with_context(|| "`--baseline` requires a path")?;
+                baseline = Some(PathBuf::from(val));
+            }
+            "--mft-file" => {
+                let val = iter
+                    .next()
+                    .with_context(|| "`--mft-file` requires a path")?;
+                current = Some(PathBuf::from(val));
+            }
+            "--drive" | "-d" => {
+                let val = iter
+                    .next()
+                    .with_context(|| "`--drive` requires a volume (e.g. C or srv01\\C)")?;
+                volume = Some(
+                    VolumeId::parse(val.trim_end_matches([':', '\\']))
+                        .with_context(|| format!("invalid --drive value '{val}'"))?,
+                );
+            }
+            "--depth" => {
+                let val = iter.next().with_context(|| "`--depth` requires a number")?;
+                spec.depth = val
+                    .parse::<u32>()
+                    .with_context(|| format!("invalid --depth value '{val}'"))?;
+            }
+            "--top" => {
+                let val = iter.next().with_context(|| "`--top` requires a number")?;
+                spec.top = val
+                    .parse::<u16>()
+                    .with_context(|| format!("invalid --top value '{val}'"))?;
+            }
+            "--rank" => {
+                let val = iter
+                    .next()
+                    .with_context(|| "`--rank` requires abs or rel")?;
+                spec.rank = GrowthRank::parse(val)
+                    .with_context(|| format!("invalid --rank value '{val}' (abs or rel)"))?;
+            }
+            "--format" | "-f" => {
+                iter.next()
+                    .with_context(|| "`--format` requires a format")?;
+            }
+            other => anyhow::bail!("unknown argument '{other}'; see `uffs --growth --help`"),
+        }
+    }
+
+    let (Some(baseline_path), Some(current_path)) = (baseline, current) else {
+        anyhow::bail!(
+            "missing a capture: pass the older one as `--baseline <PATH>` and the newer one \
+             as `--mft-file <PATH>`"
+        );
+    };
+    Ok(GrowthArgs {
+        baseline: baseline_path,
+        current: current_path,
+        volume,
+        spec,
+    })
+}
+
+/// Render `result` in the shape the daemon's diff path returns, so the
+/// `--agg` renderers print it unchanged.
+fn growth_to_wire(spec: &GrowthSpec, result: &GrowthResult) -> AggregateResultWire {
+    AggregateResultWire {
+        label: spec.label.clone(),
+        kind: "growth".to_owned(),
+        field: Some(spec.rank.as_str().to_owned()),
+        value: None,
+        stats: None,
+        buckets: result.rows.iter().map(growth_bucket).collect(),
+        other_count: None,
+        total_groups: Some(result.total_groups),
+        next_cursor: None,
+        exact: Some(true),
+        values_complete: Some(result.rows.len() == result.total_groups),
+        pivot: None,
+    }
+}
+
+/// One folder's bucket: the newer tree's metrics, the comparison in
+/// [`BucketWire::growth`].
+fn growth_bucket(row: &GrowthRow) -> BucketWire {
+    BucketWire {
+        key: row.path.clone(),
+        count: row.after.descendants,
+        total_bytes: row.after.treesize,
+        total_allocated: Some(row.after.tree_allocated),
+        growth: Some(GrowthWire {
+            before_bytes: row.before.treesize,
+            before_allocated: row.before.tree_allocated,
+            before_descendants: row.before.descendants,
+            delta_bytes: row.delta_bytes(),
+            delta_allocated: row.delta_allocated(),
+            delta_descendants: row.delta_descendants(),
+            growth_pct: row.growth_pct(),
+            matched: row.matched.as_str().to_owned(),
+        }),
+        ..BucketWire::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uffs_core::aggregate::GrowthRank;
+    use uffs_mft::platform::{DriveLetter, VolumeId};
+
+    use super::parse_growth_args;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|item| (*item).to_owned()).collect()
+    }
+
+    #[test]
+    fn parses_both_captures_and_the_comparison() {
+        let parsed = parse_growth_args(&args(&[
+            "--baseline",
+            "C_old.bin",
+            "--mft-file",
+            "C_new.bin",
+            "-d",
+            "srv01\\C:",
+            "--depth",
+            "2",
+            "--top",
+            "5",
+            "--rank",
+            "rel",
+            "--format",
+            "json",
+        ]))
+        .expect("parse");
+        assert_eq!(parsed.baseline.to_str(), Some("C_old.bin"));
+        assert_eq!(parsed.current.to_str(), Some("C_new.bin"));
+        assert_eq!(
+            parsed.volume,
+            Some(VolumeId::hosted(
+                "srv01".parse().expect("host"),
+                DriveLetter::C
+            ))
+        );
+        assert_eq!(parsed.spec.depth, 2);
+        assert_eq!(parsed.spec.top, 5);
+        assert_eq!(parsed.spec.rank, GrowthRank::Relative);
+    }
+
+    #[test]
+    fn both_captures_are_required() {
+        let err = parse_growth_args(&args(&["--mft-file", "C_new.bin"]))
+            .expect_err("must require a baseline");
+        assert!(err.to_string().contains("--baseline"), "{err}");
+    }
+
+    #[test]
+    fn an_unknown_rank_is_an_error() {
+        let err = parse_growth_args(&args(&[
+            "--baseline",
+            "a.bin",
+            "--mft-file",
+            "b.bin",
+            "--rank",
+            "sideways",
+        ]))
+        .expect_err("must reject the rank");
+        assert!(err.to_string().contains("sideways"), "{err}");
+    }
+}
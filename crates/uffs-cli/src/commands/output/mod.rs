@@ -15,6 +15,7 @@ use std::io::{BufWriter, Write};
 use anyhow::{Context as _, Result};
 use parity::{write_legacy_drive_footer, write_parity};
 use serde_json::Value;
+use uffs_time::TimeZone;
 
 // ── Value extraction helpers ───────────────────────────────────────────
 
@@ -51,9 +52,8 @@ fn vb(row: &Value, key: &str) -> bool {
 
 /// Context for legacy baseline-compatible footer formatting.
 pub(crate) struct CppFooterContext<'a> {
-    /// Drive letters to include in the footer (e.g.
-    /// `[DriveLetter::C, DriveLetter::D]`).
-    pub output_targets: &'a [uffs_mft::platform::DriveLetter],
+    /// Volumes to include in the footer (e.g. `C` and `srv01\D`).
+    pub output_targets: &'a [uffs_mft::platform::VolumeId],
     /// Original search pattern string.
     pub pattern: &'a str,
     /// Total result row count for fast-scan heuristic.
@@ -80,8 +80,8 @@ pub fn write_native_results(
     header: bool,
     pos: &str,
     neg: &str,
-    tz_offset: Option<i32>,
-    output_targets: &[uffs_mft::platform::DriveLetter],
+    time_zone: Option<TimeZone>,
+    output_targets: &[uffs_mft::platform::VolumeId],
     _elapsed: Duration,
     pattern: &str,
 ) -> Result<()> {
@@ -100,10 +100,7 @@ pub fn write_native_results(
     let parity_ctx = ParityContext {
         pos,
         neg,
-        tz_offset_secs: tz_offset.map_or_else(
-            || *LOCAL_TZ_OFFSET_SECS,
-            |hours| hours.saturating_mul(3_600_i32),
-        ),
+        time_zone: time_zone.unwrap_or_else(|| TimeZone::fixed(*LOCAL_TZ_OFFSET_SECS)),
     };
 
     if is_console {
@@ -244,8 +241,8 @@ struct ParityContext<'a> {
     pos: &'a str,
     /// Negative boolean string (e.g., `"0"`).
     neg: &'a str,
-    /// Timezone offset in seconds from UTC.
-    tz_offset_secs: i32,
+    /// Zone timestamps are shown in.
+    time_zone: TimeZone,
 }
 
 /// Dispatch to the appropriate formatter.
@@ -391,6 +388,10 @@ static COL_TABLE: &[ColDef] = &[
         "Malformed Path",
     ),
     ("name_hex", &["namehex"], "Name (hex)"),
+    // Cached content digest (opt-in; filled from the daemon's hash cache).
+    ("hash", &["content_hash", "digest"], "Hash"),
+    // The `--patterns-from` entry that hit (filled by the daemon).
+    ("matched_pattern", &["ioc", "matched"], "Matched Pattern"),
 ];
 
 /// Column order used when `--columns all` is specified (matches
@@ -485,9 +486,17 @@ fn resolve_columns(columns: &str) -> Vec<&'static str> {
 fn is_quoted_column(canonical: &str) -> bool {
     matches!(
         canonical,
-        // `name_hex` is a string column (quoted in uffs_format::writer); the
-        // malformed bools render as raw 0/1 like the attribute-flag columns.
-        "path" | "name" | "path_only" | "type" | "extension" | "name_hex"
+        // `name_hex`, `hash` and `matched_pattern` are string columns
+        // (quoted in uffs_format::writer); the malformed bools render as raw
+        // 0/1 like the attribute-flag columns.
+        "path"
+            | "name"
+            | "path_only"
+            | "type"
+            | "extension"
+            | "name_hex"
+            | "hash"
+            | "matched_pattern"
     )
 }
 
@@ -504,11 +513,11 @@ fn is_quoted_column(canonical: &str) -> bool {
 /// fallback output byte-identical to the daemon's pre-formatted blob
 /// on every column set.
 ///
-/// Datetime formatting honours the timezone offset carried on
-/// `parity_ctx` (matching `uffs_format::append_datetime_native`),
-/// not the host's local offset.  The caller drives the offset via
-/// `--tz-offset`; when absent, `dispatch::write_rows` feeds the
-/// host-local value into `parity_ctx.tz_offset_secs`.
+/// Datetime formatting honours the zone carried on `parity_ctx`
+/// (matching `uffs_format::append_datetime_native`), not the host's
+/// local offset.  The caller drives the zone via `--tz` / `--tz-offset`;
+/// when absent, [`write_native_results`] feeds the host-local offset into
+/// `parity_ctx.time_zone`.
 fn write_columnar<W: Write>(
     writer: &mut W,
     rows: &[Value],
@@ -548,7 +557,7 @@ fn write_columnar<W: Write>(
             if idx > 0 {
                 write!(writer, "{separator}")?;
             }
-            let value = extract_field(row, field, parity_ctx.tz_offset_secs);
+            let value = extract_field(row, field, parity_ctx.time_zone);
             if !quote.is_empty() && is_quoted_column(field) {
                 write!(writer, "{quote}{value}{quote}")?;
             } else {
@@ -564,12 +573,12 @@ fn write_columnar<W: Write>(
 ///
 /// Handles flag decomposition, path derivation, and computed columns.
 ///
-/// `tz_offset_secs` drives the `Created` / `Modified` / `Accessed`
+/// `time_zone` drives the `Created` / `Modified` / `Accessed`
 /// column formatting — matches
 /// `uffs_format::append_datetime_native` so
 /// `RequestHandler::try_pack_csv_blob`'s pre-formatted bytes stay
 /// byte-identical with this fallback path.
-fn extract_field(row: &Value, field: &str, tz_offset_secs: i32) -> String {
+fn extract_field(row: &Value, field: &str, time_zone: TimeZone) -> String {
     let flags = vu32(row, "flags");
     match field {
         "name" => vs(row, "name"),
@@ -586,9 +595,9 @@ fn extract_field(row: &Value, field: &str, tz_offset_secs: i32) -> String {
         }
         "size" => vu(row, "size").to_string(),
         "size_on_disk" => vu(row, "allocated").to_string(),
-        "created" => format_filetime_with_tz(vi(row, "created"), tz_offset_secs),
-        "modified" => format_filetime_with_tz(vi(row, "modified"), tz_offset_secs),
-        "accessed" => format_filetime_with_tz(vi(row, "accessed"), tz_offset_secs),
+        "created" => format_filetime_with_tz(vi(row, "created"), time_zone),
+        "modified" => format_filetime_with_tz(vi(row, "modified"), time_zone),
+        "accessed" => format_filetime_with_tz(vi(row, "accessed"), time_zone),
         "extension" => extract_extension(&vs(row, "name")),
         "drive" => vs(row, "drive"),
         "type" => if vb(row, "is_directory") {
@@ -644,6 +653,8 @@ fn extract_field(row: &Value, field: &str, tz_offset_secs: i32) -> String {
         "malformed" => if vb(row, "malformed") { "1" } else { "0" }.to_owned(),
         "malformed_path" => if vb(row, "malformed_path") { "1" } else { "0" }.to_owned(),
         "name_hex" => vs(row, "name_hex"),
+        "hash" => vs(row, "hash"),
+        "matched_pattern" => vs(row, "matched_pattern"),
         _ => String::new(),
     }
 }
@@ -721,8 +732,8 @@ mod parity_flags {
 static LOCAL_TZ_OFFSET_SECS: std::sync::LazyLock<i32> =
     std::sync::LazyLock::new(uffs_client::format::local_utc_offset_secs);
 
-/// Format a raw FILETIME into `YYYY-MM-DD HH:MM:SS` with the supplied
-/// timezone offset.
+/// Format a raw FILETIME into `YYYY-MM-DD HH:MM:SS` in the supplied
+/// zone.
 ///
 /// Mirrors `uffs_format::append_datetime_native` exactly, including
 /// the `"0000-00-00 00:00:00"` sentinel for a zero FILETIME (which
@@ -730,8 +741,8 @@ static LOCAL_TZ_OFFSET_SECS: std::sync::LazyLock<i32> =
 /// calls this via `extract_field` with the config-supplied TZ so the
 /// CLI's fallback path and the daemon's `try_pack_csv_blob`
 /// pre-formatted blob produce byte-identical output.
-fn format_filetime_with_tz(filetime: i64, tz_offset_secs: i32) -> String {
-    let local_ft = uffs_time::filetime_with_tz_bias(filetime, tz_offset_secs);
+fn format_filetime_with_tz(filetime: i64, time_zone: TimeZone) -> String {
+    let local_ft = time_zone.to_local(filetime);
     match uffs_time::filetime_to_calendar(local_ft) {
         Some(uffs_time::CalendarParts {
             year,
@@ -751,13 +762,13 @@ fn format_filetime_with_tz(filetime: i64, tz_offset_secs: i32) -> String {
 /// Used by [`write_table`] for human-facing display where the user
 /// expects their own wall-clock time regardless of how the CSV
 /// fallback path was configured.  CSV / parity / custom formatters
-/// take their offset from the config (`--tz-offset`) via
+/// take their zone from the config (`--tz` / `--tz-offset`) via
 /// [`format_filetime_with_tz`] instead.
 ///
 /// `pub(crate)` so the forensic `--deleted` command reuses the exact same
 /// wall-clock rendering as the search table.
 pub(crate) fn format_filetime_local(filetime: i64) -> String {
-    format_filetime_with_tz(filetime, *LOCAL_TZ_OFFSET_SECS)
+    format_filetime_with_tz(filetime, TimeZone::fixed(*LOCAL_TZ_OFFSET_SECS))
 }
 
 #[cfg(test)]
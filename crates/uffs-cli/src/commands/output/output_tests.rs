@@ -97,8 +97,8 @@ fn write_native_csv_uses_columns_without_legacy_footer() -> TestResult {
         "0",
         None,
         &[
-            uffs_mft::platform::DriveLetter::C,
-            uffs_mft::platform::DriveLetter::D,
+            uffs_mft::platform::DriveLetter::C.into(),
+            uffs_mft::platform::DriveLetter::D.into(),
         ],
         Duration::from_secs(2),
         "*.txt",
@@ -128,8 +128,8 @@ fn write_native_custom_file_appends_legacy_drive_footer() -> TestResult {
         "0",
         None,
         &[
-            uffs_mft::platform::DriveLetter::C,
-            uffs_mft::platform::DriveLetter::D,
+            uffs_mft::platform::DriveLetter::C.into(),
+            uffs_mft::platform::DriveLetter::D.into(),
         ],
         Duration::from_secs(2),
         "*.txt",
@@ -169,8 +169,8 @@ fn write_native_json_file_has_no_footer() -> TestResult {
         "0",
         None,
         &[
-            uffs_mft::platform::DriveLetter::C,
-            uffs_mft::platform::DriveLetter::D,
+            uffs_mft::platform::DriveLetter::C.into(),
+            uffs_mft::platform::DriveLetter::D.into(),
         ],
         Duration::from_secs(2),
         "*.txt",
@@ -204,7 +204,7 @@ fn legacy_footer_includes_fast_scan_message_for_full_scan_pattern() -> TestResul
         "1",
         "0",
         None,
-        &[uffs_mft::platform::DriveLetter::G],
+        &[uffs_mft::platform::DriveLetter::G.into()],
         Duration::from_millis(999),
         "*",
     )?;
@@ -233,7 +233,7 @@ fn legacy_footer_includes_fast_scan_for_transformed_pattern() -> TestResult {
         "1",
         "0",
         None,
-        &[uffs_mft::platform::DriveLetter::G],
+        &[uffs_mft::platform::DriveLetter::G.into()],
         Duration::from_millis(999),
         ">G:.*",
     )?;
@@ -262,7 +262,7 @@ fn legacy_footer_omits_fast_scan_for_real_regex_pattern() -> TestResult {
         "1",
         "0",
         None,
-        &[uffs_mft::platform::DriveLetter::G],
+        &[uffs_mft::platform::DriveLetter::G.into()],
         Duration::from_millis(999),
         r">G:.*\.(jpg|png)",
     )?;
@@ -291,7 +291,7 @@ fn legacy_footer_omits_fast_scan_message_when_many_results() -> TestResult {
         "1",
         "0",
         None,
-        &[uffs_mft::platform::DriveLetter::G],
+        &[uffs_mft::platform::DriveLetter::G.into()],
         Duration::from_secs(2),
         ">G:.*",
     )?;
@@ -433,7 +433,7 @@ fn parity_row(
     modified_filetime: i64,
 ) -> uffs_client::protocol::response::SearchRow {
     uffs_client::protocol::response::SearchRow {
-        drive: uffs_mft::platform::DriveLetter::C,
+        drive: uffs_mft::platform::DriveLetter::C.into(),
         path: path.to_owned(),
         name: name.to_owned(),
         size: 4321,
@@ -450,6 +450,8 @@ fn parity_row(
         malformed_path: false,
         name_hex: None,
         file_reference: 0,
+        content_hash: None,
+        matched_pattern: None,
     }
 }
 
@@ -476,7 +478,7 @@ fn assert_parity_bytes_match(
     let parity_ctx = super::ParityContext {
         pos: "1",
         neg: "0",
-        tz_offset_secs,
+        time_zone: uffs_time::TimeZone::fixed(tz_offset_secs),
     };
     let mut cli_bytes = Vec::new();
     super::parity::write_parity(&mut cli_bytes, &json_rows, ",", "\"", &parity_ctx)
@@ -547,7 +549,7 @@ fn parity_byte_parity_directory_rewrite() {
         0x0010,                      // DIRECTORY
         133_485_408_000_000_000_i64, // 2024-01-01 UTC
     );
-    row.drive = uffs_mft::platform::DriveLetter::D;
+    row.drive = uffs_mft::platform::DriveLetter::D.into();
     row.size = 0;
     row.allocated = 0;
     row.treesize = 65_536;
@@ -618,7 +620,7 @@ fn assert_columnar_bytes_match(
     let parity_ctx = super::ParityContext {
         pos: "1",
         neg: "0",
-        tz_offset_secs: tz_offset_hours.saturating_mul(3_600_i32),
+        time_zone: uffs_time::TimeZone::fixed(tz_offset_hours.saturating_mul(3_600_i32)),
     };
     let mut cli_bytes = Vec::new();
     super::write_columnar(
@@ -160,7 +160,7 @@ fn write_parity_row(
     // 5-7: Created, Modified, Accessed
     for key in &["created", "modified", "accessed"] {
         buf.push_str(sep);
-        append_datetime_tz(buf, vi(row, key), ctx.tz_offset_secs);
+        append_datetime_tz(buf, vi(row, key), ctx.time_zone);
     }
 
     // 8: Descendants
@@ -184,10 +184,11 @@ fn push_u64(buf: &mut String, value: u64) {
     let _ok = write!(buf, "{value}");
 }
 
-/// Format a raw FILETIME with timezone bias directly into `buf`.
+/// Format a raw FILETIME in `time_zone` directly into `buf`.
 ///
-/// Mirrors C++ `RtlTimeToTimeFields` — applies TZ bias in FILETIME ticks,
-/// then decomposes.  No intermediate Unix conversion.
+/// Mirrors C++ `RtlTimeToTimeFields` — applies the zone's offset at that
+/// instant in FILETIME ticks, then decomposes.  No intermediate Unix
+/// conversion.
 ///
 /// When `filetime` is `0` (the "unset / null" sentinel that
 /// `filetime_to_calendar` returns `None` for), emits
@@ -196,9 +197,9 @@ fn push_u64(buf: &mut String, value: u64) {
 /// daemon's `uffs_format::write_rows` for the `ParityCompat +
 /// Created/Modified/Accessed` combination that
 /// `RequestHandler::try_pack_csv_blob` now pre-formats (v0.5.64+).
-fn append_datetime_tz(buf: &mut String, filetime: i64, tz_offset_secs: i32) {
+fn append_datetime_tz(buf: &mut String, filetime: i64, time_zone: uffs_time::TimeZone) {
     use core::fmt::Write as _;
-    let local_ft = uffs_time::filetime_with_tz_bias(filetime, tz_offset_secs);
+    let local_ft = time_zone.to_local(filetime);
     if let Some(uffs_time::CalendarParts {
         year,
         month,
@@ -236,11 +237,15 @@ pub(super) fn write_legacy_drive_footer<W: Write + ?Sized>(
     ctx: &CppFooterContext<'_>,
 ) -> Result<()> {
     // `uffs-format` deliberately does not depend on `uffs-mft` (issue
-    // #216); convert at the crate boundary so the format crate keeps
-    // its narrow char-only API.
-    let chars: Vec<char> = ctx.output_targets.iter().map(|dl| dl.as_char()).collect();
+    // #216); render the drive labels at the crate boundary so the format
+    // crate keeps its narrow string-only API.
+    let labels: Vec<String> = ctx
+        .output_targets
+        .iter()
+        .map(|volume| volume.drive_label())
+        .collect();
     let fmt_ctx = uffs_format::DriveFooterContext {
-        output_targets: &chars,
+        output_targets: &labels,
         pattern: ctx.pattern,
         row_count: ctx.row_count,
     };
@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `uffs --query save|list|delete` — manage the daemon's saved-query
+//! library (`queries.toml`), and the `uffs @name` expansion every search
+//! goes through.
+//!
+//! The library lives daemon-side so the CLI, the MCP bridge, and any other
+//! client share one set of vetted queries; this module only turns argv
+//! into [`SaveQueryParams`] and back into a table.  Running a query is an
+//! ordinary search: [`expand_saved_query`] swaps `@name [--param k=v]…`
+//! for the stored arguments before anything else looks at the argv.
+
+use anyhow::{Context as _, Result};
+use uffs_client::connect_sync::UffsClientSync;
+use uffs_client::protocol::response::{QueryError, QueryInvocation, SaveQueryParams, SavedQuery};
+
+/// Run `uffs --query <ACTION> …`.
+///
+/// # Errors
+///
+/// Returns an error on bad arguments, when the daemon cannot be reached,
+/// or when it rejects the request.
+pub(crate) fn run_query(args: &[String]) -> Result<()> {
+    let (action, rest) = match args.split_first() {
+        None => ("--help", &[][..]),
+        Some((first, rest)) => (first.as_str(), rest),
+    };
+    match action {
+        "save" => save(rest),
+        "list" | "ls" => list(rest),
+        "delete" | "rm" => delete(rest),
+        "--help" | "-h" | "help" => {
+            crate::args::print_query_help();
+            Ok(())
+        }
+        other => anyhow::bail!(
+            "unknown --query action `{other}` (expected save, list, or delete; see `uffs \
+             --query --help`)"
+        ),
+    }
+}
+
+/// If `args` starts with `@name`, fetch the library and return the
+/// expanded search arguments; `Ok(None)` for an ordinary search.
+///
+/// # Errors
+///
+/// Returns an error for a malformed invocation, an unknown name, a
+/// placeholder with neither a `--param` value nor a default, or a failed
+/// `list_queries` RPC.
+pub(crate) fn expand_saved_query(
+    client: &mut UffsClientSync,
+    args: &[String],
+) -> Result<Option<Vec<String>>> {
+    let Some(invocation) = QueryInvocation::parse(args)? else {
+        return Ok(None);
+    };
+    let library = client
+        .list_queries()
+        .with_context(|| "list_queries RPC failed")?;
+    let query = library
+        .queries
+        .get(&invocation.name)
+        .ok_or_else(|| QueryError::UnknownQuery {
+            name: invocation.name.clone(),
+        })?;
+    Ok(Some(invocation.resolve(query)?))
+}
+
+/// `save <NAME> [--description TEXT] [--default K=V]… [--replace] -- <ARGS>…`
+fn parse_save_args(args: &[String]) -> Result<SaveQueryParams> {
+    let (name, rest) = args
+        .split_first()
+        .with_context(|| "`--query save` requires a name")?;
+    let mut description = None;
+    // Later `--default`s for the same key win, as in the stored map.
+    let mut defaults: Vec<(String, String)> = Vec::new();
+    let mut replace = false;
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--" => break,
+            "--description" => {
+                let text = iter
+                    .next()
+                    .with_context(|| "`--description` requires text")?;
+                description = Some(text.clone());
+            }
+            "--default" => {
+                let pair = iter
+                    .next()
+                    .with_context(|| "`--default` requires key=value")?;
+                let (key, value) = pair
+                    .split_once('=')
+                    .filter(|(key, _)| !key.is_empty())
+                    .with_context(|| format!("`--default` expects key=value, got `{pair}`"))?;
+                defaults.push((key.to_owned(), value.to_owned()));
+            }
+            "--replace" => replace = true,
+            other => anyhow::bail!(
+                "unexpected `{other}` before `--` (usage: uffs --query save <NAME> [OPTIONS] -- \
+                 <SEARCH ARGS>…)"
+            ),
+        }
+    }
+    let search_args: Vec<String> = iter.cloned().collect();
+    anyhow::ensure!(
+        !search_args.is_empty(),
+        "`--query save` needs the search after `--` (e.g. -- '*.vmdk' --older {{age}})"
+    );
+    Ok(SaveQueryParams {
+        name: name.clone(),
+        query: SavedQuery {
+            description,
+            args: search_args,
+            defaults: defaults.into_iter().collect(),
+        },
+        replace,
+    })
+}
+
+/// `uffs --query save …`.
+#[expect(clippy::print_stdout, reason = "CLI user-facing output")]
+fn save(args: &[String]) -> Result<()> {
+    let params = parse_save_args(args)?;
+    let mut client = connect()?;
+    let response = client
+        .save_query(&params)
+        .with_context(|| "save_query RPC failed")?;
+    let verb = if response.replaced {
+        "Replaced"
+    } else {
+        "Saved"
+    };
+    println!(
+        "{verb} query @{} — run it with `uffs @{}`",
+        response.name, response.name
+    );
+    Ok(())
+}
+
+/// `uffs --query list [--json]`.
+#[expect(clippy::print_stdout, reason = "CLI user-facing output")]
+fn list(args: &[String]) -> Result<()> {
+    let json = args.iter().any(|arg| arg == "--json");
+    let mut client = connect()?;
+    let library = client
+        .list_queries()
+        .with_context(|| "list_queries RPC failed")?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&library)?);
+        return Ok(());
+    }
+    if library.queries.is_empty() {
+        println!("No saved queries. Create one with `uffs --query save <NAME> -- <SEARCH ARGS>…`.");
+        return Ok(());
+    }
+    for (name, query) in &library.queries {
+        println!("@{name}");
+        if let Some(description) = &query.description {
+            println!("    {description}");
+        }
+        println!("    uffs {}", query.args.join(" "));
+        for placeholder in query.placeholders() {
+            match query.defaults.get(&placeholder) {
+                Some(default) => println!("    --param {placeholder}=<value>  (default {default})"),
+                None => println!("    --param {placeholder}=<value>  (required)"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `uffs --query delete <NAME>`.
+#[expect(clippy::print_stdout, reason = "CLI user-facing output")]
+fn delete(args: &[String]) -> Result<()> {
+    let name = args
+        .first()
+        .with_context(|| "`--query delete` requires a name")?;
+    let mut client = connect()?;
+    let response = client
+        .delete_query(name)
+        .with_context(|| "delete_query RPC failed")?;
+    if response.deleted {
+        println!("Deleted query @{}", response.name);
+    } else {
+        println!("No saved query named @{}", response.name);
+    }
+    Ok(())
+}
+
+/// Connect to (auto-starting if needed) the daemon.
+fn connect() -> Result<UffsClientSync> {
+    UffsClientSync::connect().with_context(|| "Failed to connect to UFFS daemon")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_save_args;
+
+    fn argv(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| (*item).to_owned()).collect()
+    }
+
+    #[test]
+    fn save_args_split_options_from_search_args() {
+        let parsed = parse_save_args(&argv(&[
+            "stale-vms",
+            "--description",
+            "old VM images",
+            "--default",
+            "age=90d",
+            "--replace",
+            "--",
+            "*.vmdk",
+            "--older",
+            "{age}",
+        ]))
+        .expect("parses");
+        assert_eq!(parsed.name, "stale-vms");
+        assert!(parsed.replace);
+        assert_eq!(parsed.query.description.as_deref(), Some("old VM images"));
+        assert_eq!(parsed.query.args, argv(&["*.vmdk", "--older", "{age}"]));
+        assert_eq!(
+            parsed.query.defaults.get("age").map(String::as_str),
+            Some("90d")
+        );
+    }
+
+    #[test]
+    fn save_args_require_name_and_search() {
+        parse_save_args(&[]).expect_err("no name");
+        parse_save_args(&argv(&["name"])).expect_err("no search");
+        parse_save_args(&argv(&["name", "--"])).expect_err("empty search");
+        parse_save_args(&argv(&["name", "*.vmdk"])).expect_err("search before `--`");
+        parse_save_args(&argv(&["name", "--default", "novalue", "--", "*"]))
+            .expect_err("default without `=`");
+    }
+}
@@ -74,37 +74,37 @@ pub fn write_rows(rows: &[serde_json::Value], args: &[String]) -> Result<()> {
     let header = arg_val(args, "--header").is_none_or(|val| val != "false" && val != "0");
     let pos = arg_val(args, "--pos").unwrap_or("1");
     let neg = arg_val(args, "--neg").unwrap_or("0");
-    let tz_offset = arg_val(args, "--tz-offset").and_then(|val| val.parse::<i32>().ok());
+    // `--tz` wins over `--tz-offset`, as on the daemon.
+    let time_zone = arg_val(args, "--tz")
+        .and_then(uffs_time::TimeZone::parse)
+        .or_else(|| {
+            arg_val(args, "--tz-offset")
+                .and_then(|val| val.parse::<i32>().ok())
+                .map(|hours| uffs_time::TimeZone::fixed(hours.saturating_mul(3_600)))
+        });
 
     // Extract drive targets for footer.
     let drive = arg_val(args, "--drive").or_else(|| arg_val(args, "-d"));
     let drives_str = arg_val(args, "--drives");
     let mft_str = arg_val(args, "--mft-file");
-    let mut targets: Vec<uffs_mft::platform::DriveLetter> = Vec::new();
+    let mut targets: Vec<uffs_mft::platform::VolumeId> = Vec::new();
     if let Some(drive_val) = drive {
         if let Some(letter) = drive_val
             .chars()
             .next()
             .and_then(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
         {
-            targets.push(letter);
+            targets.push(letter.into());
         }
     } else if let Some(drives_val) = drives_str {
-        for part in drives_val.split(',') {
-            let trimmed = part.trim();
-            let stripped = trimmed.strip_suffix(':').unwrap_or(trimmed);
-            if let Some(letter) = stripped
-                .chars()
-                .next()
-                .and_then(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
-            {
-                targets.push(letter);
-            }
-        }
+        // Host-qualified entries (`srv01\C`) keep their host.
+        targets.extend(drives_val.split(',').filter_map(|part| {
+            uffs_mft::platform::VolumeId::parse(part.trim().trim_end_matches(['\\', '/'])).ok()
+        }));
     } else if let Some(mft_val) = mft_str {
         for part in mft_val.split(',') {
             if let Some(letter) = extract_drive_letter(part.trim()) {
-                targets.push(letter);
+                targets.push(letter.into());
             }
         }
     }
@@ -121,7 +121,7 @@ pub fn write_rows(rows: &[serde_json::Value], args: &[String]) -> Result<()> {
         header,
         pos,
         neg,
-        tz_offset,
+        time_zone,
         &targets,
         core::time::Duration::ZERO,
         pattern,
@@ -28,12 +28,16 @@ pub(crate) enum Command {
     Agg,
     /// `--deleted --mft-file <path>`.
     Deleted,
+    /// `--growth --baseline <old> --mft-file <new>`.
+    Growth,
     /// `--snapshot --drive C --out <file>`.
     Snapshot,
     /// `--daemon <action>`.
     Daemon,
     /// `--mcp <action>`.
     Mcp,
+    /// `--query <save|list|delete>` — the daemon's saved-query library.
+    Query,
     /// `--update [action]`.
     Update,
     /// `--uninstall [flags]`.
@@ -51,9 +55,11 @@ impl Command {
             "--stats" => Self::Stats,
             "--agg" | "--aggregate" => Self::Agg,
             "--deleted" => Self::Deleted,
+            "--growth" => Self::Growth,
             "--snapshot" => Self::Snapshot,
             "--daemon" => Self::Daemon,
             "--mcp" => Self::Mcp,
+            "--query" => Self::Query,
             // `--upgrade` is a HIDDEN alias for `--update` (winget/apt muscle
             // memory) — deliberately absent from `COMMAND_TOKENS`, so it never
             // appears in help or "did you mean" suggestions.
@@ -74,9 +80,11 @@ const COMMAND_TOKENS: &[&str] = &[
     "--agg",
     "--aggregate",
     "--deleted",
+    "--growth",
     "--snapshot",
     "--daemon",
     "--mcp",
+    "--query",
     "--update",
     "--uninstall",
     "--status",
@@ -117,9 +125,11 @@ pub(crate) fn dispatch_command(command: Command, args: &[String]) -> Result<()>
         Command::Stats => crate::run_stats(args),
         Command::Agg => crate::run_aggregate(args),
         Command::Deleted => commands::deleted::run_deleted(args),
+        Command::Growth => commands::growth::run_growth(args),
         Command::Snapshot => commands::snapshot::run_snapshot(args),
         Command::Daemon => crate::run_daemon(args),
         Command::Mcp => commands::mcp_mgmt::mcp_from_args(args),
+        Command::Query => commands::queries::run_query(args),
         Command::Update => commands::update::run_update(args),
         Command::Uninstall => commands::uninstall::run_uninstall(args),
         Command::Status => {
@@ -158,11 +168,13 @@ mod tests {
         assert_eq!(Command::from_token("--uninstall"), Some(Command::Uninstall));
         assert_eq!(Command::from_token("--daemon"), Some(Command::Daemon));
         assert_eq!(Command::from_token("--mcp"), Some(Command::Mcp));
+        assert_eq!(Command::from_token("--query"), Some(Command::Query));
         assert_eq!(Command::from_token("--stats"), Some(Command::Stats));
         assert_eq!(Command::from_token("--agg"), Some(Command::Agg));
         assert_eq!(Command::from_token("--aggregate"), Some(Command::Agg));
         assert_eq!(Command::from_token("--status"), Some(Command::Status));
         assert_eq!(Command::from_token("--search"), Some(Command::Search));
+        assert_eq!(Command::from_token("--growth"), Some(Command::Growth));
     }
 
     #[test]
@@ -198,6 +210,7 @@ mod tests {
             "--limit",
             "--format",
             "--tz-offset",
+            "--tz",
             "--out",
             "--no-output",
             "--profile",
@@ -113,11 +113,11 @@ mod client_profile;
 use client_profile::{ClientProfile, print_client_profile};
 
 /// Forward raw search args to the daemon via `search_cli` RPC.
-pub(crate) fn run_search(args: &[String]) -> Result<()> {
+pub(crate) fn run_search(cli_args: &[String]) -> Result<()> {
     // No pattern, or an explicit help request as the first token
     // (`uffs --search --help`) → the search-first top-level help.
     if matches!(
-        args.first().map(String::as_str),
+        cli_args.first().map(String::as_str),
         None | Some("--help" | "-h")
     ) {
         args::print_help();
@@ -130,11 +130,11 @@ pub(crate) fn run_search(args: &[String]) -> Result<()> {
     // it to return a bare unknown-flag error. The CLI suggests over ITS own
     // command set; flag validation stays in `uffs_client::from_cli_args`, so
     // the daemon never learns CLI commands (design: cli-grammar.md §6).
-    if let Some(first) = args.first()
+    if let Some(first) = cli_args.first()
         && first.starts_with("--")
         && dispatch::Command::from_token(first).is_none()
         && let Err(uffs_client::protocol::cli_args::Error::UnknownFlag { flag }) =
-            uffs_client::protocol::SearchParams::from_cli_args(args)
+            uffs_client::protocol::SearchParams::from_cli_args(cli_args)
         && let Some(command) = dispatch::suggest_command(&flag)
     {
         anyhow::bail!(
@@ -145,7 +145,7 @@ pub(crate) fn run_search(args: &[String]) -> Result<()> {
 
     // Extract daemon-spawn args (--data-dir, --mft-file, --no-cache)
     // from the raw args so we can auto-start the daemon if needed.
-    let spawn_args = commands::search::args::extract_spawn_args(args);
+    let spawn_args = commands::search::args::extract_spawn_args(cli_args);
 
     let t_connect = std::time::Instant::now();
     let mut client = uffs_client::connect_sync::UffsClientSync::connect_with_args(&spawn_args)
@@ -160,6 +160,11 @@ pub(crate) fn run_search(args: &[String]) -> Result<()> {
         .with_context(|| "Daemon did not become ready in time")?;
     let ready_ms = t_ready.elapsed().as_millis();
 
+    // `uffs @name …` runs a saved query: swap in its stored arguments (plus
+    // any extras) so every flag check below sees the search that runs.
+    let expanded = commands::queries::expand_saved_query(&mut client, cli_args)?;
+    let args = expanded.as_deref().unwrap_or(cli_args);
+
     let t_search = std::time::Instant::now();
     // Resolve relative --out paths to absolute using the CLI's cwd, since the
     // daemon process runs in a different working directory.
@@ -119,6 +119,46 @@ impl UffsClient {
         self.notification_rx.try_recv().ok()
     }
 
+    /// Wait for the next daemon notification.
+    ///
+    /// Hands back one already queued by an earlier request first;
+    /// otherwise reads the connection until a notification arrives.
+    /// Meant for listeners (e.g. standing-query subscribers) that have no
+    /// request in flight, so any stray response line is skipped.  There
+    /// is no built-in timeout — wrap the call in `tokio::time::timeout`
+    /// to bound the wait.
+    ///
+    /// # Errors
+    ///
+    /// [`crate::error::ClientError::ConnectionClosed`] when the daemon
+    /// closes the connection, [`crate::error::ClientError::Io`] on a read
+    /// failure.
+    pub async fn recv_notification(
+        &mut self,
+    ) -> Result<crate::protocol::RpcNotification, crate::error::ClientError> {
+        if let Ok(notification) = self.notification_rx.try_recv() {
+            return Ok(notification);
+        }
+        loop {
+            let mut line = String::new();
+            let read = self
+                .reader
+                .read_line(&mut line)
+                .await
+                .map_err(|io_err| crate::error::ClientError::Io(io_err.to_string()))?;
+            if read == 0 {
+                return Err(crate::error::ClientError::ConnectionClosed);
+            }
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim())
+                && value.get("id").is_none()
+                && let Ok(notification) =
+                    serde_json::from_value::<crate::protocol::RpcNotification>(value)
+            {
+                return Ok(notification);
+            }
+        }
+    }
+
     /// Send a JSON-RPC request and read the response.
     ///
     /// D3.4.5: While waiting for the response, any incoming notifications
@@ -246,9 +286,16 @@ impl UffsClient {
         {
             let t_shmem = std::time::Instant::now();
             let shmem_path = std::path::Path::new(path);
-            let shmem_response = crate::shmem::read_search_results(shmem_path).map_err(|err| {
-                crate::error::ClientError::Protocol(format!("shmem read failed: {err}"))
-            })?;
+            let mut shmem_response =
+                crate::shmem::read_search_results(shmem_path).map_err(|err| {
+                    crate::error::ClientError::Protocol(format!("shmem read failed: {err}"))
+                })?;
+            // The shmem header has no room for the paging cursor; it
+            // only travels in the RPC response that announced the file.
+            shmem_response
+                .next_search_cursor
+                .clone_from(&response.next_search_cursor);
+            shmem_response.cursor_index_changed = response.cursor_index_changed;
             let shmem_read_ms = t_shmem.elapsed().as_millis();
             let row_count = shmem_response.payload.row_count_hint().unwrap_or(0);
             tracing::info!(
@@ -463,6 +510,97 @@ impl UffsClient {
             .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))
     }
 
+    /// Fetch the daemon's saved-query library (`queries.toml`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ClientError` on connection, protocol, or timeout failure.
+    pub async fn list_queries(
+        &mut self,
+    ) -> Result<crate::protocol::response::ListQueriesResponse, crate::error::ClientError> {
+        let result = self.send_request("list_queries", None).await?;
+        serde_json::from_value(result)
+            .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))
+    }
+
+    /// List the daemon-side diff baselines (`[snapshots]` and
+    /// `[history]`) retained per drive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ClientError` on connection, protocol, or timeout failure.
+    pub async fn list_baselines(
+        &mut self,
+    ) -> Result<crate::protocol::response::ListBaselinesResponse, crate::error::ClientError> {
+        let result = self.send_request("list_baselines", None).await?;
+        serde_json::from_value(result)
+            .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))
+    }
+
+    /// Register a standing query on this connection.
+    ///
+    /// Matches arrive as `daemon.query_match` notifications on this same
+    /// client — read them with [`Self::next_query_match`].  The
+    /// subscription lives until [`Self::unsubscribe_query`] or until the
+    /// connection closes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ClientError` on connection, protocol, or timeout failure,
+    /// and surfaces the daemon's own rejections (aggregations, snapshot
+    /// diffs, file output, too many subscriptions) as
+    /// `ClientError::Protocol`.
+    pub async fn subscribe_query(
+        &mut self,
+        params: &crate::protocol::response::SubscribeQueryParams,
+    ) -> Result<crate::protocol::response::SubscribeQueryResponse, crate::error::ClientError> {
+        let value = serde_json::to_value(params)
+            .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))?;
+        let result = self.send_request("subscribe_query", Some(value)).await?;
+        serde_json::from_value(result)
+            .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))
+    }
+
+    /// Drop a standing query registered by [`Self::subscribe_query`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ClientError` on connection, protocol, or timeout failure.
+    pub async fn unsubscribe_query(
+        &mut self,
+        subscription_id: u64,
+    ) -> Result<crate::protocol::response::UnsubscribeQueryResponse, crate::error::ClientError>
+    {
+        let value = serde_json::to_value(crate::protocol::response::UnsubscribeQueryParams {
+            subscription_id,
+        })
+        .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))?;
+        let result = self.send_request("unsubscribe_query", Some(value)).await?;
+        serde_json::from_value(result)
+            .map_err(|err| crate::error::ClientError::Protocol(err.to_string()))
+    }
+
+    /// Wait for the next standing-query match, skipping every other daemon
+    /// notification (lifecycle events, stats heartbeats).
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`Self::recv_notification`], plus
+    /// `ClientError::Protocol` for a malformed `daemon.query_match`
+    /// payload.
+    pub async fn next_query_match(
+        &mut self,
+    ) -> Result<crate::protocol::response::QueryMatch, crate::error::ClientError> {
+        loop {
+            let notification = self.recv_notification().await?;
+            if notification.method != crate::protocol::response::QUERY_MATCH_METHOD {
+                continue;
+            }
+            return serde_json::from_value(notification.params.unwrap_or_default())
+                .map_err(|err| crate::error::ClientError::Protocol(err.to_string()));
+        }
+    }
+
     /// Send a keepalive to reset the daemon's idle timer.
     ///
     /// # Errors
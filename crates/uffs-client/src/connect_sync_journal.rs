@@ -3,17 +3,20 @@
 
 //! USN-journal RPC helper for [`crate::connect_sync::UffsClientSync`].
 //!
-//! Split off [`crate::connect_sync`] so the journal RPC
-//! (`changed_since`) lives next to the
-//! [`crate::protocol::response::ChangedSinceResponse`] wire types it
-//! consumes — the
+//! Split off [`crate::connect_sync`] so the journal RPCs
+//! (`changed_since`, `tail_changes`) live next to the
+//! [`crate::protocol::response::ChangedSinceResponse`] /
+//! [`crate::protocol::response::TailChangesResponse`] wire types they
+//! consume — the
 //! same sibling-module pattern as [`crate::connect_sync_tiering`].
 //! Paired with the daemon-side handler in
 //! `crates/uffs-daemon/src/handler_journal.rs`.
 
 use crate::connect_sync::UffsClientSync;
 use crate::error::ClientError;
-use crate::protocol::response::{ChangedSinceParams, ChangedSinceResponse};
+use crate::protocol::response::{
+    ChangedSinceParams, ChangedSinceResponse, TailChangesParams, TailChangesResponse,
+};
 
 impl UffsClientSync {
     /// Ask the daemon which files changed on a drive since a USN
@@ -39,4 +42,25 @@ impl UffsClientSync {
         let result = self.send_request("changed_since", Some(payload))?;
         serde_json::from_value(result).map_err(|err| ClientError::Protocol(err.to_string()))
     }
+
+    /// Read a drive's durable change feed after a sequence cursor.
+    ///
+    /// See [`TailChangesResponse`] for the cursor contract: persist
+    /// `next_seq`, page on while `truncated == true`, and treat
+    /// `complete == false` as a gap pruned by retention.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError` on I/O, protocol, or timeout failure, and
+    /// surfaces the daemon's own errors (invalid params; change log
+    /// disabled) as `ClientError::Protocol`.
+    pub fn tail_changes(
+        &mut self,
+        params: &TailChangesParams,
+    ) -> Result<TailChangesResponse, ClientError> {
+        let payload =
+            serde_json::to_value(params).map_err(|err| ClientError::Protocol(err.to_string()))?;
+        let result = self.send_request("tail_changes", Some(payload))?;
+        serde_json::from_value(result).map_err(|err| ClientError::Protocol(err.to_string()))
+    }
 }
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Saved-query RPC helpers for [`crate::connect_sync::UffsClientSync`]:
+//! `save_query`, `list_queries`, and `delete_query`.
+//!
+//! Split off [`crate::connect_sync`] so the cluster lives next to the
+//! [`crate::protocol::response::SavedQuery`] wire types it consumes —
+//! the same sibling-module pattern as [`crate::connect_sync_journal`].
+//! Paired with the daemon-side handler in
+//! `crates/uffs-daemon/src/handler_queries.rs`.
+
+use crate::connect_sync::UffsClientSync;
+use crate::error::ClientError;
+use crate::protocol::response::{
+    DeleteQueryParams, DeleteQueryResponse, ListQueriesResponse, SaveQueryParams, SaveQueryResponse,
+};
+
+impl UffsClientSync {
+    /// Store a named query in the daemon's library.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError` on I/O, protocol, or timeout failure, and
+    /// surfaces the daemon's own rejections (bad name, name taken without
+    /// `replace`, arguments that do not parse) as `ClientError::Protocol`.
+    pub fn save_query(
+        &mut self,
+        params: &SaveQueryParams,
+    ) -> Result<SaveQueryResponse, ClientError> {
+        let payload =
+            serde_json::to_value(params).map_err(|err| ClientError::Protocol(err.to_string()))?;
+        let result = self.send_request("save_query", Some(payload))?;
+        serde_json::from_value(result).map_err(|err| ClientError::Protocol(err.to_string()))
+    }
+
+    /// Fetch the whole saved-query library.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError` on I/O, protocol, or timeout failure.
+    pub fn list_queries(&mut self) -> Result<ListQueriesResponse, ClientError> {
+        let result = self.send_request("list_queries", None)?;
+        serde_json::from_value(result).map_err(|err| ClientError::Protocol(err.to_string()))
+    }
+
+    /// Remove a named query (a no-op reported as `deleted == false` when
+    /// the name is unknown).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError` on I/O, protocol, or timeout failure.
+    pub fn delete_query(&mut self, name: &str) -> Result<DeleteQueryResponse, ClientError> {
+        let payload = serde_json::to_value(DeleteQueryParams {
+            name: name.to_owned(),
+        })
+        .map_err(|err| ClientError::Protocol(err.to_string()))?;
+        let result = self.send_request("delete_query", Some(payload))?;
+        serde_json::from_value(result).map_err(|err| ClientError::Protocol(err.to_string()))
+    }
+}
@@ -456,3 +456,48 @@ async fn await_ready_times_out_when_drive_load_progress_stalls() {
         ),
     }
 }
+
+// ── Standing queries ───────────────────────────────────────────────────
+
+/// `subscribe_query` returns the daemon-assigned id, and
+/// `next_query_match` skips unrelated notifications (stats heartbeats)
+/// — including one that raced ahead of the subscribe response — to
+/// hand back the first `daemon.query_match` payload.
+#[tokio::test]
+async fn next_query_match_skips_other_notifications() {
+    let canned = concat!(
+        r#"{"jsonrpc":"2.0","method":"daemon.stats","params":{"event":"stats_heartbeat","total_queries":1,"uptime_secs":1,"total_records":1,"connections":1}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","id":1,"result":{"subscription_id":4}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","method":"daemon.query_match","params":{"event":"query_match","subscription_id":4,"change":"created","row":{"drive":"C","path":"C:\\x.ps1","name":"x.ps1","size":1,"is_directory":false,"modified":0,"created":0,"accessed":0,"flags":0,"allocated":0,"descendants":0,"treesize":0}}}"#,
+        "\n",
+    )
+    .as_bytes();
+    let (mut client, writer) = client_with_canned_response(canned);
+
+    let params = crate::protocol::response::SubscribeQueryParams {
+        search: crate::protocol::SearchParams {
+            pattern: "*.ps1".to_owned(),
+            ..crate::protocol::SearchParams::default()
+        },
+        changes: Vec::new(),
+    };
+    let subscribed = client.subscribe_query(&params).await.expect("subscribe");
+    assert_eq!(subscribed.subscription_id, 4);
+    let sent = String::from_utf8(writer.take()).expect("utf-8 request");
+    assert!(sent.contains(r#""method":"subscribe_query""#), "{sent}");
+
+    let matched = client.next_query_match().await.expect("query match");
+    assert_eq!(matched.subscription_id, 4);
+    assert_eq!(
+        matched.change,
+        crate::protocol::response::QueryChangeKind::Created
+    );
+    assert_eq!(matched.row.name, "x.ps1");
+
+    assert!(matches!(
+        client.next_query_match().await,
+        Err(ClientError::ConnectionClosed)
+    ));
+}
@@ -366,6 +366,9 @@ pub(crate) fn is_aggregate_preset(spec: &str) -> bool {
             | "dups"
             | "media"
             | "cleanup"
+            | "name_years"
+            | "nameyears"
+            | "years"
     )
 }
 
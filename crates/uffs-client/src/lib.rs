@@ -145,6 +145,9 @@ pub(crate) mod connect_sync_journal;
 /// callers see no change.  Also hosts the env-override regression
 /// tests for `rpc_deadline`.
 pub(crate) mod connect_sync_platform;
+/// Saved-query RPC helpers (`save_query`, `list_queries`,
+/// `delete_query`) — split off `connect_sync` for the same reason.
+pub(crate) mod connect_sync_queries;
 /// Wire-protocol unit tests for [`connect_sync::UffsClientSync`].
 ///
 /// Exercises the JSON-RPC request/response path via in-memory
@@ -53,8 +53,8 @@ pub struct AggregateSpecWire {
     /// Sort direction for sample rows.  `true` = descending (largest first).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sample_desc: Option<bool>,
-    /// Duplicate verification mode: `"first_bytes"`, `"sha256"`, or
-    /// absent/`"none"`.
+    /// Duplicate verification mode: `"first_bytes"`, `"sha256"`,
+    /// `"xxh3"`, `"blake3"`, or absent/`"none"`.
     ///
     /// Only meaningful when `kind` is `"duplicates"`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -62,6 +62,14 @@ pub struct AggregateSpecWire {
     /// Byte count for `verify=first_bytes` mode (default: 4096).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub verify_bytes: Option<u32>,
+    /// Folder depth a `growth` spec compares at (`1` = the volume
+    /// root's children, the default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    /// Order a `growth` spec ranks folders in: `"absolute"` (bytes
+    /// gained, the default) or `"relative"` (percentage grown).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
     /// Drive letter scoping an `ancestor`/`drilldown` rollup (e.g. `"C"`).
     ///
     /// Required when `kind` is `"rollup"` with `field` = `"ancestor"` —
@@ -69,6 +77,17 @@ pub struct AggregateSpecWire {
     /// name the drive it belongs to.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub drive: Option<String>,
+    /// Offset from UTC that calendar buckets are cut in (`"UTC"`, `"+2"`,
+    /// `"-05:30"`), for this spec and its `sub` chain unless a level
+    /// names its own `tz=`.  Absent means UTC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tz: Option<String>,
+    /// Child aggregation evaluated per bucket of this one, in the same
+    /// scan pass. Both levels must be bucketed kinds (terms, histogram,
+    /// date histogram, range, rollup); each [`BucketWire`] then carries
+    /// the child's buckets in [`BucketWire::sub_buckets`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<Box<Self>>,
 }
 
 /// Wire format for an aggregate result.
@@ -116,6 +135,9 @@ pub struct AggregateResultWire {
     /// Absent for non-bucket results.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub values_complete: Option<bool>,
+    /// The cross-tab matrix (for `kind="pivot"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pivot: Option<PivotWire>,
 }
 
 /// Wire format for scalar statistics.
@@ -135,6 +157,19 @@ pub struct StatsWire {
     pub waste_bytes: u64,
     /// Waste percentage.
     pub waste_pct: f64,
+    /// Requested percentiles of the field (`metrics=p50+p99`), in
+    /// request order. Sketch estimates within 1 % of the true value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub percentiles: Vec<PercentileWire>,
+}
+
+/// Wire format for one estimated percentile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PercentileWire {
+    /// Percentile label: `p50`, `p90`, `p99.9`.
+    pub rank: String,
+    /// Estimated value at that percentile.
+    pub value: u64,
 }
 
 /// Wire format for a single bucket row.
@@ -158,6 +193,9 @@ pub struct BucketWire {
     /// Share of total bytes (percentage).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub share_bytes: Option<f64>,
+    /// Requested file-size percentiles of this bucket, in request order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub percentiles: Vec<PercentileWire>,
     /// Sample rows (top-hits) — representative records from this bucket.
     /// Empty when no `sample` was requested in the spec.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -166,10 +204,12 @@ pub struct BucketWire {
     /// Includes both the original query predicates and the bucket key.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub drilldown: Vec<DrilldownWire>,
-    /// Nested sub-aggregation bucket results (populated by nested rollups).
+    /// Nested sub-aggregation bucket results.
     ///
-    /// When a rollup spec has a `sub` aggregation, each top-level bucket
-    /// contains the sub-aggregation's buckets here.
+    /// When the spec has a `sub` aggregation (or a `parent>child` raw
+    /// chain), each bucket contains the child's buckets here, recursively.
+    /// Child shares are relative to this bucket; pagination pages the
+    /// top-level buckets and each page keeps whole subtrees.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub sub_buckets: Vec<Self>,
     /// Whether this duplicate group has been content-verified.
@@ -177,6 +217,81 @@ pub struct BucketWire {
     /// Only present for `kind="duplicates"` results with `verify != "none"`.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub verified: bool,
+    /// Baseline comparison for this folder.
+    ///
+    /// Only present for `kind="growth"` results, where `key` is the
+    /// folder path and `count` / `total_bytes` / `total_allocated` are its
+    /// current `descendants` / `treesize` / `tree_allocated`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub growth: Option<GrowthWire>,
+}
+
+/// Wire format for one folder's growth against a diff baseline.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GrowthWire {
+    /// `treesize` in the baseline.
+    pub before_bytes: u64,
+    /// `tree_allocated` in the baseline.
+    pub before_allocated: u64,
+    /// `descendants` in the baseline.
+    pub before_descendants: u64,
+    /// `treesize` change (negative when the folder shrank).
+    pub delta_bytes: i64,
+    /// `tree_allocated` change.
+    pub delta_allocated: i64,
+    /// `descendants` change.
+    pub delta_descendants: i64,
+    /// `treesize` change as a percentage of the baseline; absent when the
+    /// folder had no baseline size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub growth_pct: Option<f64>,
+    /// How the folder was paired with its baseline: `"file_ref"`,
+    /// `"path"`, `"added"` or `"removed"`.
+    pub matched: String,
+}
+
+/// Wire format for a pivot (cross-tab) result.
+///
+/// Rows and columns beyond their `top` are rolled up into a trailing
+/// `"(other)"` row / column, so the totals always cover every record.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PivotWire {
+    /// Field bucketed down the rows.
+    pub row_field: String,
+    /// Field bucketed across the columns.
+    pub col_field: String,
+    /// The metric every value reports (`"count"`, `"total_bytes"`,
+    /// `"p90"`, …).
+    pub metric: String,
+    /// Column labels, in display order.
+    pub columns: Vec<String>,
+    /// Rows, in display order.
+    pub rows: Vec<PivotRowWire>,
+    /// Metric of each column across all rows.
+    pub column_totals: Vec<f64>,
+    /// Metric over every record in the pivot.
+    pub grand_total: f64,
+    /// Distinct column buckets before the `top` cut.
+    pub total_cols: usize,
+    /// Weekday rows × hour-of-day columns, every slot present: render
+    /// as an activity heatmap.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub heatmap: bool,
+    /// Zone the calendar axes were cut in (`"UTC+02:00"`); absent
+    /// without a calendar axis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+}
+
+/// Wire format for one pivot row.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PivotRowWire {
+    /// Row label.
+    pub key: String,
+    /// Metric per column; `null` where no record falls in the cell.
+    pub values: Vec<Option<f64>>,
+    /// Metric across the whole row.
+    pub total: f64,
 }
 
 /// Wire format for a sample row (top-hit) within a bucket.
@@ -15,11 +15,19 @@
 // `cli_args_helpers.rs` for the full rationale.
 pub use super::cli_args_helpers::CliArgsError as Error;
 use super::cli_args_helpers::{
-    CliArgsError, drives_csv, extract_extensions_from_regex, flag_val, is_pure_ext_glob, non_empty,
-    parse_bool, parse_i32, parse_size, parse_u16, parse_u32, parse_u64,
+    CliArgsError, absolute_path, aggregation_wire_specs, check_time_zone, drives_csv,
+    expand_aggregate_sugar, extract_extensions_from_regex, flag_val, is_pure_ext_glob, non_empty,
+    parse_bool, parse_fuzzy_threshold, parse_hash_mode, parse_i32, parse_size, parse_u16,
+    parse_u32, parse_u64, parse_where, read_pattern_list, volumes_csv,
 };
 use super::{SearchFilterMode, SearchParams, SearchResponseMode};
 
+// The raw flag holder lives in a sibling file to keep this file under the
+// 800-line policy ceiling.
+#[path = "cli_args_raw.rs"]
+mod raw;
+use raw::RawCliArgs;
+
 // ── Public entry point ─────────────────────────────────────────────────
 
 impl SearchParams {
@@ -64,8 +72,14 @@ impl SearchParams {
                 "--case" => raw.case = true,
                 "--smart-case" => raw.smart_case = true,
                 "--word" => raw.word = true,
+                "--fuzzy" => raw.fuzzy = true,
+                "--fuzzy-threshold" => {
+                    let tv = flag_val(&arg, "--fuzzy-threshold", &mut iter)?;
+                    raw.fuzzy_threshold = Some(parse_fuzzy_threshold(&tv)?);
+                }
                 "--name-only" => raw.name_only = true,
                 "--sort-desc" => raw.sort_desc = true,
+                "--rank" => raw.rank = true,
                 "--parity-compat" => raw.parity_compat = true,
                 "--count" => raw.count = true,
                 "--rows" => raw.rows = true,
@@ -76,7 +90,7 @@ impl SearchParams {
                 }
                 "--drives" => {
                     let dv = flag_val(&arg, "--drives", &mut iter)?;
-                    raw.drives = Some(drives_csv(&dv)?);
+                    raw.drives = Some(volumes_csv(&dv)?);
                 }
                 "--mft-file" => {
                     let mv = flag_val(&arg, "--mft-file", &mut iter)?;
@@ -99,6 +113,19 @@ impl SearchParams {
                 // given baseline MFT capture vs the live index. Every other flag
                 // then filters/shapes that set like a normal search.
                 "--diff" => raw.diff_baseline = Some(flag_val(&arg, "--diff", &mut iter)?),
+                "--as-of" => raw.as_of = Some(flag_val(&arg, "--as-of", &mut iter)?),
+                "--hash-in" => raw.hash_in = Some(flag_val(&arg, "--hash-in", &mut iter)?),
+                "--field" => raw.fields.push(flag_val(&arg, "--field", &mut iter)?),
+                "--where" => raw
+                    .where_clauses
+                    .push(flag_val(&arg, "--where", &mut iter)?),
+                "--patterns-from" => {
+                    raw.patterns_from = Some(flag_val(&arg, "--patterns-from", &mut iter)?);
+                }
+                "--hash-mode" => {
+                    raw.hash_mode =
+                        Some(parse_hash_mode(&flag_val(&arg, "--hash-mode", &mut iter)?)?);
+                }
                 "--newer" => raw.newer = Some(flag_val(&arg, "--newer", &mut iter)?),
                 "--older" => raw.older = Some(flag_val(&arg, "--older", &mut iter)?),
                 "--newer-created" => {
@@ -156,6 +183,7 @@ impl SearchParams {
                 "--neg" => raw.neg = flag_val(&arg, "--neg", &mut iter)?,
                 "--query-mode" => raw.query_mode = flag_val(&arg, "--query-mode", &mut iter)?,
                 "--limit" | "-n" => raw.limit = parse_u32(flag, &flag_val(&arg, flag, &mut iter)?)?,
+                "--tz" => raw.tz = Some(flag_val(&arg, "--tz", &mut iter)?),
                 "--tz-offset" => {
                     raw.tz_offset = Some(parse_i32(
                         "--tz-offset",
@@ -291,121 +319,13 @@ impl SearchParams {
     }
 }
 
-// ── Raw CLI args holder ────────────────────────────────────────────────
-
-/// Transient container for raw CLI values before sugar expansion.
-///
-/// Fields mirror CLI flags 1:1; documented at the flag-parsing level.
-#[derive(Default)]
-#[expect(clippy::struct_excessive_bools, reason = "mirrors CLI flags")]
-#[cfg_attr(
-    not(test),
-    expect(
-        clippy::missing_docs_in_private_items,
-        reason = "fields mirror CLI flags — documented at the parser level"
-    )
-)]
-struct RawCliArgs {
-    pattern: Option<String>,
-    drive: Option<uffs_mft::platform::DriveLetter>,
-    drives: Option<Vec<uffs_mft::platform::DriveLetter>>,
-    files_only: bool,
-    dirs_only: bool,
-    hide_system: bool,
-    hide_ads: bool,
-    normalize_malformed: bool,
-    /// Diagnostic-only flag (see `--resolve-lcn-order` above).
-    resolve_lcn_order: bool,
-    /// WI-4.4: `Some(true)` from `--malformed`, `Some(false)` from
-    /// `--well-formed`, `None` if neither (no filter).
-    malformed: Option<bool>,
-    /// WI-4.4: `Some(true)` from `--malformed-path`.
-    malformed_path: Option<bool>,
-    /// Snapshot-diff baseline path from `--diff <BASELINE>`: turns the query
-    /// into a search over the deleted set of that baseline vs the live index.
-    diff_baseline: Option<String>,
-    profile: bool,
-    benchmark: bool,
-    no_cache: bool,
-    case: bool,
-    smart_case: bool,
-    word: bool,
-    name_only: bool,
-    sort_desc: bool,
-    parity_compat: bool,
-    count: bool,
-    rows: bool,
-    no_output: bool,
-    sort: Option<String>,
-    ext: Option<String>,
-    attr: Option<String>,
-    newer: Option<String>,
-    older: Option<String>,
-    newer_created: Option<String>,
-    older_created: Option<String>,
-    newer_accessed: Option<String>,
-    older_accessed: Option<String>,
-    exclude: Option<String>,
-    in_path: Option<String>,
-    path_excludes: Option<String>,
-    type_filter: Option<String>,
-    month: Option<String>,
-    between: Option<String>,
-    begins_with: Option<String>,
-    ends_with: Option<String>,
-    contains: Option<String>,
-    not_contains: Option<String>,
-    limit: u32,
-    min_size: Option<u64>,
-    max_size: Option<u64>,
-    exact_size: Option<u64>,
-    min_size_on_disk: Option<u64>,
-    max_size_on_disk: Option<u64>,
-    exact_size_on_disk: Option<u64>,
-    min_treesize: Option<u64>,
-    max_treesize: Option<u64>,
-    min_tree_allocated: Option<u64>,
-    max_tree_allocated: Option<u64>,
-    min_descendants: Option<u32>,
-    max_descendants: Option<u32>,
-    exact_descendants: Option<u32>,
-    min_name_length: Option<u16>,
-    max_name_length: Option<u16>,
-    min_path_length: Option<u16>,
-    max_path_length: Option<u16>,
-    min_bulkiness: Option<u64>,
-    max_bulkiness: Option<u64>,
-    format: String,
-    out: String,
-    columns: String,
-    sep: String,
-    quotes: String,
-    header: Option<bool>,
-    pos: String,
-    neg: String,
-    query_mode: String,
-    tz_offset: Option<i32>,
-    agg: Vec<String>,
-    facet: Vec<String>,
-    stats: Vec<String>,
-    histogram: Vec<String>,
-    agg_cursor: Option<String>,
-    agg_page_size: Option<u16>,
-    mft_file: Vec<String>,
-    data_dir: Option<String>,
-}
-
 impl RawCliArgs {
     /// Convert raw CLI values into a fully-populated [`SearchParams`],
     /// performing all sugar expansion.
     #[expect(clippy::too_many_lines, reason = "sugar expansion for 60+ flags")]
-    #[expect(
-        clippy::cognitive_complexity,
-        reason = "three composable sugar rewrites (drive-prefix, ext-glob, regex-ext) plus filter normalisation; splitting further would fragment the parse pipeline"
-    )]
     fn into_search_params(mut self) -> Result<SearchParams, CliArgsError> {
         // ── Pattern sugar: --begins-with / --ends-with / --contains ─
-        let raw_pattern = self
+        let mut raw_pattern = self
             .pattern
             .take()
             .or_else(|| self.begins_with.take().map(|prefix| format!("{prefix}*")))
@@ -421,6 +341,12 @@ impl RawCliArgs {
             (None, None) => None,
         };
 
+        // ── Fuzzy prefix: fuzzy:<needle> → --fuzzy + <needle> ─────
+        if raw_pattern.starts_with("fuzzy:") {
+            self.fuzzy = true;
+            raw_pattern.replace_range(.."fuzzy:".len(), "");
+        }
+
         // ── Scope prefixes: path:/dir:/file:/<letter>: ─────────────
         //
         // `pattern` is `mut` so the ext-pattern sugar block below can
@@ -563,34 +489,23 @@ impl RawCliArgs {
         }
 
         // ── Aggregate sugar ────────────────────────────────────────
-        let mut agg_specs = self.agg;
-        if self.count && !agg_specs.iter().any(|spec| spec == "count") {
-            agg_specs.push("count".to_owned());
-        }
-        for facet in &self.facet {
-            if let Some((field, top)) = facet.split_once(':') {
-                agg_specs.push(format!("terms:{field},top={top}"));
-            } else {
-                agg_specs.push(format!("terms:{facet},top=20"));
-            }
-        }
-        for stat in &self.stats {
-            agg_specs.push(format!("stats:{stat}"));
-        }
-        for hist in &self.histogram {
-            if let Some((field, interval)) = hist.split_once(':') {
-                agg_specs.push(format!("hist:{field},interval={interval}"));
-            } else {
-                agg_specs.push(format!("hist:{hist}"));
-            }
-        }
+        let agg_specs = expand_aggregate_sugar(
+            self.agg,
+            self.count,
+            &self.facet,
+            &self.stats,
+            &self.histogram,
+        );
         let force_rows = self.rows;
         let agg_only = !agg_specs.is_empty() && !force_rows;
 
         // ── Drives ─────────────────────────────────────────────────
-        let drives: Vec<uffs_mft::platform::DriveLetter> = self
+        let drives: Vec<uffs_mft::platform::VolumeId> = self
             .drives
-            .or_else(|| self.drive.map(|ch| vec![ch]))
+            .or_else(|| {
+                self.drive
+                    .map(|ch| vec![uffs_mft::platform::VolumeId::local(ch)])
+            })
             .unwrap_or_default();
 
         // ── Filter mode ────────────────────────────────────────────
@@ -637,37 +552,11 @@ impl RawCliArgs {
         };
 
         // ── Output config ──────────────────────────────────────────
-        let output_file = if self.out.is_empty() || self.out == "console" {
-            None
-        } else {
-            let path = std::path::Path::new(&self.out);
-            let abs = if path.is_absolute() {
-                path.to_path_buf()
-            } else {
-                std::env::current_dir().unwrap_or_default().join(path)
-            };
-            Some(abs.to_string_lossy().into_owned())
-        };
+        let output_file =
+            (!self.out.is_empty() && self.out != "console").then(|| absolute_path(&self.out));
 
         // ── Aggregation wire specs ─────────────────────────────────
-        let aggregations = agg_specs
-            .iter()
-            .map(|spec| {
-                let is_preset = crate::format::is_aggregate_preset(spec);
-                super::AggregateSpecWire {
-                    kind: if spec == "count" {
-                        "count".to_owned()
-                    } else if is_preset {
-                        "preset".to_owned()
-                    } else {
-                        "raw".to_owned()
-                    },
-                    label: (!is_preset && spec != "count").then(|| spec.clone()),
-                    preset: is_preset.then(|| spec.clone()),
-                    ..super::AggregateSpecWire::default()
-                }
-            })
-            .collect();
+        let aggregations = aggregation_wire_specs(&agg_specs);
 
         // ── Assemble ───────────────────────────────────────────────
         let mut params = SearchParams {
@@ -676,12 +565,16 @@ impl RawCliArgs {
             case_sensitive,
             whole_word: false,
             match_path,
+            fuzzy: self.fuzzy || self.fuzzy_threshold.is_some(),
+            fuzzy_threshold: self.fuzzy_threshold,
             // Sort
             sort: self.sort,
             sorts,
             sort_desc: self.sort_desc,
+            rank: self.rank,
             // Limit
             limit,
+            search_cursor: None,
             // Filter mode
             filter,
             filter_mode,
@@ -746,18 +639,15 @@ impl RawCliArgs {
             output_file,
             output_separator: non_empty(self.sep),
             output_quote: non_empty(self.quotes),
-            // Forward `None` when the user did not pass `--header` so
-            // the daemon's `uffs_format::OutputConfig` default
-            // (`header = true`) wins.  Passing `Some(false)` here
-            // unconditionally (as we used to) stripped the header
-            // line from every default CSV query — see the comment
-            // on the `"--header" =>` arm above.
+            // `None` without `--header`, so the daemon's default
+            // (`header = true`) wins — see the `"--header" =>` arm above.
             output_header: self.header,
             output_pos: non_empty(self.pos),
             output_neg: non_empty(self.neg),
             output_columns: non_empty(columns),
             output_parity_compat: self.parity_compat.then_some(true),
             output_tz_offset_hours: self.tz_offset,
+            time_zone: check_time_zone(self.tz)?,
             // Forward the CLI's `--format` value so the daemon can
             // gate its `try_pack_csv_blob` pre-format fast path on it.
             // Phase 3: `"csv"` and `"custom"` both take the fast path;
@@ -773,7 +663,7 @@ impl RawCliArgs {
             // in `uffs_daemon::handler::RequestHandler::is_csv_blob_eligible`.
             output_format: Some(non_empty(self.format.clone()).unwrap_or_else(|| "csv".to_owned())),
             // Drives to echo into the legacy drive footer when
-            // `--format custom`.  Same letters as `drives` above for
+            // `--format custom`.  Same volumes as `drives` above for
             // the main CLI path (which populates `drives` from
             // `--drive` / `--drives`); the thin-client passthrough in
             // `commands::search::dispatch` handles `--mft-file`
@@ -784,12 +674,22 @@ impl RawCliArgs {
             // parses the remaining flags as a normal search); a plain search
             // leaves it unset.
             diff_baseline: self.diff_baseline.clone(),
+            as_of: self.as_of.clone(),
+            // The daemon reads the list, so it needs a cwd-independent path.
+            hash_in: self.hash_in.as_deref().map(absolute_path),
+            hash_mode: self.hash_mode,
+            patterns: read_pattern_list(self.patterns_from.as_deref())?,
+            computed_fields: self.fields,
         };
         // A diff with no explicit pattern lists every deleted file.
         if params.diff_baseline.is_some() && params.pattern.is_empty() {
             "*".clone_into(&mut params.pattern);
         }
         params.populate_canonical_fields();
+        // `--where` clauses join the predicates the legacy flags produced.
+        for clause in &self.where_clauses {
+            params.predicates.push(parse_where(clause)?);
+        }
         Ok(params)
     }
 }
@@ -12,7 +12,7 @@
 
 use core::num::ParseIntError;
 
-use uffs_mft::platform::{DriveLetter, DriveLetterError};
+use uffs_mft::platform::{DriveLetter, DriveLetterError, VolumeId, VolumeIdError};
 
 use crate::format::ParseSizeError;
 // `parse_size` is re-exported so the importer can glob-import every
@@ -89,6 +89,16 @@ pub enum CliArgsError {
         #[source]
         source: DriveLetterError,
     },
+    /// `volumes_csv` saw a host-qualified segment (`srv01\\C`) that
+    /// [`VolumeId::parse`] rejected — a bad host label or volume letter.
+    #[error("Bad volume: '{input}' ({source})")]
+    BadVolume {
+        /// The offending segment (original, untrimmed).
+        input: String,
+        /// The underlying [`VolumeId::parse`] failure.
+        #[source]
+        source: VolumeIdError,
+    },
     /// [`crate::format::parse_size`] rejected one of the
     /// `--{min,max,exact}-size*` / `--{min,max}-treesize` /
     /// `--{min,max}-tree-allocated` operands.  The underlying
@@ -122,6 +132,41 @@ pub enum CliArgsError {
     /// file name, not the path.
     #[error("--name-only cannot be used with path patterns containing '\\' or '/'")]
     NameOnlyWithPathPattern,
+    /// `--hash-mode` was neither `match` nor `known_good`.
+    #[error("Bad --hash-mode: '{value}' (expected match or known_good)")]
+    BadHashMode {
+        /// The offending value as supplied by the operator.
+        value: String,
+    },
+    /// The `--patterns-from` file could not be read.
+    #[error("Cannot read --patterns-from '{path}': {reason}")]
+    PatternsFile {
+        /// The file as supplied by the operator.
+        path: String,
+        /// The I/O failure.
+        reason: String,
+    },
+    /// `--fuzzy-threshold` was not a number in `(0, 1]`.
+    #[error("Bad --fuzzy-threshold: '{value}' (expected a number in (0, 1])")]
+    BadFuzzyThreshold {
+        /// The offending value as supplied by the operator.
+        value: String,
+    },
+    /// `--tz` was neither an IANA zone nor an offset from UTC.
+    #[error(
+        "Bad --tz: '{value}' (expected a zone such as Europe/Berlin or an offset such as +02:00)"
+    )]
+    BadTimeZone {
+        /// The offending value as supplied by the operator.
+        value: String,
+    },
+    /// `--where` was not `FIELD OP VALUE` with a comparison operator and
+    /// an integer or size value.
+    #[error("Bad --where: '{clause}' (expected FIELD OP VALUE, e.g. 'slack > 1MB')")]
+    BadWhere {
+        /// The offending clause as supplied by the operator.
+        clause: String,
+    },
 }
 
 /// Returns `Some(val)` if `val` is non-empty, otherwise `None`.
@@ -177,6 +222,27 @@ pub(super) fn drives_csv(input: &str) -> Result<Vec<DriveLetter>, CliArgsError>
         .collect()
 }
 
+/// Parse a comma-separated `--drives` list.  Segments are bare letters
+/// (as for [`drives_csv`]) or host-qualified volumes (`srv01\\C`,
+/// `srv01/C:`) naming one machine's capture.
+pub(super) fn volumes_csv(input: &str) -> Result<Vec<VolumeId>, CliArgsError> {
+    let mut volumes = Vec::new();
+    for part in input.split(',') {
+        let trimmed = part.trim();
+        if trimmed.contains(['\\', '/']) {
+            volumes.push(
+                VolumeId::parse(trimmed).map_err(|source| CliArgsError::BadVolume {
+                    input: part.to_owned(),
+                    source,
+                })?,
+            );
+        } else {
+            volumes.extend(drives_csv(part)?.into_iter().map(VolumeId::local));
+        }
+    }
+    Ok(volumes)
+}
+
 /// Parse string to `u16`.
 pub(super) fn parse_u16(flag: &str, text: &str) -> Result<u16, CliArgsError> {
     text.parse().map_err(|source| CliArgsError::BadInt {
@@ -218,6 +284,171 @@ pub(super) fn parse_bool(flag: &str, text: &str) -> Result<bool, CliArgsError> {
     }
 }
 
+/// Parse a `--fuzzy-threshold` operand: a similarity in `(0, 1]`.
+pub(super) fn parse_fuzzy_threshold(text: &str) -> Result<f32, CliArgsError> {
+    text.parse::<f32>()
+        .ok()
+        .filter(|val| *val > 0.0 && *val <= 1.0)
+        .ok_or_else(|| CliArgsError::BadFuzzyThreshold {
+            value: text.to_owned(),
+        })
+}
+
+/// Parse a `--hash-mode` operand.
+pub(super) fn parse_hash_mode(text: &str) -> Result<super::HashListMode, CliArgsError> {
+    match text {
+        "match" => Ok(super::HashListMode::Match),
+        "known_good" | "known-good" | "exclude" => Ok(super::HashListMode::KnownGood),
+        _ => Err(CliArgsError::BadHashMode {
+            value: text.to_owned(),
+        }),
+    }
+}
+
+/// `--tz`: the zone in its canonical spelling (`Europe/Berlin`,
+/// `UTC+02:00`), so a typo fails here instead of at the daemon.
+pub(super) fn check_time_zone(value: Option<String>) -> Result<Option<String>, CliArgsError> {
+    value
+        .map(|text| {
+            uffs_time::TimeZone::parse(&text)
+                .map(|zone| zone.to_string())
+                .ok_or(CliArgsError::BadTimeZone { value: text })
+        })
+        .transpose()
+}
+
+/// `path` made absolute against the current directory, for paths the
+/// daemon opens on the client's behalf (`--out`, `--hash-in`).
+pub(super) fn absolute_path(path: &str) -> String {
+    let as_path = std::path::Path::new(path);
+    let abs = if as_path.is_absolute() {
+        as_path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(as_path)
+    };
+    abs.to_string_lossy().into_owned()
+}
+
+/// The entries of a `--patterns-from` file: one per line, with blank
+/// lines and `#` comments dropped.  No file, no entries.
+pub(super) fn read_pattern_list(path: Option<&str>) -> Result<Vec<String>, CliArgsError> {
+    let Some(list_path) = path else {
+        return Ok(Vec::new());
+    };
+    let text = std::fs::read_to_string(list_path).map_err(|err| CliArgsError::PatternsFile {
+        path: list_path.to_owned(),
+        reason: err.to_string(),
+    })?;
+    Ok(text
+        .lines()
+        .map(|line| line.trim().trim_start_matches('\u{feff}'))
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect())
+}
+
+/// A `--where 'FIELD OP VALUE'` clause as a predicate: `OP` is one of
+/// `= == != < <= > >=`, `VALUE` an integer or a size (`1MB`).
+/// Non-negative values go out as `U64` so built-in numeric fields take
+/// them too; computed fields accept either.
+pub(super) fn parse_where(clause: &str) -> Result<super::SearchPredicate, CliArgsError> {
+    use super::{SearchPredicateOp as Op, SearchPredicateValue as Value};
+    let bad = || CliArgsError::BadWhere {
+        clause: clause.to_owned(),
+    };
+    let op_at = clause.find(['<', '>', '=', '!']).ok_or_else(bad)?;
+    let (raw_field, rest) = clause.split_at(op_at);
+    let (op, raw_value) = [
+        ("<=", Op::Lte),
+        (">=", Op::Gte),
+        ("!=", Op::Ne),
+        ("==", Op::Eq),
+        ("<", Op::Lt),
+        (">", Op::Gt),
+        ("=", Op::Eq),
+    ]
+    .into_iter()
+    .find_map(|(token, op)| rest.strip_prefix(token).map(|value| (op, value.trim())))
+    .ok_or_else(bad)?;
+    let field = raw_field.trim();
+    if field.is_empty() || raw_value.is_empty() {
+        return Err(bad());
+    }
+    let value = raw_value
+        .strip_prefix('-')
+        .map_or_else(
+            || parse_size(raw_value).ok().map(Value::U64),
+            |magnitude| {
+                parse_size(magnitude)
+                    .ok()
+                    .and_then(|abs| i64::try_from(abs).ok())
+                    .map(|abs| Value::I64(-abs))
+            },
+        )
+        .ok_or_else(bad)?;
+    Ok(super::SearchPredicate {
+        field: field.to_owned(),
+        op,
+        value,
+    })
+}
+
+/// Expand the aggregate sugar flags into `--agg` spec strings:
+/// `--count` → `count`, `--facet F[:N]` → `terms:F,top=N` (default 20),
+/// `--stats F` → `stats:F`, `--histogram F[:I]` → `hist:F,interval=I`.
+pub(super) fn expand_aggregate_sugar(
+    mut agg_specs: Vec<String>,
+    count: bool,
+    facets: &[String],
+    stats: &[String],
+    histograms: &[String],
+) -> Vec<String> {
+    if count && !agg_specs.iter().any(|spec| spec == "count") {
+        agg_specs.push("count".to_owned());
+    }
+    for facet in facets {
+        if let Some((field, top)) = facet.split_once(':') {
+            agg_specs.push(format!("terms:{field},top={top}"));
+        } else {
+            agg_specs.push(format!("terms:{facet},top=20"));
+        }
+    }
+    for stat in stats {
+        agg_specs.push(format!("stats:{stat}"));
+    }
+    for hist in histograms {
+        if let Some((field, interval)) = hist.split_once(':') {
+            agg_specs.push(format!("hist:{field},interval={interval}"));
+        } else {
+            agg_specs.push(format!("hist:{hist}"));
+        }
+    }
+    agg_specs
+}
+
+/// Map expanded `--agg` spec strings onto their wire form: `count`, a
+/// named preset, or a raw spec carried in `label`.
+pub(super) fn aggregation_wire_specs(agg_specs: &[String]) -> Vec<super::AggregateSpecWire> {
+    agg_specs
+        .iter()
+        .map(|spec| {
+            let is_preset = crate::format::is_aggregate_preset(spec);
+            super::AggregateSpecWire {
+                kind: if spec == "count" {
+                    "count".to_owned()
+                } else if is_preset {
+                    "preset".to_owned()
+                } else {
+                    "raw".to_owned()
+                },
+                label: (!is_preset && spec != "count").then(|| spec.clone()),
+                preset: is_preset.then(|| spec.clone()),
+                ..super::AggregateSpecWire::default()
+            }
+        })
+        .collect()
+}
+
 /// Return `true` when `s` is exactly `*.<alnum+underscore>+` — a pure
 /// extension glob that can be safely promoted to an `ExtensionIndex` lookup.
 ///
@@ -315,272 +546,8 @@ pub(super) fn extract_extensions_from_regex(pattern: &str) -> Option<Vec<String>
 // means (including the previously-broken `C:`, `C:\`, and
 // `C:\path\…` forms).
 
+// Tests live in a sibling file to keep this file under the
+// 800-line policy ceiling.
 #[cfg(test)]
-mod cli_args_error_tests {
-    //! Phase 5d regression tests for [`CliArgsError`] and the typed
-    //! return types of every helper in this module.
-    //!
-    //! Each test locks one variant of [`CliArgsError`] at the
-    //! byte-identical Display string the pre-Phase-5d
-    //! `Result<_, String>` produced, so operator-facing CLI error
-    //! output is unchanged through the migration.  Variants that
-    //! chain an underlying error (`BadInt`, `BadDriveParse`,
-    //! `BadSize`) additionally walk [`core::error::Error::source`]
-    //! to assert the typed chain is intact — the real improvement
-    //! over the previous flattened `String`.
-
-    use core::error::Error as _;
-
-    use super::{
-        CliArgsError, drives_csv, parse_bool, parse_i32, parse_size, parse_u16, parse_u32,
-        parse_u64, take_next,
-    };
-    use crate::format::ParseSizeError;
-
-    #[test]
-    fn take_next_missing_value_locks_display() {
-        let mut empty = core::iter::empty::<String>();
-        let err = take_next("--drive", &mut empty).expect_err("missing value must error");
-        assert_eq!(err, CliArgsError::MissingValue {
-            flag: "--drive".to_owned(),
-        },);
-        assert_eq!(err.to_string(), "Missing value for --drive");
-        assert!(err.source().is_none(), "MissingValue has no chained source");
-    }
-
-    #[test]
-    fn parse_u16_bad_int_locks_display_and_chains_source() {
-        let err =
-            parse_u16("--agg-page-size", "not-a-number").expect_err("non-numeric input must error");
-        let CliArgsError::BadInt { flag, source } = &err else {
-            panic!("expected BadInt variant, got {err:?}");
-        };
-        assert_eq!(flag, "--agg-page-size");
-        // Display matches the pre-Phase-5d `"Bad {flag}: {err}"` shape.
-        assert_eq!(err.to_string(), format!("Bad --agg-page-size: {source}"));
-        // The typed source chain is the migration's actual improvement
-        // over the flattened `String`.
-        let chained = err.source().expect("BadInt exposes its ParseIntError");
-        assert_eq!(chained.to_string(), source.to_string());
-    }
-
-    #[test]
-    fn parse_u32_bad_int_locks_display() {
-        let err = parse_u32("--max-descendants", "abc").expect_err("must error");
-        assert!(matches!(&err, CliArgsError::BadInt { flag, .. } if flag == "--max-descendants"));
-        assert!(err.to_string().starts_with("Bad --max-descendants: "));
-    }
-
-    #[test]
-    fn parse_u64_bad_int_locks_display() {
-        let err = parse_u64("--min-bulkiness", "xyz").expect_err("must error");
-        assert!(matches!(&err, CliArgsError::BadInt { flag, .. } if flag == "--min-bulkiness"));
-        assert!(err.to_string().starts_with("Bad --min-bulkiness: "));
-    }
-
-    #[test]
-    fn parse_i32_bad_int_locks_display() {
-        let err = parse_i32("--tz-offset", "qux").expect_err("must error");
-        assert!(matches!(&err, CliArgsError::BadInt { flag, .. } if flag == "--tz-offset"));
-        assert!(err.to_string().starts_with("Bad --tz-offset: "));
-    }
-
-    #[test]
-    fn parse_bool_locks_display() {
-        let err = parse_bool("--header", "maybe").expect_err("must error");
-        assert_eq!(err, CliArgsError::BadBool {
-            flag: "--header".to_owned(),
-            value: "maybe".to_owned(),
-        },);
-        assert_eq!(err.to_string(), "Bad bool for --header: 'maybe'");
-    }
-
-    #[test]
-    fn parse_bool_accepts_canonical_forms() {
-        assert_eq!(parse_bool("--header", "true"), Ok(true));
-        assert_eq!(parse_bool("--header", "1"), Ok(true));
-        assert_eq!(parse_bool("--header", "yes"), Ok(true));
-        assert_eq!(parse_bool("--header", "false"), Ok(false));
-        assert_eq!(parse_bool("--header", "0"), Ok(false));
-        assert_eq!(parse_bool("--header", "no"), Ok(false));
-    }
-
-    /// Empty segment in the CSV (e.g. `,C`) takes the `EmptyDrive`
-    /// branch.  Locks the Display at `"empty drive"`.
-    #[test]
-    fn drives_csv_empty_segment_locks_display() {
-        let err = drives_csv(",C").expect_err("empty leading segment must error");
-        assert_eq!(err, CliArgsError::EmptyDrive);
-        assert_eq!(err.to_string(), "empty drive");
-    }
-
-    /// Multi-character segment walks the `BadDrive` branch (it's not
-    /// a single ASCII letter).  Display echoes the original part.
-    #[test]
-    fn drives_csv_multi_char_segment_locks_display() {
-        let err = drives_csv("CD").expect_err("multi-char segment must error");
-        assert_eq!(err, CliArgsError::BadDrive {
-            input: "CD".to_owned(),
-        },);
-        assert_eq!(err.to_string(), "Bad drive: 'CD'");
-    }
-
-    /// Non-alphabetic segment walks `BadDrive` (digits, punctuation).
-    #[test]
-    fn drives_csv_non_alpha_segment_locks_display() {
-        let err = drives_csv("1").expect_err("digit segment must error");
-        assert_eq!(err, CliArgsError::BadDrive {
-            input: "1".to_owned(),
-        },);
-        assert_eq!(err.to_string(), "Bad drive: '1'");
-    }
-
-    /// Happy-path lock — `drives_csv` must accept comma-separated
-    /// letters with optional `:` suffix and surrounding whitespace.
-    #[test]
-    fn drives_csv_accepts_canonical_forms() {
-        let parsed = drives_csv("C,D:,E").expect("canonical CSV must parse");
-        assert_eq!(parsed.len(), 3);
-    }
-
-    /// `?`-propagation via `#[from]` lifts a `ParseSizeError` into
-    /// `CliArgsError::BadSize` without losing the chain.  This is the
-    /// path `from_cli_args` takes for `--min-size` / etc.
-    #[test]
-    fn parse_size_propagates_via_from_into_bad_size() {
-        // Direct construction of the error path the `?` operator
-        // would synthesise in `from_cli_args`.
-        let inner = parse_size("abc").expect_err("must error");
-        let outer: CliArgsError = inner.clone().into();
-        assert_eq!(outer, CliArgsError::BadSize {
-            source: inner.clone(),
-        },);
-        // Display delegates to the inner `ParseSizeError` ("invalid
-        // size: abc"), matching the pre-Phase-5d byte sequence which
-        // came directly from `parse_size`'s String return.
-        assert_eq!(outer.to_string(), inner.to_string());
-        // Chained source is the typed inner.
-        let chained = outer.source().expect("BadSize exposes its ParseSizeError");
-        let chained_size: &ParseSizeError = chained
-            .downcast_ref()
-            .expect("source must downcast to ParseSizeError");
-        assert_eq!(chained_size, &inner);
-    }
-
-    #[test]
-    fn unknown_flag_display_locked() {
-        let err = CliArgsError::UnknownFlag {
-            flag: "--nope".to_owned(),
-        };
-        assert_eq!(err.to_string(), "Unknown flag: '--nope'");
-    }
-
-    #[test]
-    fn unexpected_argument_display_locked() {
-        let err = CliArgsError::UnexpectedArgument {
-            arg: "extra".to_owned(),
-        };
-        assert_eq!(err.to_string(), "Unexpected argument: 'extra'");
-    }
-
-    #[test]
-    fn name_only_with_path_pattern_display_locked() {
-        let err = CliArgsError::NameOnlyWithPathPattern;
-        assert_eq!(
-            err.to_string(),
-            "--name-only cannot be used with path patterns containing '\\' or '/'",
-        );
-    }
-
-    /// End-to-end: feeding `from_cli_args` an unknown flag must
-    /// surface as the typed `UnknownFlag` variant.  This is the
-    /// boundary where `cli_args.rs` raises the inline error.
-    #[test]
-    fn from_cli_args_surfaces_unknown_flag() {
-        use crate::protocol::SearchParams;
-        let args = vec!["--bogus-flag".to_owned()];
-        let err = SearchParams::from_cli_args(&args).expect_err("unknown flag must error");
-        assert_eq!(err, CliArgsError::UnknownFlag {
-            flag: "--bogus-flag".to_owned(),
-        },);
-    }
-
-    /// `--diff <baseline>` sets `diff_baseline` and composes with a plain
-    /// pattern + the `*.ext` ext-glob sugar, so a snapshot-diff is just a
-    /// search over the deleted set.
-    #[test]
-    fn from_cli_args_diff_baseline_composes_with_filters() {
-        use crate::protocol::SearchParams;
-        // `*.txt` is UFFS ext-glob sugar → pattern `*` + an ext filter, so the
-        // diff's deleted set is filtered to `.txt`; a plain `report` pattern
-        // would survive verbatim. Use the latter to pin pattern composition.
-        let args = vec![
-            "report".to_owned(),
-            "--diff".to_owned(),
-            "C_old.bin".to_owned(),
-            "--drive".to_owned(),
-            "C".to_owned(),
-        ];
-        let params = SearchParams::from_cli_args(&args).expect("valid diff search");
-        assert_eq!(params.diff_baseline.as_deref(), Some("C_old.bin"));
-        assert_eq!(
-            params.pattern, "report",
-            "the pattern still filters the deleted set"
-        );
-    }
-
-    /// A bare `--diff <baseline>` (no pattern) defaults the pattern to `*` so
-    /// it lists every deleted file.
-    #[test]
-    fn from_cli_args_bare_diff_defaults_pattern_to_star() {
-        use crate::protocol::SearchParams;
-        let args = vec![
-            "--diff".to_owned(),
-            "C_old.bin".to_owned(),
-            "--drive".to_owned(),
-            "C".to_owned(),
-        ];
-        let params = SearchParams::from_cli_args(&args).expect("valid bare diff");
-        assert_eq!(params.diff_baseline.as_deref(), Some("C_old.bin"));
-        assert_eq!(params.pattern, "*", "a bare diff lists all deleted files");
-    }
-
-    /// End-to-end: a second positional argument after the pattern
-    /// surfaces as `UnexpectedArgument`.
-    #[test]
-    fn from_cli_args_surfaces_unexpected_argument() {
-        use crate::protocol::SearchParams;
-        let args = vec!["pattern1".to_owned(), "pattern2".to_owned()];
-        let err = SearchParams::from_cli_args(&args).expect_err("second positional must error");
-        assert_eq!(err, CliArgsError::UnexpectedArgument {
-            arg: "pattern2".to_owned(),
-        },);
-    }
-
-    /// End-to-end: `--name-only` plus a path pattern surfaces as
-    /// `NameOnlyWithPathPattern`.
-    #[test]
-    fn from_cli_args_surfaces_name_only_with_path_pattern() {
-        use crate::protocol::SearchParams;
-        let args = vec!["foo/bar".to_owned(), "--name-only".to_owned()];
-        let err = SearchParams::from_cli_args(&args).expect_err("name-only+path must error");
-        assert_eq!(err, CliArgsError::NameOnlyWithPathPattern);
-    }
-
-    /// End-to-end: `--min-size abc` surfaces a typed `BadSize` after
-    /// the `?` From-conversion in `from_cli_args`.  Display matches
-    /// the pre-Phase-5d `"invalid size: abc"` text.
-    #[test]
-    fn from_cli_args_surfaces_bad_size() {
-        use crate::protocol::SearchParams;
-        let args = vec!["*".to_owned(), "--min-size".to_owned(), "abc".to_owned()];
-        let err = SearchParams::from_cli_args(&args).expect_err("bad size must error");
-        let CliArgsError::BadSize { source } = &err else {
-            panic!("expected BadSize variant, got {err:?}");
-        };
-        assert_eq!(source, &ParseSizeError::InvalidNumber {
-            spec: "abc".to_owned(),
-        },);
-        assert_eq!(err.to_string(), "invalid size: abc");
-    }
-}
+#[path = "cli_args_helpers_tests.rs"]
+mod cli_args_error_tests;
@@ -0,0 +1,301 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Phase 5d regression tests for [`CliArgsError`] and the typed
+//! return types of every helper in `cli_args_helpers`.
+//!
+//! Each test locks one variant of [`CliArgsError`] at the
+//! byte-identical Display string the pre-Phase-5d
+//! `Result<_, String>` produced, so operator-facing CLI error
+//! output is unchanged through the migration.  Variants that
+//! chain an underlying error (`BadInt`, `BadDriveParse`,
+//! `BadSize`) additionally walk [`core::error::Error::source`]
+//! to assert the typed chain is intact — the real improvement
+//! over the previous flattened `String`.
+
+use core::error::Error as _;
+
+use super::{
+    CliArgsError, drives_csv, parse_bool, parse_fuzzy_threshold, parse_i32, parse_size, parse_u16,
+    parse_u32, parse_u64, take_next, volumes_csv,
+};
+use crate::format::ParseSizeError;
+
+#[test]
+fn take_next_missing_value_locks_display() {
+    let mut empty = core::iter::empty::<String>();
+    let err = take_next("--drive", &mut empty).expect_err("missing value must error");
+    assert_eq!(err, CliArgsError::MissingValue {
+        flag: "--drive".to_owned(),
+    },);
+    assert_eq!(err.to_string(), "Missing value for --drive");
+    assert!(err.source().is_none(), "MissingValue has no chained source");
+}
+
+#[test]
+fn parse_u16_bad_int_locks_display_and_chains_source() {
+    let err =
+        parse_u16("--agg-page-size", "not-a-number").expect_err("non-numeric input must error");
+    let CliArgsError::BadInt { flag, source } = &err else {
+        panic!("expected BadInt variant, got {err:?}");
+    };
+    assert_eq!(flag, "--agg-page-size");
+    // Display matches the pre-Phase-5d `"Bad {flag}: {err}"` shape.
+    assert_eq!(err.to_string(), format!("Bad --agg-page-size: {source}"));
+    // The typed source chain is the migration's actual improvement
+    // over the flattened `String`.
+    let chained = err.source().expect("BadInt exposes its ParseIntError");
+    assert_eq!(chained.to_string(), source.to_string());
+}
+
+#[test]
+fn parse_u32_bad_int_locks_display() {
+    let err = parse_u32("--max-descendants", "abc").expect_err("must error");
+    assert!(matches!(&err, CliArgsError::BadInt { flag, .. } if flag == "--max-descendants"));
+    assert!(err.to_string().starts_with("Bad --max-descendants: "));
+}
+
+#[test]
+fn parse_u64_bad_int_locks_display() {
+    let err = parse_u64("--min-bulkiness", "xyz").expect_err("must error");
+    assert!(matches!(&err, CliArgsError::BadInt { flag, .. } if flag == "--min-bulkiness"));
+    assert!(err.to_string().starts_with("Bad --min-bulkiness: "));
+}
+
+#[test]
+fn parse_i32_bad_int_locks_display() {
+    let err = parse_i32("--tz-offset", "qux").expect_err("must error");
+    assert!(matches!(&err, CliArgsError::BadInt { flag, .. } if flag == "--tz-offset"));
+    assert!(err.to_string().starts_with("Bad --tz-offset: "));
+}
+
+#[test]
+fn parse_fuzzy_threshold_accepts_unit_interval_only() {
+    assert_eq!(parse_fuzzy_threshold("0.75"), Ok(0.75));
+    assert_eq!(parse_fuzzy_threshold("1"), Ok(1.0));
+    for bad in ["0", "1.5", "-0.2", "NaN", "high"] {
+        let err = parse_fuzzy_threshold(bad).expect_err("out-of-range threshold must error");
+        assert_eq!(
+            err.to_string(),
+            format!("Bad --fuzzy-threshold: '{bad}' (expected a number in (0, 1])")
+        );
+    }
+}
+
+#[test]
+fn parse_bool_locks_display() {
+    let err = parse_bool("--header", "maybe").expect_err("must error");
+    assert_eq!(err, CliArgsError::BadBool {
+        flag: "--header".to_owned(),
+        value: "maybe".to_owned(),
+    },);
+    assert_eq!(err.to_string(), "Bad bool for --header: 'maybe'");
+}
+
+#[test]
+fn parse_bool_accepts_canonical_forms() {
+    assert_eq!(parse_bool("--header", "true"), Ok(true));
+    assert_eq!(parse_bool("--header", "1"), Ok(true));
+    assert_eq!(parse_bool("--header", "yes"), Ok(true));
+    assert_eq!(parse_bool("--header", "false"), Ok(false));
+    assert_eq!(parse_bool("--header", "0"), Ok(false));
+    assert_eq!(parse_bool("--header", "no"), Ok(false));
+}
+
+/// Empty segment in the CSV (e.g. `,C`) takes the `EmptyDrive`
+/// branch.  Locks the Display at `"empty drive"`.
+#[test]
+fn drives_csv_empty_segment_locks_display() {
+    let err = drives_csv(",C").expect_err("empty leading segment must error");
+    assert_eq!(err, CliArgsError::EmptyDrive);
+    assert_eq!(err.to_string(), "empty drive");
+}
+
+/// Multi-character segment walks the `BadDrive` branch (it's not
+/// a single ASCII letter).  Display echoes the original part.
+#[test]
+fn drives_csv_multi_char_segment_locks_display() {
+    let err = drives_csv("CD").expect_err("multi-char segment must error");
+    assert_eq!(err, CliArgsError::BadDrive {
+        input: "CD".to_owned(),
+    },);
+    assert_eq!(err.to_string(), "Bad drive: 'CD'");
+}
+
+/// Non-alphabetic segment walks `BadDrive` (digits, punctuation).
+#[test]
+fn drives_csv_non_alpha_segment_locks_display() {
+    let err = drives_csv("1").expect_err("digit segment must error");
+    assert_eq!(err, CliArgsError::BadDrive {
+        input: "1".to_owned(),
+    },);
+    assert_eq!(err.to_string(), "Bad drive: '1'");
+}
+
+/// Happy-path lock — `drives_csv` must accept comma-separated
+/// letters with optional `:` suffix and surrounding whitespace.
+#[test]
+fn drives_csv_accepts_canonical_forms() {
+    let parsed = drives_csv("C,D:,E").expect("canonical CSV must parse");
+    assert_eq!(parsed.len(), 3);
+}
+
+/// `--drives` mixes bare letters and host-qualified volumes; a bad
+/// host label surfaces as `BadVolume` with the typed source chained.
+#[test]
+fn volumes_csv_accepts_host_qualified_segments() {
+    let parsed = volumes_csv("C, srv01\\D:,web-02/E").expect("mixed CSV must parse");
+    let rendered: Vec<String> = parsed.iter().map(ToString::to_string).collect();
+    assert_eq!(rendered, ["C", "srv01\\D", "web-02\\E"]);
+
+    let err = volumes_csv("bad host\\C").expect_err("space in host label must error");
+    assert!(matches!(err, CliArgsError::BadVolume { .. }), "{err:?}");
+    assert!(err.source().is_some());
+    assert_eq!(
+        volumes_csv("CD"),
+        Err(CliArgsError::BadDrive {
+            input: "CD".to_owned(),
+        })
+    );
+}
+
+/// `?`-propagation via `#[from]` lifts a `ParseSizeError` into
+/// `CliArgsError::BadSize` without losing the chain.  This is the
+/// path `from_cli_args` takes for `--min-size` / etc.
+#[test]
+fn parse_size_propagates_via_from_into_bad_size() {
+    // Direct construction of the error path the `?` operator
+    // would synthesise in `from_cli_args`.
+    let inner = parse_size("abc").expect_err("must error");
+    let outer: CliArgsError = inner.clone().into();
+    assert_eq!(outer, CliArgsError::BadSize {
+        source: inner.clone(),
+    },);
+    // Display delegates to the inner `ParseSizeError` ("invalid
+    // size: abc"), matching the pre-Phase-5d byte sequence which
+    // came directly from `parse_size`'s String return.
+    assert_eq!(outer.to_string(), inner.to_string());
+    // Chained source is the typed inner.
+    let chained = outer.source().expect("BadSize exposes its ParseSizeError");
+    let chained_size: &ParseSizeError = chained
+        .downcast_ref()
+        .expect("source must downcast to ParseSizeError");
+    assert_eq!(chained_size, &inner);
+}
+
+#[test]
+fn unknown_flag_display_locked() {
+    let err = CliArgsError::UnknownFlag {
+        flag: "--nope".to_owned(),
+    };
+    assert_eq!(err.to_string(), "Unknown flag: '--nope'");
+}
+
+#[test]
+fn unexpected_argument_display_locked() {
+    let err = CliArgsError::UnexpectedArgument {
+        arg: "extra".to_owned(),
+    };
+    assert_eq!(err.to_string(), "Unexpected argument: 'extra'");
+}
+
+#[test]
+fn name_only_with_path_pattern_display_locked() {
+    let err = CliArgsError::NameOnlyWithPathPattern;
+    assert_eq!(
+        err.to_string(),
+        "--name-only cannot be used with path patterns containing '\\' or '/'",
+    );
+}
+
+/// End-to-end: feeding `from_cli_args` an unknown flag must
+/// surface as the typed `UnknownFlag` variant.  This is the
+/// boundary where `cli_args.rs` raises the inline error.
+#[test]
+fn from_cli_args_surfaces_unknown_flag() {
+    use crate::protocol::SearchParams;
+    let args = vec!["--bogus-flag".to_owned()];
+    let err = SearchParams::from_cli_args(&args).expect_err("unknown flag must error");
+    assert_eq!(err, CliArgsError::UnknownFlag {
+        flag: "--bogus-flag".to_owned(),
+    },);
+}
+
+/// `--diff <baseline>` sets `diff_baseline` and composes with a plain
+/// pattern + the `*.ext` ext-glob sugar, so a snapshot-diff is just a
+/// search over the deleted set.
+#[test]
+fn from_cli_args_diff_baseline_composes_with_filters() {
+    use crate::protocol::SearchParams;
+    // `*.txt` is UFFS ext-glob sugar → pattern `*` + an ext filter, so the
+    // diff's deleted set is filtered to `.txt`; a plain `report` pattern
+    // would survive verbatim. Use the latter to pin pattern composition.
+    let args = vec![
+        "report".to_owned(),
+        "--diff".to_owned(),
+        "C_old.bin".to_owned(),
+        "--drive".to_owned(),
+        "C".to_owned(),
+    ];
+    let params = SearchParams::from_cli_args(&args).expect("valid diff search");
+    assert_eq!(params.diff_baseline.as_deref(), Some("C_old.bin"));
+    assert_eq!(
+        params.pattern, "report",
+        "the pattern still filters the deleted set"
+    );
+}
+
+/// A bare `--diff <baseline>` (no pattern) defaults the pattern to `*` so
+/// it lists every deleted file.
+#[test]
+fn from_cli_args_bare_diff_defaults_pattern_to_star() {
+    use crate::protocol::SearchParams;
+    let args = vec![
+        "--diff".to_owned(),
+        "C_old.bin".to_owned(),
+        "--drive".to_owned(),
+        "C".to_owned(),
+    ];
+    let params = SearchParams::from_cli_args(&args).expect("valid bare diff");
+    assert_eq!(params.diff_baseline.as_deref(), Some("C_old.bin"));
+    assert_eq!(params.pattern, "*", "a bare diff lists all deleted files");
+}
+
+/// End-to-end: a second positional argument after the pattern
+/// surfaces as `UnexpectedArgument`.
+#[test]
+fn from_cli_args_surfaces_unexpected_argument() {
+    use crate::protocol::SearchParams;
+    let args = vec!["pattern1".to_owned(), "pattern2".to_owned()];
+    let err = SearchParams::from_cli_args(&args).expect_err("second positional must error");
+    assert_eq!(err, CliArgsError::UnexpectedArgument {
+        arg: "pattern2".to_owned(),
+    },);
+}
+
+/// End-to-end: `--name-only` plus a path pattern surfaces as
+/// `NameOnlyWithPathPattern`.
+#[test]
+fn from_cli_args_surfaces_name_only_with_path_pattern() {
+    use crate::protocol::SearchParams;
+    let args = vec!["foo/bar".to_owned(), "--name-only".to_owned()];
+    let err = SearchParams::from_cli_args(&args).expect_err("name-only+path must error");
+    assert_eq!(err, CliArgsError::NameOnlyWithPathPattern);
+}
+
+/// End-to-end: `--min-size abc` surfaces a typed `BadSize` after
+/// the `?` From-conversion in `from_cli_args`.  Display matches
+/// the pre-Phase-5d `"invalid size: abc"` text.
+#[test]
+fn from_cli_args_surfaces_bad_size() {
+    use crate::protocol::SearchParams;
+    let args = vec!["*".to_owned(), "--min-size".to_owned(), "abc".to_owned()];
+    let err = SearchParams::from_cli_args(&args).expect_err("bad size must error");
+    let CliArgsError::BadSize { source } = &err else {
+        panic!("expected BadSize variant, got {err:?}");
+    };
+    assert_eq!(source, &ParseSizeError::InvalidNumber {
+        spec: "abc".to_owned(),
+    },);
+    assert_eq!(err.to_string(), "invalid size: abc");
+}
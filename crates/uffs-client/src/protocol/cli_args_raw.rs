@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Raw CLI flag holder filled by `SearchParams::from_cli_args`.
+//!
+//! Lifted out of `cli_args.rs` to keep that file under the 800-line
+//! policy ceiling; the sugar expansion itself stays there.
+
+use super::super::HashListMode;
+
+/// Transient container for raw CLI values before sugar expansion.
+///
+/// Fields mirror CLI flags 1:1; documented at the flag-parsing level.
+#[derive(Default)]
+#[expect(clippy::struct_excessive_bools, reason = "mirrors CLI flags")]
+#[cfg_attr(
+    not(test),
+    expect(
+        clippy::missing_docs_in_private_items,
+        reason = "fields mirror CLI flags — documented at the parser level"
+    )
+)]
+pub(super) struct RawCliArgs {
+    pub(super) pattern: Option<String>,
+    pub(super) drive: Option<uffs_mft::platform::DriveLetter>,
+    pub(super) drives: Option<Vec<uffs_mft::platform::VolumeId>>,
+    pub(super) files_only: bool,
+    pub(super) dirs_only: bool,
+    pub(super) hide_system: bool,
+    pub(super) hide_ads: bool,
+    pub(super) normalize_malformed: bool,
+    /// Diagnostic-only flag (see `--resolve-lcn-order` above).
+    pub(super) resolve_lcn_order: bool,
+    /// WI-4.4: `Some(true)` from `--malformed`, `Some(false)` from
+    /// `--well-formed`, `None` if neither (no filter).
+    pub(super) malformed: Option<bool>,
+    /// WI-4.4: `Some(true)` from `--malformed-path`.
+    pub(super) malformed_path: Option<bool>,
+    /// Snapshot-diff baseline path from `--diff <BASELINE>`: turns the query
+    /// into a search over the deleted set of that baseline vs the live index.
+    pub(super) diff_baseline: Option<String>,
+    /// `--as-of <TIME>`: search the index as it stood at that moment.
+    pub(super) as_of: Option<String>,
+    /// `--hash-in <FILE>`: known-hash list to filter the matches by.
+    pub(super) hash_in: Option<String>,
+    /// `--hash-mode match|known_good`: how `hash_in` filters.
+    pub(super) hash_mode: Option<HashListMode>,
+    /// `--patterns-from <FILE>`: name pattern list, read here.
+    pub(super) patterns_from: Option<String>,
+    /// `--field NAME=EXPR` computed-field definitions, in order.
+    pub(super) fields: Vec<String>,
+    /// `--where 'FIELD OP VALUE'` clauses.
+    pub(super) where_clauses: Vec<String>,
+    pub(super) profile: bool,
+    pub(super) benchmark: bool,
+    pub(super) no_cache: bool,
+    pub(super) case: bool,
+    pub(super) smart_case: bool,
+    pub(super) word: bool,
+    /// `--fuzzy` or a `fuzzy:` pattern prefix; `--fuzzy-threshold` implies it.
+    pub(super) fuzzy: bool,
+    pub(super) fuzzy_threshold: Option<f32>,
+    pub(super) name_only: bool,
+    pub(super) sort_desc: bool,
+    pub(super) rank: bool,
+    pub(super) parity_compat: bool,
+    pub(super) count: bool,
+    pub(super) rows: bool,
+    pub(super) no_output: bool,
+    pub(super) sort: Option<String>,
+    pub(super) ext: Option<String>,
+    pub(super) attr: Option<String>,
+    pub(super) newer: Option<String>,
+    pub(super) older: Option<String>,
+    pub(super) newer_created: Option<String>,
+    pub(super) older_created: Option<String>,
+    pub(super) newer_accessed: Option<String>,
+    pub(super) older_accessed: Option<String>,
+    pub(super) exclude: Option<String>,
+    pub(super) in_path: Option<String>,
+    pub(super) path_excludes: Option<String>,
+    pub(super) type_filter: Option<String>,
+    pub(super) month: Option<String>,
+    pub(super) between: Option<String>,
+    pub(super) begins_with: Option<String>,
+    pub(super) ends_with: Option<String>,
+    pub(super) contains: Option<String>,
+    pub(super) not_contains: Option<String>,
+    pub(super) limit: u32,
+    pub(super) min_size: Option<u64>,
+    pub(super) max_size: Option<u64>,
+    pub(super) exact_size: Option<u64>,
+    pub(super) min_size_on_disk: Option<u64>,
+    pub(super) max_size_on_disk: Option<u64>,
+    pub(super) exact_size_on_disk: Option<u64>,
+    pub(super) min_treesize: Option<u64>,
+    pub(super) max_treesize: Option<u64>,
+    pub(super) min_tree_allocated: Option<u64>,
+    pub(super) max_tree_allocated: Option<u64>,
+    pub(super) min_descendants: Option<u32>,
+    pub(super) max_descendants: Option<u32>,
+    pub(super) exact_descendants: Option<u32>,
+    pub(super) min_name_length: Option<u16>,
+    pub(super) max_name_length: Option<u16>,
+    pub(super) min_path_length: Option<u16>,
+    pub(super) max_path_length: Option<u16>,
+    pub(super) min_bulkiness: Option<u64>,
+    pub(super) max_bulkiness: Option<u64>,
+    pub(super) format: String,
+    pub(super) out: String,
+    pub(super) columns: String,
+    pub(super) sep: String,
+    pub(super) quotes: String,
+    pub(super) header: Option<bool>,
+    pub(super) pos: String,
+    pub(super) neg: String,
+    pub(super) query_mode: String,
+    pub(super) tz_offset: Option<i32>,
+    /// `--tz <ZONE>`: IANA zone or offset; wins over `tz_offset`.
+    pub(super) tz: Option<String>,
+    pub(super) agg: Vec<String>,
+    pub(super) facet: Vec<String>,
+    pub(super) stats: Vec<String>,
+    pub(super) histogram: Vec<String>,
+    pub(super) agg_cursor: Option<String>,
+    pub(super) agg_page_size: Option<u16>,
+    pub(super) mft_file: Vec<String>,
+    pub(super) data_dir: Option<String>,
+}
@@ -14,7 +14,11 @@ pub mod aggregate_wire;
 pub mod cli_args;
 mod cli_args_helpers;
 pub mod response;
+pub(crate) mod response_baselines;
+pub(crate) mod response_change_log;
 pub(crate) mod response_journal;
+pub(crate) mod response_queries;
+pub(crate) mod response_standing;
 pub(crate) mod response_status;
 pub(crate) mod response_tiering;
 pub mod search_params;
@@ -22,7 +26,8 @@ pub mod search_params;
 mod tests;
 
 pub use aggregate_wire::{
-    AggregateResultWire, AggregateSpecWire, BucketWire, DrilldownWire, SampleRowWire, StatsWire,
+    AggregateResultWire, AggregateSpecWire, BucketWire, DrilldownWire, GrowthWire, PercentileWire,
+    PivotRowWire, PivotWire, SampleRowWire, StatsWire,
 };
 use serde::{Deserialize, Serialize};
 
@@ -115,6 +120,17 @@ pub enum SearchFilterMode {
     Dirs,
 }
 
+/// How a `--hash-in` list filters the matched files.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HashListMode {
+    /// Keep only files whose content hash is on the list (IOC hunt).
+    #[default]
+    Match,
+    /// Drop files whose content hash is on the list (NSRL known-good).
+    KnownGood,
+}
+
 /// Canonical sort direction in the daemon wire contract.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -263,6 +279,15 @@ pub struct SearchParams {
     /// matches filename-only, consistent with Everything's default behaviour.
     #[serde(default)]
     pub match_path: bool,
+    /// Typo-tolerant matching: rank names by trigram + edit-distance
+    /// similarity to `pattern` instead of matching it exactly.  Also
+    /// selected by a `fuzzy:` pattern prefix.
+    #[serde(default)]
+    pub fuzzy: bool,
+    /// Minimum fuzzy similarity in `(0, 1]` (`None` = the core default,
+    /// 0.6).  Ignored unless fuzzy mode is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzzy_threshold: Option<f32>,
 
     // ── Sort ────────────────────────────────────────────────────────
     /// Sort column name (e.g. `"modified"`, `"size"`, `"name"`).
@@ -274,11 +299,20 @@ pub struct SearchParams {
     /// Sort direction: `true` = descending.
     #[serde(default)]
     pub sort_desc: bool,
+    /// Order hits by relevance (name match, depth, recency, system noise)
+    /// instead of the sort column.  Ignored for `*` and fuzzy queries.
+    #[serde(default)]
+    pub rank: bool,
 
     // ── Limit ───────────────────────────────────────────────────────
     /// Maximum results to return (`None` = unlimited).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
+    /// Opaque keyset cursor from a previous response's
+    /// [`SearchResponse::next_search_cursor`]: return the `limit` rows
+    /// after the last row of that page instead of the first `limit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_cursor: Option<String>,
 
     // ── Filter mode ────────────────────────────────────────────────
     /// Filter mode: `"all"` (default), `"files"`, `"dirs"`.
@@ -290,9 +324,11 @@ pub struct SearchParams {
     /// Canonical predicates. Preferred over legacy filter fields.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub predicates: Vec<SearchPredicate>,
-    /// Specific drives to search (empty = all loaded).
+    /// Specific volumes to search (empty = all loaded).  A bare letter
+    /// (`"C"`) selects that letter on every host; a host-qualified id
+    /// (`"srv01\\C"`) selects one volume.
     #[serde(default)]
-    pub drives: Vec<uffs_mft::platform::DriveLetter>,
+    pub drives: Vec<uffs_mft::platform::VolumeId>,
     /// Requested projection fields in canonical order.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub projection: Vec<String>,
@@ -518,6 +554,12 @@ pub struct SearchParams {
     /// formatting (overrides auto-detected local timezone).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_tz_offset_hours: Option<i32>,
+    /// `--tz`: IANA zone (`Europe/Berlin`) or UTC offset (`UTC+05:30`)
+    /// for timestamp output, date filters and calendar buckets.  A named
+    /// zone applies its DST rules per timestamp.  Wins over
+    /// `output_tz_offset_hours`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
     /// Output config: CLI-layer `--format` value (e.g. `"csv"`, `"json"`,
     /// `"custom"`, `"table"`).
     ///
@@ -543,8 +585,9 @@ pub struct SearchParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_format: Option<String>,
 
-    /// Drive letters the search targeted, echoed back into the legacy
-    /// drive footer when `output_format == Some("custom")`.
+    /// Volumes the search targeted, echoed back into the legacy drive
+    /// footer when `output_format == Some("custom")`.  Local volumes
+    /// keep the bare-letter wire form.
     ///
     /// Matches the CLI's local `targets` computation: populated from
     /// `--drive` / `--drives` (and, in the thin-client passthrough
@@ -559,7 +602,7 @@ pub struct SearchParams {
     /// C.mft` targets drive C for the footer but leaves `drives`
     /// empty because the MFT path is a separate wire selector.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub output_drive_targets: Vec<uffs_mft::platform::DriveLetter>,
+    pub output_drive_targets: Vec<uffs_mft::platform::VolumeId>,
 
     // ── Snapshot-diff (delete visibility) ──────────────────────────
     /// When set, this is a **snapshot-diff search**: the daemon loads the
@@ -569,8 +612,50 @@ pub struct SearchParams {
     /// other field (pattern, `ext`, `newer`/`older`, `min_size`, sort,
     /// projection, output format) then filters/shapes the deleted set exactly
     /// like a normal search. `None` = ordinary live search.
+    ///
+    /// `@<time>` (`@yesterday`, `@2026-10-01`, `@1760000000000`) names a
+    /// daemon-side baseline instead of a capture file: a scheduled
+    /// `[snapshots]` snapshot or a point in the `[history]` (see
+    /// `list_baselines`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub diff_baseline: Option<String>,
+
+    /// Point-in-time search: run the query against the index as it stood
+    /// at this moment, rebuilt from the daemon's `[history]` checkpoints
+    /// and journal deltas. Same grammar as `newer` plus bare Unix
+    /// milliseconds. With `diff_baseline`, the moment is the diff's
+    /// *current* side. `None` = the live index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+
+    /// Known-hash list (`--hash-in`): absolute path of a file of SHA-256,
+    /// SHA-1 or MD5 digests — one per line, `sha256sum` output, or an
+    /// NSRL-style CSV with a header row. The daemon hashes only the matched
+    /// files whose size the list allows and keeps the results in its
+    /// content-hash cache. `None` = no content filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_in: Option<String>,
+
+    /// How `hash_in` applies: `match` (default) keeps the listed files,
+    /// `known_good` drops them (NSRL exclusion).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_mode: Option<HashListMode>,
+
+    /// Name pattern list (`--patterns-from`, or an array over MCP): a row
+    /// must also match one entry. A bare entry is a whole file name; globs
+    /// and `>` regexes work as in `pattern`, case-insensitively. The daemon
+    /// compiles the list into one matcher and reports the entry that hit
+    /// in the `matched_pattern` column. Empty = no list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+
+    /// Computed-field definitions (`--field NAME=EXPR`), e.g.
+    /// `avgsize=treesize/descendants`. They add to (and override) the
+    /// daemon's saved `[fields]` and can be named in `predicates`, `sort`,
+    /// `projection` and `stats`/`hist`/`range` aggregations. Empty = saved
+    /// fields only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub computed_fields: Vec<String>,
 }
 
 /// Default-true helper for serde.
@@ -585,10 +670,14 @@ impl Default for SearchParams {
             case_sensitive: false,
             whole_word: false,
             match_path: false,
+            fuzzy: false,
+            fuzzy_threshold: None,
             sort: None,
             sorts: vec![],
             sort_desc: false,
+            rank: false,
             limit: None,
+            search_cursor: None,
             filter: None,
             filter_mode: None,
             predicates: vec![],
@@ -644,9 +733,15 @@ impl Default for SearchParams {
             output_columns: None,
             output_parity_compat: None,
             output_tz_offset_hours: None,
+            time_zone: None,
             output_format: None,
             output_drive_targets: Vec::new(),
             diff_baseline: None,
+            as_of: None,
+            hash_in: None,
+            hash_mode: None,
+            patterns: Vec::new(),
+            computed_fields: Vec::new(),
         }
     }
 }
@@ -11,7 +11,19 @@
 
 use serde::{Deserialize, Serialize};
 
+pub use super::response_baselines::{DriveBaselines, ListBaselinesResponse, SnapshotInfo};
+pub use super::response_change_log::{
+    ChangeLogEntry, ChangeLogKind, TailChangesParams, TailChangesResponse,
+};
 pub use super::response_journal::{ChangedSinceParams, ChangedSinceResponse, JournalChange};
+pub use super::response_queries::{
+    DeleteQueryParams, DeleteQueryResponse, ListQueriesResponse, MAX_QUERY_NAME_LEN, QueryError,
+    QueryInvocation, SaveQueryParams, SaveQueryResponse, SavedQuery, validate_query_name,
+};
+pub use super::response_standing::{
+    QUERY_MATCH_METHOD, QueryChangeKind, QueryMatch, SubscribeQueryParams, SubscribeQueryResponse,
+    UnsubscribeQueryParams, UnsubscribeQueryResponse,
+};
 pub use super::response_status::{
     DaemonPaths, DaemonStatus, DriveInfo, DriveMemoryInfo, DrivesResponse, LiveUpdateInfo,
     ShardTier, StatsResponse, StatusResponse,
@@ -28,9 +40,9 @@ use super::{
 /// Parameters for the `refresh` method.
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct RefreshParams {
-    /// Specific drives to refresh (empty = all loaded).
+    /// Volumes to refresh (empty = all loaded; a bare letter = every host).
     #[serde(default)]
-    pub drives: Vec<uffs_mft::platform::DriveLetter>,
+    pub drives: Vec<uffs_mft::platform::VolumeId>,
 }
 
 /// Parameters for the `load_drive` method.
@@ -362,6 +374,16 @@ pub struct SearchResponse {
     pub promotion_ms: Option<u64>,
     /// Whether results were truncated by limit.
     pub truncated: bool,
+    /// Keyset cursor for the next page — pass it back as
+    /// [`super::SearchParams::search_cursor`].  `None` on the last page and
+    /// whenever the search was not limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_search_cursor: Option<String>,
+    /// The request's `search_cursor` was cut from an older index version:
+    /// the page resumes from the cursor row's sort key, so rows added,
+    /// removed or re-keyed in between may shift the boundary.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cursor_index_changed: bool,
     /// Detailed timing breakdown from the daemon (only when
     /// `SearchParams::profile` was `true`).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -493,10 +515,10 @@ pub struct DriveProfile {
 }
 
 /// A single search result row.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchRow {
-    /// Drive letter.
-    pub drive: uffs_mft::platform::DriveLetter,
+    /// Volume the row belongs to (serialised as `"C"` or `"srv01\\C"`).
+    pub drive: uffs_mft::platform::VolumeId,
     /// Full resolved path.
     pub path: String,
     /// Filename.
@@ -548,6 +570,16 @@ pub struct SearchRow {
     /// additive fields above.
     #[serde(default)]
     pub file_reference: u64,
+    /// Cached full-content digest (`blake3:…`) for the `hash` column, read
+    /// from the daemon's content-hash cache when the column is projected.
+    /// `None` for files never hashed; omitted from JSON in that case.
+    /// Serialized as `hash`, the column's canonical name.
+    #[serde(default, rename = "hash", skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// The `--patterns-from` entry the name matched, for the
+    /// `matched_pattern` column; omitted from JSON when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_pattern: Option<String>,
 }
 
 /// Feed `SearchRow` directly into the shared `uffs-format` writer.
@@ -570,7 +602,11 @@ impl uffs_format::FormatRow for SearchRow {
         // doesn't depend on `uffs-mft`, so the trait surface stays
         // `char`-typed.  We translate at this boundary; the cost is
         // one byte read.
-        self.drive.as_char()
+        self.drive.letter().as_char()
+    }
+    #[inline]
+    fn host(&self) -> &'static str {
+        self.drive.host_str()
     }
     #[inline]
     fn path(&self) -> &str {
@@ -632,6 +668,14 @@ impl uffs_format::FormatRow for SearchRow {
     fn name_hex(&self) -> Option<&str> {
         self.name_hex.as_deref()
     }
+    #[inline]
+    fn content_hash(&self) -> Option<&str> {
+        self.content_hash.as_deref()
+    }
+    #[inline]
+    fn matched_pattern(&self) -> Option<&str> {
+        self.matched_pattern.as_deref()
+    }
 }
 
 /// Response for the `info` method (all 25 columns for a path).
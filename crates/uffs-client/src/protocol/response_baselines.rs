@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Diff-baseline RPC wire types: `list_baselines`.
+//!
+//! A snapshot diff (`diff_baseline`) can name a capture file or a
+//! daemon-side moment, `@<time>`. Daemon-side moments come from two
+//! opt-in stores: the scheduled `[snapshots]` (one body per drive per
+//! day, named after its local day — `@2026-10-01`) and the `[history]`
+//! checkpoints, which can rebuild any moment since their oldest
+//! checkpoint. `list_baselines` reports what each store holds per drive
+//! so a caller can pick a baseline without guessing.
+//!
+//! Split into this sibling file to keep [`super::response`] under the
+//! workspace 800-LOC policy ceiling — same precedent as
+//! [`super::response_change_log`].
+
+use serde::{Deserialize, Serialize};
+
+/// One retained scheduled snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// The snapshot's name — its local calendar day (`2026-10-01`);
+    /// pass it as `diff_baseline: "@2026-10-01"`.
+    pub name: String,
+    /// When the snapshot was taken, in Unix milliseconds.
+    pub at_ms: u64,
+    /// Size of the snapshot file on disk.
+    #[serde(default)]
+    pub bytes: u64,
+}
+
+/// The baselines retained for one drive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DriveBaselines {
+    /// The drive.
+    pub drive: uffs_mft::platform::DriveLetter,
+    /// Scheduled snapshots, oldest first.
+    #[serde(default)]
+    pub snapshots: Vec<SnapshotInfo>,
+    /// Oldest moment the index history can rebuild (Unix ms); `None`
+    /// when `[history]` is disabled or holds nothing for the drive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_since_ms: Option<u64>,
+}
+
+/// Response for the `list_baselines` method.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListBaselinesResponse {
+    /// `[snapshots]` is enabled in the daemon config.
+    #[serde(default)]
+    pub snapshots_enabled: bool,
+    /// `[history]` is enabled in the daemon config.
+    #[serde(default)]
+    pub history_enabled: bool,
+    /// One entry per registered drive, in drive order.
+    #[serde(default)]
+    pub drives: Vec<DriveBaselines>,
+}
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Change-feed RPC wire types: `tail_changes`.
+//!
+//! `changed_since` answers only while the NTFS journal still holds the
+//! range. When the daemon's change log is enabled (`[change_log]` in
+//! `daemon.toml`), every journal delta it applies is also appended —
+//! with resolved paths — to a rotating per-drive NDJSON log under the
+//! cache root, and `tail_changes` reads that log back. The log survives
+//! journal wrap and daemon restarts, bounded only by its own retention.
+//!
+//! Split into this sibling file to keep [`super::response`] under the
+//! workspace 800-LOC policy ceiling — same precedent as
+//! [`super::response_journal`].
+//!
+//! ## Cursor contract
+//!
+//! Entries carry a per-drive sequence number that increases by one per
+//! entry and is never reused. A caller persists the `next_seq` of each
+//! response and passes it back as `after_seq`; `after_seq == 0` reads
+//! from the oldest retained entry. When retention has already pruned
+//! entries the caller never saw, the response says `complete == false`
+//! and starts at the oldest retained entry — the caller must resync by
+//! other means for the gap.
+
+use serde::{Deserialize, Serialize};
+
+/// What happened to a file in a [`ChangeLogEntry`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeLogKind {
+    /// The file was created.
+    Created,
+    /// The file was deleted.
+    Deleted,
+    /// The file was renamed or moved.
+    Renamed,
+    /// The file's size or metadata changed.
+    Modified,
+}
+
+/// One applied change, as stored on disk (one NDJSON line) and as
+/// returned by `tail_changes`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangeLogEntry {
+    /// Per-drive sequence number (the `tail_changes` cursor).
+    pub seq: u64,
+    /// When the daemon applied the change, in Unix milliseconds.
+    pub at_ms: u64,
+    /// What happened.
+    pub kind: ChangeLogKind,
+    /// File Record Segment number (48-bit, sequence masked off).
+    pub frs: u64,
+    /// Full path after the change (before it, for a delete).  Empty
+    /// when the index could not resolve the record.
+    pub path: String,
+    /// Path before a rename, when the index knew the record.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    /// USN of the newest journal record behind the change (`0` when
+    /// unknown).  A journal replayed after a daemon restart re-delivers
+    /// changes the log already holds; the log recognises them by it and
+    /// does not record them twice.
+    #[serde(default)]
+    pub usn: i64,
+}
+
+/// Parameters for the `tail_changes` method.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TailChangesParams {
+    /// Drive whose change log to read.
+    pub drive: uffs_mft::platform::DriveLetter,
+    /// Cursor: return entries with `seq` strictly greater than this.
+    /// `0` = from the oldest retained entry.
+    #[serde(default)]
+    pub after_seq: u64,
+    /// Upper bound on entries returned.  `None` ⇒ the daemon's own cap;
+    /// larger values are clamped.  Page on with the response's
+    /// `next_seq` while `truncated == true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<u32>,
+}
+
+/// Response for the `tail_changes` method.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TailChangesResponse {
+    /// Drive the response describes (echo of the request).
+    #[serde(default)]
+    pub drive: Option<uffs_mft::platform::DriveLetter>,
+    /// The entries after the cursor, in `seq` order.
+    #[serde(default)]
+    pub entries: Vec<ChangeLogEntry>,
+    /// The caller's next cursor: the last returned `seq`, or the
+    /// request's `after_seq` when nothing new was logged.
+    #[serde(default)]
+    pub next_seq: u64,
+    /// Oldest `seq` still retained (`0` when the log is empty).
+    #[serde(default)]
+    pub oldest_seq: u64,
+    /// `false` ⇒ retention pruned entries between the cursor and
+    /// [`Self::oldest_seq`]; the feed has a gap.
+    #[serde(default)]
+    pub complete: bool,
+    /// `true` ⇒ more entries follow; call again from
+    /// [`Self::next_seq`].
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `after_seq` defaults to "from the start", `max_entries` stays off
+    /// the wire when unset, and `old_path` only appears on renames.
+    #[test]
+    fn wire_defaults_and_optional_fields() {
+        let params: TailChangesParams =
+            serde_json::from_str(r#"{"drive":"C"}"#).expect("drive alone is a valid request");
+        assert_eq!(params.after_seq, 0);
+        assert_eq!(params.max_entries, None);
+        let encoded = serde_json::to_string(&params).expect("params serialise");
+        assert!(!encoded.contains("max_entries"), "got: {encoded}");
+
+        let entry = ChangeLogEntry {
+            seq: 7,
+            at_ms: 1,
+            kind: ChangeLogKind::Created,
+            frs: 42,
+            path: "C:\\new.txt".to_owned(),
+            old_path: None,
+            usn: 4_096,
+        };
+        let line = serde_json::to_string(&entry).expect("entry serialises");
+        assert!(line.contains(r#""kind":"created""#), "got: {line}");
+        assert!(!line.contains("old_path"), "got: {line}");
+        let back: ChangeLogEntry = serde_json::from_str(&line).expect("entry round-trips");
+        assert_eq!(back, entry);
+
+        let legacy: ChangeLogEntry =
+            serde_json::from_str(r#"{"seq":1,"at_ms":1,"kind":"deleted","frs":5,"path":""}"#)
+                .expect("a line written before `usn` still parses");
+        assert_eq!(legacy.usn, 0);
+    }
+}
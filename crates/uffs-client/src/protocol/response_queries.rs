@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Saved-query RPC wire types: `save_query`, `list_queries`, and
+//! `delete_query`.
+//!
+//! A saved query is a named, reusable search: the same argument list a
+//! user would type after `uffs`, with `{placeholder}` slots filled in at
+//! run time.  The daemon keeps the library in `queries.toml` next to
+//! `daemon.toml`, so the CLI (`uffs @stale-vms --drive D`), the MCP
+//! bridge (one resource + one prompt per query), and any other client
+//! share one vetted set.
+//!
+//! Storing the *argument list* rather than a [`super::SearchParams`]
+//! keeps the file human-editable and lets every search flag — filters,
+//! sorts, `--columns` projection, `--agg` aggregations, output format —
+//! ride along without a second schema; [`super::SearchParams::from_cli_args`]
+//! stays the single parser.
+//!
+//! Split into this sibling file to keep [`super::response`] under the
+//! workspace 800-LOC policy ceiling — same precedent as
+//! [`super::response_journal`] and [`super::response_tiering`].
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted saved-query name, in bytes.
+pub const MAX_QUERY_NAME_LEN: usize = 64;
+
+/// One named query in the library.
+///
+/// `args` may contain `{name}` placeholders — a letter or `_` followed by
+/// letters, digits, or `_` inside braces.  Anything else in braces (a regex
+/// quantifier such as `{3}` or `{2,5}`) is literal text.
+///
+/// ```toml
+/// [stale-vms]
+/// description = "VM images untouched for a while"
+/// args = ["*", "--ext", "vmdk,vhdx,vdi", "--older", "{age}", "--sort", "size"]
+///
+/// [stale-vms.defaults]
+/// age = "90d"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedQuery {
+    /// One-line summary shown by `uffs --query list` and the MCP prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Search arguments, exactly as typed after `uffs`.
+    pub args: Vec<String>,
+    /// Fallback values for placeholders the caller does not set.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub defaults: BTreeMap<String, String>,
+}
+
+impl SavedQuery {
+    /// Every placeholder name referenced by [`Self::args`].
+    #[must_use]
+    pub fn placeholders(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for arg in &self.args {
+            substitute(arg, &mut |name| {
+                names.insert(name.to_owned());
+                None
+            });
+        }
+        names
+    }
+
+    /// Fill the placeholders from `values`, falling back to
+    /// [`Self::defaults`].
+    ///
+    /// # Errors
+    ///
+    /// [`QueryError::MissingParam`] for the first placeholder with neither
+    /// a value nor a default.
+    pub fn expand(&self, values: &BTreeMap<String, String>) -> Result<Vec<String>, QueryError> {
+        let mut missing = None;
+        let expanded = self
+            .args
+            .iter()
+            .map(|arg| {
+                substitute(arg, &mut |name| {
+                    let value = values.get(name).or_else(|| self.defaults.get(name));
+                    if value.is_none() && missing.is_none() {
+                        missing = Some(name.to_owned());
+                    }
+                    value.cloned()
+                })
+            })
+            .collect();
+        missing.map_or(Ok(expanded), |name| Err(QueryError::MissingParam { name }))
+    }
+}
+
+/// A parsed `@name [--param k=v]… [search args]…` invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryInvocation {
+    /// Saved-query name (without the `@`).
+    pub name: String,
+    /// Placeholder values from `--param k=v`.
+    pub values: BTreeMap<String, String>,
+    /// Every other argument, appended after the expanded query so it can
+    /// narrow or override it (`--drive D`, a second `--limit`).
+    pub extra_args: Vec<String>,
+}
+
+impl QueryInvocation {
+    /// Parse `args` when its first token is `@name`; `Ok(None)` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// [`QueryError::InvalidName`] for a bad name after `@`, and
+    /// [`QueryError::BadParam`] for a `--param` without `key=value`.
+    pub fn parse(args: &[String]) -> Result<Option<Self>, QueryError> {
+        let Some(name) = args.first().and_then(|first| first.strip_prefix('@')) else {
+            return Ok(None);
+        };
+        validate_query_name(name)?;
+        let mut values = BTreeMap::new();
+        let mut extra_args = Vec::new();
+        let mut iter = args.iter().skip(1);
+        while let Some(arg) = iter.next() {
+            let pair = if arg == "--param" {
+                iter.next().map_or("", String::as_str)
+            } else if let Some(inline) = arg.strip_prefix("--param=") {
+                inline
+            } else {
+                extra_args.push(arg.clone());
+                continue;
+            };
+            let (key, value) = pair
+                .split_once('=')
+                .filter(|(key, _)| !key.is_empty())
+                .ok_or_else(|| QueryError::BadParam {
+                    raw: pair.to_owned(),
+                })?;
+            values.insert(key.to_owned(), value.to_owned());
+        }
+        Ok(Some(Self {
+            name: name.to_owned(),
+            values,
+            extra_args,
+        }))
+    }
+
+    /// The full search argument list: `query`'s expanded args followed by
+    /// [`Self::extra_args`].
+    ///
+    /// # Errors
+    ///
+    /// See [`SavedQuery::expand`].
+    pub fn resolve(&self, query: &SavedQuery) -> Result<Vec<String>, QueryError> {
+        let mut args = query.expand(&self.values)?;
+        args.extend(self.extra_args.iter().cloned());
+        Ok(args)
+    }
+}
+
+/// Check a saved-query name: 1–[`MAX_QUERY_NAME_LEN`] ASCII letters,
+/// digits, `-`, or `_`, so it works unquoted after `@` in every shell and
+/// inside a `uffs://queries/{name}` URI.
+///
+/// # Errors
+///
+/// [`QueryError::InvalidName`] when the name breaks those rules.
+pub fn validate_query_name(name: &str) -> Result<(), QueryError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_QUERY_NAME_LEN
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+    if valid {
+        Ok(())
+    } else {
+        Err(QueryError::InvalidName {
+            name: name.to_owned(),
+        })
+    }
+}
+
+/// Errors from naming, invoking, or expanding a saved query.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum QueryError {
+    /// The name is empty, too long, or has characters outside
+    /// `[A-Za-z0-9_-]`.
+    #[error(
+        "invalid query name `{name}` (use up to {MAX_QUERY_NAME_LEN} letters, digits, `-` or `_`)"
+    )]
+    InvalidName {
+        /// The rejected name.
+        name: String,
+    },
+    /// A `--param` value is not `key=value`.
+    #[error("--param expects key=value, got `{raw}`")]
+    BadParam {
+        /// The rejected argument.
+        raw: String,
+    },
+    /// A placeholder has neither a `--param` value nor a default.
+    #[error("saved query needs --param {name}=<value>")]
+    MissingParam {
+        /// The unfilled placeholder.
+        name: String,
+    },
+    /// No saved query by that name.
+    #[error("no saved query named `{name}` (see `uffs --query list`)")]
+    UnknownQuery {
+        /// The requested name.
+        name: String,
+    },
+}
+
+/// Replace every `{name}` placeholder in `arg` with `lookup(name)`; a
+/// `None` keeps the placeholder text as-is.
+fn substitute(arg: &str, lookup: &mut dyn FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(open) = rest.find('{') {
+        let (before, from_brace) = rest.split_at(open);
+        out.push_str(before);
+        let placeholder = from_brace
+            .get(1..)
+            .and_then(|inner| inner.split_once('}'))
+            .filter(|(name, _)| is_placeholder_name(name));
+        if let Some((name, after)) = placeholder {
+            if let Some(value) = lookup(name) {
+                out.push_str(&value);
+            } else {
+                out.push('{');
+                out.push_str(name);
+                out.push('}');
+            }
+            rest = after;
+        } else {
+            out.push('{');
+            rest = from_brace.get(1..).unwrap_or_default();
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `[A-Za-z_][A-Za-z0-9_]*`.
+fn is_placeholder_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    bytes
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == b'_')
+        && bytes.all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// save_query / list_queries / delete_query
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Parameters for the `save_query` method.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SaveQueryParams {
+    /// Library key; see [`validate_query_name`].
+    pub name: String,
+    /// The query to store.
+    pub query: SavedQuery,
+    /// Overwrite an existing query of the same name.  Without it, saving
+    /// over an existing name is rejected so one user cannot silently
+    /// clobber another's vetted query.
+    #[serde(default)]
+    pub replace: bool,
+}
+
+/// Response for the `save_query` method.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SaveQueryResponse {
+    /// The stored name.
+    pub name: String,
+    /// An existing query was overwritten.
+    #[serde(default)]
+    pub replaced: bool,
+}
+
+/// Response for the `list_queries` method (no parameters).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListQueriesResponse {
+    /// The whole library, keyed and sorted by name.
+    #[serde(default)]
+    pub queries: BTreeMap<String, SavedQuery>,
+}
+
+/// Parameters for the `delete_query` method.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeleteQueryParams {
+    /// Name of the query to remove.
+    pub name: String,
+}
+
+/// Response for the `delete_query` method.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeleteQueryResponse {
+    /// The requested name.
+    pub name: String,
+    /// `false` when no query had that name (deleting is idempotent).
+    #[serde(default)]
+    pub deleted: bool,
+}
@@ -62,6 +62,14 @@ pub enum ShardTier {
 pub struct DriveInfo {
     /// Drive letter.
     pub letter: uffs_mft::platform::DriveLetter,
+    /// Host label of a shard loaded from another machine's capture;
+    /// absent for local volumes and from older daemons.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Volume serial (`1A2B-3C4D`) of a hosted shard whose capture
+    /// recorded one; absent otherwise and from older daemons.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
     /// Number of records in the compact index.  `0` for `Parked` /
     /// `Cold` shards whose body has been released.
     pub records: usize,
@@ -97,6 +105,23 @@ pub struct DriveInfo {
     pub loading: Option<bool>,
 }
 
+impl DriveInfo {
+    /// The shard's volume identity, host-qualified when `host` is set and
+    /// serial-qualified when `serial` is.
+    #[must_use]
+    pub fn volume(&self) -> uffs_mft::platform::VolumeId {
+        let host = self
+            .host
+            .as_deref()
+            .and_then(|label| uffs_mft::platform::HostLabel::parse(label).ok());
+        let serial = self
+            .serial
+            .as_deref()
+            .and_then(|serial| uffs_mft::platform::VolumeSerial::parse(serial).ok());
+        uffs_mft::platform::VolumeId::new(host, self.letter).with_serial(serial)
+    }
+}
+
 /// Response for the `status` method.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatusResponse {
@@ -270,7 +295,7 @@ pub enum DaemonStatus {
     /// Daemon is refreshing one or more drives.
     #[serde(rename = "refreshing")]
     Refreshing {
-        /// Drives being refreshed.
-        drives: Vec<uffs_mft::platform::DriveLetter>,
+        /// Volumes being refreshed.
+        drives: Vec<uffs_mft::platform::VolumeId>,
     },
 }
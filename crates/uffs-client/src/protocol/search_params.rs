@@ -223,6 +223,17 @@ impl SearchParams {
         if self.response_mode.is_none() {
             self.response_mode = Some(self.resolved_response_mode());
         }
+        // `--tz` (else `--tz-offset`) is the zone calendar buckets are
+        // cut in unless a spec names its own.
+        let request_zone = self.time_zone.clone().or_else(|| {
+            self.output_tz_offset_hours
+                .map(|hours| format!("{hours:+}"))
+        });
+        if let Some(zone) = request_zone {
+            for spec in &mut self.aggregations {
+                spec.tz.get_or_insert_with(|| zone.clone());
+            }
+        }
     }
 
     /// Canonicalize a legacy comma-separated sort string plus `sort_desc` flag.
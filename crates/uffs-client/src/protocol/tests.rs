@@ -151,6 +151,93 @@ fn from_cli_args_resolve_lcn_order_flag() {
     assert!(!off.resolve_lcn_order, "absent flag defaults off");
 }
 
+/// The CLI surface: `--hash-in` is sent as an absolute path (the daemon
+/// reads it from its own working directory); `--hash-mode` takes
+/// `match` / `known_good` and rejects anything else.
+#[test]
+fn from_cli_args_hash_in_and_mode() {
+    let parsed = SearchParams::from_cli_args(&[
+        "*".to_owned(),
+        "--hash-in".to_owned(),
+        "iocs.txt".to_owned(),
+        "--hash-mode".to_owned(),
+        "known-good".to_owned(),
+    ])
+    .expect("parse hash flags");
+    let list = parsed.hash_in.expect("hash_in set");
+    assert!(
+        std::path::Path::new(&list).is_absolute() && list.ends_with("iocs.txt"),
+        "relative list path must be made absolute: {list}"
+    );
+    assert_eq!(parsed.hash_mode, Some(HashListMode::KnownGood));
+
+    let bad = SearchParams::from_cli_args(&[
+        "*".to_owned(),
+        "--hash-mode".to_owned(),
+        "maybe".to_owned(),
+    ]);
+    assert!(bad.is_err(), "unknown --hash-mode must be rejected");
+}
+
+#[test]
+fn from_cli_args_patterns_from_reads_the_list() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let list = dir.path().join("iocs.txt");
+    std::fs::write(&list, "\u{feff}# feed\nmimikatz.exe\r\n\n  *dump*  \n").expect("write");
+    let parsed =
+        SearchParams::from_cli_args(&["--patterns-from".to_owned(), list.display().to_string()])
+            .expect("parse --patterns-from");
+    assert_eq!(parsed.patterns, ["mimikatz.exe", "*dump*"]);
+    assert_eq!(parsed.pattern, "*");
+
+    let missing = SearchParams::from_cli_args(&[
+        "--patterns-from".to_owned(),
+        dir.path().join("absent.txt").display().to_string(),
+    ]);
+    assert!(missing.is_err(), "an unreadable list must be rejected");
+}
+
+#[test]
+fn from_cli_args_field_and_where() {
+    let args: Vec<String> = [
+        "*",
+        "--min-size",
+        "1KB",
+        "--field",
+        "slack=allocated-size",
+        "--field=age=age_days(modified)",
+        "--where",
+        "slack >= 1MB",
+        "--where=age<-1",
+    ]
+    .map(str::to_owned)
+    .to_vec();
+    let parsed = SearchParams::from_cli_args(&args).expect("parse --field/--where");
+    assert_eq!(parsed.computed_fields, [
+        "slack=allocated-size",
+        "age=age_days(modified)"
+    ]);
+    let [.., legacy, slack, age] = parsed.predicates.as_slice() else {
+        panic!("expected three predicates, got {:?}", parsed.predicates);
+    };
+    assert_eq!(
+        legacy.field, "size",
+        "--min-size survives next to the clauses"
+    );
+    assert_eq!(*slack, SearchPredicate {
+        field: "slack".to_owned(),
+        op: SearchPredicateOp::Gte,
+        value: SearchPredicateValue::U64(1024 * 1024),
+    });
+    assert_eq!(age.op, SearchPredicateOp::Lt);
+    assert_eq!(age.value, SearchPredicateValue::I64(-1));
+
+    for bad in ["slack", "slack >", "> 5", "slack ~ 5", "slack > big"] {
+        let err = SearchParams::from_cli_args(&["--where".to_owned(), bad.to_owned()]);
+        assert!(err.is_err(), "{bad} must be rejected");
+    }
+}
+
 /// Canonical helpers preserve legacy single-flag sort semantics.
 ///
 /// First field: ascending by default (no `--sort-desc`).
@@ -241,6 +328,55 @@ fn resolved_filter_mode_prefers_canonical_field() {
     assert_eq!(params.resolved_filter_mode(), SearchFilterMode::Files);
 }
 
+/// `--tz-offset` becomes the zone of every aggregation without its own.
+#[test]
+fn populate_canonical_fields_defaults_aggregation_tz() {
+    let spec = |tz: Option<&str>| AggregateSpecWire {
+        kind: "raw".to_owned(),
+        label: Some("heatmap:modified".to_owned()),
+        tz: tz.map(str::to_owned),
+        ..Default::default()
+    };
+    let mut params = SearchParams {
+        aggregations: vec![spec(None), spec(Some("UTC"))],
+        output_tz_offset_hours: Some(-8_i32),
+        ..Default::default()
+    };
+    params.populate_canonical_fields();
+    assert_eq!(params.aggregations[0].tz.as_deref(), Some("-8"));
+    assert_eq!(params.aggregations[1].tz.as_deref(), Some("UTC"));
+
+    let mut named = SearchParams {
+        aggregations: vec![spec(None)],
+        output_tz_offset_hours: Some(-8_i32),
+        time_zone: Some("Europe/Berlin".to_owned()),
+        ..Default::default()
+    };
+    named.populate_canonical_fields();
+    assert_eq!(named.aggregations[0].tz.as_deref(), Some("Europe/Berlin"));
+}
+
+#[test]
+fn tz_flag_is_checked_and_canonicalized() {
+    let params = SearchParams::from_cli_args(&[
+        "*".to_owned(),
+        "--tz".to_owned(),
+        "asia/calcutta".to_owned(),
+    ])
+    .expect("known zone");
+    assert_eq!(params.time_zone.as_deref(), Some("Asia/Calcutta"));
+    let offset =
+        SearchParams::from_cli_args(&["*".to_owned(), "--tz=+5:30".to_owned()]).expect("offset");
+    assert_eq!(offset.time_zone.as_deref(), Some("UTC+05:30"));
+    let err = SearchParams::from_cli_args(&[
+        "*".to_owned(),
+        "--tz".to_owned(),
+        "Mars/Olympus".to_owned(),
+    ])
+    .expect_err("unknown zone");
+    assert!(err.to_string().starts_with("Bad --tz: 'Mars/Olympus'"));
+}
+
 /// D2.2.5: `DaemonStatus` serialize/deserialize.
 #[test]
 fn daemon_status_round_trip() {
@@ -268,7 +404,7 @@ fn daemon_status_round_trip() {
 fn search_response_inline_rows_round_trip() {
     let resp = SearchResponse {
         payload: SearchPayload::InlineRows(vec![SearchRow {
-            drive: uffs_mft::platform::DriveLetter::C,
+            drive: uffs_mft::platform::DriveLetter::C.into(),
             path: "C:\\test.rs".to_owned(),
             name: "test.rs".to_owned(),
             size: 1024,
@@ -285,12 +421,16 @@ fn search_response_inline_rows_round_trip() {
             malformed_path: false,
             name_hex: None,
             file_reference: 0,
+            content_hash: None,
+            matched_pattern: None,
         }]),
         total_count: 1,
         records_scanned: 1_000_000,
         duration_ms: 8,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: vec![SearchSortSpec {
             field: "modified".to_owned(),
@@ -338,7 +478,7 @@ fn search_response_inline_rows_round_trip() {
 #[test]
 fn search_row_default_json_carries_name_hex_for_malformed_only() {
     let base = SearchRow {
-        drive: uffs_mft::platform::DriveLetter::G,
+        drive: uffs_mft::platform::DriveLetter::G.into(),
         path: "G:\\corrupted\\file.txt".to_owned(),
         name: "file.txt".to_owned(),
         size: 0,
@@ -355,6 +495,8 @@ fn search_row_default_json_carries_name_hex_for_malformed_only() {
         malformed_path: false,
         name_hex: None,
         file_reference: 0,
+        content_hash: None,
+        matched_pattern: None,
     };
 
     // Well-formed row: no hex evidence, so the key is dropped entirely.
@@ -408,6 +550,8 @@ fn search_response_shmem_blob_round_trip() {
         duration_ms: 60,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: vec![],
         applied_projection: vec!["path".to_owned()],
@@ -469,6 +613,8 @@ fn search_response_inline_blob_round_trip() {
         duration_ms: 5,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: vec![],
         applied_projection: vec!["path".to_owned()],
@@ -542,6 +688,8 @@ fn search_response_shmem_rows_round_trip() {
         duration_ms: 42,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: vec![],
         applied_projection: vec!["path".to_owned(), "size".to_owned()],
@@ -680,6 +828,7 @@ fn stats_wire_round_trip() {
         avg: 500.0,
         waste_bytes: 200_000,
         waste_pct: 4.0,
+        percentiles: Vec::new(),
     };
     let json = serde_json::to_string(&stats).expect("serialize");
     let parsed: StatsWire = serde_json::from_str(&json).expect("deserialize");
@@ -703,10 +852,12 @@ fn bucket_wire_full_round_trip() {
         avg_size: Some(4_000.0_f64),
         share_count: Some(5.0_f64),
         share_bytes: Some(3.2_f64),
+        percentiles: Vec::new(),
         sample_rows: Vec::new(),
         drilldown: Vec::new(),
         sub_buckets: Vec::new(),
         verified: false,
+        growth: None,
     };
     let json = serde_json::to_string(&bucket).expect("serialize");
     let parsed: BucketWire = serde_json::from_str(&json).expect("deserialize");
@@ -753,6 +904,7 @@ fn aggregate_result_wire_count_round_trip() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     let parsed: AggregateResultWire = serde_json::from_str(&json).expect("deserialize");
@@ -778,6 +930,7 @@ fn aggregate_result_wire_stats_round_trip() {
             avg: 500.0,
             waste_bytes: 1_000,
             waste_pct: 2.0,
+            percentiles: Vec::new(),
         }),
         buckets: vec![],
         other_count: None,
@@ -785,6 +938,7 @@ fn aggregate_result_wire_stats_round_trip() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     let parsed: AggregateResultWire = serde_json::from_str(&json).expect("deserialize");
@@ -825,6 +979,7 @@ fn aggregate_result_wire_terms_round_trip() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     let parsed: AggregateResultWire = serde_json::from_str(&json).expect("deserialize");
@@ -884,6 +1039,8 @@ fn search_response_with_aggregations_round_trip() {
         duration_ms: 12,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: vec![],
         applied_projection: vec![],
@@ -902,6 +1059,7 @@ fn search_response_with_aggregations_round_trip() {
                 next_cursor: None,
                 exact: None,
                 values_complete: None,
+                pivot: None,
             },
             AggregateResultWire {
                 label: Some("type_breakdown".to_owned()),
@@ -923,6 +1081,7 @@ fn search_response_with_aggregations_round_trip() {
                 next_cursor: None,
                 exact: None,
                 values_complete: None,
+                pivot: None,
             },
         ],
     };
@@ -1036,6 +1195,7 @@ fn bucket_wire_with_samples_round_trip() {
         avg_size: None,
         share_count: None,
         share_bytes: None,
+        percentiles: Vec::new(),
         sample_rows: vec![SampleRowWire {
             fields,
             sort_key: Some(999),
@@ -1047,6 +1207,7 @@ fn bucket_wire_with_samples_round_trip() {
         }],
         sub_buckets: Vec::new(),
         verified: false,
+        growth: None,
     };
     let json = serde_json::to_string(&bucket).expect("serialize");
     assert!(json.contains("sample_rows"));
@@ -1173,6 +1334,7 @@ fn aggregate_result_wire_next_cursor_round_trip() {
         next_cursor: Some("0:50:50".to_owned()),
         exact: None,
         values_complete: None,
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     assert!(json.contains(r#""next_cursor":"0:50:50""#));
@@ -1196,6 +1358,7 @@ fn aggregate_result_wire_next_cursor_omitted_when_none() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     assert!(!json.contains("next_cursor"));
@@ -1216,6 +1379,7 @@ fn aggregate_result_wire_exact_and_values_complete_round_trip() {
         next_cursor: None,
         exact: Some(true),
         values_complete: Some(true),
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     assert!(json.contains(r#""exact":true"#), "json: {json}");
@@ -1240,6 +1404,7 @@ fn aggregate_result_wire_exact_omitted_when_none() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     assert!(!json.contains("exact"), "json: {json}");
@@ -1501,6 +1666,7 @@ fn aggregate_result_wire_values_complete_false() {
         next_cursor: None,
         exact: Some(true),
         values_complete: Some(false),
+        pivot: None,
     };
     let json = serde_json::to_string(&result).expect("serialize");
     let parsed: AggregateResultWire = serde_json::from_str(&json).expect("deserialize");
@@ -1631,6 +1797,81 @@ fn from_cli_args_path_scope_ext_glob_not_promoted() {
     assert!(params.ext.is_none());
 }
 
+/// `--fuzzy` and the `fuzzy:` prefix both select fuzzy mode; the prefix is
+/// stripped and composes with the drive prefix behind it.
+#[test]
+fn from_cli_args_fuzzy_flag_and_prefix() {
+    let flag = SearchParams::from_cli_args(&["--fuzzy".into(), "invoce".into()]).expect("parse");
+    assert!(flag.fuzzy);
+    assert_eq!(flag.pattern, "invoce");
+    assert!(flag.fuzzy_threshold.is_none());
+
+    let prefixed = SearchParams::from_cli_args(&["fuzzy:C:invoce".into()]).expect("parse");
+    assert!(prefixed.fuzzy);
+    assert_eq!(prefixed.pattern, "invoce");
+    assert_eq!(prefixed.drives, vec![uffs_mft::platform::DriveLetter::C]);
+
+    let plain = SearchParams::from_cli_args(&["invoce".into()]).expect("parse");
+    assert!(!plain.fuzzy, "fuzzy mode is opt-in");
+}
+
+/// `--fuzzy-threshold` implies `--fuzzy` and rejects values outside (0, 1].
+#[test]
+fn from_cli_args_fuzzy_threshold() {
+    let args: Vec<String> = vec!["--fuzzy-threshold=0.45".into(), "invoce".into()];
+    let params = SearchParams::from_cli_args(&args).expect("parse");
+    assert!(params.fuzzy);
+    assert_eq!(params.fuzzy_threshold, Some(0.45));
+
+    let bad: Vec<String> = vec!["--fuzzy-threshold".into(), "2".into(), "invoce".into()];
+    assert!(matches!(
+        SearchParams::from_cli_args(&bad),
+        Err(cli_args::Error::BadFuzzyThreshold { .. })
+    ));
+}
+
+/// `--rank` is opt-in and survives the JSON round trip to the daemon.
+#[test]
+fn from_cli_args_rank_flag() {
+    let params = SearchParams::from_cli_args(&["--rank".into(), "report".into()]).expect("parse");
+    assert!(params.rank);
+    let wire = serde_json::to_value(&params).expect("serialize");
+    let back: SearchParams = serde_json::from_value(wire).expect("deserialize");
+    assert!(back.rank);
+
+    let plain = SearchParams::from_cli_args(&["report".into()]).expect("parse");
+    assert!(!plain.rank, "rank mode is opt-in");
+}
+
+/// `search_cursor` / `next_search_cursor` are optional on the wire: absent
+/// when unused (older daemons and clients keep parsing), carried verbatim
+/// when set.
+#[test]
+fn search_cursor_fields_are_optional_on_the_wire() {
+    let plain = serde_json::to_value(SearchParams::default()).expect("serialize");
+    assert!(plain.get("search_cursor").is_none());
+
+    let params = SearchParams {
+        search_cursor: Some("k1:3:50:2a:C:i7".to_owned()),
+        ..Default::default()
+    };
+    let wire = serde_json::to_value(&params).expect("serialize");
+    let back: SearchParams = serde_json::from_value(wire).expect("deserialize");
+    assert_eq!(back.search_cursor.as_deref(), Some("k1:3:50:2a:C:i7"));
+
+    let resp: SearchResponse = serde_json::from_value(serde_json::json!({
+        "payload": { "kind": "empty" },
+        "records_scanned": 0_u64,
+        "duration_ms": 0_u64,
+        "truncated": false,
+        "next_search_cursor": "k1:3:100:2b:C:i6",
+        "cursor_index_changed": true,
+    }))
+    .expect("deserialize");
+    assert_eq!(resp.next_search_cursor.as_deref(), Some("k1:3:100:2b:C:i6"));
+    assert!(resp.cursor_index_changed);
+}
+
 // ── <letter>: → drive-filter promotion regression tests ──────────────
 //
 // These tests pin the parse-time rewrite that promotes a bare drive
@@ -2221,3 +2462,89 @@ fn from_cli_args_no_drive_path_pattern_unscoped() {
     assert_eq!(params.pattern, "\\rnio\\*");
     assert_eq!(params.drives, Vec::<uffs_mft::platform::DriveLetter>::new());
 }
+
+// ── Saved queries (`@name`, `{placeholder}`) ─────────────────────────
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use crate::protocol::response::{QueryError, QueryInvocation, SavedQuery};
+
+fn stale_vms() -> SavedQuery {
+    SavedQuery {
+        description: Some("VM images untouched for a while".to_owned()),
+        args: vec![
+            ">\\.v(md|hd)k?x?$".into(),
+            "--older".into(),
+            "{age}".into(),
+            "--min-size={min_size}".into(),
+            "--exclude".into(),
+            "x{2,3}".into(),
+        ],
+        defaults: [("age".to_owned(), "90d".to_owned())].into(),
+    }
+}
+
+/// Regex quantifiers stay literal; only `{identifier}` is a placeholder.
+#[test]
+fn saved_query_placeholders_skip_regex_quantifiers() {
+    let names: Vec<String> = stale_vms().placeholders().into_iter().collect();
+    assert_eq!(names, ["age", "min_size"]);
+}
+
+/// Values override defaults; an unfilled placeholder is an error.
+#[test]
+fn saved_query_expand_fills_values_then_defaults() {
+    let query = stale_vms();
+    assert_eq!(
+        query.expand(&BTreeMap::default()),
+        Err(QueryError::MissingParam {
+            name: "min_size".to_owned()
+        })
+    );
+    let values = [("min_size".to_owned(), "2G".to_owned())].into();
+    let args = query.expand(&values).expect("all filled");
+    assert_eq!(args, [
+        ">\\.v(md|hd)k?x?$",
+        "--older",
+        "90d",
+        "--min-size=2G",
+        "--exclude",
+        "x{2,3}"
+    ]);
+}
+
+/// `@name --param k=v --drive D` splits into the name, the values and the
+/// pass-through search args, which land after the expanded query.
+#[test]
+fn query_invocation_parses_params_and_extra_args() {
+    let args: Vec<String> = [
+        "@stale-vms",
+        "--param",
+        "min_size=1GB",
+        "--drive",
+        "D",
+        "--param=age=30d",
+    ]
+    .map(str::to_owned)
+    .into();
+    let invocation = QueryInvocation::parse(&args).expect("valid").expect("is @");
+    assert_eq!(invocation.name, "stale-vms");
+    assert_eq!(invocation.extra_args, ["--drive", "D"]);
+    let resolved = invocation.resolve(&stale_vms()).expect("filled");
+    assert_eq!(resolved.get(2).map(String::as_str), Some("30d"));
+    assert_eq!(resolved.last().map(String::as_str), Some("D"));
+    let params = SearchParams::from_cli_args(&resolved).expect("expanded args parse");
+    assert_eq!(params.min_size, crate::format::parse_size("1GB").ok());
+
+    assert_eq!(QueryInvocation::parse(&["*.pdf".to_owned()]), Ok(None));
+    assert!(matches!(
+        QueryInvocation::parse(&["@bad name".to_owned()]),
+        Err(QueryError::InvalidName { .. })
+    ));
+    assert!(matches!(
+        QueryInvocation::parse(&["@ok".to_owned(), "--param".to_owned(), "novalue".to_owned()]),
+        Err(QueryError::BadParam { .. })
+    ));
+}
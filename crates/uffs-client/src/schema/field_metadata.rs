@@ -883,6 +883,72 @@ impl FieldId {
                     default_top: 0,
                 },
             },
+            Self::Host => FieldMeta {
+                id: self,
+                canonical_name: "host",
+                aliases: &["machine"],
+                field_type: FieldType::String,
+                access: FieldAccess::Hot,
+                sortable: true,
+                default_sort_direction: Some(SortDirection::Ascending),
+                filterable: true,
+                projectable: true,
+                tui_label: "Host",
+                display_name: "Host",
+                df_column: "",
+                default_value: "",
+                aggregate: AggregateMeta {
+                    aggregatable: false,
+                    groupable: true,
+                    bucket_support: false,
+                    cardinality: Cardinality::Low,
+                    default_top: 50,
+                },
+            },
+            Self::Hash => FieldMeta {
+                id: self,
+                canonical_name: "hash",
+                aliases: &["content_hash", "digest"],
+                field_type: FieldType::String,
+                access: FieldAccess::Cold,
+                sortable: false,
+                default_sort_direction: None,
+                filterable: false,
+                projectable: true,
+                tui_label: "Hash",
+                display_name: "Hash",
+                df_column: "",
+                default_value: "",
+                aggregate: AggregateMeta {
+                    aggregatable: false,
+                    groupable: false,
+                    bucket_support: false,
+                    cardinality: Cardinality::Unbounded,
+                    default_top: 0,
+                },
+            },
+            Self::MatchedPattern => FieldMeta {
+                id: self,
+                canonical_name: "matched_pattern",
+                aliases: &["ioc", "matched"],
+                field_type: FieldType::String,
+                access: FieldAccess::Cold,
+                sortable: false,
+                default_sort_direction: None,
+                filterable: false,
+                projectable: true,
+                tui_label: "Matched",
+                display_name: "Matched Pattern",
+                df_column: "",
+                default_value: "",
+                aggregate: AggregateMeta {
+                    aggregatable: false,
+                    groupable: false,
+                    bucket_support: false,
+                    cardinality: Cardinality::Unbounded,
+                    default_top: 0,
+                },
+            },
         }
     }
 }
@@ -116,6 +116,13 @@ pub enum FieldId {
     NameLength,
     /// Full-path length in characters.
     PathLength,
+    /// Host label of the volume (empty for local volumes).
+    Host,
+    /// Cached full-content digest from duplicate verification (projection
+    /// only).
+    Hash,
+    /// The `--patterns-from` entry that matched the name (projection only).
+    MatchedPattern,
 }
 
 /// Cardinality hint for aggregation planning.
@@ -284,6 +291,9 @@ impl FieldId {
         Self::ParityAttributes,
         Self::NameLength,
         Self::PathLength,
+        Self::Host,
+        Self::Hash,
+        Self::MatchedPattern,
     ];
 
     /// The number of variants in `FieldId`.
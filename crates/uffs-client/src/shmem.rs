@@ -76,7 +76,16 @@ const MAGIC: u32 = 0x5346_4655; // b"UFFS" LE
 /// (e.g. an ad-hoc single-filename search) stay under the threshold and
 /// are delivered inline instead, which is why this only ever showed up on
 /// large scans.
-const VERSION: u32 = 4;
+///
+/// v5: the former `_pad2` word carries `host_len`, the length of the row's
+/// host label, stored in the string table right after the name (empty for
+/// local volumes). Same 96-byte record; the bump stops a v4 reader from
+/// dropping the host of an offline-estate row.
+///
+/// v6: that slot holds the row's whole volume id for hosted volumes
+/// (`srv01\C#1A2B-3C4D`) instead of the bare host label, so rows keep the
+/// volume serial. Still empty for local volumes.
+const VERSION: u32 = 6;
 
 // ── On-disk structures ────────────────────────────────────────────────────
 
@@ -135,8 +144,9 @@ pub(crate) struct ShmemRecord {
     file_reference: u64,
     /// Descendant count (dirs only).
     descendants: u32,
-    /// Padding.
-    _pad2: u32,
+    /// Byte length of the hosted volume id, which follows the name in the
+    /// string table (`0` for local volumes).
+    host_len: u32,
     /// Subtree total size (dirs only).
     treesize: u64,
     /// Subtree allocated size (dirs only).
@@ -233,8 +243,16 @@ pub fn write_search_results(
         string_table.extend_from_slice(name_bytes);
         let name_len = u32::try_from(name_bytes.len()).unwrap_or(u32::MAX);
 
+        let volume = if row.drive.is_local() {
+            String::new()
+        } else {
+            row.drive.to_string()
+        };
+        string_table.extend_from_slice(volume.as_bytes());
+        let host_len = u32::try_from(volume.len()).unwrap_or(u32::MAX);
+
         records.push(ShmemRecord {
-            drive: row.drive.as_byte(),
+            drive: row.drive.letter().as_byte(),
             is_directory: u8::from(row.is_directory),
             malformed: u8::from(row.malformed),
             malformed_path: u8::from(row.malformed_path),
@@ -246,7 +264,7 @@ pub fn write_search_results(
             accessed: row.accessed,
             file_reference: row.file_reference,
             descendants: row.descendants,
-            _pad2: 0,
+            host_len,
             treesize: row.treesize,
             tree_allocated: row.tree_allocated,
             path_off,
@@ -310,6 +328,23 @@ pub fn write_search_results(
     Ok(path)
 }
 
+/// Rebuild a row's [`VolumeId`](uffs_mft::platform::VolumeId) from the
+/// record's drive byte and its (possibly empty) hosted volume id.
+///
+/// Shmem records are written with `letter().as_byte()` (which is always in
+/// `b'A'..=b'Z'`), so the `TryFrom` succeeds in normal operation.  A fallback
+/// to `DriveLetter::X` keeps the reader resilient against truncated /
+/// corrupted blobs without panicking.
+fn decode_volume(drive: u8, host: &str) -> io::Result<uffs_mft::platform::VolumeId> {
+    let letter = uffs_mft::platform::DriveLetter::try_from(drive)
+        .unwrap_or(uffs_mft::platform::DriveLetter::X);
+    if host.is_empty() {
+        return Ok(uffs_mft::platform::VolumeId::local(letter));
+    }
+    uffs_mft::platform::VolumeId::parse(host)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
 /// Read search results from a shared-memory file and delete it.
 ///
 /// Returns a fully populated [`SearchResponse`] with inline `rows`.
@@ -388,8 +423,9 @@ pub fn read_search_results(path: &Path) -> io::Result<SearchResponse> {
         let path_end = path_start + rec.path_len as usize; // u32→usize lossless on 64-bit
         let name_start = rec.name_off as usize; // u32→usize lossless on 64-bit
         let name_end = name_start + rec.name_len as usize; // u32→usize lossless on 64-bit
+        let host_end = name_end + rec.host_len as usize; // u32→usize lossless on 64-bit
 
-        if path_end > string_table.len() || name_end > string_table.len() {
+        if path_end > string_table.len() || host_end > string_table.len() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("string offset out of bounds at row {i}"),
@@ -400,15 +436,10 @@ pub fn read_search_results(path: &Path) -> io::Result<SearchResponse> {
             .map_err(|utf8_err| io::Error::new(io::ErrorKind::InvalidData, utf8_err))?;
         let name_str = core::str::from_utf8(&string_table[name_start..name_end])
             .map_err(|utf8_err| io::Error::new(io::ErrorKind::InvalidData, utf8_err))?;
-
+        let host_str = core::str::from_utf8(&string_table[name_end..host_end])
+            .map_err(|utf8_err| io::Error::new(io::ErrorKind::InvalidData, utf8_err))?;
         rows.push(SearchRow {
-            // Shmem records are written with `row.drive.as_byte()`
-            // (which is always in `b'A'..=b'Z'`), so the `TryFrom`
-            // succeeds in normal operation.  A fallback to
-            // `DriveLetter::X` keeps the reader resilient against
-            // truncated / corrupted blobs without panicking.
-            drive: uffs_mft::platform::DriveLetter::try_from(rec.drive)
-                .unwrap_or(uffs_mft::platform::DriveLetter::X),
+            drive: decode_volume(rec.drive, host_str)?,
             path: path_str.to_owned(),
             name: name_str.to_owned(),
             size: rec.size,
@@ -434,6 +465,10 @@ pub fn read_search_results(path: &Path) -> io::Result<SearchResponse> {
             // path instead.
             name_hex: None,
             file_reference: rec.file_reference,
+            // Likewise not in the record: the `hash` column is a projection
+            // served over the JSON path.
+            content_hash: None,
+            matched_pattern: None,
         });
     }
 
@@ -459,6 +494,8 @@ pub fn read_search_results(path: &Path) -> io::Result<SearchResponse> {
         // blob was announced in.
         promotion_ms: None,
         truncated: header.truncated != 0,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: Vec::new(),
         applied_projection: Vec::new(),
@@ -65,7 +65,7 @@ fn lock_shmem_dir() -> MutexGuard<'static, ()> {
 /// Helper: build a minimal `SearchRow` for testing.
 fn sample_row(name: &str) -> SearchRow {
     SearchRow {
-        drive: uffs_mft::platform::DriveLetter::C,
+        drive: uffs_mft::platform::DriveLetter::C.into(),
         path: format!("C:\\test\\{name}"),
         name: name.to_owned(),
         size: 1024,
@@ -87,6 +87,8 @@ fn sample_row(name: &str) -> SearchRow {
         // reader hardcoded `file_reference: 0` regardless of what was
         // written (see `VERSION`'s v4 doc note in `shmem.rs`).
         file_reference: 0x0002_0000_0000_2AF8,
+        content_hash: None,
+        matched_pattern: None,
     }
 }
 
@@ -140,6 +140,8 @@ fn build_drive() -> DriveCompactIndex {
         .collect();
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: TrigramIndex::empty().into(),
@@ -8,8 +8,18 @@
 //! `feed()` is called for every matching record. After scanning,
 //! `finalize()` produces the data needed for the response.
 
-use super::spec::{AggregateKind, BucketMetric, ScalarMetric, TopHitsSpec};
+use alloc::sync::Arc;
+
+use uffs_time::TimeZone;
+
+use super::extract::{
+    extract_group_key, extract_timestamp, is_missing, measure_value, truncate_timestamp,
+};
+use super::nested::NestedAccumulators;
+use super::percentile::{PercentileRank, QuantileSketch, wants_bucket_sketch};
+use super::spec::{AggregateKind, AggregateSpec, BucketMetric, ScalarMetric, TopHitsSpec};
 use crate::compact::{CompactRecord, DriveCompactIndex};
+use crate::search::computed::{self, ComputedField};
 use crate::search::field::FieldId;
 
 /// Running statistics for a single group or global scope.
@@ -30,6 +40,9 @@ pub struct StatsAccumulator {
     pub max: u64,
     /// Sum of allocated sizes (for waste calculation).
     pub sum_allocated: u64,
+    /// Quantile sketch of the fed values, present only when percentile
+    /// metrics were requested (see [`super::percentile`]).
+    pub sketch: Option<QuantileSketch>,
 }
 
 impl StatsAccumulator {
@@ -42,12 +55,23 @@ impl StatsAccumulator {
             min: u64::MAX,
             max: 0,
             sum_allocated: 0,
+            sketch: None,
+        }
+    }
+
+    /// Create an empty accumulator that also sketches its values when
+    /// `percentiles` is set.
+    #[must_use]
+    pub(crate) fn tracking(percentiles: bool) -> Self {
+        Self {
+            sketch: percentiles.then(QuantileSketch::new),
+            ..Self::new()
         }
     }
 
     /// Feed a value from a record.
     #[inline]
-    pub(crate) const fn feed_value(&mut self, value: u64, allocated: u64) {
+    pub(crate) fn feed_value(&mut self, value: u64, allocated: u64) {
         self.count += 1;
         self.sum += value;
         if value < self.min {
@@ -57,10 +81,13 @@ impl StatsAccumulator {
             self.max = value;
         }
         self.sum_allocated += allocated;
+        if let Some(sketch) = self.sketch.as_mut() {
+            sketch.insert(value);
+        }
     }
 
     /// Merge another accumulator into this one.
-    pub(crate) const fn merge(&mut self, other: &Self) {
+    pub(crate) fn merge(&mut self, other: &Self) {
         self.count += other.count;
         self.sum += other.sum;
         if other.min < self.min {
@@ -70,6 +97,18 @@ impl StatsAccumulator {
             self.max = other.max;
         }
         self.sum_allocated += other.sum_allocated;
+        match (self.sketch.as_mut(), other.sketch.as_ref()) {
+            (Some(ours), Some(theirs)) => ours.merge(theirs),
+            (None, Some(theirs)) => self.sketch = Some(theirs.clone()),
+            (_, None) => {}
+        }
+    }
+
+    /// Value at `rank` from the sketch; `p0` / `p100` are the exact
+    /// minimum and maximum. `None` without a sketch or values.
+    #[must_use]
+    pub(crate) fn percentile(&self, rank: PercentileRank) -> Option<u64> {
+        self.sketch.as_ref()?.quantile(rank.fraction())
     }
 
     /// Compute the average value (returns 0 if count is 0).
@@ -123,9 +162,19 @@ impl Default for StatsAccumulator {
 /// - `Count`: just a counter
 /// - `Stats`: a `StatsAccumulator`
 /// - `Terms`: a map from key to `StatsAccumulator`
+/// - `RegexTerms`: a map from captured text to bucket statistics
 /// - `Histogram`/`DateHistogram`/`Range`: array of `StatsAccumulator`
 /// - `Missing`/`Distinct`: specialized counters
+/// - `Pivot`: a sparse map from (row, column) key to `StatsAccumulator`
+/// - `SimilarNames`: a map from normalised-name key to cluster statistics
+///
+/// Bucketed kinds with a nested [`super::spec::AggregateSpec::sub`] also
+/// keep one child accumulator per bucket (see [`super::nested`]).
 #[derive(Debug, Clone)]
+#[expect(
+    clippy::partial_pub_fields,
+    reason = "nested child state is an engine detail, not part of the public accumulator API"
+)]
 pub struct GroupAccumulator {
     /// What this accumulator computes.
     pub kind: AccumulatorKind,
@@ -133,6 +182,11 @@ pub struct GroupAccumulator {
     pub field: Option<FieldId>,
     /// Label for output.
     pub label: Option<String>,
+    /// Per-bucket child accumulators, when the spec nests a `sub`.
+    pub(crate) sub: Option<NestedAccumulators>,
+    /// Computed field measured instead of `field`, with the `now` it is
+    /// evaluated at (fixed for the whole scan).
+    pub(crate) computed: Option<(Arc<ComputedField>, i64)>,
 }
 
 /// The internal accumulator strategy.
@@ -167,6 +221,13 @@ pub enum AccumulatorKind {
         /// Spec for creating new heaps (stored for lazy init).
         sample_spec: Option<TopHitsSpec>,
     },
+    /// Group-by regex capture: maps captured text → stats.
+    RegexTerms {
+        /// Inner capture-group accumulator.
+        inner: super::regex_key::RegexTermsAccumulator,
+        /// Requested metrics.
+        metrics: Vec<BucketMetric>,
+    },
     /// Fixed-size histogram buckets.
     Histogram {
         /// One accumulator per bucket (sorted by boundary).
@@ -182,6 +243,8 @@ pub enum AccumulatorKind {
         buckets: alloc::collections::BTreeMap<i64, StatsAccumulator>,
         /// Calendar interval for truncation.
         calendar: super::spec::CalendarInterval,
+        /// Zone buckets are cut in.
+        time_zone: TimeZone,
         /// Requested metrics.
         metrics: Vec<BucketMetric>,
     },
@@ -201,12 +264,6 @@ pub enum AccumulatorKind {
         inner: super::rollup::RollupAccumulator,
         /// Requested metrics.
         metrics: Vec<BucketMetric>,
-        /// Per-group sub-accumulators for nested rollups, keyed by the
-        /// drive-qualified rollup group key.
-        /// `None` when no sub-aggregation is requested.
-        sub_accumulators: Option<std::collections::HashMap<u64, GroupAccumulator>>,
-        /// The sub-aggregation spec (cloned from `AggregateKind::Rollup.sub`).
-        sub_kind: Option<super::spec::AggregateSpec>,
     },
     /// Duplicate detection accumulator.
     Duplicates {
@@ -215,9 +272,33 @@ pub enum AccumulatorKind {
         /// Sample row spec for materializing member indices post-scan.
         sample_spec: Option<TopHitsSpec>,
     },
+    /// Two-axis pivot accumulator.
+    Pivot {
+        /// Inner sparse cell accumulator.
+        inner: super::pivot::PivotAccumulator,
+    },
+    /// Near-duplicate filename cluster accumulator.
+    SimilarNames {
+        /// Inner cluster accumulator.
+        inner: super::similar::SimilarNamesAccumulator,
+    },
 }
 
 impl GroupAccumulator {
+    /// Create a new accumulator for `spec`, including the per-bucket state
+    /// for its nested `sub` chain.
+    #[must_use]
+    pub(crate) fn from_spec(spec: &AggregateSpec) -> Self {
+        let mut acc = Self::from_kind(&spec.kind, spec.label.clone());
+        acc.sub = spec.sub.as_deref().map(NestedAccumulators::new);
+        if let Some(field) = &spec.computed {
+            let now = if field.uses_now() { computed::now() } else { 0 };
+            acc.computed = Some((Arc::clone(field), now));
+            acc.field = None;
+        }
+        acc
+    }
+
     /// Create a new accumulator for the given aggregate kind.
     #[must_use]
     #[expect(
@@ -229,7 +310,9 @@ impl GroupAccumulator {
             AggregateKind::Count => (AccumulatorKind::Count { count: 0 }, None),
             AggregateKind::Stats { field, metrics } => (
                 AccumulatorKind::Stats {
-                    stats: StatsAccumulator::new(),
+                    stats: StatsAccumulator::tracking(
+                        !super::percentile::scalar_ranks(metrics).is_empty(),
+                    ),
                     metrics: metrics.clone(),
                 },
                 Some(*field),
@@ -273,14 +356,29 @@ impl GroupAccumulator {
                 field,
                 calendar,
                 metrics,
-            } => (
-                AccumulatorKind::DateHistogram {
-                    buckets: alloc::collections::BTreeMap::new(),
-                    calendar: *calendar,
-                    metrics: metrics.clone(),
-                },
-                Some(*field),
-            ),
+                time_zone,
+            } => {
+                // A cyclic histogram lists every hour / weekday, active
+                // or not — an empty slot is the answer to "when".
+                let buckets = (0..calendar.period().unwrap_or(0))
+                    .filter_map(|slot| i64::try_from(slot).ok())
+                    .map(|slot| {
+                        (
+                            slot,
+                            StatsAccumulator::tracking(wants_bucket_sketch(metrics)),
+                        )
+                    })
+                    .collect();
+                (
+                    AccumulatorKind::DateHistogram {
+                        buckets,
+                        calendar: *calendar,
+                        time_zone: time_zone.unwrap_or_default(),
+                        metrics: metrics.clone(),
+                    },
+                    Some(*field),
+                )
+            }
             AggregateKind::Range {
                 field,
                 boundaries,
@@ -289,13 +387,34 @@ impl GroupAccumulator {
                 let bucket_count = boundaries.len() + 1;
                 (
                     AccumulatorKind::Histogram {
-                        buckets: (0..bucket_count).map(|_| StatsAccumulator::new()).collect(),
+                        buckets: (0..bucket_count)
+                            .map(|_| StatsAccumulator::tracking(wants_bucket_sketch(metrics)))
+                            .collect(),
                         boundaries: boundaries.clone(),
                         metrics: metrics.clone(),
                     },
                     Some(*field),
                 )
             }
+            AggregateKind::RegexTerms {
+                key,
+                top,
+                metrics,
+                sample,
+                unmatched,
+            } => (
+                AccumulatorKind::RegexTerms {
+                    inner: super::regex_key::RegexTermsAccumulator::new(
+                        key.clone(),
+                        *top,
+                        *unmatched,
+                        wants_bucket_sketch(metrics),
+                        sample.clone(),
+                    ),
+                    metrics: metrics.clone(),
+                },
+                Some(key.field()),
+            ),
             AggregateKind::Missing { field } => {
                 (AccumulatorKind::Missing { count: 0 }, Some(*field))
             }
@@ -306,26 +425,15 @@ impl GroupAccumulator {
                 Some(*field),
             ),
             AggregateKind::Rollup {
-                mode,
-                top,
-                metrics,
-                sub,
-                ..
-            } => {
-                let sub_accumulators = sub
-                    .as_ref()
-                    .map(|_| std::collections::HashMap::<u64, Self>::new());
-                let sub_kind = sub.as_deref().cloned();
-                (
-                    AccumulatorKind::Rollup {
-                        inner: super::rollup::RollupAccumulator::new(*mode, *top),
-                        metrics: metrics.clone(),
-                        sub_accumulators,
-                        sub_kind,
-                    },
-                    None,
-                )
-            }
+                mode, top, metrics, ..
+            } => (
+                AccumulatorKind::Rollup {
+                    inner: super::rollup::RollupAccumulator::new(*mode, *top)
+                        .tracking_percentiles(wants_bucket_sketch(metrics)),
+                    metrics: metrics.clone(),
+                },
+                None,
+            ),
             AggregateKind::Duplicates {
                 keys,
                 verify,
@@ -347,12 +455,48 @@ impl GroupAccumulator {
                     None,
                 )
             }
+            AggregateKind::Pivot {
+                rows,
+                cols,
+                metric,
+                time_zone,
+            } => (
+                AccumulatorKind::Pivot {
+                    inner: super::pivot::PivotAccumulator::new(
+                        rows.clone(),
+                        cols.clone(),
+                        *metric,
+                        time_zone.unwrap_or_default(),
+                    ),
+                },
+                None,
+            ),
+            AggregateKind::SimilarNames {
+                scope,
+                rank,
+                top,
+                sample,
+                max_groups,
+            } => (
+                AccumulatorKind::SimilarNames {
+                    inner: super::similar::SimilarNamesAccumulator::new(
+                        *scope,
+                        *rank,
+                        *top,
+                        sample.clone(),
+                        *max_groups,
+                    ),
+                },
+                None,
+            ),
         };
 
         Self {
             kind: acc_kind,
             field,
             label,
+            sub: None,
+            computed: None,
         }
     }
 
@@ -363,6 +507,9 @@ impl GroupAccumulator {
     /// * `idx`   — the record's index within the drive's `records` array.
     /// * `drive_ordinal` — the ordinal position of this drive in the drives
     ///   array, stored in sample heap entries for later materialization.
+    ///
+    /// Bucketed kinds pass the record on to the nested child accumulator of
+    /// the bucket it landed in.
     #[inline]
     pub(crate) fn feed(
         &mut self,
@@ -373,22 +520,29 @@ impl GroupAccumulator {
         ext_map: &super::ExtensionMap,
     ) {
         let field = self.field;
-        match &mut self.kind {
+        let computed = self.computed.as_ref();
+        let bucket = match &mut self.kind {
             AccumulatorKind::Count { count } => {
                 *count += 1;
+                None
             }
             AccumulatorKind::Stats { stats, .. } => {
-                let value = extract_value(field, record);
-                stats.feed_value(value, record.allocated);
+                if let Some(value) = measure_value(field, computed, record, &drive.names) {
+                    stats.feed_value(value, record.allocated);
+                }
+                None
             }
             AccumulatorKind::Terms {
                 groups,
+                metrics,
                 sample_heaps,
                 sample_spec,
                 ..
             } => {
                 let key = extract_group_key(field, record, drive, drive_ordinal, ext_map);
-                let stats = groups.entry(key).or_insert_with(StatsAccumulator::new);
+                let stats = groups
+                    .entry(key)
+                    .or_insert_with(|| StatsAccumulator::tracking(wants_bucket_sketch(metrics)));
                 stats.feed_value(record.size, record.allocated);
                 // Push into per-bucket sample heap if configured.
                 if let (Some(heaps), Some(spec)) = (sample_heaps.as_mut(), sample_spec.as_ref()) {
@@ -397,70 +551,77 @@ impl GroupAccumulator {
                         .or_insert_with(|| super::sample_heap::SampleHeap::from_spec(spec));
                     heap.push(record, uffs_mft::len_to_u32(idx), drive_ordinal);
                 }
+                Some(key)
+            }
+            AccumulatorKind::RegexTerms { inner, .. } => {
+                inner.feed(record, drive, idx, drive_ordinal)
             }
             AccumulatorKind::Histogram {
                 buckets,
                 boundaries,
-                ..
+                metrics,
             } => {
-                let value = extract_value(field, record);
-                let bucket_idx = boundaries.partition_point(|&boundary| boundary <= value);
-                // Grow buckets if needed.
-                while buckets.len() <= bucket_idx {
-                    buckets.push(StatsAccumulator::new());
-                }
-                if let Some(bucket) = buckets.get_mut(bucket_idx) {
-                    bucket.feed_value(record.size, record.allocated);
-                }
+                measure_value(field, computed, record, &drive.names).map(|value| {
+                    let bucket_idx = boundaries.partition_point(|&boundary| boundary <= value);
+                    // Grow buckets if needed.
+                    while buckets.len() <= bucket_idx {
+                        buckets.push(StatsAccumulator::tracking(wants_bucket_sketch(metrics)));
+                    }
+                    if let Some(bucket) = buckets.get_mut(bucket_idx) {
+                        bucket.feed_value(record.size, record.allocated);
+                    }
+                    uffs_mft::usize_to_u64(bucket_idx)
+                })
             }
             AccumulatorKind::DateHistogram {
-                buckets, calendar, ..
+                buckets,
+                calendar,
+                time_zone,
+                metrics,
             } => {
                 let ts = extract_timestamp(field, record);
-                let truncated = truncate_timestamp(ts, *calendar);
+                let truncated = truncate_timestamp(ts, *calendar, *time_zone);
                 let stats = buckets
                     .entry(truncated)
-                    .or_insert_with(StatsAccumulator::new);
+                    .or_insert_with(|| StatsAccumulator::tracking(wants_bucket_sketch(metrics)));
                 stats.feed_value(record.size, record.allocated);
+                Some(truncated.cast_unsigned())
             }
             AccumulatorKind::Missing { count } => {
                 if is_missing(field, record) {
                     *count += 1;
                 }
+                None
             }
             AccumulatorKind::Distinct { seen } => {
                 let key = extract_group_key(field, record, drive, drive_ordinal, ext_map);
                 seen.insert(key);
+                None
             }
-            AccumulatorKind::Rollup {
-                inner,
-                sub_accumulators,
-                sub_kind,
-                ..
-            } => {
-                // Compute group key and feed the top-level stats.
+            AccumulatorKind::Rollup { inner, .. } => {
                 // `feed` returns false when the record is out of scope
                 // (ancestor rollups skip records on other drives) — such
-                // records must not reach the sub-accumulators either.
-                let bucketed = inner.feed(record, drive, idx, drive_ordinal);
-
-                // If nested sub-aggregation is configured, feed the
-                // per-group sub-accumulator.
-                if bucketed
-                    && let (Some(sub_map), Some(sub_spec)) =
-                        (sub_accumulators.as_mut(), sub_kind.as_ref())
-                {
-                    let key = inner.last_key();
-                    let sub_acc = sub_map
-                        .entry(key)
-                        .or_insert_with(|| Self::from_kind(&sub_spec.kind, sub_spec.label.clone()));
-                    sub_acc.feed(record, drive, idx, drive_ordinal, ext_map);
-                }
+                // records must not reach the nested child either.
+                inner
+                    .feed(record, drive, idx, drive_ordinal)
+                    .then(|| inner.last_key())
             }
             AccumulatorKind::Duplicates { inner, .. } => {
                 inner.set_drive_ordinal(drive_ordinal);
                 inner.feed(record, drive, idx);
+                None
+            }
+            AccumulatorKind::Pivot { inner } => {
+                inner.feed(record, drive, drive_ordinal, ext_map);
+                None
             }
+            AccumulatorKind::SimilarNames { inner } => {
+                inner.feed(record, drive, idx, drive_ordinal);
+                None
+            }
+        };
+        if let (Some(key), Some(nested)) = (bucket, self.sub.as_mut()) {
+            nested.feed(key, record, drive, idx, drive_ordinal, ext_map);
         }
     }
 
@@ -474,6 +635,9 @@ impl GroupAccumulator {
         reason = "`a` (self) and `b` (other) are the conventional pair-destructure bindings in this merge function; renaming to verbose names would obscure the parallel structure of each match arm"
     )]
     pub fn merge(&mut self, other: &Self) {
+        if let (Some(ours), Some(theirs)) = (self.sub.as_mut(), other.sub.as_ref()) {
+            ours.merge(theirs);
+        }
         match (&mut self.kind, &other.kind) {
             (AccumulatorKind::Count { count: a }, AccumulatorKind::Count { count: b })
             | (AccumulatorKind::Missing { count: a }, AccumulatorKind::Missing { count: b }) => {
@@ -539,27 +703,10 @@ impl GroupAccumulator {
                 }
             }
             (
-                AccumulatorKind::Rollup {
-                    inner: a,
-                    sub_accumulators: sub_a,
-                    ..
-                },
-                AccumulatorKind::Rollup {
-                    inner: b,
-                    sub_accumulators: sub_b,
-                    ..
-                },
+                AccumulatorKind::Rollup { inner: a, .. },
+                AccumulatorKind::Rollup { inner: b, .. },
             ) => {
                 a.merge(b);
-                // Merge sub-accumulators if present.
-                if let (Some(map_a), Some(map_b)) = (sub_a.as_mut(), sub_b) {
-                    for (key, acc_b) in map_b {
-                        map_a
-                            .entry(*key)
-                            .and_modify(|acc_a| acc_a.merge(acc_b))
-                            .or_insert_with(|| acc_b.clone());
-                    }
-                }
             }
             (
                 AccumulatorKind::Duplicates { inner: a, .. },
@@ -572,151 +719,22 @@ impl GroupAccumulator {
                 // drops singletons.
                 a.merge(b);
             }
-            _ => {} // mismatched kinds — should not happen
-        }
-    }
-}
-
-/// Extract a numeric value from a record for stats/histogram.
-#[inline]
-fn extract_value(field: Option<FieldId>, record: &CompactRecord) -> u64 {
-    match field {
-        Some(FieldId::Size) => record.size,
-        Some(FieldId::SizeOnDisk) => record.allocated,
-        Some(FieldId::TreeSize) => record.treesize,
-        Some(FieldId::TreeAllocated) => record.tree_allocated,
-        Some(FieldId::Descendants) => u64::from(record.descendants),
-        Some(FieldId::NameLength) => u64::from(record.name_len),
-        Some(FieldId::PathLength) => u64::from(record.path_len),
-        Some(FieldId::Created) => uffs_mft::nonneg_to_u64(record.created),
-        Some(FieldId::Modified) => uffs_mft::nonneg_to_u64(record.modified),
-        Some(FieldId::Accessed) => uffs_mft::nonneg_to_u64(record.accessed),
-        _ => 0,
-    }
-}
-
-/// Extract a timestamp from a record.
-#[inline]
-const fn extract_timestamp(field: Option<FieldId>, record: &CompactRecord) -> i64 {
-    match field {
-        Some(FieldId::Created) => record.created,
-        Some(FieldId::Modified) => record.modified,
-        Some(FieldId::Accessed) => record.accessed,
-        _ => 0,
-    }
-}
-
-/// Extract a group key (encoded as u64) from a record.
-///
-/// For `Extension`, uses the `ExtensionMap` to return a canonical
-/// cross-drive extension ID.  This ensures that `"exe"` on drive C and
-/// `"exe"` on drive D share the same group key.  The map is required:
-/// raw per-drive `extension_id`s must never enter a cross-drive merge
-/// key (the same id means different extensions on different drives).
-#[inline]
-fn extract_group_key(
-    field: Option<FieldId>,
-    record: &CompactRecord,
-    drive: &DriveCompactIndex,
-    drive_ordinal: u8,
-    ext_map: &super::ExtensionMap,
-) -> u64 {
-    match field {
-        Some(FieldId::Extension) => ext_map.canonical_id(drive_ordinal, record.extension_id),
-        Some(FieldId::Drive) => u64::from(u32::from(drive.letter.as_byte())),
-        Some(FieldId::Type) => {
-            use crate::search::derived::{
-                SEMANTIC_TYPE_ID_DIRECTORY, SEMANTIC_TYPE_ID_FILE, semantic_type_id_from_extension,
-            };
-            if record.flags & 0x0010 != 0 {
-                return SEMANTIC_TYPE_ID_DIRECTORY;
+            (
+                AccumulatorKind::RegexTerms { inner: a, .. },
+                AccumulatorKind::RegexTerms { inner: b, .. },
+            ) => {
+                a.merge(b);
             }
-            let name = record.name(&drive.names);
-            let ext = name.rsplit('.').next().unwrap_or("");
-            if ext.len() == name.len() {
-                return SEMANTIC_TYPE_ID_FILE;
+            (AccumulatorKind::Pivot { inner: a }, AccumulatorKind::Pivot { inner: b }) => {
+                a.merge(b);
             }
-            // Stack-based lowercase for short extensions (covers 99%+).
-            let mut buf = [0_u8; 16];
-            let ext_len = ext.len();
-            let ext_lower = buf.get_mut(..ext_len).map_or(ext, |slot| {
-                for (dst, src) in slot.iter_mut().zip(ext.bytes()) {
-                    *dst = src.to_ascii_lowercase();
-                }
-                core::str::from_utf8(slot).unwrap_or(ext)
-            });
-            semantic_type_id_from_extension(ext_lower)
-        }
-        Some(FieldId::DirectoryFlag) => u64::from(record.flags & 0x0010 != 0),
-        Some(FieldId::Hidden) => u64::from(record.flags & 0x0002 != 0),
-        Some(FieldId::System) => u64::from(record.flags & 0x0004 != 0),
-        Some(FieldId::ReadOnly) => u64::from(record.flags & 0x0001 != 0),
-        Some(FieldId::Compressed) => u64::from(record.flags & 0x0800 != 0),
-        Some(FieldId::Encrypted) => u64::from(record.flags & 0x4000 != 0),
-        Some(FieldId::Archive) => u64::from(record.flags & 0x0020 != 0),
-        Some(FieldId::Sparse) => u64::from(record.flags & 0x0200 != 0),
-        Some(FieldId::Reparse) => u64::from(record.flags & 0x0400 != 0),
-        Some(FieldId::Temporary) => u64::from(record.flags & 0x0100 != 0),
-        Some(FieldId::Offline) => u64::from(record.flags & 0x1000 != 0),
-        Some(FieldId::NotIndexed) => u64::from(record.flags & 0x2000 != 0),
-        Some(FieldId::Virtual) => u64::from(record.flags & 0x1_0000 != 0),
-        Some(FieldId::Integrity) => u64::from(record.flags & 0x8000 != 0),
-        Some(FieldId::NoScrub) => u64::from(record.flags & 0x2_0000 != 0),
-        Some(FieldId::Pinned) => u64::from(record.flags & 0x8_0000 != 0),
-        Some(FieldId::Unpinned) => u64::from(record.flags & 0x10_0000 != 0),
-        Some(FieldId::RecallOnOpen) => u64::from(record.flags & 0x4_0000 != 0),
-        Some(FieldId::RecallOnDataAccess) => u64::from(record.flags & 0x40_0000 != 0),
-        _ => 0,
-    }
-}
-
-/// Check if a field has a "missing" value for this record.
-#[inline]
-const fn is_missing(field: Option<FieldId>, record: &CompactRecord) -> bool {
-    match field {
-        Some(FieldId::Extension) => record.extension_id == 0,
-        Some(FieldId::Size) => record.size == 0,
-        Some(FieldId::SizeOnDisk) => record.allocated == 0,
-        Some(FieldId::Created) => record.created == 0,
-        Some(FieldId::Modified) => record.modified == 0,
-        Some(FieldId::Accessed) => record.accessed == 0,
-        _ => false,
-    }
-}
-
-/// Truncate a raw FILETIME (100-ns ticks since 1601) to a calendar interval
-/// boundary.
-///
-/// Returns a FILETIME value aligned to the start of the given interval.
-const fn truncate_timestamp(filetime: i64, calendar: super::spec::CalendarInterval) -> i64 {
-    use uffs_time::FILETIME_TICKS_PER_SECOND;
-
-    use super::spec::CalendarInterval;
-
-    let ticks_per_hour: i64 = FILETIME_TICKS_PER_SECOND * 3600;
-    let ticks_per_day: i64 = FILETIME_TICKS_PER_SECOND * 86400;
-
-    match calendar {
-        CalendarInterval::Hour => (filetime / ticks_per_hour) * ticks_per_hour,
-        CalendarInterval::Day => (filetime / ticks_per_day) * ticks_per_day,
-        CalendarInterval::Week => {
-            // FILETIME epoch 1601-01-01 was a Monday — convenient!
-            let days = filetime / ticks_per_day;
-            let day_of_week = days % 7; // Mon=0 (since 1601-01-01 = Monday)
-            (days - day_of_week) * ticks_per_day
-        }
-        CalendarInterval::Month => {
-            // Approximate: 30-day months.
-            let ticks_per_30d = ticks_per_day * 30;
-            (filetime / ticks_per_30d) * ticks_per_30d
-        }
-        CalendarInterval::Quarter => {
-            let ticks_per_90d = ticks_per_day * 90;
-            (filetime / ticks_per_90d) * ticks_per_90d
-        }
-        CalendarInterval::Year => {
-            let ticks_per_365d = ticks_per_day * 365;
-            (filetime / ticks_per_365d) * ticks_per_365d
+            (
+                AccumulatorKind::SimilarNames { inner: a },
+                AccumulatorKind::SimilarNames { inner: b },
+            ) => {
+                a.merge(b);
+            }
+            _ => {} // mismatched kinds — should not happen
         }
     }
 }
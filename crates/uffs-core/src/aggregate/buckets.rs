@@ -39,7 +39,7 @@ pub enum SizeBucket {
 pub(crate) const SIZE_BUCKET_COUNT: usize = 7;
 
 /// Size bucket boundary upper limits (exclusive).
-const SIZE_BOUNDARIES: [u64; 6] = [
+pub(super) const SIZE_BOUNDARIES: [u64; 6] = [
     1_024,          // < 1 KB
     102_400,        // < 100 KB
     1_048_576,      // < 1 MB
@@ -239,6 +239,23 @@ impl PathRiskBucket {
         [Self::Safe, Self::Long, Self::Warning, Self::Critical];
 }
 
+/// Format a range bucket key.
+pub(super) fn format_range_key(index: usize, boundaries: &[u64]) -> String {
+    let Some((first, last)) = boundaries.first().zip(boundaries.last()) else {
+        return format!("bucket_{index}");
+    };
+    if index == 0 {
+        format!("< {first}")
+    } else if index >= boundaries.len() {
+        format!(">= {last}")
+    } else {
+        match (boundaries.get(index - 1), boundaries.get(index)) {
+            (Some(lo), Some(hi)) => format!("{lo} - {hi}"),
+            _ => format!("bucket_{index}"),
+        }
+    }
+}
+
 #[cfg(test)]
 #[expect(
     clippy::indexing_slicing,
@@ -5,9 +5,15 @@
 //!
 //! Converts finalized aggregate results into tabular export formats.
 
+use core::fmt::Write as _;
 use std::io::Write;
 
-use super::finalize::{AggregateResponse, AggregateResultData, BucketRow};
+use serde_json::{Value, json};
+
+use super::finalize::{AggregateResponse, AggregateResultData, BucketRow, StatsResult};
+use super::percentile::PercentileRank;
+use super::pivot::{PivotResult, metric_name};
+use super::spec::BucketMetric;
 
 /// Export format for aggregate results.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,7 +22,7 @@ pub enum ExportFormat {
     Csv,
     /// Tab-separated values.
     Tsv,
-    /// JSON (one array per result).
+    /// JSON (an array with one object per result).
     Json,
 }
 
@@ -72,18 +78,20 @@ fn export_csv<W: Write>(
                 writeln!(writer, "# {label}")?;
                 writeln!(
                     writer,
-                    "field{sep_char}count{sep_char}sum{sep_char}min{sep_char}max{sep_char}avg{sep_char}waste_bytes{sep_char}waste_pct"
+                    "field{sep_char}count{sep_char}sum{sep_char}min{sep_char}max{sep_char}avg{sep_char}waste_bytes{sep_char}waste_pct{}",
+                    percentile_header(&stats.percentiles, sep_char)
                 )?;
                 writeln!(
                     writer,
-                    "{field}{sep_char}{}{sep_char}{}{sep_char}{}{sep_char}{}{sep_char}{:.2}{sep_char}{}{sep_char}{:.2}",
+                    "{field}{sep_char}{}{sep_char}{}{sep_char}{}{sep_char}{}{sep_char}{:.2}{sep_char}{}{sep_char}{:.2}{}",
                     stats.count,
                     stats.sum,
                     stats.min,
                     stats.max,
                     stats.avg,
                     stats.waste_bytes,
-                    stats.waste_pct
+                    stats.waste_pct,
+                    percentile_cells(&stats.percentiles, sep_char)
                 )?;
             }
 
@@ -124,6 +132,11 @@ fn export_csv<W: Write>(
                     )?;
                 }
             }
+
+            AggregateResultData::Pivot { result: pivot } => {
+                writeln!(writer, "# {label}")?;
+                write_pivot_csv(writer, pivot, sep_char)?;
+            }
         }
         writeln!(writer)?; // Blank line between results
     }
@@ -137,14 +150,20 @@ fn write_bucket_csv<W: Write>(
     rows: &[BucketRow],
     sep: char,
 ) -> std::io::Result<()> {
+    // Every non-empty row carries the same requested percentiles.
+    let ranked = rows
+        .iter()
+        .find(|row| !row.size_percentiles.is_empty())
+        .map_or(&[][..], |row| row.size_percentiles.as_slice());
     writeln!(
         writer,
-        "key{sep}count{sep}total_bytes{sep}total_allocated{sep}avg_size{sep}waste_bytes{sep}waste_pct{sep}share_count{sep}share_bytes"
+        "key{sep}count{sep}total_bytes{sep}total_allocated{sep}avg_size{sep}waste_bytes{sep}waste_pct{sep}share_count{sep}share_bytes{}",
+        percentile_header(ranked, sep)
     )?;
     for row in rows {
         writeln!(
             writer,
-            "{}{sep}{}{sep}{}{sep}{}{sep}{:.2}{sep}{}{sep}{:.2}{sep}{:.2}{sep}{:.2}",
+            "{}{sep}{}{sep}{}{sep}{}{sep}{:.2}{sep}{}{sep}{:.2}{sep}{:.2}{sep}{:.2}{}",
             row.key,
             row.count,
             row.total_bytes,
@@ -153,18 +172,221 @@ fn write_bucket_csv<W: Write>(
             row.waste_bytes,
             row.waste_pct,
             row.share_of_total_count,
-            row.share_of_total_bytes
+            row.share_of_total_bytes,
+            percentile_cells_for(ranked, &row.size_percentiles, sep)
         )?;
     }
     Ok(())
 }
 
-/// Write as JSON.
-fn export_json<W: Write>(_response: &AggregateResponse, writer: &mut W) -> std::io::Result<()> {
-    // JSON export is handled by serde at the protocol layer.
-    // This is a lightweight fallback for non-daemon usage.
+/// Extra header columns for `percentiles` (`,p50,p99`).
+fn percentile_header(percentiles: &[(PercentileRank, u64)], sep: char) -> String {
+    percentiles
+        .iter()
+        .fold(String::new(), |mut out, (rank, _)| {
+            _ = write!(out, "{sep}{}", rank.label());
+            out
+        })
+}
+
+/// Extra value columns matching [`percentile_header`].
+fn percentile_cells(percentiles: &[(PercentileRank, u64)], sep: char) -> String {
+    percentiles
+        .iter()
+        .fold(String::new(), |mut out, (_, value)| {
+            _ = write!(out, "{sep}{value}");
+            out
+        })
+}
+
+/// Value columns for the header's `ranked` percentiles; a row without an
+/// estimate (an empty bucket) leaves its cells blank.
+fn percentile_cells_for(
+    ranked: &[(PercentileRank, u64)],
+    percentiles: &[(PercentileRank, u64)],
+    sep: char,
+) -> String {
+    ranked
+        .iter()
+        .map(|(rank, _)| {
+            percentiles
+                .iter()
+                .find(|(own, _)| own == rank)
+                .map_or_else(|| sep.to_string(), |(_, value)| format!("{sep}{value}"))
+        })
+        .collect()
+}
+
+/// Write a pivot as a CSV/TSV matrix: one line per row with a trailing
+/// row total, then a `total` line with the column totals. Empty cells
+/// stay blank.
+fn write_pivot_csv<W: Write>(
+    writer: &mut W,
+    pivot: &PivotResult,
+    sep: char,
+) -> std::io::Result<()> {
+    let precision = pivot_precision(pivot.metric);
+    let cell = |value: f64| format!("{value:.precision$}");
+    let header = pivot.columns.iter().fold(String::new(), |mut out, column| {
+        _ = write!(out, "{sep}{column}");
+        out
+    });
     writeln!(
         writer,
-        "{{\"note\": \"Use --format json on the CLI for full JSON output\"}}"
-    )
+        "{}\\{}{header}{sep}total",
+        pivot.row_field, pivot.col_field
+    )?;
+    for row in &pivot.rows {
+        let cells = row.values.iter().fold(String::new(), |mut out, value| {
+            _ = write!(out, "{sep}{}", value.map(cell).unwrap_or_default());
+            out
+        });
+        writeln!(writer, "{}{cells}{sep}{}", row.key, cell(row.total))?;
+    }
+    let totals = pivot
+        .column_totals
+        .iter()
+        .fold(String::new(), |mut out, &value| {
+            _ = write!(out, "{sep}{}", cell(value));
+            out
+        });
+    writeln!(writer, "total{totals}{sep}{}", cell(pivot.grand_total))
+}
+
+/// Decimal places for a pivot metric: whole numbers for counts and
+/// byte totals, two places for averages and percentages.
+const fn pivot_precision(metric: BucketMetric) -> usize {
+    match metric {
+        BucketMetric::WastePct
+        | BucketMetric::AvgSize
+        | BucketMetric::ShareOfTotalCount
+        | BucketMetric::ShareOfTotalBytes => 2,
+        BucketMetric::Count
+        | BucketMetric::TotalBytes
+        | BucketMetric::TotalAllocated
+        | BucketMetric::WasteBytes
+        | BucketMetric::MinSize
+        | BucketMetric::MaxSize
+        | BucketMetric::SizePercentile(_) => 0,
+    }
+}
+
+/// Write as a pretty-printed JSON array with one object per result.
+fn export_json<W: Write>(response: &AggregateResponse, writer: &mut W) -> std::io::Result<()> {
+    let results: Vec<Value> = response
+        .results
+        .iter()
+        .map(|result| {
+            let mut object = result_json(&result.data);
+            if let (Some(label), Some(map)) = (&result.label, object.as_object_mut()) {
+                map.insert("label".to_owned(), json!(label));
+            }
+            object
+        })
+        .collect();
+    serde_json::to_writer_pretty(&mut *writer, &results)?;
+    writeln!(writer)
+}
+
+/// JSON object for one result payload.
+fn result_json(data: &AggregateResultData) -> Value {
+    match data {
+        AggregateResultData::Count { value } => json!({ "kind": "count", "value": value }),
+        AggregateResultData::Stats { field, stats } => stats_json(field, stats),
+        AggregateResultData::Buckets {
+            field,
+            rows,
+            other_count,
+            total_groups,
+            ..
+        } => json!({
+            "kind": "buckets",
+            "field": field,
+            "rows": rows.iter().map(bucket_json).collect::<Vec<_>>(),
+            "other_count": other_count,
+            "total_groups": total_groups,
+        }),
+        AggregateResultData::Rollup { mode, rows } => json!({
+            "kind": "rollup",
+            "mode": mode,
+            "rows": rows.iter().map(bucket_json).collect::<Vec<_>>(),
+        }),
+        AggregateResultData::Missing { field, count } => {
+            json!({ "kind": "missing", "field": field, "count": count })
+        }
+        AggregateResultData::Distinct { field, count } => {
+            json!({ "kind": "distinct", "field": field, "count": count })
+        }
+        AggregateResultData::Duplicates { result } => json!({
+            "kind": "duplicates",
+            "groups": result
+                .groups
+                .iter()
+                .map(|group| json!({
+                    "count": group.count,
+                    "file_size": group.file_size,
+                    "total_bytes": group.total_bytes,
+                    "reclaimable": group.reclaimable_bytes,
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        AggregateResultData::Pivot { result } => json!({
+            "kind": "pivot",
+            "row_field": result.row_field,
+            "col_field": result.col_field,
+            "metric": metric_name(result.metric),
+            "columns": result.columns,
+            "rows": result
+                .rows
+                .iter()
+                .map(|row| json!({ "key": row.key, "values": row.values, "total": row.total }))
+                .collect::<Vec<_>>(),
+            "column_totals": result.column_totals,
+            "grand_total": result.grand_total,
+            "total_rows": result.total_rows,
+            "total_cols": result.total_cols,
+        }),
+    }
+}
+
+/// JSON object for a stats result.
+fn stats_json(field: &str, stats: &StatsResult) -> Value {
+    json!({
+        "kind": "stats",
+        "field": field,
+        "count": stats.count,
+        "sum": stats.sum,
+        "min": stats.min,
+        "max": stats.max,
+        "avg": stats.avg,
+        "waste_bytes": stats.waste_bytes,
+        "waste_pct": stats.waste_pct,
+        "percentiles": percentiles_json(&stats.percentiles),
+    })
+}
+
+/// JSON object for a bucket row, nested rows included.
+fn bucket_json(row: &BucketRow) -> Value {
+    json!({
+        "key": row.key,
+        "count": row.count,
+        "total_bytes": row.total_bytes,
+        "total_allocated": row.total_allocated,
+        "avg_size": row.avg_size,
+        "waste_bytes": row.waste_bytes,
+        "waste_pct": row.waste_pct,
+        "share_count": row.share_of_total_count,
+        "share_bytes": row.share_of_total_bytes,
+        "percentiles": percentiles_json(&row.size_percentiles),
+        "sub_buckets": row.sub_buckets.iter().map(bucket_json).collect::<Vec<_>>(),
+    })
+}
+
+/// `{"p50": 4096, "p99": 900000}`.
+fn percentiles_json(percentiles: &[(PercentileRank, u64)]) -> Value {
+    percentiles
+        .iter()
+        .map(|(rank, value)| (rank.label(), json!(value)))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
 }
@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Per-record field extraction shared by the accumulators.
+//!
+//! Turns a [`CompactRecord`] into the numeric value, timestamp or group
+//! key an aggregation buckets on. Group keys must be comparable across
+//! drives, since per-drive accumulators are merged after the scan.
+
+use alloc::sync::Arc;
+
+use uffs_mft::platform::{VolumeId, VolumeSerial};
+
+use crate::compact::{CompactRecord, DriveCompactIndex};
+use crate::search::computed::ComputedField;
+use crate::search::field::FieldId;
+
+/// Extract a numeric value from a record for stats/histogram.
+#[inline]
+pub(super) fn extract_value(field: Option<FieldId>, record: &CompactRecord) -> u64 {
+    match field {
+        Some(FieldId::Size) => record.size,
+        Some(FieldId::SizeOnDisk) => record.allocated,
+        Some(FieldId::TreeSize) => record.treesize,
+        Some(FieldId::TreeAllocated) => record.tree_allocated,
+        Some(FieldId::Descendants) => u64::from(record.descendants),
+        Some(FieldId::NameLength) => u64::from(record.name_len),
+        Some(FieldId::PathLength) => u64::from(record.path_len),
+        Some(FieldId::Created) => uffs_mft::nonneg_to_u64(record.created),
+        Some(FieldId::Modified) => uffs_mft::nonneg_to_u64(record.modified),
+        Some(FieldId::Accessed) => uffs_mft::nonneg_to_u64(record.accessed),
+        _ => 0,
+    }
+}
+
+/// Numeric value a stats/histogram/range accumulator measures: the bound
+/// computed field (evaluated at its captured `now`, negatives clamped to
+/// `0`) when there is one, else `field`.  `None` skips the record.
+#[inline]
+pub(super) fn measure_value(
+    field: Option<FieldId>,
+    computed: Option<&(Arc<ComputedField>, i64)>,
+    record: &CompactRecord,
+    names: &[u8],
+) -> Option<u64> {
+    match computed {
+        Some((expr, now)) => expr
+            .eval_record(record, names, *now)
+            .map(uffs_mft::nonneg_to_u64),
+        None => Some(extract_value(field, record)),
+    }
+}
+
+/// Extract a timestamp from a record.
+#[inline]
+pub(super) const fn extract_timestamp(field: Option<FieldId>, record: &CompactRecord) -> i64 {
+    match field {
+        Some(FieldId::Created) => record.created,
+        Some(FieldId::Modified) => record.modified,
+        Some(FieldId::Accessed) => record.accessed,
+        _ => 0,
+    }
+}
+
+/// FNV-1a over `parts`, with bit 63 set so the key never collides with a
+/// local volume's letter key or the local host key `0`.
+fn stable_key(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for &byte in parts.iter().copied().flatten() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01B3);
+    }
+    hash | (1 << 63_u32)
+}
+
+/// Group key for a volume's host: `0` for local volumes, otherwise a hash
+/// of the label.  Stable across daemon runs, so bucket order — and the
+/// aggregation cursors that page through it — does not change.
+#[inline]
+pub(super) fn host_group_key(host: Option<uffs_mft::platform::HostLabel>) -> u64 {
+    host.map_or(0, |label| stable_key(&[label.as_str().as_bytes()]))
+}
+
+/// Inverse of [`host_group_key`]: the label of the host among `drives`
+/// whose key is `key`, or `""` for local volumes (key `0`).
+pub(super) fn host_label_for_key(drives: &[&DriveCompactIndex], key: u64) -> String {
+    drives
+        .iter()
+        .filter_map(|drive| drive.host)
+        .find(|&host| host_group_key(Some(host)) == key)
+        .map_or_else(String::new, |host| host.as_str().to_owned())
+}
+
+/// Group key for a volume: the letter's byte for a bare letter, otherwise
+/// a stable hash of host, letter and serial, so `srv01\C` and `srv02\C`
+/// land in different buckets.
+#[inline]
+pub(super) fn volume_group_key(volume: VolumeId) -> u64 {
+    let letter = volume.letter().as_byte();
+    if volume.host().is_none() && volume.serial().is_none() {
+        return u64::from(letter);
+    }
+    let host = volume.host().map_or("", |host| host.as_str());
+    let serial = volume.serial().map_or(0, VolumeSerial::get).to_le_bytes();
+    stable_key(&[host.as_bytes(), &[b'\\', letter], &serial])
+}
+
+/// Inverse of [`volume_group_key`]: the [drive label] of the volume among
+/// `drives` whose key is `key`.
+///
+/// [drive label]: VolumeId::drive_label
+pub(super) fn volume_label_for_key(drives: &[&DriveCompactIndex], key: u64) -> String {
+    drives
+        .iter()
+        .map(|drive| drive.volume())
+        .find(|&volume| volume_group_key(volume) == key)
+        .map_or_else(|| "?:".to_owned(), VolumeId::drive_label)
+}
+
+/// Extract a group key (encoded as u64) from a record.
+///
+/// For `Extension`, uses the `ExtensionMap` to return a canonical
+/// cross-drive extension ID.  This ensures that `"exe"` on drive C and
+/// `"exe"` on drive D share the same group key.  The map is required:
+/// raw per-drive `extension_id`s must never enter a cross-drive merge
+/// key (the same id means different extensions on different drives).
+#[inline]
+pub(super) fn extract_group_key(
+    field: Option<FieldId>,
+    record: &CompactRecord,
+    drive: &DriveCompactIndex,
+    drive_ordinal: u8,
+    ext_map: &super::ExtensionMap,
+) -> u64 {
+    match field {
+        Some(FieldId::Extension) => ext_map.canonical_id(drive_ordinal, record.extension_id),
+        Some(FieldId::Drive) => volume_group_key(drive.volume()),
+        Some(FieldId::Host) => host_group_key(drive.host),
+        Some(FieldId::Type) => {
+            use crate::search::derived::{
+                SEMANTIC_TYPE_ID_DIRECTORY, SEMANTIC_TYPE_ID_FILE, semantic_type_id_from_extension,
+            };
+            if record.flags & 0x0010 != 0 {
+                return SEMANTIC_TYPE_ID_DIRECTORY;
+            }
+            let name = record.name(&drive.names);
+            let ext = name.rsplit('.').next().unwrap_or("");
+            if ext.len() == name.len() {
+                return SEMANTIC_TYPE_ID_FILE;
+            }
+            // Stack-based lowercase for short extensions (covers 99%+).
+            let mut buf = [0_u8; 16];
+            let ext_len = ext.len();
+            let ext_lower = buf.get_mut(..ext_len).map_or(ext, |slot| {
+                for (dst, src) in slot.iter_mut().zip(ext.bytes()) {
+                    *dst = src.to_ascii_lowercase();
+                }
+                core::str::from_utf8(slot).unwrap_or(ext)
+            });
+            semantic_type_id_from_extension(ext_lower)
+        }
+        Some(FieldId::DirectoryFlag) => u64::from(record.flags & 0x0010 != 0),
+        Some(FieldId::Hidden) => u64::from(record.flags & 0x0002 != 0),
+        Some(FieldId::System) => u64::from(record.flags & 0x0004 != 0),
+        Some(FieldId::ReadOnly) => u64::from(record.flags & 0x0001 != 0),
+        Some(FieldId::Compressed) => u64::from(record.flags & 0x0800 != 0),
+        Some(FieldId::Encrypted) => u64::from(record.flags & 0x4000 != 0),
+        Some(FieldId::Archive) => u64::from(record.flags & 0x0020 != 0),
+        Some(FieldId::Sparse) => u64::from(record.flags & 0x0200 != 0),
+        Some(FieldId::Reparse) => u64::from(record.flags & 0x0400 != 0),
+        Some(FieldId::Temporary) => u64::from(record.flags & 0x0100 != 0),
+        Some(FieldId::Offline) => u64::from(record.flags & 0x1000 != 0),
+        Some(FieldId::NotIndexed) => u64::from(record.flags & 0x2000 != 0),
+        Some(FieldId::Virtual) => u64::from(record.flags & 0x1_0000 != 0),
+        Some(FieldId::Integrity) => u64::from(record.flags & 0x8000 != 0),
+        Some(FieldId::NoScrub) => u64::from(record.flags & 0x2_0000 != 0),
+        Some(FieldId::Pinned) => u64::from(record.flags & 0x8_0000 != 0),
+        Some(FieldId::Unpinned) => u64::from(record.flags & 0x10_0000 != 0),
+        Some(FieldId::RecallOnOpen) => u64::from(record.flags & 0x4_0000 != 0),
+        Some(FieldId::RecallOnDataAccess) => u64::from(record.flags & 0x40_0000 != 0),
+        _ => 0,
+    }
+}
+
+/// Check if a field has a "missing" value for this record.
+#[inline]
+pub(super) const fn is_missing(field: Option<FieldId>, record: &CompactRecord) -> bool {
+    match field {
+        Some(FieldId::Extension) => record.extension_id == 0,
+        Some(FieldId::Size) => record.size == 0,
+        Some(FieldId::SizeOnDisk) => record.allocated == 0,
+        Some(FieldId::Created) => record.created == 0,
+        Some(FieldId::Modified) => record.modified == 0,
+        Some(FieldId::Accessed) => record.accessed == 0,
+        _ => false,
+    }
+}
+
+/// Bucket key of a raw FILETIME (100-ns ticks since 1601) for a calendar
+/// interval, in `time_zone` (DST applied per timestamp for a named zone).
+///
+/// Linear intervals return the local FILETIME aligned to the start of the
+/// interval; cyclic ones return the position in the cycle — hour `0..24`,
+/// or weekday `0..7` from Monday.
+pub(super) fn truncate_timestamp(
+    filetime: i64,
+    calendar: super::spec::CalendarInterval,
+    time_zone: uffs_time::TimeZone,
+) -> i64 {
+    use uffs_time::FILETIME_TICKS_PER_SECOND;
+
+    use super::spec::CalendarInterval;
+
+    let ticks_per_hour: i64 = FILETIME_TICKS_PER_SECOND * 3600;
+    let ticks_per_day: i64 = FILETIME_TICKS_PER_SECOND * 86400;
+    let local = time_zone.to_local(filetime);
+
+    match calendar {
+        CalendarInterval::Hour => (local / ticks_per_hour) * ticks_per_hour,
+        CalendarInterval::Day => (local / ticks_per_day) * ticks_per_day,
+        CalendarInterval::Week => {
+            // FILETIME epoch 1601-01-01 was a Monday — convenient!
+            let days = local / ticks_per_day;
+            let day_of_week = days % 7; // Mon=0 (since 1601-01-01 = Monday)
+            (days - day_of_week) * ticks_per_day
+        }
+        CalendarInterval::Month => {
+            // Approximate: 30-day months.
+            let ticks_per_30d = ticks_per_day * 30;
+            (local / ticks_per_30d) * ticks_per_30d
+        }
+        CalendarInterval::Quarter => {
+            let ticks_per_90d = ticks_per_day * 90;
+            (local / ticks_per_90d) * ticks_per_90d
+        }
+        CalendarInterval::Year => {
+            let ticks_per_365d = ticks_per_day * 365;
+            (local / ticks_per_365d) * ticks_per_365d
+        }
+        CalendarInterval::HourOfDay => local.div_euclid(ticks_per_hour).rem_euclid(24),
+        CalendarInterval::DayOfWeek => local.div_euclid(ticks_per_day).rem_euclid(7),
+    }
+}
@@ -8,7 +8,10 @@
 //! response objects.
 
 use super::accumulators::{AccumulatorKind, GroupAccumulator, StatsAccumulator};
+use super::nested::Nester;
+use super::percentile::{PercentileRank, bucket_ranks, scalar_ranks};
 use super::planner::AggregatePlan;
+use super::sample_heap::materialize_sample_entry;
 use crate::compact::DriveCompactIndex;
 
 /// Options controlling finalization behavior.
@@ -104,6 +107,11 @@ pub enum AggregateResultData {
         /// Full duplicate result data.
         result: super::duplicates::DuplicateResult,
     },
+    /// Two-axis pivot (cross-tab) result.
+    Pivot {
+        /// The pivot matrix.
+        result: super::pivot::PivotResult,
+    },
 }
 
 /// Scalar statistics result.
@@ -123,6 +131,8 @@ pub struct StatsResult {
     pub waste_bytes: u64,
     /// Waste percentage.
     pub waste_pct: f64,
+    /// Requested percentiles of the field, in request order.
+    pub percentiles: Vec<(PercentileRank, u64)>,
 }
 
 /// A single row in a bucket result.
@@ -150,6 +160,8 @@ pub struct BucketRow {
     pub share_of_total_count: f64,
     /// Share of total bytes (percentage, 0.0–100.0).
     pub share_of_total_bytes: f64,
+    /// Requested file-size percentiles, in request order.
+    pub size_percentiles: Vec<(PercentileRank, u64)>,
     /// Optional sample rows (top-N records in this bucket).
     pub sample_rows: Vec<SampleRow>,
     /// Drill-down predicates: the original query predicates **plus**
@@ -159,10 +171,11 @@ pub struct BucketRow {
     /// A client can re-issue a row-level search using these predicates
     /// to retrieve the actual records behind the bucket.
     pub drilldown: Vec<DrilldownPredicate>,
-    /// Nested sub-aggregation bucket rows (populated by nested rollups).
+    /// Nested sub-aggregation bucket rows.
     ///
-    /// When a rollup spec has `sub`, each top-level bucket finalizes
-    /// its per-group sub-accumulator and stores the result here.
+    /// When the spec has a `sub`, each bucket finalizes its per-bucket
+    /// child and stores the rows here, recursively for deeper levels.
+    /// Child shares are relative to this row, not to the whole result.
     pub sub_buckets: Vec<Self>,
 }
 
@@ -210,27 +223,33 @@ pub struct SampleRow {
     pub sort_key: i64,
 }
 
+/// What every bucket row of one result is measured against.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct RowContext<'ranks> {
+    /// Share-of-total count denominator.
+    total_matched: u64,
+    /// Share-of-total bytes denominator.
+    total_bytes: u64,
+    /// Requested size percentiles.
+    percentiles: &'ranks [PercentileRank],
+}
+
 impl BucketRow {
     /// Create a bucket row from a stats accumulator and context.
     #[expect(
         clippy::float_arithmetic,
         reason = "integer count/byte→f64 share-of-total percentages are the documented bucket-row formula"
     )]
-    fn from_stats(
-        key: String,
-        stats: &StatsAccumulator,
-        total_matched: u64,
-        total_bytes: u64,
-    ) -> Self {
-        let share_count = if total_matched == 0 {
+    pub(super) fn from_stats(key: String, stats: &StatsAccumulator, ctx: RowContext<'_>) -> Self {
+        let share_count = if ctx.total_matched == 0 {
             0.0_f64
         } else {
-            uffs_mft::u64_to_f64(stats.count) / uffs_mft::u64_to_f64(total_matched) * 100.0_f64
+            uffs_mft::u64_to_f64(stats.count) / uffs_mft::u64_to_f64(ctx.total_matched) * 100.0_f64
         };
-        let share_bytes = if total_bytes == 0 {
+        let share_bytes = if ctx.total_bytes == 0 {
             0.0_f64
         } else {
-            uffs_mft::u64_to_f64(stats.sum) / uffs_mft::u64_to_f64(total_bytes) * 100.0_f64
+            uffs_mft::u64_to_f64(stats.sum) / uffs_mft::u64_to_f64(ctx.total_bytes) * 100.0_f64
         };
         Self {
             key,
@@ -244,6 +263,7 @@ impl BucketRow {
             waste_pct: stats.waste_pct(),
             share_of_total_count: share_count,
             share_of_total_bytes: share_bytes,
+            size_percentiles: percentiles_of(stats, ctx.percentiles),
             sample_rows: Vec::new(),
             drilldown: Vec::new(),
             sub_buckets: Vec::new(),
@@ -251,6 +271,17 @@ impl BucketRow {
     }
 }
 
+/// The `ranks` percentiles `stats` can estimate, paired with their values.
+fn percentiles_of(
+    stats: &StatsAccumulator,
+    ranks: &[PercentileRank],
+) -> Vec<(PercentileRank, u64)> {
+    ranks
+        .iter()
+        .filter_map(|&rank| stats.percentile(rank).map(|value| (rank, value)))
+        .collect()
+}
+
 /// Finalize accumulated results into a response.
 /// Finalize aggregate results using the cross-drive
 /// [`crate::aggregate::ExtensionMap`] for correct extension key resolution.
@@ -293,46 +324,12 @@ fn compute_global_bytes(accumulators: &[GroupAccumulator]) -> u64 {
     0
 }
 
-/// Finalize a standalone accumulator (used by nested rollups).
-///
-/// Wraps `finalize_one` with default options.
-fn finalize_accumulator(
-    acc: GroupAccumulator,
-    total_matched: u64,
-    total_bytes: u64,
-    drives: &[&DriveCompactIndex],
-    ext_map: &super::ExtensionMap,
-    _predicates: &[DrilldownPredicate],
-) -> AggregateResult {
-    finalize_one(
-        acc,
-        total_matched,
-        total_bytes,
-        &FinalizeOptions::default(),
-        drives,
-        ext_map,
-    )
-}
-
-/// Convert a sub-aggregation result into drilldown bucket rows.
-///
-/// Extracts `BucketRow`s from bucket-like result variants; non-bucket
-/// results (count, stats, missing, distinct) produce an empty vec.
-#[expect(
-    clippy::wildcard_enum_match_arm,
-    reason = "non-bucket result variants intentionally collapse to empty rows"
-)]
-fn sub_result_to_bucket_rows(result: &AggregateResult) -> Vec<BucketRow> {
-    match &result.data {
-        AggregateResultData::Buckets { rows, .. } | AggregateResultData::Rollup { rows, .. } => {
-            rows.clone()
-        }
-        _ => Vec::new(),
-    }
-}
-
 /// Finalize a single accumulator into an `AggregateResult`.
-fn finalize_one(
+///
+/// `total_matched` / `total_bytes` are the share-of-total denominators:
+/// the whole result set at the top level, the parent row for nested
+/// children (see [`super::nested`]).
+pub(super) fn finalize_one(
     acc: GroupAccumulator,
     total_matched: u64,
     total_bytes: u64,
@@ -342,25 +339,39 @@ fn finalize_one(
 ) -> AggregateResult {
     let label = acc.label.clone();
     let field = acc.field;
-    let field_name = field
-        .map(|f_id| f_id.metadata().canonical_name.to_owned())
-        .unwrap_or_default();
+    let mut nester = Nester::new(acc.sub, drives, ext_map);
+    let field_name = match &acc.computed {
+        Some((computed, _)) => computed.name().to_owned(),
+        None => field
+            .map(|f_id| f_id.metadata().canonical_name.to_owned())
+            .unwrap_or_default(),
+    };
+    let ranks = match &acc.kind {
+        AccumulatorKind::Terms { metrics, .. }
+        | AccumulatorKind::RegexTerms { metrics, .. }
+        | AccumulatorKind::Histogram { metrics, .. }
+        | AccumulatorKind::DateHistogram { metrics, .. }
+        | AccumulatorKind::Rollup { metrics, .. } => bucket_ranks(metrics),
+        AccumulatorKind::Count { .. }
+        | AccumulatorKind::Stats { .. }
+        | AccumulatorKind::Missing { .. }
+        | AccumulatorKind::Distinct { .. }
+        | AccumulatorKind::Duplicates { .. }
+        | AccumulatorKind::Pivot { .. }
+        | AccumulatorKind::SimilarNames { .. } => Vec::new(),
+    };
+    let ctx = RowContext {
+        total_matched,
+        total_bytes,
+        percentiles: &ranks,
+    };
 
     let data = match acc.kind {
         AccumulatorKind::Count { count } => AggregateResultData::Count { value: count },
 
-        AccumulatorKind::Stats { stats, .. } => AggregateResultData::Stats {
-            field: field_name,
-            stats: StatsResult {
-                count: stats.count,
-                sum: stats.sum,
-                min: if stats.min == u64::MAX { 0 } else { stats.min },
-                max: stats.max,
-                avg: stats.avg(),
-                waste_bytes: stats.waste_bytes(),
-                waste_pct: stats.waste_pct(),
-            },
-        },
+        AccumulatorKind::Stats { stats, metrics } => {
+            finalize_stats(field_name, &stats, &scalar_ranks(&metrics))
+        }
 
         AccumulatorKind::Terms {
             groups,
@@ -375,44 +386,26 @@ fn finalize_one(
             top,
             sample_heaps,
             sample_spec.as_ref(),
-            total_matched,
-            total_bytes,
+            ctx,
             options,
             drives,
             ext_map,
+            &mut nester,
         ),
 
+        AccumulatorKind::RegexTerms { inner, .. } => {
+            inner.finalize(ctx, options, drives, &mut nester)
+        }
+
         AccumulatorKind::Histogram {
             buckets,
             boundaries,
             ..
-        } => finalize_histogram(
-            field_name,
-            &buckets,
-            &boundaries,
-            total_matched,
-            total_bytes,
-            options,
-        ),
+        } => finalize_histogram(field_name, &buckets, &boundaries, ctx, options, &mut nester),
 
-        AccumulatorKind::DateHistogram { buckets, .. } => {
-            let rows: Vec<_> = buckets
-                .iter()
-                .filter(|(_, stats)| options.include_empty_buckets || stats.count > 0)
-                .map(|(&ts, stats)| {
-                    let key = format_timestamp_key(ts);
-                    BucketRow::from_stats(key, stats, total_matched, total_bytes)
-                })
-                .collect();
-
-            AggregateResultData::Buckets {
-                field: field_name,
-                rows,
-                other_count: 0,
-                total_groups: buckets.len(),
-                exact: true,
-            }
-        }
+        AccumulatorKind::DateHistogram {
+            buckets, calendar, ..
+        } => finalize_date_histogram(field_name, &buckets, calendar, ctx, options, &mut nester),
 
         AccumulatorKind::Missing { count } => AggregateResultData::Missing {
             field: field_name,
@@ -424,44 +417,93 @@ fn finalize_one(
             count: seen.len() as u64,
         },
 
-        AccumulatorKind::Rollup {
-            inner,
-            sub_accumulators,
-            ..
-        } => finalize_rollup(
-            &inner,
-            sub_accumulators,
-            total_matched,
-            total_bytes,
-            drives,
-            ext_map,
-        ),
+        AccumulatorKind::Rollup { inner, .. } => finalize_rollup(&inner, ctx, drives, &mut nester),
 
         AccumulatorKind::Duplicates { inner, sample_spec } => {
             finalize_duplicates(inner, sample_spec, drives)
         }
+
+        AccumulatorKind::Pivot { inner } => AggregateResultData::Pivot {
+            result: inner.finalize(drives, ext_map),
+        },
+
+        AccumulatorKind::SimilarNames { inner } => inner.finalize(ctx, drives),
     };
 
     AggregateResult { label, data }
 }
 
+/// Finalize a `Stats` accumulator; an empty set reports `min` as 0.
+fn finalize_stats(
+    field_name: String,
+    stats: &StatsAccumulator,
+    ranks: &[PercentileRank],
+) -> AggregateResultData {
+    AggregateResultData::Stats {
+        field: field_name,
+        stats: StatsResult {
+            count: stats.count,
+            sum: stats.sum,
+            min: if stats.min == u64::MAX { 0 } else { stats.min },
+            max: stats.max,
+            avg: stats.avg(),
+            waste_bytes: stats.waste_bytes(),
+            waste_pct: stats.waste_pct(),
+            percentiles: percentiles_of(stats, ranks),
+        },
+    }
+}
+
+/// Finalize a `DateHistogram` accumulator: label each slot by calendar,
+/// keep empty slots for periodic calendars (every hour / weekday shows).
+fn finalize_date_histogram(
+    field_name: String,
+    buckets: &alloc::collections::BTreeMap<i64, StatsAccumulator>,
+    calendar: super::spec::CalendarInterval,
+    ctx: RowContext<'_>,
+    options: &FinalizeOptions,
+    nester: &mut Nester<'_>,
+) -> AggregateResultData {
+    let every_slot = options.include_empty_buckets || calendar.period().is_some();
+    let rows: Vec<_> = buckets
+        .iter()
+        .filter(|(_, stats)| every_slot || stats.count > 0)
+        .map(|(&ts, stats)| {
+            let key = calendar.key_label(ts);
+            let mut row = BucketRow::from_stats(key, stats, ctx);
+            nester.attach(&mut row, ts.cast_unsigned());
+            row
+        })
+        .collect();
+
+    AggregateResultData::Buckets {
+        field: field_name,
+        rows,
+        other_count: 0,
+        total_groups: buckets.len(),
+        exact: true,
+    }
+}
+
 /// Finalize a `Histogram` accumulator: format range keys, drop empty
-/// buckets unless requested otherwise.
+/// buckets unless requested otherwise, attach nested children.
 fn finalize_histogram(
     field_name: String,
     buckets: &[StatsAccumulator],
     boundaries: &[u64],
-    total_matched: u64,
-    total_bytes: u64,
+    ctx: RowContext<'_>,
     options: &FinalizeOptions,
+    nester: &mut Nester<'_>,
 ) -> AggregateResultData {
     let rows: Vec<_> = buckets
         .iter()
         .enumerate()
         .filter(|(_, stats)| options.include_empty_buckets || stats.count > 0)
         .map(|(i, stats)| {
-            let key = format_range_key(i, boundaries);
-            BucketRow::from_stats(key, stats, total_matched, total_bytes)
+            let key = super::buckets::format_range_key(i, boundaries);
+            let mut row = BucketRow::from_stats(key, stats, ctx);
+            nester.attach(&mut row, uffs_mft::usize_to_u64(i));
+            row
         })
         .collect();
 
@@ -475,7 +517,7 @@ fn finalize_histogram(
 }
 
 /// Finalize a `Terms` accumulator: sort buckets by count, take top-N, attach
-/// sample rows and drill-down predicates.
+/// sample rows, drill-down predicates and nested children.
 #[expect(
     clippy::too_many_arguments,
     reason = "explicit parameter list keeps the helper signature transparent at the call site"
@@ -487,11 +529,11 @@ fn finalize_terms(
     top: u16,
     mut sample_heaps: Option<std::collections::HashMap<u64, super::sample_heap::SampleHeap>>,
     sample_spec: Option<&super::spec::TopHitsSpec>,
-    total_matched: u64,
-    total_bytes: u64,
+    ctx: RowContext<'_>,
     options: &FinalizeOptions,
     drives: &[&DriveCompactIndex],
     ext_map: &super::ExtensionMap,
+    nester: &mut Nester<'_>,
 ) -> AggregateResultData {
     let total_groups = groups.len();
 
@@ -501,10 +543,7 @@ fn finalize_terms(
         .iter()
         .map(|(&key, stats)| {
             let key_str = resolve_group_key(field, key, drives, ext_map);
-            (
-                key,
-                BucketRow::from_stats(key_str, stats, total_matched, total_bytes),
-            )
+            (key, BucketRow::from_stats(key_str, stats, ctx))
         })
         .collect();
 
@@ -545,6 +584,7 @@ fn finalize_terms(
             &row.key,
             drives,
         );
+        nester.attach(row, *group_key);
     }
 
     let rows: Vec<BucketRow> = keyed_rows.into_iter().map(|(_, row)| row).collect();
@@ -562,11 +602,9 @@ fn finalize_terms(
 /// rollup, attach nested sub-aggregation rows where present.
 fn finalize_rollup(
     inner: &super::rollup::RollupAccumulator,
-    mut sub_accumulators: Option<std::collections::HashMap<u64, GroupAccumulator>>,
-    total_matched: u64,
-    total_bytes: u64,
+    ctx: RowContext<'_>,
     drives: &[&DriveCompactIndex],
-    ext_map: &super::ExtensionMap,
+    nester: &mut Nester<'_>,
 ) -> AggregateResultData {
     let mode_str = match inner.mode {
         super::spec::RollupMode::Drive => "drive".to_owned(),
@@ -580,15 +618,8 @@ fn finalize_rollup(
         .into_iter()
         .map(|(key, stats)| {
             let key_str = super::rollup::resolve_rollup_key(key, inner.mode, drives);
-            let mut row = BucketRow::from_stats(key_str, stats, total_matched, total_bytes);
-
-            // Attach sub-aggregation from nested sub-accumulator.
-            if let Some(sub_acc) = sub_accumulators.as_mut().and_then(|map| map.remove(&key)) {
-                let sub_result =
-                    finalize_accumulator(sub_acc, total_matched, total_bytes, drives, ext_map, &[]);
-                row.sub_buckets = sub_result_to_bucket_rows(&sub_result);
-            }
-
+            let mut row = BucketRow::from_stats(key_str, stats, ctx);
+            nester.attach(&mut row, key);
             row
         })
         .collect();
@@ -653,104 +684,24 @@ fn build_drilldown(
     preds
 }
 
-/// Materialize a single sample entry into a [`SampleRow`].
-///
-/// Looks up the record by `(drive_ordinal, rec_idx)` in `drives`,
-/// then projects the requested fields into key-value pairs.
-fn materialize_sample_entry(
-    entry: &super::sample_heap::SampleEntry,
-    projection: &[crate::search::field::FieldId],
-    drives: &[&DriveCompactIndex],
-) -> SampleRow {
-    let drive_ref = drives.get(usize::from(entry.drive_ordinal));
-    let record =
-        drive_ref.and_then(|drive| drive.records.get(uffs_mft::u32_as_usize(entry.rec_idx)));
-
-    let fields: Vec<(String, String)> = projection
-        .iter()
-        .map(|fid| {
-            let name = fid.metadata().canonical_name.to_owned();
-            let value = match (record, drive_ref) {
-                (Some(rec), Some(drv)) => format_field(*fid, rec, drv),
-                _ => String::new(),
-            };
-            (name, value)
-        })
-        .collect();
-
-    SampleRow {
-        fields,
-        sort_key: entry.sort_key,
-    }
-}
-
-/// Format a single field value for sample row output.
-#[expect(
-    clippy::wildcard_enum_match_arm,
-    reason = "FieldId is open-ended; fields without a textual representation fall back to empty string"
-)]
-fn format_field(
-    field: crate::search::field::FieldId,
-    record: &crate::compact::CompactRecord,
-    drive: &DriveCompactIndex,
-) -> String {
-    use crate::search::field::FieldId;
-    match field {
-        FieldId::Name => record.name(&drive.names).to_owned(),
-        FieldId::Size => record.size.to_string(),
-        FieldId::SizeOnDisk => record.allocated.to_string(),
-        FieldId::Modified => format_timestamp_key(record.modified),
-        FieldId::Created => format_timestamp_key(record.created),
-        FieldId::Accessed => format_timestamp_key(record.accessed),
-        FieldId::Extension => {
-            let ext_id = usize::from(record.extension_id);
-            drive
-                .ext_names
-                .get(ext_id)
-                .map(ToString::to_string)
-                .unwrap_or_default()
-        }
-        FieldId::Path | FieldId::PathOnly => {
-            // Full path resolution is expensive — return parent index
-            // as a placeholder.  Callers needing full paths should use
-            // the search pipeline instead.
-            format!("parent_idx:{}", record.parent_idx)
-        }
-        FieldId::DirectoryFlag => {
-            if record.flags & 0x0010 != 0 {
-                "directory".to_owned()
-            } else {
-                "file".to_owned()
-            }
-        }
-        FieldId::Hidden => format!("{}", record.flags & 0x0002 != 0),
-        FieldId::System => format!("{}", record.flags & 0x0004 != 0),
-        FieldId::ReadOnly => format!("{}", record.flags & 0x0001 != 0),
-        FieldId::TreeSize => record.treesize.to_string(),
-        FieldId::Descendants => record.descendants.to_string(),
-        _ => String::new(),
-    }
-}
-
 /// Resolve a u64 group key to a display string.
 ///
 /// For `Extension`, group keys are canonical cross-drive IDs from the
 /// [`super::ExtensionMap`]; the map is the only correct way to resolve
 /// them (a raw per-drive intern table would map the same id to
 /// different extensions on different drives).
-fn resolve_group_key(
+pub(super) fn resolve_group_key(
     field: Option<crate::search::field::FieldId>,
     key: u64,
-    _drives: &[&DriveCompactIndex],
+    drives: &[&DriveCompactIndex],
     ext_map: &super::ExtensionMap,
 ) -> String {
     use crate::search::field::FieldId;
     match field {
         Some(FieldId::Extension) => ext_map.resolve(key),
-        Some(FieldId::Drive) => {
-            let ch = char::from(u8::try_from(key).unwrap_or(b'?'));
-            format!("{ch}:")
-        }
+        // Local volumes (key 0) render as the empty host, like the column.
+        Some(FieldId::Host) => super::extract::host_label_for_key(drives, key),
+        Some(FieldId::Drive) => super::extract::volume_label_for_key(drives, key),
         Some(FieldId::Type) => crate::search::derived::semantic_type_name_from_id(key).to_owned(),
         Some(FieldId::DirectoryFlag) => {
             if key == 1 {
@@ -772,25 +723,8 @@ fn resolve_group_key(
     }
 }
 
-/// Format a range bucket key.
-fn format_range_key(index: usize, boundaries: &[u64]) -> String {
-    let Some((first, last)) = boundaries.first().zip(boundaries.last()) else {
-        return format!("bucket_{index}");
-    };
-    if index == 0 {
-        format!("< {first}")
-    } else if index >= boundaries.len() {
-        format!(">= {last}")
-    } else {
-        match (boundaries.get(index - 1), boundaries.get(index)) {
-            (Some(lo), Some(hi)) => format!("{lo} - {hi}"),
-            _ => format!("bucket_{index}"),
-        }
-    }
-}
-
 /// Format a FILETIME timestamp key as an ISO date (`YYYY-MM-DD`).
-fn format_timestamp_key(filetime: i64) -> String {
+pub(super) fn format_timestamp_key(filetime: i64) -> String {
     match uffs_time::filetime_to_calendar(filetime) {
         Some(uffs_time::CalendarParts {
             year, month, day, ..
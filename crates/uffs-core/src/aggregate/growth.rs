@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Growth aggregate — per-folder storage growth between two indexes of
+//! one drive.
+//!
+//! Every other aggregate folds a single index in one pass; `growth`
+//! compares a **baseline** against the **current** index, so it runs
+//! outside the [`super::AggregatePlan`] scan. The daemon evaluates it in
+//! its snapshot-diff path (a search with `diff_baseline`), where both
+//! sides are loaded.
+//!
+//! The folders compared are the directories exactly `depth` levels below
+//! the drive root — the same levels `rollup:path,depth=N` groups by. Each
+//! current folder is paired with its baseline by **NTFS File Reference**,
+//! so a renamed or moved folder keeps its history. A folder that was
+//! recreated (new File Reference, same path) falls back to a
+//! case-insensitive **path** match. Folders on only one side are reported
+//! as `added` / `removed`.
+//!
+//! Rows compare the pre-computed tree metrics — `treesize`,
+//! `tree_allocated` and `descendants` — and are ranked by absolute
+//! (`rank=abs`, bytes) or relative (`rank=rel`, percent of the baseline
+//! `treesize`) growth.
+
+use rustc_hash::FxHashMap;
+
+use crate::compact::{CompactRecord, DriveCompactIndex, MalformedRender};
+use crate::search::tree::resolve_path;
+
+/// Default folder depth (top-level folders).
+const DEFAULT_DEPTH: u32 = 1;
+
+/// Default number of rows returned.
+const DEFAULT_TOP: u16 = 30;
+
+/// How [`compute_growth`] ranks its rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GrowthRank {
+    /// Largest `treesize` growth in bytes first.
+    #[default]
+    Absolute,
+    /// Largest `treesize` growth relative to the baseline first. Folders
+    /// with no baseline size have no percentage and rank last.
+    Relative,
+}
+
+impl GrowthRank {
+    /// Parse a rank name (`abs` / `absolute` / `rel` / `relative`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "abs" | "absolute" | "bytes" => Some(Self::Absolute),
+            "rel" | "relative" | "pct" | "percent" => Some(Self::Relative),
+            _ => None,
+        }
+    }
+
+    /// Canonical name, as echoed in the result's `field`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Absolute => "absolute",
+            Self::Relative => "relative",
+        }
+    }
+}
+
+/// A `growth` aggregate request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrowthSpec {
+    /// Folder depth below the drive root (1 = top-level folders).
+    pub depth: u32,
+    /// Maximum rows returned.
+    pub top: u16,
+    /// Ranking order.
+    pub rank: GrowthRank,
+    /// Optional label for the result.
+    pub label: Option<String>,
+}
+
+impl Default for GrowthSpec {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_DEPTH,
+            top: DEFAULT_TOP,
+            rank: GrowthRank::default(),
+            label: None,
+        }
+    }
+}
+
+/// A folder's tree metrics on one side of the comparison.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TreeTotals {
+    /// Sum of logical sizes below the folder.
+    pub treesize: u64,
+    /// Sum of allocated sizes below the folder.
+    pub tree_allocated: u64,
+    /// Number of records below the folder.
+    pub descendants: u64,
+}
+
+impl TreeTotals {
+    /// The tree metrics of a directory record.
+    fn of(record: &CompactRecord) -> Self {
+        Self {
+            treesize: record.treesize,
+            tree_allocated: record.tree_allocated,
+            descendants: u64::from(record.descendants),
+        }
+    }
+}
+
+/// How a [`GrowthRow`]'s two sides were paired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthMatch {
+    /// Same NTFS File Reference on both sides.
+    FileRef,
+    /// Different File Reference, same path (the folder was recreated).
+    Path,
+    /// Only in the current index.
+    Added,
+    /// Only in the baseline.
+    Removed,
+}
+
+impl GrowthMatch {
+    /// Wire name of the pairing.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FileRef => "file_ref",
+            Self::Path => "path",
+            Self::Added => "added",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+/// One folder's growth between the baseline and the current index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrowthRow {
+    /// Full path — from the current index, or the baseline for a removed
+    /// folder.
+    pub path: String,
+    /// Tree metrics in the baseline (zero for an added folder).
+    pub before: TreeTotals,
+    /// Tree metrics now (zero for a removed folder).
+    pub after: TreeTotals,
+    /// How the two sides were paired.
+    pub matched: GrowthMatch,
+}
+
+impl GrowthRow {
+    /// `treesize` growth in bytes (negative when the folder shrank).
+    #[must_use]
+    pub fn delta_bytes(&self) -> i64 {
+        signed_delta(self.before.treesize, self.after.treesize)
+    }
+
+    /// `tree_allocated` growth in bytes.
+    #[must_use]
+    pub fn delta_allocated(&self) -> i64 {
+        signed_delta(self.before.tree_allocated, self.after.tree_allocated)
+    }
+
+    /// `descendants` growth.
+    #[must_use]
+    pub fn delta_descendants(&self) -> i64 {
+        signed_delta(self.before.descendants, self.after.descendants)
+    }
+
+    /// `treesize` growth as a percentage of the baseline, `None` when the
+    /// folder had no baseline size.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "relative growth is a byte ratio rendered as a percentage"
+    )]
+    pub fn growth_pct(&self) -> Option<f64> {
+        (self.before.treesize > 0).then(|| {
+            (uffs_mft::u64_to_f64(self.after.treesize) / uffs_mft::u64_to_f64(self.before.treesize))
+                .mul_add(100.0_f64, -100.0_f64)
+        })
+    }
+}
+
+/// The ranked result of a `growth` aggregate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GrowthResult {
+    /// The top rows, in rank order.
+    pub rows: Vec<GrowthRow>,
+    /// Folders compared before truncation to `top`.
+    pub total_groups: usize,
+}
+
+/// Compare the folders `spec.depth` levels deep in `baseline` and
+/// `current` (two indexes of the same drive) and rank their growth.
+#[must_use]
+pub fn compute_growth(
+    baseline: &DriveCompactIndex,
+    current: &DriveCompactIndex,
+    spec: &GrowthSpec,
+) -> GrowthResult {
+    let base_prefix = baseline.volume_prefix();
+    let current_prefix = current.volume_prefix();
+    let path_of = |drive: &DriveCompactIndex, prefix: &str, idx: usize| {
+        drive.display_path(resolve_path(drive, idx, prefix, MalformedRender::Lossy))
+    };
+
+    let mut base_by_ref: FxHashMap<u64, usize> = folders_at_depth(baseline, spec.depth)
+        .map(|(idx, rec)| (rec.file_ref, idx))
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut unpaired = Vec::new();
+    for (idx, rec) in folders_at_depth(current, spec.depth) {
+        let paired = base_by_ref
+            .remove(&rec.file_ref)
+            .and_then(|base_idx| baseline.records.get(base_idx));
+        if let Some(before) = paired {
+            rows.push(GrowthRow {
+                path: path_of(current, &current_prefix, idx),
+                before: TreeTotals::of(before),
+                after: TreeTotals::of(rec),
+                matched: GrowthMatch::FileRef,
+            });
+        } else {
+            unpaired.push((idx, rec));
+        }
+    }
+
+    // Path fallback for the folders the File Reference did not pair.
+    let mut base_by_path: FxHashMap<String, (String, &CompactRecord)> = base_by_ref
+        .into_values()
+        .filter_map(|idx| {
+            let rec = baseline.records.get(idx)?;
+            let path = path_of(baseline, &base_prefix, idx);
+            Some((path.to_lowercase(), (path, rec)))
+        })
+        .collect();
+    for (idx, rec) in unpaired {
+        let path = path_of(current, &current_prefix, idx);
+        let (before, matched) = match base_by_path.remove(&path.to_lowercase()) {
+            Some((_, before)) => (TreeTotals::of(before), GrowthMatch::Path),
+            None => (TreeTotals::default(), GrowthMatch::Added),
+        };
+        rows.push(GrowthRow {
+            path,
+            before,
+            after: TreeTotals::of(rec),
+            matched,
+        });
+    }
+    rows.extend(base_by_path.into_values().map(|(path, before)| GrowthRow {
+        path,
+        before: TreeTotals::of(before),
+        after: TreeTotals::default(),
+        matched: GrowthMatch::Removed,
+    }));
+
+    rank_rows(&mut rows, spec.rank);
+    let total_groups = rows.len();
+    rows.truncate(usize::from(spec.top));
+    GrowthResult { rows, total_groups }
+}
+
+/// Sort `rows` into `rank` order; ties break by path so the result is
+/// deterministic.
+fn rank_rows(rows: &mut [GrowthRow], rank: GrowthRank) {
+    match rank {
+        GrowthRank::Absolute => rows.sort_by(|left, right| {
+            right
+                .delta_bytes()
+                .cmp(&left.delta_bytes())
+                .then_with(|| left.path.cmp(&right.path))
+        }),
+        GrowthRank::Relative => rows.sort_by(|left, right| {
+            match (left.growth_pct(), right.growth_pct()) {
+                (Some(left_pct), Some(right_pct)) => right_pct.total_cmp(&left_pct),
+                (Some(_), None) => core::cmp::Ordering::Less,
+                (None, Some(_)) => core::cmp::Ordering::Greater,
+                (None, None) => right.delta_bytes().cmp(&left.delta_bytes()),
+            }
+            .then_with(|| left.path.cmp(&right.path))
+        }),
+    }
+}
+
+/// The live directories of `drive` exactly `depth` levels below its root,
+/// with their record indices.
+fn folders_at_depth(
+    drive: &DriveCompactIndex,
+    depth: u32,
+) -> impl Iterator<Item = (usize, &CompactRecord)> {
+    drive.records.iter().enumerate().filter(move |&(idx, rec)| {
+        rec.is_directory()
+            && rec.file_ref != 0
+            && rec.name_len != 0
+            && depth_of(drive, idx, depth) == Some(depth)
+    })
+}
+
+/// Number of named ancestors between the record at `idx` and the drive
+/// root (the record itself included), or `None` when the chain is broken
+/// or deeper than `limit`. The chain ends at the record named `.` or at a
+/// top-level entry (`parent_idx == u32::MAX`) — the terminators
+/// [`resolve_path`] uses.
+fn depth_of(drive: &DriveCompactIndex, idx: usize, limit: u32) -> Option<u32> {
+    let mut depth = 0_u32;
+    let mut cursor = idx;
+    loop {
+        let rec = drive.records.get(cursor)?;
+        let name = rec.name_bytes(&drive.names);
+        if name.is_empty() || name == b"." {
+            return Some(depth);
+        }
+        depth += 1;
+        if depth > limit {
+            return None;
+        }
+        if rec.parent_idx == u32::MAX {
+            return Some(depth);
+        }
+        cursor = uffs_mft::u32_as_usize(rec.parent_idx);
+    }
+}
+
+/// `after - before` as a signed delta, saturating at the `i64` range.
+fn signed_delta(before: u64, after: u64) -> i64 {
+    let delta = i128::from(after) - i128::from(before);
+    i64::try_from(delta).unwrap_or(if delta < 0 { i64::MIN } else { i64::MAX })
+}
+
+#[cfg(test)]
+#[path = "growth_tests.rs"]
+mod tests;
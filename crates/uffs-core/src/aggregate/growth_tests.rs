@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for the `growth` aggregate: File Reference / path pairing,
+//! ranking, and the depth limit.
+
+use uffs_mft::index::{IndexNameRef, MftIndex, ROOT_FRS};
+use uffs_mft::platform::DriveLetter;
+
+use super::*;
+use crate::compact::build_compact_index;
+
+/// One directory of a fixture drive: `(frs, parent_frs, name, treesize,
+/// descendants)`.
+type Folder<'a> = (u64, u64, &'a str, u64, u32);
+
+/// Build `C:\` with the given directories (parents before children).
+fn drive(folders: &[Folder<'_>]) -> DriveCompactIndex {
+    let mut idx = MftIndex::new(DriveLetter::C);
+    let root_off = idx.add_name(".");
+    let root = idx.get_or_create(ROOT_FRS.into());
+    root.stdinfo.set_directory(true);
+    root.first_name.name = IndexNameRef::new(root_off, 1, true, IndexNameRef::NO_EXTENSION);
+    root.first_name.parent_frs = Into::into(ROOT_FRS);
+
+    for &(frs, parent, name, treesize, descendants) in folders {
+        let off = idx.add_name(name);
+        let dir = idx.get_or_create(frs.into());
+        dir.stdinfo.set_directory(true);
+        dir.first_name.name = IndexNameRef::new(
+            off,
+            uffs_mft::len_to_u16(name.len()),
+            true,
+            IndexNameRef::NO_EXTENSION,
+        );
+        dir.first_name.parent_frs = Into::into(parent);
+        dir.treesize = treesize;
+        dir.tree_allocated = treesize.next_multiple_of(4096);
+        dir.descendants = descendants;
+    }
+    build_compact_index(DriveLetter::C, &idx).0
+}
+
+fn spec(depth: u32, top: u16, rank: GrowthRank) -> GrowthSpec {
+    GrowthSpec {
+        depth,
+        top,
+        rank,
+        label: None,
+    }
+}
+
+fn summary(result: &GrowthResult) -> Vec<(&str, i64, GrowthMatch)> {
+    result
+        .rows
+        .iter()
+        .map(|row| (row.path.as_str(), row.delta_bytes(), row.matched))
+        .collect()
+}
+
+#[test]
+fn folders_pair_by_file_reference_then_path() {
+    let baseline = drive(&[
+        (100, ROOT_FRS, "Projects", 1_000, 10),
+        (101, ROOT_FRS, "Media", 500, 5),
+        (102, ROOT_FRS, "Old", 300, 3),
+    ]);
+    let current = drive(&[
+        // Renamed, same File Reference.
+        (100, ROOT_FRS, "Code", 4_000, 40),
+        // Recreated under the same path.
+        (300, ROOT_FRS, "media", 800, 8),
+        (400, ROOT_FRS, "New", 200, 2),
+    ]);
+
+    let result = compute_growth(&baseline, &current, &spec(1, 30, GrowthRank::Absolute));
+    assert_eq!(summary(&result), vec![
+        ("C:\\Code", 3_000, GrowthMatch::FileRef),
+        ("C:\\media", 300, GrowthMatch::Path),
+        ("C:\\New", 200, GrowthMatch::Added),
+        ("C:\\Old", -300, GrowthMatch::Removed),
+    ]);
+    assert_eq!(result.total_groups, 4);
+
+    let code = result.rows.first().expect("Code row");
+    assert_eq!(code.before.descendants, 10);
+    assert_eq!(code.after.descendants, 40);
+    assert_eq!(code.delta_descendants(), 30);
+    assert_eq!(code.delta_allocated(), 0, "both round up to one cluster");
+    assert_eq!(code.growth_pct().map(f64::round), Some(300.0_f64));
+}
+
+#[test]
+fn relative_rank_puts_new_folders_last_and_truncates_to_top() {
+    let baseline = drive(&[
+        (100, ROOT_FRS, "Big", 10_000, 1),
+        (101, ROOT_FRS, "Small", 100, 1),
+        (102, ROOT_FRS, "Flat", 700, 1),
+    ]);
+    let current = drive(&[
+        (100, ROOT_FRS, "Big", 15_000, 1),
+        (101, ROOT_FRS, "Small", 300, 1),
+        (102, ROOT_FRS, "Flat", 700, 1),
+        (103, ROOT_FRS, "Fresh", 50_000, 1),
+    ]);
+
+    let result = compute_growth(&baseline, &current, &spec(1, 3, GrowthRank::Relative));
+    let paths: Vec<&str> = result.rows.iter().map(|row| row.path.as_str()).collect();
+    assert_eq!(paths, vec!["C:\\Small", "C:\\Big", "C:\\Flat"]);
+    assert_eq!(
+        result.total_groups, 4,
+        "Fresh was compared, then cut by top"
+    );
+
+    let absolute = compute_growth(&baseline, &current, &spec(1, 1, GrowthRank::Absolute));
+    assert_eq!(summary(&absolute), vec![(
+        "C:\\Fresh",
+        50_000,
+        GrowthMatch::Added
+    )]);
+}
+
+#[test]
+fn depth_selects_one_folder_level() {
+    let folders = [
+        (100, ROOT_FRS, "Users", 5_000, 3),
+        (110, 100, "alice", 4_000, 2),
+        (120, 110, "Documents", 3_000, 1),
+    ];
+    let baseline = drive(&folders);
+    let mut grown = folders;
+    if let Some(alice) = grown.get_mut(1) {
+        alice.3 = 6_000;
+    }
+    let current = drive(&grown);
+
+    let result = compute_growth(&baseline, &current, &spec(2, 30, GrowthRank::Absolute));
+    assert_eq!(summary(&result), vec![(
+        "C:\\Users\\alice",
+        2_000,
+        GrowthMatch::FileRef
+    )]);
+}
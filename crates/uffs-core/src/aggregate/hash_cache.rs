@@ -0,0 +1,616 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Content-hash cache for duplicate verification.
+//!
+//! Every digest the [`DuplicateVerifier`](super::verify::DuplicateVerifier)
+//! computes is keyed by the file's [`ContentKey`] — `(file_ref, size,
+//! modified)` — plus the [`HashKind`] that produced it.  A file whose
+//! record has not changed keeps its key, so a repeat scan answers from the
+//! cache instead of re-reading the content; an edited file gets a new size
+//! or timestamp and misses.
+//!
+//! [`HashCache`] is one volume's table.  It serialises to a small binary
+//! sidecar ([`HashCache::write_to`] / [`HashCache::read_from`]) and drops
+//! the entries of changed files on journal patches
+//! ([`HashCache::invalidate`]).  The verifier reaches it through the
+//! [`HashStore`] seam, which maps `(record_idx, drive_ordinal)` to the
+//! owning volume's table.
+//!
+//! # Sidecar format
+//!
+//! ```text
+//! "UFHC" | version: u8 | count: u64 LE | count × entry
+//! entry  = file_ref u64 | size u64 | modified i64 | algo u8 | span u8
+//!        | head u32 | digest [u8; 32]            (all little-endian)
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read, Write};
+
+use crate::compact::CompactRecord;
+
+/// Bytes read from each end of a file for the [`HashSpan::Sample`] stage.
+pub const SAMPLE_CHUNK: u32 = 64 * 1024;
+
+/// Sidecar magic.
+const MAGIC: &[u8; 4] = b"UFHC";
+
+/// Sidecar format version.
+const FORMAT_VERSION: u8 = 1;
+
+/// A content digest, zero-padded to 32 bytes for the shorter algorithms
+/// (128-bit xxh3, SHA-1, MD5).
+pub type Digest = [u8; 32];
+
+/// Hash function behind a cached digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashAlgo {
+    /// SHA-256 (`verify=sha256`).
+    Sha256,
+    /// 128-bit xxh3 — fast, non-cryptographic (`verify=xxh3`, and the
+    /// digest behind `verify=first_bytes`).
+    Xxh3,
+    /// BLAKE3 — cryptographic and still fast (`verify=blake3`).
+    Blake3,
+    /// SHA-1 — only computed to match `--hash-in` lists (NSRL, IOC feeds).
+    Sha1,
+    /// MD5 — only computed to match `--hash-in` lists.
+    Md5,
+}
+
+impl HashAlgo {
+    /// Lowercase name, as shown in the `hash` column (`blake3:…`).
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Xxh3 => "xxh3",
+            Self::Blake3 => "blake3",
+            Self::Sha1 => "sha1",
+            Self::Md5 => "md5",
+        }
+    }
+
+    /// Inverse of [`Self::name`], case-insensitive; also accepts the
+    /// hyphenated spellings hash lists use in their headers (`SHA-256`).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "sha256" => Some(Self::Sha256),
+            "xxh3" => Some(Self::Xxh3),
+            "blake3" => Some(Self::Blake3),
+            "sha1" => Some(Self::Sha1),
+            "md5" => Some(Self::Md5),
+            _ => None,
+        }
+    }
+
+    /// Significant bytes of this algorithm's [`Digest`].
+    #[must_use]
+    pub const fn digest_len(self) -> usize {
+        match self {
+            Self::Xxh3 | Self::Md5 => 16,
+            Self::Sha1 => 20,
+            Self::Sha256 | Self::Blake3 => 32,
+        }
+    }
+
+    /// Digest `bytes`.
+    #[must_use]
+    pub fn digest(self, bytes: &[u8]) -> Digest {
+        let mut hasher = self.hasher();
+        hasher.update(bytes);
+        hasher.finish()
+    }
+
+    /// Incremental hasher, for content too large to hold in memory.
+    #[must_use]
+    pub fn hasher(self) -> ContentHasher {
+        use sha2::Digest as _;
+        match self {
+            Self::Sha256 => ContentHasher::Sha256(sha2::Sha256::new()),
+            Self::Xxh3 => ContentHasher::Xxh3(Box::default()),
+            Self::Blake3 => ContentHasher::Blake3(Box::default()),
+            Self::Sha1 => ContentHasher::Sha1(sha1::Sha1::new()),
+            Self::Md5 => ContentHasher::Md5(md5::Md5::new()),
+        }
+    }
+
+    /// Sidecar tag.
+    const fn tag(self) -> u8 {
+        match self {
+            Self::Sha256 => 0,
+            Self::Xxh3 => 1,
+            Self::Blake3 => 2,
+            Self::Sha1 => 3,
+            Self::Md5 => 4,
+        }
+    }
+
+    /// Inverse of [`Self::tag`].
+    const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Sha256),
+            1 => Some(Self::Xxh3),
+            2 => Some(Self::Blake3),
+            3 => Some(Self::Sha1),
+            4 => Some(Self::Md5),
+            _ => None,
+        }
+    }
+}
+
+/// In-progress digest of one [`HashAlgo`]; see [`HashAlgo::hasher`].
+#[derive(Clone)]
+pub enum ContentHasher {
+    /// SHA-256 state.
+    Sha256(sha2::Sha256),
+    /// xxh3-128 state (boxed: the streaming state is ~600 bytes).
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+    /// BLAKE3 state (boxed: ~1.9 KB).
+    Blake3(Box<blake3::Hasher>),
+    /// SHA-1 state.
+    Sha1(sha1::Sha1),
+    /// MD5 state.
+    Md5(md5::Md5),
+}
+
+impl ContentHasher {
+    /// Feed the next chunk of content.
+    pub fn update(&mut self, bytes: &[u8]) {
+        use sha2::Digest as _;
+        match self {
+            Self::Sha256(state) => state.update(bytes),
+            Self::Xxh3(state) => state.update(bytes),
+            Self::Blake3(state) => {
+                state.update(bytes);
+            }
+            Self::Sha1(state) => state.update(bytes),
+            Self::Md5(state) => state.update(bytes),
+        }
+    }
+
+    /// The finished [`Digest`], zero-padded to 32 bytes.
+    #[must_use]
+    pub fn finish(self) -> Digest {
+        use sha2::Digest as _;
+        match self {
+            Self::Sha256(state) => state.finalize().into(),
+            Self::Xxh3(state) => padded(&state.digest128().to_le_bytes()),
+            Self::Blake3(state) => *state.finalize().as_bytes(),
+            Self::Sha1(state) => padded(&state.finalize()),
+            Self::Md5(state) => padded(&state.finalize()),
+        }
+    }
+}
+
+impl core::fmt::Debug for ContentHasher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ContentHasher")
+    }
+}
+
+/// Hash everything `reader` yields with each of `algos` in one pass.
+/// Digests come back in `algos` order.
+///
+/// # Errors
+///
+/// Propagates read failures.
+pub fn digest_reader<R: Read>(mut reader: R, algos: &[HashAlgo]) -> io::Result<Vec<Digest>> {
+    let mut hashers: Vec<ContentHasher> = algos.iter().map(|algo| algo.hasher()).collect();
+    let mut buf = vec![0_u8; 256 * 1024];
+    loop {
+        let read = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        let chunk = buf.get(..read).unwrap_or_default();
+        for hasher in &mut hashers {
+            hasher.update(chunk);
+        }
+    }
+    Ok(hashers.into_iter().map(ContentHasher::finish).collect())
+}
+
+/// `bytes` left-aligned in a zeroed [`Digest`].
+fn padded(bytes: &[u8]) -> Digest {
+    let mut out = [0_u8; 32];
+    for (slot, byte) in out.iter_mut().zip(bytes) {
+        *slot = *byte;
+    }
+    out
+}
+
+/// Which bytes of a file a digest covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashSpan {
+    /// The first `n` bytes (`verify=first_bytes`).
+    Head(u32),
+    /// The first and last [`SAMPLE_CHUNK`] bytes — the cheap first stage
+    /// of the staged modes.
+    Sample,
+    /// The whole file.
+    Full,
+}
+
+impl HashSpan {
+    /// Sidecar `(tag, head)` pair.
+    const fn encode(self) -> (u8, u32) {
+        match self {
+            Self::Head(count) => (0, count),
+            Self::Sample => (1, 0),
+            Self::Full => (2, 0),
+        }
+    }
+
+    /// Inverse of [`Self::encode`].
+    const fn decode(tag: u8, head: u32) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Head(head)),
+            1 => Some(Self::Sample),
+            2 => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    /// Bytes a read of this span costs for a file of `size` bytes.
+    #[must_use]
+    pub fn read_cost(self, size: u64) -> u64 {
+        match self {
+            Self::Head(count) => size.min(u64::from(count)),
+            Self::Sample => size.min(u64::from(SAMPLE_CHUNK).saturating_mul(2)),
+            Self::Full => size,
+        }
+    }
+}
+
+/// Algorithm plus span: what a cached digest is a digest *of*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HashKind {
+    /// Hash function.
+    pub algo: HashAlgo,
+    /// Bytes covered.
+    pub span: HashSpan,
+}
+
+impl HashKind {
+    /// `algo` over `span`.
+    #[must_use]
+    pub const fn new(algo: HashAlgo, span: HashSpan) -> Self {
+        Self { algo, span }
+    }
+}
+
+/// Identity of one version of a file's content on a volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContentKey {
+    /// Packed NTFS file reference (FRS + sequence number).
+    pub file_ref: u64,
+    /// Logical size in bytes.
+    pub size: u64,
+    /// Last-write time (Unix microseconds).
+    pub modified: i64,
+}
+
+impl ContentKey {
+    /// Key of `record`, or `None` for synthetic rows (`file_ref == 0`),
+    /// which have no stable identity to cache against.
+    #[must_use]
+    pub const fn of(record: &CompactRecord) -> Option<Self> {
+        if record.file_ref == 0 {
+            return None;
+        }
+        Some(Self {
+            file_ref: record.file_ref,
+            size: record.size,
+            modified: record.modified,
+        })
+    }
+}
+
+/// Lookup/record seam between the verifier and a [`HashCache`] owner.
+///
+/// Members are addressed the way [`FileReader`](super::verify::FileReader)
+/// addresses them — `(record_idx, drive_ordinal)` — so the implementor
+/// resolves the record's [`ContentKey`] and volume.  Methods take `&self`:
+/// the daemon's store sits behind a lock.
+pub trait HashStore {
+    /// Cached digest of `kind` for the member, if any.
+    fn lookup(&self, record_idx: usize, drive_ordinal: u8, kind: HashKind) -> Option<Digest>;
+
+    /// Remember `digest` as the member's digest of `kind`.
+    fn record(&self, record_idx: usize, drive_ordinal: u8, kind: HashKind, digest: Digest);
+}
+
+/// One volume's cached digests.
+#[derive(Debug, Clone, Default)]
+pub struct HashCache {
+    /// Digest per `(content, kind)`.
+    entries: HashMap<(ContentKey, HashKind), Digest>,
+    /// Whether the table changed since it was loaded or last saved.
+    dirty: bool,
+}
+
+impl HashCache {
+    /// Empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached digests.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the table changed since it was loaded or last saved.
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Mark the table as persisted.
+    pub const fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Cached digest of `kind` for `key`.
+    #[must_use]
+    pub fn get(&self, key: ContentKey, kind: HashKind) -> Option<Digest> {
+        self.entries.get(&(key, kind)).copied()
+    }
+
+    /// Cache `digest` as `key`'s digest of `kind`.
+    pub fn insert(&mut self, key: ContentKey, kind: HashKind, digest: Digest) {
+        if self.entries.insert((key, kind), digest) != Some(digest) {
+            self.dirty = true;
+        }
+    }
+
+    /// Strongest full-content digest cached for `key`: BLAKE3, then
+    /// SHA-256, SHA-1, MD5 and finally xxh3.
+    #[must_use]
+    pub fn full_digest(&self, key: ContentKey) -> Option<(HashAlgo, Digest)> {
+        [
+            HashAlgo::Blake3,
+            HashAlgo::Sha256,
+            HashAlgo::Sha1,
+            HashAlgo::Md5,
+            HashAlgo::Xxh3,
+        ]
+        .into_iter()
+        .find_map(|algo| {
+            self.get(key, HashKind::new(algo, HashSpan::Full))
+                .map(|digest| (algo, digest))
+        })
+    }
+
+    /// Drop every digest of the files whose FRS (MFT slot) is in `frs`.
+    /// Returns how many entries were removed.
+    pub fn invalidate(&mut self, frs: &[u64]) -> usize {
+        if frs.is_empty() || self.entries.is_empty() {
+            return 0;
+        }
+        let changed: HashSet<u64> = frs.iter().copied().collect();
+        let before = self.entries.len();
+        self.entries
+            .retain(|(key, _), _| !changed.contains(&CompactRecord::unpack_frs(key.file_ref)));
+        let removed = before - self.entries.len();
+        if removed > 0 {
+            self.dirty = true;
+        }
+        removed
+    }
+
+    /// Serialise the table in the sidecar format.
+    ///
+    /// # Errors
+    ///
+    /// Propagates write failures.
+    #[expect(
+        clippy::iter_over_hash_type,
+        reason = "sidecar entry order is irrelevant — `read_from` rebuilds a map"
+    )]
+    pub fn write_to<W: Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(MAGIC)?;
+        out.write_all(&[FORMAT_VERSION])?;
+        out.write_all(&uffs_mft::usize_to_u64(self.entries.len()).to_le_bytes())?;
+        for ((key, kind), digest) in &self.entries {
+            let (span_tag, head) = kind.span.encode();
+            out.write_all(&key.file_ref.to_le_bytes())?;
+            out.write_all(&key.size.to_le_bytes())?;
+            out.write_all(&key.modified.to_le_bytes())?;
+            out.write_all(&[kind.algo.tag(), span_tag])?;
+            out.write_all(&head.to_le_bytes())?;
+            out.write_all(digest)?;
+        }
+        out.flush()
+    }
+
+    /// Read a table written by [`Self::write_to`].  The result is clean.
+    ///
+    /// # Errors
+    ///
+    /// Propagates read failures; a bad magic, unknown version or
+    /// unknown tag is [`io::ErrorKind::InvalidData`].
+    pub fn read_from<R: Read>(mut input: R) -> io::Result<Self> {
+        let mut magic = [0_u8; 4];
+        input.read_exact(&mut magic)?;
+        let version = read_array::<1, _>(&mut input)?;
+        if &magic != MAGIC || version != [FORMAT_VERSION] {
+            return Err(invalid("not a hash-cache sidecar of a known version"));
+        }
+        let count = u64::from_le_bytes(read_array(&mut input)?);
+        let mut entries = HashMap::new();
+        for _ in 0..count {
+            let key = ContentKey {
+                file_ref: u64::from_le_bytes(read_array(&mut input)?),
+                size: u64::from_le_bytes(read_array(&mut input)?),
+                modified: i64::from_le_bytes(read_array(&mut input)?),
+            };
+            let [algo_tag, span_tag] = read_array(&mut input)?;
+            let head = u32::from_le_bytes(read_array(&mut input)?);
+            let digest: Digest = read_array(&mut input)?;
+            let algo = HashAlgo::from_tag(algo_tag).ok_or_else(|| invalid("unknown algorithm"))?;
+            let span = HashSpan::decode(span_tag, head).ok_or_else(|| invalid("unknown span"))?;
+            entries.insert((key, HashKind::new(algo, span)), digest);
+        }
+        Ok(Self {
+            entries,
+            dirty: false,
+        })
+    }
+}
+
+/// Read exactly `N` bytes.
+fn read_array<const N: usize, R: Read>(input: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0_u8; N];
+    input.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// `InvalidData` error with `message`.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// Render `digest` as `<algo>:<lowercase hex>` for display.
+#[must_use]
+pub fn render_digest(algo: HashAlgo, digest: &Digest) -> String {
+    /// Lowercase hex digit for a 0..=15 nibble (out-of-range → '?').
+    fn nibble(value: u8) -> char {
+        char::from_digit(u32::from(value), 16).unwrap_or('?')
+    }
+    let mut out = String::with_capacity(algo.digest_len().saturating_mul(2).saturating_add(8));
+    out.push_str(algo.name());
+    out.push(':');
+    for byte in digest.iter().take(algo.digest_len()) {
+        out.push(nibble(byte >> 4));
+        out.push(nibble(byte & 0x0F));
+    }
+    out
+}
+
+/// Parse a digest as hash lists spell it: `<algo>:<hex>` (the
+/// [`render_digest`] form) or bare hex, whose length picks the algorithm —
+/// 32 digits MD5, 40 SHA-1, 64 SHA-256.  `None` for anything else.
+#[must_use]
+pub fn parse_digest(text: &str) -> Option<(HashAlgo, Digest)> {
+    let (algo, hex) = text.split_once(':').map_or_else(
+        || {
+            let algo = match text.len() {
+                32 => HashAlgo::Md5,
+                40 => HashAlgo::Sha1,
+                64 => HashAlgo::Sha256,
+                _ => return None,
+            };
+            Some((algo, text))
+        },
+        |(name, digits)| HashAlgo::from_name(name).map(|algo| (algo, digits)),
+    )?;
+    if hex.len() != algo.digest_len().saturating_mul(2)
+        || !hex.bytes().all(|byte| byte.is_ascii_hexdigit())
+    {
+        return None;
+    }
+    let mut digest = [0_u8; 32];
+    for (slot, pair) in digest.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        let text_pair = core::str::from_utf8(pair).ok()?;
+        *slot = u8::from_str_radix(text_pair, 16).ok()?;
+    }
+    Some((algo, digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(frs: u64, seq: u16, size: u64) -> ContentKey {
+        ContentKey {
+            file_ref: CompactRecord::pack_file_reference(frs, seq),
+            size,
+            modified: 1_700_000_000_000_000,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_the_sidecar_format() {
+        let mut cache = HashCache::new();
+        let full = HashKind::new(HashAlgo::Blake3, HashSpan::Full);
+        let head = HashKind::new(HashAlgo::Xxh3, HashSpan::Head(4096));
+        cache.insert(key(42, 3, 10), full, HashAlgo::Blake3.digest(b"hello"));
+        cache.insert(key(42, 3, 10), head, HashAlgo::Xxh3.digest(b"hel"));
+        assert!(cache.is_dirty());
+
+        let mut bytes = Vec::new();
+        cache.write_to(&mut bytes).unwrap();
+        let loaded = HashCache::read_from(bytes.as_slice()).unwrap();
+        assert!(!loaded.is_dirty());
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            loaded.get(key(42, 3, 10), full),
+            Some(HashAlgo::Blake3.digest(b"hello"))
+        );
+        assert_eq!(
+            loaded.get(key(42, 3, 10), head),
+            Some(HashAlgo::Xxh3.digest(b"hel"))
+        );
+        // A different size is a different content version.
+        assert_eq!(loaded.get(key(42, 3, 11), full), None);
+
+        HashCache::read_from(&b"nope"[..]).expect_err("not a hash cache");
+    }
+
+    #[test]
+    fn invalidate_drops_every_digest_of_a_changed_slot() {
+        let mut cache = HashCache::new();
+        let full = HashKind::new(HashAlgo::Sha256, HashSpan::Full);
+        cache.insert(key(7, 1, 10), full, HashAlgo::Sha256.digest(b"a"));
+        cache.insert(key(7, 2, 20), full, HashAlgo::Sha256.digest(b"b"));
+        cache.insert(key(8, 1, 10), full, HashAlgo::Sha256.digest(b"c"));
+        cache.mark_clean();
+
+        assert_eq!(cache.invalidate(&[7]), 2);
+        assert!(cache.is_dirty());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(key(8, 1, 10), full).is_some());
+        assert_eq!(cache.invalidate(&[99]), 0);
+    }
+
+    #[test]
+    fn full_digest_prefers_the_strongest_algorithm_and_renders_it() {
+        let mut cache = HashCache::new();
+        let file = key(5, 1, 3);
+        cache.insert(
+            file,
+            HashKind::new(HashAlgo::Xxh3, HashSpan::Full),
+            HashAlgo::Xxh3.digest(b"abc"),
+        );
+        let (algo, digest) = cache.full_digest(file).unwrap();
+        assert_eq!(algo, HashAlgo::Xxh3);
+        let rendered = render_digest(algo, &digest);
+        assert!(rendered.starts_with("xxh3:"));
+        assert_eq!(rendered.len(), "xxh3:".len() + 32);
+
+        cache.insert(
+            file,
+            HashKind::new(HashAlgo::Blake3, HashSpan::Full),
+            HashAlgo::Blake3.digest(b"abc"),
+        );
+        assert_eq!(cache.full_digest(file).unwrap().0, HashAlgo::Blake3);
+        assert_eq!(
+            render_digest(HashAlgo::Blake3, &HashAlgo::Blake3.digest(b"abc")),
+            "blake3:6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+}
@@ -100,6 +100,19 @@ fn run(specs: &[AggregateSpec]) -> AggregateResponse {
     output.response
 }
 
+/// Assert a metric total matches `expected` up to float rounding.
+#[track_caller]
+#[expect(
+    clippy::float_arithmetic,
+    reason = "the tolerance check is a float difference"
+)]
+fn assert_total(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6_f64,
+        "expected {expected}, got {actual}"
+    );
+}
+
 // ── S1G.10: overview preset ──────────────────────────────────────
 
 #[test]
@@ -138,6 +151,18 @@ fn overview_preset_has_size_stats() {
     if let AggregateResultData::Stats { stats, .. } = &stats_result.data {
         assert!(stats.count > 0);
         assert!(stats.sum > 0);
+        let ranks: Vec<_> = stats
+            .percentiles
+            .iter()
+            .map(|(rank, _)| rank.label())
+            .collect();
+        assert_eq!(ranks, ["p50", "p90", "p99"]);
+        assert!(
+            stats
+                .percentiles
+                .windows(2)
+                .all(|pair| matches!(pair, [(_, lo), (_, hi)] if lo <= hi))
+        );
     }
 }
 
@@ -261,6 +286,7 @@ fn datehist_modified_monthly_produces_buckets() {
         field: crate::search::field::FieldId::Modified,
         calendar: CalendarInterval::Month,
         metrics: vec![BucketMetric::Count],
+        time_zone: None,
     });
     spec.label = Some("mod_monthly".to_owned());
     let resp = run(&[spec]);
@@ -711,7 +737,6 @@ fn ancestor_rollup_scoped_to_named_drive() {
         top: 30,
         metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
         sample: None,
-        sub: None,
     });
     let output =
         run_aggregate(&[&drive_c, &drive_d], &[spec], &FinalizeOptions::default()).unwrap();
@@ -948,13 +973,13 @@ fn s3f4_nested_rollup_drive_with_terms_type() {
         top: 10,
         metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
         sample: None,
-        sub: Some(Box::new(AggregateSpec::new(AggregateKind::Terms {
-            field: crate::search::field::FieldId::Type,
-            top: 20,
-            metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
-            sample: None,
-        }))),
-    });
+    })
+    .with_sub(AggregateSpec::new(AggregateKind::Terms {
+        field: crate::search::field::FieldId::Type,
+        top: 20,
+        metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
+        sample: None,
+    }));
     let output = run_aggregate(&[&drive], &[spec], &FinalizeOptions::default()).unwrap();
     let result = &output.response.results[0];
 
@@ -985,6 +1010,123 @@ fn s3f4_nested_rollup_drive_with_terms_type() {
     );
 }
 
+// ── Nested sub-aggregations ─────────────────────────────────────
+
+/// Drive C (`Projects`, 3×rs 2×md toml bin) and drive D (`Photos`, 2×jpg);
+/// the root and folder records on each drive have no extension.
+fn nested_test_drives() -> (DriveCompactIndex, DriveCompactIndex) {
+    let drive_d = build_drive_with_folder(uffs_mft::platform::DriveLetter::D, "Photos", &[
+        ("img_001.jpg", 101, 5000, 8192, TS_JAN_2024),
+        ("img_002.jpg", 102, 7000, 8192, TS_MAR_2024),
+    ]);
+    (build_agg_test_drive(), drive_d)
+}
+
+#[test]
+fn nested_terms_break_down_each_drive() {
+    let (drive_c, drive_d) = nested_test_drives();
+    let spec = parse_agg_spec("terms:drive>terms:extension,top=3").unwrap();
+    let output =
+        run_aggregate(&[&drive_c, &drive_d], &[spec], &FinalizeOptions::default()).unwrap();
+    let result = &output.response.results[0];
+    let AggregateResultData::Buckets { rows, .. } = &result.data else {
+        panic!("expected Buckets, got: {:?}", result.data);
+    };
+
+    let by_key = |key: &str| {
+        rows.iter()
+            .find(|row| row.key == key)
+            .unwrap_or_else(|| panic!("missing {key}"))
+    };
+    let ext_counts = |row: &BucketRow| {
+        let mut counts: Vec<(String, u64)> = row
+            .sub_buckets
+            .iter()
+            .map(|sub| (sub.key.clone(), sub.count))
+            .collect();
+        counts.sort();
+        counts
+    };
+    let drive_c_row = by_key("C:");
+    assert_eq!(drive_c_row.sub_buckets[0].key, "rs");
+    assert_eq!(
+        ext_counts(drive_c_row),
+        [
+            (String::new(), 2),
+            ("md".to_owned(), 2),
+            ("rs".to_owned(), 3)
+        ],
+        "top 3 extensions on C:"
+    );
+    assert_eq!(ext_counts(by_key("D:")), [
+        (String::new(), 2),
+        ("jpg".to_owned(), 2)
+    ]);
+
+    // Shares are relative to the parent bucket.
+    let rs = &drive_c_row.sub_buckets[0];
+    let expected =
+        uffs_mft::u64_to_f64(rs.count) / uffs_mft::u64_to_f64(drive_c_row.count) * 100.0_f64;
+    assert!((rs.share_of_total_count - expected).abs() < 1e-9_f64);
+
+    // Child drill-downs extend the parent's with the child key.
+    let fields: Vec<(&str, &DrilldownValue)> = rs
+        .drilldown
+        .iter()
+        .map(|pred| (pred.field.as_str(), &pred.value))
+        .collect();
+    assert_eq!(fields, [
+        ("drive", &DrilldownValue::String("C:".to_owned())),
+        ("extension", &DrilldownValue::String("rs".to_owned())),
+    ]);
+
+    // Pagination cuts top-level buckets; each keeps its whole subtree.
+    let page = paginate_result(result, &AggregateCursor::new(0, 1)).unwrap();
+    assert_eq!(page.rows.len(), 1);
+    assert_eq!(page.rows[0].sub_buckets.len(), rows[0].sub_buckets.len());
+    assert!(page.has_more);
+}
+
+#[test]
+fn nested_chain_recurses_three_levels() {
+    let (drive_c, drive_d) = nested_test_drives();
+    let spec =
+        parse_agg_spec("terms:drive>datehist:modified,calendar=month>terms:extension").unwrap();
+    let output =
+        run_aggregate(&[&drive_c, &drive_d], &[spec], &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Buckets { rows, .. } = &output.response.results[0].data else {
+        panic!("expected Buckets");
+    };
+    let drive_c_row = rows.iter().find(|row| row.key == "C:").expect("C:");
+
+    // Every month bucket on C: splits its files by extension, and the
+    // extension counts add up to the month's count.
+    let months = &drive_c_row.sub_buckets;
+    assert!(months.len() >= 3, "Jan, Mar and Jun 2024 on C:");
+    for month in months {
+        let ext_total: u64 = month.sub_buckets.iter().map(|ext| ext.count).sum();
+        assert_eq!(ext_total, month.count, "{}", month.key);
+        // A date-histogram level has no drill-down, so nothing below it
+        // can scope a follow-up query either.
+        assert!(month.drilldown.is_empty());
+        assert!(month.sub_buckets.iter().all(|ext| ext.drilldown.is_empty()));
+    }
+    let jan = months
+        .iter()
+        .find(|month| {
+            month
+                .sub_buckets
+                .iter()
+                .any(|ext| ext.key == "rs" && ext.count == 2)
+        })
+        .expect("January holds main.rs and lib.rs");
+    assert!(
+        jan.sub_buckets
+            .iter()
+            .any(|ext| ext.key == "md" && ext.count == 1)
+    );
+}
+
 // ── Regression: ext filter absent on the drive must match NOTHING ──
 //
 // 2026-06-11, found live by the bench suite's count-vs-file-sink cross-check:
@@ -1108,12 +1250,12 @@ fn over_records_counts_exactly_the_handed_set() {
 
     // Hand-pick two records by name — the shape of the row-fed path:
     // the row search decides WHAT matched, aggregation only folds it.
-    let matched: Vec<(uffs_mft::platform::DriveLetter, u32)> = drive
+    let matched: Vec<(uffs_mft::platform::VolumeId, u32)> = drive
         .records
         .iter()
         .enumerate()
         .filter(|(_, rec)| matches!(rec.name(&drive.names), "main.rs" | "data.bin"))
-        .map(|(idx, _)| (drive.letter, uffs_mft::len_to_u32(idx)))
+        .map(|(idx, _)| (drive.volume(), uffs_mft::len_to_u32(idx)))
         .collect();
     assert_eq!(matched.len(), 2, "fixture must contain both probe files");
 
@@ -1148,3 +1290,487 @@ fn over_records_empty_set_counts_zero() {
         output.response.results[0].data
     );
 }
+
+// ── Percentile metrics ───────────────────────────────────────────
+
+#[test]
+fn bucket_percentiles_estimate_file_sizes() {
+    let spec = parse_agg_spec("terms:extension,metrics=count+median+p100").unwrap();
+    let resp = run(&[spec]);
+    let AggregateResultData::Buckets { rows, .. } = &resp.results[0].data else {
+        panic!("expected buckets");
+    };
+    // rs: 1000, 2000, 3000 bytes.
+    let rs = rows.iter().find(|row| row.key == "rs").expect("rs bucket");
+    let [(median_rank, median), (top_rank, top)] = rs.size_percentiles[..] else {
+        panic!("expected two percentiles, got {:?}", rs.size_percentiles);
+    };
+    assert_eq!(median_rank, PercentileRank::MEDIAN);
+    assert!(median.abs_diff(2000) <= 20, "median {median}");
+    assert_eq!(top_rank.label(), "p100");
+    assert_eq!(top, 3000, "p100 is the exact max");
+}
+
+#[test]
+fn stats_percentiles_merge_across_drives() {
+    let c_drive = build_agg_test_drive();
+    let d_drive = build_drive_with_folder(uffs_mft::platform::DriveLetter::D, "Media", &[
+        ("a.bin", 201, 40_000, 40_960, TS_JAN_2024),
+        ("b.bin", 202, 50_000, 53_248, TS_JAN_2024),
+        ("c.bin", 203, 60_000, 61_440, TS_JAN_2024),
+    ]);
+    let spec = parse_agg_spec("stats:size,metrics=p0+p50+p100").unwrap();
+    let output =
+        run_aggregate(&[&c_drive, &d_drive], &[spec], &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Stats { stats, .. } = &output.response.results[0].data else {
+        panic!("expected stats");
+    };
+    // 14 records: 4 zero-sized directories, then 100 … 60 000 bytes; the
+    // median lands on C:'s 800 byte file, so D's files must have merged.
+    let values: Vec<u64> = stats.percentiles.iter().map(|&(_, value)| value).collect();
+    let [low, median, high] = values[..] else {
+        panic!("expected three percentiles, got {values:?}");
+    };
+    assert_eq!(low, 0);
+    assert!(median.abs_diff(800) <= 8, "median {median}");
+    assert_eq!(high, 60_000);
+}
+
+#[test]
+fn timestamp_percentiles_are_rejected() {
+    let drive = build_agg_test_drive();
+    let spec = parse_agg_spec("stats:modified,metrics=p50").unwrap();
+    run_aggregate(&[&drive], &[spec], &FinalizeOptions::default())
+        .expect_err("timestamps cannot take percentiles");
+}
+
+#[test]
+fn pivot_cells_add_up_to_row_and_column_totals() {
+    let spec = parse_agg_spec("pivot:extension,cols=modified@month,metric=total_bytes").unwrap();
+    let resp = run(&[spec]);
+    let AggregateResultData::Pivot { result } = &resp.results[0].data else {
+        panic!("expected pivot");
+    };
+    assert_eq!(result.row_field, "extension");
+    assert_eq!(result.col_field, "modified");
+    assert_total(result.grand_total, 17_400.0_f64);
+    // rs: 2000 + 3000 in January, 1000 in March.
+    let rs = result
+        .rows
+        .iter()
+        .find(|row| row.key == "rs")
+        .expect("rs row");
+    assert_total(rs.total, 6_000.0_f64);
+    let mut cells: Vec<f64> = rs.values.iter().flatten().copied().collect();
+    cells.sort_by(f64::total_cmp);
+    let [march, january] = cells[..] else {
+        panic!("expected two rs cells, got {cells:?}");
+    };
+    assert_total(march, 1_000.0_f64);
+    assert_total(january, 5_000.0_f64);
+    // Calendar columns are in time order and cover every record.
+    let mut sorted = result.columns.clone();
+    sorted.sort();
+    assert_eq!(result.columns, sorted);
+    assert_total(result.column_totals.iter().sum(), 17_400.0_f64);
+    assert_total(result.rows.iter().map(|row| row.total).sum(), 17_400.0_f64);
+}
+
+#[test]
+fn hour_of_day_histogram_lists_every_hour_in_its_zone() {
+    // Jan and Mar files are Saturday 21:20 UTC, Jun files Thursday
+    // 10:40 UTC; the two directories sit at timestamp 0.
+    let spec = parse_agg_spec("datehist:modified,calendar=hour_of_day").unwrap();
+    let resp = run(&[spec]);
+    let AggregateResultData::Buckets { rows, .. } = &resp.results[0].data else {
+        panic!("expected buckets");
+    };
+    let keys: Vec<&str> = rows.iter().map(|row| row.key.as_str()).collect();
+    assert_eq!(keys.len(), 24);
+    assert_eq!((keys[0], keys[23]), ("00", "23"));
+    let count = |hour: usize| rows[hour].count;
+    assert_eq!((count(21), count(10), count(0), count(5)), (5, 2, 2, 0));
+
+    let dow = parse_agg_spec("datehist:modified,calendar=dow,tz=+3").unwrap();
+    let dow_resp = run(&[dow]);
+    let AggregateResultData::Buckets { rows: dow_rows, .. } = &dow_resp.results[0].data else {
+        panic!("expected buckets");
+    };
+    let days: Vec<(&str, u64)> = dow_rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.count))
+        .collect();
+    assert_eq!(days, [
+        ("Mon", 2),
+        ("Tue", 0),
+        ("Wed", 0),
+        ("Thu", 2),
+        ("Fri", 0),
+        ("Sat", 0),
+        ("Sun", 5),
+    ]);
+}
+
+#[test]
+fn heatmap_is_a_full_weekday_by_hour_grid() {
+    let mut spec = parse_agg_spec("heatmap:modified").unwrap();
+    spec.apply_default_tz(uffs_time::TimeZone::fixed(3 * 3600));
+    let resp = run(&[spec]);
+    let AggregateResultData::Pivot { result } = &resp.results[0].data else {
+        panic!("expected pivot");
+    };
+    assert!(result.heatmap);
+    assert_eq!(result.time_zone.as_deref(), Some("UTC+03:00"));
+    assert_eq!(result.rows.len(), 7);
+    assert_eq!(result.columns.len(), 24);
+    assert_eq!(result.rows[6].key, "Sun");
+    assert_eq!(
+        result.rows[6].values[0],
+        Some(5.0_f64),
+        "Sat 21:20 UTC is Sun 00:20"
+    );
+    assert_eq!(
+        result.rows[3].values[13],
+        Some(2.0_f64),
+        "Thu 10:40 UTC is 13:40"
+    );
+    assert_eq!(result.rows[2].values[13], None);
+    assert_total(result.grand_total, 9.0_f64);
+}
+
+#[test]
+fn pivot_rolls_up_beyond_top_on_both_axes() {
+    let spec = parse_agg_spec("pivot:extension,cols=size,top=2,cols_top=1").unwrap();
+    let resp = run(&[spec]);
+    let AggregateResultData::Pivot { result } = &resp.results[0].data else {
+        panic!("expected pivot");
+    };
+    assert_eq!(result.rows.len(), 3, "two kept rows plus (other)");
+    assert_eq!(result.rows[2].key, pivot::OTHER_KEY);
+    assert_eq!(result.columns.len(), 2, "one kept column plus (other)");
+    assert_eq!(result.columns[1], pivot::OTHER_KEY);
+    assert!(result.total_rows > 2);
+    assert!(result.total_cols > 1);
+    // rs (3 files) ranks first; counts still cover every record.
+    assert_eq!(result.rows[0].key, "rs");
+    assert_total(result.rows[0].total, 3.0_f64);
+    assert_total(
+        result.rows.iter().map(|row| row.total).sum(),
+        result.grand_total,
+    );
+}
+
+#[test]
+fn pivot_merges_cells_across_drives() {
+    let c_drive = build_agg_test_drive();
+    let d_drive = build_drive_with_folder(uffs_mft::platform::DriveLetter::D, "Media", &[
+        ("a.rs", 201, 40_000, 40_960, TS_JAN_2024),
+        ("b.bin", 202, 50_000, 53_248, TS_JAN_2024),
+    ]);
+    let spec = parse_agg_spec("pivot:extension,cols=drive,metric=total_bytes").unwrap();
+    let output =
+        run_aggregate(&[&c_drive, &d_drive], &[spec], &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Pivot { result } = &output.response.results[0].data else {
+        panic!("expected pivot");
+    };
+    let col = |name: &str| result.columns.iter().position(|c| c == name).expect(name);
+    let rs = result
+        .rows
+        .iter()
+        .find(|row| row.key == "rs")
+        .expect("rs row");
+    assert_eq!(rs.values[col("C:")], Some(6_000.0_f64));
+    assert_eq!(rs.values[col("D:")], Some(40_000.0_f64));
+    let md = result
+        .rows
+        .iter()
+        .find(|row| row.key == "md")
+        .expect("md row");
+    assert_eq!(md.values[col("D:")], None, "no md files on D:");
+}
+
+#[test]
+fn pivot_rejects_axis_bucketing_the_field_cannot_take() {
+    let drive = build_agg_test_drive();
+    let spec = parse_agg_spec("pivot:size@age,cols=extension").unwrap();
+    run_aggregate(&[&drive], &[spec], &FinalizeOptions::default())
+        .expect_err("size cannot bucket by age");
+}
+
+#[test]
+fn pivot_exports_csv_matrix_and_json() {
+    let spec = parse_agg_spec("pivot:extension,cols=drive").unwrap();
+    let resp = run(&[spec]);
+    let mut buf = Vec::new();
+    export_results(&resp, ExportFormat::Csv, &mut buf).unwrap();
+    let csv = String::from_utf8(buf).unwrap();
+    assert!(csv.contains("extension\\drive,C:,total"), "{csv}");
+    assert!(csv.contains("\nrs,3,3\n"), "{csv}");
+    assert!(csv.contains("\ntotal,9,9\n"), "{csv}");
+
+    let mut json = Vec::new();
+    export_results(&resp, ExportFormat::Json, &mut json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(parsed[0]["kind"], "pivot");
+    assert_eq!(parsed[0]["metric"], "count");
+    assert_eq!(parsed[0]["columns"][0], "C:");
+    assert_eq!(parsed[0]["grand_total"], 9.0_f64);
+}
+
+/// Drive C `Docs` folder with copy clutter: four spellings of `report.docx`,
+/// two of `notes.txt`, and a lone `budget.xlsx`.
+fn similar_names_drive() -> DriveCompactIndex {
+    build_drive_with_folder(uffs_mft::platform::DriveLetter::C, "Docs", &[
+        ("report.docx", 101, 1000, 4096, TS_JAN_2024),
+        ("report (1).docx", 102, 1000, 4096, TS_JAN_2024),
+        ("Copy of report.docx", 103, 1200, 4096, TS_MAR_2024),
+        ("report_final_v2.docx", 104, 1500, 4096, TS_JUN_2024),
+        ("notes.txt", 105, 2_000, 4096, TS_JAN_2024),
+        ("notes - Copy.txt", 106, 2_000, 4096, TS_MAR_2024),
+        ("budget.xlsx", 107, 300, 4096, TS_JAN_2024),
+    ])
+}
+
+#[test]
+fn similar_names_clusters_copies_within_a_directory() {
+    let drive = similar_names_drive();
+    let spec = parse_agg_spec("similar_names:name,sample=2").unwrap();
+    let output = run_aggregate(&[&drive], &[spec], &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Buckets {
+        rows,
+        total_groups,
+        exact,
+        ..
+    } = &output.response.results[0].data
+    else {
+        panic!("expected buckets");
+    };
+    assert_eq!(*total_groups, 2, "budget.xlsx has no near-duplicates");
+    assert!(exact);
+    assert_eq!(rows[0].key, "C:\\Docs\\report.docx");
+    assert_eq!(rows[0].count, 4);
+    assert_eq!(rows[0].total_bytes, 4_700);
+    assert_eq!(rows[0].sample_rows.len(), 2);
+    assert_eq!(rows[1].key, "C:\\Docs\\notes.txt");
+    assert_eq!(rows[1].count, 2);
+}
+
+#[test]
+fn similar_names_across_selection_ranks_by_bytes_and_skips_exact_names() {
+    let c_drive = similar_names_drive();
+    let d_drive = build_drive_with_folder(uffs_mft::platform::DriveLetter::D, "Backup", &[
+        ("report - Copy.docx", 201, 9_000, 12_288, TS_JUN_2024),
+        ("budget.xlsx", 202, 300, 4096, TS_JAN_2024),
+    ]);
+    let specs = [
+        parse_agg_spec("similar_names:name,scope=dir").unwrap(),
+        parse_agg_spec("similar:scope=all,rank=bytes,top=1").unwrap(),
+    ];
+    let output = run_aggregate(&[&c_drive, &d_drive], &specs, &FinalizeOptions::default()).unwrap();
+    let buckets = |idx: usize| {
+        let AggregateResultData::Buckets {
+            rows, other_count, ..
+        } = &output.response.results[idx].data
+        else {
+            panic!("expected buckets");
+        };
+        (rows.clone(), *other_count)
+    };
+    // Per directory, the lone copy on D: clusters with nothing.
+    let (per_dir, _) = buckets(0);
+    assert_eq!(per_dir.len(), 2);
+    assert!(per_dir.iter().all(|row| row.key.starts_with("C:\\Docs\\")));
+    // Across drives it joins the C: reports and outweighs the notes; the
+    // two identically named budget files are not a near-duplicate cluster.
+    let (selection, other_count) = buckets(1);
+    assert_eq!(selection.len(), 1);
+    assert_eq!(selection[0].key, "report.docx");
+    assert_eq!(selection[0].count, 5);
+    assert_eq!(selection[0].total_bytes, 13_700);
+    assert_eq!(other_count, 2, "notes cluster rolled past top=1");
+}
+
+/// `C:\Reports\` with year-prefixed, camera-style and plain names.
+fn regex_key_drive() -> DriveCompactIndex {
+    build_drive_with_folder(uffs_mft::platform::DriveLetter::C, "Reports", &[
+        ("2023-budget.xlsx", 101, 1_000, 4096, TS_JAN_2024),
+        ("2023-review.pdf", 102, 3_000, 4096, TS_MAR_2024),
+        ("2024-budget.xlsx", 103, 2_000, 4096, TS_JUN_2024),
+        ("IMG_0042.jpg", 104, 5_000, 8192, TS_JUN_2024),
+        ("notes.txt", 105, 100, 4096, TS_JAN_2024),
+    ])
+}
+
+#[test]
+fn regex_terms_key_on_the_first_capture_group() {
+    let drive = regex_key_drive();
+    let specs = [
+        parse_agg_spec(r#"terms:regex(name, "^(\d{4})-(budget|review)"),sample=1"#).unwrap(),
+        parse_agg_spec(r#"terms:regex(name, "^(\d{4})-"),unmatched=true,top=2"#).unwrap(),
+    ];
+    let options = FinalizeOptions {
+        query_predicates: vec![DrilldownPredicate {
+            field: "size".to_owned(),
+            op: "gte".to_owned(),
+            value: DrilldownValue::String("1".to_owned()),
+        }],
+        ..FinalizeOptions::default()
+    };
+    let output = run_aggregate(&[&drive], &specs, &options).unwrap();
+    let buckets = |idx: usize| {
+        let AggregateResultData::Buckets {
+            field,
+            rows,
+            other_count,
+            total_groups,
+            ..
+        } = &output.response.results[idx].data
+        else {
+            panic!("expected buckets");
+        };
+        (field.clone(), rows.clone(), *other_count, *total_groups)
+    };
+
+    // Unmatched records are skipped by default.
+    let (field, rows, other_count, total_groups) = buckets(0);
+    assert_eq!(field, r#"regex(name, "^(\d{4})-(budget|review)")"#);
+    let keys: Vec<(&str, u64, u64)> = rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.count, row.total_bytes))
+        .collect();
+    assert_eq!(keys, [("2023", 2, 4_000), ("2024", 1, 2_000)]);
+    assert_eq!((other_count, total_groups), (0, 2));
+    assert_eq!(rows[0].sample_rows.len(), 1);
+    // The drill-down keeps the query's predicates and pins the group.
+    assert_eq!(rows[0].drilldown.len(), 2);
+    assert_eq!(rows[0].drilldown[0].field, "size");
+    assert_eq!(rows[0].drilldown[1].field, "name");
+    assert_eq!(rows[0].drilldown[1].op, "regex");
+    assert_eq!(
+        rows[0].drilldown[1].value,
+        DrilldownValue::String("^(2023)-(budget|review)".to_owned())
+    );
+
+    // `unmatched=true` collects the rest (the two directories included);
+    // `top` cuts after ranking.
+    let (_, unmatched_rows, unmatched_other, unmatched_groups) = buckets(1);
+    let unmatched_keys: Vec<(&str, u64)> = unmatched_rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.count))
+        .collect();
+    assert_eq!(unmatched_keys, [("(unmatched)", 4), ("2023", 2)]);
+    assert_eq!((unmatched_other, unmatched_groups), (1, 3));
+    assert_eq!(unmatched_rows[0].drilldown[1].op, "not_regex");
+    assert_eq!(
+        unmatched_rows[0].drilldown[1].value,
+        DrilldownValue::String(r"^(\d{4})-".to_owned())
+    );
+
+    // Pages walk the ranked buckets like any other terms result.
+    let cursor = AggregateCursor::new(1, 1).next();
+    let page = paginate_result(&output.response.results[1], &cursor).unwrap();
+    assert_eq!(page.rows[0].key, "2023");
+    assert!(!page.has_more);
+}
+
+#[test]
+fn regex_terms_on_paths_nest_and_merge_across_drives() {
+    let (drive_c, drive_d) = nested_test_drives();
+    let spec =
+        parse_agg_spec(r#"terms:regex(path, "^\w:\\([^\\]+)\\")>terms:extension,top=1"#).unwrap();
+    let output =
+        run_aggregate(&[&drive_c, &drive_d], &[spec], &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Buckets { rows, .. } = &output.response.results[0].data else {
+        panic!("expected buckets");
+    };
+    let keys: Vec<(&str, u64, &str)> = rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.count, row.sub_buckets[0].key.as_str()))
+        .collect();
+    assert_eq!(keys, [("Projects", 7, "rs"), ("Photos", 2, "jpg")]);
+    assert_eq!(
+        rows[1].sub_buckets[0].drilldown[0].value,
+        DrilldownValue::String(r"^\w:\\(Photos)\\".to_owned())
+    );
+}
+
+#[test]
+fn regex_terms_on_paths_see_the_host_of_a_hosted_volume() {
+    let mut drive = build_agg_test_drive();
+    drive.host = Some("srv01".parse().unwrap());
+    let spec =
+        parse_agg_spec(r#"terms:regex(path, "^srv01.*[\\/]([^\\/]+)[\\/][^\\/]+$")"#).unwrap();
+    let output = run_aggregate(&[&drive], &[spec], &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Buckets { rows, .. } = &output.response.results[0].data else {
+        panic!("expected buckets");
+    };
+    assert_eq!((rows[0].key.as_str(), rows[0].count), ("Projects", 7));
+}
+
+#[test]
+fn drive_terms_keep_volumes_sharing_a_letter_apart() {
+    let local = build_agg_test_drive();
+    let mut srv01 = build_agg_test_drive();
+    srv01.host = Some("srv01".parse().unwrap());
+    let mut srv02 = build_agg_test_drive();
+    srv02.host = Some("srv02".parse().unwrap());
+    srv02.serial = Some(uffs_mft::platform::VolumeSerial::parse("1A2B-3C4D").unwrap());
+    let spec = parse_agg_spec("terms:drive").unwrap();
+    let output = run_aggregate(
+        &[&local, &srv01, &srv02],
+        &[spec],
+        &FinalizeOptions::default(),
+    )
+    .unwrap();
+    let AggregateResultData::Buckets { rows, .. } = &output.response.results[0].data else {
+        panic!("expected buckets");
+    };
+    let mut keys: Vec<(&str, u64)> = rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.count))
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(keys, [
+        ("C:", 9),
+        ("srv01\\C:", 9),
+        ("srv02\\C:#1A2B-3C4D", 9)
+    ]);
+}
+
+#[test]
+fn name_years_preset_buckets_years_in_names() {
+    let drive = regex_key_drive();
+    let specs = parse_and_expand_agg_specs(&["preset:name_years"]).unwrap();
+    let output = run_aggregate(&[&drive], &specs, &FinalizeOptions::default()).unwrap();
+    let AggregateResultData::Buckets { rows, .. } = &output.response.results[0].data else {
+        panic!("expected buckets");
+    };
+    let keys: Vec<(&str, u64)> = rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.count))
+        .collect();
+    assert_eq!(keys, [("(unmatched)", 4), ("2023", 2), ("2024", 1)]);
+}
+
+#[test]
+fn computed_field_drives_stats_and_range() {
+    let fields = crate::search::computed::ComputedFields::parse(["slack=allocated-size"]).unwrap();
+    let slack = parse_agg_spec_with("stats:slack", &fields).unwrap();
+    let range = parse_agg_spec_with("range:SLACK,bins=0..1000+1000..", &fields).unwrap();
+    let resp = run(&[slack, range]);
+    let AggregateResultData::Stats { field, stats } = &resp.results[0].data else {
+        panic!("expected stats");
+    };
+    assert_eq!(field, "slack");
+    // Per-file slack: 2096 + 1096 + 3096 + 12 + 224 + 412 + 6384; the two
+    // directories contribute 0.
+    assert_eq!((stats.count, stats.sum, stats.max), (9, 13_320, 6384));
+    let AggregateResultData::Buckets { rows, .. } = &resp.results[1].data else {
+        panic!("expected buckets");
+    };
+    let mut counts: Vec<u64> = rows.iter().map(|row| row.count).collect();
+    counts.sort_unstable();
+    assert_eq!(counts, [4, 5]);
+    // Unknown names still fail like any other field.
+    parse_agg_spec_with("stats:nope", &fields).expect_err("undefined field");
+}
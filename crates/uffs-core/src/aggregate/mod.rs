@@ -13,7 +13,8 @@
 //! AggregateSpec  ──▶  AggregatePlan  ──▶  AggregateEngine::run()
 //!                      (compile)           │
 //!                                          ├─ per-drive parallel scan
-//!                                          ├─ accumulators (feed/merge)
+//!                                          ├─ accumulators (feed/merge,
+//!                                          │   nested per bucket)
 //!                                          └─ finalize → AggregateResult
 //! ```
 //!
@@ -25,17 +26,28 @@ pub mod buckets;
 pub mod cache;
 pub mod duplicates;
 pub mod export;
+/// Per-record value, timestamp and group-key extraction.
+mod extract;
 pub mod finalize;
+pub mod growth;
+pub mod hash_cache;
+/// Per-bucket child accumulators for nested sub-aggregations.
+mod nested;
 pub mod pagination;
 pub mod parser;
+pub mod parser_computed;
 pub mod parser_error;
+pub mod percentile;
+pub mod pivot;
 pub(crate) mod planner;
 pub mod presets;
+pub mod regex_key;
 pub mod rollup;
 /// Per-bucket sample heap for tracking top-N records.
 pub(crate) mod sample_heap;
+pub mod similar;
 pub mod spec;
-/// Duplicate verification (first-bytes / SHA-256).
+/// Duplicate verification (first-bytes / SHA-256 / staged xxh3 and BLAKE3).
 pub mod verify;
 
 // Re-export core public types.
@@ -47,16 +59,24 @@ pub use export::{ExportFormat, export_results};
 pub use finalize::{
     AggregateResponse, BucketRow, DrilldownPredicate, DrilldownValue, FinalizeOptions, SampleRow,
 };
+pub use growth::{GrowthRank, GrowthResult, GrowthSpec, compute_growth};
+pub use hash_cache::{ContentKey, HashAlgo, HashCache, HashKind, HashSpan, HashStore};
 pub use pagination::{AggregateCursor, PaginatedBuckets, paginate_result};
-pub use parser::{parse_agg_spec, parse_and_expand_agg_specs};
+pub use parser::{parse_agg_spec, parse_and_expand_agg_specs, parse_growth_spec};
+pub use parser_computed::parse_agg_spec_with;
 pub use parser_error::ParseAggSpecError;
+pub use percentile::QuantileSketch;
+pub use pivot::{PivotAccumulator, PivotResult, PivotRow};
 pub use planner::AggregatePlan;
 pub use presets::AggregatePreset;
 use rayon::prelude::*;
+pub use regex_key::RegexTermsAccumulator;
 pub use rollup::RollupAccumulator;
+pub use similar::{SimilarNamesAccumulator, normalize_name};
 pub use spec::{
-    AggregateKind, AggregateSpec, BucketMetric, CalendarInterval, DuplicateVerify, RollupMode,
-    ScalarMetric, TopHitsSpec,
+    AggregateKind, AggregateSpec, AxisBuckets, BucketMetric, CalendarInterval, DuplicateVerify,
+    PercentileRank, PivotAxis, RegexKey, RollupMode, ScalarMetric, SimilarRank, SimilarScope,
+    TopHitsSpec,
 };
 pub use verify::{DuplicateVerifier, FileReader, VerificationBudget, VerificationSummary};
 
@@ -597,33 +617,33 @@ pub fn run_aggregate_over_records(
     drives: &[&DriveCompactIndex],
     specs: &[AggregateSpec],
     options: &FinalizeOptions,
-    matched: &[(uffs_mft::platform::DriveLetter, u32)],
+    matched: &[(uffs_mft::platform::VolumeId, u32)],
 ) -> Result<AggregateOutput, AggregateError> {
     let start = std::time::Instant::now();
     let plan = AggregatePlan::compile(specs)?;
     let ext_map = ExtensionMap::build(drives);
     let mut accumulators = plan.create_accumulators();
 
-    // Letter → (ordinal, drive) for O(1) row dispatch.  Ordinals index
+    // Volume → (ordinal, drive) for O(1) row dispatch.  Ordinals index
     // into `drives`, matching what the scan entry points feed and what
     // finalize expects for drive-keyed groupings.
-    let by_letter: std::collections::HashMap<
-        uffs_mft::platform::DriveLetter,
+    let by_volume: std::collections::HashMap<
+        uffs_mft::platform::VolumeId,
         (u8, &DriveCompactIndex),
     > = drives
         .iter()
         .enumerate()
         .map(|(ordinal, drive)| {
             (
-                drive.letter,
+                drive.volume(),
                 (u8::try_from(ordinal).unwrap_or(u8::MAX), *drive),
             )
         })
         .collect();
 
     let mut fed: u64 = 0;
-    for &(letter, record_idx) in matched {
-        let Some(&(ordinal, drive)) = by_letter.get(&letter) else {
+    for &(volume, record_idx) in matched {
+        let Some(&(ordinal, drive)) = by_volume.get(&volume) else {
             // Row from a drive outside the aggregation scope (e.g. a
             // `--drives` subset) — scoping, not an error.
             continue;
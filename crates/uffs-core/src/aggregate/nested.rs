@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Nested sub-aggregations.
+//!
+//! A bucketed [`AggregateSpec`] with a [`AggregateSpec::sub`] child keeps
+//! one child [`GroupAccumulator`] per parent bucket, fed in the same scan
+//! pass as the parent. During finalization each surviving parent row
+//! finalizes its child and stores the rows in [`BucketRow::sub_buckets`];
+//! children of children recurse the same way.
+//!
+//! Child rows are relative to their parent bucket: share-of-total is a
+//! share of the parent row, and drill-down predicates extend the parent
+//! row's predicates with the child bucket key.
+
+use std::collections::HashMap;
+
+use super::accumulators::GroupAccumulator;
+use super::finalize::{AggregateResultData, BucketRow, FinalizeOptions, finalize_one};
+use super::spec::AggregateSpec;
+use crate::compact::{CompactRecord, DriveCompactIndex};
+
+/// Per-bucket child accumulators for one nesting level.
+#[derive(Debug, Clone)]
+pub(crate) struct NestedAccumulators {
+    /// The child spec (including its own `sub`, if any).
+    spec: AggregateSpec,
+    /// Child accumulators keyed by the parent's bucket key.
+    buckets: HashMap<u64, GroupAccumulator>,
+}
+
+impl NestedAccumulators {
+    /// Empty per-bucket state for the child `spec`.
+    pub(crate) fn new(spec: &AggregateSpec) -> Self {
+        Self {
+            spec: spec.clone(),
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Feed a record that landed in the parent bucket `key`.
+    #[inline]
+    pub(crate) fn feed(
+        &mut self,
+        key: u64,
+        record: &CompactRecord,
+        drive: &DriveCompactIndex,
+        idx: usize,
+        drive_ordinal: u8,
+        ext_map: &super::ExtensionMap,
+    ) {
+        let spec = &self.spec;
+        self.buckets
+            .entry(key)
+            .or_insert_with(|| GroupAccumulator::from_spec(spec))
+            .feed(record, drive, idx, drive_ordinal, ext_map);
+    }
+
+    /// Merge another drive's per-bucket state into this one.
+    #[expect(
+        clippy::iter_over_hash_type,
+        reason = "per-key merge is order-independent: each entry is merged into self by key"
+    )]
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (key, theirs) in &other.buckets {
+            self.buckets
+                .entry(*key)
+                .and_modify(|ours| ours.merge(theirs))
+                .or_insert_with(|| theirs.clone());
+        }
+    }
+}
+
+/// Attaches child rows to parent rows while a bucketed accumulator is
+/// finalized. A no-op when the accumulator has no nested child.
+pub(super) struct Nester<'ctx> {
+    /// The parent's nested state, if any.
+    nested: Option<NestedAccumulators>,
+    /// Drives the scan ran over (for key resolution and samples).
+    drives: &'ctx [&'ctx DriveCompactIndex],
+    /// Cross-drive extension map.
+    ext_map: &'ctx super::ExtensionMap,
+}
+
+impl<'ctx> Nester<'ctx> {
+    /// Wrap the parent's nested state for finalization.
+    pub(super) const fn new(
+        nested: Option<NestedAccumulators>,
+        drives: &'ctx [&'ctx DriveCompactIndex],
+        ext_map: &'ctx super::ExtensionMap,
+    ) -> Self {
+        Self {
+            nested,
+            drives,
+            ext_map,
+        }
+    }
+
+    /// Finalize the child of the parent bucket `key` into
+    /// `row.sub_buckets`.
+    ///
+    /// Call after `row.drilldown` is set: the child's drill-downs extend
+    /// it. Parents without drill-downs (histograms, rollups) cannot scope
+    /// a follow-up query, so their children carry none either.
+    pub(super) fn attach(&mut self, row: &mut BucketRow, key: u64) {
+        let Some(child) = self
+            .nested
+            .as_mut()
+            .and_then(|nested| nested.buckets.remove(&key))
+        else {
+            return;
+        };
+        let options = FinalizeOptions {
+            query_predicates: row.drilldown.clone(),
+            ..FinalizeOptions::default()
+        };
+        let result = finalize_one(
+            child,
+            row.count,
+            row.total_bytes,
+            &options,
+            self.drives,
+            self.ext_map,
+        );
+        row.sub_buckets = into_rows(result.data);
+        if row.drilldown.is_empty() {
+            clear_drilldowns(&mut row.sub_buckets);
+        }
+    }
+}
+
+/// The bucket rows of a finalized child. Children are always bucketed
+/// (the planner rejects anything else), so other variants are empty.
+#[expect(
+    clippy::wildcard_enum_match_arm,
+    reason = "non-bucket result variants intentionally collapse to empty rows"
+)]
+fn into_rows(data: AggregateResultData) -> Vec<BucketRow> {
+    match data {
+        AggregateResultData::Buckets { rows, .. } | AggregateResultData::Rollup { rows, .. } => {
+            rows
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Drop the drill-downs of `rows` and their descendants.
+fn clear_drilldowns(rows: &mut [BucketRow]) {
+    for row in rows {
+        row.drilldown.clear();
+        clear_drilldowns(&mut row.sub_buckets);
+    }
+}
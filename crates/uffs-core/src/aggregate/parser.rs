@@ -6,22 +6,40 @@
 //! Parses strings like:
 //! - `count`
 //! - `stats:size`
+//! - `stats:size,metrics=p50+p90+p99+max`
 //! - `terms:extension,top=50,metrics=count+total_bytes`
+//! - `terms:regex(name, "^(\d{4})-"),unmatched=true` (first capture group as
+//!   the key)
 //! - `hist:size,interval=1048576`
 //! - `datehist:modified,calendar=month`
+//! - `datehist:modified,calendar=hour_of_day,tz=+2`
 //! - `range:size,bins=0..1024+1024..1048576+1048576..`
 //! - `rollup:path,depth=1,top=30`
 //! - `duplicates:size+name,verify=none,top=100,sample=2`
 //! - `preset:overview`
 //! - `missing:extension`
 //! - `distinct:extension`
+//! - `pivot:extension,cols=modified@year,metric=total_bytes,top=20,cols_top=10`
+//! - `heatmap:modified,metric=count,tz=-5` (weekday × hour-of-day pivot)
+//! - `similar_names:name,scope=all,rank=bytes,top=50,sample=3`
+//! - `growth:path,depth=2,top=20,rank=rel` (diff only — see
+//!   [`parse_growth_spec`])
+//!
+//! `>` nests bucketed specs: `terms:drive>terms:extension,top=10` runs the
+//! extension terms once per drive bucket (see [`AggregateSpec::sub`]).
 
 use core::num::ParseIntError;
 
+use uffs_time::TimeZone;
+
+use super::growth::{GrowthRank, GrowthSpec};
 use super::parser_error::ParseAggSpecError;
+use super::pivot::{DEFAULT_COLS_TOP, DEFAULT_ROWS_TOP, PivotAxis};
+use super::regex_key::{RegexKey, split_unquoted};
+use super::similar::{DEFAULT_MAX_GROUPS, DEFAULT_SAMPLE, DEFAULT_TOP};
 use super::spec::{
-    AggregateKind, AggregateSpec, BucketMetric, CalendarInterval, DuplicateVerify, RollupMode,
-    ScalarMetric, TopHitsSpec,
+    AggregateKind, AggregateSpec, AxisBuckets, BucketMetric, CalendarInterval, DuplicateVerify,
+    PercentileRank, RollupMode, ScalarMetric, SimilarRank, SimilarScope, TopHitsSpec,
 };
 use crate::search::field::FieldId;
 
@@ -46,6 +64,9 @@ fn invalid_int(
 
 /// Parse a single `--agg` specification string into an `AggregateSpec`.
 ///
+/// A `parent>child` chain parses each segment and nests the child under
+/// the parent; every segment must be a bucketed kind.
+///
 /// # Errors
 ///
 /// Returns a [`ParseAggSpecError`] variant when the spec is
@@ -53,6 +74,29 @@ fn invalid_int(
 /// pre-Phase-5d `Result<_, String>` payloads so any operator-facing
 /// log output (daemon `tracing::warn!` and CLI stderr) is unchanged.
 pub fn parse_agg_spec(input: &str) -> Result<AggregateSpec, ParseAggSpecError> {
+    let Some((head, tail)) = split_unquoted(input, '>') else {
+        return parse_level(input);
+    };
+    nest(parse_level(head)?, parse_agg_spec(tail)?, input)
+}
+
+/// Nest `child` under `parent`, rejecting kinds without buckets.
+pub(super) fn nest(
+    parent: AggregateSpec,
+    child: AggregateSpec,
+    input: &str,
+) -> Result<AggregateSpec, ParseAggSpecError> {
+    if parent.kind.is_bucketed() && child.kind.is_bucketed() {
+        Ok(parent.with_sub(child))
+    } else {
+        Err(ParseAggSpecError::NotBucketed {
+            spec: input.trim().to_owned(),
+        })
+    }
+}
+
+/// Parse one level of a spec (no `>`).
+pub(super) fn parse_level(input: &str) -> Result<AggregateSpec, ParseAggSpecError> {
     let trimmed = input.trim();
 
     // Split on first ':'
@@ -81,12 +125,57 @@ pub fn parse_agg_spec(input: &str) -> Result<AggregateSpec, ParseAggSpecError> {
 
         "distinct" => parse_distinct(rest),
 
+        "pivot" | "crosstab" => parse_pivot(rest),
+
+        "heatmap" => parse_heatmap(rest),
+
+        "similar_names" | "similar" => parse_similar_names(rest),
+
+        "growth" => Err(ParseAggSpecError::GrowthNeedsBaseline),
+
         _ => Err(ParseAggSpecError::UnknownKind {
             kind: kind_str.to_owned(),
         }),
     }
 }
 
+/// Parse a `growth[:path][,depth=N][,top=N][,rank=abs|rel]` spec, or
+/// return `None` when `input` is not a `growth` spec.
+///
+/// Growth compares two indexes, so it cannot run in the single-pass scan
+/// [`parse_agg_spec`] feeds (which rejects it); the caller holding both
+/// sides evaluates it with [`super::growth::compute_growth`].
+///
+/// # Errors
+///
+/// [`ParseAggSpecError::InvalidIntOption`] for a bad `depth` / `top`, or
+/// [`ParseAggSpecError::UnknownGrowthRank`].
+#[must_use]
+pub fn parse_growth_spec(input: &str) -> Option<Result<GrowthSpec, ParseAggSpecError>> {
+    let trimmed = input.trim();
+    let (kind_str, rest) = trimmed.split_once(':').unwrap_or((trimmed, ""));
+    (kind_str == "growth").then(|| parse_growth(rest))
+}
+
+/// Parse "path,depth=N,top=N,rank=R" → growth spec.
+fn parse_growth(rest: &str) -> Result<GrowthSpec, ParseAggSpecError> {
+    let mut spec = GrowthSpec::default();
+    for (key, val) in parse_options(rest) {
+        match key {
+            "depth" => spec.depth = val.parse().map_err(invalid_int("depth", val.to_owned()))?,
+            "top" => spec.top = val.parse().map_err(invalid_int("top", val.to_owned()))?,
+            "rank" | "by" => {
+                spec.rank =
+                    GrowthRank::parse(val).ok_or_else(|| ParseAggSpecError::UnknownGrowthRank {
+                        val: val.to_owned(),
+                    })?;
+            }
+            _ => {}
+        }
+    }
+    Ok(spec)
+}
+
 /// Parse key=value options from a comma-separated string.
 fn parse_options(input: &str) -> Vec<(&str, &str)> {
     input
@@ -137,12 +226,18 @@ fn parse_stats(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
 
 /// Parse "field,top=N,metrics=M+M" → Terms spec.
 fn parse_terms(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
-    let (field_str, opts_str) = split_field_and_options(rest);
-    let field = parse_field(field_str)?;
+    let (regex_key, field_str, opts_str) = RegexKey::parse_prefix(rest).transpose()?.map_or_else(
+        || {
+            let (plain_field, plain_opts) = split_field_and_options(rest);
+            (None, plain_field, plain_opts)
+        },
+        |(key, tail)| (Some(key), "", tail),
+    );
     let opts = parse_options(opts_str);
     let mut top: u16 = 20;
     let mut metrics = Vec::new();
     let mut sample_count: u8 = 0;
+    let mut unmatched = false;
 
     for (key, val) in &opts {
         match *key {
@@ -157,6 +252,13 @@ fn parse_terms(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
                     .parse()
                     .map_err(invalid_int("sample", (*val).to_owned()))?;
             }
+            "unmatched" if regex_key.is_some() => {
+                unmatched = val
+                    .parse()
+                    .map_err(|_err| ParseAggSpecError::InvalidUnmatched {
+                        val: (*val).to_owned(),
+                    })?;
+            }
             _ => {}
         }
     }
@@ -167,11 +269,20 @@ fn parse_terms(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
 
     let sample = (sample_count > 0).then(|| TopHitsSpec::with_count(sample_count));
 
-    Ok(AggregateSpec::new(AggregateKind::Terms {
-        field,
-        top,
-        metrics,
-        sample,
+    Ok(AggregateSpec::new(match regex_key {
+        Some(key) => AggregateKind::RegexTerms {
+            key,
+            top,
+            metrics,
+            sample,
+            unmatched,
+        },
+        None => AggregateKind::Terms {
+            field: parse_field(field_str)?,
+            top,
+            metrics,
+            sample,
+        },
     }))
 }
 
@@ -207,13 +318,21 @@ fn parse_histogram(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
     }))
 }
 
-/// Parse "field,calendar=INTERVAL" → `DateHistogram` spec.
+/// Parse a `tz=` value: an IANA zone name or an offset from UTC.
+fn parse_tz(val: &str) -> Result<TimeZone, ParseAggSpecError> {
+    TimeZone::parse(val).ok_or_else(|| ParseAggSpecError::InvalidTimeZone {
+        val: val.to_owned(),
+    })
+}
+
+/// Parse "field,calendar=INTERVAL,tz=OFFSET" → `DateHistogram` spec.
 fn parse_date_histogram(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
     let (field_str, opts_str) = split_field_and_options(rest);
     let field = parse_field(field_str)?;
     let opts = parse_options(opts_str);
     let mut calendar = CalendarInterval::Month;
     let mut metrics = vec![BucketMetric::Count, BucketMetric::TotalBytes];
+    let mut time_zone = None;
 
     for (key, val) in &opts {
         match *key {
@@ -230,6 +349,7 @@ fn parse_date_histogram(rest: &str) -> Result<AggregateSpec, ParseAggSpecError>
                     metrics.push(parse_bucket_metric(metric)?);
                 }
             }
+            "tz" => time_zone = Some(parse_tz(val)?),
             _ => {}
         }
     }
@@ -238,6 +358,7 @@ fn parse_date_histogram(rest: &str) -> Result<AggregateSpec, ParseAggSpecError>
         field,
         calendar,
         metrics,
+        time_zone,
     }))
 }
 
@@ -306,7 +427,7 @@ fn parse_rollup(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
     let mut sample_count: u8 = 0;
     let mut record_idx: Option<u32> = None;
     let mut drive: Option<uffs_mft::platform::DriveLetter> = None;
-    let mut sub_spec: Option<Box<AggregateSpec>> = None;
+    let mut sub_spec: Option<AggregateSpec> = None;
 
     for (key, val) in &opts {
         match *key {
@@ -334,11 +455,8 @@ fn parse_rollup(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
                     .parse()
                     .map_err(invalid_int("sample", (*val).to_owned()))?;
             }
-            "sub" => {
-                // Parse nested sub-aggregation spec (e.g. "terms:type").
-                let inner = parse_agg_spec(val)?;
-                sub_spec = Some(Box::new(inner));
-            }
+            // Shorthand for `rollup:…>terms:type`.
+            "sub" => sub_spec = Some(parse_level(val)?),
             _ => {}
         }
     }
@@ -363,13 +481,16 @@ fn parse_rollup(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
 
     let sample = (sample_count > 0).then(|| TopHitsSpec::with_count(sample_count));
 
-    Ok(AggregateSpec::new(AggregateKind::Rollup {
+    let rollup = AggregateSpec::new(AggregateKind::Rollup {
         mode,
         top,
         metrics,
         sample,
-        sub: sub_spec,
-    }))
+    });
+    match sub_spec {
+        Some(child) => nest(rollup, child, rest),
+        None => Ok(rollup),
+    }
 }
 
 /// Parse "size+name,verify=MODE,top=N,sample=N" → Duplicates spec.
@@ -397,6 +518,8 @@ fn parse_duplicates(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
                     "none" => DuplicateVerify::None,
                     "first_bytes" | "first" => DuplicateVerify::FirstBytes { count: 4096 },
                     "sha256" | "hash" => DuplicateVerify::Sha256,
+                    "xxh3" | "fast" => DuplicateVerify::Xxh3,
+                    "blake3" => DuplicateVerify::Blake3,
                     _ => {
                         return Err(ParseAggSpecError::UnknownVerifyMode {
                             val: (*val).to_owned(),
@@ -459,6 +582,145 @@ fn parse_distinct(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
     Ok(AggregateSpec::new(AggregateKind::Distinct { field }))
 }
 
+/// Parse `ROWS,cols=COLS,metric=M,top=N,cols_top=N` → Pivot spec.
+///
+/// Each axis is `FIELD[@values|@sizes|@age|@<calendar>|@B+B+…]` (see
+/// [`PivotAxis::parse`]).
+fn parse_pivot(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
+    let (rows_str, opts_str) = split_field_and_options(rest);
+    let mut cols_str = None;
+    let mut metric = BucketMetric::Count;
+    let mut rows_top = DEFAULT_ROWS_TOP;
+    let mut cols_top = DEFAULT_COLS_TOP;
+    let mut time_zone = None;
+
+    for (key, val) in parse_options(opts_str) {
+        match key {
+            "cols" | "columns" => cols_str = Some(val),
+            "metric" | "metrics" => metric = parse_pivot_metric(val)?,
+            "top" | "rows_top" => {
+                rows_top = val.parse().map_err(invalid_int("top", val.to_owned()))?;
+            }
+            "cols_top" => {
+                cols_top = val
+                    .parse()
+                    .map_err(invalid_int("cols_top", val.to_owned()))?;
+            }
+            "tz" => time_zone = Some(parse_tz(val)?),
+            _ => {}
+        }
+    }
+
+    let cols = cols_str.ok_or(ParseAggSpecError::PivotRequiresCols)?;
+    Ok(AggregateSpec::new(AggregateKind::Pivot {
+        rows: PivotAxis::parse(rows_str, rows_top)?,
+        cols: PivotAxis::parse(cols, cols_top)?,
+        metric,
+        time_zone,
+    }))
+}
+
+/// Parse `[FIELD],metric=M,tz=OFFSET` → a day-of-week × hour-of-day
+/// Pivot over `FIELD` (default `modified`): the 7×24 activity heatmap.
+fn parse_heatmap(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
+    let (field_str, opts_str) = split_field_and_options(rest);
+    let field = if field_str.trim().is_empty() {
+        FieldId::Modified
+    } else {
+        parse_field(field_str)?
+    };
+    let mut metric = BucketMetric::Count;
+    let mut time_zone = None;
+    for (key, val) in parse_options(opts_str) {
+        match key {
+            "metric" | "metrics" => metric = parse_pivot_metric(val)?,
+            "tz" => time_zone = Some(parse_tz(val)?),
+            _ => {}
+        }
+    }
+    let axis = |calendar: CalendarInterval, top: u16| PivotAxis {
+        field,
+        buckets: AxisBuckets::Calendar(calendar),
+        top,
+    };
+    Ok(AggregateSpec::new(AggregateKind::Pivot {
+        rows: axis(CalendarInterval::DayOfWeek, 7),
+        cols: axis(CalendarInterval::HourOfDay, 24),
+        metric,
+        time_zone,
+    }))
+}
+
+/// Parse a pivot's single `metric=`.
+fn parse_pivot_metric(val: &str) -> Result<BucketMetric, ParseAggSpecError> {
+    if val.contains('+') {
+        return Err(ParseAggSpecError::PivotSingleMetric {
+            val: val.to_owned(),
+        });
+    }
+    parse_bucket_metric(val)
+}
+
+/// Parse `[name],scope=dir|all,rank=count|bytes,top=N,sample=N,max_groups=N`
+/// → `SimilarNames` spec.
+fn parse_similar_names(rest: &str) -> Result<AggregateSpec, ParseAggSpecError> {
+    // The field slot is optional: `similar_names,scope=all` is fine too.
+    let (field_part, opts_part) = split_field_and_options(rest);
+    let field_str = field_part.trim();
+    let options = if field_str.contains('=') {
+        rest
+    } else {
+        if !field_str.is_empty() && parse_field(field_str)? != FieldId::Name {
+            return Err(ParseAggSpecError::SimilarNamesField {
+                name: field_str.to_owned(),
+            });
+        }
+        opts_part
+    };
+
+    let mut scope = SimilarScope::default();
+    let mut rank = SimilarRank::default();
+    let mut top = DEFAULT_TOP;
+    let mut sample_count = DEFAULT_SAMPLE;
+    let mut max_groups = DEFAULT_MAX_GROUPS;
+    for (key, val) in parse_options(options) {
+        match key {
+            "scope" | "within" => {
+                scope = SimilarScope::parse(val).ok_or_else(|| {
+                    ParseAggSpecError::UnknownSimilarScope {
+                        val: val.to_owned(),
+                    }
+                })?;
+            }
+            "rank" | "by" => {
+                rank = SimilarRank::parse(val).ok_or_else(|| {
+                    ParseAggSpecError::UnknownSimilarRank {
+                        val: val.to_owned(),
+                    }
+                })?;
+            }
+            "top" => top = val.parse().map_err(invalid_int("top", val.to_owned()))?,
+            "sample" => {
+                sample_count = val.parse().map_err(invalid_int("sample", val.to_owned()))?;
+            }
+            "max_groups" => {
+                max_groups = val
+                    .parse()
+                    .map_err(invalid_int("max_groups", val.to_owned()))?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(AggregateSpec::new(AggregateKind::SimilarNames {
+        scope,
+        rank,
+        top,
+        sample: (sample_count > 0).then(|| TopHitsSpec::with_count(sample_count)),
+        max_groups,
+    }))
+}
+
 /// Parse a scalar metric name.
 fn parse_scalar_metric(name: &str) -> Result<ScalarMetric, ParseAggSpecError> {
     match name {
@@ -468,9 +730,11 @@ fn parse_scalar_metric(name: &str) -> Result<ScalarMetric, ParseAggSpecError> {
         "avg" | "mean" => Ok(ScalarMetric::Avg),
         "value_count" | "count" => Ok(ScalarMetric::ValueCount),
         "missing_count" | "missing" => Ok(ScalarMetric::MissingCount),
-        _ => Err(ParseAggSpecError::UnknownScalarMetric {
-            name: name.to_owned(),
-        }),
+        _ => PercentileRank::parse(name)
+            .map(ScalarMetric::Percentile)
+            .ok_or_else(|| ParseAggSpecError::UnknownScalarMetric {
+                name: name.to_owned(),
+            }),
     }
 }
 
@@ -487,9 +751,11 @@ fn parse_bucket_metric(name: &str) -> Result<BucketMetric, ParseAggSpecError> {
         "max_size" | "max" => Ok(BucketMetric::MaxSize),
         "share_count" | "share_of_count" => Ok(BucketMetric::ShareOfTotalCount),
         "share_bytes" | "share_of_bytes" => Ok(BucketMetric::ShareOfTotalBytes),
-        _ => Err(ParseAggSpecError::UnknownBucketMetric {
-            name: name.to_owned(),
-        }),
+        _ => PercentileRank::parse(name)
+            .map(BucketMetric::SizePercentile)
+            .ok_or_else(|| ParseAggSpecError::UnknownBucketMetric {
+                name: name.to_owned(),
+            }),
     }
 }
 
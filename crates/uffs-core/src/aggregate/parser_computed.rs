@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `--agg` specs over user-defined computed fields.
+//!
+//! `stats:slack` or `hist:age,interval=30` name a computed field (see
+//! [`crate::search::computed`]) where `stats`, `hist` or `range` would
+//! take a numeric field.  The level is parsed as if it measured `size`,
+//! then bound to the computed field via [`AggregateSpec::with_computed`];
+//! every other kind keeps the plain [`super::parser::parse_agg_spec`]
+//! grammar.
+
+use alloc::sync::Arc;
+
+use super::parser::{nest, parse_level};
+use super::parser_error::ParseAggSpecError;
+use super::regex_key::split_unquoted;
+use super::spec::AggregateSpec;
+use crate::search::computed::ComputedFields;
+
+/// [`super::parser::parse_agg_spec`] with `fields` in scope.
+///
+/// # Errors
+///
+/// Returns [`ParseAggSpecError`] exactly where `parse_agg_spec` would.
+pub fn parse_agg_spec_with(
+    input: &str,
+    fields: &ComputedFields,
+) -> Result<AggregateSpec, ParseAggSpecError> {
+    let Some((head, tail)) = split_unquoted(input, '>') else {
+        return parse_level_with(input, fields);
+    };
+    nest(
+        parse_level_with(head, fields)?,
+        parse_agg_spec_with(tail, fields)?,
+        input,
+    )
+}
+
+/// Parse one level, binding a computed field when it names one.
+fn parse_level_with(
+    input: &str,
+    fields: &ComputedFields,
+) -> Result<AggregateSpec, ParseAggSpecError> {
+    let trimmed = input.trim();
+    if let Some((kind, rest)) = trimmed.split_once(':')
+        && matches!(kind, "stats" | "hist" | "histogram" | "range")
+    {
+        let (name, options) = rest.split_once(',').unwrap_or((rest, ""));
+        if let Some(field) = fields.get(name.trim()) {
+            let measured = parse_level(&format!("{kind}:size,{options}"))?;
+            return Ok(measured.with_computed(Arc::clone(field)));
+        }
+    }
+    parse_level(input)
+}
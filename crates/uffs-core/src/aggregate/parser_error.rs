@@ -35,7 +35,8 @@ pub enum ParseAggSpecError {
     /// The top-level `kind:` segment did not match any known
     /// aggregate kind (`count`, `stats`, `terms`, `facet`, `hist`,
     /// `histogram`, `datehist`, `date_histogram`, `range`, `rollup`,
-    /// `duplicates`, `dups`, `preset`, `missing`, `distinct`).
+    /// `duplicates`, `dups`, `preset`, `missing`, `distinct`, `growth`,
+    /// `pivot`, `crosstab`, `similar_names`, `similar`).
     #[error("Unknown aggregate kind: `{kind}`")]
     UnknownKind {
         /// The unrecognised kind token (as supplied on the command line).
@@ -50,7 +51,7 @@ pub enum ParseAggSpecError {
     },
     /// One of the integer-valued option keys (`top`, `sample`,
     /// `interval`, `range boundary`, `depth`, `record index`,
-    /// `max_groups`) failed to parse as the target integer type.
+    /// `max_groups`, `cols_top`) failed to parse as the target integer type.
     ///
     /// The `option` field carries the lower-case label used in the
     /// pre-Phase-5d Display message so the byte sequence is
@@ -77,6 +78,16 @@ pub enum ParseAggSpecError {
         /// The unrecognised calendar identifier.
         val: String,
     },
+    /// `tz=` was neither an IANA zone nor an offset from UTC
+    /// ([`uffs_time::TimeZone::parse`]).
+    #[error(
+        "Invalid time zone: `{val}`. Use a zone such as 'Europe/Berlin' or an offset such as \
+         'UTC', '+2' or '-05:30'."
+    )]
+    InvalidTimeZone {
+        /// The unrecognised zone.
+        val: String,
+    },
     /// `rollup:ancestor` was supplied without the required
     /// `record=<idx>` option (also known as `frs=` / `ancestor=`).
     #[error("rollup:ancestor requires record=<idx> option")]
@@ -100,7 +111,8 @@ pub enum ParseAggSpecError {
         mode: String,
     },
     /// The duplicates `verify=` mode did not match `none` /
-    /// `first_bytes` / `first` / `sha256` / `hash`.
+    /// `first_bytes` / `first` / `sha256` / `hash` / `xxh3` / `fast` /
+    /// `blake3`.
     #[error("Unknown verify mode: `{val}`")]
     UnknownVerifyMode {
         /// The unrecognised verify mode.
@@ -121,7 +133,8 @@ pub enum ParseAggSpecError {
     },
     /// A `metrics=` segment in a `stats:*` spec named a scalar metric
     /// that did not match `sum` / `min` / `max` / `avg` / `mean` /
-    /// `value_count` / `count` / `missing_count` / `missing`.
+    /// `value_count` / `count` / `missing_count` / `missing`, nor a
+    /// percentile (`pNN`, `pNN.N`, `median`).
     #[error("Unknown scalar metric: `{name}`")]
     UnknownScalarMetric {
         /// The unrecognised scalar metric name.
@@ -132,10 +145,103 @@ pub enum ParseAggSpecError {
     /// `size` / `total_allocated` / `allocated` / `waste_bytes` /
     /// `waste` / `waste_pct` / `waste_percent` / `avg_size` / `avg` /
     /// `min_size` / `min` / `max_size` / `max` / `share_count` /
-    /// `share_of_count` / `share_bytes` / `share_of_bytes`.
+    /// `share_of_count` / `share_bytes` / `share_of_bytes`, nor a size
+    /// percentile (`pNN`, `pNN.N`, `median`).
     #[error("Unknown bucket metric: `{name}`")]
     UnknownBucketMetric {
         /// The unrecognised bucket metric name.
         name: String,
     },
+    /// A `growth` spec reached the single-index aggregate scan. Growth
+    /// compares a baseline with the current index, so it only runs in a
+    /// snapshot diff (see [`super::parser::parse_growth_spec`]).
+    #[error("growth compares two indexes and needs a diff baseline (--diff)")]
+    GrowthNeedsBaseline,
+    /// The growth `rank=` option did not match `abs` / `absolute` /
+    /// `bytes` / `rel` / `relative` / `pct` / `percent`.
+    #[error("Unknown growth rank: `{val}`. Use 'abs' or 'rel'.")]
+    UnknownGrowthRank {
+        /// The unrecognised rank.
+        val: String,
+    },
+    /// A nested chain (`parent>child`, or rollup `sub=`) put a kind that
+    /// has no buckets (`count`, `stats`, `missing`, `distinct`,
+    /// `duplicates`, `preset`) on either side of a nesting step.
+    #[error(
+        "Cannot nest `{spec}`: nested aggregations need bucketed kinds (terms, hist, datehist, \
+         range, rollup) at every level"
+    )]
+    NotBucketed {
+        /// The offending chain segment.
+        spec: String,
+    },
+    /// A `pivot` spec had no `cols=` axis.
+    #[error("pivot requires cols=<field> option")]
+    PivotRequiresCols,
+    /// A pivot axis' `@` suffix was not `values`, `sizes`, `age`, a
+    /// calendar interval, or `+`-separated range boundaries.
+    #[error("Invalid pivot axis: `{axis}`")]
+    InvalidPivotAxis {
+        /// The offending axis.
+        axis: String,
+    },
+    /// A pivot `metric=` named more than one metric.
+    #[error("pivot takes a single metric, got `{val}`")]
+    PivotSingleMetric {
+        /// The offending value.
+        val: String,
+    },
+    /// A `similar_names` spec named a field other than `name`.
+    #[error("similar_names clusters by name, got `{name}`")]
+    SimilarNamesField {
+        /// The offending field.
+        name: String,
+    },
+    /// The `similar_names` `scope=` option was not `dir` or `all`.
+    #[error("Unknown similar_names scope: `{val}`. Use 'dir' or 'all'.")]
+    UnknownSimilarScope {
+        /// The unrecognised scope.
+        val: String,
+    },
+    /// The `similar_names` `rank=` option was not `count` or `bytes`.
+    #[error("Unknown similar_names rank: `{val}`. Use 'count' or 'bytes'.")]
+    UnknownSimilarRank {
+        /// The unrecognised rank.
+        val: String,
+    },
+    /// A `terms:regex(...)` key was not of the form
+    /// `regex(FIELD, "PATTERN")`.
+    #[error(
+        "Invalid regex key: `{expr}`. Use regex(name, \"PATTERN\") or regex(path, \"PATTERN\")."
+    )]
+    InvalidRegexKey {
+        /// The offending expression.
+        expr: String,
+    },
+    /// A regex key named a field other than `name` or `path`.
+    #[error("regex keys match name or path, got `{name}`")]
+    RegexKeyField {
+        /// The offending field.
+        name: String,
+    },
+    /// A regex key's pattern did not compile.
+    #[error("Invalid regex `{pattern}`: {reason}")]
+    InvalidRegex {
+        /// The pattern as written.
+        pattern: String,
+        /// The regex engine's message.
+        reason: String,
+    },
+    /// A regex key's pattern had no capture group to take the key from.
+    #[error("regex key `{pattern}` needs a capture group, e.g. `^(\\d{{4}})-`")]
+    RegexWithoutCapture {
+        /// The pattern as written.
+        pattern: String,
+    },
+    /// The `unmatched=` option of a regex key was not `true` or `false`.
+    #[error("Invalid unmatched: `{val}`. Use 'true' or 'false'.")]
+    InvalidUnmatched {
+        /// The offending value.
+        val: String,
+    },
 }
@@ -90,6 +90,21 @@ fn parse_duplicates() {
     }
 }
 
+#[test]
+fn parse_duplicates_hash_cache_modes() {
+    for (text, expected) in [
+        ("xxh3", DuplicateVerify::Xxh3),
+        ("fast", DuplicateVerify::Xxh3),
+        ("blake3", DuplicateVerify::Blake3),
+    ] {
+        let spec = parse_agg_spec(&format!("duplicates:size,verify={text}")).unwrap();
+        assert!(
+            matches!(spec.kind, AggregateKind::Duplicates { verify, .. } if verify == expected),
+            "{text}"
+        );
+    }
+}
+
 #[test]
 fn parse_missing() {
     let spec = parse_agg_spec("missing:extension").unwrap();
@@ -288,9 +303,9 @@ fn parse_rollup_drilldown_alias() {
 #[test]
 fn parse_rollup_with_nested_sub() {
     let spec = parse_agg_spec("rollup:drive,sub=terms:extension").unwrap();
-    if let AggregateKind::Rollup { mode, sub, .. } = &spec.kind {
+    if let AggregateKind::Rollup { mode, .. } = &spec.kind {
         assert_eq!(*mode, RollupMode::Drive);
-        let sub_spec = sub.as_ref().expect("sub should be present");
+        let sub_spec = spec.sub.as_ref().expect("sub should be present");
         assert!(
             matches!(&sub_spec.kind, AggregateKind::Terms { .. }),
             "sub should be a terms aggregation"
@@ -303,25 +318,71 @@ fn parse_rollup_with_nested_sub() {
 #[test]
 fn parse_rollup_no_sub_by_default() {
     let spec = parse_agg_spec("rollup:drive,top=5").unwrap();
-    if let AggregateKind::Rollup { sub, .. } = &spec.kind {
-        assert!(sub.is_none(), "sub should be None when not specified");
-    } else {
-        panic!("expected Rollup");
-    }
+    assert!(matches!(spec.kind, AggregateKind::Rollup { .. }));
+    assert!(spec.sub.is_none(), "sub should be None when not specified");
 }
 
 #[test]
 fn parse_nested_rollup_path_with_terms_type() {
     let spec = parse_agg_spec("rollup:path,depth=1,sub=terms:type,top=20").unwrap();
-    if let AggregateKind::Rollup { mode, sub, top, .. } = &spec.kind {
+    if let AggregateKind::Rollup { mode, top, .. } = &spec.kind {
         assert_eq!(*mode, RollupMode::Path { depth: 1 });
         assert_eq!(*top, 20);
-        let sub_spec = sub.as_ref().expect("sub should be present");
+        let sub_spec = spec.sub.as_ref().expect("sub should be present");
         assert!(matches!(&sub_spec.kind, AggregateKind::Terms { .. }));
     } else {
         panic!("expected Rollup");
     }
 }
+#[test]
+fn parse_nested_chain() {
+    let spec = parse_agg_spec("terms:drive>terms:extension,top=10>hist:size").unwrap();
+    assert!(matches!(spec.kind, AggregateKind::Terms {
+        field: FieldId::Drive,
+        ..
+    }));
+    let child = spec.sub.as_deref().expect("extension level");
+    assert!(matches!(child.kind, AggregateKind::Terms {
+        field: FieldId::Extension,
+        top: 10,
+        ..
+    }));
+    let grandchild = child.sub.as_deref().expect("size level");
+    assert!(matches!(grandchild.kind, AggregateKind::Histogram { .. }));
+    assert!(grandchild.sub.is_none());
+
+    // Rollup `sub=` and `>` compose: the chain nests under the sub.
+    let rollup = parse_agg_spec("rollup:drive,sub=terms:type>datehist:modified").unwrap();
+    let type_level = rollup.sub.as_deref().expect("type level");
+    assert!(matches!(
+        type_level.sub.as_deref().map(|leaf| &leaf.kind),
+        Some(AggregateKind::DateHistogram { .. })
+    ));
+}
+
+#[test]
+fn nested_chain_needs_bucketed_kinds() {
+    for input in [
+        "count>terms:drive",
+        "terms:drive>stats:size",
+        "terms:drive>preset:overview",
+        "rollup:drive,sub=distinct:extension",
+    ] {
+        assert!(
+            matches!(
+                parse_agg_spec(input),
+                Err(ParseAggSpecError::NotBucketed { .. })
+            ),
+            "{input}"
+        );
+    }
+    // Errors inside a segment surface as-is.
+    assert!(matches!(
+        parse_agg_spec("terms:drive>terms:nope"),
+        Err(ParseAggSpecError::UnknownField { .. })
+    ));
+}
+
 #[test]
 fn parse_terms_sample_zero_is_none() {
     let spec = parse_agg_spec("terms:extension,sample=0").unwrap();
@@ -514,6 +575,192 @@ fn unknown_scalar_metric_display_locked() {
     assert_eq!(err.to_string(), "Unknown scalar metric: `bogus`");
 }
 
+#[test]
+fn parse_percentile_metrics() {
+    let spec = parse_agg_spec("stats:size,metrics=median+p99.9+max").unwrap();
+    let AggregateKind::Stats { metrics, .. } = &spec.kind else {
+        panic!("expected Stats");
+    };
+    assert_eq!(metrics, &[
+        ScalarMetric::Percentile(PercentileRank::MEDIAN),
+        ScalarMetric::Percentile(PercentileRank::parse("p99.9").unwrap()),
+        ScalarMetric::Max,
+    ]);
+
+    let hist = parse_agg_spec("hist:size,interval=1024,metrics=count+p90").unwrap();
+    let AggregateKind::Histogram {
+        metrics: bucket_metrics,
+        ..
+    } = &hist.kind
+    else {
+        panic!("expected Histogram");
+    };
+    assert_eq!(bucket_metrics, &[
+        BucketMetric::Count,
+        BucketMetric::SizePercentile(PercentileRank::P90),
+    ]);
+
+    let err = parse_agg_spec("stats:size,metrics=p101").expect_err("p101 is out of range");
+    assert_eq!(err, ParseAggSpecError::UnknownScalarMetric {
+        name: "p101".to_owned(),
+    });
+}
+
+#[test]
+fn parse_pivot_spec() {
+    let spec = parse_agg_spec("pivot:type,cols=modified@year,metric=p90,top=5,cols_top=3").unwrap();
+    let AggregateKind::Pivot {
+        rows, cols, metric, ..
+    } = &spec.kind
+    else {
+        panic!("expected Pivot");
+    };
+    assert_eq!(rows.field, FieldId::Type);
+    assert_eq!(rows.top, 5);
+    assert_eq!(cols.field, FieldId::Modified);
+    assert_eq!(cols.top, 3);
+    assert_eq!(cols.buckets, AxisBuckets::Calendar(CalendarInterval::Year));
+    assert_eq!(*metric, BucketMetric::SizePercentile(PercentileRank::P90));
+
+    let defaults = parse_agg_spec("pivot:extension,cols=drive").unwrap();
+    let AggregateKind::Pivot {
+        rows: default_rows,
+        cols: default_cols,
+        metric: default_metric,
+        ..
+    } = &defaults.kind
+    else {
+        panic!("expected Pivot");
+    };
+    assert_eq!((default_rows.top, default_cols.top), (20, 10));
+    assert_eq!(*default_metric, BucketMetric::Count);
+
+    assert_eq!(
+        parse_agg_spec("pivot:extension").expect_err("cols required"),
+        ParseAggSpecError::PivotRequiresCols
+    );
+    assert!(matches!(
+        parse_agg_spec("pivot:extension,cols=drive,metric=count+total_bytes"),
+        Err(ParseAggSpecError::PivotSingleMetric { .. })
+    ));
+    assert!(matches!(
+        parse_agg_spec("pivot:extension,cols=size@fortnight"),
+        Err(ParseAggSpecError::InvalidPivotAxis { .. })
+    ));
+}
+
+#[test]
+fn parse_cyclic_calendars_heatmap_and_tz() {
+    let spec = parse_agg_spec("datehist:created,calendar=hod,tz=UTC+05:30").unwrap();
+    let AggregateKind::DateHistogram {
+        calendar,
+        time_zone,
+        ..
+    } = spec.kind
+    else {
+        panic!("expected DateHistogram");
+    };
+    assert_eq!(calendar, CalendarInterval::HourOfDay);
+    assert_eq!(time_zone, Some(TimeZone::fixed(19_800)));
+    assert_eq!(
+        CalendarInterval::parse("weekday"),
+        Some(CalendarInterval::DayOfWeek)
+    );
+
+    let heatmap = parse_agg_spec("heatmap").unwrap();
+    let AggregateKind::Pivot {
+        rows,
+        cols,
+        metric,
+        time_zone: heatmap_zone,
+    } = &heatmap.kind
+    else {
+        panic!("expected Pivot");
+    };
+    assert_eq!(
+        (rows.field, cols.field),
+        (FieldId::Modified, FieldId::Modified)
+    );
+    assert_eq!(
+        rows.buckets,
+        AxisBuckets::Calendar(CalendarInterval::DayOfWeek)
+    );
+    assert_eq!(
+        cols.buckets,
+        AxisBuckets::Calendar(CalendarInterval::HourOfDay)
+    );
+    assert_eq!((*metric, *heatmap_zone), (BucketMetric::Count, None));
+
+    let pivot = parse_agg_spec("pivot:type,cols=accessed@dow,tz=-8").unwrap();
+    assert!(matches!(pivot.kind, AggregateKind::Pivot {
+        time_zone: Some(zone),
+        ..
+    } if zone == TimeZone::fixed(-28_800)));
+    let berlin = parse_agg_spec("heatmap:accessed,tz=Europe/Berlin").unwrap();
+    assert!(matches!(berlin.kind, AggregateKind::Pivot {
+        time_zone: Some(zone),
+        ..
+    } if zone.name() == Some("Europe/Berlin")));
+    assert_eq!(
+        parse_agg_spec("heatmap:accessed,tz=Mars/Olympus").expect_err("unknown zone"),
+        ParseAggSpecError::InvalidTimeZone {
+            val: "Mars/Olympus".to_owned()
+        }
+    );
+}
+
+#[test]
+fn parse_similar_names_spec() {
+    let spec = parse_agg_spec("similar_names").unwrap();
+    let AggregateKind::SimilarNames {
+        scope,
+        rank,
+        top,
+        sample,
+        ..
+    } = &spec.kind
+    else {
+        panic!("expected SimilarNames");
+    };
+    assert_eq!(
+        (*scope, *rank, *top),
+        (SimilarScope::Directory, SimilarRank::Count, 50)
+    );
+    assert_eq!(sample.as_ref().map(|hits| hits.count), Some(3));
+
+    for input in [
+        "similar_names:name,scope=all,rank=bytes,top=5,sample=0",
+        "similar:scope=all,rank=bytes,top=5,sample=0",
+    ] {
+        assert!(
+            matches!(
+                parse_agg_spec(input).unwrap().kind,
+                AggregateKind::SimilarNames {
+                    scope: SimilarScope::Selection,
+                    rank: SimilarRank::Bytes,
+                    top: 5,
+                    sample: None,
+                    ..
+                }
+            ),
+            "{input}"
+        );
+    }
+
+    assert!(matches!(
+        parse_agg_spec("similar_names:size"),
+        Err(ParseAggSpecError::SimilarNamesField { .. })
+    ));
+    assert!(matches!(
+        parse_agg_spec("similar_names:scope=tree"),
+        Err(ParseAggSpecError::UnknownSimilarScope { .. })
+    ));
+    assert!(matches!(
+        parse_agg_spec("similar_names:rank=age"),
+        Err(ParseAggSpecError::UnknownSimilarRank { .. })
+    ));
+}
+
 #[test]
 fn unknown_bucket_metric_display_locked() {
     let err = parse_agg_spec("terms:extension,metrics=bogus").expect_err("must error");
@@ -522,3 +769,31 @@ fn unknown_bucket_metric_display_locked() {
     });
     assert_eq!(err.to_string(), "Unknown bucket metric: `bogus`");
 }
+
+#[test]
+fn parse_growth_options() {
+    let spec = parse_growth_spec("growth:path,depth=2,top=20,rank=rel")
+        .expect("a growth spec")
+        .unwrap();
+    assert_eq!(spec.depth, 2);
+    assert_eq!(spec.top, 20);
+    assert_eq!(spec.rank, GrowthRank::Relative);
+
+    let defaults = parse_growth_spec("growth").expect("a growth spec").unwrap();
+    assert_eq!(defaults, GrowthSpec::default());
+    assert!(parse_growth_spec("rollup:path").is_none());
+}
+
+#[test]
+fn growth_errors() {
+    let err = parse_growth_spec("growth:rank=sideways")
+        .expect("a growth spec")
+        .expect_err("must error");
+    assert_eq!(err, ParseAggSpecError::UnknownGrowthRank {
+        val: "sideways".to_owned(),
+    });
+    assert_eq!(
+        parse_agg_spec("growth:path").expect_err("needs a baseline"),
+        ParseAggSpecError::GrowthNeedsBaseline
+    );
+}
@@ -0,0 +1,386 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Percentile metrics backed by a mergeable quantile sketch.
+//!
+//! File sizes are heavily skewed — a handful of multi-gigabyte files drag
+//! the average far above what a typical file weighs — so `p50` / `p90` /
+//! `p99` say far more than `avg`. Exact percentiles would need every value
+//! kept in memory, so accumulators feed a [`QuantileSketch`] instead: a
+//! `DDSketch` with logarithmic bins whose estimates are within 1 % of the
+//! true value. Sketches merge by adding bin counts, so the per-drive
+//! parallel scan combines them exactly as it combines sums and counts.
+
+use super::spec::{BucketMetric, ScalarMetric};
+
+/// A requested percentile, in hundredths of a percent (`p99.9` is 9990).
+///
+/// `p0` and `p100` are the exact minimum and maximum; everything in
+/// between is a sketch estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PercentileRank(u16);
+
+impl PercentileRank {
+    /// The median (`p50`).
+    pub const MEDIAN: Self = Self(5_000);
+    /// `p90`.
+    pub const P90: Self = Self(9_000);
+    /// `p99`.
+    pub const P99: Self = Self(9_900);
+
+    /// Parse `median` or `pNN` with up to two decimals (`p50`, `p99.9`,
+    /// `p99.99`).
+    ///
+    /// Returns `None` for anything else, including ranks above `p100`.
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        let lower = input.trim().to_ascii_lowercase();
+        if lower == "median" {
+            return Some(Self::MEDIAN);
+        }
+        let digits = lower.strip_prefix('p')?;
+        let (whole, frac) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty()
+            || frac.len() > 2
+            || !whole
+                .bytes()
+                .chain(frac.bytes())
+                .all(|byte| byte.is_ascii_digit())
+        {
+            return None;
+        }
+        let percent: u16 = whole.parse().ok()?;
+        let hundredths: u16 = match frac.len() {
+            0 => 0,
+            1 => frac.parse::<u16>().ok()? * 10,
+            _ => frac.parse().ok()?,
+        };
+        let rank = percent.checked_mul(100)?.checked_add(hundredths)?;
+        (rank <= 10_000).then_some(Self(rank))
+    }
+
+    /// Display label: `p50`, `p99.9`, `p99.99`.
+    #[must_use]
+    pub fn label(self) -> String {
+        let whole = self.0 / 100;
+        let frac = self.0 % 100;
+        if frac == 0 {
+            format!("p{whole}")
+        } else if frac.is_multiple_of(10) {
+            format!("p{whole}.{}", frac / 10)
+        } else {
+            format!("p{whole}.{frac:02}")
+        }
+    }
+
+    /// The rank as a fraction in `0.0..=1.0`.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "hundredths-of-a-percent → fraction is a single documented division"
+    )]
+    pub fn fraction(self) -> f64 {
+        f64::from(self.0) / 10_000.0_f64
+    }
+}
+
+/// The percentile ranks requested by a stats aggregation, in request
+/// order without duplicates.
+#[must_use]
+pub(crate) fn scalar_ranks(metrics: &[ScalarMetric]) -> Vec<PercentileRank> {
+    dedup_ranks(metrics.iter().filter_map(|metric| match metric {
+        ScalarMetric::Percentile(rank) => Some(*rank),
+        ScalarMetric::Sum
+        | ScalarMetric::Min
+        | ScalarMetric::Max
+        | ScalarMetric::Avg
+        | ScalarMetric::ValueCount
+        | ScalarMetric::MissingCount => None,
+    }))
+}
+
+/// The size percentile ranks requested for each bucket, in request order
+/// without duplicates.
+#[must_use]
+pub(crate) fn bucket_ranks(metrics: &[BucketMetric]) -> Vec<PercentileRank> {
+    dedup_ranks(metrics.iter().filter_map(|metric| match metric {
+        BucketMetric::SizePercentile(rank) => Some(*rank),
+        BucketMetric::Count
+        | BucketMetric::TotalBytes
+        | BucketMetric::TotalAllocated
+        | BucketMetric::WasteBytes
+        | BucketMetric::WastePct
+        | BucketMetric::AvgSize
+        | BucketMetric::MinSize
+        | BucketMetric::MaxSize
+        | BucketMetric::ShareOfTotalCount
+        | BucketMetric::ShareOfTotalBytes => None,
+    }))
+}
+
+/// Whether any bucket metric needs a per-bucket sketch.
+#[must_use]
+pub(crate) fn wants_bucket_sketch(metrics: &[BucketMetric]) -> bool {
+    metrics
+        .iter()
+        .any(|metric| matches!(metric, BucketMetric::SizePercentile(_)))
+}
+
+/// Drop repeated ranks, keeping the first occurrence.
+fn dedup_ranks(ranks: impl Iterator<Item = PercentileRank>) -> Vec<PercentileRank> {
+    let mut out: Vec<PercentileRank> = Vec::new();
+    for rank in ranks {
+        if !out.contains(&rank) {
+            out.push(rank);
+        }
+    }
+    out
+}
+
+/// Relative accuracy of [`QuantileSketch`] estimates.
+const RELATIVE_ACCURACY: f64 = 0.01;
+
+/// Bin growth factor: `(1 + α) / (1 - α)`.
+const GAMMA: f64 = (1.0_f64 + RELATIVE_ACCURACY) / (1.0_f64 - RELATIVE_ACCURACY);
+
+/// A mergeable quantile sketch over `u64` values (`DDSketch`).
+///
+/// Value `v ≥ 1` lands in bin `⌈log_γ v⌉`; zeros are counted apart. Bins
+/// are stored densely from the lowest occupied index, so a bucket whose
+/// files span 1 KiB–1 GiB needs roughly 700 counters. The exact minimum
+/// and maximum ride alongside, so `p0` / `p100` are never estimates.
+#[derive(Debug, Clone, Default)]
+pub struct QuantileSketch {
+    /// Number of zero values.
+    zeros: u64,
+    /// Bin index of `bins[0]`.
+    offset: usize,
+    /// Counts per bin, starting at `offset`.
+    bins: Vec<u64>,
+    /// Total values inserted (zeros included).
+    count: u64,
+    /// Smallest value inserted (meaningless while `count == 0`).
+    min: u64,
+    /// Largest value inserted (meaningless while `count == 0`).
+    max: u64,
+}
+
+impl QuantileSketch {
+    /// An empty sketch.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            zeros: 0,
+            offset: 0,
+            bins: Vec::new(),
+            count: 0,
+            min: 0,
+            max: 0,
+        }
+    }
+
+    /// Record one value.
+    #[inline]
+    pub fn insert(&mut self, value: u64) {
+        self.track_extremes(value, value);
+        self.count += 1;
+        if value == 0 {
+            self.zeros += 1;
+        } else {
+            self.add(bin_index(value), 1);
+        }
+    }
+
+    /// Fold another sketch into this one.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        self.track_extremes(other.min, other.max);
+        self.count += other.count;
+        self.zeros += other.zeros;
+        for (pos, &hits) in other.bins.iter().enumerate() {
+            if hits > 0 {
+                self.add(other.offset + pos, hits);
+            }
+        }
+    }
+
+    /// Value at `fraction` (`0.0..=1.0`), or `None` when empty.
+    ///
+    /// `0.0` and `1.0` return the exact minimum and maximum; anything in
+    /// between is an estimate, clamped to that range.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "DDSketch rank = q × (n − 1) is the documented quantile formula"
+    )]
+    pub fn quantile(&self, fraction: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        if fraction <= 0.0_f64 {
+            return Some(self.min);
+        }
+        if fraction >= 1.0_f64 {
+            return Some(self.max);
+        }
+        let rank = fraction.clamp(0.0_f64, 1.0_f64) * uffs_mft::u64_to_f64(self.count - 1);
+        let mut seen = self.zeros;
+        if uffs_mft::u64_to_f64(seen) > rank {
+            return Some(0);
+        }
+        let mut last = None;
+        for (pos, &hits) in self.bins.iter().enumerate() {
+            if hits == 0 {
+                continue;
+            }
+            seen += hits;
+            last = Some(self.offset + pos);
+            if uffs_mft::u64_to_f64(seen) > rank {
+                break;
+            }
+        }
+        Some(last.map_or(0, bin_value).clamp(self.min, self.max))
+    }
+
+    /// Widen the tracked extremes to cover `low..=high`.
+    const fn track_extremes(&mut self, low: u64, high: u64) {
+        if self.count == 0 {
+            self.min = low;
+            self.max = high;
+        } else {
+            if low < self.min {
+                self.min = low;
+            }
+            if high > self.max {
+                self.max = high;
+            }
+        }
+    }
+
+    /// Add `hits` to bin `index`, growing the dense bin range as needed.
+    fn add(&mut self, index: usize, hits: u64) {
+        if self.bins.is_empty() {
+            self.offset = index;
+        } else if index < self.offset {
+            let grow = self.offset - index;
+            self.bins.splice(0..0, core::iter::repeat_n(0, grow));
+            self.offset = index;
+        }
+        let pos = index - self.offset;
+        if pos >= self.bins.len() {
+            self.bins.resize(pos + 1, 0);
+        }
+        if let Some(slot) = self.bins.get_mut(pos) {
+            *slot += hits;
+        }
+    }
+}
+
+/// The bin holding `value` (`value ≥ 1`): `⌈log_γ value⌉`.
+#[inline]
+fn bin_index(value: u64) -> usize {
+    uffs_mft::f64_to_usize(uffs_mft::u64_to_f64(value).log(GAMMA).ceil())
+}
+
+/// Representative value of bin `index`: `2γⁱ / (γ + 1)`, which is within
+/// [`RELATIVE_ACCURACY`] of every value in the bin.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "DDSketch bin midpoint is 2γⁱ / (γ + 1)"
+)]
+fn bin_value(index: usize) -> u64 {
+    let exponent = uffs_mft::u64_to_f64(uffs_mft::usize_to_u64(index));
+    uffs_mft::f64_to_u64((2.0_f64 * GAMMA.powf(exponent) / (GAMMA + 1.0_f64)).round())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_parse_and_label_round_trip() {
+        for (input, label) in [
+            ("p50", "p50"),
+            ("P90", "p90"),
+            ("p99.9", "p99.9"),
+            ("p99.99", "p99.99"),
+            ("p0", "p0"),
+            ("p100", "p100"),
+            ("median", "p50"),
+        ] {
+            let rank = PercentileRank::parse(input).expect(input);
+            assert_eq!(rank.label(), label, "{input}");
+        }
+        for bad in ["p", "50", "p101", "p99.999", "p-1", "p9x", "pmedian"] {
+            assert!(PercentileRank::parse(bad).is_none(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn estimates_stay_within_relative_accuracy() {
+        let mut sketch = QuantileSketch::new();
+        for value in 1..=10_000_u64 {
+            sketch.insert(value * value);
+        }
+        for (fraction, exact) in [(0.5_f64, 5_000_u64), (0.9_f64, 9_000), (0.99_f64, 9_900)] {
+            let estimate = uffs_mft::u64_to_f64(sketch.quantile(fraction).expect("non-empty"));
+            let truth = uffs_mft::u64_to_f64(exact * exact);
+            let error = (estimate - truth).abs() / truth;
+            assert!(error <= 0.011_f64, "q={fraction}: {estimate} vs {truth}");
+        }
+    }
+
+    #[test]
+    fn zeros_and_empty_sketches() {
+        let mut sketch = QuantileSketch::new();
+        assert_eq!(sketch.quantile(0.5_f64), None);
+        for value in [0, 0, 0, 1_000] {
+            sketch.insert(value);
+        }
+        assert_eq!(sketch.quantile(0.5_f64), Some(0));
+        assert_eq!(
+            sketch.quantile(1.0_f64),
+            Some(1_000),
+            "p100 is the exact max"
+        );
+    }
+
+    #[test]
+    fn extremes_are_exact_after_merge() {
+        let mut low = QuantileSketch::new();
+        let mut high = QuantileSketch::new();
+        for value in [1_234_u64, 5_000, 9_999] {
+            low.insert(value);
+        }
+        for value in [77_777_u64, 123_457] {
+            high.insert(value);
+        }
+        low.merge(&high);
+        low.merge(&QuantileSketch::new());
+        assert_eq!(low.quantile(0.0_f64), Some(1_234));
+        assert_eq!(low.quantile(1.0_f64), Some(123_457));
+    }
+
+    #[test]
+    fn merge_matches_a_single_sketch() {
+        let mut whole = QuantileSketch::new();
+        let mut low = QuantileSketch::new();
+        let mut high = QuantileSketch::new();
+        for value in 0..5_000_u64 {
+            let size = value * 37 + 1;
+            whole.insert(size);
+            if value < 2_500 { &mut low } else { &mut high }.insert(size);
+        }
+        // Merge the high-valued half into the low one and vice versa:
+        // both directions must grow the dense range correctly.
+        let mut merged = high.clone();
+        merged.merge(&low);
+        low.merge(&high);
+        for fraction in [0.0_f64, 0.25_f64, 0.5_f64, 0.9_f64, 0.99_f64, 1.0_f64] {
+            assert_eq!(merged.quantile(fraction), whole.quantile(fraction));
+            assert_eq!(low.quantile(fraction), whole.quantile(fraction));
+        }
+    }
+}
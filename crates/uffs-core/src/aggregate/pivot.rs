@@ -0,0 +1,595 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Two-dimensional pivot (cross-tab) aggregation.
+//!
+//! A pivot buckets every record on two axes at once — say `extension`
+//! down the side and `modified` age across the top — and reports one
+//! metric per cell. Cells are kept sparsely (only combinations that
+//! occur), so a pivot over two high-cardinality fields costs what the
+//! data costs, not rows × columns.
+//!
+//! At finalization each axis keeps its `top` values by record count;
+//! everything else rolls up into an [`OTHER_KEY`] row or column so the
+//! totals still add up. Value axes are listed in rank order, bucketed
+//! axes (size classes, ranges, calendar intervals, age) in their natural
+//! order. A cyclic calendar axis (hour of day, day of week) lists every
+//! slot of its cycle, so a weekday × hour pivot is a full 7×24 heatmap.
+
+use std::collections::HashMap;
+
+use uffs_time::TimeZone;
+
+use super::accumulators::StatsAccumulator;
+use super::buckets::{AgeBucket, SIZE_BOUNDARIES, SizeBucket, format_range_key};
+use super::extract::{extract_group_key, extract_timestamp, extract_value, truncate_timestamp};
+use super::finalize::resolve_group_key;
+use super::parser_error::ParseAggSpecError;
+use super::spec::{BucketMetric, CalendarInterval};
+use crate::compact::{CompactRecord, DriveCompactIndex};
+use crate::search::field::{FieldId, FieldType};
+
+/// Key of the roll-up row / column holding everything beyond `top`.
+pub const OTHER_KEY: &str = "(other)";
+
+/// Default number of rows kept by a pivot.
+pub const DEFAULT_ROWS_TOP: u16 = 20;
+
+/// Default number of columns kept by a pivot.
+pub const DEFAULT_COLS_TOP: u16 = 10;
+
+/// One axis of a pivot: which field, how its values become buckets, and
+/// how many buckets survive finalization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PivotAxis {
+    /// The field bucketed along this axis.
+    pub field: FieldId,
+    /// How values of `field` map to buckets.
+    pub buckets: AxisBuckets,
+    /// Buckets kept before the rest rolls up into [`OTHER_KEY`].
+    pub top: u16,
+}
+
+/// How a [`PivotAxis`] turns field values into buckets.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AxisBuckets {
+    /// One bucket per distinct value (groupable fields).
+    Values,
+    /// The standard size classes of [`SizeBucket`] (numeric fields).
+    SizeClasses,
+    /// Explicit boundaries: N boundaries → N+1 buckets (numeric fields).
+    Ranges(Vec<u64>),
+    /// Calendar-aligned intervals (timestamp fields).
+    Calendar(CalendarInterval),
+    /// Age relative to the scan, as [`AgeBucket`]s (timestamp fields).
+    Age,
+}
+
+impl PivotAxis {
+    /// An axis over `field` with the bucketing that suits its type:
+    /// [`AxisBuckets::Age`] for timestamps, [`AxisBuckets::Values`] for
+    /// groupable fields, [`AxisBuckets::SizeClasses`] otherwise.
+    #[must_use]
+    pub fn new(field: FieldId, top: u16) -> Self {
+        let meta = field.metadata();
+        let buckets = if meta.field_type == FieldType::Timestamp {
+            AxisBuckets::Age
+        } else if meta.aggregate.groupable {
+            AxisBuckets::Values
+        } else {
+            AxisBuckets::SizeClasses
+        };
+        Self {
+            field,
+            buckets,
+            top,
+        }
+    }
+
+    /// Parse `FIELD[@values|@sizes|@age|@<calendar>|@B+B+…]`.
+    ///
+    /// # Errors
+    ///
+    /// [`ParseAggSpecError::UnknownField`] for an unknown field, or
+    /// [`ParseAggSpecError::InvalidPivotAxis`] for an unknown bucketing.
+    pub fn parse(input: &str, top: u16) -> Result<Self, ParseAggSpecError> {
+        let trimmed = input.trim();
+        let (name, how) = trimmed.split_once('@').unwrap_or((trimmed, ""));
+        let field = FieldId::parse(name).ok_or_else(|| ParseAggSpecError::UnknownField {
+            name: name.to_owned(),
+        })?;
+        let mut axis = Self::new(field, top);
+        axis.buckets = match how {
+            "" => return Ok(axis),
+            "values" | "terms" => AxisBuckets::Values,
+            "sizes" | "size" => AxisBuckets::SizeClasses,
+            "age" => AxisBuckets::Age,
+            _ => CalendarInterval::parse(how)
+                .map_or_else(
+                    || parse_boundaries(how).map(AxisBuckets::Ranges),
+                    |calendar| Some(AxisBuckets::Calendar(calendar)),
+                )
+                .ok_or_else(|| ParseAggSpecError::InvalidPivotAxis {
+                    axis: input.trim().to_owned(),
+                })?,
+        };
+        Ok(axis)
+    }
+
+    /// Check that `field` supports this axis' bucketing.
+    ///
+    /// # Errors
+    ///
+    /// [`super::AggregateError::UnsupportedField`] naming the operation.
+    pub(crate) fn validate(&self) -> Result<(), super::AggregateError> {
+        let meta = self.field.metadata();
+        let (supported, operation) = match &self.buckets {
+            AxisBuckets::Values => (meta.aggregate.groupable, "pivot values (group-by)"),
+            AxisBuckets::SizeClasses | AxisBuckets::Ranges(_) => (
+                meta.aggregate.bucket_support && meta.field_type == FieldType::Numeric,
+                "pivot ranges (numeric bucket)",
+            ),
+            AxisBuckets::Calendar(_) | AxisBuckets::Age => (
+                meta.field_type == FieldType::Timestamp,
+                "pivot calendar/age (timestamp)",
+            ),
+        };
+        if supported {
+            Ok(())
+        } else {
+            Err(super::AggregateError::UnsupportedField {
+                field: meta.canonical_name.to_owned(),
+                operation: operation.to_owned(),
+            })
+        }
+    }
+
+    /// Whether buckets have a natural order (everything but values).
+    const fn is_ordered(&self) -> bool {
+        !matches!(self.buckets, AxisBuckets::Values)
+    }
+
+    /// Slots in one cycle of a cyclic calendar axis.
+    const fn cycle(&self) -> Option<usize> {
+        match &self.buckets {
+            AxisBuckets::Calendar(calendar) => calendar.period(),
+            AxisBuckets::Values
+            | AxisBuckets::SizeClasses
+            | AxisBuckets::Ranges(_)
+            | AxisBuckets::Age => None,
+        }
+    }
+
+    /// The bucket key of `record` on this axis.
+    fn key(&self, record: &CompactRecord, scan: &ScanContext<'_>) -> u64 {
+        let field = Some(self.field);
+        match &self.buckets {
+            AxisBuckets::Values => {
+                extract_group_key(field, record, scan.drive, scan.drive_ordinal, scan.ext_map)
+            }
+            AxisBuckets::SizeClasses => {
+                bucket_index(&SIZE_BOUNDARIES, extract_value(field, record))
+            }
+            AxisBuckets::Ranges(boundaries) => {
+                bucket_index(boundaries, extract_value(field, record))
+            }
+            AxisBuckets::Calendar(calendar) => {
+                truncate_timestamp(extract_timestamp(field, record), *calendar, scan.time_zone)
+                    .cast_unsigned()
+            }
+            AxisBuckets::Age => {
+                let age_us = (scan.now - extract_timestamp(field, record))
+                    / uffs_time::FILETIME_TICKS_PER_MICROSECOND;
+                let bucket = AgeBucket::classify(age_us);
+                uffs_mft::usize_to_u64(
+                    AgeBucket::ALL
+                        .iter()
+                        .position(|&age| age == bucket)
+                        .unwrap_or_default(),
+                )
+            }
+        }
+    }
+
+    /// Display label of bucket `key`.
+    fn label(
+        &self,
+        key: u64,
+        drives: &[&DriveCompactIndex],
+        ext_map: &super::ExtensionMap,
+    ) -> String {
+        let index = usize::try_from(key).unwrap_or(usize::MAX);
+        match &self.buckets {
+            AxisBuckets::Values => resolve_group_key(Some(self.field), key, drives, ext_map),
+            AxisBuckets::SizeClasses => SizeBucket::ALL
+                .get(index)
+                .map_or_else(|| format!("bucket_{index}"), |size| size.label().to_owned()),
+            AxisBuckets::Ranges(boundaries) => format_range_key(index, boundaries),
+            AxisBuckets::Calendar(calendar) => calendar.key_label(key.cast_signed()),
+            AxisBuckets::Age => AgeBucket::ALL
+                .get(index)
+                .map_or_else(|| format!("bucket_{index}"), |age| age.label().to_owned()),
+        }
+    }
+}
+
+/// Parse `B+B+…` (or range syntax `A..B+B..C`) into sorted boundaries.
+fn parse_boundaries(input: &str) -> Option<Vec<u64>> {
+    let mut boundaries = input
+        .split('+')
+        .flat_map(|part| part.split(".."))
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    boundaries.sort_unstable();
+    boundaries.dedup();
+    (!boundaries.is_empty()).then_some(boundaries)
+}
+
+/// Index of the bucket holding `value` (upper boundaries exclusive).
+fn bucket_index(boundaries: &[u64], value: u64) -> u64 {
+    uffs_mft::usize_to_u64(boundaries.partition_point(|&boundary| boundary <= value))
+}
+
+/// Per-record context for [`PivotAxis::key`].
+struct ScanContext<'scan> {
+    /// The drive being scanned.
+    drive: &'scan DriveCompactIndex,
+    /// Its ordinal in the scan.
+    drive_ordinal: u8,
+    /// Cross-drive extension ids.
+    ext_map: &'scan super::ExtensionMap,
+    /// FILETIME the accumulator was created at (for age buckets).
+    now: i64,
+    /// Zone calendar buckets are cut in.
+    time_zone: TimeZone,
+}
+
+/// Sparse per-cell statistics for one pivot.
+#[derive(Debug, Clone)]
+pub struct PivotAccumulator {
+    /// Row axis.
+    rows: PivotAxis,
+    /// Column axis.
+    cols: PivotAxis,
+    /// Reported metric.
+    metric: BucketMetric,
+    /// `(row key, column key)` → statistics of the records in that cell.
+    cells: HashMap<(u64, u64), StatsAccumulator>,
+    /// FILETIME age buckets are measured from.
+    now: i64,
+    /// Zone calendar axes are cut in.
+    time_zone: TimeZone,
+}
+
+impl PivotAccumulator {
+    /// An empty pivot whose calendar axes are cut in `time_zone`.
+    #[must_use]
+    pub(crate) fn new(
+        rows: PivotAxis,
+        cols: PivotAxis,
+        metric: BucketMetric,
+        time_zone: TimeZone,
+    ) -> Self {
+        Self {
+            rows,
+            cols,
+            metric,
+            cells: HashMap::new(),
+            now: crate::search::filters::now_filetime(),
+            time_zone,
+        }
+    }
+
+    /// Add one record to its cell.
+    #[inline]
+    pub(crate) fn feed(
+        &mut self,
+        record: &CompactRecord,
+        drive: &DriveCompactIndex,
+        drive_ordinal: u8,
+        ext_map: &super::ExtensionMap,
+    ) {
+        let scan = ScanContext {
+            drive,
+            drive_ordinal,
+            ext_map,
+            now: self.now,
+            time_zone: self.time_zone,
+        };
+        let cell = (self.rows.key(record, &scan), self.cols.key(record, &scan));
+        let sketch = matches!(self.metric, BucketMetric::SizePercentile(_));
+        self.cells
+            .entry(cell)
+            .or_insert_with(|| StatsAccumulator::tracking(sketch))
+            .feed_value(record.size, record.allocated);
+    }
+
+    /// Fold another drive's cells into this one.
+    #[expect(
+        clippy::iter_over_hash_type,
+        reason = "per-cell merge is order-independent"
+    )]
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (cell, theirs) in &other.cells {
+            self.cells
+                .entry(*cell)
+                .and_modify(|ours| ours.merge(theirs))
+                .or_insert_with(|| theirs.clone());
+        }
+    }
+
+    /// Keep the top rows / columns, roll the rest into [`OTHER_KEY`], and
+    /// evaluate the metric for every cell, row, column and the grand total.
+    #[must_use]
+    #[expect(
+        clippy::iter_over_hash_type,
+        reason = "cells fold into per-slot accumulators; merge order does not matter"
+    )]
+    pub(crate) fn finalize(
+        &self,
+        drives: &[&DriveCompactIndex],
+        ext_map: &super::ExtensionMap,
+    ) -> PivotResult {
+        let mut row_marginals: HashMap<u64, u64> = HashMap::new();
+        let mut col_marginals: HashMap<u64, u64> = HashMap::new();
+        for (&(row, col), stats) in &self.cells {
+            *row_marginals.entry(row).or_default() += stats.count;
+            *col_marginals.entry(col).or_default() += stats.count;
+        }
+        let row_axis = AxisSlots::select(&self.rows, &row_marginals);
+        let col_axis = AxisSlots::select(&self.cols, &col_marginals);
+
+        let mut grid: HashMap<(usize, usize), StatsAccumulator> = HashMap::new();
+        let mut row_totals = vec![StatsAccumulator::new(); row_axis.len()];
+        let mut col_totals = vec![StatsAccumulator::new(); col_axis.len()];
+        let mut grand = StatsAccumulator::new();
+        for (&(row, col), stats) in &self.cells {
+            let slot = (row_axis.slot(row), col_axis.slot(col));
+            grid.entry(slot).or_default().merge(stats);
+            if let Some(total) = row_totals.get_mut(slot.0) {
+                total.merge(stats);
+            }
+            if let Some(total) = col_totals.get_mut(slot.1) {
+                total.merge(stats);
+            }
+            grand.merge(stats);
+        }
+
+        let value = |stats: &StatsAccumulator| metric_value(stats, self.metric, &grand);
+        let rows = row_axis
+            .labels(&self.rows, drives, ext_map)
+            .into_iter()
+            .zip(&row_totals)
+            .enumerate()
+            .map(|(row, (key, total))| PivotRow {
+                key,
+                values: (0..col_axis.len())
+                    .map(|col| grid.get(&(row, col)).map(value))
+                    .collect(),
+                total: value(total),
+            })
+            .collect();
+
+        let calendar = |axis: &PivotAxis| matches!(axis.buckets, AxisBuckets::Calendar(_));
+        PivotResult {
+            row_field: self.rows.field.metadata().canonical_name.to_owned(),
+            col_field: self.cols.field.metadata().canonical_name.to_owned(),
+            metric: self.metric,
+            columns: col_axis.labels(&self.cols, drives, ext_map),
+            rows,
+            column_totals: col_totals.iter().map(value).collect(),
+            grand_total: value(&grand),
+            total_rows: row_marginals.len(),
+            total_cols: col_marginals.len(),
+            heatmap: self.rows.cycle().is_some() && self.cols.cycle().is_some(),
+            time_zone: (calendar(&self.rows) || calendar(&self.cols))
+                .then(|| self.time_zone.to_string()),
+        }
+    }
+}
+
+/// The surviving buckets of one axis, in display order, plus whether an
+/// [`OTHER_KEY`] slot follows them.
+struct AxisSlots {
+    /// Kept bucket keys, in display order.
+    kept: Vec<u64>,
+    /// Whether buckets beyond `top` exist.
+    other: bool,
+}
+
+impl AxisSlots {
+    /// Keep the `top` buckets with the most records — or, on a cyclic
+    /// axis with room for it, the whole cycle.
+    fn select(axis: &PivotAxis, marginals: &HashMap<u64, u64>) -> Self {
+        if let Some(cycle) = axis.cycle()
+            && cycle <= usize::from(axis.top)
+        {
+            return Self {
+                kept: (0..cycle).map(uffs_mft::usize_to_u64).collect(),
+                other: false,
+            };
+        }
+        let mut ranked: Vec<(u64, u64)> = marginals.iter().map(|(&key, &n)| (key, n)).collect();
+        ranked.sort_unstable_by(|lhs, rhs| rhs.1.cmp(&lhs.1).then(lhs.0.cmp(&rhs.0)));
+        let other = ranked.len() > usize::from(axis.top);
+        ranked.truncate(usize::from(axis.top));
+        let mut kept: Vec<u64> = ranked.into_iter().map(|(key, _)| key).collect();
+        if axis.is_ordered() {
+            kept.sort_unstable();
+        }
+        Self { kept, other }
+    }
+
+    /// Number of slots, the roll-up included.
+    fn len(&self) -> usize {
+        self.kept.len() + usize::from(self.other)
+    }
+
+    /// Slot of bucket `key`: its position, or the roll-up slot.
+    fn slot(&self, key: u64) -> usize {
+        self.kept
+            .iter()
+            .position(|&kept| kept == key)
+            .unwrap_or(self.kept.len())
+    }
+
+    /// Display labels of every slot.
+    fn labels(
+        &self,
+        axis: &PivotAxis,
+        drives: &[&DriveCompactIndex],
+        ext_map: &super::ExtensionMap,
+    ) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .kept
+            .iter()
+            .map(|&key| axis.label(key, drives, ext_map))
+            .collect();
+        if self.other {
+            labels.push(OTHER_KEY.to_owned());
+        }
+        labels
+    }
+}
+
+/// `metric` of `stats`; shares are relative to `grand`.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "share-of-total percentages are the documented bucket-row formula"
+)]
+fn metric_value(stats: &StatsAccumulator, metric: BucketMetric, grand: &StatsAccumulator) -> f64 {
+    let share = |part: u64, whole: u64| {
+        if whole == 0 {
+            0.0_f64
+        } else {
+            uffs_mft::u64_to_f64(part) / uffs_mft::u64_to_f64(whole) * 100.0_f64
+        }
+    };
+    match metric {
+        BucketMetric::Count => uffs_mft::u64_to_f64(stats.count),
+        BucketMetric::TotalBytes => uffs_mft::u64_to_f64(stats.sum),
+        BucketMetric::TotalAllocated => uffs_mft::u64_to_f64(stats.sum_allocated),
+        BucketMetric::WasteBytes => uffs_mft::u64_to_f64(stats.waste_bytes()),
+        BucketMetric::WastePct => stats.waste_pct(),
+        BucketMetric::AvgSize => stats.avg(),
+        BucketMetric::MinSize if stats.count == 0 => 0.0_f64,
+        BucketMetric::MinSize => uffs_mft::u64_to_f64(stats.min),
+        BucketMetric::MaxSize => uffs_mft::u64_to_f64(stats.max),
+        BucketMetric::ShareOfTotalCount => share(stats.count, grand.count),
+        BucketMetric::ShareOfTotalBytes => share(stats.sum, grand.sum),
+        BucketMetric::SizePercentile(rank) => {
+            uffs_mft::u64_to_f64(stats.percentile(rank).unwrap_or_default())
+        }
+    }
+}
+
+/// Canonical name of `metric`, as accepted by the `metrics=` / `metric=`
+/// options (`count`, `total_bytes`, `p90`, …).
+#[must_use]
+pub fn metric_name(metric: BucketMetric) -> String {
+    match metric {
+        BucketMetric::Count => "count".to_owned(),
+        BucketMetric::TotalBytes => "total_bytes".to_owned(),
+        BucketMetric::TotalAllocated => "total_allocated".to_owned(),
+        BucketMetric::WasteBytes => "waste_bytes".to_owned(),
+        BucketMetric::WastePct => "waste_pct".to_owned(),
+        BucketMetric::AvgSize => "avg_size".to_owned(),
+        BucketMetric::MinSize => "min_size".to_owned(),
+        BucketMetric::MaxSize => "max_size".to_owned(),
+        BucketMetric::ShareOfTotalCount => "share_count".to_owned(),
+        BucketMetric::ShareOfTotalBytes => "share_bytes".to_owned(),
+        BucketMetric::SizePercentile(rank) => rank.label(),
+    }
+}
+
+/// A finalized pivot: one metric value per (row, column) cell.
+#[derive(Debug, Clone)]
+pub struct PivotResult {
+    /// Field bucketed down the rows.
+    pub row_field: String,
+    /// Field bucketed across the columns.
+    pub col_field: String,
+    /// The metric every value reports.
+    pub metric: BucketMetric,
+    /// Column labels, in display order ([`OTHER_KEY`] last when present).
+    pub columns: Vec<String>,
+    /// Rows in display order ([`OTHER_KEY`] last when present).
+    pub rows: Vec<PivotRow>,
+    /// Metric of each column across all rows.
+    pub column_totals: Vec<f64>,
+    /// Metric over every record in the pivot.
+    pub grand_total: f64,
+    /// Distinct row buckets before the `top` cut.
+    pub total_rows: usize,
+    /// Distinct column buckets before the `top` cut.
+    pub total_cols: usize,
+    /// Both axes are cyclic calendars (weekday × hour): every slot is
+    /// present, and the matrix reads as a heatmap.
+    pub heatmap: bool,
+    /// Zone the calendar axes were cut in (`UTC`, `UTC+02:00`); `None`
+    /// without a calendar axis.
+    pub time_zone: Option<String>,
+}
+
+/// One pivot row.
+#[derive(Debug, Clone)]
+pub struct PivotRow {
+    /// Row label.
+    pub key: String,
+    /// Metric per column; `None` where no record falls in the cell.
+    pub values: Vec<Option<f64>>,
+    /// Metric across the whole row.
+    pub total: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_parse_picks_bucketing() {
+        let axis = PivotAxis::parse("extension", 5).expect("values");
+        assert_eq!(axis.buckets, AxisBuckets::Values);
+        assert_eq!(axis.top, 5);
+        let age = PivotAxis::parse("modified", 5).expect("age default");
+        assert_eq!(age.buckets, AxisBuckets::Age);
+        let sizes = PivotAxis::parse("size", 5).expect("size classes");
+        assert_eq!(sizes.buckets, AxisBuckets::SizeClasses);
+        let month = PivotAxis::parse("created@month", 5).expect("calendar");
+        assert_eq!(
+            month.buckets,
+            AxisBuckets::Calendar(CalendarInterval::Month)
+        );
+        let ranges = PivotAxis::parse("size@1048576+1024", 5).expect("ranges");
+        assert_eq!(ranges.buckets, AxisBuckets::Ranges(vec![1024, 1_048_576]));
+        assert!(matches!(
+            PivotAxis::parse("size@bogus", 5),
+            Err(ParseAggSpecError::InvalidPivotAxis { .. })
+        ));
+        assert!(matches!(
+            PivotAxis::parse("nope", 5),
+            Err(ParseAggSpecError::UnknownField { .. })
+        ));
+    }
+
+    #[test]
+    fn axis_validation_matches_field_types() {
+        PivotAxis::parse("extension", 5)
+            .expect("ok")
+            .validate()
+            .expect("extension takes its own values");
+        PivotAxis::parse("size@age", 5)
+            .expect("ok")
+            .validate()
+            .expect_err("size@age is not a valid axis");
+        PivotAxis::parse("modified@values", 5)
+            .expect("ok")
+            .validate()
+            .expect_err("modified@values is not a valid axis");
+        PivotAxis::parse("extension@sizes", 5)
+            .expect("ok")
+            .validate()
+            .expect_err("extension@sizes is not a valid axis");
+    }
+}
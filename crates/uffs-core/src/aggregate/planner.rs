@@ -9,6 +9,7 @@
 use super::AggregateError;
 use super::accumulators::GroupAccumulator;
 use super::spec::{AggregateKind, AggregateSpec};
+use crate::search::field::FieldType;
 
 /// A compiled aggregate execution plan.
 ///
@@ -44,10 +45,7 @@ impl AggregatePlan {
     /// Create a fresh set of accumulators for this plan.
     #[must_use]
     pub(crate) fn create_accumulators(&self) -> Vec<GroupAccumulator> {
-        self.specs
-            .iter()
-            .map(|spec| GroupAccumulator::from_kind(&spec.kind, spec.label.clone()))
-            .collect()
+        self.specs.iter().map(GroupAccumulator::from_spec).collect()
     }
 
     /// Number of specs in this plan.
@@ -62,15 +60,33 @@ impl AggregatePlan {
         self.specs.is_empty()
     }
 
-    /// Validate that a spec's field supports the requested operation.
+    /// Validate that a spec's field supports the requested operation, and
+    /// that a nested `sub` chain only nests bucketed kinds.
     fn validate_spec(spec: &AggregateSpec) -> Result<(), AggregateError> {
-        match &spec.kind {
+        if let Some(sub) = spec.sub.as_deref() {
+            if !spec.kind.is_bucketed() || !sub.kind.is_bucketed() {
+                return Err(AggregateError::InvalidConfig(
+                    "nested aggregations need bucketed kinds (terms, hist, datehist, range, \
+                     rollup) at every level"
+                        .to_owned(),
+                ));
+            }
+            Self::validate_spec(sub)?;
+        }
+        Self::validate_kind(&spec.kind)
+    }
+
+    /// Validate that a kind's field supports the requested operation.
+    fn validate_kind(kind: &AggregateKind) -> Result<(), AggregateError> {
+        match kind {
             AggregateKind::Count
             | AggregateKind::Missing { .. }
+            | AggregateKind::RegexTerms { .. }
             | AggregateKind::Rollup { .. }
-            | AggregateKind::Duplicates { .. } => Ok(()),
+            | AggregateKind::Duplicates { .. }
+            | AggregateKind::SimilarNames { .. } => Ok(()),
 
-            AggregateKind::Stats { field, .. } => {
+            AggregateKind::Stats { field, metrics } => {
                 let meta = field.metadata();
                 if !meta.aggregate.aggregatable {
                     return Err(AggregateError::UnsupportedField {
@@ -78,6 +94,16 @@ impl AggregatePlan {
                         operation: "stats (sum/min/max/avg)".to_owned(),
                     });
                 }
+                // The sketch's error is relative to the value; on FILETIME
+                // ticks 1 % spans years, so timestamps get no percentiles.
+                if meta.field_type != FieldType::Numeric
+                    && !super::percentile::scalar_ranks(metrics).is_empty()
+                {
+                    return Err(AggregateError::UnsupportedField {
+                        field: meta.canonical_name.to_owned(),
+                        operation: "percentiles (numeric fields only)".to_owned(),
+                    });
+                }
                 Ok(())
             }
 
@@ -114,6 +140,11 @@ impl AggregatePlan {
                 Ok(())
             }
 
+            AggregateKind::Pivot { rows, cols, .. } => {
+                rows.validate()?;
+                cols.validate()
+            }
+
             AggregateKind::Distinct { field } => {
                 let meta = field.metadata();
                 if !meta.aggregate.groupable {
@@ -131,7 +162,7 @@ impl AggregatePlan {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::aggregate::spec::{BucketMetric, ScalarMetric};
+    use crate::aggregate::spec::{BucketMetric, PercentileRank, ScalarMetric};
     use crate::search::field::FieldId;
 
     #[test]
@@ -162,4 +193,41 @@ mod tests {
         let plan = AggregatePlan::compile(&specs).expect("ext terms should compile");
         assert_eq!(plan.len(), 1);
     }
+
+    #[test]
+    fn compile_rejects_non_bucketed_nesting() {
+        let terms = AggregateSpec::new(AggregateKind::Terms {
+            field: FieldId::Drive,
+            top: 26,
+            metrics: vec![BucketMetric::Count],
+            sample: None,
+        });
+        let under_count = AggregateSpec::new(AggregateKind::Count).with_sub(terms.clone());
+        let count_under = terms
+            .clone()
+            .with_sub(AggregateSpec::new(AggregateKind::Count));
+        for spec in [under_count, count_under] {
+            assert!(matches!(
+                AggregatePlan::compile(&[spec]),
+                Err(AggregateError::InvalidConfig(_))
+            ));
+        }
+        let nested = terms.clone().with_sub(terms);
+        AggregatePlan::compile(&[nested]).expect("terms>terms should compile");
+    }
+
+    #[test]
+    fn compile_rejects_timestamp_percentiles() {
+        let stats = |field| {
+            AggregateSpec::new(AggregateKind::Stats {
+                field,
+                metrics: vec![ScalarMetric::Percentile(PercentileRank::MEDIAN)],
+            })
+        };
+        AggregatePlan::compile(&[stats(FieldId::Size)]).expect("size median should compile");
+        assert!(matches!(
+            AggregatePlan::compile(&[stats(FieldId::Modified)]),
+            Err(AggregateError::UnsupportedField { .. })
+        ));
+    }
 }
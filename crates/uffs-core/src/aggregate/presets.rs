@@ -8,7 +8,8 @@
 //! directly to the aggregation engine.
 
 use super::spec::{
-    AggregateKind, AggregateSpec, BucketMetric, CalendarInterval, ScalarMetric, TopHitsSpec,
+    AggregateKind, AggregateSpec, BucketMetric, CalendarInterval, PercentileRank, RegexKey,
+    ScalarMetric, TopHitsSpec,
 };
 use crate::search::field::FieldId;
 
@@ -18,8 +19,10 @@ use crate::search::field::FieldId;
 /// [`Self::expand()`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AggregatePreset {
-    /// Full filesystem overview: count, files-vs-dirs, size totals,
-    /// type facet, drive facet, monthly modified histogram.
+    /// Full filesystem overview: count, files-vs-dirs, size totals and
+    /// median/p90/p99,
+    /// type facet, drive facet (with a nested per-drive type
+    /// breakdown), monthly modified histogram.
     Overview,
     /// Breakdown by semantic type with size/waste metrics.
     ByType,
@@ -43,6 +46,8 @@ pub enum AggregatePreset {
     Media,
     /// Cleanup: zero-byte files, temp files, old files.
     Cleanup,
+    /// Name years: files by a 19xx/20xx year in their name.
+    NameYears,
 }
 
 impl AggregatePreset {
@@ -64,6 +69,7 @@ impl AggregatePreset {
             "duplicates" | "dups" => Some(Self::Duplicates),
             "media" => Some(Self::Media),
             "cleanup" => Some(Self::Cleanup),
+            "name_years" | "nameyears" | "years" => Some(Self::NameYears),
             _ => None,
         }
     }
@@ -84,6 +90,7 @@ impl AggregatePreset {
             Self::Duplicates => expand_duplicates(),
             Self::Media => expand_media(),
             Self::Cleanup => expand_cleanup(),
+            Self::NameYears => expand_name_years(),
         }
     }
 
@@ -101,10 +108,12 @@ impl AggregatePreset {
         "duplicates",
         "media",
         "cleanup",
+        "name_years",
     ];
 }
 
-/// Overview: count + files-vs-dirs + size stats + type facet + drive facet +
+/// Overview: count + files-vs-dirs + size stats (with median/p90/p99) +
+/// type facet + drive facet +
 /// monthly histogram.
 fn expand_overview() -> Vec<AggregateSpec> {
     let default_metrics = vec![
@@ -134,6 +143,9 @@ fn expand_overview() -> Vec<AggregateSpec> {
                     ScalarMetric::Min,
                     ScalarMetric::Max,
                     ScalarMetric::Avg,
+                    ScalarMetric::Percentile(PercentileRank::MEDIAN),
+                    ScalarMetric::Percentile(PercentileRank::P90),
+                    ScalarMetric::Percentile(PercentileRank::P99),
                 ],
             },
             "size_stats",
@@ -151,15 +163,22 @@ fn expand_overview() -> Vec<AggregateSpec> {
             AggregateKind::Terms {
                 field: FieldId::Drive,
                 top: 26,
-                metrics: default_metrics,
+                metrics: default_metrics.clone(),
                 sample: None,
             },
             "by_drive",
-        ),
+        )
+        .with_sub(AggregateSpec::new(AggregateKind::Terms {
+            field: FieldId::Type,
+            top: 10,
+            metrics: default_metrics,
+            sample: None,
+        })),
         AggregateSpec::with_label(
             AggregateKind::DateHistogram {
                 field: FieldId::Modified,
                 calendar: CalendarInterval::Month,
+                time_zone: None,
                 metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
             },
             "modified_monthly",
@@ -276,6 +295,7 @@ fn expand_by_age() -> Vec<AggregateSpec> {
         AggregateKind::DateHistogram {
             field: FieldId::Modified,
             calendar: CalendarInterval::Month,
+            time_zone: None,
             metrics: vec![
                 BucketMetric::Count,
                 BucketMetric::TotalBytes,
@@ -343,6 +363,7 @@ fn expand_activity() -> Vec<AggregateSpec> {
             AggregateKind::DateHistogram {
                 field: FieldId::Modified,
                 calendar: CalendarInterval::Month,
+                time_zone: None,
                 metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
             },
             "modified_monthly",
@@ -351,6 +372,7 @@ fn expand_activity() -> Vec<AggregateSpec> {
             AggregateKind::DateHistogram {
                 field: FieldId::Created,
                 calendar: CalendarInterval::Month,
+                time_zone: None,
                 metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
             },
             "created_monthly",
@@ -359,6 +381,7 @@ fn expand_activity() -> Vec<AggregateSpec> {
             AggregateKind::DateHistogram {
                 field: FieldId::Accessed,
                 calendar: CalendarInterval::Month,
+                time_zone: None,
                 metrics: vec![BucketMetric::Count],
             },
             "accessed_monthly",
@@ -381,7 +404,6 @@ fn expand_top_folders() -> Vec<AggregateSpec> {
                 BucketMetric::ShareOfTotalBytes,
             ],
             sample: None,
-            sub: None,
         },
         "top_folders",
     )]
@@ -443,6 +465,7 @@ fn expand_media() -> Vec<AggregateSpec> {
             AggregateKind::DateHistogram {
                 field: FieldId::Created,
                 calendar: CalendarInterval::Month,
+                time_zone: None,
                 metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
             },
             "media_created_monthly",
@@ -475,6 +498,34 @@ fn expand_cleanup() -> Vec<AggregateSpec> {
     ]
 }
 
+/// A 19xx/20xx year standing on its own in a name (`2023-report`,
+/// `IMG_1999_07`), not part of a longer number.
+const NAME_YEAR_PATTERN: &str = "(?:^|[^0-9])((?:19|20)[0-9]{2})(?:[^0-9]|$)";
+
+/// Name years: files by the year in their name, with an `(unmatched)`
+/// bucket for the rest.
+fn expand_name_years() -> Vec<AggregateSpec> {
+    RegexKey::new(FieldId::Name, NAME_YEAR_PATTERN)
+        .into_iter()
+        .map(|key| {
+            AggregateSpec::with_label(
+                AggregateKind::RegexTerms {
+                    key,
+                    top: 30,
+                    metrics: vec![
+                        BucketMetric::Count,
+                        BucketMetric::TotalBytes,
+                        BucketMetric::ShareOfTotalCount,
+                    ],
+                    sample: None,
+                    unmatched: true,
+                },
+                "name_years",
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 #[expect(
     clippy::indexing_slicing,
@@ -513,6 +564,19 @@ mod tests {
         assert!(labels.contains(&"size_stats"));
         assert!(labels.contains(&"by_type"));
         assert!(labels.contains(&"by_drive"));
+
+        // by_drive nests a per-drive type breakdown.
+        let by_drive = specs
+            .iter()
+            .find(|spec| spec.label.as_deref() == Some("by_drive"))
+            .expect("by_drive");
+        assert!(matches!(
+            by_drive.sub.as_deref().map(|sub| &sub.kind),
+            Some(AggregateKind::Terms {
+                field: FieldId::Type,
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -555,6 +619,19 @@ mod tests {
         assert!(matches!(specs[0].kind, AggregateKind::DateHistogram { .. }));
     }
 
+    #[test]
+    fn name_years_expansion() {
+        let specs = AggregatePreset::NameYears.expand();
+        assert_eq!(specs.len(), 1);
+        let AggregateKind::RegexTerms { key, unmatched, .. } = &specs[0].kind else {
+            panic!("expected RegexTerms");
+        };
+        assert!(unmatched);
+        assert_eq!(key.capture("2023-report.pdf"), Some("2023"));
+        assert_eq!(key.capture("IMG_1999_07.jpg"), Some("1999"));
+        assert_eq!(key.capture("invoice-120234.pdf"), None);
+    }
+
     #[test]
     fn all_presets_produce_valid_specs() {
         use crate::aggregate::planner::AggregatePlan;
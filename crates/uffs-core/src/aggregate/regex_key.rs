@@ -0,0 +1,601 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Regex capture groups as `terms` keys.
+//!
+//! `terms:regex(name, "^(\d{4})-")` groups records by the text the first
+//! capture group of a regex takes from the file name, and
+//! `terms:regex(path, "\\Clients\\([^\\]+)\\")` does the same over the
+//! full path — project codes, years or client folders without
+//! post-processing the rows. Matching is case-insensitive, like `>regex`
+//! search patterns; a key keeps the spelling it was captured with.
+//!
+//! Records the regex does not match are skipped, or counted in an
+//! `(unmatched)` bucket with `unmatched=true`. Each bucket's drill-down
+//! pins the capture group to the bucket's key (`^(2023)-`), so a
+//! follow-up `>regex` search returns exactly its records.
+
+use core::hash::{Hash, Hasher};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+
+use regex::{Regex, RegexBuilder};
+
+use super::accumulators::StatsAccumulator;
+use super::finalize::{
+    AggregateResultData, BucketRow, DrilldownPredicate, DrilldownValue, FinalizeOptions, RowContext,
+};
+use super::nested::Nester;
+use super::parser_error::ParseAggSpecError;
+use super::sample_heap::{SampleHeap, materialize_sample_entry};
+use super::spec::TopHitsSpec;
+use crate::compact::{CompactRecord, DriveCompactIndex, MalformedRender};
+use crate::search::field::FieldId;
+use crate::search::tree::{DirCache, resolve_path_cached};
+
+/// Key of the bucket holding records the regex does not match.
+pub const UNMATCHED_KEY: &str = "(unmatched)";
+
+/// A regex over a record's name or full path whose first capture group
+/// is a bucket key.
+///
+/// Compiled once when parsed; equality and hashing (for the aggregate
+/// cache) go by field and pattern text.
+#[derive(Debug, Clone)]
+pub struct RegexKey {
+    /// [`FieldId::Name`] or [`FieldId::Path`].
+    field: FieldId,
+    /// The pattern as written.
+    pattern: String,
+    /// The compiled, case-insensitive pattern.
+    regex: Regex,
+}
+
+impl RegexKey {
+    /// Compile `pattern` as a key over `field`.
+    ///
+    /// # Errors
+    ///
+    /// [`ParseAggSpecError::RegexKeyField`] for a field other than `name`
+    /// or `path`, [`ParseAggSpecError::InvalidRegex`] for a pattern that
+    /// does not compile, and [`ParseAggSpecError::RegexWithoutCapture`]
+    /// for one without a capture group.
+    pub fn new(field: FieldId, pattern: &str) -> Result<Self, ParseAggSpecError> {
+        if !matches!(field, FieldId::Name | FieldId::Path) {
+            return Err(ParseAggSpecError::RegexKeyField {
+                name: field.metadata().canonical_name.to_owned(),
+            });
+        }
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|err| ParseAggSpecError::InvalidRegex {
+                pattern: pattern.to_owned(),
+                reason: err.to_string(),
+            })?;
+        if regex.captures_len() < 2 {
+            return Err(ParseAggSpecError::RegexWithoutCapture {
+                pattern: pattern.to_owned(),
+            });
+        }
+        Ok(Self {
+            field,
+            pattern: pattern.to_owned(),
+            regex,
+        })
+    }
+
+    /// Parse a leading `regex(FIELD, "PATTERN")` off `input`, returning the
+    /// key and the text after the closing `)` — or `None` when `input`
+    /// does not start with `regex(`.
+    ///
+    /// Inside the quotes `\"` stands for a quote; every other character,
+    /// backslashes included, is part of the pattern.
+    ///
+    /// # Errors
+    ///
+    /// [`ParseAggSpecError::InvalidRegexKey`] for a malformed expression,
+    /// or any error of [`Self::new`].
+    #[must_use]
+    pub fn parse_prefix(input: &str) -> Option<Result<(Self, &str), ParseAggSpecError>> {
+        let inner = input.trim_start().strip_prefix("regex(")?;
+        let malformed = || ParseAggSpecError::InvalidRegexKey {
+            expr: input.trim().to_owned(),
+        };
+        Some((|| {
+            let (field_str, quoted) = inner.split_once(',').ok_or_else(malformed)?;
+            let field = FieldId::parse(field_str.trim()).ok_or_else(|| {
+                ParseAggSpecError::UnknownField {
+                    name: field_str.trim().to_owned(),
+                }
+            })?;
+            let body = quoted
+                .trim_start()
+                .strip_prefix('"')
+                .ok_or_else(malformed)?;
+            let (pattern, after) = unquote(body).ok_or_else(malformed)?;
+            let rest = after.trim_start().strip_prefix(')').ok_or_else(malformed)?;
+            Ok((Self::new(field, &pattern)?, rest))
+        })())
+    }
+
+    /// The field the regex runs over.
+    #[must_use]
+    pub const fn field(&self) -> FieldId {
+        self.field
+    }
+
+    /// The pattern as written.
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// `regex(FIELD, "PATTERN")`, the result's field label.
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!(
+            "regex({}, \"{}\")",
+            self.field.metadata().canonical_name,
+            self.pattern
+        )
+    }
+
+    /// The first capture group's text in `haystack`, or `None` when the
+    /// regex does not match or the group took no part in the match.
+    #[must_use]
+    pub fn capture<'hay>(&self, haystack: &'hay str) -> Option<&'hay str> {
+        self.regex
+            .captures(haystack)
+            .and_then(|caps| caps.get(1))
+            .map(|group| group.as_str())
+    }
+
+    /// Drill-down narrowing to the bucket `key`: the pattern with its
+    /// first capture group pinned to the key, or a `not_regex` on the
+    /// whole pattern for the unmatched bucket.
+    fn drilldown(&self, key: Option<&str>) -> DrilldownPredicate {
+        let (op, pattern) = key.map_or_else(
+            || ("not_regex", self.pattern.clone()),
+            |text| {
+                (
+                    "regex",
+                    pin_first_group(&self.pattern, &regex::escape(text))
+                        .unwrap_or_else(|| self.pattern.clone()),
+                )
+            },
+        );
+        DrilldownPredicate {
+            field: self.field.metadata().canonical_name.to_owned(),
+            op: op.to_owned(),
+            value: DrilldownValue::String(pattern),
+        }
+    }
+}
+
+impl PartialEq for RegexKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.field == other.field && self.pattern == other.pattern
+    }
+}
+
+impl Eq for RegexKey {}
+
+impl Hash for RegexKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.field.hash(state);
+        self.pattern.hash(state);
+    }
+}
+
+/// Read a quoted pattern body up to its closing `"`, turning `\"` into
+/// `"`. Returns the pattern and the text after the quote.
+fn unquote(body: &str) -> Option<(String, &str)> {
+    let mut pattern = String::with_capacity(body.len());
+    let mut chars = body.char_indices();
+    while let Some((pos, ch)) = chars.next() {
+        match ch {
+            '"' => return Some((pattern, body.get(pos + 1..)?)),
+            '\\' => match chars.next() {
+                Some((_, '"')) => pattern.push('"'),
+                Some((_, next)) => {
+                    pattern.push('\\');
+                    pattern.push(next);
+                }
+                None => return None,
+            },
+            _ => pattern.push(ch),
+        }
+    }
+    None
+}
+
+/// Split `input` at the first `sep` outside a double-quoted section, so a
+/// regex key's pattern may hold `>` and `,` (`\"` does not close a
+/// quote).
+pub(super) fn split_unquoted(input: &str, sep: char) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    let mut chars = input.char_indices();
+    while let Some((pos, ch)) = chars.next() {
+        match ch {
+            '\\' if quoted => {
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            _ if ch == sep && !quoted => {
+                return Some((input.get(..pos)?, input.get(pos + ch.len_utf8()..)?));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Replace the body of the first capture group of `pattern` with
+/// `literal`, keeping the group (and its name) in place:
+/// `^(\d{4})-` → `^(2023)-`. `None` when no capture group is found.
+///
+/// Escapes and character classes are skipped, so `\(` and `[(]` do not
+/// open a group; `(?:…)`, flag groups and look-arounds do not capture.
+fn pin_first_group(pattern: &str, literal: &str) -> Option<String> {
+    let mut depth = 0_usize;
+    let mut open: Option<(usize, usize)> = None;
+    let mut class_depth = 0_usize;
+    let mut chars = pattern.char_indices().peekable();
+    while let Some((pos, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '[' => {
+                if class_depth > 0 && chars.peek().is_some_and(|&(_, next)| next == ':') {
+                    // `[:alpha:]` inside a class: skip to its `:]`.
+                    while let Some((_, inner)) = chars.next() {
+                        if inner == ':' && chars.peek().is_some_and(|&(_, next)| next == ']') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                class_depth += 1;
+                // A `]` right after `[` or `[^` is a literal member.
+                if chars.peek().is_some_and(|&(_, next)| next == '^') {
+                    chars.next();
+                }
+                if chars.peek().is_some_and(|&(_, next)| next == ']') {
+                    chars.next();
+                }
+            }
+            ']' if class_depth > 0 => class_depth -= 1,
+            _ if class_depth > 0 => {}
+            '(' => {
+                depth += 1;
+                if open.is_none() {
+                    let rest = pattern.get(pos + 1..)?;
+                    let name_len = capture_prefix_len(rest);
+                    if let Some(len) = name_len {
+                        open = Some((pos + 1 + len, depth));
+                    }
+                }
+            }
+            ')' => {
+                if let Some((body_start, group_depth)) = open
+                    && group_depth == depth
+                {
+                    return Some(format!(
+                        "{}{literal}{}",
+                        pattern.get(..body_start)?,
+                        pattern.get(pos..)?
+                    ));
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Length of the group syntax after a `(` when the group captures: `0`
+/// for a plain group, the length of `?P<name>` / `?<name>` for a named
+/// one; `None` for non-capturing, flag and look-around groups.
+fn capture_prefix_len(after_paren: &str) -> Option<usize> {
+    let Some(rest) = after_paren.strip_prefix('?') else {
+        return Some(0);
+    };
+    let name_open = if rest.starts_with("P<") {
+        3
+    } else if rest.starts_with('<') && !rest.starts_with("<=") && !rest.starts_with("<!") {
+        2
+    } else {
+        return None;
+    };
+    after_paren
+        .find('>')
+        .filter(|&end| end >= name_open)
+        .map(|end| end + 1)
+}
+
+/// Group key of a capture (`None` = unmatched), equal across drives so
+/// per-drive accumulators merge.
+fn group_key(capture: Option<&str>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    capture.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One bucket being accumulated.
+#[derive(Debug, Clone)]
+struct RegexGroup {
+    /// The captured text; `None` for the unmatched bucket.
+    key: Option<String>,
+    /// Size statistics of the members.
+    stats: StatsAccumulator,
+    /// Sample rows, present when samples were requested.
+    samples: Option<SampleHeap>,
+}
+
+/// Accumulator for `terms` over a [`RegexKey`].
+#[derive(Debug, Clone)]
+pub struct RegexTermsAccumulator {
+    /// The key regex.
+    key: RegexKey,
+    /// Buckets returned.
+    top: u16,
+    /// Whether unmatched records get a bucket.
+    unmatched: bool,
+    /// Whether buckets sketch file sizes for percentile metrics.
+    percentiles: bool,
+    /// Sample rows per bucket.
+    sample: Option<TopHitsSpec>,
+    /// Buckets by [`group_key`].
+    groups: HashMap<u64, RegexGroup>,
+    /// Resolved directory paths of the drive being scanned (path keys).
+    dir_cache: DirCache,
+    /// Ordinal of the drive `dir_cache` belongs to.
+    cached_drive: Option<u8>,
+    /// Volume root of the drive being scanned (`C:\`, or host-qualified
+    /// for a hosted volume), as [`DriveCompactIndex::volume_prefix`].
+    volume_prefix: String,
+}
+
+impl RegexTermsAccumulator {
+    /// An empty accumulator.
+    #[must_use]
+    pub(crate) fn new(
+        key: RegexKey,
+        top: u16,
+        unmatched: bool,
+        percentiles: bool,
+        sample: Option<TopHitsSpec>,
+    ) -> Self {
+        Self {
+            key,
+            top,
+            unmatched,
+            percentiles,
+            sample,
+            groups: HashMap::new(),
+            dir_cache: DirCache::default(),
+            cached_drive: None,
+            volume_prefix: String::new(),
+        }
+    }
+
+    /// Feed one record; returns its bucket key, or `None` when the regex
+    /// does not match and unmatched records are skipped.
+    pub(crate) fn feed(
+        &mut self,
+        record: &CompactRecord,
+        drive: &DriveCompactIndex,
+        idx: usize,
+        drive_ordinal: u8,
+    ) -> Option<u64> {
+        let path;
+        let haystack = if self.key.field == FieldId::Path {
+            path = self.path_of(drive, idx, drive_ordinal);
+            path.as_str()
+        } else {
+            record.name(&drive.names)
+        };
+        let capture = self.key.capture(haystack);
+        if capture.is_none() && !self.unmatched {
+            return None;
+        }
+        let key = group_key(capture);
+        let (percentiles, sample) = (self.percentiles, self.sample.as_ref());
+        let group = self.groups.entry(key).or_insert_with(|| RegexGroup {
+            key: capture.map(str::to_owned),
+            stats: StatsAccumulator::tracking(percentiles),
+            samples: sample.map(SampleHeap::from_spec),
+        });
+        group.stats.feed_value(record.size, record.allocated);
+        if let Some(heap) = group.samples.as_mut() {
+            heap.push(record, uffs_mft::len_to_u32(idx), drive_ordinal);
+        }
+        Some(key)
+    }
+
+    /// Full path of record `idx`, reusing resolved directories while the
+    /// scan stays on one drive.
+    fn path_of(&mut self, drive: &DriveCompactIndex, idx: usize, drive_ordinal: u8) -> String {
+        if self.cached_drive != Some(drive_ordinal) {
+            self.dir_cache.clear();
+            self.cached_drive = Some(drive_ordinal);
+            self.volume_prefix = drive.volume_prefix().into_owned();
+        }
+        drive.display_path(resolve_path_cached(
+            drive,
+            idx,
+            &self.volume_prefix,
+            &mut self.dir_cache,
+            MalformedRender::Lossy,
+        ))
+    }
+
+    /// Merge another accumulator's buckets into this one.
+    #[expect(
+        clippy::iter_over_hash_type,
+        reason = "per-key merge is order-independent: each bucket merges into self by key"
+    )]
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (key, theirs) in &other.groups {
+            if let Some(ours) = self.groups.get_mut(key) {
+                ours.stats.merge(&theirs.stats);
+                if let (Some(mine), Some(their_heap)) =
+                    (ours.samples.as_mut(), theirs.samples.as_ref())
+                {
+                    mine.merge(their_heap);
+                }
+            } else {
+                self.groups.insert(*key, theirs.clone());
+            }
+        }
+    }
+
+    /// Rank buckets by count (ties by key), keep the top ones and attach
+    /// samples, drill-downs and nested children.
+    pub(super) fn finalize(
+        self,
+        ctx: RowContext<'_>,
+        options: &FinalizeOptions,
+        drives: &[&DriveCompactIndex],
+        nester: &mut Nester<'_>,
+    ) -> AggregateResultData {
+        let mut groups: Vec<(u64, RegexGroup)> = self.groups.into_iter().collect();
+        groups.sort_by(|left, right| {
+            right
+                .1
+                .stats
+                .count
+                .cmp(&left.1.stats.count)
+                .then_with(|| left.1.key.cmp(&right.1.key))
+        });
+        let total_groups = groups.len();
+        let limit = usize::from(self.top);
+        let other_count = groups.get(limit..).map_or(0, |tail| {
+            tail.iter().map(|(_, group)| group.stats.count).sum()
+        });
+        groups.truncate(limit);
+
+        let projection = self
+            .sample
+            .as_ref()
+            .map(TopHitsSpec::effective_projection)
+            .unwrap_or_default();
+        let rows = groups
+            .into_iter()
+            .map(|(group_key, mut group)| {
+                let label = group.key.as_deref().unwrap_or(UNMATCHED_KEY).to_owned();
+                let mut row = BucketRow::from_stats(label, &group.stats, ctx);
+                if let Some(heap) = group.samples.as_mut() {
+                    row.sample_rows = heap
+                        .drain_sorted()
+                        .iter()
+                        .map(|entry| materialize_sample_entry(entry, projection, drives))
+                        .collect();
+                }
+                row.drilldown.clone_from(&options.query_predicates);
+                row.drilldown.push(self.key.drilldown(group.key.as_deref()));
+                nester.attach(&mut row, group_key);
+                row
+            })
+            .collect();
+
+        AggregateResultData::Buckets {
+            field: self.key.label(),
+            rows,
+            other_count,
+            total_groups,
+            exact: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<(RegexKey, &str), ParseAggSpecError> {
+        RegexKey::parse_prefix(input).expect("starts with regex(")
+    }
+
+    #[test]
+    fn parses_field_pattern_and_trailing_options() {
+        let (key, rest) = parse(r#"regex(name, "^(\d{4})-"),top=5"#).expect("valid");
+        assert_eq!(key.field(), FieldId::Name);
+        assert_eq!(key.pattern(), r"^(\d{4})-");
+        assert_eq!(rest, ",top=5");
+        assert_eq!(key.capture("2023-report.pdf"), Some("2023"));
+        assert_eq!(key.capture("report.pdf"), None);
+
+        let (path_key, _) = parse(r#"regex(path, "\\Clients\\([^\\]+)\\")"#).expect("valid");
+        assert_eq!(
+            path_key.capture(r"C:\Work\clients\Acme\brief.docx"),
+            Some("Acme")
+        );
+        let (quoted, _) = parse(r#"regex(name, "say \"(\w+)\"")"#).expect("valid");
+        assert_eq!(quoted.pattern(), r#"say "(\w+)""#);
+
+        assert!(RegexKey::parse_prefix("extension").is_none());
+        assert!(matches!(
+            parse(r#"regex(size, "(\d+)")"#),
+            Err(ParseAggSpecError::RegexKeyField { .. })
+        ));
+        assert!(matches!(
+            parse(r#"regex(name, "\d+")"#),
+            Err(ParseAggSpecError::RegexWithoutCapture { .. })
+        ));
+        assert!(matches!(
+            parse(r#"regex(name, "(")"#),
+            Err(ParseAggSpecError::InvalidRegex { .. })
+        ));
+        assert!(matches!(
+            parse(r#"regex(name, "(\d+)""#),
+            Err(ParseAggSpecError::InvalidRegexKey { .. })
+        ));
+    }
+
+    #[test]
+    fn split_ignores_separators_inside_quotes() {
+        assert_eq!(
+            split_unquoted(r#"terms:regex(name, "(?P<y>\d{4})")>terms:drive"#, '>'),
+            Some((r#"terms:regex(name, "(?P<y>\d{4})")"#, "terms:drive"))
+        );
+        assert_eq!(split_unquoted(r#""a\">b""#, '>'), None);
+    }
+
+    #[test]
+    fn pins_the_first_capture_group() {
+        assert_eq!(
+            pin_first_group(r"^(\d{4})-", "2023").as_deref(),
+            Some("^(2023)-")
+        );
+        assert_eq!(
+            pin_first_group(r"\\Clients\\([^\\]+)\\", "Acme").as_deref(),
+            Some(r"\\Clients\\(Acme)\\")
+        );
+        assert_eq!(
+            pin_first_group(r"(?i)(?:IMG|DSC)[(_]((?P<cam>\w+))_", "X").as_deref(),
+            Some("(?i)(?:IMG|DSC)[(_](X)_")
+        );
+        assert_eq!(
+            pin_first_group(r"(?P<year>\d{4})", "2020").as_deref(),
+            Some("(?P<year>2020)")
+        );
+        assert_eq!(pin_first_group(r"\(\d+\)", "1"), None);
+    }
+
+    #[test]
+    fn drilldowns_pin_the_key_or_exclude_the_pattern() {
+        let (key, _) = parse(r#"regex(name, "^(\w+)\.")"#).expect("valid");
+        let hit = key.drilldown(Some("a.b"));
+        assert_eq!(hit.field, "name");
+        assert_eq!(hit.op, "regex");
+        assert_eq!(hit.value, DrilldownValue::String(r"^(a\.b)\.".to_owned()));
+        let miss = key.drilldown(None);
+        assert_eq!(miss.op, "not_regex");
+        assert_eq!(miss.value, DrilldownValue::String(r"^(\w+)\.".to_owned()));
+    }
+}
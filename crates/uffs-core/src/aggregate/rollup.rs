@@ -34,6 +34,8 @@ pub struct RollupAccumulator {
     /// Last computed group key (used by nested rollups to route
     /// sub-accumulator feeding without recomputing the key).
     pub last_key: u64,
+    /// Whether each group sketches its file sizes for percentile metrics.
+    pub percentiles: bool,
 }
 
 impl RollupAccumulator {
@@ -45,9 +47,17 @@ impl RollupAccumulator {
             mode,
             top,
             last_key: 0,
+            percentiles: false,
         }
     }
 
+    /// Sketch each group's file sizes so size percentiles can be reported.
+    #[must_use]
+    pub const fn tracking_percentiles(mut self, percentiles: bool) -> Self {
+        self.percentiles = percentiles;
+        self
+    }
+
     /// Feed a record into the rollup.
     ///
     /// `drive_ordinal` is the position of `drive` in the aggregation's
@@ -85,7 +95,11 @@ impl RollupAccumulator {
         let key = encode_rollup_key(drive_ordinal, low);
 
         self.last_key = key;
-        let stats = self.groups.entry(key).or_default();
+        let percentiles = self.percentiles;
+        let stats = self
+            .groups
+            .entry(key)
+            .or_insert_with(|| StatsAccumulator::tracking(percentiles));
         stats.feed_value(record.size, record.allocated);
         true
     }
@@ -382,6 +396,8 @@ mod tests {
 
         DriveCompactIndex {
             letter: uffs_mft::platform::DriveLetter::C,
+            host: None,
+            serial: None,
             records: crate::compact_storage::ColumnStorage::from_vec(records),
             names: crate::compact_storage::ColumnStorage::from_vec(names_blob),
             trigram: Arc::new(TrigramIndex::empty()),
@@ -15,8 +15,9 @@
 
 use alloc::collections::BinaryHeap;
 
+use super::finalize::SampleRow;
 use super::spec::TopHitsSpec;
-use crate::compact::CompactRecord;
+use crate::compact::{CompactRecord, DriveCompactIndex};
 use crate::search::field::FieldId;
 
 /// A single entry stored in the per-bucket sample heap.
@@ -291,6 +292,80 @@ impl SampleHeap {
     }
 }
 
+/// Materialize a single sample entry into a [`SampleRow`].
+///
+/// Looks up the record by `(drive_ordinal, rec_idx)` in `drives`,
+/// then projects the requested fields into key-value pairs.
+pub(super) fn materialize_sample_entry(
+    entry: &SampleEntry,
+    projection: &[FieldId],
+    drives: &[&DriveCompactIndex],
+) -> SampleRow {
+    let drive_ref = drives.get(usize::from(entry.drive_ordinal));
+    let record =
+        drive_ref.and_then(|drive| drive.records.get(uffs_mft::u32_as_usize(entry.rec_idx)));
+
+    let fields: Vec<(String, String)> = projection
+        .iter()
+        .map(|fid| {
+            let name = fid.metadata().canonical_name.to_owned();
+            let value = match (record, drive_ref) {
+                (Some(rec), Some(drv)) => format_field(*fid, rec, drv),
+                _ => String::new(),
+            };
+            (name, value)
+        })
+        .collect();
+
+    SampleRow {
+        fields,
+        sort_key: entry.sort_key,
+    }
+}
+
+/// Format a single field value for sample row output.
+#[expect(
+    clippy::wildcard_enum_match_arm,
+    reason = "FieldId is open-ended; fields without a textual representation fall back to empty string"
+)]
+fn format_field(field: FieldId, record: &CompactRecord, drive: &DriveCompactIndex) -> String {
+    match field {
+        FieldId::Name => record.name(&drive.names).to_owned(),
+        FieldId::Size => record.size.to_string(),
+        FieldId::SizeOnDisk => record.allocated.to_string(),
+        FieldId::Modified => super::finalize::format_timestamp_key(record.modified),
+        FieldId::Created => super::finalize::format_timestamp_key(record.created),
+        FieldId::Accessed => super::finalize::format_timestamp_key(record.accessed),
+        FieldId::Extension => {
+            let ext_id = usize::from(record.extension_id);
+            drive
+                .ext_names
+                .get(ext_id)
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        }
+        FieldId::Path | FieldId::PathOnly => {
+            // Full path resolution is expensive — return parent index
+            // as a placeholder.  Callers needing full paths should use
+            // the search pipeline instead.
+            format!("parent_idx:{}", record.parent_idx)
+        }
+        FieldId::DirectoryFlag => {
+            if record.flags & 0x0010 != 0 {
+                "directory".to_owned()
+            } else {
+                "file".to_owned()
+            }
+        }
+        FieldId::Hidden => format!("{}", record.flags & 0x0002 != 0),
+        FieldId::System => format!("{}", record.flags & 0x0004 != 0),
+        FieldId::ReadOnly => format!("{}", record.flags & 0x0001 != 0),
+        FieldId::TreeSize => record.treesize.to_string(),
+        FieldId::Descendants => record.descendants.to_string(),
+        _ => String::new(),
+    }
+}
+
 #[cfg(test)]
 #[expect(
     clippy::indexing_slicing,
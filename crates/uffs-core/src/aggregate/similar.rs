@@ -0,0 +1,518 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Near-duplicate filename clustering (`similar_names`).
+//!
+//! The `duplicates` aggregate needs an exact size + name match, but real
+//! clutter is spelled differently: `report (1).docx`, `report - Copy.docx`,
+//! `report_final_v2.docx`, `Copy of report.docx`. [`normalize_name`] strips
+//! copy markers, version tokens and short numbering from the stem so all of
+//! those collapse to `report.docx`, and [`SimilarNamesAccumulator`] groups
+//! files by that normalised name — per directory or across the whole
+//! selection.
+//!
+//! A cluster is only reported when it holds at least two files spelled
+//! differently; files sharing one exact name are the `duplicates`
+//! aggregate's job. Clusters come back as ordinary bucket rows, ranked by
+//! file count or bytes, with sample rows from a [`SampleHeap`].
+
+use core::hash::{Hash as _, Hasher as _};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+
+use super::accumulators::StatsAccumulator;
+use super::finalize::{AggregateResultData, BucketRow, RowContext};
+use super::sample_heap::{SampleEntry, SampleHeap, materialize_sample_entry};
+use super::spec::TopHitsSpec;
+use crate::compact::{CompactRecord, DriveCompactIndex, MalformedRender};
+
+/// Default number of clusters returned.
+pub const DEFAULT_TOP: u16 = 50;
+
+/// Default sample rows per cluster.
+pub const DEFAULT_SAMPLE: u8 = 3;
+
+/// Default cap on tracked clusters (OOM guard, as for `duplicates`).
+pub const DEFAULT_MAX_GROUPS: u32 = 500_000;
+
+/// Trailing stem tokens that mark a copy or a revision of the same file.
+const NOISE_WORDS: &[&str] = &[
+    "copy", "final", "old", "new", "backup", "bak", "draft", "edited", "latest", "orig",
+    "original", "updated", "rev", "ver", "version",
+];
+
+/// Prefixes that form a version or copy token when followed by digits
+/// (`v2`, `rev3`, `ver10`, `copy2`).
+const NUMBERED_PREFIXES: &[&str] = &["copy", "rev", "ver", "v"];
+
+/// Longest bare number treated as copy numbering (`report 2`, `report_01`).
+///
+/// Longer runs are kept: `IMG_0001` and `scan 2024` name distinct files.
+const MAX_NUMBERING_DIGITS: usize = 2;
+
+/// Longest suffix after the last `.` treated as an extension.
+const MAX_EXTENSION_LEN: usize = 10;
+
+/// Where clusters are formed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SimilarScope {
+    /// Only files in the same directory cluster together.
+    #[default]
+    Directory,
+    /// Files anywhere in the selection cluster together.
+    Selection,
+}
+
+impl SimilarScope {
+    /// Parse `dir` / `directory` / `folder` or `all` / `selection` /
+    /// `global`.
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "dir" | "directory" | "folder" => Some(Self::Directory),
+            "all" | "selection" | "global" => Some(Self::Selection),
+            _ => None,
+        }
+    }
+}
+
+/// How clusters are ranked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SimilarRank {
+    /// Most files first (ties broken by bytes).
+    #[default]
+    Count,
+    /// Most bytes first (ties broken by file count).
+    Bytes,
+}
+
+impl SimilarRank {
+    /// Parse `count` / `files` or `bytes` / `size`.
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "count" | "files" => Some(Self::Count),
+            "bytes" | "size" => Some(Self::Bytes),
+            _ => None,
+        }
+    }
+}
+
+/// Normalise a filename for near-duplicate clustering.
+///
+/// Lowercases, splits off the extension, drops a leading `Copy of` and
+/// repeatedly strips trailing copy markers (`copy`, `(1)`), version tokens
+/// (`v2`, `rev3`, `final`, `draft`) and short numbering, then rejoins the
+/// remaining stem words with single spaces:
+///
+/// `Report_Final_v2.DOCX`, `report - Copy (3).docx` and
+/// `Copy of report.docx` all become `report.docx`. The first stem word is
+/// never stripped, so `new.txt` stays `new.txt`.
+#[must_use]
+pub fn normalize_name(name: &str) -> String {
+    let mut normalizer = Normalizer::default();
+    normalizer.normalize(name).to_owned()
+}
+
+/// Reusable buffers for [`normalize_name`], so the scan does not allocate
+/// per record.
+#[derive(Debug, Clone, Default)]
+struct Normalizer {
+    /// Lowercased input name.
+    lower: String,
+    /// Byte spans of the stem's alphanumeric words within `lower`.
+    words: Vec<(usize, usize)>,
+    /// Normalised output.
+    out: String,
+}
+
+impl Normalizer {
+    /// Normalise `name` into [`Self::out`] and return it.
+    fn normalize(&mut self, name: &str) -> &str {
+        self.lower.clear();
+        self.lower.extend(name.chars().flat_map(char::to_lowercase));
+        let (stem, ext) = split_extension(&self.lower);
+
+        self.words.clear();
+        let mut start = None;
+        for (pos, ch) in stem.char_indices() {
+            if ch.is_alphanumeric() {
+                start.get_or_insert(pos);
+            } else if let Some(begin) = start.take() {
+                self.words.push((begin, pos));
+            }
+        }
+        if let Some(begin) = start {
+            self.words.push((begin, stem.len()));
+        }
+
+        let lower = self.lower.as_str();
+        let word = |span: Option<&(usize, usize)>| {
+            span.and_then(|&(begin, end)| lower.get(begin..end))
+                .unwrap_or_default()
+        };
+        loop {
+            let first = word(self.words.first());
+            let second = word(self.words.get(1));
+            let third = word(self.words.get(2));
+            if self.words.len() > 2 && first == "copy" && second == "of" {
+                self.words.drain(..2);
+            } else if self.words.len() > 3
+                && first == "copy"
+                && is_numbering(second)
+                && third == "of"
+            {
+                self.words.drain(..3);
+            } else if self.words.len() > 1 && is_noise(word(self.words.last())) {
+                self.words.pop();
+            } else {
+                break;
+            }
+        }
+
+        self.out.clear();
+        if self.words.is_empty() {
+            self.out.push_str(lower);
+            return &self.out;
+        }
+        for (pos, span) in self.words.iter().enumerate() {
+            if pos > 0 {
+                self.out.push(' ');
+            }
+            self.out.push_str(word(Some(span)));
+        }
+        if let Some(extension) = ext {
+            self.out.push('.');
+            self.out.push_str(extension);
+        }
+        &self.out
+    }
+}
+
+/// Split `name` into stem and extension. Suffixes that do not look like
+/// an extension (too long, or holding spaces or punctuation) stay in the
+/// stem.
+fn split_extension(name: &str) -> (&str, Option<&str>) {
+    match name.rsplit_once('.') {
+        Some((stem, ext))
+            if !stem.is_empty()
+                && !ext.is_empty()
+                && ext.len() <= MAX_EXTENSION_LEN
+                && ext.chars().all(char::is_alphanumeric) =>
+        {
+            (stem, Some(ext))
+        }
+        _ => (name, None),
+    }
+}
+
+/// Whether `word` is short copy numbering (`1`, `02`).
+fn is_numbering(word: &str) -> bool {
+    !word.is_empty()
+        && word.len() <= MAX_NUMBERING_DIGITS
+        && word.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Whether a trailing stem word marks a copy, a version or numbering.
+fn is_noise(word: &str) -> bool {
+    is_numbering(word)
+        || NOISE_WORDS.contains(&word)
+        || NUMBERED_PREFIXES.iter().any(|prefix| {
+            word.strip_prefix(prefix).is_some_and(|digits| {
+                !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+            })
+        })
+}
+
+/// Hash of a lowercased name, for telling spellings apart.
+fn name_hash(lower: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    lower.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One cluster being accumulated.
+#[derive(Debug, Clone)]
+struct SimilarGroup {
+    /// Size statistics of the members.
+    stats: StatsAccumulator,
+    /// Lowest `(drive, record)` member — names the cluster at finalize.
+    exemplar: SampleEntry,
+    /// Smallest and largest hash of the members' lowercased names; they
+    /// differ once the cluster holds two spellings.
+    spellings: (u64, u64),
+    /// Sample rows, present when samples were requested.
+    samples: Option<SampleHeap>,
+}
+
+impl SimilarGroup {
+    /// Fold another partial cluster with the same key into this one.
+    fn merge(&mut self, other: &Self) {
+        self.stats.merge(&other.stats);
+        if (other.exemplar.drive_ordinal, other.exemplar.rec_idx)
+            < (self.exemplar.drive_ordinal, self.exemplar.rec_idx)
+        {
+            self.exemplar = other.exemplar;
+        }
+        self.spellings = (
+            self.spellings.0.min(other.spellings.0),
+            self.spellings.1.max(other.spellings.1),
+        );
+        if let (Some(ours), Some(theirs)) = (self.samples.as_mut(), other.samples.as_ref()) {
+            ours.merge(theirs);
+        }
+    }
+
+    /// Whether this is a real cluster: two or more files, spelled at
+    /// least two different ways.
+    const fn is_cluster(&self) -> bool {
+        self.stats.count > 1 && self.spellings.0 != self.spellings.1
+    }
+}
+
+/// Accumulator for the `similar_names` aggregate.
+#[derive(Debug, Clone)]
+pub struct SimilarNamesAccumulator {
+    /// Where clusters are formed.
+    scope: SimilarScope,
+    /// Ranking order.
+    rank: SimilarRank,
+    /// Clusters returned.
+    top: u16,
+    /// Sample rows per cluster.
+    sample: Option<TopHitsSpec>,
+    /// Cap on tracked clusters.
+    max_groups: u32,
+    /// Clusters by key (normalised name, plus directory when scoped).
+    groups: HashMap<u64, SimilarGroup>,
+    /// Whether a new cluster was dropped at the `max_groups` cap.
+    truncated: bool,
+    /// Scratch buffers for normalisation.
+    normalizer: Normalizer,
+}
+
+impl SimilarNamesAccumulator {
+    /// An empty accumulator.
+    #[must_use]
+    pub(crate) fn new(
+        scope: SimilarScope,
+        rank: SimilarRank,
+        top: u16,
+        sample: Option<TopHitsSpec>,
+        max_groups: u32,
+    ) -> Self {
+        Self {
+            scope,
+            rank,
+            top,
+            sample,
+            max_groups,
+            groups: HashMap::new(),
+            truncated: false,
+            normalizer: Normalizer::default(),
+        }
+    }
+
+    /// Feed one record. Directories are skipped.
+    pub(crate) fn feed(
+        &mut self,
+        record: &CompactRecord,
+        drive: &DriveCompactIndex,
+        idx: usize,
+        drive_ordinal: u8,
+    ) {
+        if record.flags & 0x0010 != 0 {
+            return;
+        }
+        let normalized = self.normalizer.normalize(record.name(&drive.names));
+        let mut hasher = DefaultHasher::new();
+        if self.scope == SimilarScope::Directory {
+            (drive_ordinal, record.parent_idx).hash(&mut hasher);
+        }
+        normalized.hash(&mut hasher);
+        let key = hasher.finish();
+        let spelling = name_hash(&self.normalizer.lower);
+        let rec_idx = uffs_mft::len_to_u32(idx);
+
+        if !self.groups.contains_key(&key)
+            && uffs_mft::len_to_u32(self.groups.len()) >= self.max_groups
+        {
+            self.truncated = true;
+            return;
+        }
+        let sample = self.sample.as_ref();
+        let group = self.groups.entry(key).or_insert_with(|| SimilarGroup {
+            stats: StatsAccumulator::new(),
+            exemplar: SampleEntry {
+                sort_key: 0,
+                rec_idx,
+                drive_ordinal,
+            },
+            spellings: (spelling, spelling),
+            samples: sample.map(SampleHeap::from_spec),
+        });
+        group.stats.feed_value(record.size, record.allocated);
+        if (drive_ordinal, rec_idx) < (group.exemplar.drive_ordinal, group.exemplar.rec_idx) {
+            group.exemplar.drive_ordinal = drive_ordinal;
+            group.exemplar.rec_idx = rec_idx;
+        }
+        group.spellings = (
+            group.spellings.0.min(spelling),
+            group.spellings.1.max(spelling),
+        );
+        if let Some(heap) = group.samples.as_mut() {
+            heap.push(record, rec_idx, drive_ordinal);
+        }
+    }
+
+    /// Merge another accumulator's clusters into this one.
+    #[expect(
+        clippy::iter_over_hash_type,
+        reason = "per-key merge is order-independent: each cluster merges into self by key"
+    )]
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.truncated |= other.truncated;
+        for (key, theirs) in &other.groups {
+            if let Some(ours) = self.groups.get_mut(key) {
+                ours.merge(theirs);
+            } else if uffs_mft::len_to_u32(self.groups.len()) < self.max_groups {
+                self.groups.insert(*key, theirs.clone());
+            } else {
+                self.truncated = true;
+            }
+        }
+    }
+
+    /// Rank the clusters and build one bucket row per kept cluster, keyed
+    /// by the normalised name (prefixed with its directory when scoped).
+    pub(super) fn finalize(
+        self,
+        ctx: RowContext<'_>,
+        drives: &[&DriveCompactIndex],
+    ) -> AggregateResultData {
+        let mut clusters: Vec<SimilarGroup> = self
+            .groups
+            .into_values()
+            .filter(SimilarGroup::is_cluster)
+            .collect();
+        let order = |group: &SimilarGroup| match self.rank {
+            SimilarRank::Count => (group.stats.count, group.stats.sum),
+            SimilarRank::Bytes => (group.stats.sum, group.stats.count),
+        };
+        clusters.sort_by(|left, right| {
+            order(right).cmp(&order(left)).then_with(|| {
+                (left.exemplar.drive_ordinal, left.exemplar.rec_idx)
+                    .cmp(&(right.exemplar.drive_ordinal, right.exemplar.rec_idx))
+            })
+        });
+
+        let total_groups = clusters.len();
+        let limit = usize::from(self.top);
+        let other_count = clusters
+            .get(limit..)
+            .map_or(0, |tail| tail.iter().map(|group| group.stats.count).sum());
+        clusters.truncate(limit);
+
+        let projection = self
+            .sample
+            .as_ref()
+            .map(TopHitsSpec::effective_projection)
+            .unwrap_or_default();
+        let mut normalizer = Normalizer::default();
+        let rows = clusters
+            .into_iter()
+            .map(|mut group| {
+                let key = cluster_key(self.scope, &group.exemplar, drives, &mut normalizer);
+                let mut row = BucketRow::from_stats(key, &group.stats, ctx);
+                if let Some(heap) = group.samples.as_mut() {
+                    row.sample_rows = heap
+                        .drain_sorted()
+                        .iter()
+                        .map(|entry| materialize_sample_entry(entry, projection, drives))
+                        .collect();
+                }
+                row
+            })
+            .collect();
+
+        AggregateResultData::Buckets {
+            field: "name".to_owned(),
+            rows,
+            other_count,
+            total_groups,
+            exact: !self.truncated,
+        }
+    }
+}
+
+/// Display key of a cluster: the exemplar's normalised name, prefixed with
+/// its directory for [`SimilarScope::Directory`].
+fn cluster_key(
+    scope: SimilarScope,
+    exemplar: &SampleEntry,
+    drives: &[&DriveCompactIndex],
+    normalizer: &mut Normalizer,
+) -> String {
+    let rec_idx = uffs_mft::u32_as_usize(exemplar.rec_idx);
+    let Some((drive, record)) = drives
+        .get(usize::from(exemplar.drive_ordinal))
+        .and_then(|drive| drive.records.get(rec_idx).map(|record| (drive, record)))
+    else {
+        return format!("record_{}", exemplar.rec_idx);
+    };
+    let name_key = normalizer.normalize(record.name(&drive.names));
+    match scope {
+        SimilarScope::Selection => name_key.to_owned(),
+        SimilarScope::Directory => {
+            let prefix = format!("{}:\\", drive.letter);
+            let path =
+                crate::search::tree::resolve_path(drive, rec_idx, &prefix, MalformedRender::Lossy);
+            let parent = path.rsplit_once('\\').map_or(path.as_str(), |(dir, _)| dir);
+            format!("{parent}\\{name_key}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_markers_versions_and_numbering_collapse() {
+        for name in [
+            "report.docx",
+            "report (1).docx",
+            "report - Copy.docx",
+            "report - Copy (2).docx",
+            "Report_Final_v2.DOCX",
+            "Copy of report.docx",
+            "Copy (3) of report.docx",
+            "report.v2.1.docx",
+            "report_draft_03.docx",
+            "report copy2.docx",
+        ] {
+            assert_eq!(normalize_name(name), "report.docx", "{name}");
+        }
+    }
+
+    #[test]
+    fn distinct_files_stay_apart() {
+        assert_eq!(normalize_name("IMG_0001.jpg"), "img 0001.jpg");
+        assert_eq!(normalize_name("scan 2024.pdf"), "scan 2024.pdf");
+        assert_eq!(normalize_name("new.txt"), "new.txt");
+        assert_eq!(normalize_name("annual report.pdf"), "annual report.pdf");
+        assert_ne!(normalize_name("report.pdf"), normalize_name("report.docx"));
+        assert_eq!(normalize_name("Makefile"), "makefile");
+        assert_eq!(normalize_name("(1).txt"), "1.txt");
+    }
+
+    #[test]
+    fn scope_and_rank_parse() {
+        assert_eq!(SimilarScope::parse("dir"), Some(SimilarScope::Directory));
+        assert_eq!(SimilarScope::parse("ALL"), Some(SimilarScope::Selection));
+        assert_eq!(SimilarScope::parse("tree"), None);
+        assert_eq!(SimilarRank::parse("bytes"), Some(SimilarRank::Bytes));
+        assert_eq!(SimilarRank::parse("files"), Some(SimilarRank::Count));
+        assert_eq!(SimilarRank::parse("age"), None);
+    }
+}
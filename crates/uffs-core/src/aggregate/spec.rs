@@ -7,6 +7,15 @@
 //! during a search scan. Multiple specs can be composed to produce a rich
 //! statistical profile in a single pass.
 
+use alloc::sync::Arc;
+
+use uffs_time::TimeZone;
+
+pub use super::percentile::PercentileRank;
+pub use super::pivot::{AxisBuckets, PivotAxis};
+pub use super::regex_key::RegexKey;
+pub use super::similar::{SimilarRank, SimilarScope};
+use crate::search::computed::ComputedField;
 use crate::search::field::FieldId;
 
 /// A single aggregation operation to compute during a search scan.
@@ -16,13 +25,31 @@ pub struct AggregateSpec {
     pub kind: AggregateKind,
     /// Optional label for this aggregation in the output.
     pub label: Option<String>,
+    /// Optional child aggregation evaluated per bucket of this one, in the
+    /// same scan pass.
+    ///
+    /// Both levels must be bucketed (see [`AggregateKind::is_bucketed`]);
+    /// each parent bucket row carries the child's rows in
+    /// `BucketRow::sub_buckets`. Chains nest arbitrarily deep
+    /// (`terms:drive>terms:extension>hist:size`).
+    pub sub: Option<Box<Self>>,
+    /// User-defined computed field measured instead of the kind's `field`.
+    ///
+    /// Honoured by `stats`, `hist` and `range`; negative values count as
+    /// `0` and records where the field is undefined are skipped.
+    pub computed: Option<Arc<ComputedField>>,
 }
 
 impl AggregateSpec {
     /// Create a new aggregate spec with the given kind.
     #[must_use]
     pub const fn new(kind: AggregateKind) -> Self {
-        Self { kind, label: None }
+        Self {
+            kind,
+            label: None,
+            sub: None,
+            computed: None,
+        }
     }
 
     /// Create a new aggregate spec with a label.
@@ -31,6 +58,42 @@ impl AggregateSpec {
         Self {
             kind,
             label: Some(label.into()),
+            sub: None,
+            computed: None,
+        }
+    }
+
+    /// Measure `field` instead of the kind's built-in field.
+    #[must_use]
+    pub fn with_computed(mut self, field: Arc<ComputedField>) -> Self {
+        self.computed = Some(field);
+        self
+    }
+
+    /// Nest `sub` under the deepest level of this spec, so
+    /// `a.with_sub(b).with_sub(c)` evaluates `c` per bucket of `b` per
+    /// bucket of `a`.
+    #[must_use]
+    pub fn with_sub(mut self, sub: Self) -> Self {
+        let mut slot = &mut self.sub;
+        while let Some(child) = slot {
+            slot = &mut child.sub;
+        }
+        *slot = Some(Box::new(sub));
+        self
+    }
+
+    /// Cut every calendar bucket of this spec and its nested levels that
+    /// names no zone of its own in `zone`.
+    pub fn apply_default_tz(&mut self, zone: TimeZone) {
+        let mut level = Some(self);
+        while let Some(spec) = level {
+            if let AggregateKind::DateHistogram { time_zone, .. }
+            | AggregateKind::Pivot { time_zone, .. } = &mut spec.kind
+            {
+                time_zone.get_or_insert(zone);
+            }
+            level = spec.sub.as_deref_mut();
         }
     }
 }
@@ -61,6 +124,22 @@ pub enum AggregateKind {
         sample: Option<TopHitsSpec>,
     },
 
+    /// Group records by the first capture group of a regex over their
+    /// name or path (`terms:regex(name, "^(\d{4})-")`).
+    RegexTerms {
+        /// The regex and the field it matches.
+        key: RegexKey,
+        /// Maximum number of groups to return.
+        top: u16,
+        /// Metrics to compute per group (default: count + `total_bytes`).
+        metrics: Vec<BucketMetric>,
+        /// Optional sample rows per bucket.
+        sample: Option<TopHitsSpec>,
+        /// Count records the regex does not match in an `(unmatched)`
+        /// bucket instead of skipping them.
+        unmatched: bool,
+    },
+
     /// Group records into fixed-size numeric buckets.
     Histogram {
         /// Which field to bucket (must have `bucket_support`).
@@ -79,6 +158,11 @@ pub enum AggregateKind {
         calendar: CalendarInterval,
         /// Metrics per bucket.
         metrics: Vec<BucketMetric>,
+        /// Zone buckets are cut in — a fixed offset or a named IANA zone,
+        /// whose DST moves each timestamp by its own offset.  `None` takes
+        /// the request's zone (see [`AggregateSpec::apply_default_tz`]),
+        /// else UTC.
+        time_zone: Option<TimeZone>,
     },
 
     /// Group records into explicit numeric ranges.
@@ -113,11 +197,6 @@ pub enum AggregateKind {
         metrics: Vec<BucketMetric>,
         /// Optional sample rows per group.
         sample: Option<TopHitsSpec>,
-        /// Optional nested sub-aggregation per group (max 1 level deep in v1).
-        ///
-        /// When set, each top-level bucket runs a sub-aggregation on
-        /// its members and the result appears in `BucketWire.drilldown`.
-        sub: Option<Box<AggregateSpec>>,
     },
 
     /// Duplicate candidate detection.
@@ -133,6 +212,53 @@ pub enum AggregateKind {
         /// Maximum groups to track (OOM guard).
         max_groups: u32,
     },
+
+    /// Cross-tab of two fields: one metric per (row, column) cell, with
+    /// the top buckets of each axis kept and the rest rolled up.
+    Pivot {
+        /// Row axis.
+        rows: PivotAxis,
+        /// Column axis.
+        cols: PivotAxis,
+        /// Metric reported per cell.
+        metric: BucketMetric,
+        /// Zone for calendar axes; as for `DateHistogram`.
+        time_zone: Option<TimeZone>,
+    },
+
+    /// Near-duplicate filename clusters: names equal once copy markers,
+    /// version tokens and numbering are stripped (see
+    /// [`super::similar::normalize_name`]).
+    SimilarNames {
+        /// Cluster per directory or across the selection.
+        scope: SimilarScope,
+        /// Rank clusters by file count or bytes.
+        rank: SimilarRank,
+        /// Maximum clusters to return.
+        top: u16,
+        /// Sample rows per cluster.
+        sample: Option<TopHitsSpec>,
+        /// Maximum clusters to track (OOM guard).
+        max_groups: u32,
+    },
+}
+
+impl AggregateKind {
+    /// Whether this kind produces bucket rows that can carry a nested
+    /// [`AggregateSpec::sub`] (terms, histogram, date histogram, range,
+    /// rollup).
+    #[must_use]
+    pub const fn is_bucketed(&self) -> bool {
+        matches!(
+            self,
+            Self::Terms { .. }
+                | Self::RegexTerms { .. }
+                | Self::Histogram { .. }
+                | Self::DateHistogram { .. }
+                | Self::Range { .. }
+                | Self::Rollup { .. }
+        )
+    }
 }
 
 /// Rollup mode for path-based or drive-based rollups.
@@ -176,6 +302,11 @@ pub enum DuplicateVerify {
     },
     /// Full SHA-256 hash verification.
     Sha256,
+    /// Staged xxh3: hash the head and tail of each member, then the
+    /// whole file only for members whose samples agree.
+    Xxh3,
+    /// Staged BLAKE3 — as [`Self::Xxh3`], with a cryptographic hash.
+    Blake3,
 }
 
 /// Maximum allowed sample rows per bucket.
@@ -346,6 +477,9 @@ pub enum ScalarMetric {
     ValueCount,
     /// Count of records missing a value for this field.
     MissingCount,
+    /// A percentile (`p50`, `p99.9`, or `median`), estimated from a
+    /// mergeable sketch. Numeric fields only.
+    Percentile(PercentileRank),
 }
 
 /// A metric computed per bucket/group in a terms, histogram, or range
@@ -372,9 +506,16 @@ pub enum BucketMetric {
     ShareOfTotalCount,
     /// Share of total bytes (percentage).
     ShareOfTotalBytes,
+    /// A file-size percentile within the bucket (`p50`, `p99.9`, or
+    /// `median`), estimated from a mergeable sketch.
+    SizePercentile(PercentileRank),
 }
 
 /// Calendar-aligned time intervals for date histogram aggregation.
+///
+/// The linear intervals (`Hour` … `Year`) bucket by point in time; the
+/// cyclic ones (`HourOfDay`, `DayOfWeek`) fold every day or week onto
+/// one, to show *when* files change rather than since when.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CalendarInterval {
     /// One hour.
@@ -389,6 +530,10 @@ pub enum CalendarInterval {
     Quarter,
     /// One calendar year.
     Year,
+    /// Hour of the day, `00`–`23`, whatever the date.
+    HourOfDay,
+    /// Day of the week, `Mon`–`Sun`, whatever the date.
+    DayOfWeek,
 }
 
 impl CalendarInterval {
@@ -406,306 +551,44 @@ impl CalendarInterval {
             "month" | "m" | "monthly" => Some(Self::Month),
             "quarter" | "q" | "quarterly" => Some(Self::Quarter),
             "year" | "y" | "yearly" | "annual" => Some(Self::Year),
+            "hour_of_day" | "hourofday" | "hod" => Some(Self::HourOfDay),
+            "day_of_week" | "dayofweek" | "dow" | "weekday" => Some(Self::DayOfWeek),
             _ => None,
         }
     }
-}
-
-#[cfg(test)]
-#[expect(
-    clippy::indexing_slicing,
-    reason = "tests assert against fixtures with known shape; indexing panic = test failure"
-)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn count_spec() {
-        let spec = AggregateSpec::new(AggregateKind::Count);
-        assert!(spec.label.is_none());
-        assert!(matches!(spec.kind, AggregateKind::Count));
-    }
-
-    #[test]
-    fn stats_spec() {
-        let spec = AggregateSpec::with_label(
-            AggregateKind::Stats {
-                field: FieldId::Size,
-                metrics: vec![ScalarMetric::Sum, ScalarMetric::Avg],
-            },
-            "size_stats",
-        );
-        assert_eq!(spec.label.as_deref(), Some("size_stats"));
-    }
-
-    #[test]
-    fn terms_spec() {
-        let spec = AggregateSpec::new(AggregateKind::Terms {
-            field: FieldId::Extension,
-            top: 50,
-            metrics: vec![BucketMetric::Count, BucketMetric::TotalBytes],
-            sample: None,
-        });
-        if let AggregateKind::Terms { field, top, .. } = &spec.kind {
-            assert_eq!(*field, FieldId::Extension);
-            assert_eq!(*top, 50);
-        } else {
-            panic!("expected Terms");
-        }
-    }
 
-    #[test]
-    fn calendar_interval_parse() {
-        assert_eq!(
-            CalendarInterval::parse("month"),
-            Some(CalendarInterval::Month)
-        );
-        assert_eq!(CalendarInterval::parse("M"), Some(CalendarInterval::Month));
-        assert_eq!(
-            CalendarInterval::parse("yearly"),
-            Some(CalendarInterval::Year)
-        );
-        assert_eq!(
-            CalendarInterval::parse("hourly"),
-            Some(CalendarInterval::Hour)
-        );
-        assert_eq!(
-            CalendarInterval::parse("weekly"),
-            Some(CalendarInterval::Week)
-        );
-        assert_eq!(
-            CalendarInterval::parse("quarterly"),
-            Some(CalendarInterval::Quarter)
-        );
-        assert_eq!(
-            CalendarInterval::parse("daily"),
-            Some(CalendarInterval::Day)
-        );
-        assert!(CalendarInterval::parse("millennium").is_none());
-    }
-
-    #[test]
-    fn all_scalar_metrics() {
-        // Ensure all variants are distinct.
-        let all = [
-            ScalarMetric::Sum,
-            ScalarMetric::Min,
-            ScalarMetric::Max,
-            ScalarMetric::Avg,
-            ScalarMetric::ValueCount,
-            ScalarMetric::MissingCount,
-        ];
-        for (i, lhs) in all.iter().enumerate() {
-            for (j, rhs) in all.iter().enumerate() {
-                assert_eq!(i == j, lhs == rhs);
-            }
+    /// Number of buckets in one cycle of a cyclic interval (24 hours,
+    /// 7 days); `None` for the linear ones.
+    #[must_use]
+    pub const fn period(self) -> Option<usize> {
+        match self {
+            Self::HourOfDay => Some(24),
+            Self::DayOfWeek => Some(7),
+            Self::Hour | Self::Day | Self::Week | Self::Month | Self::Quarter | Self::Year => None,
         }
     }
 
-    #[test]
-    fn all_bucket_metrics() {
-        let all = [
-            BucketMetric::Count,
-            BucketMetric::TotalBytes,
-            BucketMetric::TotalAllocated,
-            BucketMetric::WasteBytes,
-            BucketMetric::WastePct,
-            BucketMetric::AvgSize,
-            BucketMetric::MinSize,
-            BucketMetric::MaxSize,
-            BucketMetric::ShareOfTotalCount,
-            BucketMetric::ShareOfTotalBytes,
-        ];
-        for (i, lhs) in all.iter().enumerate() {
-            for (j, rhs) in all.iter().enumerate() {
-                assert_eq!(i == j, lhs == rhs);
+    /// Display label of a bucket key from this interval: the ISO date of
+    /// a linear bucket, `00`–`23` for an hour of the day, `Mon`–`Sun` for
+    /// a weekday.
+    #[must_use]
+    pub(crate) fn key_label(self, key: i64) -> String {
+        const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        match self {
+            Self::HourOfDay => format!("{key:02}"),
+            Self::DayOfWeek => usize::try_from(key)
+                .ok()
+                .and_then(|day| WEEKDAYS.get(day))
+                .map_or_else(|| format!("day_{key}"), |&day| day.to_owned()),
+            Self::Hour | Self::Day | Self::Week | Self::Month | Self::Quarter | Self::Year => {
+                super::finalize::format_timestamp_key(key)
             }
         }
     }
-
-    #[test]
-    fn range_spec() {
-        let spec = AggregateSpec::new(AggregateKind::Range {
-            field: FieldId::Size,
-            boundaries: vec![1024, 1_048_576, 1_073_741_824],
-            metrics: vec![BucketMetric::Count],
-        });
-        if let AggregateKind::Range { boundaries, .. } = &spec.kind {
-            assert_eq!(boundaries.len(), 3);
-        } else {
-            panic!("expected Range");
-        }
-    }
-
-    #[test]
-    fn missing_and_distinct_specs() {
-        let missing = AggregateSpec::new(AggregateKind::Missing {
-            field: FieldId::Extension,
-        });
-        assert!(matches!(missing.kind, AggregateKind::Missing { .. }));
-
-        let distinct = AggregateSpec::new(AggregateKind::Distinct {
-            field: FieldId::Type,
-        });
-        assert!(matches!(distinct.kind, AggregateKind::Distinct { .. }));
-    }
-
-    // ── TopHitsSpec tests ────────────────────────────────────────
-
-    #[test]
-    fn top_hits_default() {
-        let spec = TopHitsSpec::default();
-        assert_eq!(spec.count, 2);
-        assert_eq!(spec.sort_field, FieldId::Size);
-        assert!(spec.sort_desc);
-        assert_eq!(spec.projection, Vec::<FieldId>::new());
-    }
-
-    #[test]
-    fn top_hits_with_count_clamps() {
-        let zero = TopHitsSpec::with_count(0);
-        assert_eq!(zero.count, 1, "count=0 clamped to 1");
-
-        let huge = TopHitsSpec::with_count(99);
-        assert_eq!(huge.count, MAX_SAMPLE_COUNT, "count=99 clamped to MAX");
-
-        let normal = TopHitsSpec::with_count(3);
-        assert_eq!(normal.count, 3);
-    }
-
-    #[test]
-    fn top_hits_new_full() {
-        let spec = TopHitsSpec::new(4, FieldId::Modified, false, vec![
-            FieldId::Name,
-            FieldId::Size,
-        ]);
-        assert_eq!(spec.count, 4);
-        assert_eq!(spec.sort_field, FieldId::Modified);
-        assert!(!spec.sort_desc);
-        assert_eq!(spec.projection.len(), 2);
-    }
-
-    #[test]
-    fn top_hits_effective_projection_default() {
-        let spec = TopHitsSpec::default();
-        let proj = spec.effective_projection();
-        assert_eq!(proj, DEFAULT_PROJECTION);
-        assert!(proj.contains(&FieldId::Name));
-        assert!(proj.contains(&FieldId::Size));
-        assert!(proj.contains(&FieldId::Path));
-    }
-
-    #[test]
-    fn top_hits_effective_projection_custom() {
-        let spec = TopHitsSpec::new(1, FieldId::Size, true, vec![
-            FieldId::Name,
-            FieldId::Extension,
-        ]);
-        let proj = spec.effective_projection();
-        assert_eq!(proj.len(), 2);
-        assert_eq!(proj[0], FieldId::Name);
-        assert_eq!(proj[1], FieldId::Extension);
-    }
-
-    #[test]
-    fn top_hits_validate_ok() {
-        let spec = TopHitsSpec::default();
-        spec.validate().unwrap();
-    }
-
-    #[test]
-    fn top_hits_validate_unsortable_field() {
-        let spec = TopHitsSpec::new(2, FieldId::Attributes, true, vec![]);
-        let err = spec.validate().expect_err("Attributes is not sortable");
-        assert!(
-            matches!(&err, TopHitsValidateError::UnsortableField { field } if *field == FieldId::Attributes),
-            "expected UnsortableField(Attributes), got {err:?}",
-        );
-        // Display contract preserved from the pre-Phase-5d `String` return.
-        assert!(err.to_string().contains("not sortable"), "error: {err}");
-    }
-
-    #[test]
-    fn top_hits_validate_zero_count() {
-        // Construct directly to bypass `new`'s validation (if any) so we
-        // can exercise the `ZeroCount` arm.
-        let spec = TopHitsSpec {
-            count: 0,
-            sort_field: FieldId::Size,
-            sort_desc: true,
-            projection: vec![],
-        };
-        let err = spec.validate().expect_err("zero count must error");
-        assert_eq!(err, TopHitsValidateError::ZeroCount);
-        assert_eq!(err.to_string(), "TopHitsSpec count must be ≥ 1");
-    }
-
-    #[test]
-    fn top_hits_validate_count_exceeds_max() {
-        let over = MAX_SAMPLE_COUNT + 1;
-        let spec = TopHitsSpec {
-            count: over,
-            sort_field: FieldId::Size,
-            sort_desc: true,
-            projection: vec![],
-        };
-        let err = spec.validate().expect_err("over-max count must error");
-        assert_eq!(err, TopHitsValidateError::CountExceedsMax { count: over });
-        assert_eq!(
-            err.to_string(),
-            format!("TopHitsSpec count {over} exceeds maximum {MAX_SAMPLE_COUNT}"),
-        );
-    }
-
-    #[test]
-    fn terms_with_sample() {
-        let spec = AggregateSpec::new(AggregateKind::Terms {
-            field: FieldId::Type,
-            top: 10,
-            metrics: vec![BucketMetric::Count],
-            sample: Some(TopHitsSpec::with_count(3)),
-        });
-        if let AggregateKind::Terms { sample, .. } = &spec.kind {
-            let sample_spec = sample.as_ref().expect("sample should be Some");
-            assert_eq!(sample_spec.count, 3);
-            assert_eq!(sample_spec.sort_field, FieldId::Size);
-        } else {
-            panic!("expected Terms");
-        }
-    }
-
-    #[test]
-    fn duplicates_with_top_hits_sample() {
-        let spec = AggregateSpec::new(AggregateKind::Duplicates {
-            keys: vec![FieldId::Size, FieldId::Name],
-            verify: DuplicateVerify::None,
-            top: 50,
-            sample: Some(TopHitsSpec::new(2, FieldId::Modified, false, vec![])),
-            max_groups: 100_000,
-        });
-        if let AggregateKind::Duplicates { sample, .. } = &spec.kind {
-            let sample_spec = sample.as_ref().expect("sample should be Some");
-            assert_eq!(sample_spec.count, 2);
-            assert_eq!(sample_spec.sort_field, FieldId::Modified);
-            assert!(!sample_spec.sort_desc);
-        } else {
-            panic!("expected Duplicates");
-        }
-    }
-
-    #[test]
-    fn rollup_with_sample() {
-        let spec = AggregateSpec::new(AggregateKind::Rollup {
-            mode: RollupMode::Drive,
-            top: 5,
-            metrics: vec![BucketMetric::Count],
-            sample: Some(TopHitsSpec::default()),
-            sub: None,
-        });
-        if let AggregateKind::Rollup { sample, .. } = &spec.kind {
-            assert!(sample.is_some());
-        } else {
-            panic!("expected Rollup");
-        }
-    }
 }
+
+// Tests live in a sibling file via `#[path]` to keep this file under
+// the 800-line policy ceiling.
+#[cfg(test)]
+#[path = "spec_tests.rs"]
+mod tests;
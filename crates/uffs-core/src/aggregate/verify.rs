@@ -17,10 +17,16 @@
 //! File I/O is abstracted via the [`FileReader`] trait so that:
 //! - The daemon provides a real reader using resolved file paths.
 //! - Tests use a mock reader returning controlled byte content.
+//!
+//! Every mode compares per-member digests.  With a [`HashStore`]
+//! ([`DuplicateVerifier::verify_cached`]) each digest is looked up before
+//! any byte is read and remembered after, so files that have not changed
+//! since an earlier scan cost no I/O at all.
 
 use std::io;
 
 use super::duplicates::{DuplicateGroup, DuplicateResult};
+use super::hash_cache::{Digest, HashAlgo, HashKind, HashSpan, HashStore, SAMPLE_CHUNK};
 use super::spec::DuplicateVerify;
 
 // ── File reader trait ────────────────────────────────────────────────────
@@ -66,6 +72,30 @@ pub trait FileReader {
     ///
     /// Returns `Err` if the file cannot be read (permissions, moved, etc.).
     fn read_all(&self, record_idx: usize, drive_ordinal: u8) -> io::Result<Vec<u8>>;
+
+    /// Read the first `chunk` bytes followed by the last `chunk` bytes of
+    /// the file — the whole file when it is at most `2 * chunk` long.
+    ///
+    /// The default reads the whole file and keeps both ends; readers that
+    /// can seek should override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be read (permissions, moved, etc.).
+    fn read_head_tail(
+        &self,
+        record_idx: usize,
+        drive_ordinal: u8,
+        chunk: u32,
+    ) -> io::Result<Vec<u8>> {
+        let mut bytes = self.read_all(record_idx, drive_ordinal)?;
+        let chunk_len = uffs_mft::u32_as_usize(chunk);
+        if bytes.len() > chunk_len.saturating_mul(2) {
+            let tail_start = bytes.len() - chunk_len;
+            bytes.drain(chunk_len..tail_start);
+        }
+        Ok(bytes)
+    }
 }
 
 // ── Verification budget ──────────────────────────────────────────────────
@@ -107,7 +137,8 @@ impl VerificationBudget {
     }
 
     /// Check whether the budget allows reading `bytes` more bytes.
-    const fn can_read(&self, bytes: u64) -> bool {
+    #[must_use]
+    pub const fn can_read(&self, bytes: u64) -> bool {
         if self.max_bytes > 0 && self.bytes_used + bytes > self.max_bytes {
             return false;
         }
@@ -118,7 +149,7 @@ impl VerificationBudget {
     }
 
     /// Record that `bytes` were read from one file.
-    const fn record_read(&mut self, bytes: u64) {
+    pub const fn record_read(&mut self, bytes: u64) {
         self.bytes_used += bytes;
         self.files_used += 1;
     }
@@ -178,6 +209,8 @@ pub struct VerificationSummary {
     pub bytes_read: u64,
     /// Total files read during verification.
     pub files_read: u32,
+    /// Member digests answered by the hash store instead of a read.
+    pub cache_hits: u64,
 }
 
 // ── Verifier ─────────────────────────────────────────────────────────────
@@ -207,9 +240,22 @@ impl DuplicateVerifier {
     ///
     /// Returns the updated result plus a summary of the verification pass.
     pub fn verify(
+        &mut self,
+        result: DuplicateResult,
+        reader: &dyn FileReader,
+    ) -> (DuplicateResult, VerificationSummary) {
+        self.verify_cached(result, reader, None)
+    }
+
+    /// [`Self::verify`] backed by a content-hash store: member digests
+    /// found in `store` are used without reading the file, and every
+    /// digest computed is recorded there.  Only uncached members count
+    /// against the budget.
+    pub fn verify_cached(
         &mut self,
         mut result: DuplicateResult,
         reader: &dyn FileReader,
+        store: Option<&dyn HashStore>,
     ) -> (DuplicateResult, VerificationSummary) {
         let mut summary = VerificationSummary::default();
 
@@ -227,14 +273,13 @@ impl DuplicateVerifier {
 
             // Pre-flight: estimate total reads for this group and skip if
             // the budget can't accommodate them.
-            let per_file_estimate = match self.mode {
-                DuplicateVerify::None => 0,
-                DuplicateVerify::FirstBytes { count } => group.file_size.min(u64::from(count)),
-                DuplicateVerify::Sha256 => group.file_size,
-            };
-            let group_estimate = per_file_estimate * group.member_indices.len() as u64;
+            let group_estimate = self.group_estimate(&group, store);
 
-            if self.budget.exhausted() || !self.budget.can_read(group_estimate) {
+            // A fully cached group reads nothing, so it is verified even
+            // once the budget is spent.
+            if group_estimate > 0
+                && (self.budget.exhausted() || !self.budget.can_read(group_estimate))
+            {
                 summary.groups_skipped += 1;
                 summary.budget_exhausted = true;
                 // Keep the group as-is (unverified).
@@ -242,7 +287,14 @@ impl DuplicateVerifier {
                 continue;
             }
 
-            match self.verify_group(&group, reader) {
+            let mut source = DigestSource {
+                reader,
+                store,
+                cache_hits: 0,
+            };
+            let outcome = self.verify_group(&group, &mut source);
+            summary.cache_hits += source.cache_hits;
+            match outcome {
                 VerifyOutcome::Match => {
                     group.verified = true;
                     summary.groups_verified += 1;
@@ -286,373 +338,148 @@ impl DuplicateVerifier {
         (result, summary)
     }
 
-    /// Verify a single group. Returns whether all members match.
-    fn verify_group(&mut self, group: &DuplicateGroup, reader: &dyn FileReader) -> VerifyOutcome {
+    /// The final digest kind of this verifier's mode, `None` for
+    /// [`DuplicateVerify::None`].
+    const fn final_kind(&self) -> Option<HashKind> {
         match self.mode {
-            DuplicateVerify::None => VerifyOutcome::Match,
-            DuplicateVerify::FirstBytes { count } => self.verify_first_bytes(group, reader, count),
-            DuplicateVerify::Sha256 => self.verify_sha256(group, reader),
+            DuplicateVerify::None => None,
+            DuplicateVerify::FirstBytes { count } => {
+                Some(HashKind::new(HashAlgo::Xxh3, HashSpan::Head(count)))
+            }
+            DuplicateVerify::Sha256 => Some(HashKind::new(HashAlgo::Sha256, HashSpan::Full)),
+            DuplicateVerify::Xxh3 => Some(HashKind::new(HashAlgo::Xxh3, HashSpan::Full)),
+            DuplicateVerify::Blake3 => Some(HashKind::new(HashAlgo::Blake3, HashSpan::Full)),
         }
     }
 
-    /// Compare first N bytes across all group members.
-    fn verify_first_bytes(
+    /// Bytes verifying `group` may read: the final span of every member
+    /// whose final digest is not already in `store`.
+    fn group_estimate(&self, group: &DuplicateGroup, store: Option<&dyn HashStore>) -> u64 {
+        let Some(kind) = self.final_kind() else {
+            return 0;
+        };
+        let uncached = group
+            .member_indices
+            .iter()
+            .filter(|&&(record_idx, drive_ordinal)| {
+                store.is_none_or(|hashes| hashes.lookup(record_idx, drive_ordinal, kind).is_none())
+            })
+            .count();
+        kind.span
+            .read_cost(group.file_size)
+            .saturating_mul(uffs_mft::usize_to_u64(uncached))
+    }
+
+    /// Verify a single group. Returns whether all members match.
+    ///
+    /// The staged modes compare head+tail samples first, so most false
+    /// candidates are rejected after reading at most `2 * SAMPLE_CHUNK`
+    /// bytes per member.  Files that small are hashed whole straight away,
+    /// and so is a group whose full digests are all cached already.
+    fn verify_group(
         &mut self,
         group: &DuplicateGroup,
-        reader: &dyn FileReader,
-        count: u32,
+        source: &mut DigestSource<'_>,
     ) -> VerifyOutcome {
-        let mut reference: Option<Vec<u8>> = None;
-
-        for &(record_idx, drive_ordinal) in &group.member_indices {
-            // Estimate using the file size (capped by requested count).
-            let read_estimate = group.file_size.min(u64::from(count));
-            if !self.budget.can_read(read_estimate) {
-                return VerifyOutcome::IoError; // Budget would be exceeded
-            }
-
-            match reader.read_first_bytes(record_idx, drive_ordinal, count) {
-                Ok(bytes) => {
-                    self.budget.record_read(bytes.len() as u64);
-                    match &reference {
-                        None => reference = Some(bytes),
-                        Some(ref_bytes) => {
-                            if bytes != *ref_bytes {
-                                return VerifyOutcome::Mismatch;
-                            }
-                        }
-                    }
-                }
-                Err(_) => return VerifyOutcome::IoError,
+        let Some(kind) = self.final_kind() else {
+            return VerifyOutcome::Match;
+        };
+        let staged = matches!(self.mode, DuplicateVerify::Xxh3 | DuplicateVerify::Blake3);
+        if staged
+            && group.file_size > u64::from(SAMPLE_CHUNK).saturating_mul(2)
+            && self.group_estimate(group, source.store) > 0
+        {
+            let sample = HashKind::new(kind.algo, HashSpan::Sample);
+            match self.compare_members(group, sample, source) {
+                VerifyOutcome::Match => {}
+                outcome @ (VerifyOutcome::Mismatch | VerifyOutcome::IoError) => return outcome,
             }
         }
-
-        VerifyOutcome::Match
+        self.compare_members(group, kind, source)
     }
 
-    /// Full SHA-256 hash verification.
-    ///
-    /// Reads the entire file for each member, computes SHA-256, and
-    /// compares all hashes. All members must hash identically.
-    fn verify_sha256(&mut self, group: &DuplicateGroup, reader: &dyn FileReader) -> VerifyOutcome {
-        use sha2::{Digest as _, Sha256};
-
-        let mut reference_hash: Option<[u8; 32]> = None;
+    /// Digest every member as `kind` and compare the digests.
+    fn compare_members(
+        &mut self,
+        group: &DuplicateGroup,
+        kind: HashKind,
+        source: &mut DigestSource<'_>,
+    ) -> VerifyOutcome {
+        let mut reference: Option<Digest> = None;
 
         for &(record_idx, drive_ordinal) in &group.member_indices {
-            // Estimate read size from file_size for budget check.
-            if !self.budget.can_read(group.file_size) {
+            let Some(digest) =
+                self.member_digest(record_idx, drive_ordinal, kind, group.file_size, source)
+            else {
                 return VerifyOutcome::IoError;
-            }
-
-            match reader.read_all(record_idx, drive_ordinal) {
-                Ok(bytes) => {
-                    self.budget.record_read(bytes.len() as u64);
-                    let hash: [u8; 32] = Sha256::digest(&bytes).into();
-                    match &reference_hash {
-                        None => reference_hash = Some(hash),
-                        Some(ref_hash) => {
-                            if hash != *ref_hash {
-                                return VerifyOutcome::Mismatch;
-                            }
-                        }
+            };
+            match reference {
+                None => reference = Some(digest),
+                Some(ref_digest) => {
+                    if digest != ref_digest {
+                        return VerifyOutcome::Mismatch;
                     }
                 }
-                Err(_) => return VerifyOutcome::IoError,
             }
         }
 
         VerifyOutcome::Match
     }
-}
 
-#[cfg(test)]
-#[expect(
-    clippy::indexing_slicing,
-    reason = "tests assert against fixtures with known shape; indexing panic = test failure"
-)]
-mod tests {
-    use super::*;
-    use crate::aggregate::duplicates::{DuplicateGroup, DuplicateResult};
-    use crate::aggregate::spec::DuplicateVerify;
-
-    /// Mock file reader that returns controlled byte content.
-    struct MockReader {
-        /// Map from (`record_idx`, `drive_ordinal`) → file bytes.
-        files: std::collections::HashMap<(usize, u8), Vec<u8>>,
-    }
-
-    impl MockReader {
-        fn new() -> Self {
-            Self {
-                files: std::collections::HashMap::new(),
-            }
-        }
-
-        fn add(&mut self, idx: usize, drive: u8, content: Vec<u8>) {
-            self.files.insert((idx, drive), content);
-        }
-    }
-
-    impl FileReader for MockReader {
-        fn read_first_bytes(
-            &self,
-            record_idx: usize,
-            drive_ordinal: u8,
-            count: u32,
-        ) -> io::Result<Vec<u8>> {
-            let bytes = self
-                .files
-                .get(&(record_idx, drive_ordinal))
-                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "file not found"))?;
-            let n = uffs_mft::u32_as_usize(count).min(bytes.len());
-            Ok(bytes[..n].to_vec())
+    /// One member's digest of `kind`: from the store when cached,
+    /// otherwise read (within budget), hashed and recorded.  `None` when
+    /// the budget would be exceeded or the read fails.
+    fn member_digest(
+        &mut self,
+        record_idx: usize,
+        drive_ordinal: u8,
+        kind: HashKind,
+        file_size: u64,
+        source: &mut DigestSource<'_>,
+    ) -> Option<Digest> {
+        if let Some(cached) = source
+            .store
+            .and_then(|hashes| hashes.lookup(record_idx, drive_ordinal, kind))
+        {
+            source.cache_hits += 1;
+            return Some(cached);
         }
-
-        fn read_all(&self, record_idx: usize, drive_ordinal: u8) -> io::Result<Vec<u8>> {
-            self.files
-                .get(&(record_idx, drive_ordinal))
-                .cloned()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "file not found"))
+        // Estimate the read from the file size for the budget check.
+        if !self.budget.can_read(kind.span.read_cost(file_size)) {
+            return None;
         }
-    }
-
-    fn make_group(members: Vec<(usize, u8)>, file_size: u64) -> DuplicateGroup {
-        let count = members.len() as u64;
-        DuplicateGroup {
-            count,
-            total_bytes: file_size * count,
-            file_size,
-            reclaimable_bytes: file_size * (count - 1),
-            member_indices: members,
-            sample_rows: Vec::new(),
-            verified: false,
+        let bytes = match kind.span {
+            HashSpan::Head(count) => {
+                source
+                    .reader
+                    .read_first_bytes(record_idx, drive_ordinal, count)
+            }
+            HashSpan::Sample => {
+                source
+                    .reader
+                    .read_head_tail(record_idx, drive_ordinal, SAMPLE_CHUNK)
+            }
+            HashSpan::Full => source.reader.read_all(record_idx, drive_ordinal),
         }
-    }
-
-    fn make_result(groups: Vec<DuplicateGroup>) -> DuplicateResult {
-        let candidate_files: u64 = groups.iter().map(|group| group.count).sum();
-        let total_dup = groups.iter().map(|group| group.total_bytes).sum();
-        let total_reclaim = groups.iter().map(|group| group.reclaimable_bytes).sum();
-        DuplicateResult {
-            candidate_groups: groups.len(),
-            candidate_files,
-            total_duplicate_bytes: total_dup,
-            total_reclaimable_bytes: total_reclaim,
-            groups,
-            verification_mode: DuplicateVerify::None,
+        .ok()?;
+        self.budget.record_read(bytes.len() as u64);
+        let digest = kind.algo.digest(&bytes);
+        if let Some(hashes) = source.store {
+            hashes.record(record_idx, drive_ordinal, kind, digest);
         }
+        Some(digest)
     }
+}
 
-    // ── first_bytes tests ────────────────────────────────────────
-
-    #[test]
-    fn first_bytes_match_keeps_group() {
-        let mut reader = MockReader::new();
-        reader.add(0, 0, vec![1, 2, 3, 4]);
-        reader.add(1, 0, vec![1, 2, 3, 4]);
-
-        let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::unlimited(),
-        );
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(vfy_result.groups.len(), 1);
-        assert!(vfy_result.groups[0].verified);
-        assert_eq!(summary.groups_verified, 1);
-        assert_eq!(summary.groups_rejected, 0);
-    }
-
-    #[test]
-    fn first_bytes_mismatch_rejects_group() {
-        let mut reader = MockReader::new();
-        reader.add(0, 0, vec![1, 2, 3, 4]);
-        reader.add(1, 0, vec![5, 6, 7, 8]); // different content
-
-        let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::unlimited(),
-        );
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(vfy_result.groups.len(), 0); // rejected
-        assert_eq!(summary.groups_rejected, 1);
-    }
-
-    // ── sha256 tests ─────────────────────────────────────────────
-
-    #[test]
-    fn sha256_match_keeps_group() {
-        let content = b"hello world duplicate content".to_vec();
-        let mut reader = MockReader::new();
-        reader.add(0, 0, content.clone());
-        reader.add(1, 0, content);
-
-        let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 28)]);
-        let mut verifier =
-            DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::unlimited());
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(vfy_result.groups.len(), 1);
-        assert!(vfy_result.groups[0].verified);
-        assert_eq!(summary.groups_verified, 1);
-    }
-
-    #[test]
-    fn sha256_mismatch_rejects_group() {
-        let mut reader = MockReader::new();
-        reader.add(0, 0, b"file A content".to_vec());
-        reader.add(1, 0, b"file B content".to_vec());
-
-        let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 14)]);
-        let mut verifier =
-            DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::unlimited());
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(vfy_result.groups.len(), 0);
-        assert_eq!(summary.groups_rejected, 1);
-    }
-
-    // ── Budget tests ─────────────────────────────────────────────
-
-    #[test]
-    fn budget_exhaustion_skips_remaining() {
-        let mut reader = MockReader::new();
-        // Group 1: 2 files, 4 bytes each → 8 bytes read
-        reader.add(0, 0, vec![1, 2, 3, 4]);
-        reader.add(1, 0, vec![1, 2, 3, 4]);
-        // Group 2: 2 files — should be skipped
-        reader.add(2, 0, vec![5, 6, 7, 8]);
-        reader.add(3, 0, vec![5, 6, 7, 8]);
-
-        let result = make_result(vec![
-            make_group(vec![(0, 0), (1, 0)], 4),
-            make_group(vec![(2, 0), (3, 0)], 4),
-        ]);
-
-        // Budget: 10 bytes max → group 1 reads 8 bytes (ok), group 2 needs 8 more (over
-        // budget)
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::new(10, 0),
-        );
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(summary.groups_verified, 1);
-        assert_eq!(summary.groups_skipped, 1);
-        assert!(summary.budget_exhausted);
-        // Both groups kept: 1 verified, 1 unverified
-        assert_eq!(vfy_result.groups.len(), 2);
-        assert!(vfy_result.groups[0].verified);
-        assert!(!vfy_result.groups[1].verified);
-    }
-
-    #[test]
-    fn file_count_budget() {
-        let mut reader = MockReader::new();
-        reader.add(0, 0, vec![1]);
-        reader.add(1, 0, vec![1]);
-        reader.add(2, 0, vec![2]);
-        reader.add(3, 0, vec![2]);
-
-        let result = make_result(vec![
-            make_group(vec![(0, 0), (1, 0)], 1),
-            make_group(vec![(2, 0), (3, 0)], 1),
-        ]);
-
-        // Max 2 file reads → first group verified (2 reads), second skipped
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::new(0, 2),
-        );
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(summary.groups_verified, 1);
-        assert_eq!(summary.groups_skipped, 1);
-        assert!(summary.budget_exhausted);
-        assert_eq!(vfy_result.groups.len(), 2);
-    }
-
-    // ── Edge cases ───────────────────────────────────────────────
-
-    #[test]
-    fn none_mode_passes_through() {
-        let reader = MockReader::new();
-        let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
-        let mut verifier =
-            DuplicateVerifier::new(DuplicateVerify::None, VerificationBudget::unlimited());
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        // No verification — group kept as-is, not marked verified
-        assert_eq!(vfy_result.groups.len(), 1);
-        assert!(!vfy_result.groups[0].verified);
-        assert_eq!(summary.groups_verified, 0);
-    }
-
-    #[test]
-    fn io_error_keeps_group_unverified() {
-        let reader = MockReader::new(); // no files → read will fail
-
-        let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::unlimited(),
-        );
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        // I/O error keeps group unverified but doesn't reject
-        assert_eq!(vfy_result.groups.len(), 1);
-        assert!(!vfy_result.groups[0].verified);
-        assert_eq!(summary.groups_errored, 1);
-    }
-
-    #[test]
-    fn single_member_group_removed() {
-        let mut reader = MockReader::new();
-        reader.add(0, 0, vec![1, 2, 3]);
-
-        let result = make_result(vec![make_group(vec![(0, 0)], 3)]);
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::unlimited(),
-        );
-        let (vfy_result, _) = verifier.verify(result, &reader);
-
-        // Single-member groups are removed
-        assert!(vfy_result.groups.is_empty());
-    }
-
-    #[test]
-    fn multi_group_mixed_results() {
-        let mut reader = MockReader::new();
-        // Group 1: matches
-        reader.add(0, 0, vec![1, 2, 3]);
-        reader.add(1, 0, vec![1, 2, 3]);
-        // Group 2: mismatches
-        reader.add(2, 0, vec![4, 5, 6]);
-        reader.add(3, 0, vec![7, 8, 9]);
-        // Group 3: matches
-        reader.add(4, 0, vec![10, 11]);
-        reader.add(5, 0, vec![10, 11]);
-
-        let result = make_result(vec![
-            make_group(vec![(0, 0), (1, 0)], 3),
-            make_group(vec![(2, 0), (3, 0)], 3),
-            make_group(vec![(4, 0), (5, 0)], 2),
-        ]);
-
-        let mut verifier = DuplicateVerifier::new(
-            DuplicateVerify::FirstBytes { count: 4096 },
-            VerificationBudget::unlimited(),
-        );
-        let (vfy_result, summary) = verifier.verify(result, &reader);
-
-        assert_eq!(vfy_result.groups.len(), 2); // group 2 rejected
-        assert_eq!(summary.groups_verified, 2);
-        assert_eq!(summary.groups_rejected, 1);
-        assert!(vfy_result.groups.iter().all(|group| group.verified));
-    }
+/// Where member digests come from during one group's verification.
+struct DigestSource<'a> {
+    /// Content reader for uncached members.
+    reader: &'a dyn FileReader,
+    /// Optional content-hash store.
+    store: Option<&'a dyn HashStore>,
+    /// Digests answered by `store`.
+    cache_hits: u64,
 }
+
+#[cfg(test)]
+mod tests;
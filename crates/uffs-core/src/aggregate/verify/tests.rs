@@ -0,0 +1,406 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Unit tests for [`super`] (`verify`).
+//!
+//! Extracted from `verify.rs` into a sibling submodule so the lib file
+//! stays under the 800 LOC policy ceiling (mirrors the
+//! `duplicates/tests.rs` extraction).
+
+#![expect(
+    clippy::indexing_slicing,
+    reason = "tests assert against fixtures with known shape; indexing panic = test failure"
+)]
+
+use super::*;
+use crate::aggregate::duplicates::{DuplicateGroup, DuplicateResult};
+use crate::aggregate::spec::DuplicateVerify;
+
+/// Mock file reader that returns controlled byte content.
+struct MockReader {
+    /// Map from (`record_idx`, `drive_ordinal`) → file bytes.
+    files: std::collections::HashMap<(usize, u8), Vec<u8>>,
+    /// Reads served so far.
+    reads: core::cell::Cell<u32>,
+}
+
+impl MockReader {
+    fn new() -> Self {
+        Self {
+            files: std::collections::HashMap::new(),
+            reads: core::cell::Cell::new(0),
+        }
+    }
+
+    fn add(&mut self, idx: usize, drive: u8, content: Vec<u8>) {
+        self.files.insert((idx, drive), content);
+    }
+}
+
+impl FileReader for MockReader {
+    fn read_first_bytes(
+        &self,
+        record_idx: usize,
+        drive_ordinal: u8,
+        count: u32,
+    ) -> io::Result<Vec<u8>> {
+        self.reads.set(self.reads.get() + 1);
+        let bytes = self
+            .files
+            .get(&(record_idx, drive_ordinal))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "file not found"))?;
+        let n = uffs_mft::u32_as_usize(count).min(bytes.len());
+        Ok(bytes[..n].to_vec())
+    }
+
+    fn read_all(&self, record_idx: usize, drive_ordinal: u8) -> io::Result<Vec<u8>> {
+        self.reads.set(self.reads.get() + 1);
+        self.files
+            .get(&(record_idx, drive_ordinal))
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "file not found"))
+    }
+}
+
+/// In-memory [`HashStore`] keyed directly by member.
+#[derive(Default)]
+struct MemoryStore {
+    /// Digest per `(record_idx, drive_ordinal, kind)`.
+    digests: core::cell::RefCell<std::collections::HashMap<(usize, u8, HashKind), Digest>>,
+}
+
+impl HashStore for MemoryStore {
+    fn lookup(&self, record_idx: usize, drive_ordinal: u8, kind: HashKind) -> Option<Digest> {
+        self.digests
+            .borrow()
+            .get(&(record_idx, drive_ordinal, kind))
+            .copied()
+    }
+
+    fn record(&self, record_idx: usize, drive_ordinal: u8, kind: HashKind, digest: Digest) {
+        self.digests
+            .borrow_mut()
+            .insert((record_idx, drive_ordinal, kind), digest);
+    }
+}
+
+fn make_group(members: Vec<(usize, u8)>, file_size: u64) -> DuplicateGroup {
+    let count = members.len() as u64;
+    DuplicateGroup {
+        count,
+        total_bytes: file_size * count,
+        file_size,
+        reclaimable_bytes: file_size * (count - 1),
+        member_indices: members,
+        sample_rows: Vec::new(),
+        verified: false,
+    }
+}
+
+fn make_result(groups: Vec<DuplicateGroup>) -> DuplicateResult {
+    let candidate_files: u64 = groups.iter().map(|group| group.count).sum();
+    let total_dup = groups.iter().map(|group| group.total_bytes).sum();
+    let total_reclaim = groups.iter().map(|group| group.reclaimable_bytes).sum();
+    DuplicateResult {
+        candidate_groups: groups.len(),
+        candidate_files,
+        total_duplicate_bytes: total_dup,
+        total_reclaimable_bytes: total_reclaim,
+        groups,
+        verification_mode: DuplicateVerify::None,
+    }
+}
+
+// ── first_bytes tests ────────────────────────────────────────
+
+#[test]
+fn first_bytes_match_keeps_group() {
+    let mut reader = MockReader::new();
+    reader.add(0, 0, vec![1, 2, 3, 4]);
+    reader.add(1, 0, vec![1, 2, 3, 4]);
+
+    let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::unlimited(),
+    );
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(vfy_result.groups.len(), 1);
+    assert!(vfy_result.groups[0].verified);
+    assert_eq!(summary.groups_verified, 1);
+    assert_eq!(summary.groups_rejected, 0);
+}
+
+#[test]
+fn first_bytes_mismatch_rejects_group() {
+    let mut reader = MockReader::new();
+    reader.add(0, 0, vec![1, 2, 3, 4]);
+    reader.add(1, 0, vec![5, 6, 7, 8]); // different content
+
+    let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::unlimited(),
+    );
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(vfy_result.groups.len(), 0); // rejected
+    assert_eq!(summary.groups_rejected, 1);
+}
+
+// ── sha256 tests ─────────────────────────────────────────────
+
+#[test]
+fn sha256_match_keeps_group() {
+    let content = b"hello world duplicate content".to_vec();
+    let mut reader = MockReader::new();
+    reader.add(0, 0, content.clone());
+    reader.add(1, 0, content);
+
+    let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 28)]);
+    let mut verifier =
+        DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::unlimited());
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(vfy_result.groups.len(), 1);
+    assert!(vfy_result.groups[0].verified);
+    assert_eq!(summary.groups_verified, 1);
+}
+
+#[test]
+fn sha256_mismatch_rejects_group() {
+    let mut reader = MockReader::new();
+    reader.add(0, 0, b"file A content".to_vec());
+    reader.add(1, 0, b"file B content".to_vec());
+
+    let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 14)]);
+    let mut verifier =
+        DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::unlimited());
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(vfy_result.groups.len(), 0);
+    assert_eq!(summary.groups_rejected, 1);
+}
+
+// ── Budget tests ─────────────────────────────────────────────
+
+#[test]
+fn budget_exhaustion_skips_remaining() {
+    let mut reader = MockReader::new();
+    // Group 1: 2 files, 4 bytes each → 8 bytes read
+    reader.add(0, 0, vec![1, 2, 3, 4]);
+    reader.add(1, 0, vec![1, 2, 3, 4]);
+    // Group 2: 2 files — should be skipped
+    reader.add(2, 0, vec![5, 6, 7, 8]);
+    reader.add(3, 0, vec![5, 6, 7, 8]);
+
+    let result = make_result(vec![
+        make_group(vec![(0, 0), (1, 0)], 4),
+        make_group(vec![(2, 0), (3, 0)], 4),
+    ]);
+
+    // Budget: 10 bytes max → group 1 reads 8 bytes (ok), group 2 needs 8 more (over
+    // budget)
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::new(10, 0),
+    );
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(summary.groups_verified, 1);
+    assert_eq!(summary.groups_skipped, 1);
+    assert!(summary.budget_exhausted);
+    // Both groups kept: 1 verified, 1 unverified
+    assert_eq!(vfy_result.groups.len(), 2);
+    assert!(vfy_result.groups[0].verified);
+    assert!(!vfy_result.groups[1].verified);
+}
+
+#[test]
+fn file_count_budget() {
+    let mut reader = MockReader::new();
+    reader.add(0, 0, vec![1]);
+    reader.add(1, 0, vec![1]);
+    reader.add(2, 0, vec![2]);
+    reader.add(3, 0, vec![2]);
+
+    let result = make_result(vec![
+        make_group(vec![(0, 0), (1, 0)], 1),
+        make_group(vec![(2, 0), (3, 0)], 1),
+    ]);
+
+    // Max 2 file reads → first group verified (2 reads), second skipped
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::new(0, 2),
+    );
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(summary.groups_verified, 1);
+    assert_eq!(summary.groups_skipped, 1);
+    assert!(summary.budget_exhausted);
+    assert_eq!(vfy_result.groups.len(), 2);
+}
+
+// ── Edge cases ───────────────────────────────────────────────
+
+#[test]
+fn none_mode_passes_through() {
+    let reader = MockReader::new();
+    let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
+    let mut verifier =
+        DuplicateVerifier::new(DuplicateVerify::None, VerificationBudget::unlimited());
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    // No verification — group kept as-is, not marked verified
+    assert_eq!(vfy_result.groups.len(), 1);
+    assert!(!vfy_result.groups[0].verified);
+    assert_eq!(summary.groups_verified, 0);
+}
+
+#[test]
+fn io_error_keeps_group_unverified() {
+    let reader = MockReader::new(); // no files → read will fail
+
+    let result = make_result(vec![make_group(vec![(0, 0), (1, 0)], 4)]);
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::unlimited(),
+    );
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    // I/O error keeps group unverified but doesn't reject
+    assert_eq!(vfy_result.groups.len(), 1);
+    assert!(!vfy_result.groups[0].verified);
+    assert_eq!(summary.groups_errored, 1);
+}
+
+#[test]
+fn single_member_group_removed() {
+    let mut reader = MockReader::new();
+    reader.add(0, 0, vec![1, 2, 3]);
+
+    let result = make_result(vec![make_group(vec![(0, 0)], 3)]);
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::unlimited(),
+    );
+    let (vfy_result, _) = verifier.verify(result, &reader);
+
+    // Single-member groups are removed
+    assert!(vfy_result.groups.is_empty());
+}
+
+#[test]
+fn multi_group_mixed_results() {
+    let mut reader = MockReader::new();
+    // Group 1: matches
+    reader.add(0, 0, vec![1, 2, 3]);
+    reader.add(1, 0, vec![1, 2, 3]);
+    // Group 2: mismatches
+    reader.add(2, 0, vec![4, 5, 6]);
+    reader.add(3, 0, vec![7, 8, 9]);
+    // Group 3: matches
+    reader.add(4, 0, vec![10, 11]);
+    reader.add(5, 0, vec![10, 11]);
+
+    let result = make_result(vec![
+        make_group(vec![(0, 0), (1, 0)], 3),
+        make_group(vec![(2, 0), (3, 0)], 3),
+        make_group(vec![(4, 0), (5, 0)], 2),
+    ]);
+
+    let mut verifier = DuplicateVerifier::new(
+        DuplicateVerify::FirstBytes { count: 4096 },
+        VerificationBudget::unlimited(),
+    );
+    let (vfy_result, summary) = verifier.verify(result, &reader);
+
+    assert_eq!(vfy_result.groups.len(), 2); // group 2 rejected
+    assert_eq!(summary.groups_verified, 2);
+    assert_eq!(summary.groups_rejected, 1);
+    assert!(vfy_result.groups.iter().all(|group| group.verified));
+}
+
+#[test]
+fn cached_digests_skip_reads_on_a_repeat_scan() {
+    let mut reader = MockReader::new();
+    reader.add(0, 0, b"same content".to_vec());
+    reader.add(1, 0, b"same content".to_vec());
+    let store = MemoryStore::default();
+
+    let mut first =
+        DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::unlimited());
+    let (_, summary) = first.verify_cached(
+        make_result(vec![make_group(vec![(0, 0), (1, 0)], 12)]),
+        &reader,
+        Some(&store),
+    );
+    assert_eq!(summary.groups_verified, 1);
+    assert_eq!(summary.cache_hits, 0);
+    assert_eq!(reader.reads.get(), 2);
+
+    // Even a spent budget verifies a group that needs no reads.
+    let mut repeat = DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::new(1, 1));
+    let (cached, cached_summary) = repeat.verify_cached(
+        make_result(vec![make_group(vec![(0, 0), (1, 0)], 12)]),
+        &reader,
+        Some(&store),
+    );
+    assert!(cached.groups[0].verified);
+    assert_eq!(cached_summary.cache_hits, 2);
+    assert_eq!(cached_summary.files_read, 0);
+    assert_eq!(reader.reads.get(), 2);
+}
+
+#[test]
+fn staged_modes_reject_on_the_sample_before_reading_whole_files() {
+    let size = 200_000_usize;
+    let mut tail_differs = vec![7_u8; size];
+    if let Some(last) = tail_differs.last_mut() {
+        *last = 8;
+    }
+    let mut middle_differs = vec![7_u8; size];
+    middle_differs[size / 2] = 9;
+
+    for mode in [DuplicateVerify::Xxh3, DuplicateVerify::Blake3] {
+        let mut reader = MockReader::new();
+        reader.add(0, 0, vec![7_u8; size]);
+        reader.add(1, 0, tail_differs.clone());
+        let mut verifier = DuplicateVerifier::new(mode, VerificationBudget::unlimited());
+        let (result, summary) = verifier.verify(
+            make_result(vec![make_group(vec![(0, 0), (1, 0)], 200_000)]),
+            &reader,
+        );
+        assert!(result.groups.is_empty(), "{mode:?}");
+        assert_eq!(summary.groups_rejected, 1);
+        // Only the two head+tail samples were read.
+        assert_eq!(summary.bytes_read, 2 * 2 * u64::from(SAMPLE_CHUNK));
+
+        // Equal samples fall through to the full hash, which still tells
+        // the files apart.
+        let mut full_reader = MockReader::new();
+        full_reader.add(0, 0, vec![7_u8; size]);
+        full_reader.add(1, 0, middle_differs.clone());
+        let mut full_verifier = DuplicateVerifier::new(mode, VerificationBudget::unlimited());
+        let (full, full_summary) = full_verifier.verify(
+            make_result(vec![make_group(vec![(0, 0), (1, 0)], 200_000)]),
+            &full_reader,
+        );
+        assert!(full.groups.is_empty(), "{mode:?}");
+        assert_eq!(full_summary.files_read, 4);
+    }
+
+    let mut reader = MockReader::new();
+    reader.add(0, 0, vec![7_u8; size]);
+    reader.add(1, 0, vec![7_u8; size]);
+    let mut verifier =
+        DuplicateVerifier::new(DuplicateVerify::Blake3, VerificationBudget::unlimited());
+    let (result, _) = verifier.verify(
+        make_result(vec![make_group(vec![(0, 0), (1, 0)], 200_000)]),
+        &reader,
+    );
+    assert!(result.groups[0].verified);
+    assert_eq!(result.verification_mode, DuplicateVerify::Blake3);
+}
@@ -63,6 +63,15 @@ pub(crate) const TRIGRAM_COMPACT_THRESHOLD: u32 = 50_000;
 pub struct DriveCompactIndex {
     /// Drive letter (e.g., 'C').
     pub letter: uffs_mft::platform::DriveLetter,
+    /// Host the volume was captured on; `None` for the daemon's own
+    /// volumes.  Together with [`Self::letter`] this is the shard's
+    /// [`VolumeId`](uffs_mft::platform::VolumeId) — see [`Self::volume`].
+    /// Not persisted in the compact cache (the cache filename carries it).
+    pub host: Option<uffs_mft::platform::HostLabel>,
+    /// Serial of the captured volume when the capture recorded one; part
+    /// of [`Self::volume`] like [`Self::host`], and likewise carried by the
+    /// cache filename rather than the cache body.
+    pub serial: Option<uffs_mft::platform::VolumeSerial>,
     /// Compact records — one per MFT file/directory.
     ///
     /// Backed by [`ColumnStorage`] so Phase 2b can transparently
@@ -194,6 +203,40 @@ impl AsRef<Self> for DriveCompactIndex {
 }
 
 impl DriveCompactIndex {
+    /// This volume's identity (host label + letter + serial).
+    #[inline]
+    #[must_use]
+    pub const fn volume(&self) -> uffs_mft::platform::VolumeId {
+        uffs_mft::platform::VolumeId::new(self.host, self.letter).with_serial(self.serial)
+    }
+
+    /// Root that resolved paths start with: `C:\` for local volumes, the
+    /// installed [`PathTemplate`](uffs_mft::platform::PathTemplate)
+    /// rendering (`srv01\C:\`, `/evidence/srv01/C/`) for hosted ones.
+    #[inline]
+    #[must_use]
+    pub fn volume_prefix(&self) -> Cow<'static, str> {
+        self.volume().display_root()
+    }
+
+    /// Rewrite a path resolved below [`Self::volume_prefix`] into display
+    /// form.  Only `/`-separated templates change anything: the resolver
+    /// joins components with `\`, which is swapped for `/` after the root.
+    #[must_use]
+    pub fn display_path(&self, path: String) -> String {
+        let volume = self.volume();
+        if volume.display_separator() == '\\' {
+            return path;
+        }
+        let root_len = volume.display_root().len();
+        match path.split_at_checked(root_len) {
+            Some((root, rest)) if rest.contains('\\') => {
+                format!("{root}{}", rest.replace('\\', "/"))
+            }
+            _ => path,
+        }
+    }
+
     /// Trigram candidate search through the base ∪ delta overlay (design §5.2).
     ///
     /// The single choke point every trigram caller goes through. When
@@ -261,6 +304,43 @@ impl DriveCompactIndex {
         Some(result)
     }
 
+    /// Record indices sharing at least `min_shared` of `trigrams` with their
+    /// current name — the candidate pass of fuzzy search
+    /// ([`crate::search::fuzzy`]).  Returned sorted ascending.
+    ///
+    /// Counts hits over each trigram's effective posting (base ∪ delta), so
+    /// names created or renamed since the last compaction are found.  A
+    /// tombstoned record's base postings describe a stale name and are not
+    /// counted; only its delta (re-added) postings are.
+    #[must_use]
+    pub fn trigram_overlap(&self, trigrams: &[u64], min_shared: usize) -> Vec<u32> {
+        let mut hits: rustc_hash::FxHashMap<u32, usize> = rustc_hash::FxHashMap::default();
+        for &tri in trigrams {
+            let base = self.trigram.get_posting(tri).unwrap_or(&[]);
+            match &self.delta {
+                None => {
+                    for &idx in base {
+                        *hits.entry(idx).or_default() += 1;
+                    }
+                }
+                Some(delta) => {
+                    let added = delta.trigram_postings(tri);
+                    for idx in delta::merge_postings(base, added) {
+                        if !delta.is_tombstoned(idx) || added.binary_search(&idx).is_ok() {
+                            *hits.entry(idx).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+        let mut result: Vec<u32> = hits
+            .into_iter()
+            .filter_map(|(idx, count)| (count >= min_shared).then_some(idx))
+            .collect();
+        result.sort_unstable();
+        result
+    }
+
     /// Fold the delta overlay back into fresh bases and clear it (design §5.4
     /// compaction). Rebuilds the trigram (Phase 2b) and extension (Phase 4a)
     /// bases from the current records — which already reflect every applied
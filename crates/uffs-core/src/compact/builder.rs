@@ -316,6 +316,8 @@ pub fn build_compact_index(
     // sentinel `frs_to_compact` uses for unmapped slots.
     let mut compact_index = DriveCompactIndex {
         letter: drive_letter,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
@@ -163,8 +163,10 @@ const COMPACT_MAGIC: &[u8; 8] = b"UFFSCOM\0";
 ///   reuse); rejecting them forces a rebuild that captures the sequence number.
 const COMPACT_VERSION: u16 = 13;
 
+pub mod checkpoint;
 mod filters_io;
 pub mod parked;
+pub use checkpoint::{read_compact_checkpoint, write_compact_checkpoint};
 pub use parked::{ParkedBody, deserialize_parked_body, load_parked_body};
 /// Bytes per `CompactRecord`.
 const RECORD_BYTES: usize = size_of::<CompactRecord>();
@@ -174,9 +176,12 @@ const ZSTD_LEVEL: i32 = 3;
 const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
 
 /// Returns the cache file path for a compact index.
+///
+/// Keyed by [`VolumeId::file_stem`](uffs_mft::platform::VolumeId::file_stem)
+/// so two machines' `C:` captures never share a file.
 #[must_use]
-pub fn compact_cache_path(drive_letter: uffs_mft::platform::DriveLetter) -> PathBuf {
-    uffs_mft::cache::cache_dir().join(format!("{drive_letter}_compact.uffs"))
+pub fn compact_cache_path<V: Into<uffs_mft::platform::VolumeId>>(drive: V) -> PathBuf {
+    uffs_mft::cache::cache_dir().join(format!("{}_compact.uffs", drive.into().file_stem()))
 }
 
 /// Returns the per-drive USN cursor file path used by the Phase 7
@@ -194,8 +199,8 @@ pub fn compact_cache_path(drive_letter: uffs_mft::platform::DriveLetter) -> Path
 ///
 /// [`CursorStore`]: # "Trait defined in `uffs-daemon::cache::journal_loop`"
 #[must_use]
-pub fn usn_cursor_path(drive_letter: uffs_mft::platform::DriveLetter) -> PathBuf {
-    uffs_mft::cache::cache_dir().join(format!("{drive_letter}_usn.cursor"))
+pub fn usn_cursor_path<V: Into<uffs_mft::platform::VolumeId>>(drive: V) -> PathBuf {
+    uffs_mft::cache::cache_dir().join(format!("{}_usn.cursor", drive.into().file_stem()))
 }
 
 /// Remove a stale **directory** at the compact-cache target path, if any.
@@ -799,6 +804,8 @@ where
 
     let mut compact_index = DriveCompactIndex {
         letter: parsed.drive_letter,
+        host: None,
+        serial: None,
         records,
         names,
         trigram: Arc::new(trigram),
@@ -954,7 +961,7 @@ fn parse_compact_header(data: &[u8]) -> Result<(u64, usize, u16), &'static str>
 /// Returns an error if compression, encryption, or file writing fails.
 pub fn save_compact_cache(index: &DriveCompactIndex) -> io::Result<()> {
     let profile = std::env::var_os("UFFS_CACHE_PROFILE").is_some();
-    let path = compact_cache_path(index.letter);
+    let path = compact_cache_path(index.volume());
     if let Some(dir) = path.parent() {
         uffs_mft::cache::create_secure_dir(dir)?;
     }
@@ -1004,7 +1011,7 @@ pub fn save_compact_cache_background(index: &DriveCompactIndex) -> io::Result<()
         );
     }
 
-    let path = compact_cache_path(index.letter);
+    let path = compact_cache_path(index.volume());
     if let Some(dir) = path.parent() {
         uffs_mft::cache::create_secure_dir(dir)?;
     }
@@ -1071,7 +1078,7 @@ fn encrypt_and_write(
 /// "stale vs MFT" without re-reading the metadata.
 fn check_compact_cache_freshness(
     path: &Path,
-    drive_letter: uffs_mft::platform::DriveLetter,
+    volume: uffs_mft::platform::VolumeId,
     ttl_seconds: u64,
     trust_ttl_only: bool,
 ) -> LoadCacheResult<()> {
@@ -1094,13 +1101,13 @@ fn check_compact_cache_freshness(
     if trust_ttl_only {
         return Ok(());
     }
-    let mft_path = uffs_mft::cache::cache_file_path(drive_letter);
+    let mft_path = uffs_mft::cache::cache_file_path(volume);
     if let Ok(mft_meta) = std::fs::metadata(&mft_path)
         && let Ok(mft_mtime) = mft_meta.modified()
         && mft_mtime > compact_mtime
     {
         tracing::debug!(
-            drive = %drive_letter,
+            drive = %volume,
             "Compact cache older than MftIndex cache — rebuilding"
         );
         return Err(LoadCacheError::StaleVsMft);
@@ -1128,14 +1135,16 @@ fn check_compact_cache_freshness(
 /// return; callers can now distinguish e.g. "cache file missing"
 /// (cold-boot rebuild) from "decryption failed" (key rotated;
 /// rebuild + alert) from "stale by epoch" (incremental refresh).
-pub fn load_compact_cache(
-    drive_letter: uffs_mft::platform::DriveLetter,
+pub fn load_compact_cache<V: Into<uffs_mft::platform::VolumeId>>(
+    drive: V,
     ttl_seconds: u64,
     mft_build_epoch: u64,
     trust_ttl_only: bool,
 ) -> LoadCacheResult<DriveCompactIndex> {
-    let path = compact_cache_path(drive_letter);
-    check_compact_cache_freshness(&path, drive_letter, ttl_seconds, trust_ttl_only)?;
+    let volume = drive.into();
+    let drive_letter = volume.letter();
+    let path = compact_cache_path(volume);
+    check_compact_cache_freshness(&path, volume, ttl_seconds, trust_ttl_only)?;
 
     let profile = std::env::var_os("UFFS_CACHE_PROFILE").is_some();
     let t_total = Instant::now();
@@ -1190,9 +1199,11 @@ pub fn load_compact_cache(
         .map_err(|err| LoadCacheError::RuntimeTempfile(err.to_string()))?;
     let runtime_dir = uffs_security::runtime_dir::DefaultRuntimeDir::default();
     let t_deser = Instant::now();
-    let (index, tri_ms) =
+    let (mut index, tri_ms) =
         deserialize_compact_into_runtime(&plaintext, drive_letter, &runtime_dir, &runtime_path)
             .map_err(|err| LoadCacheError::Deserialize(err.to_string()))?;
+    index.host = volume.host();
+    index.serial = volume.serial();
     let deser_ms = t_deser.elapsed().as_millis();
 
     if profile {
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Standalone compact-index checkpoint files.
+//!
+//! The live compact cache lives at one fixed path per volume
+//! ([`super::compact_cache_path`]) and is overwritten on every save. The
+//! daemon's point-in-time history keeps *older* bodies around as well, so
+//! it needs the same on-disk format — zstd + AES-256-GCM over
+//! [`super::serialize_compact`] — at a caller-chosen path.
+//!
+//! Checkpoints are loaded onto the heap ([`super::deserialize_compact`]),
+//! not through the runtime mmap tempfile: they back one-off historical
+//! queries and are dropped as soon as the query returns.
+
+use std::io;
+use std::path::Path;
+
+use super::{DriveCompactIndex, ZSTD_LEVEL, ZSTD_MAGIC, deserialize_compact};
+
+/// Write `index` to `path` as an encrypted, compressed checkpoint,
+/// creating the parent directory (owner-only) if needed. The write is
+/// atomic: a crash leaves either the old file or the new one.
+///
+/// # Errors
+///
+/// Returns an error if directory creation, compression, encryption, or
+/// the write fails.
+pub fn write_compact_checkpoint(index: &DriveCompactIndex, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        uffs_mft::cache::create_secure_dir(dir)?;
+    }
+    uffs_mft::cache::compress_encrypt_write_streaming(
+        |encoder| super::serialize_compact_to_writer(index, encoder),
+        path,
+        ZSTD_LEVEL,
+        false,
+        "checkpoint",
+    )
+}
+
+/// Load a checkpoint written by [`write_compact_checkpoint`] for
+/// `volume`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, the cache key is
+/// unavailable, or the bytes fail to decrypt, decompress, or parse.
+pub fn read_compact_checkpoint<V: Into<uffs_mft::platform::VolumeId>>(
+    path: &Path,
+    volume: V,
+) -> io::Result<DriveCompactIndex> {
+    let volume_id = volume.into();
+    let raw = std::fs::read(path)?;
+    let key = uffs_security::keystore::get_cache_key()?;
+    let decrypted = uffs_security::crypto::decrypt_cache(&raw, &key)?;
+    let plaintext = if decrypted.get(..4).is_some_and(|magic| magic == ZSTD_MAGIC) {
+        zstd::decode_all(decrypted.as_slice())?
+    } else {
+        decrypted
+    };
+    let (mut index, _trigram_ms) =
+        deserialize_compact(&plaintext, volume_id.letter()).map_err(io::Error::other)?;
+    index.host = volume_id.host();
+    index.serial = volume_id.serial();
+    Ok(index)
+}
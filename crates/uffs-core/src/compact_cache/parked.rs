@@ -418,21 +418,21 @@ fn emit_parked_load_profile(
 /// matches `load_compact_cache`'s "any failure → caller rebuilds"
 /// contract.
 #[must_use]
-pub fn load_parked_body(
-    drive_letter: uffs_mft::platform::DriveLetter,
+pub fn load_parked_body<V: Into<uffs_mft::platform::VolumeId>>(
+    drive: V,
     ttl_seconds: u64,
     mft_build_epoch: u64,
     trust_ttl_only: bool,
 ) -> Option<ParkedBody> {
-    let path = compact_cache_path(drive_letter);
+    let volume = drive.into();
+    let drive_letter = volume.letter();
+    let path = compact_cache_path(volume);
     // `load_parked_body` keeps its `Option` return for now (#96 scope
     // limited the Result conversion to `load_compact_cache`); fold any
     // structured `LoadCacheError` into a debug-logged `None`.
-    if let Err(err) =
-        check_compact_cache_freshness(&path, drive_letter, ttl_seconds, trust_ttl_only)
-    {
+    if let Err(err) = check_compact_cache_freshness(&path, volume, ttl_seconds, trust_ttl_only) {
         tracing::debug!(
-            drive = %drive_letter,
+            drive = %volume,
             error = %err,
             "parked-body load: freshness check failed",
         );
@@ -507,6 +507,8 @@ mod tests {
         let ext_index = ExtensionIndex::build(&records);
         let mut index = DriveCompactIndex {
             letter: uffs_mft::platform::DriveLetter::C,
+            host: None,
+            serial: None,
             records: ColumnStorage::from_vec(records),
             names: ColumnStorage::from_vec(names),
             trigram: Arc::new(trigram),
@@ -71,6 +71,8 @@ fn make_test_index() -> DriveCompactIndex {
         .collect();
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
@@ -584,3 +586,27 @@ fn purge_legacy_dir_non_empty_directory_is_refused() {
         "non-empty dir contents must be untouched after refused purge",
     );
 }
+
+/// A checkpoint written to an arbitrary path reads back with the same
+/// records, names and volume.
+#[test]
+fn checkpoint_round_trips_through_encrypted_file() {
+    let tmp = tempfile::TempDir::new().expect("tempdir");
+    let path = tmp.path().join("history").join("T").join("1.checkpoint");
+    let index = make_test_index();
+
+    write_compact_checkpoint(&index, &path).expect("write checkpoint");
+    let loaded = read_compact_checkpoint(&path, uffs_mft::platform::DriveLetter::T)
+        .expect("read checkpoint");
+
+    assert_eq!(loaded.records.len(), index.records.len());
+    assert_eq!(loaded.names.as_slice(), index.names.as_slice());
+    assert_eq!(loaded.letter, uffs_mft::platform::DriveLetter::T);
+    assert!(
+        read_compact_checkpoint(
+            &tmp.path().join("missing"),
+            uffs_mft::platform::DriveLetter::T
+        )
+        .is_err()
+    );
+}
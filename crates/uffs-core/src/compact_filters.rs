@@ -202,6 +202,8 @@ mod tests {
         let ext_index = ExtensionIndex::build(&records);
         DriveCompactIndex {
             letter: uffs_mft::platform::DriveLetter::C,
+            host: None,
+            serial: None,
             records: ColumnStorage::from_vec(records),
             names: ColumnStorage::from_vec(names),
             trigram: Arc::new(trigram),
@@ -322,6 +324,8 @@ mod tests {
         let ext_index = ExtensionIndex::build(&records);
         let drive = DriveCompactIndex {
             letter: uffs_mft::platform::DriveLetter::X,
+            host: None,
+            serial: None,
             records: ColumnStorage::from_vec(records),
             names: ColumnStorage::from_vec(names),
             trigram: Arc::new(trigram),
@@ -40,8 +40,9 @@ pub struct LoadTiming {
 #[derive(Debug, Clone)]
 pub enum MftSource {
     /// Offline MFT file (`.uffs`, `.raw`, `.iocp` capture).
-    /// Second field is an optional drive-letter override.
-    File(PathBuf, Option<uffs_mft::platform::DriveLetter>),
+    /// Second field is an optional volume override — a bare letter, or a
+    /// host-qualified id (`srv01\C`) for a capture from another machine.
+    File(PathBuf, Option<uffs_mft::platform::VolumeId>),
     /// Live Windows NTFS volume (e.g., `'C'`).
     #[cfg(windows)]
     Live(uffs_mft::platform::DriveLetter),
@@ -109,20 +110,23 @@ pub fn load_drive(
     source: &MftSource,
     no_cache: bool,
 ) -> anyhow::Result<(DriveCompactIndex, LoadTiming)> {
-    let drive_letter = match source {
+    let volume = match source {
         MftSource::File(path, drive_override) => drive_override.unwrap_or_else(|| {
             let stem = path
                 .file_name()
                 .and_then(|name| name.to_str())
                 .unwrap_or("X");
-            stem.chars()
+            let letter = stem
+                .chars()
                 .next()
                 .and_then(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
-                .unwrap_or(uffs_mft::platform::DriveLetter::X)
+                .unwrap_or(uffs_mft::platform::DriveLetter::X);
+            uffs_mft::platform::VolumeId::local(letter)
         }),
         #[cfg(windows)]
-        MftSource::Live(ch) | MftSource::Device(_, ch) => *ch,
+        MftSource::Live(ch) | MftSource::Device(_, ch) => uffs_mft::platform::VolumeId::local(*ch),
     };
+    let drive_letter = volume.letter();
 
     // ── Load MftIndex (cache + USN replay, or cold) ────────────────
     //
@@ -145,7 +149,7 @@ pub fn load_drive(
     // guarantee.
     let mft_start = Instant::now();
     let mft_index = match source {
-        MftSource::File(path, _) => load_mft_index_from_file(path, drive_letter, no_cache)?,
+        MftSource::File(path, _) => load_mft_index_from_file(path, volume, no_cache)?,
         #[cfg(windows)]
         MftSource::Live(ch) => load_mft_index_live(*ch, no_cache)?,
         #[cfg(windows)]
@@ -155,6 +159,8 @@ pub fn load_drive(
 
     // ── Build compact index ────────────────────────────────────────
     let (mut compact, compact_elapsed, tri_elapsed) = build_compact_index(drive_letter, &mft_index);
+    compact.host = volume.host();
+    compact.serial = volume.serial();
 
     // Log per-component heap footprint.
     compact.log_heap_report();
@@ -174,7 +180,7 @@ pub fn load_drive(
     if !no_cache && !source.is_ephemeral_device() {
         let t_compact_save = Instant::now();
         if let Err(err) = crate::compact_cache::save_compact_cache_background(&compact) {
-            tracing::warn!(drive = %drive_letter, error = %err, "Failed to start compact cache save");
+            tracing::warn!(drive = %volume, error = %err, "Failed to start compact cache save");
         }
         let compact_save_ms = t_compact_save.elapsed().as_millis();
         tracing::debug!(
@@ -340,20 +346,14 @@ fn parse_mft_file_to_index(
 
 /// Kick off the post-parse background cache save and emit a matching
 /// tracing line for success or failure.
-fn spawn_mft_cache_save(index: &MftIndex, drive_letter: uffs_mft::platform::DriveLetter) {
-    match uffs_mft::cache::save_to_cache_background(
-        index,
-        drive_letter,
-        0,
-        0,
-        uffs_mft::usn::Usn::ZERO,
-    ) {
+fn spawn_mft_cache_save(index: &MftIndex, volume: uffs_mft::platform::VolumeId) {
+    match uffs_mft::cache::save_to_cache_background(index, volume, 0, 0, uffs_mft::usn::Usn::ZERO) {
         Ok(()) => {
-            tracing::info!(drive = %drive_letter, "💾 MFT cache save started (background)");
+            tracing::info!(drive = %volume, "💾 MFT cache save started (background)");
         }
         Err(err) => {
             tracing::warn!(
-                drive = %drive_letter,
+                drive = %volume,
                 error = %err,
                 "Failed to start .uffs cache save"
             );
@@ -364,17 +364,17 @@ fn spawn_mft_cache_save(index: &MftIndex, drive_letter: uffs_mft::platform::Driv
 /// Load `MftIndex` from an offline file (cache → cold parse).
 fn load_mft_index_from_file(
     mft_path: &std::path::Path,
-    drive_letter: uffs_mft::platform::DriveLetter,
+    volume: uffs_mft::platform::VolumeId,
     no_cache: bool,
 ) -> anyhow::Result<MftIndex> {
     let cached = if no_cache {
         None
     } else {
-        uffs_mft::cache::load_cached_index(drive_letter, INDEX_TTL_SECONDS)
+        uffs_mft::cache::load_cached_index(volume, INDEX_TTL_SECONDS)
     };
     if let Some((cached_index, _header)) = cached {
         tracing::info!(
-            drive = %drive_letter,
+            drive = %volume,
             records = cached_index.records.len(),
             "📦 Cache hit — loaded .uffs cache"
         );
@@ -382,12 +382,12 @@ fn load_mft_index_from_file(
     }
 
     tracing::info!(
-        drive = %drive_letter,
+        drive = %volume,
         path = %mft_path.display(),
         "📖 Parsing MFT file (delegating to uffs-mft)"
     );
-    let parsed = parse_mft_file_to_index(mft_path, drive_letter)?;
-    spawn_mft_cache_save(&parsed, drive_letter);
+    let parsed = parse_mft_file_to_index(mft_path, volume.letter())?;
+    spawn_mft_cache_save(&parsed, volume);
     Ok(parsed)
 }
 
@@ -499,7 +499,7 @@ pub fn refresh_drive(drive: &DriveCompactIndex) -> anyhow::Result<(DriveCompactI
                     anyhow::bail!("Cannot refresh live drive {}: on non-Windows", drive.letter);
                 }
             } else {
-                MftSource::File(path.clone(), Some(drive.letter))
+                MftSource::File(path.clone(), Some(drive.volume()))
             };
             load_drive(&source, false)
         }
@@ -519,7 +519,13 @@ pub fn load_mft_file(
     drive: Option<uffs_mft::platform::DriveLetter>,
     no_cache: bool,
 ) -> anyhow::Result<(DriveCompactIndex, LoadTiming)> {
-    load_drive(&MftSource::File(mft_path.to_path_buf(), drive), no_cache)
+    load_drive(
+        &MftSource::File(
+            mft_path.to_path_buf(),
+            drive.map(uffs_mft::platform::VolumeId::local),
+        ),
+        no_cache,
+    )
 }
 
 /// Order a USN batch so that a created/renamed record is applied AFTER the
@@ -68,6 +68,8 @@ fn build_nested_fixture() -> DriveCompactIndex {
         .collect();
     let mut drive = DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records.clone()),
         names: ColumnStorage::from_vec(names.clone()),
         trigram: Arc::new(TrigramIndex::build(&records, &names, fold)),
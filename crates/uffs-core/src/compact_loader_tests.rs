@@ -101,6 +101,8 @@ pub(super) fn make_synthetic_drive() -> DriveCompactIndex {
 
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
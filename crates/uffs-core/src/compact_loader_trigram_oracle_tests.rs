@@ -110,6 +110,8 @@ fn build_drive() -> DriveCompactIndex {
     let ext_index = ExtensionIndex::build(&records);
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
@@ -257,6 +259,8 @@ fn build_nested_dir_fixture() -> DriveCompactIndex {
     let ext_index = ExtensionIndex::build(&records);
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
@@ -664,6 +664,8 @@ fn drive_with_full_ext_names_table() -> DriveCompactIndex {
 
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::C,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
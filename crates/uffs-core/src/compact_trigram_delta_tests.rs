@@ -63,6 +63,8 @@ fn build_drive(file_names: &[&str]) -> DriveCompactIndex {
     // Fields in struct-definition order (clippy::inconsistent_struct_constructor).
     DriveCompactIndex {
         letter: uffs_mft::platform::DriveLetter::T,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
@@ -167,3 +169,42 @@ fn short_needle_returns_none_like_base() {
     // delta present.
     assert!(drive.trigram_search("re").is_none());
 }
+
+/// Fuzzy candidates: records sharing at least `min_shared` of the needle's
+/// trigrams through the overlay.
+fn overlap(drive: &DriveCompactIndex, needle: &str, min_shared: usize) -> Vec<u32> {
+    let tris = needle_trigrams(needle, drive.fold).unwrap();
+    drive.trigram_overlap(&tris, min_shared)
+}
+
+#[test]
+fn overlap_counts_partial_trigram_matches() {
+    let drive = build_drive(&["receive_invoice.xlsx", "alpha.txt"]);
+    // The misspelling shares some but not all of its trigrams with idx 1.
+    assert_eq!(overlap(&drive, "recieve_invoce", 3), vec![1]);
+    assert_eq!(overlap(&drive, "recieve_invoce", 50), Vec::<u32>::new());
+}
+
+#[test]
+fn overlap_sees_renames_and_deletes_through_the_delta() {
+    let mut drive = build_drive(&["report.txt", "alpha.txt", "invoice.pdf"]);
+    let fold = drive.fold;
+    // Rename idx 1 "report.txt" -> "receipt.txt", delete idx 3.
+    let delta = drive.delta.get_or_insert_with(Default::default);
+    delta.tombstone(1);
+    delta.add_record(1, &needle_trigrams("receipt.txt", fold).unwrap(), 0, 0);
+    delta.tombstone(3);
+
+    assert_eq!(overlap(&drive, "reciept", 1), vec![1], "new name counted");
+    assert_eq!(
+        overlap(&drive, "reprot", 1),
+        Vec::<u32>::new(),
+        "stale base postings of the old name ignored"
+    );
+    assert_eq!(
+        overlap(&drive, "invoise", 1),
+        Vec::<u32>::new(),
+        "deleted record gone"
+    );
+    assert_eq!(overlap(&drive, "alpah", 1), vec![2], "sibling unaffected");
+}
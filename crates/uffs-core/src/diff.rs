@@ -94,9 +94,14 @@ type Meta = (u64, i64);
 /// such rows are excluded from both sides of the diff. Real files can never
 /// have `file_ref == 0`: that would require FRS 0 (the `$MFT` metafile itself),
 /// which is excluded from the compact index at build time.
+///
+/// A row the USN patch path deleted keeps its `file_ref` but is tombstoned
+/// (`name_len == 0`, parent unmapped); it no longer exists on the volume,
+/// so it is excluded too — otherwise a journal-patched current side would
+/// hide every delete since the index was built.
 #[inline]
 const fn is_real(rec: &CompactRecord) -> bool {
-    rec.file_ref != 0
+    rec.file_ref != 0 && !(rec.name_len == 0 && rec.parent_idx == u32::MAX)
 }
 
 /// Diff two compact record arrays, classifying every real row as added,
@@ -323,6 +328,24 @@ mod tests {
         assert_eq!(report.modified, vec![0], "same ref, changed mtime → modify");
     }
 
+    /// A journal-patched current side keeps a deleted row as a tombstone
+    /// (`name_len == 0`, parent unmapped) with its File Reference intact;
+    /// it must still count as deleted.
+    #[test]
+    fn usn_tombstone_in_current_is_classified_deleted() {
+        let baseline = [rec(10, 1, 100, 5), rec(11, 1, 200, 6)];
+        let tombstone = CompactRecord {
+            parent_idx: u32::MAX,
+            name_len: 0,
+            ..rec(11, 1, 200, 6)
+        };
+        let current = [rec(10, 1, 100, 5), tombstone];
+        let report = diff_records(&baseline, &current);
+        assert_eq!(report.deleted, vec![1], "the tombstoned row was deleted");
+        assert_eq!(report.added, Vec::<u32>::new());
+        assert_eq!(report.modified, Vec::<u32>::new());
+    }
+
     /// The anchor test: a delete-then-reuse of the *same MFT slot* bumps the
     /// sequence number. FRS-only keying would call this a "modify"; keying on
     /// the full File Reference makes it an exact delete + add.
@@ -466,6 +489,8 @@ mod tests {
         let ext_index = ExtensionIndex::build(&records);
         DriveCompactIndex {
             letter: uffs_mft::platform::DriveLetter::C,
+            host: None,
+            serial: None,
             records: ColumnStorage::from_vec(records),
             names: ColumnStorage::from_vec(names),
             trigram: Arc::new(trigram),
@@ -15,6 +15,7 @@ use core::fmt::Write as _;
 use std::io::Write;
 
 use uffs_polars::{Column, DataFrame, DataType};
+use uffs_time::TimeZone;
 
 use super::{BASELINE_COLUMN_ORDER, OutputColumn};
 use crate::error::Result;
@@ -34,11 +35,12 @@ pub struct OutputConfig {
     pub pos: String,
     /// Representation for false/inactive boolean (default: "0").
     pub neg: String,
-    /// Fixed timezone offset in seconds from UTC (computed once at startup).
-    /// This matches established behavior where Windows'
-    /// `FileTimeToLocalFileTime()` uses the CURRENT timezone offset for ALL
-    /// timestamps, ignoring historical DST.
-    pub timezone_offset_secs: i32,
+    /// Zone for timestamp display.  Defaults to the fixed local offset
+    /// (computed once at startup), matching established behavior where
+    /// Windows' `FileTimeToLocalFileTime()` uses the CURRENT timezone offset
+    /// for ALL timestamps, ignoring historical DST.  A named zone (`--tz`)
+    /// resolves DST per timestamp.
+    pub time_zone: TimeZone,
     /// Parity-compat mode: directories get trailing `\` in `Path`,
     /// empty `Name`, self-path in `PathOnly`, and treesize for `Size`.
     pub parity_compat: bool,
@@ -51,7 +53,7 @@ impl Default for OutputConfig {
     fn default() -> Self {
         // Get current timezone offset once. On Windows,
         // Windows' FileTimeToLocalFileTime() uses the CURRENT offset for all timestamps
-        let timezone_offset_secs = chrono::Local::now().offset().local_minus_utc();
+        let time_zone = TimeZone::fixed(chrono::Local::now().offset().local_minus_utc());
 
         Self {
             columns: None,
@@ -60,7 +62,7 @@ impl Default for OutputConfig {
             header: true,
             pos: "1".to_owned(),
             neg: "0".to_owned(),
-            timezone_offset_secs,
+            time_zone,
             parity_compat: false,
         }
     }
@@ -173,7 +175,15 @@ impl OutputConfig {
     /// generated in a different DST period than the current one.
     #[must_use]
     pub const fn with_tz_offset_hours(mut self, hours: i32) -> Self {
-        self.timezone_offset_secs = hours * 3_600_i32;
+        self.time_zone = TimeZone::fixed(hours * 3_600_i32);
+        self
+    }
+
+    /// Override the zone used for timestamp display — a fixed offset or a
+    /// named IANA zone whose DST rules apply per timestamp.
+    #[must_use]
+    pub const fn with_time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
         self
     }
 
@@ -245,8 +255,6 @@ impl OutputConfig {
             BASELINE_COLUMN_ORDER
         };
 
-        let fixed_tz = chrono::FixedOffset::east_opt(self.timezone_offset_secs);
-
         let resolved_columns: Vec<_> = output_cols
             .iter()
             .map(|col| {
@@ -289,7 +297,7 @@ impl OutputConfig {
 
                 match resolved_column {
                     Ok(series) => {
-                        self.write_value(&mut row_buffer, series, row_idx, fixed_tz.as_ref());
+                        self.write_value(&mut row_buffer, series, row_idx, self.time_zone);
                     }
                     Err(default_value) => {
                         // Column not in DataFrame - use appropriate default.
@@ -342,7 +350,7 @@ impl OutputConfig {
         row_buffer: &mut String,
         series: &Column,
         row_idx: usize,
-        fixed_tz: Option<&chrono::FixedOffset>,
+        time_zone: TimeZone,
     ) {
         use uffs_polars::{AnyValue, TimeUnit};
 
@@ -416,7 +424,7 @@ impl OutputConfig {
                 }
             }
             DataType::Datetime(TimeUnit::Microseconds, _) => {
-                Self::append_filetime_value(row_buffer, series, row_idx, fixed_tz);
+                Self::append_filetime_value(row_buffer, series, row_idx, time_zone);
             }
             _ => {
                 if let Ok(val) = series.get(row_idx)
@@ -464,7 +472,7 @@ impl OutputConfig {
         row_buffer: &mut String,
         series: &Column,
         row_idx: usize,
-        fixed_tz: Option<&chrono::FixedOffset>,
+        time_zone: TimeZone,
     ) {
         use uffs_polars::{AnyValue, TimeUnit};
 
@@ -476,8 +484,7 @@ impl OutputConfig {
             _ => None,
         };
         let Some(filetime) = filetime_opt else { return };
-        let tz_offset_secs: i32 = fixed_tz.map_or(0_i32, chrono::FixedOffset::local_minus_utc);
-        let local_ft = uffs_time::filetime_with_tz_bias(filetime, tz_offset_secs);
+        let local_ft = time_zone.to_local(filetime);
         if let Some(uffs_time::CalendarParts {
             year,
             month,
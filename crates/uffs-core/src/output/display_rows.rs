@@ -21,6 +21,7 @@ use core::fmt::Write as _;
 use std::io::Write;
 
 use rayon::prelude::*;
+use uffs_time::TimeZone;
 
 use super::{BASELINE_COLUMN_ORDER, OutputColumn, OutputConfig};
 use crate::error::Result;
@@ -270,13 +271,13 @@ pub(crate) fn write_display_row_columns(
                 }
             }
             OutputColumn::Created => {
-                append_datetime_native(buf, row.created, cfg.timezone_offset_secs);
+                append_datetime_native(buf, row.created, cfg.time_zone);
             }
             OutputColumn::Modified => {
-                append_datetime_native(buf, row.modified, cfg.timezone_offset_secs);
+                append_datetime_native(buf, row.modified, cfg.time_zone);
             }
             OutputColumn::Accessed => {
-                append_datetime_native(buf, row.accessed, cfg.timezone_offset_secs);
+                append_datetime_native(buf, row.accessed, cfg.time_zone);
             }
             OutputColumn::Descendants => {
                 buf.push_str(itoa_buf.format(row.descendants));
@@ -327,7 +328,12 @@ pub(crate) fn write_display_row_columns(
                 buf.push_str(itoa_buf.format(pct));
             }
             OutputColumn::Drive => {
-                buf.push(row.drive.as_char());
+                buf.push(row.drive.letter().as_char());
+            }
+            OutputColumn::Host => {
+                buf.push_str(&cfg.quote);
+                buf.push_str(row.drive.host_str());
+                buf.push_str(&cfg.quote);
             }
             OutputColumn::Extension => {
                 buf.push_str(&cfg.quote);
@@ -363,6 +369,20 @@ pub(crate) fn write_display_row_columns(
                 }
                 buf.push_str(&cfg.quote);
             }
+            OutputColumn::Hash => {
+                buf.push_str(&cfg.quote);
+                if let Some(digest) = row.content_hash.as_deref() {
+                    buf.push_str(digest);
+                }
+                buf.push_str(&cfg.quote);
+            }
+            OutputColumn::MatchedPattern => {
+                buf.push_str(&cfg.quote);
+                if let Some(pattern) = row.matched_pattern.as_deref() {
+                    buf.push_str(pattern);
+                }
+                buf.push_str(&cfg.quote);
+            }
         }
     }
 }
@@ -396,15 +416,15 @@ pub(crate) fn push_bool(buf: &mut String, cfg: &OutputConfig, value: bool) {
 /// microseconds and produced year-6220 output for 2026-era timestamps (the
 /// ~369-year + 10× unit offset between the two encodings).
 ///
-/// Delegates to `uffs_time::filetime_with_tz_bias` + `filetime_to_calendar`
+/// Delegates to `uffs_time::TimeZone::to_local` + `filetime_to_calendar`
 /// for the canonical Hinnant civil-calendar decomposition — same helpers
 /// used by the parity-compat CSV writer in
 /// `uffs_cli::commands::output::parity::append_datetime_tz`.
 ///
 /// Regression-pinned by `append_datetime_native_*` in the `tests`
 /// submodule at `display_rows_tests.rs`.
-pub(crate) fn append_datetime_native(buf: &mut String, filetime: i64, tz_offset_secs: i32) {
-    let local_ft = uffs_time::filetime_with_tz_bias(filetime, tz_offset_secs);
+pub(crate) fn append_datetime_native(buf: &mut String, filetime: i64, time_zone: TimeZone) {
+    let local_ft = time_zone.to_local(filetime);
     if let Some(uffs_time::CalendarParts {
         year,
         month,
@@ -75,5 +75,8 @@ pub const fn field_id_to_format_column(col: OutputColumn) -> FmtColumn {
         OutputColumn::Malformed => FmtColumn::Malformed,
         OutputColumn::MalformedPath => FmtColumn::MalformedPath,
         OutputColumn::NameHex => FmtColumn::NameHex,
+        OutputColumn::Host => FmtColumn::Host,
+        OutputColumn::Hash => FmtColumn::Hash,
+        OutputColumn::MatchedPattern => FmtColumn::MatchedPattern,
     }
 }
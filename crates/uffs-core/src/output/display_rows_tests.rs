@@ -11,6 +11,8 @@
 //! resolves against the `display_rows` module and keeps tests out of
 //! the public API surface.
 
+use uffs_time::TimeZone;
+
 use super::append_datetime_native;
 
 /// Regression: `append_datetime_native` must interpret its `filetime`
@@ -30,7 +32,7 @@ fn append_datetime_native_formats_filetime_as_2024() {
     // lets any future divergence surface across the whole suite.
     let ft_2024: i64 = 133_485_408_000_000_000;
     let mut buf = String::new();
-    append_datetime_native(&mut buf, ft_2024, 0);
+    append_datetime_native(&mut buf, ft_2024, TimeZone::UTC);
     assert_eq!(
         buf, "2024-01-01 00:00:00",
         "v13+ stores timestamps as raw FILETIME — the output writer \
@@ -54,7 +56,7 @@ fn append_datetime_native_never_emits_year_6220() {
     let ft_2026 =
         unix_secs_2026_01_20 * uffs_time::FILETIME_TICKS_PER_SECOND + uffs_time::FILETIME_UNIX_DIFF;
     let mut buf = String::new();
-    append_datetime_native(&mut buf, ft_2026, 0);
+    append_datetime_native(&mut buf, ft_2026, TimeZone::UTC);
     assert!(
         !buf.starts_with("6220"),
         "FILETIME-as-Unix-micros regression: formatter emitted '{buf}' \
@@ -75,7 +77,7 @@ fn append_datetime_native_never_emits_year_6220() {
 #[test]
 fn append_datetime_native_zero_filetime_is_zero_sentinel() {
     let mut buf = String::new();
-    append_datetime_native(&mut buf, 0, 0);
+    append_datetime_native(&mut buf, 0, TimeZone::UTC);
     assert_eq!(buf, "0000-00-00 00:00:00");
 }
 
@@ -88,7 +90,7 @@ fn append_datetime_native_tz_bias_pst() {
     let ft_2024: i64 = 133_485_408_000_000_000; // 2024-01-01 00:00:00 UTC
     let pst_offset: i32 = -8 * 3600; // UTC-8
     let mut buf = String::new();
-    append_datetime_native(&mut buf, ft_2024, pst_offset);
+    append_datetime_native(&mut buf, ft_2024, TimeZone::fixed(pst_offset));
     assert_eq!(
         buf, "2023-12-31 16:00:00",
         "tz bias must be applied before calendar decomposition"
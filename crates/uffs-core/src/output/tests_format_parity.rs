@@ -49,7 +49,7 @@ fn assert_format_parity(
         header: config.header,
         pos: config.pos.clone(),
         neg: config.neg.clone(),
-        timezone_offset_secs: config.timezone_offset_secs,
+        time_zone: config.time_zone,
         parity_compat: config.parity_compat,
     };
     let mut shared = Vec::new();
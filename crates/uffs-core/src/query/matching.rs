@@ -9,6 +9,7 @@ use super::MftQuery;
 use crate::compiled_pattern::compile_pattern;
 use crate::error::Result;
 use crate::glob::glob_to_regex;
+use crate::search::pattern_set::PatternSet;
 
 impl MftQuery {
     // =========================================================================
@@ -78,6 +79,25 @@ impl MftQuery {
         })
     }
 
+    /// Keep names on a `--patterns-from` list, case-insensitively.
+    ///
+    /// Bare names run as one `is_in` lookup and `*needle*` entries as one
+    /// `contains_any` automaton (see [`PatternSet::compiled`]).
+    #[must_use]
+    pub fn pattern_set(self, set: &PatternSet) -> Self {
+        let expr = set
+            .compiled()
+            .iter()
+            .map(|pattern| pattern.to_expr("name", false))
+            .reduce(Expr::or);
+        match expr {
+            Some(filter_expr) => Self {
+                lazy: self.lazy.filter(filter_expr),
+            },
+            None => self,
+        }
+    }
+
     /// Match files containing exact substring (fastest).
     #[must_use]
     pub fn contains(self, substring: &str) -> Self {
@@ -105,6 +105,26 @@ fn pattern_suffix() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn pattern_set_lowers_to_set_kernels() -> TestResult {
+    use crate::compiled_pattern::CompiledPattern;
+    use crate::search::pattern_set::PatternSet;
+
+    let set = PatternSet::new(&["README.md", "*FIG*", "*.rs", "doc*.txt"])?;
+    assert!(matches!(set.compiled(), [
+        CompiledPattern::ExactSet(_),
+        CompiledPattern::ContainsAny(_),
+        CompiledPattern::SuffixSet(_),
+        CompiledPattern::PrefixSuffix { .. }
+    ]));
+    let result = MftQuery::new(create_pattern_test_df()?)
+        .pattern_set(&set)
+        .collect()?;
+    // readme.md, config.json, main.rs, test.rs, document.txt
+    assert_eq!(result.height(), 5);
+    Ok(())
+}
+
 #[test]
 fn pattern_prefix() -> TestResult {
     use crate::pattern::ParsedPattern;
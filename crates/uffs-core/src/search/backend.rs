@@ -9,14 +9,16 @@
 //! crates rely on (`uffs_core::search::backend::DisplayRow`) is preserved.
 
 use alloc::sync::Arc;
+use core::hash::{Hash as _, Hasher as _};
 use std::time::Instant;
 
-use rayon::prelude::*;
-
+use super::cursor::{CursorPage, PageOrder, SearchCursor, cut_page};
 use super::dispatch::{
-    apply_dispatch_safety_nets, dispatch_match_all, dispatch_regex, dispatch_trigram_or_tree,
-    pick_mode_label,
+    apply_dispatch_safety_nets, dispatch_fuzzy, dispatch_match_all, dispatch_regex,
+    dispatch_trigram_or_tree, pick_mode_label, take_fuzzy_prefix,
 };
+use super::query::rank_top_n;
+use super::rank::{RankCut, RankNeedle};
 use crate::compact::DriveCompactIndex;
 use crate::search::field::FieldId;
 
@@ -117,6 +119,10 @@ pub struct SortSpec {
 /// CLI, tests) use one consistent API and `search` stays under the
 /// `clippy::too_many_arguments` threshold.
 #[derive(Debug)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "independent per-query switches mirroring the `SearchParams` wire flags"
+)]
 pub struct SearchRequest<'a> {
     /// The search pattern (glob, substring, regex with `>` prefix, or `*`).
     pub pattern: &'a str,
@@ -132,9 +138,17 @@ pub struct SearchRequest<'a> {
     pub filter_mode: FilterMode,
     /// Mutable search filters (extensions, dates, size, etc.).
     pub search_filters: &'a mut super::filters::SearchFilters,
-    /// Drive-letter filter: only search drives whose letter is in this
-    /// slice.  An empty slice means "search all loaded drives".
-    pub drives_filter: &'a [uffs_mft::platform::DriveLetter],
+    /// Volume filter: only search drives selected by an entry of this
+    /// slice (see [`uffs_mft::platform::VolumeId::selects`]).  An empty
+    /// slice means "search all loaded drives".
+    pub drives_filter: &'a [uffs_mft::platform::VolumeId],
+    /// Fuzzy mode threshold in `[0, 1]` (`None` = exact matching).  A
+    /// `fuzzy:` pattern prefix turns this on at
+    /// [`super::fuzzy::DEFAULT_THRESHOLD`]; see [`super::fuzzy`].
+    pub fuzzy: Option<f32>,
+    /// Order hits by relevance instead of the sort column (`--rank`); see
+    /// [`super::rank`].  Ignored for match-all (`*`) and fuzzy queries.
+    pub rank: bool,
 }
 
 impl<'a> SearchRequest<'a> {
@@ -155,6 +169,8 @@ impl<'a> SearchRequest<'a> {
             filter_mode: FilterMode::All,
             search_filters,
             drives_filter: &[],
+            fuzzy: None,
+            rank: false,
         }
     }
 }
@@ -257,8 +273,8 @@ impl MultiDriveBackend {
     /// backend's current `sort_column` / `sort_desc`, then truncated to
     /// `result_limit`.
     ///
-    /// When `drives_filter` is non-empty, only drives whose letter is in
-    /// the slice are searched.
+    /// When `drives_filter` is non-empty, only drives selected by an
+    /// entry of the slice are searched.
     #[expect(
         clippy::too_many_lines,
         reason = "search dispatch with three modes and a drive filter"
@@ -276,10 +292,12 @@ impl MultiDriveBackend {
             filter_mode,
             search_filters,
             drives_filter,
+            mut fuzzy,
+            rank,
         } = req;
 
         let start = Instant::now();
-        let mut rows = Vec::new();
+        let mut rows: Vec<DisplayRow>;
         let mut phase_timings: Option<PhaseTimings> = None;
 
         if pattern.is_empty() {
@@ -297,7 +315,8 @@ impl MultiDriveBackend {
         // docs for the composition rules.  `drive_from_prefix` owns the
         // single-element vec so the borrow in `effective_drives_filter`
         // stays valid through the stash-and-partition block below.
-        let mut drive_from_prefix: Vec<uffs_mft::platform::DriveLetter> = Vec::new();
+        take_fuzzy_prefix(&mut pattern, &mut fuzzy);
+        let mut drive_from_prefix: Vec<uffs_mft::platform::VolumeId> = Vec::new();
         apply_dispatch_safety_nets(
             &mut pattern,
             match_path,
@@ -306,7 +325,7 @@ impl MultiDriveBackend {
             search_filters,
             &mut drive_from_prefix,
         );
-        let effective_drives_filter: &[uffs_mft::platform::DriveLetter] =
+        let effective_drives_filter: &[uffs_mft::platform::VolumeId] =
             if drive_from_prefix.is_empty() {
                 drives_filter
             } else {
@@ -322,7 +341,7 @@ impl MultiDriveBackend {
             let all = core::mem::take(&mut self.drives);
             let (keep, rest): (Vec<_>, Vec<_>) = all
                 .into_iter()
-                .partition(|dr| effective_drives_filter.contains(&dr.letter));
+                .partition(|dr| selects_drive(effective_drives_filter, dr));
             self.drives = keep;
             Some(rest)
         };
@@ -365,115 +384,69 @@ impl MultiDriveBackend {
             if search_filters.needs_display_row_filter() {
                 super::filters::apply_search_filters(&mut rows, search_filters);
             }
-        } else if is_regex {
-            let regex_pattern = needle.strip_prefix('>').unwrap_or(&needle);
-            match regex::RegexBuilder::new(regex_pattern)
-                .case_insensitive(!case_sensitive)
-                .build()
-            {
-                Ok(compiled_re) => {
-                    // Immutable reborrow so the parallel per-drive closure is
-                    // `Sync` (a captured `&mut` is not). The record-level
-                    // filters are applied inside each scan, before its limit.
-                    let sf: &super::filters::SearchFilters = search_filters;
-                    let drive_results: Vec<Vec<DisplayRow>> = self
-                        .drives
-                        .par_iter()
-                        .map(|drive| {
-                            super::query::search_compact_drive_regex(drive, &compiled_re, limit, sf)
-                        })
-                        .collect();
-                    for drive_rows in drive_results {
-                        rows.extend(drive_rows);
-                    }
-                    super::filters::apply_filter(&mut rows, filter_mode);
-                    super::filters::apply_search_filters(&mut rows, search_filters);
-                    sort_rows(
-                        &mut rows,
-                        self.sort_column,
-                        self.sort_desc,
-                        &self.extra_sort_tiers,
-                    );
-                    rows.truncate(limit);
-                }
-                Err(_err) => {
-                    // Restore stashed drives before returning.
-                    if let Some(rest) = stashed_drives {
-                        self.drives.extend(rest);
-                    }
-                    self.last_results.clear();
-                    return SearchResult {
-                        rows: Vec::new(),
-                        duration: start.elapsed(),
-                        records_scanned: 0,
-                        phase_timings: None,
-                    };
-                }
-            }
-        } else if is_prefix {
-            // Trigram-accelerated prefix scan (`win*`). `is_prefix` already
-            // proved `is_prefix_pattern` holds, so the strip is infallible.
-            if let Some(prefix) = crate::search::tree::is_prefix_pattern(&needle) {
-                let sf: &super::filters::SearchFilters = search_filters;
-                let drive_results: Vec<Vec<DisplayRow>> = self
-                    .drives
-                    .par_iter()
-                    .map(|drive| {
-                        super::query::search_compact_drive_prefix(
-                            drive,
-                            prefix,
-                            limit,
-                            case_sensitive,
-                            sf,
-                        )
-                    })
-                    .collect();
-                for drive_rows in drive_results {
-                    rows.extend(drive_rows);
-                }
-                super::filters::apply_filter(&mut rows, filter_mode);
-                super::filters::apply_search_filters(&mut rows, search_filters);
-                sort_rows(
-                    &mut rows,
+        } else if let Some(threshold) = fuzzy {
+            let drives: Vec<&DriveCompactIndex> = self.drives.iter().collect();
+            rows = dispatch_fuzzy(
+                &drives,
+                pattern,
+                threshold,
+                limit,
+                filter_mode,
+                search_filters,
+            );
+        } else {
+            let drives: Vec<&DriveCompactIndex> = self.drives.iter().collect();
+            let scan_limit = if rank { UNLIMITED } else { limit };
+            let rank_needle = rank.then(|| RankNeedle::new(pattern, fold));
+            search_filters.rank =
+                RankCut::for_scan(rank_needle.as_ref(), result_limit, filter_mode);
+            let maybe_hits = if is_regex {
+                dispatch_regex(
+                    &drives,
+                    &needle,
+                    case_sensitive,
+                    scan_limit,
+                    filter_mode,
+                    search_filters,
                     self.sort_column,
                     self.sort_desc,
                     &self.extra_sort_tiers,
-                );
-                rows.truncate(limit);
-            }
-        } else {
-            let sf: &super::filters::SearchFilters = search_filters;
-            let drive_results: Vec<Vec<DisplayRow>> = self
-                .drives
-                .par_iter()
-                .map(|drive| {
-                    if is_path {
-                        super::query::search_compact_drive_tree(drive, &needle, limit, sf)
-                    } else {
-                        super::query::search_compact_drive(
-                            drive,
-                            &needle,
-                            limit,
-                            case_sensitive,
-                            whole_word,
-                            match_path,
-                            sf,
-                        )
-                    }
-                })
-                .collect();
-            for drive_rows in drive_results {
-                rows.extend(drive_rows);
-            }
-            super::filters::apply_filter(&mut rows, filter_mode);
-            super::filters::apply_search_filters(&mut rows, search_filters);
-            sort_rows(
-                &mut rows,
-                self.sort_column,
-                self.sort_desc,
-                &self.extra_sort_tiers,
-            );
-            rows.truncate(limit);
+                )
+            } else {
+                Some(dispatch_trigram_or_tree(
+                    &drives,
+                    &needle,
+                    is_path,
+                    is_prefix,
+                    case_sensitive,
+                    whole_word,
+                    match_path,
+                    scan_limit,
+                    filter_mode,
+                    search_filters,
+                    self.sort_column,
+                    self.sort_desc,
+                    &self.extra_sort_tiers,
+                ))
+            };
+            search_filters.rank = None;
+            let Some(hits) = maybe_hits else {
+                // Bad regex: restore stashed drives before returning.
+                if let Some(rest) = stashed_drives {
+                    self.drives.extend(rest);
+                }
+                self.last_results.clear();
+                return SearchResult {
+                    rows: Vec::new(),
+                    duration: start.elapsed(),
+                    records_scanned: 0,
+                    phase_timings: None,
+                };
+            };
+            rows = match &rank_needle {
+                Some(ranker) => rank_top_n(hits, ranker, limit),
+                None => hits,
+            };
         }
 
         let scanned = self.drives.iter().map(|dr| dr.records.len()).sum();
@@ -484,17 +457,7 @@ impl MultiDriveBackend {
         }
         let wall_ms = start.elapsed().as_millis();
 
-        let mode = if is_match_all {
-            "match-all"
-        } else if is_regex {
-            "regex"
-        } else if is_path {
-            "tree"
-        } else if is_prefix {
-            "prefix"
-        } else {
-            "trigram"
-        };
+        let mode = pick_mode_label(is_match_all, fuzzy.is_some(), is_regex, is_path, is_prefix);
         tracing::debug!(
             target: "cache_profile",
             wall_ms = %wall_ms,
@@ -547,6 +510,47 @@ impl MultiDriveBackend {
         sort_rows(&mut self.last_results, self.sort_column, self.sort_desc, &[
         ]);
     }
+
+    /// Fingerprint of the loaded drives, used as the index version of
+    /// [`SearchCursor`]s cut by [`Self::page`].  Changes when a drive is
+    /// loaded, dropped, rebuilt or patched with new records.
+    #[must_use]
+    pub fn index_version(&self) -> u64 {
+        let mut hasher = std::hash::DefaultHasher::new();
+        for drive in &self.drives {
+            (drive.volume(), drive.source_epoch, drive.records.len()).hash(&mut hasher);
+            drive
+                .delta
+                .as_ref()
+                .map(crate::compact::IndexDelta::len)
+                .hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// One page of [`Self::last_results`]: the rows after `cursor` (the
+    /// first page when `None`) in keyset order, plus the cursor for the
+    /// page after.
+    #[must_use]
+    pub fn page(
+        &self,
+        cursor: Option<&SearchCursor>,
+        page_size: usize,
+    ) -> (Vec<DisplayRow>, CursorPage) {
+        let mut rows = self.last_results.clone();
+        let page = cut_page(
+            &mut rows,
+            cursor,
+            PageOrder::Column {
+                column: self.sort_column,
+                descending: self.sort_desc,
+                tiers: &self.extra_sort_tiers,
+            },
+            page_size,
+            self.index_version(),
+        );
+        (rows, page)
+    }
 }
 
 // ── Free-function search for concurrent access ───────────────────────
@@ -582,6 +586,8 @@ pub fn search_index(
         filter_mode,
         search_filters,
         drives_filter,
+        mut fuzzy,
+        rank,
     } = req;
 
     let start = Instant::now();
@@ -600,7 +606,8 @@ pub fn search_index(
     // docs for the composition rules.  `drive_from_prefix` owns the
     // single-element vec so the borrow in `effective_drives_filter`
     // stays valid for the rest of the function.
-    let mut drive_from_prefix: Vec<uffs_mft::platform::DriveLetter> = Vec::new();
+    take_fuzzy_prefix(&mut pattern, &mut fuzzy);
+    let mut drive_from_prefix: Vec<uffs_mft::platform::VolumeId> = Vec::new();
     apply_dispatch_safety_nets(
         &mut pattern,
         match_path,
@@ -609,19 +616,18 @@ pub fn search_index(
         search_filters,
         &mut drive_from_prefix,
     );
-    let effective_drives_filter: &[uffs_mft::platform::DriveLetter] =
-        if drive_from_prefix.is_empty() {
-            drives_filter
-        } else {
-            &drive_from_prefix
-        };
+    let effective_drives_filter: &[uffs_mft::platform::VolumeId] = if drive_from_prefix.is_empty() {
+        drives_filter
+    } else {
+        &drive_from_prefix
+    };
 
     // Filter drives without mutation — just skip non-matching ones.
     let mut active_drives: Vec<&DriveCompactIndex> = index
         .drives
         .iter()
         .filter(|dr| {
-            effective_drives_filter.is_empty() || effective_drives_filter.contains(&dr.letter)
+            effective_drives_filter.is_empty() || selects_drive(effective_drives_filter, dr)
         })
         .map(Arc::as_ref)
         .collect();
@@ -633,7 +639,7 @@ pub fn search_index(
     // correctness contract: only ext filters drive the skip
     // decision; substring queries never bloom-skip (no false
     // negatives possible).
-    apply_bloom_pre_check(&mut active_drives, &search_filters.extensions);
+    super::bloom_skip::apply_pre_check(&mut active_drives, &search_filters.extensions);
 
     let is_match_all = pattern == "*";
     let is_regex = pattern.starts_with('>') && pattern.len() > 1;
@@ -646,6 +652,9 @@ pub fn search_index(
             drive.fold
         });
     let needle = super::dispatch::fold_needle(case_sensitive, pattern, fold);
+    // Scan in volume order: the top-N heap breaks key ties by drive index,
+    // which must agree with the volume tiebreaker of a keyset page.
+    active_drives.sort_by_key(|drive| drive.volume());
     let is_path = !is_match_all && !is_regex && crate::search::tree::is_path_pattern(&needle);
     let is_prefix = !is_match_all
         && !is_regex
@@ -663,7 +672,14 @@ pub fn search_index(
         "[1] search_index entry"
     );
 
-    let (rows, phase_timings): (Vec<DisplayRow>, Option<PhaseTimings>) = if is_match_all {
+    // Rank mode matches without the limit; each drive scores its hits and
+    // resolves only its best `limit` (the `RankCut`), and the merge below
+    // keeps the best overall.  Match-all and fuzzy pick their own top-N.
+    let is_ranked = rank && !is_match_all && fuzzy.is_none();
+    let scan_limit = if is_ranked { UNLIMITED } else { limit };
+    let rank_needle = is_ranked.then(|| RankNeedle::new(pattern, fold));
+    search_filters.rank = RankCut::for_scan(rank_needle.as_ref(), result_limit, filter_mode);
+    let (hits, phase_timings): (Vec<DisplayRow>, Option<PhaseTimings>) = if is_match_all {
         dispatch_match_all(
             &active_drives,
             limit,
@@ -672,18 +688,31 @@ pub fn search_index(
             filter_mode,
             search_filters,
         )
+    } else if let Some(threshold) = fuzzy {
+        (
+            dispatch_fuzzy(
+                &active_drives,
+                pattern,
+                threshold,
+                limit,
+                filter_mode,
+                search_filters,
+            ),
+            None,
+        )
     } else if is_regex {
         let Some(regex_rows) = dispatch_regex(
             &active_drives,
             &needle,
             case_sensitive,
-            limit,
+            scan_limit,
             filter_mode,
             search_filters,
             sort_column,
             sort_desc,
             extra_sort_tiers,
         ) else {
+            search_filters.rank = None;
             return SearchResult {
                 rows: Vec::new(),
                 duration: start.elapsed(),
@@ -702,7 +731,7 @@ pub fn search_index(
                 case_sensitive,
                 whole_word,
                 match_path,
-                limit,
+                scan_limit,
                 filter_mode,
                 search_filters,
                 sort_column,
@@ -713,9 +742,15 @@ pub fn search_index(
         )
     };
 
+    search_filters.rank = None;
+    let rows = match &rank_needle {
+        Some(ranker) => rank_top_n(hits, ranker, limit),
+        None => hits,
+    };
+
     let scanned = active_drives.iter().map(|dr| dr.records.len()).sum();
     let wall_ms = start.elapsed().as_millis();
-    let mode = pick_mode_label(is_match_all, is_regex, is_path, is_prefix);
+    let mode = pick_mode_label(is_match_all, fuzzy.is_some(), is_regex, is_path, is_prefix);
     tracing::debug!(
         target: "cache_profile",
         wall_ms = %wall_ms,
@@ -736,46 +771,16 @@ pub fn search_index(
 // ── Dispatch helpers ───────────────────────────────────────────────────
 // The pattern-rewrite safety nets (`apply_dispatch_safety_nets` and its
 // internal helpers) plus the three per-branch dispatch functions
-// (`dispatch_match_all`, `dispatch_regex`, `dispatch_trigram_or_tree`)
-// and the `pick_mode_label` tracing helper live in `dispatch.rs`,
-// extracted for the 800-LOC file-size policy.  Imported at the top of
-// this file.
-
-/// Phase 4 Commit F — drop drives whose bloom misses the ext
-/// filter from the active subset.
-///
-/// Mutates `active_drives` in place.  Emits one
-/// `shard.bloom.decision` tracing event per drive (skip or keep)
-/// so operators can audit the skip rate and false-positive rate.
-///
-/// `ext_terms` is the lowercase-no-dot extension list as held in
-/// [`crate::search::filters::SearchFilters::extensions`].  Empty
-/// `ext_terms` short-circuits to a no-op via
-/// [`crate::search::bloom_skip::decide_for_ext_filter`]'s
-/// `Keep`-on-empty contract.
-#[expect(
-    clippy::single_call_fn,
-    reason = "extracted from search_index for readability — the inline body \
-              would push the cognitive-complexity lint past its budget"
-)]
-fn apply_bloom_pre_check(active_drives: &mut Vec<&DriveCompactIndex>, ext_terms: &[String]) {
-    if ext_terms.is_empty() {
-        return;
-    }
-    active_drives.retain(|drive| {
-        let decision =
-            crate::search::bloom_skip::decide_for_ext_filter(drive.bloom.as_ref(), ext_terms);
-        let matched = decision.keep();
-        tracing::debug!(
-            target: "shard.bloom.decision",
-            drive = %drive.letter,
-            r#match = matched,
-            terms = ?ext_terms,
-            source = "search_dispatch",
-            "bloom pre-check"
-        );
-        matched
-    });
+// (`dispatch_match_all`, `dispatch_regex`, `dispatch_fuzzy`,
+// `dispatch_trigram_or_tree`) and the `pick_mode_label` tracing helper
+// live in `dispatch.rs`, extracted for the 800-LOC file-size policy.
+// Imported at the top of this file.  The bloom pre-check lives beside
+// its decision function in `bloom_skip.rs`.
+
+/// Whether any selector in `filter` selects `drive`'s volume.
+fn selects_drive(filter: &[uffs_mft::platform::VolumeId], drive: &DriveCompactIndex) -> bool {
+    let volume = drive.volume();
+    filter.iter().any(|sel| sel.selects(volume))
 }
 
 // ── Sorting & DataFrame conversion ─────────────────────────────────────
@@ -271,7 +271,7 @@ fn sort_by_drive_ascending() {
     ];
     sort_rows(&mut rows, SortColumn::Drive, false, &[]);
     assert_eq!(
-        rows.first().expect("first").drive,
+        rows.first().expect("first").drive.letter(),
         uffs_mft::platform::DriveLetter::C
     );
 }
@@ -415,7 +415,7 @@ fn multi_drive_merges_results_from_both_drives() {
     let mut filters = super::super::filters::SearchFilters::default();
     let result = backend.search(SearchRequest::new("*", &mut filters));
     let drives: std::collections::HashSet<uffs_mft::platform::DriveLetter> =
-        result.rows.iter().map(|row| row.drive).collect();
+        result.rows.iter().map(|row| row.drive.letter()).collect();
     assert!(
         drives.contains(&uffs_mft::platform::DriveLetter::C),
         "must include drive C results"
@@ -443,7 +443,7 @@ fn multi_drive_sort_across_drives() {
         "data.csv",
         "largest file across drives must be first"
     );
-    assert_eq!(first.drive, uffs_mft::platform::DriveLetter::D);
+    assert_eq!(first.drive.letter(), uffs_mft::platform::DriveLetter::D);
 }
 
 #[test]
@@ -719,7 +719,7 @@ fn search_index_returns_results_from_both_drives() {
         &[],
     );
     let drives: std::collections::HashSet<uffs_mft::platform::DriveLetter> =
-        result.rows.iter().map(|row| row.drive).collect();
+        result.rows.iter().map(|row| row.drive.letter()).collect();
     assert!(
         drives.contains(&uffs_mft::platform::DriveLetter::C),
         "must include C: results"
@@ -737,7 +737,9 @@ fn search_index_drives_filter_excludes_non_matching() {
     let result = search_index(
         &index,
         SearchRequest {
-            drives_filter: &[uffs_mft::platform::DriveLetter::C],
+            drives_filter: &[uffs_mft::platform::VolumeId::local(
+                uffs_mft::platform::DriveLetter::C,
+            )],
             ..SearchRequest::new("*", &mut filters)
         },
         FieldId::Modified,
@@ -748,12 +750,364 @@ fn search_index_drives_filter_excludes_non_matching() {
         result
             .rows
             .iter()
-            .all(|row| row.drive == uffs_mft::platform::DriveLetter::C),
+            .all(|row| row.drive.letter() == uffs_mft::platform::DriveLetter::C),
         "drive filter must exclude D: results"
     );
     assert!(!result.rows.is_empty(), "must have at least one C: result");
 }
 
+// ── Fuzzy mode ──────────────────────────────────────────────────────
+
+/// One drive (C:) holding `file_names` as files under the root.
+fn build_fuzzy_index(file_names: &[&str]) -> DriveIndex {
+    use uffs_mft::index::{IndexNameRef, MftIndex, ROOT_FRS};
+
+    use crate::compact::build_compact_index;
+
+    let letter = uffs_mft::platform::DriveLetter::C;
+    let mut idx = MftIndex::new(letter);
+    let root_off = idx.add_name(".");
+    let root = idx.get_or_create(ROOT_FRS.into());
+    root.stdinfo.set_directory(true);
+    root.first_name.name = IndexNameRef::new(root_off, 1, true, IndexNameRef::NO_EXTENSION);
+    root.first_name.parent_frs = Into::into(ROOT_FRS);
+    for (frs, file_name) in (200_u64..).zip(file_names) {
+        let f_off = idx.add_name(file_name);
+        let f_ext = idx.intern_extension(file_name);
+        let file_rec = idx.get_or_create(frs.into());
+        file_rec.first_name.name = IndexNameRef::new(
+            f_off,
+            u16::try_from(file_name.len()).expect("name too long"),
+            true,
+            f_ext,
+        );
+        file_rec.first_name.parent_frs = Into::into(ROOT_FRS);
+        file_rec.stdinfo.flags = 0x20;
+    }
+    let (drive, _, _) = build_compact_index(letter, &idx);
+    DriveIndex {
+        drives: vec![Arc::new(drive)],
+    }
+}
+
+#[test]
+fn fuzzy_finds_misspelled_name_ranked_by_similarity() {
+    let index = build_fuzzy_index(&[
+        "kernel32.dll",
+        "invoice_draft.xlsx",
+        "receive_invoice.xlsx",
+        "receive_invoices.xlsx",
+    ]);
+    let mut filters = super::super::filters::SearchFilters::default();
+    let result = search_index(
+        &index,
+        SearchRequest {
+            fuzzy: Some(super::super::fuzzy::DEFAULT_THRESHOLD),
+            ..SearchRequest::new("recieve_invoce.xlsx", &mut filters)
+        },
+        FieldId::Name,
+        true, // ignored — fuzzy ranks by score
+        &[],
+    );
+    let names: Vec<&str> = result.rows.iter().map(DisplayRow::name).collect();
+    assert_eq!(names, vec!["receive_invoice.xlsx", "receive_invoices.xlsx"]);
+}
+
+#[test]
+fn fuzzy_pattern_prefix_selects_fuzzy_mode() {
+    let mut backend = MultiDriveBackend::new();
+    backend.drives = build_fuzzy_index(&["receive_invoice.xlsx", "alpha.txt"])
+        .drives
+        .into_iter()
+        .map(|drive| Arc::try_unwrap(drive).unwrap_or_else(|_| panic!("sole owner")))
+        .collect();
+    let mut filters = super::super::filters::SearchFilters::default();
+    let exact = backend.search(SearchRequest::new("recieve_invoce", &mut filters));
+    assert!(
+        exact.rows.is_empty(),
+        "exact substring must not match a typo"
+    );
+    let fuzzy = backend.search(SearchRequest::new(
+        "fuzzy:recieve_invoce.xlsx",
+        &mut filters,
+    ));
+    let names: Vec<&str> = fuzzy.rows.iter().map(DisplayRow::name).collect();
+    assert_eq!(names, vec!["receive_invoice.xlsx"]);
+}
+
+#[test]
+fn fuzzy_threshold_controls_recall() {
+    let index = build_fuzzy_index(&["invoice.pdf", "invitation.pdf"]);
+    let mut filters = super::super::filters::SearchFilters::default();
+    let mut run = |threshold: f32| -> Vec<String> {
+        search_index(
+            &index,
+            SearchRequest {
+                fuzzy: Some(threshold),
+                ..SearchRequest::new("invoce.pdf", &mut filters)
+            },
+            FieldId::Name,
+            false,
+            &[],
+        )
+        .rows
+        .iter()
+        .map(|row| row.name().to_owned())
+        .collect()
+    };
+    assert_eq!(run(0.9), Vec::<String>::new());
+    assert_eq!(run(0.6), vec!["invoice.pdf"]);
+    assert_eq!(run(0.3), vec!["invoice.pdf", "invitation.pdf"]);
+}
+
+#[test]
+fn fuzzy_limit_keeps_the_best_scores_not_the_first_candidates() {
+    let index = build_fuzzy_index(&["invitation.pdf", "invoices.pdf", "invoice.pdf"]);
+    let mut filters = super::super::filters::SearchFilters::default();
+    let mut run = |limit: u32| -> Vec<String> {
+        search_index(
+            &index,
+            SearchRequest {
+                result_limit: Some(limit),
+                fuzzy: Some(0.3),
+                ..SearchRequest::new("invoce.pdf", &mut filters)
+            },
+            FieldId::Name,
+            false,
+            &[],
+        )
+        .rows
+        .iter()
+        .map(|row| row.name().to_owned())
+        .collect()
+    };
+    assert_eq!(run(1), vec!["invoice.pdf"]);
+    assert_eq!(run(2), vec!["invoice.pdf", "invoices.pdf"]);
+}
+
+// ── Rank mode ───────────────────────────────────────────────────────
+
+#[test]
+fn rank_orders_exact_then_prefix_then_substring() {
+    let index = build_fuzzy_index(&["q3_report_final.pdf", "report_2024.pdf", "report.pdf"]);
+    let mut filters = super::super::filters::SearchFilters::default();
+    let result = search_index(
+        &index,
+        SearchRequest {
+            result_limit: Some(2),
+            rank: true,
+            ..SearchRequest::new("report", &mut filters)
+        },
+        FieldId::Name,
+        false, // ignored — rank orders by relevance
+        &[],
+    );
+    let names: Vec<&str> = result.rows.iter().map(DisplayRow::name).collect();
+    assert_eq!(names, vec!["report.pdf", "report_2024.pdf"]);
+}
+
+#[test]
+fn rank_applies_the_limit_after_scoring() {
+    let mut backend = MultiDriveBackend::new();
+    backend.drives = build_fuzzy_index(&["a_report.pdf", "b_report.pdf", "report.pdf"])
+        .drives
+        .into_iter()
+        .map(|drive| Arc::try_unwrap(drive).unwrap_or_else(|_| panic!("sole owner")))
+        .collect();
+    let mut filters = super::super::filters::SearchFilters::default();
+    let unranked = backend.search(SearchRequest {
+        result_limit: Some(1),
+        ..SearchRequest::new("report", &mut filters)
+    });
+    let names: Vec<&str> = unranked.rows.iter().map(DisplayRow::name).collect();
+    assert_eq!(names, vec!["a_report.pdf"], "name sort keeps the first");
+    let ranked = backend.search(SearchRequest {
+        result_limit: Some(1),
+        rank: true,
+        ..SearchRequest::new("report", &mut filters)
+    });
+    let ranked_names: Vec<&str> = ranked.rows.iter().map(DisplayRow::name).collect();
+    assert_eq!(ranked_names, vec!["report.pdf"]);
+}
+
+/// The per-drive cut scores compact records by their parent chain; every
+/// score must equal the one the merge computes from the resolved row.
+#[test]
+fn rank_record_scores_match_row_scores() {
+    use uffs_mft::index::{IndexNameRef, MftIndex, ROOT_FRS};
+
+    use super::super::rank::RankNeedle;
+    use crate::compact::build_compact_index;
+
+    let letter = uffs_mft::platform::DriveLetter::C;
+    let mut idx = MftIndex::new(letter);
+    let root_off = idx.add_name(".");
+    let root = idx.get_or_create(ROOT_FRS.into());
+    root.stdinfo.set_directory(true);
+    root.first_name.name = IndexNameRef::new(root_off, 1, true, IndexNameRef::NO_EXTENSION);
+    root.first_name.parent_frs = Into::into(ROOT_FRS);
+    for (frs, parent, name, is_dir) in [
+        (300_u64, ROOT_FRS, "reports", true),
+        (301, 300, "WinSxS", true),
+        (200, 300, "report.pdf", false),
+        (201, 301, "q3_report.pdf", false),
+        (202, ROOT_FRS, "notes.txt", false),
+    ] {
+        let n_off = idx.add_name(name);
+        let n_ext = idx.intern_extension(name);
+        let rec = idx.get_or_create(frs.into());
+        rec.stdinfo.set_directory(is_dir);
+        rec.first_name.name = IndexNameRef::new(
+            n_off,
+            u16::try_from(name.len()).expect("name too long"),
+            true,
+            n_ext,
+        );
+        rec.first_name.parent_frs = parent.into();
+    }
+    let (compact, _, _) = build_compact_index(letter, &idx);
+    let index = DriveIndex {
+        drives: vec![Arc::new(compact)],
+    };
+    let drive = index.drives.first().expect("one drive");
+    let needle = RankNeedle::new("report", drive.fold);
+
+    let mut filters = super::super::filters::SearchFilters::default();
+    let rows = search_index(
+        &index,
+        SearchRequest::new("*", &mut filters),
+        FieldId::Name,
+        false,
+        &[],
+    )
+    .rows;
+    assert!(rows.len() >= 5, "every record resolves: {rows:?}");
+    for row in &rows {
+        assert_eq!(
+            needle.score_record(drive, row.record_index),
+            Some(needle.score(row)),
+            "{}",
+            row.path
+        );
+    }
+}
+
+// ── Keyset paging ───────────────────────────────────────────────────
+
+#[test]
+fn page_walks_last_results_by_cursor() {
+    let mut backend = MultiDriveBackend::new();
+    backend.drives = build_fuzzy_index(&["c_report.pdf", "a_report.pdf", "b_report.pdf"])
+        .drives
+        .into_iter()
+        .map(|drive| Arc::try_unwrap(drive).unwrap_or_else(|_| panic!("sole owner")))
+        .collect();
+    backend.sort_column = FieldId::Name;
+    backend.sort_desc = false;
+    let mut filters = super::super::filters::SearchFilters::default();
+    backend.search(SearchRequest::new("report", &mut filters));
+
+    let (first, first_page) = backend.page(None, 2);
+    let first_names: Vec<&str> = first.iter().map(DisplayRow::name).collect();
+    assert_eq!(first_names, vec!["a_report.pdf", "b_report.pdf"]);
+    let token = first_page.next.expect("a third row remains").encode();
+
+    let cursor = SearchCursor::decode(&token).expect("round-trips");
+    let (rest, rest_page) = backend.page(Some(&cursor), 2);
+    let rest_names: Vec<&str> = rest.iter().map(DisplayRow::name).collect();
+    assert_eq!(rest_names, vec!["c_report.pdf"]);
+    assert!(rest_page.next.is_none());
+    assert!(!rest_page.index_changed);
+
+    backend.drives.clear();
+    assert_ne!(backend.index_version(), cursor.index_version);
+}
+
+/// Paging a size sort whose rows all tie: the cursor bound pushed into
+/// the match-all heap yields every file exactly once, with no empty page.
+#[test]
+fn page_bound_walks_tied_sizes_across_drives_without_repeats() {
+    use alloc::sync::Arc;
+
+    use uffs_mft::index::{IndexNameRef, MftIndex, ROOT_FRS, SizeInfo};
+
+    use super::super::cursor::{PageBound, PageOrder, cut_page};
+    use crate::compact::build_compact_index;
+
+    let mut drives = Vec::new();
+    for letter in [
+        uffs_mft::platform::DriveLetter::D,
+        uffs_mft::platform::DriveLetter::C,
+    ] {
+        let mut idx = MftIndex::new(letter);
+        let root_off = idx.add_name(".");
+        let root = idx.get_or_create(ROOT_FRS.into());
+        root.stdinfo.set_directory(true);
+        root.first_name.name = IndexNameRef::new(root_off, 1, true, IndexNameRef::NO_EXTENSION);
+        root.first_name.parent_frs = Into::into(ROOT_FRS);
+        for (frs, file_name) in [(200_u64, "a.bin"), (201, "b.bin"), (202, "c.bin")] {
+            let f_off = idx.add_name(file_name);
+            let f_ext = idx.intern_extension(file_name);
+            let file_rec = idx.get_or_create(frs.into());
+            file_rec.first_name.name = IndexNameRef::new(f_off, 5, true, f_ext);
+            file_rec.first_name.parent_frs = Into::into(ROOT_FRS);
+            file_rec.first_stream.size = SizeInfo {
+                length: 100,
+                allocated: 512,
+            };
+        }
+        let (drive, _, _) = build_compact_index(letter, &idx);
+        drives.push(Arc::new(drive));
+    }
+    let index = DriveIndex { drives };
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let mut filters = super::super::filters::SearchFilters {
+            page_after: cursor
+                .as_ref()
+                .and_then(|cur| PageBound::new(cur, FieldId::Size, true)),
+            ..Default::default()
+        };
+        let mut rows = search_index(
+            &index,
+            SearchRequest {
+                result_limit: Some(3),
+                filter_mode: FilterMode::FilesOnly,
+                ..SearchRequest::new("*", &mut filters)
+            },
+            FieldId::Size,
+            true,
+            &[],
+        )
+        .rows;
+        let page = cut_page(
+            &mut rows,
+            cursor.as_ref(),
+            PageOrder::Column {
+                column: FieldId::Size,
+                descending: true,
+                tiers: &[],
+            },
+            2,
+            0,
+        );
+        assert!(!rows.is_empty(), "no empty page while rows remain");
+        seen.extend(
+            rows.iter()
+                .map(|row| (row.drive.letter(), row.name().to_owned())),
+        );
+        let Some(next) = page.next else { break };
+        cursor = Some(next);
+    }
+    let mut unique = seen.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(seen.len(), 6, "every file once: {seen:?}");
+    assert_eq!(unique.len(), 6, "no repeats: {seen:?}");
+}
+
 // ── *.ext → ext-filter safety-net promotion tests ───────────────────
 //
 // These pin the dispatch-time rewrite in `search_index` that routes
@@ -1492,7 +1846,7 @@ fn search_index_promotes_drive_prefix_with_ext_glob() {
     );
     let first = result.rows.first().expect("asserted non-empty above");
     assert_eq!(
-        first.drive,
+        first.drive.letter(),
         uffs_mft::platform::DriveLetter::C,
         "result must be from drive C only"
     );
@@ -1554,7 +1908,7 @@ fn search_index_drive_prefix_case_insensitive_letter() {
         "lowercase drive letter must still match drive C"
     );
     assert_eq!(
-        result.rows.first().expect("one row").drive,
+        result.rows.first().expect("one row").drive.letter(),
         uffs_mft::platform::DriveLetter::C,
         "result must be from drive C"
     );
@@ -1572,7 +1926,9 @@ fn search_index_drive_prefix_explicit_filter_not_clobbered() {
     let result = search_index(
         &index,
         SearchRequest {
-            drives_filter: &[uffs_mft::platform::DriveLetter::D],
+            drives_filter: &[uffs_mft::platform::VolumeId::local(
+                uffs_mft::platform::DriveLetter::D,
+            )],
             ..SearchRequest::new("C:*.txt", &mut filters)
         },
         FieldId::Modified,
@@ -2058,7 +2414,7 @@ fn search_index_drive_root_glob_scopes_drive_and_promotes_ext() {
         result
             .rows
             .iter()
-            .all(|row| row.drive == uffs_mft::platform::DriveLetter::C),
+            .all(|row| row.drive.letter() == uffs_mft::platform::DriveLetter::C),
         "results must be scoped to drive C"
     );
     assert!(
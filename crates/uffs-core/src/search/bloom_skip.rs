@@ -55,6 +55,7 @@
 //! and at what false-positive rate.
 
 use crate::bloom::Bloom;
+use crate::compact::DriveCompactIndex;
 
 /// Decision outcome for a single drive's bloom probe.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -121,6 +122,41 @@ pub fn decide_for_ext_filter(bloom: Option<&Bloom>, ext_terms: &[String]) -> Blo
     }
 }
 
+/// Phase 4 Commit F — drop drives whose bloom misses the ext
+/// filter from the active subset.
+///
+/// Mutates `active_drives` in place.  Emits one
+/// `shard.bloom.decision` tracing event per drive (skip or keep)
+/// so operators can audit the skip rate and false-positive rate.
+///
+/// `ext_terms` is the lowercase-no-dot extension list as held in
+/// [`crate::search::filters::SearchFilters::extensions`].  Empty
+/// `ext_terms` short-circuits to a no-op via
+/// [`decide_for_ext_filter`]'s `Keep`-on-empty contract.
+#[expect(
+    clippy::single_call_fn,
+    reason = "extracted from search_index for readability — the inline body \
+              would push the cognitive-complexity lint past its budget"
+)]
+pub(crate) fn apply_pre_check(active_drives: &mut Vec<&DriveCompactIndex>, ext_terms: &[String]) {
+    if ext_terms.is_empty() {
+        return;
+    }
+    active_drives.retain(|drive| {
+        let decision = decide_for_ext_filter(drive.bloom.as_ref(), ext_terms);
+        let matched = decision.keep();
+        tracing::debug!(
+            target: "shard.bloom.decision",
+            drive = %drive.letter,
+            r#match = matched,
+            terms = ?ext_terms,
+            source = "search_dispatch",
+            "bloom pre-check"
+        );
+        matched
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
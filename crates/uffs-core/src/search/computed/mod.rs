@@ -0,0 +1,508 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! User-defined computed fields (`--field avgsize=treesize/descendants`).
+//!
+//! [`crate::search::derived`] hard-codes a handful of derived columns; a
+//! computed field is the ad-hoc version — a small typed integer expression
+//! over [`FieldId`]s, declared per query or saved in the daemon config:
+//!
+//! | Definition                                | Meaning                      |
+//! |-------------------------------------------|------------------------------|
+//! | `avgsize = treesize / descendants`        | average file size per folder |
+//! | `slack = allocated - size`                | bytes lost to cluster slack  |
+//! | `age = age_days(modified)`                | days since last write        |
+//! | `stem = len(name) - len(extension)`       | name length without extension|
+//! | `lag = modified - created`                | seconds between create/write |
+//!
+//! Operands are integer literals (size suffixes allowed: `4KB`), the
+//! numeric fields (`size`, `allocated`, `treesize`, `tree_allocated`,
+//! `descendants`, `name_length`, `path_length`), the timestamps
+//! (`created`, `modified`, `accessed`, `now`) and other computed fields.
+//! Operators are `+ - * / %` and unary minus; functions are `len(name |
+//! extension | path)`, `age_days(t)`, `abs(n)`, `min(a, b)` and
+//! `max(a, b)`.  Timestamps only take part in `t - t` (whole seconds),
+//! `age_days`, `min` and `max`, and a field's final value is a number.
+//!
+//! Evaluation runs straight off a [`CompactRecord`] (the scan path) or a
+//! [`DisplayRow`] via [`FieldSource`], with checked `i64` arithmetic: an
+//! overflow, a division by zero or an unset timestamp makes the value
+//! undefined, and an undefined value never passes a filter.
+
+mod parser;
+
+use alloc::sync::Arc;
+use core::fmt;
+use std::collections::HashMap;
+
+use uffs_mft::platform::VolumeId;
+
+use self::parser::{BinOp, Expr, RESERVED, Ty};
+use super::backend::DisplayRow;
+use super::field::FieldId;
+use super::filters::{extract_extension_after_dot, now_filetime};
+use crate::compact::CompactRecord;
+
+/// FILETIME ticks in one day.
+const TICKS_PER_DAY: i64 = uffs_time::FILETIME_TICKS_PER_SECOND * 86_400;
+
+/// Error defining a computed field.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ComputedError {
+    /// A definition has no `=` between name and expression.
+    #[error("`{def}` is not NAME=EXPRESSION")]
+    MissingEquals {
+        /// The definition as given.
+        def: String,
+    },
+    /// The name is not an identifier (`[A-Za-z_][A-Za-z0-9_]*`).
+    #[error("`{name}` is not a valid field name")]
+    InvalidName {
+        /// The rejected name.
+        name: String,
+    },
+    /// The name is already a built-in field, `now` or a function.
+    #[error("`{name}` is a built-in name and cannot be redefined")]
+    ShadowsField {
+        /// The rejected name.
+        name: String,
+    },
+    /// The expression failed to parse or type-check.
+    #[error("field `{name}`: {reason}")]
+    Expression {
+        /// The field being defined.
+        name: String,
+        /// What is wrong with its expression.
+        reason: String,
+    },
+    /// The field refers back to itself through other computed fields.
+    #[error("field `{name}` refers to itself")]
+    Cycle {
+        /// A field on the cycle.
+        name: String,
+    },
+}
+
+/// Supplies field values to a computed-field expression.
+///
+/// Numeric fields and timestamps (`Created`, `Modified`, `Accessed`, raw
+/// FILETIME) are returned as-is; `Name` and `Extension` return their
+/// length in characters and `Path` returns [`path_length`].  `None` means
+/// the source cannot provide the field, which makes the expression
+/// undefined.
+pub trait FieldSource {
+    /// Value of `field` for this row.
+    fn value(&self, field: FieldId) -> Option<i64>;
+}
+
+/// Saturating `u64` → `i64` for byte counts.
+fn bytes(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// Character count as `i64`.
+fn chars(text: &str) -> Option<i64> {
+    i64::try_from(text.chars().count()).ok()
+}
+
+/// Characters in a local volume root, `X:\`.
+const LOCAL_ROOT_CHARS: usize = 3;
+
+/// `len(path)` of a row rendered under `volume`'s display root.
+///
+/// Counted in characters from a local `X:\` root, saturating at
+/// `u16::MAX` — the figure the scan precomputes in
+/// [`CompactRecord::path_len`] — so a host-qualified `srv01\C:\…` row
+/// measures the same as the record it was built from.
+#[must_use]
+pub fn path_length(volume: VolumeId, rendered: &str) -> i64 {
+    let root_chars = volume.display_root().chars().count();
+    let below_root = rendered.chars().count().saturating_sub(root_chars);
+    i64::from(uffs_mft::len_to_u16(LOCAL_ROOT_CHARS + below_root))
+}
+
+impl FieldSource for DisplayRow {
+    #[expect(
+        clippy::wildcard_enum_match_arm,
+        reason = "fields without a numeric value make the expression undefined"
+    )]
+    fn value(&self, field: FieldId) -> Option<i64> {
+        match field {
+            FieldId::Size => Some(bytes(self.size)),
+            FieldId::SizeOnDisk => Some(bytes(self.allocated)),
+            FieldId::TreeSize => Some(bytes(self.treesize)),
+            FieldId::TreeAllocated => Some(bytes(self.tree_allocated)),
+            FieldId::Descendants => Some(i64::from(self.descendants)),
+            FieldId::Created => Some(self.created),
+            FieldId::Modified => Some(self.modified),
+            FieldId::Accessed => Some(self.accessed),
+            FieldId::Name => chars(self.name()),
+            FieldId::Extension => chars(extract_extension_after_dot(self.name())),
+            FieldId::Path => Some(path_length(self.drive, &self.path)),
+            _ => None,
+        }
+    }
+}
+
+/// A [`CompactRecord`] with the name buffer it points into.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordSource<'rec> {
+    /// The record.
+    pub record: &'rec CompactRecord,
+    /// The drive's name buffer.
+    pub names: &'rec [u8],
+}
+
+impl FieldSource for RecordSource<'_> {
+    #[expect(
+        clippy::wildcard_enum_match_arm,
+        reason = "fields without a numeric value make the expression undefined"
+    )]
+    fn value(&self, field: FieldId) -> Option<i64> {
+        let rec = self.record;
+        match field {
+            FieldId::Size => Some(bytes(rec.size)),
+            FieldId::SizeOnDisk => Some(bytes(rec.allocated)),
+            FieldId::TreeSize => Some(bytes(rec.treesize)),
+            FieldId::TreeAllocated => Some(bytes(rec.tree_allocated)),
+            FieldId::Descendants => Some(i64::from(rec.descendants)),
+            FieldId::Created => Some(rec.created),
+            FieldId::Modified => Some(rec.modified),
+            FieldId::Accessed => Some(rec.accessed),
+            FieldId::Name => chars(rec.name(self.names)),
+            FieldId::Extension => chars(extract_extension_after_dot(rec.name(self.names))),
+            FieldId::Path => Some(i64::from(rec.path_len)),
+            _ => None,
+        }
+    }
+}
+
+/// Current time as a raw FILETIME, the `now` a computed field sees.
+#[must_use]
+pub fn now() -> i64 {
+    now_filetime()
+}
+
+/// One compiled computed field.
+///
+/// References to other computed fields are inlined at definition time, so
+/// a field evaluates on its own.
+#[derive(Clone)]
+pub struct ComputedField {
+    /// Lower-cased field name.
+    name: String,
+    /// Expression as written.
+    source: String,
+    /// Compiled expression.
+    expr: Expr,
+}
+
+impl ComputedField {
+    /// Lower-cased field name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Expression as written.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the value depends on the evaluation time (`now`,
+    /// `age_days`).
+    #[must_use]
+    pub fn uses_now(&self) -> bool {
+        uses_now(&self.expr)
+    }
+
+    /// Evaluate against `src` at time `now` (FILETIME); `None` when the
+    /// value is undefined.
+    #[must_use]
+    pub fn eval<S: FieldSource + ?Sized>(&self, src: &S, now: i64) -> Option<i64> {
+        eval(&self.expr, src, now)
+    }
+
+    /// Evaluate against a compact record (the scan path).
+    #[must_use]
+    pub fn eval_record(&self, record: &CompactRecord, names: &[u8], now: i64) -> Option<i64> {
+        self.eval(&RecordSource { record, names }, now)
+    }
+}
+
+impl fmt::Debug for ComputedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.source)
+    }
+}
+
+impl PartialEq for ComputedField {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.expr == other.expr
+    }
+}
+
+impl Eq for ComputedField {}
+
+impl core::hash::Hash for ComputedField {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.expr.hash(state);
+    }
+}
+
+/// Whether `expr` reads the clock.
+fn uses_now(expr: &Expr) -> bool {
+    match expr {
+        Expr::Now | Expr::AgeDays(_) => true,
+        Expr::Const(_) | Expr::Field(_) | Expr::Timestamp(_) => false,
+        Expr::Neg(inner) | Expr::Abs(inner) => uses_now(inner),
+        Expr::Binary(_, lhs, rhs)
+        | Expr::Min(lhs, rhs)
+        | Expr::Max(lhs, rhs)
+        | Expr::Elapsed(lhs, rhs) => uses_now(lhs) || uses_now(rhs),
+    }
+}
+
+/// Evaluate `expr` with checked arithmetic.
+fn eval<S: FieldSource + ?Sized>(expr: &Expr, src: &S, now: i64) -> Option<i64> {
+    match expr {
+        Expr::Const(value) => Some(*value),
+        Expr::Now => Some(now),
+        Expr::Field(field) => src.value(*field),
+        Expr::Timestamp(field) => src.value(*field).filter(|&ticks| ticks > 0),
+        Expr::Neg(inner) => eval(inner, src, now)?.checked_neg(),
+        Expr::Abs(inner) => eval(inner, src, now)?.checked_abs(),
+        Expr::Binary(op, lhs, rhs) => {
+            let (left, right) = (eval(lhs, src, now)?, eval(rhs, src, now)?);
+            match op {
+                BinOp::Add => left.checked_add(right),
+                BinOp::Sub => left.checked_sub(right),
+                BinOp::Mul => left.checked_mul(right),
+                BinOp::Div => left.checked_div(right),
+                BinOp::Rem => left.checked_rem(right),
+            }
+        }
+        Expr::Min(lhs, rhs) => Some(eval(lhs, src, now)?.min(eval(rhs, src, now)?)),
+        Expr::Max(lhs, rhs) => Some(eval(lhs, src, now)?.max(eval(rhs, src, now)?)),
+        Expr::Elapsed(lhs, rhs) => eval(lhs, src, now)?
+            .checked_sub(eval(rhs, src, now)?)?
+            .checked_div(uffs_time::FILETIME_TICKS_PER_SECOND),
+        Expr::AgeDays(inner) => now
+            .checked_sub(eval(inner, src, now)?)?
+            .checked_div(TICKS_PER_DAY),
+    }
+}
+
+/// The computed fields in scope for one query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComputedFields {
+    /// Compiled fields, in definition order.
+    fields: Vec<Arc<ComputedField>>,
+}
+
+impl ComputedFields {
+    /// Compile `NAME=EXPRESSION` definitions.
+    ///
+    /// Names are case-insensitive; a later definition of a name replaces
+    /// an earlier one, so request-level fields listed after the saved
+    /// config fields override them.  Fields may refer to each other in
+    /// any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ComputedError`] for a malformed definition, a name that
+    /// shadows a built-in, an expression that does not parse or
+    /// type-check, or a reference cycle.
+    pub fn parse<I, S>(defs: I) -> Result<Self, ComputedError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut sources: Vec<(String, String)> = Vec::new();
+        for raw_def in defs {
+            let def = raw_def.as_ref();
+            let Some((raw_name, raw_source)) = def.split_once('=') else {
+                return Err(ComputedError::MissingEquals {
+                    def: def.to_owned(),
+                });
+            };
+            let name = validate_name(raw_name.trim())?;
+            let source = raw_source.trim().to_owned();
+            match sources.iter_mut().find(|(existing, _)| *existing == name) {
+                Some(slot) => slot.1 = source,
+                None => sources.push((name, source)),
+            }
+        }
+        let mut resolver = Resolver {
+            sources: &sources,
+            done: HashMap::new(),
+            active: Vec::new(),
+        };
+        let mut fields = Vec::with_capacity(sources.len());
+        for (name, source) in &sources {
+            let expr = resolver.resolve(name)?.unwrap_or(Expr::Const(0));
+            fields.push(Arc::new(ComputedField {
+                name: name.clone(),
+                source: source.clone(),
+                expr,
+            }));
+        }
+        Ok(Self { fields })
+    }
+
+    /// The field called `name` (case-insensitive).
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Arc<ComputedField>> {
+        self.fields
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether no fields are defined.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Fields in definition order.
+    pub fn iter(&self) -> core::slice::Iter<'_, Arc<ComputedField>> {
+        self.fields.iter()
+    }
+}
+
+impl<'fields> IntoIterator for &'fields ComputedFields {
+    type Item = &'fields Arc<ComputedField>;
+    type IntoIter = core::slice::Iter<'fields, Arc<ComputedField>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Check a field name and lower-case it.
+fn validate_name(name: &str) -> Result<String, ComputedError> {
+    let mut chars = name.chars();
+    let well_formed = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if !well_formed {
+        return Err(ComputedError::InvalidName {
+            name: name.to_owned(),
+        });
+    }
+    let lowered = name.to_ascii_lowercase();
+    if FieldId::parse(&lowered).is_some() || RESERVED.contains(&lowered.as_str()) {
+        return Err(ComputedError::ShadowsField {
+            name: name.to_owned(),
+        });
+    }
+    Ok(lowered)
+}
+
+/// Compiles definitions depth-first so references inline in any order.
+struct Resolver<'defs> {
+    /// `(name, source)` definitions.
+    sources: &'defs [(String, String)],
+    /// Already-compiled expressions by name.
+    done: HashMap<String, Expr>,
+    /// Names being compiled (cycle detection).
+    active: Vec<String>,
+}
+
+impl Resolver<'_> {
+    /// Compiled expression of `name`, or `None` when no such field exists.
+    fn resolve(&mut self, name: &str) -> Result<Option<Expr>, ComputedError> {
+        let lowered = name.to_ascii_lowercase();
+        if let Some(expr) = self.done.get(&lowered) {
+            return Ok(Some(expr.clone()));
+        }
+        let Some((_, source)) = self.sources.iter().find(|(def, _)| *def == lowered) else {
+            return Ok(None);
+        };
+        if self.active.contains(&lowered) {
+            return Err(ComputedError::Cycle { name: lowered });
+        }
+        self.active.push(lowered.clone());
+        let parsed = parser::parse(&lowered, source, &mut |reference| self.resolve(reference));
+        self.active.pop();
+        let (expr, ty) = parsed?;
+        if ty == Ty::Time {
+            return Err(ComputedError::Expression {
+                name: lowered,
+                reason: "the result is a timestamp; subtract another timestamp or use \
+                         age_days() to get a number"
+                    .to_owned(),
+            });
+        }
+        self.done.insert(lowered, expr.clone());
+        Ok(Some(expr))
+    }
+}
+
+/// Comparison in a computed-field filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComputedCmp {
+    /// `=`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Lte,
+    /// `>`
+    Gt,
+    /// `>=`
+    Gte,
+}
+
+/// A `computed OP value` filter, evaluated on the compact scan path and
+/// on display rows alike.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ComputedFilter {
+    /// Field to evaluate.
+    pub field: Arc<ComputedField>,
+    /// Comparison.
+    pub op: ComputedCmp,
+    /// Right-hand side.
+    pub value: i64,
+    /// Evaluation time (FILETIME); `0` for fields that ignore the clock,
+    /// so equal filters compare (and cache) equal.
+    pub now: i64,
+}
+
+impl ComputedFilter {
+    /// Build a filter, capturing the clock only when the field reads it.
+    #[must_use]
+    pub fn new(field: Arc<ComputedField>, op: ComputedCmp, value: i64) -> Self {
+        let now = if field.uses_now() { now() } else { 0 };
+        Self {
+            field,
+            op,
+            value,
+            now,
+        }
+    }
+
+    /// Whether `src` passes; an undefined value never does.
+    #[must_use]
+    pub fn matches<S: FieldSource + ?Sized>(&self, src: &S) -> bool {
+        self.field
+            .eval(src, self.now)
+            .is_some_and(|actual| match self.op {
+                ComputedCmp::Eq => actual == self.value,
+                ComputedCmp::Ne => actual != self.value,
+                ComputedCmp::Lt => actual < self.value,
+                ComputedCmp::Lte => actual <= self.value,
+                ComputedCmp::Gt => actual > self.value,
+                ComputedCmp::Gte => actual >= self.value,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests;
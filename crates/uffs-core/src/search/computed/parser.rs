@@ -0,0 +1,401 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Expression parser and type checker for computed fields.
+//!
+//! Precedence climbs from `+ -` through `* / %` to unary minus, calls and
+//! parentheses.  Every node is type-checked as it is built, so a parsed
+//! [`Expr`] never needs a run-time type test.
+
+use super::ComputedError;
+use crate::search::field::FieldId;
+use crate::search::filters::parse_size;
+
+/// Value type of an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Ty {
+    /// Plain integer: a size, count, length or difference.
+    Number,
+    /// Raw FILETIME timestamp.
+    Time,
+}
+
+/// Integer operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum BinOp {
+    /// `+`
+    Add,
+    /// `-`
+    Sub,
+    /// `*`
+    Mul,
+    /// `/` (truncating)
+    Div,
+    /// `%`
+    Rem,
+}
+
+/// Typed expression tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Expr {
+    /// Integer literal (size suffixes already applied).
+    Const(i64),
+    /// The evaluation time, as a FILETIME.
+    Now,
+    /// A numeric field; text fields (`name`, `extension`, `path`) read as
+    /// their length in characters.
+    Field(FieldId),
+    /// A timestamp field; unset (zero) timestamps are undefined.
+    Timestamp(FieldId),
+    /// Unary minus.
+    Neg(Box<Self>),
+    /// `abs(n)`.
+    Abs(Box<Self>),
+    /// Integer arithmetic.
+    Binary(BinOp, Box<Self>, Box<Self>),
+    /// `min(a, b)` over two values of one type.
+    Min(Box<Self>, Box<Self>),
+    /// `max(a, b)` over two values of one type.
+    Max(Box<Self>, Box<Self>),
+    /// `time - time`, in whole seconds.
+    Elapsed(Box<Self>, Box<Self>),
+    /// `age_days(time)`: whole days from the timestamp to now.
+    AgeDays(Box<Self>),
+}
+
+/// One lexical token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'src> {
+    /// Integer literal.
+    Num(i64),
+    /// Field, function or computed-field name.
+    Ident(&'src str),
+    /// One of `+ - * / %`.
+    Op(char),
+    /// `(`
+    Open,
+    /// `)`
+    Close,
+    /// `,`
+    Comma,
+}
+
+/// Resolves a name that is not a built-in field to the inlined expression
+/// of another computed field (`Ok(None)` = no such field).
+pub(super) type Resolve<'res> = dyn FnMut(&str) -> Result<Option<Expr>, ComputedError> + 'res;
+
+/// Recursive-descent parser over one field's token stream.
+struct Parser<'src, 'res, 'cb> {
+    /// Name of the field being defined (for error messages).
+    name: &'src str,
+    /// Token stream.
+    tokens: Vec<Token<'src>>,
+    /// Next token to read.
+    pos: usize,
+    /// Computed-field reference resolver.
+    resolve: &'cb mut Resolve<'res>,
+}
+
+/// Parse and type-check the expression `source` of the field `name`.
+pub(super) fn parse(
+    name: &str,
+    source: &str,
+    resolve: &mut Resolve<'_>,
+) -> Result<(Expr, Ty), ComputedError> {
+    let tokens = tokenize(source).map_err(|reason| fail(name, reason))?;
+    if tokens.is_empty() {
+        return Err(fail(name, "the expression is empty".to_owned()));
+    }
+    let mut parser = Parser {
+        name,
+        tokens,
+        pos: 0,
+        resolve,
+    };
+    let parsed = parser.sum()?;
+    parser.peek().map_or(Ok(parsed), |token| {
+        Err(parser.error(format!("unexpected {}", describe(token))))
+    })
+}
+
+/// Build an [`ComputedError::Expression`] for `name`.
+fn fail(name: &str, reason: String) -> ComputedError {
+    ComputedError::Expression {
+        name: name.to_owned(),
+        reason,
+    }
+}
+
+/// Split `source` into tokens.  A number runs on through letters so size
+/// suffixes (`4KB`, `1GB`) arrive as one literal.
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+    while let Some(first) = rest.chars().next() {
+        let word_len = |text: &str| {
+            text.find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(text.len())
+        };
+        let (token, len) = match first {
+            '0'..='9' => {
+                let len = word_len(rest);
+                let literal = rest.get(..len).unwrap_or(rest);
+                let value = parse_size(literal)
+                    .ok()
+                    .and_then(|value| i64::try_from(value).ok())
+                    .ok_or_else(|| format!("`{literal}` is not a number"))?;
+                (Token::Num(value), len)
+            }
+            'a'..='z' | 'A'..='Z' | '_' => {
+                let len = word_len(rest);
+                (Token::Ident(rest.get(..len).unwrap_or(rest)), len)
+            }
+            '+' | '-' | '*' | '/' | '%' => (Token::Op(first), 1),
+            '(' => (Token::Open, 1),
+            ')' => (Token::Close, 1),
+            ',' => (Token::Comma, 1),
+            other => return Err(format!("unexpected character `{other}`")),
+        };
+        tokens.push(token);
+        rest = rest.get(len..).unwrap_or_default().trim_start();
+    }
+    Ok(tokens)
+}
+
+/// How a token reads in an error message.
+fn describe(token: Token<'_>) -> String {
+    match token {
+        Token::Num(value) => format!("`{value}`"),
+        Token::Ident(ident) => format!("`{ident}`"),
+        Token::Op(op) => format!("`{op}`"),
+        Token::Open => "`(`".to_owned(),
+        Token::Close => "`)`".to_owned(),
+        Token::Comma => "`,`".to_owned(),
+    }
+}
+
+/// Type of a built-in field usable in an expression, with the field its
+/// value is read from (`name_length` reads `name`).
+fn field_operand(field: FieldId) -> Option<(FieldId, Ty)> {
+    if matches!(
+        field,
+        FieldId::Size
+            | FieldId::SizeOnDisk
+            | FieldId::TreeSize
+            | FieldId::TreeAllocated
+            | FieldId::Descendants
+    ) {
+        Some((field, Ty::Number))
+    } else if field == FieldId::NameLength {
+        Some((FieldId::Name, Ty::Number))
+    } else if field == FieldId::PathLength {
+        Some((FieldId::Path, Ty::Number))
+    } else if matches!(
+        field,
+        FieldId::Created | FieldId::Modified | FieldId::Accessed
+    ) {
+        Some((field, Ty::Time))
+    } else {
+        None
+    }
+}
+
+impl<'src> Parser<'src, '_, '_> {
+    /// Next token without consuming it.
+    fn peek(&self) -> Option<Token<'src>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    /// Consume and return the next token.
+    fn next(&mut self) -> Option<Token<'src>> {
+        let token = self.peek();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    /// Consume the next token if it is `want`.
+    fn eat(&mut self, want: Token<'_>) -> bool {
+        let found = self.peek() == Some(want);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Consume `want` or fail naming what was expected.
+    fn expect(&mut self, want: Token<'_>) -> Result<(), ComputedError> {
+        if self.eat(want) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {}", describe(want))))
+        }
+    }
+
+    /// An expression error for the field being parsed.
+    fn error(&self, reason: String) -> ComputedError {
+        fail(self.name, reason)
+    }
+
+    /// `sum := product (('+' | '-') product)*`
+    fn sum(&mut self) -> Result<(Expr, Ty), ComputedError> {
+        let mut lhs = self.product()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            self.pos += 1;
+            let rhs = self.product()?;
+            lhs = self.arith(if op == '+' { BinOp::Add } else { BinOp::Sub }, lhs, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    /// `product := unary (('*' | '/' | '%') unary)*`
+    fn product(&mut self) -> Result<(Expr, Ty), ComputedError> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/' | '%'))) = self.peek() {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            let bin = match op {
+                '*' => BinOp::Mul,
+                '/' => BinOp::Div,
+                _ => BinOp::Rem,
+            };
+            lhs = self.arith(bin, lhs, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    /// Type-check one arithmetic step: numbers combine freely, and two
+    /// timestamps subtract to the seconds between them.
+    fn arith(
+        &self,
+        op: BinOp,
+        (lhs, lhs_ty): (Expr, Ty),
+        (rhs, rhs_ty): (Expr, Ty),
+    ) -> Result<(Expr, Ty), ComputedError> {
+        match (lhs_ty, rhs_ty) {
+            (Ty::Number, Ty::Number) => {
+                Ok((Expr::Binary(op, Box::new(lhs), Box::new(rhs)), Ty::Number))
+            }
+            (Ty::Time, Ty::Time) if op == BinOp::Sub => {
+                Ok((Expr::Elapsed(Box::new(lhs), Box::new(rhs)), Ty::Number))
+            }
+            (Ty::Time, _) | (_, Ty::Time) => Err(self.error(
+                "a timestamp can only be subtracted from another timestamp \
+                 (`modified - created`) or aged with age_days()"
+                    .to_owned(),
+            )),
+        }
+    }
+
+    /// `unary := '-' unary | atom`
+    fn unary(&mut self) -> Result<(Expr, Ty), ComputedError> {
+        if self.eat(Token::Op('-')) {
+            let operand = self.unary()?;
+            let inner = self.number(operand, "negate")?;
+            return Ok((Expr::Neg(Box::new(inner)), Ty::Number));
+        }
+        self.atom()
+    }
+
+    /// Require a number-typed operand for `what`.
+    fn number(&self, (expr, ty): (Expr, Ty), what: &str) -> Result<Expr, ComputedError> {
+        match ty {
+            Ty::Number => Ok(expr),
+            Ty::Time => Err(self.error(format!("cannot {what} a timestamp"))),
+        }
+    }
+
+    /// `atom := NUMBER | '(' sum ')' | IDENT | IDENT '(' args ')'`
+    fn atom(&mut self) -> Result<(Expr, Ty), ComputedError> {
+        match self.next() {
+            Some(Token::Num(value)) => Ok((Expr::Const(value), Ty::Number)),
+            Some(Token::Open) => {
+                let inner = self.sum()?;
+                self.expect(Token::Close)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(ident)) if self.eat(Token::Open) => self.call(ident),
+            Some(Token::Ident(ident)) => self.ident(ident),
+            Some(token) => Err(self.error(format!("unexpected {}", describe(token)))),
+            None => Err(self.error("the expression ends too early".to_owned())),
+        }
+    }
+
+    /// A bare name: `now`, a built-in field, or another computed field.
+    fn ident(&mut self, ident: &str) -> Result<(Expr, Ty), ComputedError> {
+        if ident.eq_ignore_ascii_case("now") {
+            return Ok((Expr::Now, Ty::Time));
+        }
+        if let Some(field) = FieldId::parse(ident) {
+            return match field_operand(field) {
+                Some((read, Ty::Number)) => Ok((Expr::Field(read), Ty::Number)),
+                Some((read, Ty::Time)) => Ok((Expr::Timestamp(read), Ty::Time)),
+                None => Err(self.error(format!(
+                    "`{ident}` is not a number or timestamp (use len({ident}) for its length)"
+                ))),
+            };
+        }
+        (self.resolve)(ident)?.map_or_else(
+            || Err(self.error(format!("unknown field `{ident}`"))),
+            |expr| Ok((expr, Ty::Number)),
+        )
+    }
+
+    /// A function call; the opening parenthesis is already consumed.
+    fn call(&mut self, function: &str) -> Result<(Expr, Ty), ComputedError> {
+        let lowered = function.to_ascii_lowercase();
+        let parsed = match lowered.as_str() {
+            "len" => {
+                let measured = match self.next() {
+                    Some(Token::Ident(ident)) => FieldId::parse(ident).filter(|candidate| {
+                        matches!(
+                            candidate,
+                            FieldId::Name | FieldId::Extension | FieldId::Path
+                        )
+                    }),
+                    _ => None,
+                };
+                let Some(field) = measured else {
+                    return Err(self.error("len() takes name, extension or path".to_owned()));
+                };
+                (Expr::Field(field), Ty::Number)
+            }
+            "age_days" => match self.sum()? {
+                (expr, Ty::Time) => (Expr::AgeDays(Box::new(expr)), Ty::Number),
+                (_, Ty::Number) => {
+                    return Err(self.error("age_days() takes a timestamp".to_owned()));
+                }
+            },
+            "abs" => {
+                let operand = self.sum()?;
+                (
+                    Expr::Abs(Box::new(self.number(operand, "take abs() of")?)),
+                    Ty::Number,
+                )
+            }
+            "min" | "max" => {
+                let (lhs, lhs_ty) = self.sum()?;
+                self.expect(Token::Comma)?;
+                let (rhs, rhs_ty) = self.sum()?;
+                if lhs_ty != rhs_ty {
+                    return Err(self.error(format!(
+                        "{lowered}() compares two numbers or two timestamps"
+                    )));
+                }
+                let (lhs_box, rhs_box) = (Box::new(lhs), Box::new(rhs));
+                if lowered == "min" {
+                    (Expr::Min(lhs_box, rhs_box), lhs_ty)
+                } else {
+                    (Expr::Max(lhs_box, rhs_box), lhs_ty)
+                }
+            }
+            _ => return Err(self.error(format!("unknown function `{function}()`"))),
+        };
+        self.expect(Token::Close)?;
+        Ok(parsed)
+    }
+}
+
+/// Names a computed field may not take: `now` and the function names.
+pub(super) const RESERVED: &[&str] = &["now", "len", "age_days", "abs", "min", "max"];
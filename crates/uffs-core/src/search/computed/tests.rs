@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for computed-field parsing, typing and evaluation.
+
+use super::*;
+use crate::search::filters::SearchFilters;
+
+/// A folder row: 12 000 bytes under 4 descendants, written one day after
+/// creation.
+fn folder() -> DisplayRow {
+    DisplayRow::new(
+        0,
+        uffs_mft::platform::DriveLetter::C,
+        "C:\\data\\report.final.txt".to_owned(),
+        1000,
+        false,
+        11 * TICKS_PER_DAY,
+        10 * TICKS_PER_DAY,
+        0,
+        0,
+        4096,
+        4,
+        12_000,
+        0,
+    )
+}
+
+/// Compile a single definition.
+fn field(def: &str) -> Arc<ComputedField> {
+    let fields = ComputedFields::parse([def]).expect("definition compiles");
+    Arc::clone(fields.iter().next().expect("one field"))
+}
+
+#[test]
+fn evaluates_arithmetic_over_fields() {
+    let row = folder();
+    assert_eq!(
+        field("avgsize=treesize/descendants").eval(&row, 0),
+        Some(3000)
+    );
+    assert_eq!(field("slack = allocated - size").eval(&row, 0), Some(3096));
+    assert_eq!(field("x=2 + 3 * 4 - -1").eval(&row, 0), Some(15));
+    assert_eq!(field("x=(2 + 3) * 4 % 7").eval(&row, 0), Some(6));
+    assert_eq!(field("big=size + 1KB").eval(&row, 0), Some(2024));
+}
+
+#[test]
+fn text_lengths_and_functions() {
+    let row = folder();
+    // "report.final.txt" is 16 chars; the extension is "txt".
+    assert_eq!(
+        field("stem=len(name) - len(extension)").eval(&row, 0),
+        Some(13)
+    );
+    assert_eq!(field("chars=name_length").eval(&row, 0), Some(16));
+    assert_eq!(field("plen=len(path)").eval(&row, 0), Some(24));
+    assert_eq!(field("gap=abs(size - allocated)").eval(&row, 0), Some(3096));
+    assert_eq!(field("low=min(size, allocated)").eval(&row, 0), Some(1000));
+    assert_eq!(field("high=max(size, allocated)").eval(&row, 0), Some(4096));
+}
+
+#[test]
+fn timestamps_subtract_to_seconds_and_age_in_days() {
+    let row = folder();
+    assert_eq!(field("lag=modified - created").eval(&row, 0), Some(86_400));
+    let age = field("age=age_days(modified)");
+    assert!(age.uses_now());
+    assert_eq!(age.eval(&row, 41 * TICKS_PER_DAY), Some(30));
+    assert_eq!(
+        field("age=age_days(max(created, modified))").eval(&row, 12 * TICKS_PER_DAY),
+        Some(1)
+    );
+    assert!(!field("lag=modified - created").uses_now());
+}
+
+#[test]
+fn undefined_values_propagate() {
+    let mut row = folder();
+    row.descendants = 0;
+    assert_eq!(field("avgsize=treesize/descendants").eval(&row, 0), None);
+    row.accessed = 0;
+    assert_eq!(field("idle=accessed - modified").eval(&row, 0), None);
+    assert_eq!(field("x=9223372036854775807 + 1").eval(&row, 0), None);
+}
+
+#[test]
+fn fields_reference_each_other_in_any_order() {
+    let fields = ComputedFields::parse(["ratio=slack * 100 / allocated", "slack=allocated-size"])
+        .expect("compiles");
+    let ratio = fields.get("RATIO").expect("case-insensitive lookup");
+    assert_eq!(ratio.eval(&folder(), 0), Some(75));
+}
+
+#[test]
+fn later_definition_replaces_earlier() {
+    let fields = ComputedFields::parse(["x=size", "X=allocated"]).expect("compiles");
+    assert_eq!(fields.iter().count(), 1);
+    assert_eq!(
+        fields.get("x").and_then(|x| x.eval(&folder(), 0)),
+        Some(4096)
+    );
+}
+
+#[test]
+fn rejects_bad_definitions() {
+    let err = |def: &str| ComputedFields::parse([def]).expect_err(def);
+    assert!(matches!(err("nosign"), ComputedError::MissingEquals { .. }));
+    assert!(matches!(err("2x=size"), ComputedError::InvalidName { .. }));
+    assert!(matches!(err("size=1"), ComputedError::ShadowsField { .. }));
+    assert!(matches!(err("len=1"), ComputedError::ShadowsField { .. }));
+    for bad in [
+        "x=",
+        "x=size +",
+        "x=(size",
+        "x=bogus",
+        "x=name",
+        "x=modified",
+        "x=modified + 1",
+        "x=age_days(size)",
+        "x=min(size, modified)",
+        "x=len(size)",
+        "x=foo(size)",
+        "x=size $ 2",
+    ] {
+        assert!(
+            matches!(err(bad), ComputedError::Expression { .. }),
+            "{bad} should not compile"
+        );
+    }
+    assert!(matches!(
+        ComputedFields::parse(["ping=pong+1", "pong=ping+1"]).expect_err("cycle"),
+        ComputedError::Cycle { .. }
+    ));
+}
+
+#[test]
+fn filter_matches_display_rows_and_compact_records() {
+    let slack = field("slack=allocated-size");
+    let filter = ComputedFilter::new(Arc::clone(&slack), ComputedCmp::Gt, 3000);
+    assert_eq!(filter.now, 0, "clock-free fields keep a stable filter");
+    assert!(filter.matches(&folder()));
+    assert!(!ComputedFilter::new(slack, ComputedCmp::Lte, 3000).matches(&folder()));
+
+    let names = b"notes.md".to_vec();
+    let record = CompactRecord {
+        size: 10,
+        allocated: 4096,
+        name_len: 8,
+        ..CompactRecord::default()
+    };
+    let mut filters = SearchFilters::default();
+    assert!(filters.is_empty());
+    filters.computed.push(filter);
+    assert!(!filters.is_empty());
+    assert!(
+        filters
+            .computed
+            .iter()
+            .all(|each| each.matches(&RecordSource {
+                record: &record,
+                names: &names
+            }))
+    );
+    let stem = field("stem=len(name)-len(extension)");
+    assert_eq!(stem.eval_record(&record, &names, 0), Some(6));
+}
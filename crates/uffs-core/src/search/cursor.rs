@@ -0,0 +1,731 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Keyset pagination over sorted search rows (`search_cursor`).
+//!
+//! A paged column sort is a total order: the sort clauses' keys
+//! ([`SortKeys`]), then the row's identity — volume, then NTFS file
+//! reference — in the primary clause's direction.  A [`SearchCursor`] pins
+//! the last row of a page by that whole position, plus the index version the
+//! page was cut from, and the next page is the rows strictly after it:
+//!
+//! - nothing is repeated, even when the cursor row itself was deleted, renamed
+//!   or resized in between — its position is still a valid bound;
+//! - nothing is skipped that sorts after the cursor, however the rows in front
+//!   of it changed;
+//! - [`PageBound`] pushes the bound into the scan when the sort column comes
+//!   straight from the record, so a deep page costs one page of rows, not every
+//!   row in front of it.
+//!
+//! Relevance order (`--rank`, fuzzy) has no column key: a page resumes after
+//! the cursor row by identity, or at the recorded offset when it is gone.
+//!
+//! [`CursorPage::index_changed`] reports whether the index moved between the
+//! two pages so clients can tell a continuation over the same rows from one
+//! over a changed index.
+//!
+//! The token is a plain `:`-separated string — opaque by contract, like
+//! [`crate::aggregate::AggregateCursor`] — with the length-prefixed keys last
+//! so folded text keys may contain any character.
+
+use core::cmp::Ordering;
+use core::fmt::Write as _;
+
+use uffs_mft::platform::VolumeId;
+
+use super::backend::SortSpec;
+use super::derived::{bulkiness_for_row, semantic_type_for_row, tree_allocated_for_row};
+use super::display_row::DisplayRow;
+use super::field::FieldId;
+use super::filters::extract_extension_after_dot;
+use super::sorting::field_to_attr_bit;
+use crate::compact::CompactRecord;
+
+/// Token format version — bumped if the field layout ever changes.
+const TOKEN_VERSION: &str = "k3";
+
+/// One sort key of a row: a sort clause's value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CursorKey {
+    /// Numeric / flag column value.
+    Int(i64),
+    /// Case-folded string column value.
+    Text(String),
+}
+
+impl CursorKey {
+    /// Key of `row` under `column`, matching how the sort compares it.
+    #[must_use]
+    pub fn of(row: &DisplayRow, column: FieldId) -> Self {
+        let fold = uffs_text::case_fold::CaseFold::default_table();
+        let mut buf = Vec::new();
+        let text = |value: &str, scratch: &mut Vec<u8>| {
+            Self::Text(fold.fold_into(value, scratch).to_owned())
+        };
+        let int = |value: u64| Self::Int(i64::try_from(value).unwrap_or(i64::MAX));
+        match column {
+            FieldId::Size => int(row.size),
+            FieldId::SizeOnDisk => int(row.allocated),
+            FieldId::Created => Self::Int(row.created),
+            FieldId::Modified => Self::Int(row.modified),
+            FieldId::Accessed => Self::Int(row.accessed),
+            FieldId::Descendants => Self::Int(i64::from(row.descendants)),
+            FieldId::TreeSize => int(row.treesize),
+            FieldId::TreeAllocated => int(tree_allocated_for_row(row)),
+            FieldId::Bulkiness => int(bulkiness_for_row(row)),
+            FieldId::NameLength => int(count_u64(row.name().chars().count())),
+            FieldId::PathLength => int(count_u64(row.path.chars().count())),
+            FieldId::Malformed => Self::Int(i64::from(row.malformed)),
+            FieldId::MalformedPath => Self::Int(i64::from(row.malformed_path)),
+            FieldId::Drive | FieldId::Host => Self::Text(row.drive.to_string()),
+            FieldId::Path => text(&row.path, &mut buf),
+            FieldId::PathOnly => text(row.path_dir(), &mut buf),
+            FieldId::Extension => text(extract_extension_after_dot(row.name()), &mut buf),
+            FieldId::Type => text(semantic_type_for_row(row), &mut buf),
+            FieldId::Hidden
+            | FieldId::System
+            | FieldId::Archive
+            | FieldId::ReadOnly
+            | FieldId::Compressed
+            | FieldId::Encrypted
+            | FieldId::Sparse
+            | FieldId::Reparse
+            | FieldId::Offline
+            | FieldId::NotIndexed
+            | FieldId::Temporary
+            | FieldId::Virtual
+            | FieldId::Pinned
+            | FieldId::Unpinned
+            | FieldId::Integrity
+            | FieldId::NoScrub
+            | FieldId::DirectoryFlag
+            | FieldId::RecallOnOpen
+            | FieldId::RecallOnDataAccess => {
+                Self::Int(i64::from(row.flags & field_to_attr_bit(column) != 0))
+            }
+            FieldId::Name
+            | FieldId::Attributes
+            | FieldId::AttributeValue
+            | FieldId::ParityAttributes
+            | FieldId::NameHex
+            | FieldId::Hash
+            | FieldId::MatchedPattern => text(row.name(), &mut buf),
+        }
+    }
+}
+
+/// A row's sort keys under a keyset order, one per sort clause.
+///
+/// Primary first.  `None` is an undefined value (a computed field that
+/// does not apply to the row); it sorts after every defined value in
+/// either direction.
+pub type SortKeys = Vec<Option<CursorKey>>;
+
+/// `row`'s keys under a `column` sort with `tiers` after it.
+#[must_use]
+pub fn column_keys(row: &DisplayRow, column: FieldId, tiers: &[SortSpec]) -> SortKeys {
+    core::iter::once(column)
+        .chain(tiers.iter().map(|tier| tier.column))
+        .map(|clause| Some(CursorKey::of(row, clause)))
+        .collect()
+}
+
+/// Position of the last row of a delivered page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchCursor {
+    /// Index version the page was cut from.
+    pub index_version: u64,
+    /// Rows delivered so far (absolute position of the next row).
+    pub offset: u64,
+    /// Volume of the last delivered row.
+    pub drive: VolumeId,
+    /// NTFS file reference of the last delivered row.
+    pub file_reference: u64,
+    /// Sort keys of the last delivered row (empty in relevance order).
+    pub keys: SortKeys,
+    /// Clock the keys were computed at, for keys that depend on it (a
+    /// computed field such as `age=now-modified`); the next page reuses it
+    /// so those keys stay comparable.  `None` for column sorts.
+    pub now: Option<i64>,
+}
+
+impl SearchCursor {
+    /// Encode as an opaque token.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let mut token = format!(
+            "{TOKEN_VERSION}:{}:{}:{:x}:{}:",
+            self.index_version, self.offset, self.file_reference, self.drive
+        );
+        if let Some(now) = self.now {
+            _ = write!(token, "{now}");
+        }
+        token.push(':');
+        for key in &self.keys {
+            match key {
+                None => token.push_str("n;"),
+                Some(CursorKey::Int(value)) => {
+                    _ = write!(token, "i{value};");
+                }
+                Some(CursorKey::Text(value)) => {
+                    _ = write!(token, "s{};{value}", value.len());
+                }
+            }
+        }
+        token
+    }
+
+    /// Decode a token produced by [`Self::encode`].
+    #[must_use]
+    pub fn decode(token: &str) -> Option<Self> {
+        let mut parts = token.splitn(7, ':');
+        if parts.next()? != TOKEN_VERSION {
+            return None;
+        }
+        let index_version = parts.next()?.parse().ok()?;
+        let offset = parts.next()?.parse().ok()?;
+        let file_reference = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let drive = VolumeId::parse(parts.next()?).ok()?;
+        let now = match parts.next()? {
+            "" => None,
+            clock => Some(clock.parse().ok()?),
+        };
+        let mut rest = parts.next()?;
+        let mut keys = SortKeys::new();
+        while !rest.is_empty() {
+            let (head, tail) = rest.split_once(';')?;
+            let key = match head.split_at_checked(1)? {
+                ("n", "") => None,
+                ("i", int) => Some(CursorKey::Int(int.parse().ok()?)),
+                ("s", len) => {
+                    let (text, after) = tail.split_at_checked(len.parse().ok()?)?;
+                    keys.push(Some(CursorKey::Text(text.to_owned())));
+                    rest = after;
+                    continue;
+                }
+                _ => return None,
+            };
+            keys.push(key);
+            rest = tail;
+        }
+        Some(Self {
+            index_version,
+            offset,
+            drive,
+            file_reference,
+            keys,
+            now,
+        })
+    }
+}
+
+/// How the rows being paged are ordered.
+#[derive(Debug, Clone, Copy)]
+pub enum PageOrder<'a> {
+    /// Sorted by a column, then by `tiers`.
+    Column {
+        /// Primary sort column.
+        column: FieldId,
+        /// `true` = descending.
+        descending: bool,
+        /// Further sort clauses, in order.
+        tiers: &'a [SortSpec],
+    },
+    /// Ordered by relevance (`--rank`, fuzzy) — no column key.
+    Relevance,
+}
+
+/// What cutting one page left behind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorPage {
+    /// Cursor for the following page; `None` on the last page.
+    pub next: Option<SearchCursor>,
+    /// The cursor was cut from a different index version.
+    pub index_changed: bool,
+}
+
+/// Cut `rows` down to the page after `cursor` (the first page when `None`)
+/// in `order`.
+///
+/// In column order `rows` may come in any order and may already be limited
+/// to rows after the cursor; they must include every row that can sort
+/// into the page, plus one to detect a next page.  In relevance order they
+/// must be the full ranked list from position 0.
+pub fn cut_page(
+    rows: &mut Vec<DisplayRow>,
+    cursor: Option<&SearchCursor>,
+    order: PageOrder<'_>,
+    page_size: usize,
+    index_version: u64,
+) -> CursorPage {
+    match order {
+        PageOrder::Column {
+            column,
+            descending,
+            tiers,
+        } => {
+            let directions: Vec<bool> = core::iter::once(descending)
+                .chain(tiers.iter().map(|tier| tier.descending))
+                .collect();
+            keyset_page(
+                rows,
+                cursor,
+                &directions,
+                |row| column_keys(row, column, tiers),
+                page_size,
+                index_version,
+            )
+        }
+        PageOrder::Relevance => relevance_page(rows, cursor, page_size, index_version),
+    }
+}
+
+/// [`cut_page`] for an order given by `keys_of` with one direction per key
+/// (`true` = descending) — the building block for orders the search core
+/// does not know, such as a computed-field sort.
+pub fn keyset_page<F>(
+    rows: &mut Vec<DisplayRow>,
+    cursor: Option<&SearchCursor>,
+    descending: &[bool],
+    keys_of: F,
+    page_size: usize,
+    index_version: u64,
+) -> CursorPage
+where
+    F: Fn(&DisplayRow) -> SortKeys,
+{
+    let bound = cursor.map(Position::of_cursor);
+    let mut keyed: Vec<(SortKeys, DisplayRow)> = rows
+        .drain(..)
+        .map(|row| (keys_of(&row), row))
+        .filter(|(keys, row)| {
+            bound.as_ref().is_none_or(|after| {
+                Position::of_row(keys, row).cmp_in(after, descending) == Ordering::Greater
+            })
+        })
+        .collect();
+    let compare = |lhs: &(SortKeys, DisplayRow), rhs: &(SortKeys, DisplayRow)| {
+        Position::of_row(&lhs.0, &lhs.1).cmp_in(&Position::of_row(&rhs.0, &rhs.1), descending)
+    };
+    let page = page_size.max(1);
+    if keyed.len() > page {
+        // Only the page and the row after it need their order.
+        keyed.select_nth_unstable_by(page, compare);
+        keyed.truncate(page.saturating_add(1));
+    }
+    keyed.sort_unstable_by(compare);
+    let more = keyed.len() > page;
+    keyed.truncate(page);
+    let next = keyed
+        .last()
+        .filter(|_| more)
+        .map(|(keys, last)| SearchCursor {
+            index_version,
+            offset: cursor
+                .map_or(0, |cur| cur.offset)
+                .saturating_add(count_u64(page)),
+            drive: last.drive,
+            file_reference: last.file_reference,
+            keys: keys.clone(),
+            now: None,
+        });
+    rows.extend(keyed.into_iter().map(|(_, row)| row));
+    CursorPage {
+        next,
+        index_changed: cursor.is_some_and(|cur| cur.index_version != index_version),
+    }
+}
+
+/// [`cut_page`] in relevance order: resume after the cursor row, or at the
+/// cursor's offset when that row is gone.
+fn relevance_page(
+    rows: &mut Vec<DisplayRow>,
+    cursor: Option<&SearchCursor>,
+    page_size: usize,
+    index_version: u64,
+) -> CursorPage {
+    let start = cursor.map_or(0, |cur| {
+        rows.iter()
+            .position(|row| row.drive == cur.drive && row.file_reference == cur.file_reference)
+            .map_or_else(
+                || usize::try_from(cur.offset).unwrap_or(usize::MAX),
+                |pos| pos + 1,
+            )
+            .min(rows.len())
+    });
+    let end = start.saturating_add(page_size.max(1)).min(rows.len());
+    let more = end < rows.len();
+    rows.truncate(end);
+    rows.drain(..start);
+    let next = rows.last().filter(|_| more).map(|last| SearchCursor {
+        index_version,
+        offset: count_u64(end),
+        drive: last.drive,
+        file_reference: last.file_reference,
+        keys: SortKeys::new(),
+        now: None,
+    });
+    CursorPage {
+        next,
+        index_changed: cursor.is_some_and(|cur| cur.index_version != index_version),
+    }
+}
+
+/// Widen a row count for the wire.
+fn count_u64(count: usize) -> u64 {
+    u64::try_from(count).unwrap_or(u64::MAX)
+}
+
+/// A row's place in a keyset order.
+struct Position<'k> {
+    /// Sort keys, primary first.
+    keys: &'k [Option<CursorKey>],
+    /// Volume (first identity tiebreaker).
+    drive: VolumeId,
+    /// NTFS file reference (second identity tiebreaker).
+    file_reference: u64,
+}
+
+impl<'k> Position<'k> {
+    /// The position a cursor pins.
+    fn of_cursor(cursor: &'k SearchCursor) -> Self {
+        Self {
+            keys: &cursor.keys,
+            drive: cursor.drive,
+            file_reference: cursor.file_reference,
+        }
+    }
+
+    /// `row`'s position given its `keys`.
+    const fn of_row(keys: &'k [Option<CursorKey>], row: &DisplayRow) -> Self {
+        Self {
+            keys,
+            drive: row.drive,
+            file_reference: row.file_reference,
+        }
+    }
+
+    /// Compare with `other` clause by clause, each in its direction, then
+    /// by identity in the primary clause's direction.
+    fn cmp_in(&self, other: &Self, descending: &[bool]) -> Ordering {
+        self.keys
+            .iter()
+            .zip(other.keys)
+            .zip(descending)
+            .map(|((lhs, rhs), desc)| match (lhs, rhs) {
+                (Some(lkey), Some(rkey)) if *desc => rkey.cmp(lkey),
+                (Some(lkey), Some(rkey)) => lkey.cmp(rkey),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+            .find(|order| order.is_ne())
+            .unwrap_or_else(|| {
+                let identity =
+                    (self.drive, self.file_reference).cmp(&(other.drive, other.file_reference));
+                if descending.first().copied().unwrap_or(false) {
+                    identity.reverse()
+                } else {
+                    identity
+                }
+            })
+    }
+}
+
+/// A cursor pushed into the record scan: records at or before it are
+/// dropped before they take a result slot.
+///
+/// Only single-clause sorts on a column whose value comes straight from the
+/// record (sizes, timestamps, descendant count, attribute flags) can be
+/// bound exactly at the record; other orders page over an unlimited scan.
+#[derive(Debug, Clone)]
+pub struct PageBound {
+    /// Sort column.
+    column: FieldId,
+    /// `true` = descending.
+    descending: bool,
+    /// The cursor row's sort key.
+    key: i64,
+    /// The cursor row's volume.
+    drive: VolumeId,
+    /// The cursor row's file reference.
+    file_reference: u64,
+    /// The scanned drive's volume against [`Self::drive`]; set per drive by
+    /// [`Self::bind_drive`].
+    drive_order: Ordering,
+}
+
+impl PageBound {
+    /// The bound after `cursor` in a `column` sort with no further clauses,
+    /// or `None` when that order cannot be bound at the record.
+    #[must_use]
+    pub fn new(cursor: &SearchCursor, column: FieldId, descending: bool) -> Option<Self> {
+        let [Some(CursorKey::Int(key))] = cursor.keys.as_slice() else {
+            return None;
+        };
+        record_key(&CompactRecord::default(), column)?;
+        Some(Self {
+            column,
+            descending,
+            key: *key,
+            drive: cursor.drive,
+            file_reference: cursor.file_reference,
+            drive_order: Ordering::Equal,
+        })
+    }
+
+    /// Prepare to scan the drive with `volume`.
+    pub(crate) fn bind_drive(&mut self, volume: VolumeId) {
+        self.drive_order = volume.cmp(&self.drive);
+    }
+
+    /// Whether `rec`, on the bound drive, sorts strictly after the cursor.
+    pub(crate) fn admits(&self, rec: &CompactRecord) -> bool {
+        let Some(key) = record_key(rec, self.column) else {
+            return true;
+        };
+        let order = key
+            .cmp(&self.key)
+            .then(self.drive_order)
+            .then_with(|| rec.file_ref.cmp(&self.file_reference));
+        if self.descending {
+            order == Ordering::Less
+        } else {
+            order == Ordering::Greater
+        }
+    }
+}
+
+/// `rec`'s key under `column` — the value [`CursorKey::of`] reads from its
+/// row — for the columns copied verbatim from the record.
+fn record_key(rec: &CompactRecord, column: FieldId) -> Option<i64> {
+    let int = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+    Some(match column {
+        FieldId::Size => int(rec.size),
+        FieldId::SizeOnDisk => int(rec.allocated),
+        FieldId::Created => rec.created,
+        FieldId::Modified => rec.modified,
+        FieldId::Accessed => rec.accessed,
+        FieldId::Descendants => i64::from(rec.descendants),
+        FieldId::TreeSize => int(rec.treesize),
+        FieldId::Hidden
+        | FieldId::System
+        | FieldId::Archive
+        | FieldId::ReadOnly
+        | FieldId::Compressed
+        | FieldId::Encrypted
+        | FieldId::Sparse
+        | FieldId::Reparse
+        | FieldId::Offline
+        | FieldId::NotIndexed
+        | FieldId::Temporary
+        | FieldId::Virtual
+        | FieldId::Pinned
+        | FieldId::Unpinned
+        | FieldId::Integrity
+        | FieldId::NoScrub
+        | FieldId::DirectoryFlag
+        | FieldId::RecallOnOpen
+        | FieldId::RecallOnDataAccess => i64::from(rec.flags & field_to_attr_bit(column) != 0),
+        FieldId::TreeAllocated
+        | FieldId::Bulkiness
+        | FieldId::NameLength
+        | FieldId::PathLength
+        | FieldId::Malformed
+        | FieldId::MalformedPath
+        | FieldId::Drive
+        | FieldId::Host
+        | FieldId::Path
+        | FieldId::PathOnly
+        | FieldId::Extension
+        | FieldId::Type
+        | FieldId::Name
+        | FieldId::Attributes
+        | FieldId::AttributeValue
+        | FieldId::ParityAttributes
+        | FieldId::NameHex
+        | FieldId::Hash
+        | FieldId::MatchedPattern => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, size: u64, file_reference: u64) -> DisplayRow {
+        DisplayRow::new(
+            0,
+            uffs_mft::platform::DriveLetter::C,
+            format!("C:\\{name}"),
+            size,
+            false,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        )
+        .with_file_reference(file_reference)
+    }
+
+    const BY_SIZE_DESC: PageOrder<'static> = PageOrder::Column {
+        column: FieldId::Size,
+        descending: true,
+        tiers: &[],
+    };
+
+    /// Cut a page out of a copy of `rows`, returning its names.
+    fn page_of(
+        rows: &[DisplayRow],
+        cursor: Option<&SearchCursor>,
+        order: PageOrder<'_>,
+        version: u64,
+    ) -> (Vec<String>, CursorPage) {
+        let mut cut = rows.to_vec();
+        let page = cut_page(&mut cut, cursor, order, 2, version);
+        let names = cut.iter().map(|row| row.name().to_owned()).collect();
+        (names, page)
+    }
+
+    #[test]
+    fn cursor_roundtrip_keeps_separators_in_text_keys() {
+        let cursor = SearchCursor {
+            index_version: 7,
+            offset: 500,
+            drive: VolumeId::parse("srv01\\D").expect("valid volume"),
+            file_reference: 0x0001_0000_0000_002A,
+            keys: vec![
+                Some(CursorKey::Text("c:\\a;b:".to_owned())),
+                None,
+                Some(CursorKey::Int(-3)),
+            ],
+            now: Some(1_700_000_000),
+        };
+        assert_eq!(SearchCursor::decode(&cursor.encode()), Some(cursor));
+        let relevance = SearchCursor::decode("k3:1:2:ff:C::").expect("valid token");
+        assert!(relevance.keys.is_empty());
+        assert_eq!(SearchCursor::decode(&relevance.encode()), Some(relevance));
+    }
+
+    #[test]
+    fn decode_rejects_malformed_tokens() {
+        for bad in [
+            "",
+            "offset:50",
+            "k2:1:2:ff:C:i1;",
+            "k3:1:2:ff:C:i1;",
+            "k3:1:2:zz:C::i1;",
+            "k3:1:2:ff:C:soon:i1;",
+            "k3:1:2:ff:C::x1;",
+            "k3:1:2:ff:C::i1",
+            "k3:1:2:ff:C::s9;short",
+        ] {
+            assert!(SearchCursor::decode(bad).is_none(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn pages_continue_strictly_after_the_cursor_row() {
+        let rows = vec![
+            row("c", 30, 3),
+            row("a", 50, 1),
+            row("e", 10, 5),
+            row("b", 40, 2),
+            row("d", 20, 4),
+        ];
+        let (first, first_page) = page_of(&rows, None, BY_SIZE_DESC, 9);
+        assert_eq!(first, ["a", "b"]);
+        let cursor = first_page.next.expect("more rows");
+        assert_eq!(cursor.offset, 2);
+        let (second, second_page) = page_of(&rows, Some(&cursor), BY_SIZE_DESC, 9);
+        assert_eq!(second, ["c", "d"]);
+        assert!(!second_page.index_changed);
+        let (last, last_page) = page_of(&rows, second_page.next.as_ref(), BY_SIZE_DESC, 9);
+        assert_eq!(last, ["e"]);
+        assert!(last_page.next.is_none());
+    }
+
+    #[test]
+    fn ties_page_by_identity_without_repeats() {
+        let rows = vec![
+            row("z", 10, 4),
+            row("y", 10, 2),
+            row("x", 10, 3),
+            row("w", 10, 1),
+        ];
+        let (first, first_page) = page_of(&rows, None, BY_SIZE_DESC, 1);
+        // Descending reverses the identity tiebreaker too.
+        assert_eq!(first, ["z", "x"]);
+        let (rest, _) = page_of(&rows, first_page.next.as_ref(), BY_SIZE_DESC, 1);
+        assert_eq!(rest, ["y", "w"]);
+    }
+
+    #[test]
+    fn vanished_cursor_row_still_bounds_the_next_page() {
+        let before = vec![
+            row("a", 50, 1),
+            row("b", 40, 2),
+            row("c", 30, 3),
+            row("d", 20, 4),
+        ];
+        let cursor = page_of(&before, None, BY_SIZE_DESC, 1)
+            .1
+            .next
+            .expect("more rows");
+        // `b` was deleted and `x` inserted ahead of the cursor in between.
+        let after = vec![
+            row("x", 60, 9),
+            row("a", 50, 1),
+            row("c", 30, 3),
+            row("d", 20, 4),
+        ];
+        let (names, page) = page_of(&after, Some(&cursor), BY_SIZE_DESC, 2);
+        assert_eq!(names, ["c", "d"]);
+        assert!(page.index_changed);
+    }
+
+    #[test]
+    fn relevance_order_falls_back_to_the_offset() {
+        let rows = vec![row("a", 0, 1), row("b", 0, 2), row("c", 0, 3)];
+        let mut cut = rows.clone();
+        let mut cursor = cut_page(&mut cut, None, PageOrder::Relevance, 1, 1)
+            .next
+            .expect("more rows");
+        let (names, _) = page_of(&rows, Some(&cursor), PageOrder::Relevance, 1);
+        assert_eq!(names, ["b", "c"]);
+        cursor.file_reference = 99;
+        let (fallback, _) = page_of(&rows, Some(&cursor), PageOrder::Relevance, 1);
+        assert_eq!(fallback, ["b", "c"]);
+    }
+
+    #[test]
+    fn page_bound_admits_only_records_after_the_cursor() {
+        let cursor = SearchCursor {
+            index_version: 1,
+            offset: 2,
+            drive: VolumeId::parse("D").expect("valid volume"),
+            file_reference: 7,
+            keys: vec![Some(CursorKey::Int(100))],
+            now: None,
+        };
+        let record = |size: u64, file_ref: u64| CompactRecord {
+            size,
+            file_ref,
+            ..CompactRecord::default()
+        };
+        let mut bound = PageBound::new(&cursor, FieldId::Size, true).expect("record column");
+        bound.bind_drive(VolumeId::parse("D").expect("valid volume"));
+        assert!(bound.admits(&record(99, 9)));
+        assert!(bound.admits(&record(100, 6)));
+        assert!(!bound.admits(&record(100, 7)));
+        assert!(!bound.admits(&record(100, 8)));
+        assert!(!bound.admits(&record(101, 1)));
+        bound.bind_drive(VolumeId::parse("C").expect("valid volume"));
+        assert!(bound.admits(&record(100, 8)));
+        assert!(PageBound::new(&cursor, FieldId::Bulkiness, true).is_none());
+    }
+}
@@ -56,7 +56,7 @@ pub fn display_rows_to_dataframe(
         .iter()
         .map(|row| {
             DRIVE_LABELS
-                .get(row.drive.alphabet_index())
+                .get(row.drive.letter().alphabet_index())
                 .copied()
                 .unwrap_or("?:")
         })
@@ -22,8 +22,8 @@
 //!    (same primitive the CLI parse layer uses, so both agree).
 //!
 //! 2. **Per-branch dispatchers** ([`dispatch_match_all`], [`dispatch_regex`],
-//!    [`dispatch_trigram_or_tree`]) — the three leaf dispatch paths +
-//!    [`pick_mode_label`] for tracing.
+//!    [`dispatch_fuzzy`], [`dispatch_trigram_or_tree`]) — the four leaf
+//!    dispatch paths + [`pick_mode_label`] for tracing.
 
 use alloc::borrow::Cow;
 
@@ -31,6 +31,7 @@ use rayon::prelude::*;
 
 use super::backend::{DisplayRow, FilterMode, PhaseTimings, SortSpec};
 use super::filters::SearchFilters;
+use super::fuzzy::{DEFAULT_THRESHOLD, FuzzyNeedle, PATTERN_PREFIX};
 use super::sorting::sort_rows;
 use crate::compact::DriveCompactIndex;
 use crate::search::field::FieldId;
@@ -187,7 +188,7 @@ fn extract_extensions_from_regex(pattern: &str) -> Option<Vec<String>> {
 ///    becomes the new pattern and downstream classification routes it to
 ///    match-all / name / tree as appropriate.  The promoted letter is pushed
 ///    into `drive_buf` (which the caller uses as backing storage for a
-///    `&[DriveLetter]` slice that lives for the rest of the dispatch).
+///    `&[VolumeId]` slice that lives for the rest of the dispatch).
 ///
 /// 2. `*.<ext>` → `pattern = "*"`, `extensions += [<ext_lower>]`. Only fires
 ///    when not `match_path`, not `case_sensitive`, and
@@ -219,7 +220,7 @@ pub(super) fn apply_dispatch_safety_nets(
     case_sensitive: bool,
     drives_filter_empty: bool,
     search_filters: &mut SearchFilters,
-    drive_buf: &mut Vec<uffs_mft::platform::DriveLetter>,
+    drive_buf: &mut Vec<uffs_mft::platform::VolumeId>,
 ) {
     if drives_filter_empty
         && !match_path
@@ -232,7 +233,7 @@ pub(super) fn apply_dispatch_safety_nets(
             "promoted <letter>:<rest> to drive filter (dispatch-time safety net)"
         );
         *pattern = rest;
-        drive_buf.push(letter);
+        drive_buf.push(letter.into());
     }
 
     if !match_path
@@ -351,6 +352,54 @@ pub(super) fn dispatch_regex(
     Some(rows)
 }
 
+/// Strip a leading [`PATTERN_PREFIX`] (`fuzzy:`) from `pattern`, switching
+/// the query to fuzzy mode at the default threshold unless the caller already
+/// chose one.  Runs before the other rewrites so `fuzzy:C:invoce` still gets
+/// its drive prefix split off.
+pub(super) fn take_fuzzy_prefix(pattern: &mut &str, fuzzy: &mut Option<f32>) {
+    if let Some(rest) = pattern.strip_prefix(PATTERN_PREFIX) {
+        *pattern = rest;
+        fuzzy.get_or_insert(DEFAULT_THRESHOLD);
+    }
+}
+
+/// Dispatch the fuzzy branch: score trigram-overlap candidates on every
+/// drive, then merge, filter and rank by score (best first).  The sort
+/// column is deliberately ignored — fuzzy results are ordered by relevance.
+pub(super) fn dispatch_fuzzy(
+    active_drives: &[&DriveCompactIndex],
+    pattern: &str,
+    threshold: f32,
+    limit: usize,
+    filter_mode: FilterMode,
+    search_filters: &SearchFilters,
+) -> Vec<DisplayRow> {
+    let fold = active_drives
+        .first()
+        .map_or_else(uffs_text::case_fold::CaseFold::default_table, |drive| {
+            drive.fold
+        });
+    let needle = FuzzyNeedle::new(pattern, threshold, fold);
+    let mut scored: Vec<(u16, DisplayRow)> = active_drives
+        .par_iter()
+        .flat_map_iter(|drive| {
+            super::query::search_compact_drive_fuzzy(
+                drive,
+                &needle,
+                limit,
+                filter_mode,
+                search_filters,
+            )
+        })
+        .collect();
+    // Stable sort: equal scores keep drive order, then per-drive index order.
+    scored.sort_by_key(|&(score, _)| core::cmp::Reverse(score));
+    let mut rows: Vec<DisplayRow> = scored.into_iter().map(|(_, row)| row).collect();
+    super::filters::apply_search_filters(&mut rows, search_filters);
+    rows.truncate(limit);
+    rows
+}
+
 /// Dispatch the default branch: tree-walk for path patterns, trigram-
 /// accelerated prefix scan for prefix patterns (`win*`), trigram for the
 /// remaining name patterns — all fanned across drives then filtered +
@@ -431,16 +480,19 @@ pub(super) fn dispatch_trigram_or_tree(
 /// dispatch branch.  Pure function — no side effects.
 #[expect(
     clippy::fn_params_excessive_bools,
-    reason = "the four bools are independent runtime dispatch flags; bundling into an enum would lose orthogonality and not improve clarity"
+    reason = "the five bools are independent runtime dispatch flags; bundling into an enum would lose orthogonality and not improve clarity"
 )]
 pub(super) const fn pick_mode_label(
     is_match_all: bool,
+    is_fuzzy: bool,
     is_regex: bool,
     is_path: bool,
     is_prefix: bool,
 ) -> &'static str {
     if is_match_all {
         "match-all"
+    } else if is_fuzzy {
+        "fuzzy"
     } else if is_regex {
         "regex"
     } else if is_path {
@@ -580,7 +632,7 @@ mod tests {
         filters: &mut SearchFilters,
     ) -> (&'a str, Vec<uffs_mft::platform::DriveLetter>) {
         let mut pat: &str = pattern;
-        let mut drive_buf: Vec<uffs_mft::platform::DriveLetter> = Vec::new();
+        let mut drive_buf: Vec<uffs_mft::platform::VolumeId> = Vec::new();
         apply_dispatch_safety_nets(
             &mut pat,
             false, // match_path
@@ -589,7 +641,11 @@ mod tests {
             filters,
             &mut drive_buf,
         );
-        (pat, drive_buf)
+        let letters = drive_buf
+            .into_iter()
+            .map(uffs_mft::platform::VolumeId::letter)
+            .collect();
+        (pat, letters)
     }
 
     #[test]
@@ -14,14 +14,14 @@
 /// field using `name_start` (byte offset where the filename begins within
 /// `path`).  This avoids one heap allocation per result row.
 ///
-/// `Default` is implemented manually below: [`uffs_mft::platform::DriveLetter`]
+/// `Default` is implemented manually below: [`uffs_mft::platform::VolumeId`]
 /// has no `Default` impl (it's a validated `A..=Z` newtype with no canonical
 /// zero), but the `sort_rows_with_fold` hot path uses
 /// [`core::mem::take`] to move rows out of a `&mut [DisplayRow]` slice
 /// as part of a Schwartzian decorate/sort/undecorate transform.  The
 /// take leaves a transient placeholder in the slice that's
 /// immediately overwritten by the put-back step, so any consistent
-/// drive letter works for the placeholder.
+/// volume works for the placeholder.
 #[derive(Debug, Clone)]
 #[expect(
     clippy::partial_pub_fields,
@@ -30,14 +30,14 @@
 pub struct DisplayRow {
     /// Record index within the compact/cache file.
     pub record_index: u32,
-    /// Drive letter this result belongs to.
-    pub drive: uffs_mft::platform::DriveLetter,
+    /// Volume (host label + drive letter) this result belongs to.
+    pub drive: uffs_mft::platform::VolumeId,
     /// Full resolved path (e.g., `C:\Users\file.txt`).
     pub path: String,
     /// Byte offset within `path` where the filename begins.
     ///
     /// `self.name()` returns `&self.path[name_start..]`.
-    /// Computed once at construction from the last `\` separator.
+    /// Computed once at construction from the last `\` (or `/`) separator.
     name_start: u32,
     /// File size in bytes.
     pub size: u64,
@@ -80,6 +80,13 @@ pub struct DisplayRow {
     /// hot path's `CompactRecord`, mirroring [`Self::with_forensics`]'s
     /// pattern so `new()`'s existing call sites stay untouched.
     pub file_reference: u64,
+    /// Cached full-content digest (`blake3:…`) for the `hash` column.
+    /// `None` unless the daemon filled it from its content-hash cache —
+    /// the search path never reads file content.
+    pub content_hash: Option<String>,
+    /// The `--patterns-from` entry this row's name matched, for the
+    /// `matched_pattern` column.  `None` unless the daemon filled it.
+    pub matched_pattern: Option<String>,
 }
 
 impl DisplayRow {
@@ -89,9 +96,9 @@ impl DisplayRow {
         clippy::too_many_arguments,
         reason = "flat struct — all fields are required, no logical grouping"
     )]
-    pub fn new(
+    pub fn new<V: Into<uffs_mft::platform::VolumeId>>(
         record_index: u32,
-        drive: uffs_mft::platform::DriveLetter,
+        drive: V,
         path: String,
         size: u64,
         is_directory: bool,
@@ -104,10 +111,10 @@ impl DisplayRow {
         treesize: u64,
         tree_allocated: u64,
     ) -> Self {
-        let name_start = uffs_mft::len_to_u32(path.rfind('\\').map_or(0, |pos| pos + 1));
+        let name_start = uffs_mft::len_to_u32(path.rfind(['\\', '/']).map_or(0, |pos| pos + 1));
         Self {
             record_index,
-            drive,
+            drive: drive.into(),
             path,
             name_start,
             size,
@@ -128,6 +135,8 @@ impl DisplayRow {
             malformed_path: false,
             name_hex: None,
             file_reference: 0,
+            content_hash: None,
+            matched_pattern: None,
         }
     }
 
@@ -203,14 +212,14 @@ impl DisplayRow {
 /// Manual `Default` impl — see the struct doc-comment for why we
 /// don't derive it.  All fields default to their natural zero
 /// (`0`, `String::new()`, `false`) except `drive`, which we set to
-/// [`uffs_mft::platform::DriveLetter::A`] purely as a placeholder for
+/// local `A:` purely as a placeholder for
 /// [`core::mem::take`] in the sort hot path.  Callers never observe
 /// this value: the take is immediately followed by a put-back.
 impl Default for DisplayRow {
     fn default() -> Self {
         Self {
             record_index: 0,
-            drive: uffs_mft::platform::DriveLetter::A,
+            drive: uffs_mft::platform::VolumeId::local(uffs_mft::platform::DriveLetter::A),
             path: String::new(),
             name_start: 0,
             size: 0,
@@ -227,6 +236,8 @@ impl Default for DisplayRow {
             malformed_path: false,
             name_hex: None,
             file_reference: 0,
+            content_hash: None,
+            matched_pattern: None,
         }
     }
 }
@@ -243,7 +254,11 @@ impl uffs_format::FormatRow for DisplayRow {
         // doesn't depend on `uffs-mft`, so the trait surface
         // stays `char`.  `DriveLetter::as_char` is the canonical
         // zero-cost conversion to the ASCII letter.
-        self.drive.as_char()
+        self.drive.letter().as_char()
+    }
+    #[inline]
+    fn host(&self) -> &'static str {
+        self.drive.host_str()
     }
     #[inline]
     fn path(&self) -> &str {
@@ -305,4 +320,12 @@ impl uffs_format::FormatRow for DisplayRow {
     fn name_hex(&self) -> Option<&str> {
         self.name_hex.as_deref()
     }
+    #[inline]
+    fn content_hash(&self) -> Option<&str> {
+        self.content_hash.as_deref()
+    }
+    #[inline]
+    fn matched_pattern(&self) -> Option<&str> {
+        self.matched_pattern.as_deref()
+    }
 }
@@ -100,6 +100,16 @@ pub enum FieldId {
     /// distinguishing ill-formed names that all display as U+FFFD. Projection
     /// only; never filtered or sorted.
     NameHex,
+    /// Host label of the record's volume — empty for local volumes, the
+    /// capture's machine name for offline estates (`srv01` in `srv01\C`).
+    Host,
+    /// Cached full-content digest from duplicate verification
+    /// (`blake3:…`), filled by the daemon's content-hash cache. Projection
+    /// only; empty for files never hashed.
+    Hash,
+    /// The `--patterns-from` entry that matched the row's name, filled by
+    /// the daemon. Projection only; empty without a pattern list.
+    MatchedPattern,
 }
 
 /// Cardinality hint for aggregation planning.
@@ -268,6 +278,9 @@ impl FieldId {
         Self::Malformed,
         Self::MalformedPath,
         Self::NameHex,
+        Self::Host,
+        Self::Hash,
+        Self::MatchedPattern,
     ];
 
     /// Parse a field name or alias into the canonical identifier.
@@ -379,7 +392,10 @@ impl FieldId {
             | Self::PathLength
             | Self::Malformed
             | Self::MalformedPath
-            | Self::NameHex => None,
+            | Self::NameHex
+            | Self::Host
+            | Self::Hash
+            | Self::MatchedPattern => None,
         }
     }
 
@@ -432,6 +448,7 @@ impl FieldId {
             Self::Extension => Self::Extension,
             Self::Type => Self::Type,
             Self::Drive => Self::Drive,
+            Self::Host => Self::Host,
             Self::Descendants => Self::Descendants,
             Self::TreeAllocated => Self::TreeAllocated,
             Self::Bulkiness => Self::Bulkiness,
@@ -462,7 +479,9 @@ impl FieldId {
             | Self::ParityAttributes
             | Self::Malformed
             | Self::MalformedPath
-            | Self::NameHex => Self::Name,
+            | Self::NameHex
+            | Self::Hash
+            | Self::MatchedPattern => Self::Name,
         }
     }
 }
@@ -8,7 +8,7 @@ use super::super::derived::is_system_name;
 use super::super::tree::name_matches;
 use super::{
     SearchFilters, extension_matches_filter, extract_extension_after_dot, lowercase_into,
-    month_from_unix_micros,
+    month_from_filetime_in,
 };
 
 impl SearchFilters {
@@ -134,6 +134,11 @@ pub(crate) fn row_passes_filters(
             return false;
         }
     }
+    if let Some(patterns) = &filters.name_patterns
+        && !patterns.matches_folded(fold.fold_into(row.name(), fold_buf))
+    {
+        return false;
+    }
     apply_derived_filters(row, filters)
 }
 
@@ -268,10 +273,11 @@ fn apply_derived_filters(row: &DisplayRow, filters: &SearchFilters) -> bool {
     }
     // ── Month-of-year filter ───────────────────────────────────
     if !filters.allowed_months.is_empty() {
-        let month = month_from_unix_micros(row.modified);
+        let month = month_from_filetime_in(row.modified, filters.time_zone);
         if !filters.allowed_months.contains(&month) {
             return false;
         }
     }
-    true
+    // ── Computed-field filters ─────────────────────────────────
+    filters.computed.iter().all(|filter| filter.matches(row))
 }
@@ -22,6 +22,8 @@ mod time_parsing;
 // own doc comment for why a downstream crate needs to call it directly.
 // The `apply::*` glob below stays `pub(crate)`: everything else in
 // `apply` (e.g. `row_passes_filters`) is an internal helper.
+use alloc::sync::Arc;
+
 pub use apply::apply_search_filters;
 pub(crate) use apply::*;
 pub use attr_parsing::*;
@@ -29,8 +31,13 @@ pub(crate) use ext_match::extract_extension_after_dot;
 pub(super) use ext_match::{extension_matches_filter, lowercase_into};
 pub(super) use path_normalize::normalize_path_separators;
 pub use time_parsing::*;
+use uffs_time::TimeZone;
 
 use super::backend::{DisplayRow, FilterMode};
+use super::computed::{ComputedFilter, RecordSource};
+use super::cursor::PageBound;
+use super::pattern_set::PatternSet;
+use super::rank::RankCut;
 use crate::compact::CompactRecord;
 use crate::search::tree::name_matches;
 
@@ -144,6 +151,9 @@ pub struct SearchFilters {
     /// Set of allowed months (1-12). Empty = no filter.
     /// Used for "every January" or "Q1" style queries.
     pub allowed_months: Vec<u32>,
+    /// Zone the month filter reads modification dates in (the time
+    /// bounds above are already resolved to UTC instants in it).
+    pub time_zone: TimeZone,
 
     // ── WI-4.4 malformed-name filter ────────────────────────────────
     /// Filter on whether the record's own leaf name is ill-formed (its true
@@ -170,6 +180,25 @@ pub struct SearchFilters {
     /// column so downstream tooling can spot/round-trip corrupt entries.
     /// `false` = default lossy rendering (matches the reference C++ tool).
     pub normalize_malformed: bool,
+
+    /// `--patterns-from` list: the name must match at least one entry.
+    /// Set by the caller after [`Self::from_params`]; an extension-only
+    /// list goes into [`Self::extensions`] instead (see
+    /// [`PatternSet::extensions`]).
+    pub name_patterns: Option<Arc<PatternSet>>,
+
+    /// Computed-field comparisons (`--where 'slack > 1MB'`); every one must
+    /// hold.  Set by the caller after [`Self::from_params`].
+    pub computed: Vec<ComputedFilter>,
+
+    /// `search_cursor` bound: only records sorting strictly after the
+    /// cursor row pass.  Set by the caller after [`Self::from_params`].
+    pub page_after: Option<PageBound>,
+
+    /// `--rank` top-N for the per-drive scans — not a filter: every hit
+    /// still matches, a drive just resolves only its best.  Set by
+    /// `search_index` for the length of one ranked scan.
+    pub rank: Option<Arc<RankCut>>,
 }
 
 impl SearchFilters {
@@ -257,6 +286,9 @@ pub struct SearchFilterParams<'a> {
     pub max_tree_allocated: Option<u64>,
     /// Allowed month numbers (1-12).
     pub allowed_months: &'a [u32],
+    /// Zone calendar dates in the time specs and the month filter are
+    /// read in (`--tz`); UTC by default.
+    pub time_zone: TimeZone,
 }
 
 impl SearchFilters {
@@ -274,6 +306,7 @@ impl SearchFilters {
     )]
     pub fn from_params(params: &SearchFilterParams<'_>) -> Self {
         let now_us = now_unix_micros();
+        let tz = params.time_zone;
         let extensions: Vec<String> = params
             .ext_filter
             .map(|ext_list| {
@@ -368,22 +401,22 @@ impl SearchFilters {
             max_size: params.max_size,
             newer_us: params
                 .newer
-                .and_then(|spec| parse_time_bound(spec, now_us, true)),
+                .and_then(|spec| parse_time_bound_in(spec, now_us, true, tz)),
             older_us: params
                 .older
-                .and_then(|spec| parse_time_bound(spec, now_us, false)),
+                .and_then(|spec| parse_time_bound_in(spec, now_us, false, tz)),
             newer_created_us: params
                 .newer_created
-                .and_then(|spec| parse_time_bound(spec, now_us, true)),
+                .and_then(|spec| parse_time_bound_in(spec, now_us, true, tz)),
             older_created_us: params
                 .older_created
-                .and_then(|spec| parse_time_bound(spec, now_us, false)),
+                .and_then(|spec| parse_time_bound_in(spec, now_us, false, tz)),
             newer_accessed_us: params
                 .newer_accessed
-                .and_then(|spec| parse_time_bound(spec, now_us, true)),
+                .and_then(|spec| parse_time_bound_in(spec, now_us, true, tz)),
             older_accessed_us: params
                 .older_accessed
-                .and_then(|spec| parse_time_bound(spec, now_us, false)),
+                .and_then(|spec| parse_time_bound_in(spec, now_us, false, tz)),
             attr_require: parse_attr_require(params.attr_filter.unwrap_or("")),
             attr_exclude: parse_attr_exclude(params.attr_filter.unwrap_or("")),
             min_descendants: params.min_descendants,
@@ -410,6 +443,7 @@ impl SearchFilters {
             min_tree_allocated: params.min_tree_allocated,
             max_tree_allocated: params.max_tree_allocated,
             allowed_months: params.allowed_months.to_vec(),
+            time_zone: tz,
             // The malformed-name filter is set by the daemon's canonical
             // predicate compiler (it is not a legacy positional param), so the
             // param-based constructor leaves it disabled.
@@ -420,6 +454,14 @@ impl SearchFilters {
             // Display-only; the daemon sets it from the request's
             // `normalize_malformed` flag, so it defaults off here.
             normalize_malformed: false,
+            // The daemon compiles `--patterns-from` lists itself.
+            name_patterns: None,
+            // Computed fields are compiled by the daemon per request.
+            computed: Vec::new(),
+            // The daemon binds a page cursor per request.
+            page_after: None,
+            // Installed by the ranked scan itself.
+            rank: None,
         }
     }
 
@@ -475,6 +517,9 @@ impl SearchFilters {
     /// Pre-resolve extension filter strings to `u16` IDs for a specific
     /// drive.  Call this **once per drive** before the hot record loop.
     pub(crate) fn resolve_ext_ids_for_drive(&mut self, drive: &crate::compact::DriveCompactIndex) {
+        if let Some(bound) = &mut self.page_after {
+            bound.bind_drive(drive.volume());
+        }
         if self.extensions.is_empty() {
             self.resolved_ext_ids.clear();
             tracing::trace!(drive = %drive.letter, "no extension filter active for drive");
@@ -573,6 +618,9 @@ impl SearchFilters {
             && self.min_tree_allocated.is_none()
             && self.max_tree_allocated.is_none()
             && self.allowed_months.is_empty()
+            && self.name_patterns.is_none()
+            && self.computed.is_empty()
+            && self.page_after.is_none()
     }
 
     /// Check whether a compact record passes all filters.
@@ -605,6 +653,11 @@ impl SearchFilters {
                 return false;
             }
         }
+        if let Some(bound) = &self.page_after
+            && !bound.admits(rec)
+        {
+            return false;
+        }
         if let Some(min) = self.min_size
             && rec.size < min
         {
@@ -693,15 +746,31 @@ impl SearchFilters {
                 return false;
             }
         }
-        if let Some(excl) = &self.exclude_lower {
-            // Zero-alloc via CaseFold: fold the name into a reusable buffer.
-            let name = rec.name(names);
-            let folded_name = fold.fold_into(name, fold_buf);
-            if name_matches(folded_name, excl) {
-                return false;
-            }
+        self.matches_folded_name(rec, names, fold_buf, fold) && self.matches_derived(rec, names)
+    }
+
+    /// Check the filters on the case-folded name: `--exclude` and the
+    /// `--patterns-from` list.  The name is folded once, into `fold_buf`
+    /// (zero-alloc), and only when either is set.
+    fn matches_folded_name(
+        &self,
+        rec: &CompactRecord,
+        names: &[u8],
+        fold_buf: &mut Vec<u8>,
+        fold: uffs_text::case_fold::CaseFold,
+    ) -> bool {
+        if self.exclude_lower.is_none() && self.name_patterns.is_none() {
+            return true;
         }
-        self.matches_derived(rec, names)
+        let folded_name = fold.fold_into(rec.name(names), fold_buf);
+        if let Some(excl) = &self.exclude_lower
+            && name_matches(folded_name, excl)
+        {
+            return false;
+        }
+        self.name_patterns
+            .as_ref()
+            .is_none_or(|patterns| patterns.matches_folded(folded_name))
     }
 
     /// Check derived/computed filters: name length, allocated, tree metrics,
@@ -791,7 +860,7 @@ impl SearchFilters {
         }
         // ── Month-of-year filter ───────────────────────────────────
         if !self.allowed_months.is_empty() {
-            let month = month_from_unix_micros(rec.modified);
+            let month = month_from_filetime_in(rec.modified, self.time_zone);
             if !self.allowed_months.contains(&month) {
                 return false;
             }
@@ -807,6 +876,13 @@ impl SearchFilters {
                 return false;
             }
         }
+        // ── Computed-field filters ─────────────────────────────────
+        if !self.computed.is_empty() {
+            let src = RecordSource { record: rec, names };
+            if !self.computed.iter().all(|filter| filter.matches(&src)) {
+                return false;
+            }
+        }
         true
     }
 
@@ -853,6 +929,9 @@ impl SearchFilters {
             // A deleted-tombstone toggle is a real filter — same match-all-gate
             // reasoning as `malformed` above.
             && self.deleted.is_none()
+            && self.name_patterns.is_none()
+            && self.computed.is_empty()
+            && self.page_after.is_none()
     }
 }
 
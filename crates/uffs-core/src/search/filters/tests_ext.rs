@@ -770,3 +770,17 @@ fn apply_min_path_len_rejects_short_paths() {
     assert_eq!(rows.len(), 1, "only path >=200 chars should remain");
     assert!(rows.first().expect("rows non-empty").path.len() >= 200);
 }
+
+#[test]
+fn parse_time_point_ms_accepts_epoch_millis_and_time_specs() {
+    let now = uffs_time::FILETIME_UNIX_DIFF + 3 * 86_400 * uffs_time::FILETIME_TICKS_PER_SECOND;
+    assert_eq!(
+        parse_time_point_ms("1760000000000", now),
+        Some(1_760_000_000_000)
+    );
+    assert_eq!(parse_time_point_ms("1970-01-02", now), Some(86_400_000));
+    assert_eq!(parse_time_point_ms("yesterday", now), Some(2 * 86_400_000));
+    assert_eq!(parse_time_point_ms("1d", now), Some(2 * 86_400_000));
+    assert_eq!(parse_time_point_ms("1600-01-01", now), None);
+    assert_eq!(parse_time_point_ms("someday", now), None);
+}
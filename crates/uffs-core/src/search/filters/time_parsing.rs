@@ -3,6 +3,8 @@
 
 //! Time-bound parsing: durations, ISO dates, named ranges, and months.
 
+use uffs_time::TimeZone;
+
 /// Extracts the 1-based month number from a raw FILETIME timestamp.
 #[must_use]
 pub const fn month_from_filetime(filetime: i64) -> u32 {
@@ -12,6 +14,16 @@ pub const fn month_from_filetime(filetime: i64) -> u32 {
     }
 }
 
+/// The 1-based month of a raw FILETIME in `time_zone`; January for unset
+/// timestamps, like [`month_from_filetime`].
+#[must_use]
+pub fn month_from_filetime_in(filetime: i64, time_zone: TimeZone) -> u32 {
+    if filetime == 0 {
+        return 1;
+    }
+    month_from_filetime(time_zone.to_local(filetime))
+}
+
 /// Backward-compatible alias — callers that still say `month_from_unix_micros`
 /// will compile, but the semantics now expect raw FILETIME input.
 #[must_use]
@@ -202,13 +214,31 @@ pub fn now_unix_micros() -> i64 {
 /// - **Named ranges:** `today`, `yesterday`, `this_week`, `last_week`,
 ///   `this_month`, `last_month`, `this_year`, `last_year`, `last_7d`,
 ///   `last_30d`, `last_90d`, `last_365d`, `ytd`
+///
+/// Calendar dates are read in UTC; see [`parse_time_bound_in`].
 #[must_use]
 pub fn parse_time_bound(spec: &str, now_ft: i64, is_newer: bool) -> Option<i64> {
+    parse_time_bound_in(spec, now_ft, is_newer, TimeZone::UTC)
+}
+
+/// [`parse_time_bound`] with calendar dates read in `time_zone`.
+///
+/// Named ranges and ISO dates start at local midnight, so `2025-03-30`
+/// in `Europe/Berlin` is 2025-03-29 23:00 UTC and `today` follows the
+/// zone's DST.  Durations stay exact spans back from `now_ft`.
+#[must_use]
+pub fn parse_time_bound_in(
+    spec: &str,
+    now_ft: i64,
+    is_newer: bool,
+    time_zone: TimeZone,
+) -> Option<i64> {
     let trimmed = spec.trim();
 
     // ── Named time ranges ──────────────────────────────────────────
-    if let Some(ts) = parse_named_time_range(trimmed, now_ft, is_newer) {
-        return Some(ts);
+    let local_now = time_zone.to_local(now_ft);
+    if let Some(local) = parse_named_time_range(trimmed, local_now, is_newer) {
+        return Some(time_zone.local_to_utc(local));
     }
 
     // ── Duration suffix (e.g. "7d", "24h") ─────────────────────────
@@ -228,10 +258,29 @@ pub fn parse_time_bound(spec: &str, now_ft: i64, is_newer: bool) -> Option<i64>
     }
 
     // ── ISO date (YYYY-MM-DD) ──────────────────────────────────────
-    parse_iso_date(trimmed)
+    parse_iso_date(trimmed).map(|local| time_zone.local_to_utc(local))
 }
 
-/// Parse an ISO date string (`YYYY-MM-DD`) into a FILETIME at midnight UTC.
+/// Parse a point in time into Unix milliseconds — the `as_of` /
+/// `@<time>` grammar of the daemon's index history.
+///
+/// A bare integer is taken as Unix milliseconds verbatim; anything else
+/// goes through [`parse_time_bound`] as a lower bound (`yesterday` = the
+/// start of yesterday, `2026-10-14` = midnight UTC, `7d` = seven days
+/// before `now_ft`). Moments before the Unix epoch are rejected.
+#[must_use]
+pub fn parse_time_point_ms(spec: &str, now_ft: i64) -> Option<u64> {
+    let trimmed = spec.trim();
+    if !trimmed.is_empty() && trimmed.bytes().all(|byte| byte.is_ascii_digit()) {
+        return trimmed.parse().ok();
+    }
+    let filetime = parse_time_bound(trimmed, now_ft, true)?;
+    let unix_ms = (filetime - uffs_time::FILETIME_UNIX_DIFF) / (TICKS_PER_SECOND / 1_000);
+    u64::try_from(unix_ms).ok()
+}
+
+/// Parse an ISO date string (`YYYY-MM-DD`) into FILETIME ticks at midnight
+/// (UTC, or local wall-clock ticks for [`parse_time_bound_in`]).
 fn parse_iso_date(trimmed: &str) -> Option<i64> {
     if trimmed.len() == 10 && trimmed.as_bytes().get(4) == Some(&b'-') {
         let parts: Vec<&str> = trimmed.split('-').collect();
@@ -537,4 +586,30 @@ mod tests {
         let result = parse_time_bound("2w", now, true).unwrap();
         assert_eq!(result, now - 14_i64 * TICKS_PER_DAY);
     }
+
+    #[test]
+    fn parse_time_bound_in_reads_dates_at_local_midnight() {
+        let berlin = TimeZone::named("Europe/Berlin").unwrap();
+        let utc_midnight = parse_time_bound("2025-03-30", 0_i64, true).unwrap();
+        // Berlin is still on CET (UTC+1) at the start of 2025-03-30.
+        assert_eq!(
+            parse_time_bound_in("2025-03-30", 0_i64, true, berlin),
+            Some(utc_midnight - 3_600_i64 * TICKS_PER_SECOND)
+        );
+        // …and on CEST (UTC+2) at the start of 2025-03-31.
+        assert_eq!(
+            parse_time_bound_in("2025-03-31", 0_i64, true, berlin),
+            Some(utc_midnight + TICKS_PER_DAY - 7_200_i64 * TICKS_PER_SECOND)
+        );
+        // 2025-03-30 22:30 UTC is already the 31st in Berlin.
+        let now = utc_midnight + TICKS_PER_DAY - 5_400_i64 * TICKS_PER_SECOND;
+        assert_eq!(
+            parse_time_bound_in("today", now, true, berlin),
+            Some(utc_midnight + TICKS_PER_DAY - 7_200_i64 * TICKS_PER_SECOND)
+        );
+        assert_eq!(
+            parse_time_bound_in("2h", now, true, berlin),
+            Some(now - 7_200_i64 * TICKS_PER_SECOND)
+        );
+    }
 }
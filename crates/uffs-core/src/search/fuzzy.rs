@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Typo-tolerant ("fuzzy") filename matching.
+//!
+//! Every other search mode is a binary match.  Fuzzy mode — `--fuzzy`, a
+//! `fuzzy:` pattern prefix, or `match_mode: fuzzy` over MCP — instead scores
+//! each candidate name against the needle and returns the hits ranked by that
+//! score, so `recieve_invoce.xlsx` still finds `receive_invoice.xlsx`.
+//!
+//! ## Scoring
+//!
+//! Needle and name are folded through the volume's `$UpCase` table, then
+//!
+//! ```text
+//! score = (dice + edit) / 2
+//! dice  = 2·|T(needle) ∩ T(name)| / (|T(needle)| + |T(name)|)
+//! edit  = 1 − levenshtein(needle, name) / max(len(needle), len(name))
+//! ```
+//!
+//! where `T(s)` is the set of folded char-trigrams of `s` (the same packing as
+//! [`crate::trigram::TrigramIndex`]).  Scores are carried as per-mille `u16`s
+//! so ranking and tie-breaking stay integral.
+//!
+//! ## Candidate bound
+//!
+//! `edit ≤ 1`, so a hit needs `dice ≥ 2·threshold − 1`, which in turn needs at
+//! least `(2·threshold − 1)·|T(needle)| / 2` shared trigrams.
+//! [`FuzzyNeedle::min_shared`] is that bound; the drive's trigram postings
+//! (base ∪ delta overlay, see
+//! [`crate::compact::DriveCompactIndex::trigram_overlap`]) yield exactly the
+//! records meeting it, so the candidate pass never drops a real hit.  A bound
+//! of `0` (threshold ≤ 0.5, or a needle under 3 codepoints) means every name
+//! is a potential hit and the caller scans the drive linearly.
+
+use uffs_text::case_fold::CaseFold;
+
+use crate::trigram_key::pack_char_trigram;
+
+/// Score a name must reach when the caller does not set a threshold.
+pub const DEFAULT_THRESHOLD: f32 = 0.6;
+
+/// Pattern prefix selecting fuzzy mode (`fuzzy:recieve_invoce`).
+pub const PATTERN_PREFIX: &str = "fuzzy:";
+
+/// Per-mille scale of every score and threshold in this module.
+const SCALE: u32 = 1000;
+
+/// A folded fuzzy needle plus its threshold, built once per query.
+#[derive(Debug, Clone)]
+pub struct FuzzyNeedle {
+    /// Needle folded codepoint-by-codepoint.
+    folded: Vec<u16>,
+    /// Sorted, deduped packed char-trigrams of `folded`.
+    trigrams: Vec<u64>,
+    /// Minimum per-mille score of a hit.
+    threshold: u16,
+}
+
+/// Reusable per-drive buffers for [`FuzzyNeedle::score`].
+#[derive(Debug, Default)]
+pub struct FuzzyScratch {
+    /// Folded name codepoints.
+    units: Vec<u16>,
+    /// Sorted, deduped packed char-trigrams of `units`.
+    trigrams: Vec<u64>,
+    /// Single DP row of the Levenshtein computation.
+    row: Vec<u32>,
+}
+
+impl FuzzyNeedle {
+    /// Fold `needle` and derive its trigram set.
+    ///
+    /// `threshold` is clamped to `[0, 1]`; callers validate user input
+    /// before it gets here.
+    #[must_use]
+    pub fn new(needle: &str, threshold: f32, fold: CaseFold) -> Self {
+        let folded: Vec<u16> = needle.chars().map(|ch| fold.fold_char(ch)).collect();
+        let mut trigrams = Vec::new();
+        trigram_set_into(&folded, &mut trigrams);
+        Self {
+            folded,
+            trigrams,
+            threshold: per_mille(threshold),
+        }
+    }
+
+    /// The needle's sorted, deduped packed char-trigrams.
+    #[must_use]
+    pub fn trigrams(&self) -> &[u64] {
+        &self.trigrams
+    }
+
+    /// Minimum number of needle trigrams a name must share to possibly
+    /// reach the threshold (see the module docs).  `0` means no bound —
+    /// every name is a candidate.
+    #[must_use]
+    pub fn min_shared(&self) -> usize {
+        let excess = u64::from(self.threshold)
+            .saturating_mul(2)
+            .saturating_sub(u64::from(SCALE));
+        let needle_tris = u64::try_from(self.trigrams.len()).unwrap_or(u64::MAX);
+        let bound = excess
+            .saturating_mul(needle_tris)
+            .div_ceil(2 * u64::from(SCALE));
+        usize::try_from(bound).unwrap_or(usize::MAX)
+    }
+
+    /// Per-mille similarity of `name`, or `None` below the threshold.
+    pub fn score(&self, name: &str, fold: CaseFold, scratch: &mut FuzzyScratch) -> Option<u16> {
+        scratch.units.clear();
+        scratch
+            .units
+            .extend(name.chars().map(|ch| fold.fold_char(ch)));
+        trigram_set_into(&scratch.units, &mut scratch.trigrams);
+
+        let longest = self.folded.len().max(scratch.units.len());
+        if longest == 0 {
+            return None;
+        }
+        let needed = u32::from(self.threshold) * 2;
+        let dice = dice_per_mille(&self.trigrams, &scratch.trigrams);
+
+        // Length difference is a lower bound on the edit distance — reject
+        // before paying for the DP when even that cannot reach the threshold.
+        let len_gap = self.folded.len().abs_diff(scratch.units.len());
+        let edit_ceiling = edit_per_mille(len_gap, longest);
+        if dice.unwrap_or(edit_ceiling) + edit_ceiling < needed {
+            return None;
+        }
+
+        let distance = levenshtein(&self.folded, &scratch.units, &mut scratch.row);
+        let edit = edit_per_mille(distance, longest);
+        // Needle and name both under 3 codepoints: no trigram signal at all,
+        // so the edit similarity alone decides.
+        let score = u32::midpoint(dice.unwrap_or(edit), edit);
+        if score < u32::from(self.threshold) {
+            return None;
+        }
+        u16::try_from(score).ok()
+    }
+}
+
+/// Convert a `[0, 1]` threshold into per-mille.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "one scale of a validated user threshold per query"
+)]
+fn per_mille(threshold: f32) -> u16 {
+    let scaled = (threshold.clamp(0.0, 1.0) * 1000.0_f32).round();
+    // Clamped to [0, 1000] above, so the conversion is exact.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "value is clamped to 0..=1000"
+    )]
+    let value = scaled as u16;
+    value
+}
+
+/// `1 − distance / longest` in per-mille (`longest > 0`).
+fn edit_per_mille(distance: usize, longest: usize) -> u32 {
+    let scaled = (longest.saturating_sub(distance) as u64).saturating_mul(u64::from(SCALE));
+    u32::try_from(scaled / longest as u64).unwrap_or(SCALE)
+}
+
+/// Sørensen–Dice coefficient of two sorted trigram sets in per-mille, or
+/// `None` when both are empty.
+fn dice_per_mille(left: &[u64], right: &[u64]) -> Option<u32> {
+    let total = left.len() + right.len();
+    if total == 0 {
+        return None;
+    }
+    let mut shared = 0_usize;
+    let mut rest = right;
+    for tri in left {
+        let skip = rest.partition_point(|other| other < tri);
+        rest = rest.get(skip..).unwrap_or_default();
+        if rest.first() == Some(tri) {
+            shared += 1;
+        }
+    }
+    let scaled = (2 * shared as u64).saturating_mul(u64::from(SCALE));
+    u32::try_from(scaled / total as u64).ok()
+}
+
+/// Rebuild `out` as the sorted, deduped packed trigrams of `units`.
+fn trigram_set_into(units: &[u16], out: &mut Vec<u64>) {
+    out.clear();
+    out.extend(
+        units
+            .windows(3)
+            .filter_map(|window| window.first_chunk::<3>())
+            .map(|&[cp0, cp1, cp2]| pack_char_trigram(cp0, cp1, cp2)),
+    );
+    out.sort_unstable();
+    out.dedup();
+}
+
+/// Levenshtein distance over folded codepoints with a single DP row.
+fn levenshtein(left: &[u16], right: &[u16], row: &mut Vec<u32>) -> usize {
+    row.clear();
+    row.extend(0..=u32::try_from(right.len()).unwrap_or(u32::MAX));
+    for (i, &lc) in left.iter().enumerate() {
+        let mut diagonal = u32::try_from(i).unwrap_or(u32::MAX);
+        if let Some(first) = row.first_mut() {
+            *first = diagonal + 1;
+        }
+        for (j, &rc) in right.iter().enumerate() {
+            let (Some(&above), Some(&left_cell)) = (row.get(j + 1), row.get(j)) else {
+                break;
+            };
+            let substitute = diagonal + u32::from(lc != rc);
+            let best = substitute.min(above + 1).min(left_cell + 1);
+            diagonal = above;
+            if let Some(cell) = row.get_mut(j + 1) {
+                *cell = best;
+            }
+        }
+    }
+    row.last().map_or(0, |&dist| dist as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(needle: &str, name: &str, threshold: f32) -> Option<u16> {
+        let fold = CaseFold::default_table();
+        FuzzyNeedle::new(needle, threshold, fold).score(name, fold, &mut FuzzyScratch::default())
+    }
+
+    #[test]
+    fn transposed_typos_still_match() {
+        let hit = score(
+            "recieve_invoce.xlsx",
+            "receive_invoice.xlsx",
+            DEFAULT_THRESHOLD,
+        );
+        assert!(hit.is_some_and(|val| val >= 600), "{hit:?}");
+    }
+
+    #[test]
+    fn exact_name_scores_full_marks_regardless_of_case() {
+        assert_eq!(
+            score("Report.PDF", "report.pdf", DEFAULT_THRESHOLD),
+            Some(1000)
+        );
+    }
+
+    #[test]
+    fn unrelated_name_is_rejected() {
+        assert_eq!(
+            score("receive_invoice", "kernel32.dll", DEFAULT_THRESHOLD),
+            None
+        );
+    }
+
+    #[test]
+    fn closer_name_scores_higher() {
+        let near = score("invoice", "invoce", 0.1);
+        let far = score("invoice", "invitation", 0.1);
+        assert!(near > far, "near={near:?} far={far:?}");
+    }
+
+    #[test]
+    fn short_needles_fall_back_to_edit_similarity() {
+        assert_eq!(score("ab", "AB", DEFAULT_THRESHOLD), Some(1000));
+        assert_eq!(score("ab", "xy", DEFAULT_THRESHOLD), None);
+    }
+
+    #[test]
+    fn min_shared_is_zero_at_or_below_half() {
+        let fold = CaseFold::default_table();
+        assert_eq!(FuzzyNeedle::new("invoice", 0.5, fold).min_shared(), 0);
+        // 5 trigrams, t = 0.6 → ceil(0.2 · 5 / 2) = 1.
+        assert_eq!(FuzzyNeedle::new("invoice", 0.6, fold).min_shared(), 1);
+        // t = 1.0 → every needle trigram must be shared.
+        assert_eq!(FuzzyNeedle::new("invoice", 1.0, fold).min_shared(), 3);
+    }
+
+    #[test]
+    fn levenshtein_counts_single_edits() {
+        let mut row = Vec::new();
+        let units = |text: &str| text.encode_utf16().collect::<Vec<u16>>();
+        assert_eq!(
+            levenshtein(&units("kitten"), &units("sitting"), &mut row),
+            3
+        );
+        assert_eq!(levenshtein(&units(""), &units("abc"), &mut row), 3);
+        assert_eq!(levenshtein(&units("same"), &units("same"), &mut row), 0);
+    }
+}
@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Known-hash lists behind `--hash-in`.
+//!
+//! A [`HashList`] is a set of content digests — an IOC feed to hunt for,
+//! or an NSRL RDS subset of known-good files to exclude — plus the file
+//! sizes the list publishes.  The daemon narrows candidates with
+//! [`HashList::is_candidate_size`] before reading anything, then hashes
+//! only the survivors with the algorithms the list uses
+//! ([`HashList::algos`]) and tests each digest with
+//! [`HashList::contains`].
+//!
+//! ## Accepted formats
+//!
+//! One entry per line; blank lines and `#` comments are skipped.
+//!
+//! ```text
+//! 9f86d081884c7d65…                       bare hex: 32 = MD5, 40 = SHA-1, 64 = SHA-256
+//! 9f86d081884c7d65…  report.pdf           sha256sum / md5sum output (rest ignored)
+//! sha1:a94a8fe5ccb1…                      `<algo>:<hex>`, as the `hash` column renders
+//! 9f86d081884c7d65…,1048576               `<hash>,<size>` — the size narrows candidates
+//! "SHA-1","MD5","CRC32","FileName","FileSize",…   CSV with a header row (NSRL RDS)
+//! ```
+//!
+//! A header row is recognised by any column named after a hash algorithm
+//! (`SHA-256`, `SHA-1`, `MD5`, …) or a size (`FileSize`, `size`); each
+//! data row then contributes every digest column it fills.  Lines that
+//! yield no digest are counted in [`HashList::skipped_lines`].
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead};
+
+use crate::aggregate::hash_cache::{Digest, HashAlgo, parse_digest};
+
+/// A parsed `--hash-in` list.
+#[derive(Debug, Clone, Default)]
+pub struct HashList {
+    /// Digests per algorithm.
+    digests: HashMap<HashAlgo, HashSet<Digest>>,
+    /// Sizes published alongside the digests.
+    sizes: HashSet<u64>,
+    /// Whether some entry came without a size — then no size can be
+    /// ruled out.
+    unsized_entries: bool,
+    /// Non-blank, non-comment lines that yielded no digest.
+    skipped: usize,
+}
+
+/// Column layout announced by a CSV header row.
+#[derive(Debug, Default)]
+struct Header {
+    /// `(column, algorithm)` of every digest column.
+    digests: Vec<(usize, HashAlgo)>,
+    /// Column of the file size, when present.
+    size: Option<usize>,
+}
+
+impl HashList {
+    /// Parse a list in any of the module-level formats.
+    ///
+    /// # Errors
+    ///
+    /// Propagates read failures; a list without a single digest is
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn parse<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut list = Self::default();
+        let mut header: Option<Header> = None;
+        let mut first = true;
+        for read in reader.lines() {
+            let line = read?;
+            let text = line.trim().trim_start_matches('\u{feff}');
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            if core::mem::take(&mut first)
+                && let Some(found) = parse_header(text)
+            {
+                header = Some(found);
+                continue;
+            }
+            let added = match &header {
+                Some(layout) => list.insert_row(layout, text),
+                None => list.insert_line(text),
+            };
+            if !added {
+                list.skipped = list.skipped.saturating_add(1);
+            }
+        }
+        if list.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "no SHA-256, SHA-1 or MD5 digests found",
+            ));
+        }
+        Ok(list)
+    }
+
+    /// Add one digest, with the size of the file it names when known.
+    pub fn insert(&mut self, algo: HashAlgo, digest: Digest, size: Option<u64>) {
+        self.digests.entry(algo).or_default().insert(digest);
+        match size {
+            Some(bytes) => {
+                self.sizes.insert(bytes);
+            }
+            None => self.unsized_entries = true,
+        }
+    }
+
+    /// Number of distinct digests.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.digests.values().map(HashSet::len).sum()
+    }
+
+    /// Whether the list holds no digest.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lines that yielded no digest.
+    #[must_use]
+    pub const fn skipped_lines(&self) -> usize {
+        self.skipped
+    }
+
+    /// Algorithms the list uses, in a stable order.
+    #[must_use]
+    pub fn algos(&self) -> Vec<HashAlgo> {
+        let mut algos: Vec<HashAlgo> = self.digests.keys().copied().collect();
+        algos.sort_unstable();
+        algos
+    }
+
+    /// Whether a file of `size` bytes can be on the list at all.  `true`
+    /// for every size once some entry came without one.
+    #[must_use]
+    pub fn is_candidate_size(&self, size: u64) -> bool {
+        self.unsized_entries || self.sizes.contains(&size)
+    }
+
+    /// Whether `digest` of `algo` is on the list.
+    #[must_use]
+    pub fn contains(&self, algo: HashAlgo, digest: &Digest) -> bool {
+        self.digests
+            .get(&algo)
+            .is_some_and(|set| set.contains(digest))
+    }
+
+    /// Headerless line: a digest, then either `,<size>` or anything.
+    fn insert_line(&mut self, text: &str) -> bool {
+        let token = text
+            .split(|ch: char| ch == ',' || ch.is_whitespace())
+            .next()
+            .unwrap_or_default();
+        let size = text
+            .strip_prefix(token)
+            .and_then(|rest| rest.strip_prefix(','))
+            .and_then(|rest| csv_fields(rest).first()?.parse::<u64>().ok());
+        let Some((algo, digest)) = parse_digest(&unquote(token).to_ascii_lowercase()) else {
+            return false;
+        };
+        self.insert(algo, digest, size);
+        true
+    }
+
+    /// CSV data row under `layout`.
+    fn insert_row(&mut self, layout: &Header, text: &str) -> bool {
+        let fields = csv_fields(text);
+        let size = layout
+            .size
+            .and_then(|column| fields.get(column))
+            .and_then(|field| field.parse::<u64>().ok());
+        let mut added = false;
+        for &(column, algo) in &layout.digests {
+            let Some(field) = fields.get(column) else {
+                continue;
+            };
+            let spelled = format!("{}:{}", algo.name(), field.to_ascii_lowercase());
+            if let Some((parsed, digest)) = parse_digest(&spelled) {
+                self.insert(parsed, digest, size);
+                added = true;
+            }
+        }
+        added
+    }
+}
+
+/// The layout of `text` when it is a header row.
+fn parse_header(text: &str) -> Option<Header> {
+    let mut header = Header::default();
+    for (column, field) in csv_fields(text).iter().enumerate() {
+        let name = field.to_ascii_lowercase();
+        if let Some(algo) = HashAlgo::from_name(&name) {
+            header.digests.push((column, algo));
+        } else if matches!(name.as_str(), "filesize" | "file_size" | "size") {
+            header.size = Some(column);
+        }
+    }
+    (!header.digests.is_empty() || header.size.is_some()).then_some(header)
+}
+
+/// Split one CSV line, honouring double quotes (`""` is a literal quote)
+/// and trimming each field.
+fn csv_fields(text: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(core::mem::take(&mut field).trim().to_owned()),
+            other => field.push(other),
+        }
+    }
+    fields.push(field.trim().to_owned());
+    fields
+}
+
+/// `text` without surrounding whitespace and double quotes.
+fn unquote(text: &str) -> &str {
+    text.trim().trim_matches('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const SHA1_ABC: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+    const MD5_ABC: &str = "900150983cd24fb0d6963f7d28e17f72";
+
+    fn parse(text: &str) -> HashList {
+        HashList::parse(text.as_bytes()).expect("valid list")
+    }
+
+    #[test]
+    fn bare_and_prefixed_digests_pick_their_algorithm() {
+        let list = parse(&format!(
+            "# iocs\n{SHA256_ABC}  evil.exe\n\nsha1:{SHA1_ABC}\n{}\nnot-a-hash\n",
+            MD5_ABC.to_uppercase()
+        ));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.skipped_lines(), 1);
+        assert_eq!(list.algos(), vec![
+            HashAlgo::Sha256,
+            HashAlgo::Sha1,
+            HashAlgo::Md5
+        ]);
+        assert!(list.contains(HashAlgo::Sha256, &HashAlgo::Sha256.digest(b"abc")));
+        assert!(list.contains(HashAlgo::Sha1, &HashAlgo::Sha1.digest(b"abc")));
+        assert!(list.contains(HashAlgo::Md5, &HashAlgo::Md5.digest(b"abc")));
+        assert!(!list.contains(HashAlgo::Md5, &HashAlgo::Md5.digest(b"abd")));
+        // No sizes published: every size stays a candidate.
+        assert!(list.is_candidate_size(12_345));
+    }
+
+    #[test]
+    fn sizes_narrow_candidates_when_every_entry_has_one() {
+        let list = parse(&format!("{SHA256_ABC},3\n{MD5_ABC},\"1024\"\n"));
+        assert!(list.is_candidate_size(3));
+        assert!(list.is_candidate_size(1024));
+        assert!(!list.is_candidate_size(4));
+    }
+
+    #[test]
+    fn nsrl_style_csv_header_maps_columns() {
+        let list = parse(&format!(
+            "\"SHA-1\",\"MD5\",\"CRC32\",\"FileName\",\"FileSize\",\"ProductCode\"\n\
+             \"{}\",\"{}\",\"352441C2\",\"abc, \"\"quoted\"\".txt\",\"3\",\"1\"\n",
+            SHA1_ABC.to_uppercase(),
+            MD5_ABC.to_uppercase()
+        ));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.algos(), vec![HashAlgo::Sha1, HashAlgo::Md5]);
+        assert!(list.contains(HashAlgo::Sha1, &HashAlgo::Sha1.digest(b"abc")));
+        assert!(list.is_candidate_size(3));
+        assert!(!list.is_candidate_size(30));
+    }
+
+    #[test]
+    fn a_list_without_digests_is_rejected() {
+        let err = HashList::parse(&b"# nothing\nhello\n"[..]).expect_err("no digests");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -9,12 +9,18 @@
 pub mod backend;
 pub mod bloom_skip;
 pub mod columns;
+pub mod computed;
+pub mod cursor;
 mod dataframe_convert;
 pub mod derived;
 mod dispatch;
 mod display_row;
 pub mod field;
 pub mod filters;
+pub mod fuzzy;
+pub mod hash_list;
+pub mod pattern_set;
 pub mod query;
+pub mod rank;
 mod sorting;
 pub mod tree;
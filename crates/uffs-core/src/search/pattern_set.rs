@@ -0,0 +1,386 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Multi-pattern filename matching behind `--patterns-from`.
+//!
+//! A threat-intel feed is hundreds of file names and globs; running one
+//! search per entry rescans every drive each time.  A [`PatternSet`]
+//! compiles the whole list once into three matchers over case-folded
+//! names, mirroring the [`crate::compiled_pattern::CompiledPattern`]
+//! buckets:
+//!
+//! | Entry shape               | Bucket                                   |
+//! |---------------------------|------------------------------------------|
+//! | `mimikatz.exe`            | exact set (`ExactSet`) — one hash lookup |
+//! | `*dump*`, `psexe*`, `*.ps1`, `a*z` | one Aho-Corasick automaton (`ContainsAny`), anchored per entry |
+//! | `?.exe`, `[ab]*.dll`, `>regex` | one [`regex::RegexSet`]             |
+//!
+//! Unlike the main search pattern, a bare entry must equal the **whole**
+//! name — IOC lists name files, and `svchost.exe` must not hit
+//! `notsvchost.exe.log`.  Write `*name*` for a substring.  Matching is
+//! always case-insensitive, like NTFS itself.
+//!
+//! When every entry is a pure `*.<ext>` glob, [`PatternSet::extensions`]
+//! hands the list to the extension index instead, and no name is read.
+//! [`PatternSet::compiled`] lowers the list for a Polars name column, where
+//! bare names become one `ExactSet` (`is_in`) and `*needle*` entries one
+//! `ContainsAny` kernel.
+//! [`PatternSet::matching_pattern`] names the first entry (in list order)
+//! a name hits, for the `matched_pattern` column.
+
+use std::collections::HashMap;
+
+use aho_corasick::AhoCorasick;
+use regex::RegexSet;
+use uffs_text::case_fold::CaseFold;
+
+use crate::compiled_pattern::{CompiledPattern, GlobKind, classify_glob};
+use crate::error::{CoreError, Result};
+
+/// How an automaton hit must sit in the name to count.
+#[derive(Debug, Clone)]
+enum Anchor {
+    /// Anywhere (`*needle*`).
+    Anywhere,
+    /// At the start (`prefix*`).
+    Start,
+    /// At the end (`*suffix`, `*.ext`).
+    End,
+    /// At the start, with the name also ending in the folded suffix
+    /// (`prefix*suffix`).
+    StartAndEnd(String),
+}
+
+/// A compiled `--patterns-from` list.
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    /// Entries as given, in list order.
+    patterns: Vec<String>,
+    /// Folded whole names → first entry naming them.
+    exact: HashMap<String, usize>,
+    /// Literal needles of the single-`*` globs.
+    literals: Option<AhoCorasick>,
+    /// `(entry, anchor)` per automaton pattern id.
+    literal_rules: Vec<(usize, Anchor)>,
+    /// Complex globs and `>` regexes.
+    regexes: Option<RegexSet>,
+    /// Entry per regex-set index.
+    regex_entries: Vec<usize>,
+    /// First `*` entry: every name matches it.
+    match_all: Option<usize>,
+    /// Lower-cased extensions when every entry is `*.<ext>`.
+    extensions: Option<Vec<String>>,
+    /// The list as Polars-lowerable patterns (see [`Self::compiled`]).
+    compiled: Vec<CompiledPattern>,
+}
+
+impl PatternSet {
+    /// Compile `entries`; blank entries and `#` comments are skipped.
+    ///
+    /// # Errors
+    ///
+    /// [`CoreError::InvalidPattern`] for a list without entries or an
+    /// automaton that cannot be built, [`CoreError::InvalidRegex`] for an
+    /// entry that does not compile.
+    pub fn new<S: AsRef<str>>(entries: &[S]) -> Result<Self> {
+        let fold = CaseFold::default_table();
+        let mut buf = Vec::new();
+        let mut set = Self {
+            patterns: Vec::new(),
+            exact: HashMap::new(),
+            literals: None,
+            literal_rules: Vec::new(),
+            regexes: None,
+            regex_entries: Vec::new(),
+            match_all: None,
+            extensions: Some(Vec::new()),
+            compiled: Vec::new(),
+        };
+        let mut parts = Parts::default();
+        for entry in entries.iter().map(|entry| entry.as_ref().trim()) {
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let idx = set.patterns.len();
+            set.patterns.push(entry.to_owned());
+            let mut folded = |text: &str| fold.fold_into(text, &mut buf).to_owned();
+            let kind = match entry.strip_prefix('>') {
+                Some(regex) => {
+                    parts.regex(regex.to_owned());
+                    set.regex_entries.push(idx);
+                    None
+                }
+                None => Some(classify_glob(entry)),
+            };
+            if !matches!(kind, Some(GlobKind::Extension(_))) {
+                set.extensions = None;
+            }
+            let (needle, anchor) = match kind {
+                None => continue,
+                Some(GlobKind::Any) => {
+                    set.match_all.get_or_insert(idx);
+                    continue;
+                }
+                Some(GlobKind::Exact(name)) => {
+                    set.exact.entry(folded(&name)).or_insert(idx);
+                    parts.exact.push(name);
+                    continue;
+                }
+                Some(GlobKind::Complex(glob)) => {
+                    parts.regex(crate::glob::glob_to_regex(&glob)?);
+                    set.regex_entries.push(idx);
+                    continue;
+                }
+                Some(GlobKind::Extension(ext)) => {
+                    if let Some(exts) = set.extensions.as_mut() {
+                        exts.push(ext.to_ascii_lowercase());
+                    }
+                    let suffix = format!(".{ext}");
+                    parts.suffixes.push(suffix.clone());
+                    (suffix, Anchor::End)
+                }
+                Some(GlobKind::Prefix(prefix)) => {
+                    parts.rest.push(CompiledPattern::Prefix(prefix.clone()));
+                    (prefix, Anchor::Start)
+                }
+                Some(GlobKind::Suffix(suffix)) => {
+                    parts.suffixes.push(suffix.clone());
+                    (suffix, Anchor::End)
+                }
+                Some(GlobKind::Contains(needle)) => {
+                    parts.contains.push(needle.clone());
+                    (needle, Anchor::Anywhere)
+                }
+                Some(GlobKind::PrefixSuffix { prefix, suffix }) => {
+                    let anchor = Anchor::StartAndEnd(folded(&suffix));
+                    parts.rest.push(CompiledPattern::PrefixSuffix {
+                        prefix: prefix.clone(),
+                        suffix,
+                    });
+                    (prefix, anchor)
+                }
+            };
+            parts.needles.push(folded(&needle));
+            set.literal_rules.push((idx, anchor));
+        }
+        set.build(parts)
+    }
+
+    /// Build the automaton, the `RegexSet` and the lowered patterns from
+    /// the classified entries.
+    fn build(mut self, parts: Parts) -> Result<Self> {
+        if self.patterns.is_empty() {
+            return Err(CoreError::InvalidPattern {
+                pattern: String::new(),
+                reason: "the pattern list has no entries".to_owned(),
+            });
+        }
+        let Parts {
+            needles, regexes, ..
+        } = &parts;
+        if !needles.is_empty() {
+            self.literals =
+                Some(
+                    AhoCorasick::new(needles).map_err(|err| CoreError::InvalidPattern {
+                        pattern: needles.join("|"),
+                        reason: err.to_string(),
+                    })?,
+                );
+        }
+        if !regexes.is_empty() {
+            self.regexes = Some(
+                RegexSet::new(regexes).map_err(|err| CoreError::InvalidRegex {
+                    pattern: regexes.join("|"),
+                    reason: err.to_string(),
+                })?,
+            );
+        }
+        self.compiled = if self.match_all.is_some() {
+            vec![CompiledPattern::Any]
+        } else {
+            parts.into_patterns()
+        };
+        Ok(self)
+    }
+
+    /// Number of entries.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Whether the set has no entries (never true for a built set).
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The lower-cased extensions when every entry is a pure `*.<ext>`
+    /// glob — the list is then an extension filter.
+    #[must_use]
+    pub fn extensions(&self) -> Option<&[String]> {
+        self.extensions.as_deref()
+    }
+
+    /// The list as [`CompiledPattern`]s for a Polars name column, matched
+    /// case-insensitively: a name is on the list when it matches any of
+    /// them.  Bare names collapse into one `ExactSet`, `*needle*` entries
+    /// into one `ContainsAny` and suffix globs into one `SuffixSet`.
+    #[must_use]
+    pub fn compiled(&self) -> &[CompiledPattern] {
+        &self.compiled
+    }
+
+    /// Whether a name, already folded with the drive's [`CaseFold`],
+    /// matches any entry.  Hot path: stops at the first hit.
+    #[must_use]
+    pub fn matches_folded(&self, folded: &str) -> bool {
+        self.match_all.is_some()
+            || self.exact.contains_key(folded)
+            || self.literal_hits(folded).next().is_some()
+            || self
+                .regexes
+                .as_ref()
+                .is_some_and(|regexes| regexes.is_match(folded))
+    }
+
+    /// The first entry, in list order, that `name` matches.
+    #[must_use]
+    pub fn matching_pattern(&self, name: &str) -> Option<&str> {
+        let mut buf = Vec::new();
+        let folded = CaseFold::default_table().fold_into(name, &mut buf);
+        let regex_hits = self.regexes.iter().flat_map(|regexes| {
+            regexes
+                .matches(folded)
+                .into_iter()
+                .filter_map(|hit| self.regex_entries.get(hit).copied())
+        });
+        self.match_all
+            .into_iter()
+            .chain(self.exact.get(folded).copied())
+            .chain(self.literal_hits(folded))
+            .chain(regex_hits)
+            .min()
+            .and_then(|idx| self.patterns.get(idx))
+            .map(String::as_str)
+    }
+
+    /// Entries whose literal sits in `folded` where its anchor requires.
+    fn literal_hits<'set>(&'set self, folded: &'set str) -> impl Iterator<Item = usize> + 'set {
+        self.literals
+            .iter()
+            .flat_map(move |literals| literals.find_overlapping_iter(folded))
+            .filter_map(move |hit| {
+                let (idx, anchor) = self.literal_rules.get(hit.pattern().as_usize())?;
+                let placed = match anchor {
+                    Anchor::Anywhere => true,
+                    Anchor::Start => hit.start() == 0,
+                    Anchor::End => hit.end() == folded.len(),
+                    Anchor::StartAndEnd(suffix) => {
+                        hit.start() == 0
+                            && folded.len() >= hit.end().saturating_add(suffix.len())
+                            && folded.ends_with(suffix.as_str())
+                    }
+                };
+                placed.then_some(*idx)
+            })
+    }
+}
+
+/// Classified entries: the automaton needles and `RegexSet` sources, and
+/// the entries grouped by the [`CompiledPattern`] they lower to.
+#[derive(Default)]
+struct Parts {
+    /// Folded automaton needles, one per literal rule.
+    needles: Vec<String>,
+    /// Case-insensitive `RegexSet` sources, one per regex entry.
+    regexes: Vec<String>,
+    /// Bare names.
+    exact: Vec<String>,
+    /// `*needle*` needles.
+    contains: Vec<String>,
+    /// `*suffix` and `*.ext` suffixes.
+    suffixes: Vec<String>,
+    /// Everything else, one pattern per entry.
+    rest: Vec<CompiledPattern>,
+}
+
+impl Parts {
+    /// Add a regex entry.
+    fn regex(&mut self, pattern: String) {
+        self.regexes.push(format!("(?i){pattern}"));
+        self.rest.push(CompiledPattern::Regex {
+            pattern,
+            anchored: false,
+        });
+    }
+
+    /// The grouped patterns, set kernels first.
+    fn into_patterns(self) -> Vec<CompiledPattern> {
+        let grouped = [
+            (!self.exact.is_empty()).then_some(CompiledPattern::ExactSet(self.exact)),
+            (!self.contains.is_empty()).then_some(CompiledPattern::ContainsAny(self.contains)),
+            (!self.suffixes.is_empty()).then_some(CompiledPattern::SuffixSet(self.suffixes)),
+        ];
+        grouped.into_iter().flatten().chain(self.rest).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(entries: &[&str]) -> PatternSet {
+        PatternSet::new(entries).expect("valid list")
+    }
+
+    #[test]
+    fn bare_entries_match_whole_names_case_insensitively() {
+        let iocs = set(&["# feed", "mimikatz.exe", "", "PsExec.exe"]);
+        assert_eq!(iocs.len(), 2);
+        assert_eq!(iocs.matching_pattern("MIMIKATZ.EXE"), Some("mimikatz.exe"));
+        assert_eq!(iocs.matching_pattern("psexec.exe"), Some("PsExec.exe"));
+        assert_eq!(iocs.matching_pattern("mimikatz.exe.log"), None);
+        assert!(iocs.extensions().is_none());
+    }
+
+    #[test]
+    fn globs_are_anchored_and_the_first_entry_wins() {
+        let iocs = set(&[
+            "*dump*",
+            "lsass*",
+            "*.dmp",
+            "a*z",
+            "?.bat",
+            ">^x[0-9]+\\.sys$",
+        ]);
+        assert_eq!(iocs.matching_pattern("lsass.DMP"), Some("lsass*"));
+        assert_eq!(iocs.matching_pattern("procdump64.exe"), Some("*dump*"));
+        assert_eq!(iocs.matching_pattern("my_lsass.exe"), None);
+        assert_eq!(iocs.matching_pattern("crash.dmp.txt"), None);
+        assert_eq!(iocs.matching_pattern("abcz"), Some("a*z"));
+        assert_eq!(iocs.matching_pattern("az.txt"), None);
+        assert_eq!(iocs.matching_pattern("1.BAT"), Some("?.bat"));
+        assert_eq!(iocs.matching_pattern("X42.sys"), Some(">^x[0-9]+\\.sys$"));
+
+        let mut buf = Vec::new();
+        let folded = CaseFold::default_table().fold_into("ProcDump.exe", &mut buf);
+        assert!(iocs.matches_folded(folded));
+    }
+
+    #[test]
+    fn an_extension_only_list_becomes_an_extension_filter() {
+        let exts = set(&["*.PS1", "*.hta"]);
+        assert_eq!(
+            exts.extensions(),
+            Some(&["ps1".to_owned(), "hta".to_owned()][..])
+        );
+        assert_eq!(exts.matching_pattern("run.hta"), Some("*.hta"));
+    }
+
+    #[test]
+    fn empty_lists_and_bad_regexes_are_rejected() {
+        PatternSet::new(&["# only a comment"]).expect_err("no entries");
+        PatternSet::new(&[">(unclosed"]).expect_err("bad regex");
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Per-drive fuzzy search: trigram-overlap candidates, scored and ranked.
+//!
+//! See [`crate::search::fuzzy`] for the scoring model and the candidate
+//! bound that makes the trigram pass lossless.
+
+use alloc::collections::BinaryHeap;
+use core::cmp::Reverse;
+
+use super::{
+    DisplayRow, FilterMode, SearchFilters, heap_push_capped, indices_to_rows, passes_filter_mode,
+};
+use crate::compact::DriveCompactIndex;
+use crate::search::fuzzy::{FuzzyNeedle, FuzzyScratch};
+
+/// Whether cache profiling is enabled (`UFFS_CACHE_PROFILE` env var).
+static CACHE_PROFILE: std::sync::LazyLock<bool> =
+    std::sync::LazyLock::new(|| std::env::var_os("UFFS_CACHE_PROFILE").is_some());
+
+/// Search one drive for names within the needle's fuzzy threshold.
+///
+/// Returns at most `limit` `(score, row)` pairs, best score first (record
+/// index breaks ties so the order is deterministic).  Record filters and
+/// `filter_mode` run before scoring so they never eat into `limit`.
+#[must_use]
+pub(crate) fn search_compact_drive_fuzzy(
+    drive: &DriveCompactIndex,
+    needle: &FuzzyNeedle,
+    limit: usize,
+    filter_mode: FilterMode,
+    filters: &SearchFilters,
+) -> Vec<(u16, DisplayRow)> {
+    let volume_root = drive.volume_prefix();
+    let profile = *CACHE_PROFILE;
+
+    let mut local_filters = filters.clone();
+    local_filters.resolve_ext_ids_for_drive(drive);
+    let mut filter_buf: Vec<u8> = Vec::with_capacity(256);
+    let mut scratch = FuzzyScratch::default();
+
+    let t_candidates = std::time::Instant::now();
+    let min_shared = needle.min_shared();
+    // Without a trigram bound every record is a candidate: walk the index
+    // range in place instead of materialising it.
+    let overlap = (min_shared > 0).then(|| drive.trigram_overlap(needle.trigrams(), min_shared));
+    let candidate_count = overlap.as_ref().map_or(drive.records.len(), Vec::len);
+    let candidates_ms = t_candidates.elapsed().as_millis();
+
+    // Min-heap capped at `limit` on `(score, Reverse(idx))`: a higher score,
+    // then a lower record index, is better — so only the kept hits are ever
+    // held, however many candidates score.
+    let t_score = std::time::Instant::now();
+    let mut heap: BinaryHeap<Reverse<(u16, Reverse<u32>)>> =
+        BinaryHeap::with_capacity(limit.min(candidate_count).saturating_add(1));
+    let mut score_candidate = |idx: u32| {
+        let Some(rec) = drive.records.get(idx as usize) else {
+            return;
+        };
+        let name = rec.name(&drive.names);
+        if name.is_empty()
+            || !passes_filter_mode(rec.is_directory(), filter_mode)
+            || !local_filters.matches_record(rec, &drive.names, &mut filter_buf, drive.fold)
+        {
+            return;
+        }
+        if let Some(score) = needle.score(name, drive.fold, &mut scratch) {
+            heap_push_capped(&mut heap, Reverse((score, Reverse(idx))), limit);
+        }
+    };
+    match &overlap {
+        Some(candidates) => candidates.iter().copied().for_each(&mut score_candidate),
+        None => (0..uffs_mft::len_to_u32(drive.records.len())).for_each(&mut score_candidate),
+    }
+    let scored: Vec<(u16, u32)> = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((score, Reverse(idx)))| (score, idx))
+        .collect();
+    let score_ms = t_score.elapsed().as_millis();
+
+    let indices: Vec<u32> = scored.iter().map(|&(_, idx)| idx).collect();
+    let rows = indices_to_rows(drive, &indices, &volume_root, filters.malformed_render());
+
+    if profile {
+        tracing::debug!(
+            target: "cache_profile",
+            drive = %drive.letter,
+            candidates = candidate_count,
+            candidates_ms = %candidates_ms,
+            hits = scored.len(),
+            score_ms = %score_ms,
+            "search_fuzzy"
+        );
+    }
+
+    // Empty names were dropped above, so `indices_to_rows` keeps every index
+    // and the rows line up with their scores.
+    scored
+        .into_iter()
+        .map(|(score, _)| score)
+        .zip(rows)
+        .collect()
+}
@@ -6,20 +6,29 @@
 //! Per-drive search (trigram, regex, tree) and global top-N collection
 //! for match-all queries. Called by `MultiDriveBackend::search()`.
 
+mod fuzzy_search;
+mod name_needle;
 mod numeric_sort_key;
 mod numeric_top_n;
 mod path_only_top_n;
 mod path_sorted_top_n;
 mod prefix_search;
+mod ranked_top_n;
+mod record_matcher;
 mod row_resolve;
 
 use alloc::collections::BinaryHeap;
 use std::sync::LazyLock;
 
+pub(crate) use fuzzy_search::search_compact_drive_fuzzy;
+use name_needle::NameNeedle;
 use numeric_top_n::collect_global_top_n_numeric;
 use path_only_top_n::collect_path_only_sorted_top_n;
 use path_sorted_top_n::collect_path_sorted_top_n;
 pub(crate) use prefix_search::search_compact_drive_prefix;
+use ranked_top_n::keep_best_hits;
+pub(crate) use ranked_top_n::rank_top_n;
+pub use record_matcher::RecordMatcher;
 use row_resolve::indices_to_rows;
 
 use super::backend::{DisplayRow, FilterMode, PhaseTimings};
@@ -35,12 +44,18 @@ static CACHE_PROFILE: LazyLock<bool> =
     LazyLock::new(|| std::env::var_os("UFFS_CACHE_PROFILE").is_some());
 
 /// Entry for the top-N binary heap used by `collect_global_top_n_numeric`.
+///
+/// Equal keys order by drive (drives are scanned in volume order), then by
+/// file reference — the identity tiebreaker of a keyset page — so the heap
+/// keeps exactly the rows a `search_cursor` page continues from.
 #[derive(Eq, PartialEq)]
 pub(super) struct HeapEntry {
     /// Sort key used for ordering.
     pub(super) sort_key: i64,
     /// Drive index.
     pub(super) drive_idx: u16,
+    /// NTFS file reference of the record.
+    pub(super) file_ref: u64,
     /// Record index within the drive.
     pub(super) rec_idx: u32,
 }
@@ -50,6 +65,7 @@ impl Ord for HeapEntry {
         self.sort_key
             .cmp(&other.sort_key)
             .then_with(|| self.drive_idx.cmp(&other.drive_idx))
+            .then_with(|| self.file_ref.cmp(&other.file_ref))
             .then_with(|| self.rec_idx.cmp(&other.rec_idx))
     }
 }
@@ -149,7 +165,10 @@ pub(crate) fn collect_global_top_n<D: AsRef<DriveCompactIndex> + Sync>(
         | FieldId::PathLength
         | FieldId::Malformed
         | FieldId::MalformedPath
-        | FieldId::NameHex => {
+        | FieldId::NameHex
+        | FieldId::Host
+        | FieldId::Hash
+        | FieldId::MatchedPattern => {
             let (rows, timings) = collect_global_top_n_numeric(
                 drives,
                 limit,
@@ -182,8 +201,8 @@ pub(crate) fn search_compact_drive_regex(
     limit: usize,
     filters: &SearchFilters,
 ) -> Vec<DisplayRow> {
-    let mut vp_buf = [0_u8; 4];
-    let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+    let volume_root = drive.volume_prefix();
+    let volume_prefix: &str = &volume_root;
     let profile = *CACHE_PROFILE;
 
     // Resolve the extension filter for THIS drive so record-level filters are
@@ -193,7 +212,7 @@ pub(crate) fn search_compact_drive_regex(
     let mut filter_buf: Vec<u8> = Vec::with_capacity(256);
 
     let t_match = std::time::Instant::now();
-    let match_indices: Vec<u32> = drive
+    let mut match_indices: Vec<u32> = drive
         .records
         .iter()
         .enumerate()
@@ -208,6 +227,7 @@ pub(crate) fn search_compact_drive_regex(
         .collect();
     let match_ms = t_match.elapsed().as_millis();
     let match_count = match_indices.len();
+    keep_best_hits(drive, &mut match_indices, filters);
 
     let t_resolve = std::time::Instant::now();
     let rows = indices_to_rows(
@@ -370,59 +390,16 @@ pub(crate) fn search_compact_drive(
     let mut local_filters = filters.clone();
     local_filters.resolve_ext_ids_for_drive(drive);
 
-    let mut vp_buf = [0_u8; 4];
-    let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+    let volume_root = drive.volume_prefix();
+    let volume_prefix: &str = &volume_root;
     let is_glob = needle.contains('*') || needle.contains('?');
     let is_or = needle.contains('|');
 
     // $UpCase case folding engine — zero-alloc comparisons, buffer-reuse fold.
-    let fold = drive.fold;
-
-    // Pre-fold the needle for case-insensitive matching.
-    let mut needle_fold_buf: Vec<u8> = Vec::with_capacity(needle.len());
-    let needle_folded = if case_sensitive {
-        needle.to_owned()
-    } else {
-        fold.fold_into(needle, &mut needle_fold_buf).to_owned()
-    };
-
-    // Pre-build a SIMD-accelerated substring finder for simple queries.
-    // For 1–2 byte needles this is dramatically faster than `str::contains`
-    // (memchr uses SSE2/AVX2/NEON vectorised search).
-    let simple_substring = !is_glob && !is_or && !whole_word && !case_sensitive;
-    let finder = simple_substring.then(|| memchr::memmem::Finder::new(needle_folded.as_bytes()));
+    let name_needle = NameNeedle::new(needle, case_sensitive, whole_word, drive.fold);
     // Reusable buffer for on-the-fly CaseFold (avoids per-record heap alloc).
     let mut fold_buf: Vec<u8> = Vec::with_capacity(256);
-    let matches = |name: &str, buf: &mut Vec<u8>| -> bool {
-        if name.is_empty() || name == "." {
-            return false;
-        }
-        if whole_word {
-            if case_sensitive {
-                if is_glob || is_or {
-                    tree::name_matches(name, needle)
-                } else {
-                    name == needle
-                }
-            } else {
-                let folded = fold.fold_into(name, buf);
-                if is_glob || is_or {
-                    tree::name_matches(folded, &needle_folded)
-                } else {
-                    folded == needle_folded
-                }
-            }
-        } else if let Some(fnd) = &finder {
-            buf.clear();
-            let folded = fold.fold_into(name, buf);
-            fnd.find(folded.as_bytes()).is_some()
-        } else if case_sensitive {
-            tree::name_matches(name, needle)
-        } else {
-            let folded = fold.fold_into(name, buf);
-            tree::name_matches(folded, &needle_folded)
-        }
-    };
+    let matches = |name: &str, buf: &mut Vec<u8>| -> bool { name_needle.matches(name, buf) };
 
     let trigram_needle = extract_trigram_needle(needle, is_glob, is_or);
     let profile = *CACHE_PROFILE;
@@ -452,6 +429,7 @@ pub(crate) fn search_compact_drive(
     if match_path && !match_indices.is_empty() {
         expand_directory_descendants(drive, &mut match_indices);
     }
+    keep_best_hits(drive, &mut match_indices, filters);
 
     let t_resolve = std::time::Instant::now();
     let rows = indices_to_rows(
@@ -520,8 +498,8 @@ pub(crate) fn search_compact_drive_tree(
     limit: usize,
     filters: &SearchFilters,
 ) -> Vec<DisplayRow> {
-    let mut vp_buf = [0_u8; 4];
-    let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+    let volume_root = drive.volume_prefix();
+    let volume_prefix: &str = &volume_root;
     let render = filters.malformed_render();
     let profile = *CACHE_PROFILE;
 
@@ -542,9 +520,21 @@ pub(crate) fn search_compact_drive_tree(
     let mut filter_buf: Vec<u8> = Vec::with_capacity(256);
 
     let t_tree = std::time::Instant::now();
-    let match_indices = tree::tree_search(drive, pattern_lower, scan_limit);
+    let mut match_indices: Vec<u32> = tree::tree_search(drive, pattern_lower, scan_limit)
+        .into_iter()
+        .filter(|&record_idx| {
+            drive.records.get(record_idx as usize).is_some_and(|rec| {
+                !rec.name(&drive.names).is_empty()
+                    && local_filters.matches_record(rec, &drive.names, &mut filter_buf, drive.fold)
+            })
+        })
+        // Re-apply the limit after filtering (the walk may have over-fetched
+        // when an extension filter was active — see `scan_limit` above).
+        .take(limit)
+        .collect();
     let tree_ms = t_tree.elapsed().as_millis();
     let match_count = match_indices.len();
+    keep_best_hits(drive, &mut match_indices, filters);
 
     let t_resolve = std::time::Instant::now();
     let mut dir_cache = tree::dir_cache_with_capacity(256);
@@ -554,33 +544,19 @@ pub(crate) fn search_compact_drive_tree(
         .filter_map(|&record_idx| {
             let rec = drive.records.get(record_idx as usize)?;
             let name = rec.name(&drive.names);
-            if name.is_empty() {
-                return None;
-            }
-            if !local_filters.matches_record(rec, &drive.names, &mut filter_buf, drive.fold) {
-                return None;
-            }
             let (path, path_malformed) = tree::resolve_path_cached_with_malformed(
                 drive,
                 record_idx as usize,
                 volume_prefix,
                 &mut dir_cache,
                 &mut mal_cache,
-                    render,
+                render,
             );
             let forensics = row_forensics(rec, &drive.names, path_malformed);
             Some(make_display_row(
-                record_idx,
-                drive.letter,
-                rec,
-                name,
-                path,
-                forensics,
+                record_idx, drive, rec, name, path, forensics,
             ))
         })
-        // Re-apply the limit after filtering (the walk may have over-fetched
-        // when an extension filter was active — see `scan_limit` above).
-        .take(limit)
         .collect();
     let resolve_ms = t_resolve.elapsed().as_millis();
 
@@ -608,7 +584,7 @@ pub(crate) fn search_compact_drive_tree(
 /// display hint is adjusted.
 pub(super) fn make_display_row(
     record_index: u32,
-    drive_letter: uffs_mft::platform::DriveLetter,
+    drive: &DriveCompactIndex,
     rec: &CompactRecord,
     name: &str,
     path: String,
@@ -619,8 +595,8 @@ pub(super) fn make_display_row(
     let is_ads = name.contains(':');
     DisplayRow::new(
         record_index,
-        drive_letter,
-        path,
+        drive.volume(),
+        drive.display_path(path),
         rec.size,
         rec.is_directory() && !is_ads,
         rec.modified,
@@ -664,7 +640,7 @@ pub(super) fn build_row_cached(
     // The leaf name is derived here (it is exactly `rec.name(...)`) so callers
     // don't thread it in — keeps the resolve→row arg list lean.
     let name = rec.name(&drive.names);
-    make_display_row(rec_idx, drive.letter, rec, name, path, forensics)
+    make_display_row(rec_idx, drive, rec, name, path, forensics)
 }
 
 /// WI-4.4 forensic facts computed against a record's lossless name bytes at
@@ -723,21 +699,6 @@ fn hex_encode(bytes: &[u8]) -> String {
     out
 }
 
-/// Build a `"X:\\"` volume prefix on the stack.
-///
-/// Returns a 3-byte `&str` without heap allocation.  Uses safe
-/// `from_utf8` with a fallback — the bytes are always valid ASCII.
-#[inline]
-pub(crate) fn stack_volume_prefix(
-    buf: &mut [u8; 4],
-    letter: uffs_mft::platform::DriveLetter,
-) -> &str {
-    buf[0] = letter.as_char() as u8;
-    buf[1] = b':';
-    buf[2] = b'\\';
-    core::str::from_utf8(buf.get(..3).unwrap_or(b"?:\\")).unwrap_or("?:\\")
-}
-
 /// Push an element into a `BinaryHeap` capped at `limit`.
 ///
 /// If the heap is below capacity, always push.  If at capacity, only push
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! The name predicate of a plain (non-regex, non-path) search pattern.
+//!
+//! Split out of `search_compact_drive` so the drive scan and the
+//! per-record [`super::RecordMatcher`] test names the same way.
+
+use uffs_text::case_fold::CaseFold;
+
+use crate::search::tree;
+
+/// A substring / glob / `|`-alternation needle, pre-folded for one drive.
+pub(super) struct NameNeedle<'a> {
+    /// The pattern as given.
+    needle: &'a str,
+    /// `needle` folded with the drive's `$UpCase` table (or as given when
+    /// case-sensitive).
+    needle_folded: String,
+    /// Whether matching is case-sensitive.
+    case_sensitive: bool,
+    /// Whether the whole name must match.
+    whole_word: bool,
+    /// Whether the pattern has a `*` / `?` wildcard or a `|` alternation.
+    is_glob_or_alt: bool,
+    /// SIMD-accelerated substring finder for simple case-insensitive
+    /// queries.  For 1–2 byte needles this is dramatically faster than
+    /// `str::contains` (memchr uses SSE2/AVX2/NEON vectorised search).
+    finder: Option<memchr::memmem::Finder<'static>>,
+    /// The drive's case-folding table.
+    fold: CaseFold,
+}
+
+impl<'a> NameNeedle<'a> {
+    /// Fold `needle` for a drive using `fold`.
+    pub(super) fn new(
+        needle: &'a str,
+        case_sensitive: bool,
+        whole_word: bool,
+        fold: CaseFold,
+    ) -> Self {
+        let is_glob = needle.contains('*') || needle.contains('?');
+        let is_or = needle.contains('|');
+        let mut needle_fold_buf: Vec<u8> = Vec::with_capacity(needle.len());
+        let needle_folded = if case_sensitive {
+            needle.to_owned()
+        } else {
+            fold.fold_into(needle, &mut needle_fold_buf).to_owned()
+        };
+        let simple_substring = !is_glob && !is_or && !whole_word && !case_sensitive;
+        let finder = simple_substring
+            .then(|| memchr::memmem::Finder::new(needle_folded.as_bytes()).into_owned());
+        Self {
+            needle,
+            needle_folded,
+            case_sensitive,
+            whole_word,
+            is_glob_or_alt: is_glob || is_or,
+            finder,
+            fold,
+        }
+    }
+
+    /// Whether `name` matches; `buf` is reusable fold scratch.
+    pub(super) fn matches(&self, name: &str, buf: &mut Vec<u8>) -> bool {
+        if name.is_empty() || name == "." {
+            return false;
+        }
+        if self.whole_word {
+            if self.case_sensitive {
+                if self.is_glob_or_alt {
+                    tree::name_matches(name, self.needle)
+                } else {
+                    name == self.needle
+                }
+            } else {
+                let folded = self.fold.fold_into(name, buf);
+                if self.is_glob_or_alt {
+                    tree::name_matches(folded, &self.needle_folded)
+                } else {
+                    folded == self.needle_folded
+                }
+            }
+        } else if let Some(fnd) = &self.finder {
+            buf.clear();
+            let folded = self.fold.fold_into(name, buf);
+            fnd.find(folded.as_bytes()).is_some()
+        } else if self.case_sensitive {
+            tree::name_matches(name, self.needle)
+        } else {
+            let folded = self.fold.fold_into(name, buf);
+            tree::name_matches(folded, &self.needle_folded)
+        }
+    }
+}
@@ -67,6 +67,17 @@ pub(super) fn extract_sort_key(
             }
             i64::from_be_bytes(key)
         }
+        // Host prefix (local volumes sort first), then the drive letter —
+        // the same lossy 8-byte prefix scheme as `Drive` / `Name`.
+        FieldId::Host => {
+            let mut key = [0_u8; 8];
+            let host = drive.host.map_or("", |host| host.as_str());
+            for (dst, byte) in key[..7].iter_mut().zip(host.bytes()) {
+                *dst = byte.to_ascii_lowercase();
+            }
+            key[7] = drive.letter.as_byte();
+            i64::from_be_bytes(key)
+        }
         FieldId::TreeSize => {
             if rec.is_directory() {
                 rec.treesize.cast_signed()
@@ -100,8 +111,9 @@ pub(super) fn extract_sort_key(
         FieldId::Virtual => i64::from(rec.flags & 0x0001_0000 != 0),
         // WI-4.4: leaf-name malformity as 0/1, from the lossless bytes (matches
         // the hot-path filter). `MalformedPath` needs the resolved parent chain
-        // (unavailable at this sort-key stage) and `NameHex` is not sortable, so
-        // both fall through to the `Modified` proxy below.
+        // (unavailable at this sort-key stage) and `NameHex`/`Hash`/
+        // `MatchedPattern` are not sortable, so all of them fall through to the `Modified` proxy
+        // below.
         FieldId::Malformed => {
             i64::from(core::str::from_utf8(rec.name_bytes(&drive.names)).is_err())
         }
@@ -110,7 +122,9 @@ pub(super) fn extract_sort_key(
         | FieldId::PathOnly
         | FieldId::Modified
         | FieldId::MalformedPath
-        | FieldId::NameHex => rec.modified,
+        | FieldId::NameHex
+        | FieldId::Hash
+        | FieldId::MatchedPattern => rec.modified,
         FieldId::NameLength => {
             i64::try_from(rec.name(&drive.names).chars().count()).unwrap_or(i64::MAX)
         }
@@ -12,7 +12,7 @@ use super::super::field::FieldId;
 use super::super::filters::SearchFilters;
 use super::super::tree;
 use super::numeric_sort_key::extract_sort_key;
-use super::{HeapEntry, heap_push_capped, make_display_row, row_forensics, stack_volume_prefix};
+use super::{HeapEntry, heap_push_capped, make_display_row, row_forensics};
 use crate::compact::{CompactRecord, DriveCompactIndex, MalformedRender};
 
 /// Target chunk size for parallel path resolution inside
@@ -160,6 +160,7 @@ fn heap_entry_for(
     HeapEntry {
         sort_key: extract_sort_key(rec, sort_column, drive),
         drive_idx: uffs_mft::len_to_u16(drive_idx),
+        file_ref: rec.file_ref,
         rec_idx: uffs_mft::len_to_u32(rec_idx),
     }
 }
@@ -436,8 +437,8 @@ fn resolve_chunk<D: AsRef<DriveCompactIndex>>(
             continue;
         }
         let name = rec.name(&drive.names);
-        let mut vp_buf = [0_u8; 4];
-        let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+        let volume_root = drive.volume_prefix();
+        let volume_prefix: &str = &volume_root;
         let cache = local_caches
             .entry(drive_idx)
             .or_insert_with(|| tree::dir_cache_with_capacity(256));
@@ -456,14 +457,7 @@ fn resolve_chunk<D: AsRef<DriveCompactIndex>>(
         resolve_fn_ns += t_resolve.elapsed().as_nanos();
         let t_build = std::time::Instant::now();
         let forensics = row_forensics(rec, &drive.names, path_malformed);
-        rows.push(make_display_row(
-            rec_idx,
-            drive.letter,
-            rec,
-            name,
-            path,
-            forensics,
-        ));
+        rows.push(make_display_row(rec_idx, drive, rec, name, path, forensics));
         build_row_ns += t_build.elapsed().as_nanos();
         candidates += 1;
     }
@@ -562,15 +556,18 @@ fn scan_all_drives_parallel<D: AsRef<DriveCompactIndex> + Sync>(
 /// re-sort by `(drive_idx, rec_idx)` for MFT locality during the
 /// downstream path-resolve phase.
 ///
-/// The numeric sort is the user-visible ordering; the locality
-/// re-sort is an internal optimisation that `backend::sort_rows`
-/// reverses after resolution using the user's requested column + tiebreakers.
+/// The numeric sort is the user-visible ordering; equal keys break on
+/// `(drive_idx, file_ref)` in the same direction, as in [`HeapEntry`], so
+/// the cut matches a keyset page.  The locality re-sort is an internal
+/// optimisation that `backend::sort_rows` reverses after resolution using
+/// the user's requested column + tiebreakers.
 ///
 /// Measured on a 1 M-record C: drive with `*.dll --sort modified`:
 /// `path_resolve_ms` drops from ~226 ms to well under 100 ms because
 /// the per-directory `DirCache` entry is reused across all `.dll`
 /// siblings of `System32\` etc.
-fn sort_and_localise(
+fn sort_and_localise<D: AsRef<DriveCompactIndex>>(
+    drives: &[D],
     mut candidates: Vec<(u16, u32, i64)>,
     sort_desc: bool,
     limit: usize,
@@ -585,11 +582,19 @@ fn sort_and_localise(
         candidates.sort_unstable_by_key(|&(drive_idx, rec_idx, _)| (drive_idx, rec_idx));
         return candidates;
     }
-    if sort_desc {
-        candidates.sort_unstable_by_key(|entry| core::cmp::Reverse(entry.2));
-    } else {
-        candidates.sort_unstable_by_key(|entry| entry.2);
-    }
+    let file_ref = |drive_idx: u16, rec_idx: u32| {
+        drives
+            .get(usize::from(drive_idx))
+            .and_then(|drive| drive.as_ref().records.get(rec_idx as usize))
+            .map_or(0, |rec| rec.file_ref)
+    };
+    candidates.sort_unstable_by(|lhs, rhs| {
+        let order = lhs
+            .2
+            .cmp(&rhs.2)
+            .then_with(|| (lhs.0, file_ref(lhs.0, lhs.1)).cmp(&(rhs.0, file_ref(rhs.0, rhs.1))));
+        if sort_desc { order.reverse() } else { order }
+    });
     candidates.truncate(limit);
     candidates.sort_unstable_by_key(|&(drive_idx, rec_idx, _)| (drive_idx, rec_idx));
     candidates
@@ -674,7 +679,7 @@ pub(super) fn collect_global_top_n_numeric<D: AsRef<DriveCompactIndex> + Sync>(
 
     // ── Sort phase: sort by key, truncate to limit, MFT-locality re-sort ──
     let t_sort = std::time::Instant::now();
-    let sorted_candidates = sort_and_localise(raw_candidates, sort_desc, limit);
+    let sorted_candidates = sort_and_localise(drives, raw_candidates, sort_desc, limit);
     let sort_ms = u64::try_from(t_sort.elapsed().as_millis()).unwrap_or(u64::MAX);
 
     // ── Path-resolve phase (parallel): `par_chunks` over candidates
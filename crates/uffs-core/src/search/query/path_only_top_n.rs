@@ -65,7 +65,7 @@ use super::super::field::FieldId;
 use super::super::filters::{SearchFilters, row_passes_filters};
 use super::super::tree::{self, DirCache, MalformedCache};
 use super::numeric_top_n::sort_indices_by_name;
-use super::{make_display_row, passes_filter_mode, row_forensics, stack_volume_prefix};
+use super::{make_display_row, passes_filter_mode, row_forensics};
 use crate::compact::DriveCompactIndex;
 
 /// Target chunk size for parallel path resolution inside
@@ -158,8 +158,8 @@ pub(super) fn collect_path_only_sorted_top_n<D: AsRef<DriveCompactIndex> + Sync>
             continue;
         };
         let drive = drive_ref.as_ref();
-        let mut vp_buf = [0_u8; 4];
-        let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+        let volume_root = drive.volume_prefix();
+        let volume_prefix: &str = &volume_root;
         let mut dir_cache = tree::dir_cache_with_capacity(256);
         let mut mal_cache = tree::malformed_cache_with_capacity(256);
 
@@ -450,7 +450,7 @@ fn emit_if_passes(
         render,
     );
     let forensics = row_forensics(rec, &drive.names, path_malformed);
-    let row = make_display_row(idx, drive.letter, rec, name, path, forensics);
+    let row = make_display_row(idx, drive, rec, name, path, forensics);
     if !row_passes_filters(&row, search_filters, fold, fold_buf) {
         return false;
     }
@@ -604,8 +604,8 @@ fn collect_path_only_via_ext_index<D: AsRef<DriveCompactIndex> + Sync>(
                 if name.is_empty() {
                     continue;
                 }
-                let mut vp_buf = [0_u8; 4];
-                let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+                let volume_root = drive.volume_prefix();
+                let volume_prefix: &str = &volume_root;
                 let cache = local_caches
                     .entry(drive_idx)
                     .or_insert_with(|| tree::dir_cache_with_capacity(256));
@@ -621,14 +621,7 @@ fn collect_path_only_via_ext_index<D: AsRef<DriveCompactIndex> + Sync>(
                     render,
                 );
                 let forensics = row_forensics(rec, &drive.names, path_malformed);
-                local_rows.push(make_display_row(
-                    rec_idx,
-                    drive.letter,
-                    rec,
-                    name,
-                    path,
-                    forensics,
-                ));
+                local_rows.push(make_display_row(rec_idx, drive, rec, name, path, forensics));
                 local_candidates += 1;
             }
             let local_cache_entries: u64 =
@@ -33,9 +33,7 @@ use super::super::field::FieldId;
 use super::super::filters::{SearchFilters, row_passes_filters};
 use super::super::tree::{self, DirCache, MalformedCache};
 use super::numeric_top_n::sort_indices_by_name;
-use super::{
-    build_row_cached, make_display_row, passes_filter_mode, row_forensics, stack_volume_prefix,
-};
+use super::{build_row_cached, make_display_row, passes_filter_mode, row_forensics};
 use crate::compact::DriveCompactIndex;
 
 /// Target chunk size for parallel path resolution inside
@@ -136,8 +134,8 @@ fn walk_tree_path_sorted<D: AsRef<DriveCompactIndex>>(
             continue;
         };
         let drive = drive_ref.as_ref();
-        let mut vp_buf = [0_u8; 4];
-        let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+        let volume_root = drive.volume_prefix();
+        let volume_prefix: &str = &volume_root;
 
         let mut roots: Vec<u32> = drive
             .records
@@ -192,7 +190,7 @@ fn walk_tree_path_sorted<D: AsRef<DriveCompactIndex>>(
                 render,
             );
             let forensics = row_forensics(rec, &drive.names, path_malformed);
-            let row = make_display_row(idx, drive.letter, rec, name, path, forensics);
+            let row = make_display_row(idx, drive, rec, name, path, forensics);
             if !row_passes_filters(&row, search_filters, &fold, &mut fold_buf) {
                 continue;
             }
@@ -329,8 +327,8 @@ fn collect_path_via_ext_index<D: AsRef<DriveCompactIndex> + Sync>(
                 if name.is_empty() {
                     continue;
                 }
-                let mut vp_buf = [0_u8; 4];
-                let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+                let volume_root = drive.volume_prefix();
+                let volume_prefix: &str = &volume_root;
                 let cache = local_caches
                     .entry(drive_idx)
                     .or_insert_with(|| tree::dir_cache_with_capacity(256));
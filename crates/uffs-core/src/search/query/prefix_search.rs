@@ -6,7 +6,7 @@
 //! Extracted from `mod.rs` to satisfy the 800-LOC file-size policy.
 
 use crate::compact::DriveCompactIndex;
-use crate::search::query::{indices_to_rows, stack_volume_prefix};
+use crate::search::query::{indices_to_rows, keep_best_hits};
 
 /// Whether cache profiling is enabled (`UFFS_CACHE_PROFILE` env var).
 static CACHE_PROFILE: std::sync::LazyLock<bool> =
@@ -29,8 +29,8 @@ pub(crate) fn search_compact_drive_prefix(
     case_sensitive: bool,
     filters: &crate::search::filters::SearchFilters,
 ) -> Vec<super::DisplayRow> {
-    let mut vp_buf = [0_u8; 4];
-    let volume_prefix = stack_volume_prefix(&mut vp_buf, drive.letter);
+    let volume_root = drive.volume_prefix();
+    let volume_prefix: &str = &volume_root;
     let profile = *CACHE_PROFILE;
 
     // Resolve the extension filter for THIS drive up front so the per-record
@@ -93,6 +93,7 @@ pub(crate) fn search_compact_drive_prefix(
 
     let match_ms = t_match.elapsed().as_millis();
     let match_count = match_indices.len();
+    keep_best_hits(drive, &mut match_indices, filters);
 
     let t_resolve = std::time::Instant::now();
     let rows = indices_to_rows(
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Relevance top-N for `--rank`.
+//!
+//! Two passes share one order — score descending, then volume, file
+//! reference and record index ascending:
+//!
+//! - [`keep_best_hits`] runs inside each drive's scan, on record indices: the
+//!   match-all [`HeapEntry`] min-heap capped at the limit keeps the drive's
+//!   best hits, so only those get their paths resolved;
+//! - [`rank_top_n`] merges the drives' rows and keeps the overall best.
+//!
+//! Because the per-drive cut breaks ties the same way as the merge, no drive
+//! drops a hit the merge would have kept.
+
+use alloc::collections::BinaryHeap;
+use core::cmp::{Ordering, Reverse};
+
+use super::{DisplayRow, HeapEntry, SearchFilters, heap_push_capped, passes_filter_mode};
+use crate::compact::DriveCompactIndex;
+use crate::search::rank::RankNeedle;
+
+/// Narrow one drive's `hits` (record indices) to its best by relevance
+/// when `filters` carries a [`crate::search::rank::RankCut`].
+///
+/// Left alone without one, and when a display-row filter still has to run
+/// on the rows — it could drop the survivors and leave the page short.
+pub(super) fn keep_best_hits(
+    drive: &DriveCompactIndex,
+    hits: &mut Vec<u32>,
+    filters: &SearchFilters,
+) {
+    let Some(cut) = filters.rank.as_deref() else {
+        return;
+    };
+    if filters.needs_display_row_filter() || hits.len() <= cut.limit {
+        return;
+    }
+    let mut heap: BinaryHeap<Reverse<HeapEntry>> =
+        BinaryHeap::with_capacity(cut.limit.saturating_add(1));
+    for &rec_idx in hits.iter() {
+        let Some(rec) = drive.records.get(rec_idx as usize) else {
+            continue;
+        };
+        let name = rec.name(&drive.names);
+        // Same drops as row resolution and the merge's `filter_mode` pass;
+        // an ADS row never counts as a directory.
+        if name.is_empty()
+            || !passes_filter_mode(rec.is_directory() && !name.contains(':'), cut.filter_mode)
+        {
+            continue;
+        }
+        let Some(score) = cut.needle.score_record(drive, rec_idx) else {
+            continue;
+        };
+        // Inverted identity: a lower file reference / index compares larger,
+        // so it survives a tie.
+        let entry = HeapEntry {
+            sort_key: score,
+            drive_idx: 0,
+            file_ref: !rec.file_ref,
+            rec_idx: !rec_idx,
+        };
+        heap_push_capped(&mut heap, Reverse(entry), cut.limit);
+    }
+    *hits = heap
+        .into_iter()
+        .map(|Reverse(entry)| !entry.rec_idx)
+        .collect();
+}
+
+/// Keep the `limit` highest-scoring `rows`, best first; equal scores order
+/// by volume, file reference and record index.
+#[must_use]
+pub(crate) fn rank_top_n(
+    rows: Vec<DisplayRow>,
+    needle: &RankNeedle,
+    limit: usize,
+) -> Vec<DisplayRow> {
+    let mut scored: Vec<(i64, DisplayRow)> = rows
+        .into_iter()
+        .map(|row| (needle.score(&row), row))
+        .collect();
+    let order = |lhs: &(i64, DisplayRow), rhs: &(i64, DisplayRow)| -> Ordering {
+        rhs.0
+            .cmp(&lhs.0)
+            .then_with(|| lhs.1.drive.cmp(&rhs.1.drive))
+            .then_with(|| lhs.1.file_reference.cmp(&rhs.1.file_reference))
+            .then_with(|| lhs.1.record_index.cmp(&rhs.1.record_index))
+    };
+    if scored.len() > limit {
+        // Only the kept rows need their order.
+        scored.select_nth_unstable_by(limit, order);
+        scored.truncate(limit);
+    }
+    scored.sort_unstable_by(order);
+    scored.into_iter().map(|(_, row)| row).collect()
+}
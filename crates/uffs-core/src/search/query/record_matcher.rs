@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! A search request compiled to a per-record predicate.
+//!
+//! [`RecordMatcher`] answers "would `search_index` return this record?"
+//! for a handful of known records — the changed records of a journal
+//! batch — without scanning the drive.  It applies the same
+//! dispatch-time rewrites as [`crate::search::backend::search_index`],
+//! picks the same name test (match-all, fuzzy, regex, path, or the
+//! substring / glob needle), and then the same record- and row-level
+//! filters.  Result limits, ranking and sorting have no per-record
+//! meaning and are ignored.
+
+use uffs_mft::platform::VolumeId;
+
+use super::name_needle::NameNeedle;
+use super::{DisplayRow, FilterMode, SearchFilters, indices_to_rows, passes_filter_mode};
+use crate::compact::DriveCompactIndex;
+use crate::error::CoreError;
+use crate::search::backend::SearchRequest;
+use crate::search::dispatch::{apply_dispatch_safety_nets, fold_needle, take_fuzzy_prefix};
+use crate::search::fuzzy::{FuzzyNeedle, FuzzyScratch};
+use crate::search::tree;
+
+/// How a [`RecordMatcher`] tests a record's name.
+#[derive(Debug)]
+enum NameTest {
+    /// `*`: every named record.
+    All,
+    /// `fuzzy:` / `--fuzzy`: within `threshold` of `pattern`.
+    Fuzzy {
+        /// The pattern, folded per drive when scored.
+        pattern: String,
+        /// Minimum similarity in `[0, 1]`.
+        threshold: f32,
+    },
+    /// `>regex`.
+    Regex(regex::Regex),
+    /// A pattern with a path separator, matched like `tree::tree_search`.
+    Path(String),
+    /// A substring / glob / `|` needle.  `expand_dirs` carries
+    /// `--match-path`: a record also matches when a directory above it does.
+    Name {
+        /// The pattern as given (folded per drive).
+        needle: String,
+        /// Whether matching is case-sensitive.
+        case_sensitive: bool,
+        /// Whether the whole name must match.
+        whole_word: bool,
+        /// Whether a matching directory also matches its descendants.
+        expand_dirs: bool,
+    },
+}
+
+/// A search request compiled once and tested record by record.
+#[derive(Debug)]
+pub struct RecordMatcher {
+    /// The name test picked for the pattern.
+    name: NameTest,
+    /// Volume selection (from the request, or a `C:` pattern prefix);
+    /// empty = every volume.
+    drives: Vec<VolumeId>,
+    /// File / directory filter mode.
+    filter_mode: FilterMode,
+    /// Record- and row-level filters, after the dispatch rewrites.
+    filters: SearchFilters,
+}
+
+impl RecordMatcher {
+    /// Compile `req`.  `result_limit` and `rank` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`CoreError::InvalidRegex`] when a `>` pattern does not compile —
+    /// `search_index` would return no rows for it.
+    pub fn new(req: SearchRequest<'_>) -> Result<Self, CoreError> {
+        let SearchRequest {
+            mut pattern,
+            case_sensitive,
+            whole_word,
+            match_path,
+            filter_mode,
+            search_filters,
+            drives_filter,
+            mut fuzzy,
+            ..
+        } = req;
+        take_fuzzy_prefix(&mut pattern, &mut fuzzy);
+        let mut drive_from_prefix: Vec<VolumeId> = Vec::new();
+        apply_dispatch_safety_nets(
+            &mut pattern,
+            match_path,
+            case_sensitive,
+            drives_filter.is_empty(),
+            search_filters,
+            &mut drive_from_prefix,
+        );
+        let drives = if drive_from_prefix.is_empty() {
+            drives_filter.to_vec()
+        } else {
+            drive_from_prefix
+        };
+        let fold = uffs_text::case_fold::CaseFold::default_table();
+        let needle = fold_needle(case_sensitive, pattern, fold);
+        let name = if pattern == "*" {
+            NameTest::All
+        } else if let Some(threshold) = fuzzy {
+            NameTest::Fuzzy {
+                pattern: pattern.to_owned(),
+                threshold,
+            }
+        } else if pattern.starts_with('>') && pattern.len() > 1 {
+            let source = needle.strip_prefix('>').unwrap_or(&needle);
+            let compiled = regex::RegexBuilder::new(source)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .map_err(|err| CoreError::InvalidRegex {
+                    pattern: source.to_owned(),
+                    reason: err.to_string(),
+                })?;
+            NameTest::Regex(compiled)
+        } else if tree::is_path_pattern(&needle) {
+            NameTest::Path(needle.into_owned())
+        } else {
+            // Prefix patterns (`win*`) take the prefix scan, which has no
+            // `--match-path` expansion.
+            let expand_dirs = match_path && tree::is_prefix_pattern(&needle).is_none();
+            NameTest::Name {
+                needle: pattern.to_owned(),
+                case_sensitive,
+                whole_word,
+                expand_dirs,
+            }
+        };
+        Ok(Self {
+            name,
+            drives,
+            filter_mode,
+            filters: search_filters.clone(),
+        })
+    }
+
+    /// Whether the request's volume selection covers `volume`.
+    #[must_use]
+    pub fn covers(&self, volume: VolumeId) -> bool {
+        self.drives.is_empty() || self.drives.iter().any(|sel| sel.selects(volume))
+    }
+
+    /// The rows for those of `records` (compact indices into `drive`) the
+    /// request matches, in `records` order.  Unknown indices are skipped.
+    #[must_use]
+    pub fn matching_rows(&self, drive: &DriveCompactIndex, records: &[u32]) -> Vec<DisplayRow> {
+        if records.is_empty() || !self.covers(drive.volume()) {
+            return Vec::new();
+        }
+        let mut local_filters = self.filters.clone();
+        local_filters.resolve_ext_ids_for_drive(drive);
+        let mut fold_buf: Vec<u8> = Vec::with_capacity(256);
+        let mut name_test = DriveNameTest::new(&self.name, drive);
+        let matched: Vec<u32> = records
+            .iter()
+            .copied()
+            .filter(|&idx| {
+                let Some(rec) = drive.records.get(idx as usize) else {
+                    return false;
+                };
+                rec.name_len != 0
+                    && passes_filter_mode(rec.is_directory(), self.filter_mode)
+                    && name_test.matches(drive, idx, &local_filters, &mut fold_buf)
+            })
+            .collect();
+        let mut rows = indices_to_rows(
+            drive,
+            &matched,
+            &drive.volume_prefix(),
+            self.filters.malformed_render(),
+        );
+        super::super::filters::apply_search_filters(&mut rows, &self.filters);
+        rows
+    }
+}
+
+/// A [`NameTest`] bound to one drive (needles folded with its table).
+enum DriveNameTest<'a> {
+    /// Every named record.
+    All,
+    /// Fuzzy needle plus its scoring scratch (boxed: the scratch is large).
+    Fuzzy(Box<(FuzzyNeedle, FuzzyScratch)>),
+    /// Compiled regex.
+    Regex(&'a regex::Regex),
+    /// Path pattern.
+    Path(&'a str),
+    /// Name needle (boxed: the memchr finder is large), with the
+    /// `--match-path` directory expansion flag.
+    Name(Box<NameNeedle<'a>>, bool),
+}
+
+impl<'a> DriveNameTest<'a> {
+    /// Bind `test` to `drive`.
+    fn new(test: &'a NameTest, drive: &DriveCompactIndex) -> Self {
+        match test {
+            NameTest::All => Self::All,
+            NameTest::Fuzzy { pattern, threshold } => Self::Fuzzy(Box::new((
+                FuzzyNeedle::new(pattern, *threshold, drive.fold),
+                FuzzyScratch::default(),
+            ))),
+            NameTest::Regex(compiled) => Self::Regex(compiled),
+            NameTest::Path(pattern) => Self::Path(pattern),
+            NameTest::Name {
+                needle,
+                case_sensitive,
+                whole_word,
+                expand_dirs,
+            } => Self::Name(
+                Box::new(NameNeedle::new(
+                    needle,
+                    *case_sensitive,
+                    *whole_word,
+                    drive.fold,
+                )),
+                *expand_dirs,
+            ),
+        }
+    }
+
+    /// Whether record `idx` matches.  Record filters gate the record
+    /// itself — or, under `--match-path`, the matching directory above it,
+    /// exactly as the drive scan filters before it expands.
+    fn matches(
+        &mut self,
+        drive: &DriveCompactIndex,
+        idx: u32,
+        filters: &SearchFilters,
+        fold_buf: &mut Vec<u8>,
+    ) -> bool {
+        let Some(rec) = drive.records.get(idx as usize) else {
+            return false;
+        };
+        let name = rec.name(&drive.names);
+        let record_passes =
+            |buf: &mut Vec<u8>| filters.matches_record(rec, &drive.names, buf, drive.fold);
+        match self {
+            Self::All => record_passes(fold_buf),
+            Self::Fuzzy(fuzzy) => {
+                let (needle, scratch) = &mut **fuzzy;
+                !name.is_empty()
+                    && record_passes(fold_buf)
+                    && needle.score(name, drive.fold, scratch).is_some()
+            }
+            Self::Regex(compiled) => {
+                !name.is_empty() && compiled.is_match(name) && record_passes(fold_buf)
+            }
+            Self::Path(pattern) => {
+                tree::path_pattern_matches(drive, idx, pattern) && record_passes(fold_buf)
+            }
+            Self::Name(needle, expand_dirs) => {
+                let dir_matches = |dir_idx: u32, buf: &mut Vec<u8>| {
+                    drive.records.get(dir_idx as usize).is_some_and(|dir| {
+                        dir.is_directory()
+                            && needle.matches(dir.name(&drive.names), buf)
+                            && filters.matches_record(dir, &drive.names, buf, drive.fold)
+                    })
+                };
+                (needle.matches(name, fold_buf) && record_passes(fold_buf))
+                    || (*expand_dirs
+                        && tree::ancestors(drive, idx)
+                            .any(|dir_idx| dir_matches(dir_idx, fold_buf)))
+            }
+        }
+    }
+}
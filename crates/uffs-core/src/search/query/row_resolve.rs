@@ -70,12 +70,7 @@ fn indices_to_rows_sequential(
             );
             let forensics = row_forensics(rec, &drive.names, path_malformed);
             Some(make_display_row(
-                record_idx,
-                drive.letter,
-                rec,
-                name,
-                path,
-                forensics,
+                record_idx, drive, rec, name, path, forensics,
             ))
         })
         .collect()
@@ -119,12 +114,7 @@ fn indices_to_rows_parallel(
                 );
                 let forensics = row_forensics(rec, &drive.names, path_malformed);
                 local_rows.push(make_display_row(
-                    record_idx,
-                    drive.letter,
-                    rec,
-                    name,
-                    path,
-                    forensics,
+                    record_idx, drive, rec, name, path, forensics,
                 ));
             }
             local_rows
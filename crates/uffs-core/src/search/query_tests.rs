@@ -165,7 +165,7 @@ fn display_row_fields_match_source_data() {
         .iter()
         .find(|row| row.name() == "readme.txt")
         .expect("not found");
-    assert_eq!(row.drive, uffs_mft::platform::DriveLetter::C);
+    assert_eq!(row.drive.letter(), uffs_mft::platform::DriveLetter::C);
     assert_eq!(row.size, 400);
     assert_eq!(row.allocated, 512);
     assert_eq!(row.flags, 0x20);
@@ -1071,6 +1071,8 @@ fn search_index_star_sort_directory_desc() {
             filter_mode: FilterMode::All,
             search_filters: &mut filters,
             drives_filter: &[],
+            fuzzy: None,
+            rank: false,
         },
         FieldId::DirectoryFlag,
         true, // descending
@@ -1111,6 +1113,8 @@ fn search_index_star_sort_directory_asc() {
             filter_mode: FilterMode::All,
             search_filters: &mut filters,
             drives_filter: &[],
+            fuzzy: None,
+            rank: false,
         },
         FieldId::DirectoryFlag,
         false, // ascending
@@ -1190,6 +1194,8 @@ fn search_index_star_sort_hidden_desc() {
             filter_mode: FilterMode::All,
             search_filters: &mut filters,
             drives_filter: &[],
+            fuzzy: None,
+            rank: false,
         },
         FieldId::Hidden,
         true,
@@ -1430,7 +1436,7 @@ fn parallel_drive_scan_merges_global_top_n_across_drives() {
     assert_eq!(rows.len(), 10, "limit=10 must return exactly 10 rows");
     for row in &rows {
         assert_eq!(
-            row.drive,
+            row.drive.letter(),
             uffs_mft::platform::DriveLetter::C,
             "Modified-DESC top-10 must all come from drive C \
              (highest FRS range); got {}:{}",
@@ -1482,7 +1488,7 @@ fn parallel_drive_scan_respects_limit_smaller_than_per_drive_count() {
     // (B's FRS range is higher, so B has the 5 newest records).
     for row in &rows {
         assert_eq!(
-            row.drive,
+            row.drive.letter(),
             uffs_mft::platform::DriveLetter::B,
             "top-5 must all be from drive B"
         );
@@ -1661,3 +1667,62 @@ fn path_sort_non_ext_filter_uses_tree_walk() {
         );
     }
 }
+
+// ── Per-record matcher ────────────────────────────────────────────────
+
+#[test]
+fn record_matcher_agrees_with_the_drive_scan() {
+    let drive = build_test_drive();
+    let mut backend = MultiDriveBackend::new();
+    backend.drives.push(build_test_drive());
+    let all: Vec<u32> = (0..uffs_mft::len_to_u32(drive.records.len())).collect();
+    let cases: [(&str, bool, FilterMode); 10] = [
+        ("*", false, FilterMode::All),
+        ("*", false, FilterMode::DirsOnly),
+        ("readme", false, FilterMode::All),
+        ("*.CSV", false, FilterMode::All),
+        ("dat*", false, FilterMode::All),
+        ("projects\\*.txt", false, FilterMode::All),
+        ("\\projects\\**", false, FilterMode::All),
+        (">^data", false, FilterMode::All),
+        ("projects", true, FilterMode::FilesOnly),
+        ("C:read", false, FilterMode::All),
+    ];
+    for (pattern, match_path, filter_mode) in cases {
+        let mut scan_filters = SearchFilters::default();
+        let mut scanned: Vec<u32> = backend
+            .search(SearchRequest {
+                match_path,
+                filter_mode,
+                ..SearchRequest::new(pattern, &mut scan_filters)
+            })
+            .rows
+            .iter()
+            .map(|row| row.record_index)
+            .collect();
+        scanned.sort_unstable();
+
+        let mut matcher_filters = SearchFilters::default();
+        let matcher = RecordMatcher::new(SearchRequest {
+            match_path,
+            filter_mode,
+            ..SearchRequest::new(pattern, &mut matcher_filters)
+        })
+        .expect("compiles");
+        let mut per_record: Vec<u32> = matcher
+            .matching_rows(&drive, &all)
+            .iter()
+            .map(|row| row.record_index)
+            .collect();
+        per_record.sort_unstable();
+        assert_eq!(per_record, scanned, "pattern {pattern:?}");
+        assert!(!per_record.is_empty(), "pattern {pattern:?} should match");
+    }
+
+    let mut regex_filters = SearchFilters::default();
+    RecordMatcher::new(SearchRequest::new(">(", &mut regex_filters)).expect_err("bad regex");
+    let mut prefix_filters = SearchFilters::default();
+    let other_drive =
+        RecordMatcher::new(SearchRequest::new("D:read", &mut prefix_filters)).expect("compiles");
+    assert!(other_drive.matching_rows(&drive, &all).is_empty());
+}
@@ -0,0 +1,354 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Relevance ranking for interactive queries (`--rank`).
+//!
+//! The default result order is a column sort, which answers "biggest" or
+//! "newest" but not "the file I meant".  Rank mode scores every hit on a few
+//! cheap signals and keeps the best `limit` by that score:
+//!
+//! | Signal                                     | Points             |
+//! |--------------------------------------------|--------------------|
+//! | basename equals the needle (or its stem)   | +400               |
+//! | basename starts with the needle            | +300               |
+//! | basename contains the needle               | +200               |
+//! | only a directory component contains it     | +100               |
+//! | modified < 1 day / 1 week / 1 month / 1 yr | +120 / +90 / +60 / +30 |
+//! | each directory level below the root        | −10 (capped at −100) |
+//! | NTFS metafile name (`$MFT`, `$Bitmap`, …)  | −400               |
+//! | `FILE_ATTRIBUTE_SYSTEM` set                | −150               |
+//! | under a noise directory (`WinSxS`, …)      | −250               |
+//!
+//! The needle is the pattern's last path segment with glob wildcards removed,
+//! folded through the volume's `$UpCase` table; a regex (`>…`) has no literal
+//! core, so every regex hit gets the same (zero) match tier and the other
+//! signals decide.  Directory signals look only at the components below the
+//! volume's display root, whatever the path template renders it as.
+//!
+//! A [`RankCut`] pushes the top-N into the per-drive scans: each drive
+//! scores its hits on the compact records ([`RankNeedle::score_record`]) and
+//! resolves paths for its best `limit` only; the merge
+//! (`search::query::rank_top_n`) re-ranks the survivors of every drive.
+
+use alloc::sync::Arc;
+
+use uffs_text::case_fold::CaseFold;
+
+use super::backend::FilterMode;
+use super::display_row::DisplayRow;
+use crate::compact::DriveCompactIndex;
+use crate::search::tree;
+
+/// Basename equals the needle, or equals it once the extension is dropped.
+const EXACT_POINTS: i64 = 400;
+/// Basename starts with the needle.
+const PREFIX_POINTS: i64 = 300;
+/// Basename contains the needle.
+const SUBSTRING_POINTS: i64 = 200;
+/// The needle only matches a directory component of the path.
+const DIRECTORY_POINTS: i64 = 100;
+
+/// Penalty per directory level below the volume root.
+const DEPTH_PENALTY: i64 = 10;
+/// Depth levels beyond this stop costing points.
+const MAX_DEPTH_PENALIZED: i64 = 10;
+
+/// Penalty for a reserved NTFS metafile name.
+const METAFILE_PENALTY: i64 = 400;
+/// Penalty for `FILE_ATTRIBUTE_SYSTEM`.
+const SYSTEM_PENALTY: i64 = 150;
+/// Penalty for a hit anywhere under a [`NOISE_DIRECTORIES`] entry.
+const NOISE_PENALTY: i64 = 250;
+
+/// `FILE_ATTRIBUTE_SYSTEM`.
+const ATTRIBUTE_SYSTEM: u32 = 0x0004;
+
+/// FILETIME ticks (100 ns) per day.
+const TICKS_PER_DAY: i64 = 86_400 * uffs_time::FILETIME_TICKS_PER_SECOND;
+
+/// Recency bonus tiers: `(max age in days, points)`, newest first.
+const RECENCY_TIERS: [(i64, i64); 4] = [(1, 120), (7, 90), (30, 60), (365, 30)];
+
+/// Directory names whose contents are almost never what an interactive
+/// search is after (compared ASCII case-insensitively).
+const NOISE_DIRECTORIES: &[&str] = &[
+    "WinSxS",
+    "$Recycle.Bin",
+    "System Volume Information",
+    "Installer",
+    "SoftwareDistribution",
+    "DriverStore",
+    "node_modules",
+    ".git",
+];
+
+/// A folded rank needle, built once per query.
+#[derive(Clone)]
+pub struct RankNeedle {
+    /// Literal core of the pattern, folded; empty for a regex or `*`.
+    core: Vec<u16>,
+    /// Case table the core was folded with.
+    fold: CaseFold,
+    /// "Now" as FILETIME ticks, fixed for the whole query.
+    now: i64,
+}
+
+impl RankNeedle {
+    /// Derive the literal core of `pattern` and fix "now" for recency.
+    #[must_use]
+    pub fn new(pattern: &str, fold: CaseFold) -> Self {
+        Self::at(pattern, fold, super::filters::now_filetime())
+    }
+
+    /// Like [`Self::new`] with an explicit "now" (FILETIME ticks).
+    #[must_use]
+    pub fn at(pattern: &str, fold: CaseFold, now: i64) -> Self {
+        let core = if pattern.starts_with('>') {
+            Vec::new()
+        } else {
+            let segment = pattern.rsplit(['\\', '/']).next().unwrap_or(pattern);
+            let literal: String = segment
+                .chars()
+                .filter(|ch| !matches!(ch, '*' | '?'))
+                .collect();
+            fold.fold_to_u16(&literal)
+        };
+        Self { core, fold, now }
+    }
+
+    /// Relevance score of `row`; higher is better.
+    #[must_use]
+    pub fn score(&self, row: &DisplayRow) -> i64 {
+        // Only the directories below the volume root (`C:\`, `srv01\C:\`,
+        // `/evidence/srv01/C/`, …) count.
+        let root = row.drive.display_root();
+        let full_dir = row.path_dir();
+        let dir = full_dir.strip_prefix(&*root).unwrap_or(full_dir);
+        let components = dir
+            .split(['\\', '/'])
+            .filter(|component| !component.is_empty());
+        self.score_parts(row.name(), components, row.modified, row.flags)
+    }
+
+    /// [`Self::score`] of record `record_idx` of `drive`, read from the
+    /// compact index: the parent chain stands in for the resolved path.
+    #[must_use]
+    pub fn score_record(&self, drive: &DriveCompactIndex, record_idx: u32) -> Option<i64> {
+        let rec = drive.records.get(record_idx as usize)?;
+        let components = tree::ancestors(drive, record_idx).filter_map(|dir_idx| {
+            let dir = drive.records.get(dir_idx as usize)?;
+            // The root (its own parent) renders as the volume root.
+            (dir.parent_idx != dir_idx).then(|| dir.name(&drive.names))
+        });
+        Some(self.score_parts(rec.name(&drive.names), components, rec.modified, rec.flags))
+    }
+
+    /// Score a hit from its basename, its directory components below the
+    /// volume root, its modification time and its attribute flags.
+    fn score_parts<'n>(
+        &self,
+        name: &str,
+        components: impl Iterator<Item = &'n str>,
+        modified: i64,
+        flags: u32,
+    ) -> i64 {
+        let mut depth: i64 = 0;
+        let mut in_directory = false;
+        let mut under_noise = false;
+        for component in components {
+            depth = depth.saturating_add(1);
+            in_directory |=
+                !self.core.is_empty() && self.fold.contains_folded(component, &self.core);
+            under_noise |= NOISE_DIRECTORIES
+                .iter()
+                .any(|noise| component.eq_ignore_ascii_case(noise));
+        }
+
+        let mut score = self.match_points(name, in_directory);
+        score += recency_points(self.now.saturating_sub(modified));
+        score -= DEPTH_PENALTY * depth.min(MAX_DEPTH_PENALIZED);
+        if crate::compact::is_ntfs_metafile_name(name) {
+            score -= METAFILE_PENALTY;
+        }
+        if flags & ATTRIBUTE_SYSTEM != 0 {
+            score -= SYSTEM_PENALTY;
+        }
+        if under_noise {
+            score -= NOISE_PENALTY;
+        }
+        score
+    }
+
+    /// Match-tier points of `name` (basename); `in_directory` is whether a
+    /// directory component contains the needle.
+    fn match_points(&self, name: &str, in_directory: bool) -> i64 {
+        if self.core.is_empty() {
+            return 0;
+        }
+        let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+        if self.fold.eq_folded(name, &self.core) || self.fold.eq_folded(stem, &self.core) {
+            EXACT_POINTS
+        } else if self.fold.starts_with_folded(name, &self.core) {
+            PREFIX_POINTS
+        } else if self.fold.contains_folded(name, &self.core) {
+            SUBSTRING_POINTS
+        } else if in_directory {
+            DIRECTORY_POINTS
+        } else {
+            0
+        }
+    }
+}
+
+/// Rank mode pushed into the per-drive scans, carried on
+/// [`super::filters::SearchFilters::rank`]: each drive keeps only its
+/// `limit` best hits before resolving their paths.
+#[derive(Debug, Clone)]
+pub struct RankCut {
+    /// Scorer shared by every drive and the merge (one "now").
+    pub needle: RankNeedle,
+    /// Hits each drive keeps.
+    pub limit: usize,
+    /// Directory / file restriction the merge applies to the rows, so a
+    /// drive does not spend its quota on hits that would be dropped.
+    pub filter_mode: FilterMode,
+}
+
+impl RankCut {
+    /// The cut for a scan ranked by `needle` (`None` = unranked); `None`
+    /// as well without a limit, when every hit is kept anyway.
+    #[must_use]
+    pub fn for_scan(
+        needle: Option<&RankNeedle>,
+        result_limit: Option<u32>,
+        filter_mode: FilterMode,
+    ) -> Option<Arc<Self>> {
+        let limit = usize::try_from(result_limit?).ok()?;
+        needle.map(|ranker| {
+            Arc::new(Self {
+                needle: ranker.clone(),
+                limit,
+                filter_mode,
+            })
+        })
+    }
+}
+
+impl core::fmt::Debug for RankNeedle {
+    /// Manual impl because [`CaseFold`] does not derive `Debug`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RankNeedle")
+            .field("core", &self.core)
+            .field("fold", &"<CaseFold>")
+            .field("now", &self.now)
+            .finish()
+    }
+}
+
+/// Recency bonus for a hit last modified `age` FILETIME ticks ago.
+fn recency_points(age: i64) -> i64 {
+    RECENCY_TIERS
+        .iter()
+        .find(|&&(days, _)| age < days * TICKS_PER_DAY)
+        .map_or(0, |&(_, points)| points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 400 * TICKS_PER_DAY;
+
+    fn row(path: &str, modified: i64, flags: u32) -> DisplayRow {
+        DisplayRow::new(
+            0,
+            uffs_mft::platform::DriveLetter::C,
+            path.to_owned(),
+            0,
+            false,
+            modified,
+            0,
+            0,
+            flags,
+            0,
+            0,
+            0,
+            0,
+        )
+    }
+
+    fn score(pattern: &str, path: &str) -> i64 {
+        RankNeedle::at(pattern, CaseFold::default_table(), NOW).score(&row(path, 0, 0))
+    }
+
+    #[test]
+    fn match_tiers_order_exact_prefix_substring_directory() {
+        let exact = score("report", r"C:\docs\Report.pdf");
+        let prefix = score("report", r"C:\docs\report_2024.pdf");
+        let substring = score("report", r"C:\docs\q3_report_final.pdf");
+        let directory = score("report", r"C:\reports\summary.pdf");
+        assert!(exact > prefix, "{exact} vs {prefix}");
+        assert!(prefix > substring, "{prefix} vs {substring}");
+        assert!(substring > directory, "{substring} vs {directory}");
+    }
+
+    #[test]
+    fn wildcards_and_path_segments_are_stripped_from_the_needle() {
+        assert_eq!(
+            score(r"docs\report*", r"C:\docs\report.pdf"),
+            score("report", r"C:\docs\report.pdf")
+        );
+    }
+
+    #[test]
+    fn shallow_beats_deep() {
+        assert!(score("a.txt", r"C:\a.txt") > score("a.txt", r"C:\x\y\z\a.txt"));
+    }
+
+    #[test]
+    fn recent_beats_old() {
+        let needle = RankNeedle::at("a.txt", CaseFold::default_table(), NOW);
+        let recent = needle.score(&row(r"C:\a.txt", NOW - TICKS_PER_DAY / 2, 0));
+        let old = needle.score(&row(r"C:\a.txt", 0, 0));
+        assert_eq!(recent - old, 120);
+    }
+
+    #[test]
+    fn noise_directories_and_system_files_sink() {
+        let clean = score("shell32.dll", r"C:\Windows\System32\shell32.dll");
+        let noise = score("shell32.dll", r"C:\Windows\winsxs\amd64\shell32.dll");
+        assert!(clean > noise, "{clean} vs {noise}");
+        let needle = RankNeedle::at("boot.ini", CaseFold::default_table(), NOW);
+        let plain = needle.score(&row(r"C:\boot.ini", 0, 0));
+        let system = needle.score(&row(r"C:\boot.ini", 0, ATTRIBUTE_SYSTEM));
+        assert_eq!(plain - system, SYSTEM_PENALTY);
+    }
+
+    #[test]
+    fn host_qualified_roots_are_not_directories() {
+        let needle = RankNeedle::at("srv01", CaseFold::default_table(), NOW);
+        let hosted = DisplayRow::new(
+            0,
+            uffs_mft::platform::VolumeId::parse("srv01\\C").expect("valid volume"),
+            r"srv01\C:\docs\a.pdf".to_owned(),
+            0,
+            false,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        );
+        let local = row(r"C:\docs\a.pdf", 0, 0);
+        assert_eq!(needle.score(&hosted), needle.score(&local));
+        assert_eq!(needle.score(&local), -DEPTH_PENALTY);
+    }
+
+    #[test]
+    fn regex_has_no_match_tier() {
+        assert_eq!(score(">rep.*", r"C:\report.pdf"), 0);
+    }
+}
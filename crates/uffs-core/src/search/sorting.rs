@@ -143,7 +143,10 @@ impl SortKeyNeeds {
             | FieldId::ParityAttributes
             | FieldId::Malformed
             | FieldId::MalformedPath
-            | FieldId::NameHex => 0,
+            | FieldId::NameHex
+            | FieldId::Host
+            | FieldId::Hash
+            | FieldId::MatchedPattern => 0,
         }
     }
 
@@ -195,6 +198,7 @@ const fn is_strict_numeric(column: FieldId) -> bool {
             | FieldId::Modified
             | FieldId::Accessed
             | FieldId::Drive
+            | FieldId::Host
             | FieldId::Descendants
             | FieldId::TreeSize
             | FieldId::TreeAllocated
@@ -301,7 +305,12 @@ fn compare_numeric_column(
         FieldId::Created => row_a.created.cmp(&row_b.created),
         FieldId::Modified => row_a.modified.cmp(&row_b.modified),
         FieldId::Accessed => row_a.accessed.cmp(&row_b.accessed),
-        FieldId::Drive => row_a.drive.cmp(&row_b.drive),
+        FieldId::Drive => row_a
+            .drive
+            .letter()
+            .cmp(&row_b.drive.letter())
+            .then_with(|| row_a.drive.cmp(&row_b.drive)),
+        FieldId::Host => row_a.drive.cmp(&row_b.drive),
         FieldId::Descendants => row_a.descendants.cmp(&row_b.descendants),
         FieldId::TreeSize => row_a.treesize.cmp(&row_b.treesize),
         FieldId::TreeAllocated => tree_allocated_for_row(row_a).cmp(&tree_allocated_for_row(row_b)),
@@ -359,8 +368,8 @@ fn compare_numeric_column(
         // String-based columns never reach this function — the caller's
         // `is_strict_numeric` guard excludes them.  Return `Equal` as a
         // defensive default (the name tiebreaker in `sort_rows_numeric_fast`
-        // will then provide the ordering).  `NameHex` is a non-sortable
-        // projection column and lands here too.
+        // will then provide the ordering).  The non-sortable projection
+        // columns (`NameHex`, `Hash`, `MatchedPattern`) land here too.
         FieldId::Name
         | FieldId::Path
         | FieldId::PathOnly
@@ -369,7 +378,9 @@ fn compare_numeric_column(
         | FieldId::Attributes
         | FieldId::AttributeValue
         | FieldId::ParityAttributes
-        | FieldId::NameHex => core::cmp::Ordering::Equal,
+        | FieldId::NameHex
+        | FieldId::Hash
+        | FieldId::MatchedPattern => core::cmp::Ordering::Equal,
     }
 }
 
@@ -580,7 +591,12 @@ fn compare_by_column(
             .path_only
             .cmp(&key_b.path_only)
             .then_with(|| row_a.path_dir().cmp(row_b.path_dir())),
-        FieldId::Drive => row_a.drive.cmp(&row_b.drive),
+        FieldId::Drive => row_a
+            .drive
+            .letter()
+            .cmp(&row_b.drive.letter())
+            .then_with(|| row_a.drive.cmp(&row_b.drive)),
+        FieldId::Host => row_a.drive.cmp(&row_b.drive),
         FieldId::Extension => key_a.ext.cmp(&key_b.ext).then_with(|| {
             let ext_a = extract_extension_after_dot(row_a.name());
             let ext_b = extract_extension_after_dot(row_b.name());
@@ -640,12 +656,14 @@ fn compare_by_column(
             .cmp(&row_b.malformed_path)
             .then_with(|| key_a.name.cmp(&key_b.name))
             .then_with(|| row_a.name().cmp(row_b.name())),
-        // ── Remaining non-sortable fields: name tiebreaker (incl. NameHex) ──
+        // ── Remaining non-sortable fields: name tiebreaker ──
         FieldId::Name
         | FieldId::Attributes
         | FieldId::AttributeValue
         | FieldId::ParityAttributes
-        | FieldId::NameHex => key_a
+        | FieldId::NameHex
+        | FieldId::Hash
+        | FieldId::MatchedPattern => key_a
             .name
             .cmp(&key_b.name)
             .then_with(|| row_a.name().cmp(row_b.name())),
@@ -702,7 +720,10 @@ pub(crate) const fn field_to_attr_bit(field: FieldId) -> u32 {
         | FieldId::PathLength
         | FieldId::Malformed
         | FieldId::MalformedPath
-        | FieldId::NameHex => 0,
+        | FieldId::NameHex
+        | FieldId::Host
+        | FieldId::Hash
+        | FieldId::MatchedPattern => 0,
     }
 }
 
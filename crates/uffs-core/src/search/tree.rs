@@ -483,6 +483,96 @@ pub(crate) fn tree_search(
     results
 }
 
+/// Per-record form of [`tree_search`]: whether record `record_idx` is in
+/// the set an unlimited `tree_search(drive, pattern_lower, _)` returns.
+///
+/// Walks the record's ancestor chain instead of descending from the
+/// candidate directories, so testing a handful of records costs their
+/// depth rather than a drive scan.
+#[must_use]
+pub(crate) fn path_pattern_matches(
+    drive: &DriveCompactIndex,
+    record_idx: u32,
+    pattern_lower: &str,
+) -> bool {
+    let normalized = pattern_lower.replace('/', "\\");
+    let stripped = normalized.strip_prefix('\\').unwrap_or(&normalized);
+    let segments: Vec<&str> = stripped.split('\\').filter(|seg| !seg.is_empty()).collect();
+    let Some((leaf_pattern, dir_segments)) = segments.split_last() else {
+        return false;
+    };
+    let Some(rec) = drive.records.get(record_idx as usize) else {
+        return false;
+    };
+    let mut fold_buf: Vec<u8> = Vec::with_capacity(256);
+    let folded = drive.fold.fold_into(rec.name(&drive.names), &mut fold_buf);
+    if dir_segments.is_empty() {
+        return !folded.is_empty() && folded != "." && folded.contains(leaf_pattern);
+    }
+    if *leaf_pattern == "**" {
+        if folded.is_empty() || folded == "." {
+            return false;
+        }
+        return ancestors(drive, record_idx)
+            .any(|dir_idx| dir_chain_matches(drive, dir_idx, dir_segments, &mut fold_buf));
+    }
+    if !name_matches(folded, leaf_pattern) {
+        return false;
+    }
+    ancestors(drive, record_idx)
+        .next()
+        .is_some_and(|parent| dir_chain_matches(drive, parent, dir_segments, &mut fold_buf))
+}
+
+/// Whether directory `dir_idx` is reached by walking `segments` the way
+/// [`tree_search`] does: the first segment matches anywhere, each later
+/// one a direct child, and `**` any strict descendant directory.
+fn dir_chain_matches(
+    drive: &DriveCompactIndex,
+    dir_idx: u32,
+    segments: &[&str],
+    fold_buf: &mut Vec<u8>,
+) -> bool {
+    let Some((&segment, outer)) = segments.split_last() else {
+        return false;
+    };
+    let Some(rec) = drive.records.get(dir_idx as usize) else {
+        return false;
+    };
+    if !rec.is_directory() || rec.name_len == 0 {
+        return false;
+    }
+    if segment == "**" {
+        return outer.is_empty()
+            || ancestors(drive, dir_idx)
+                .any(|outer_idx| dir_chain_matches(drive, outer_idx, outer, fold_buf));
+    }
+    if !segment_matches(
+        drive.fold.fold_into(rec.name(&drive.names), fold_buf),
+        segment,
+    ) {
+        return false;
+    }
+    outer.is_empty()
+        || ancestors(drive, dir_idx)
+            .next()
+            .is_some_and(|parent| dir_chain_matches(drive, parent, outer, fold_buf))
+}
+
+/// Strict ancestors of `record_idx`, nearest first, stopping at the root
+/// (whose parent is itself) and capped like [`resolve_path`]'s walk.
+pub(crate) fn ancestors(
+    drive: &DriveCompactIndex,
+    record_idx: u32,
+) -> impl Iterator<Item = u32> + '_ {
+    core::iter::successors(Some(record_idx), move |&idx| {
+        let parent = drive.records.get(idx as usize)?.parent_idx;
+        (parent != u32::MAX && parent != idx).then_some(parent)
+    })
+    .skip(1)
+    .take(256)
+}
+
 /// Recursively collect all descendant DIRECTORY indices from a directory.
 fn collect_descendant_dirs(
     drive: &DriveCompactIndex,
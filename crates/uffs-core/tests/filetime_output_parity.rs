@@ -54,19 +54,23 @@
 // reachable from this integration test's tiny surface (`OutputConfig` +
 // `DisplayRow` + `uffs_time` constants).  The pattern mirrors
 // `uffs-mcp/tests/mcp_protocol.rs`.
+use aho_corasick as _;
 use anyhow as _;
+use blake3 as _;
 use bytemuck as _;
 use chrono as _;
 use criterion as _;
 use devicons as _;
 use globset as _;
 use itoa as _;
+use md5 as _;
 use memchr as _;
 use memmap2 as _;
 use rayon as _;
 use regex as _;
 use rustc_hash as _;
 use serde_json as _;
+use sha1 as _;
 use sha2 as _;
 use tempfile as _;
 use thiserror as _;
@@ -79,6 +83,7 @@ use uffs_mft as _;
 use uffs_polars as _;
 use uffs_security as _;
 use uffs_text as _;
+use xxhash_rust as _;
 use zstd as _;
 
 /// FILETIME constants for reference calendar dates, computed once so
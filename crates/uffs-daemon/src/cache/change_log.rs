@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Durable per-volume change feed: every journal delta the daemon
+//! applies, appended as NDJSON under one directory per volume (its
+//! [`VolumeId::file_stem`]).
+//!
+//! The USN journal is a ring buffer — once it wraps, `changed_since`
+//! can only say "unknowable".  When `[change_log]` is enabled the
+//! apply path ([`crate::index::IndexManager::handle_journal_apply`] /
+//! `handle_journal_save`) also hands each applied batch, paths resolved
+//! against the index, to [`ChangeLog::append`]; the `tail_changes` RPC
+//! reads it back through [`ChangeLog::tail`].
+//!
+//! ## Layout
+//!
+//! One directory per drive holding segment files named after the first
+//! sequence number they contain (`00000000000000000001.ndjson`), one
+//! [`ChangeLogEntry`] per line.  Sequence numbers are per drive, start
+//! at `1` and are recovered from the newest segment's last line on the
+//! first append after a restart, so the cursor survives the daemon.
+//! A final line torn by a crash mid-append is cut off at that point.
+//!
+//! ## Durability and replays
+//!
+//! Each batch is written with one append and synced before the sequence
+//! counter moves.  Within a batch, entries are stamped in USN order, so
+//! the last line carries the newest USN logged.  Entries at or below it
+//! are dropped as replays: after a restart the journal is re-read from
+//! the last saved cursor, which can trail what the log already holds.
+//! A recreated journal starts its USNs over, so
+//! [`ChangeLog::journal_wrapped`] clears the mark.
+//!
+//! ## Retention
+//!
+//! Appends roll to a new segment once the active one reaches
+//! `segment_mb`; after each append the oldest segments are deleted
+//! while the drive's log exceeds `max_mb_per_drive` or the segment's
+//! newest entry is older than `max_age_days`.  The active segment is
+//! never pruned.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead as _, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use uffs_client::protocol::response::{ChangeLogEntry, TailChangesResponse};
+use uffs_mft::platform::VolumeId;
+
+use super::volume_store::{VolumeRoot, list_numbered, numbered_path};
+use crate::config::ChangeLogConfig;
+
+/// Default and hard cap on entries per `tail_changes` call — keeps one
+/// response to a few MB of JSON.
+pub(crate) const MAX_TAIL_ENTRIES: usize = 10_000;
+
+/// File extension of a segment.
+const SEGMENT_EXTENSION: &str = "ndjson";
+
+/// One MiB, for the `*_mb` config knobs.
+const MIB: u64 = 1024 * 1024;
+
+/// Write position of one drive's log.
+#[derive(Debug)]
+struct DriveLog {
+    /// Highest sequence number written so far (`0` = empty log).
+    last_seq: u64,
+    /// Newest journal USN logged so far; changes at or below it are
+    /// replays.
+    last_usn: i64,
+    /// Active segment and its current size in bytes.
+    segment: Option<(PathBuf, u64)>,
+}
+
+/// The on-disk change feed for every drive.
+#[derive(Debug)]
+pub(crate) struct ChangeLog {
+    /// Directory holding one sub-directory per volume.
+    root: VolumeRoot,
+    /// Size at which the active segment rolls over.
+    segment_bytes: u64,
+    /// Per-drive size budget across all segments.
+    max_bytes: u64,
+    /// Age past which a non-active segment is pruned.
+    max_age: Duration,
+    /// Write positions, recovered lazily per drive.  Serialises writers
+    /// only: [`Self::tail`] reads complete lines without it.
+    drives: Mutex<HashMap<VolumeId, DriveLog>>,
+}
+
+impl ChangeLog {
+    /// Build the change log described by `config`, or `None` when it is
+    /// disabled.  The directory defaults to `<cache dir>/changes` and is
+    /// created on the first append.
+    #[must_use]
+    pub(crate) fn from_config(config: &ChangeLogConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            Self::new(
+                VolumeRoot::configured(config.dir.as_deref(), "changes"),
+                config,
+            )
+        })
+    }
+
+    /// Change log rooted at `root` with `config`'s retention limits.
+    #[must_use]
+    pub(crate) fn new(root: VolumeRoot, config: &ChangeLogConfig) -> Self {
+        Self {
+            root,
+            segment_bytes: config.segment_mb.saturating_mul(MIB).max(1),
+            max_bytes: config.max_mb_per_drive.saturating_mul(MIB),
+            max_age: Duration::from_secs(config.max_age_days.saturating_mul(86_400)),
+            drives: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Append `entries` to `volume`'s log, stamping consecutive sequence
+    /// numbers in USN order over whatever `seq` they carry, then apply
+    /// retention.  Entries whose USN was already logged are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Propagates directory-creation, write, sync and serialisation
+    /// failures; the sequence counter only advances for a batch that was
+    /// written and synced, and a batch that was not is cut back off the
+    /// segment.
+    pub(crate) fn append(
+        &self,
+        volume: VolumeId,
+        mut entries: Vec<ChangeLogEntry>,
+    ) -> std::io::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let dir = self.root.dir(volume);
+        let mut drives = self.lock();
+        let log = drive_log(&mut drives, volume, &dir)?;
+
+        let replayed_to = log.last_usn;
+        entries.retain(|entry| entry.usn == 0 || entry.usn > replayed_to);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        entries.sort_by_key(|entry| entry.usn);
+
+        let mut lines = String::new();
+        let mut seq = log.last_seq;
+        for entry in &mut entries {
+            seq = seq.saturating_add(1);
+            entry.seq = seq;
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+
+        let (path, size) = match &log.segment {
+            Some((path, size)) if *size < self.segment_bytes => (path.clone(), *size),
+            Some(_) | None => {
+                uffs_mft::cache::create_secure_dir(&dir)?;
+                (segment_path(&dir, log.last_seq.saturating_add(1)), 0)
+            }
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        if let Err(err) = write_and_sync(&mut file, lines.as_bytes()) {
+            // Cut the failed batch back off so the next append does not
+            // glue onto a torn line; failing that, forget the drive so the
+            // next append recovers it from disk, torn tail and all.
+            if file.set_len(size).and_then(|()| file.sync_data()).is_ok() {
+                log.segment = Some((path, size));
+            } else {
+                drives.remove(&volume);
+            }
+            return Err(err);
+        }
+        log.last_seq = seq;
+        log.last_usn = entries
+            .iter()
+            .map(|entry| entry.usn)
+            .fold(replayed_to, i64::max);
+        log.segment = Some((
+            path.clone(),
+            size.saturating_add(uffs_mft::usize_to_u64(lines.len())),
+        ));
+        drop(drives);
+
+        self.prune(&dir, &path)
+    }
+
+    /// Forget the newest logged USN of `volume`: its journal was
+    /// recreated and numbers its records from the start again.
+    ///
+    /// # Errors
+    ///
+    /// Propagates the failure to recover the drive's write position.
+    pub(crate) fn journal_wrapped(&self, volume: VolumeId) -> std::io::Result<()> {
+        let dir = self.root.dir(volume);
+        let mut drives = self.lock();
+        drive_log(&mut drives, volume, &dir)?.last_usn = 0;
+        Ok(())
+    }
+
+    /// Read up to `max_entries` entries of `volume`'s log after
+    /// `after_seq` (clamped to [`MAX_TAIL_ENTRIES`]).
+    ///
+    /// Runs without the writers' lock: a line still being appended has
+    /// no newline yet and ends the read, and a segment pruned after the
+    /// listing is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Propagates directory-listing and read failures.  A missing log
+    /// directory is an empty feed, not an error.
+    pub(crate) fn tail(
+        &self,
+        volume: VolumeId,
+        after_seq: u64,
+        max_entries: usize,
+    ) -> std::io::Result<TailChangesResponse> {
+        let limit = max_entries.clamp(1, MAX_TAIL_ENTRIES);
+        let dir = self.root.dir(volume);
+        let segments = list_segments(&dir)?;
+        let oldest_seq = segments.first().map_or(0, |&(first, _)| first);
+        let start = segments
+            .iter()
+            .rposition(|&(first, _)| first <= after_seq.saturating_add(1))
+            .unwrap_or(0);
+
+        let mut entries = Vec::new();
+        let mut truncated = false;
+        let mut line = Vec::new();
+        'segments: for (_, path) in segments.iter().skip(start) {
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            let mut reader = BufReader::new(file);
+            loop {
+                line.clear();
+                reader.read_until(b'\n', &mut line)?;
+                if line.last() != Some(&b'\n') {
+                    continue 'segments;
+                }
+                let Ok(entry) = serde_json::from_slice::<ChangeLogEntry>(&line) else {
+                    continue;
+                };
+                if entry.seq <= after_seq {
+                    continue;
+                }
+                if entries.len() == limit {
+                    truncated = true;
+                    break 'segments;
+                }
+                entries.push(entry);
+            }
+        }
+
+        Ok(TailChangesResponse {
+            drive: Some(volume.letter()),
+            next_seq: entries.last().map_or(after_seq, |entry| entry.seq),
+            oldest_seq,
+            complete: oldest_seq <= after_seq.saturating_add(1),
+            truncated,
+            entries,
+        })
+    }
+
+    /// Delete the oldest segments of `dir` (never `active`) while the
+    /// log is over its size budget or they have aged out.
+    fn prune(&self, dir: &Path, active: &Path) -> std::io::Result<()> {
+        let segments = list_segments(dir)?;
+        let mut sized: Vec<(PathBuf, u64, Option<SystemTime>)> = segments
+            .into_iter()
+            .map(|(_, path)| {
+                let meta = std::fs::metadata(&path).ok();
+                let len = meta.as_ref().map_or(0, std::fs::Metadata::len);
+                let modified = meta.and_then(|found| found.modified().ok());
+                (path, len, modified)
+            })
+            .collect();
+        let mut total: u64 = sized.iter().map(|&(_, len, _)| len).sum();
+        let now = SystemTime::now();
+        sized.retain(|(path, _, _)| path != active);
+        for (path, len, modified) in sized {
+            let aged_out = modified
+                .and_then(|at| now.duration_since(at).ok())
+                .is_some_and(|age| age > self.max_age);
+            if total <= self.max_bytes && !aged_out {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total = total.saturating_sub(len);
+        }
+        Ok(())
+    }
+
+    /// Poison-tolerant lock of the per-drive write positions.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<VolumeId, DriveLog>> {
+        self.drives
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Path of the segment whose first entry is `first_seq`.
+fn segment_path(dir: &Path, first_seq: u64) -> PathBuf {
+    numbered_path(dir, first_seq, SEGMENT_EXTENSION)
+}
+
+/// `(first_seq, path)` of every segment in `dir`, oldest first.  A
+/// missing directory has none.
+fn list_segments(dir: &Path) -> std::io::Result<Vec<(u64, PathBuf)>> {
+    list_numbered(dir, SEGMENT_EXTENSION)
+}
+
+/// `volume`'s write position, recovered from disk on first use.
+fn drive_log<'drives>(
+    drives: &'drives mut HashMap<VolumeId, DriveLog>,
+    volume: VolumeId,
+    dir: &Path,
+) -> std::io::Result<&'drives mut DriveLog> {
+    Ok(match drives.entry(volume) {
+        std::collections::hash_map::Entry::Occupied(slot) => slot.into_mut(),
+        std::collections::hash_map::Entry::Vacant(slot) => slot.insert(recover(dir)?),
+    })
+}
+
+/// Write one batch at the end of `file` and sync it.
+fn write_and_sync(file: &mut File, bytes: &[u8]) -> std::io::Result<()> {
+    #[cfg(test)]
+    tests::injected_write_failure(file, bytes)?;
+    file.write_all(bytes)?;
+    file.sync_data()
+}
+
+/// Recover a drive's write position from its newest segment: the last
+/// parseable line's `seq` and USN, or the segment's own first sequence
+/// number minus one when it holds none.  A final line without its
+/// newline — an append the daemon died in — is truncated away first, so
+/// the next append starts on a line of its own.
+fn recover(dir: &Path) -> std::io::Result<DriveLog> {
+    let Some((first_seq, path)) = list_segments(dir)?.pop() else {
+        return Ok(DriveLog {
+            last_seq: 0,
+            last_usn: 0,
+            segment: None,
+        });
+    };
+    let mut bytes = std::fs::read(&path)?;
+    let complete = bytes
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline.saturating_add(1));
+    if complete < bytes.len() {
+        tracing::warn!(
+            target: "cache.store",
+            segment = %path.display(),
+            torn_bytes = bytes.len() - complete,
+            "Change-log segment ends in a torn line; truncating it"
+        );
+        bytes.truncate(complete);
+        let file = std::fs::OpenOptions::new().write(true).open(&path)?;
+        file.set_len(uffs_mft::usize_to_u64(complete))?;
+        file.sync_data()?;
+    }
+
+    let last = bytes
+        .rsplit(|&byte| byte == b'\n')
+        .find_map(|line| serde_json::from_slice::<ChangeLogEntry>(line).ok());
+    Ok(DriveLog {
+        last_seq: last
+            .as_ref()
+            .map_or_else(|| first_seq.saturating_sub(1), |entry| entry.seq),
+        last_usn: last.map_or(0, |entry| entry.usn),
+        segment: Some((path, uffs_mft::usize_to_u64(complete))),
+    })
+}
+
+#[cfg(test)]
+#[path = "change_log_tests.rs"]
+mod tests;
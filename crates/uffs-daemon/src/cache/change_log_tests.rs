@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for the on-disk change feed: sequence numbering, cursor
+//! paging, restart recovery, torn-line repair, failed-append rollback,
+//! replay dedupe, segment rotation and retention.
+
+use core::cell::Cell;
+
+use uffs_client::protocol::response::ChangeLogKind;
+use uffs_mft::platform::DriveLetter;
+
+use super::*;
+
+std::thread_local! {
+    /// Bytes the next [`write_and_sync`] on this thread writes before
+    /// failing, if armed.
+    static TORN_WRITE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Fail the next batch write on this thread after `bytes` bytes.
+fn tear_next_write(bytes: usize) {
+    TORN_WRITE.with(|torn| torn.set(Some(bytes)));
+}
+
+/// Hook in [`write_and_sync`]: write the armed prefix of `bytes`, then
+/// fail as a full disk would.
+pub(super) fn injected_write_failure(file: &mut File, bytes: &[u8]) -> std::io::Result<()> {
+    let Some(torn) = TORN_WRITE.with(Cell::take) else {
+        return Ok(());
+    };
+    file.write_all(bytes.get(..torn).unwrap_or(bytes))?;
+    Err(std::io::Error::other("injected write failure"))
+}
+
+fn entry(frs: u64) -> ChangeLogEntry {
+    ChangeLogEntry {
+        seq: 0,
+        at_ms: 1_000,
+        kind: ChangeLogKind::Created,
+        frs,
+        path: format!("C:\\file{frs}.txt"),
+        old_path: None,
+        usn: 0,
+    }
+}
+
+/// [`entry`] for a journal change at `usn`.
+fn journal_entry(frs: u64, usn: i64) -> ChangeLogEntry {
+    ChangeLogEntry { usn, ..entry(frs) }
+}
+
+/// Change log with byte-sized limits so rotation is cheap to provoke.
+fn tiny_log(root: &Path, segment_bytes: u64, max_bytes: u64) -> ChangeLog {
+    ChangeLog {
+        root: VolumeRoot::new(root.to_path_buf()),
+        segment_bytes,
+        max_bytes,
+        max_age: Duration::from_secs(3600),
+        drives: Mutex::new(HashMap::new()),
+    }
+}
+
+fn seqs(response: &TailChangesResponse) -> Vec<u64> {
+    response.entries.iter().map(|entry| entry.seq).collect()
+}
+
+#[test]
+fn append_numbers_entries_and_tail_pages_by_cursor() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let log = tiny_log(tmp.path(), MIB, MIB);
+    log.append(DriveLetter::C.into(), vec![entry(10), entry(11), entry(12)])
+        .expect("append");
+    log.append(DriveLetter::D.into(), vec![entry(99)])
+        .expect("append");
+
+    let first = log.tail(DriveLetter::C.into(), 0, 2).expect("tail");
+    assert_eq!(seqs(&first), [1, 2]);
+    assert!(first.truncated && first.complete);
+    assert_eq!((first.next_seq, first.oldest_seq), (2, 1));
+
+    let rest = log
+        .tail(DriveLetter::C.into(), first.next_seq, 2)
+        .expect("tail");
+    assert_eq!(seqs(&rest), [3]);
+    assert!(!rest.truncated);
+
+    let idle = log
+        .tail(DriveLetter::C.into(), rest.next_seq, 2)
+        .expect("tail");
+    assert!(idle.entries.is_empty());
+    assert_eq!(idle.next_seq, 3, "an empty page keeps the cursor");
+
+    let other = log.tail(DriveLetter::D.into(), 0, 10).expect("tail");
+    assert_eq!(seqs(&other), [1], "sequences are per drive");
+    let never = log.tail(DriveLetter::E.into(), 0, 10).expect("tail");
+    assert!(never.entries.is_empty() && never.complete);
+}
+
+#[test]
+fn sequence_survives_a_restart() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    tiny_log(tmp.path(), MIB, MIB)
+        .append(DriveLetter::C.into(), vec![entry(1), entry(2)])
+        .expect("append");
+
+    let reopened = tiny_log(tmp.path(), MIB, MIB);
+    reopened
+        .append(DriveLetter::C.into(), vec![entry(3)])
+        .expect("append");
+    let all = reopened.tail(DriveLetter::C.into(), 0, 10).expect("tail");
+    assert_eq!(seqs(&all), [1, 2, 3]);
+    assert_eq!(
+        all.entries.last().map(|entry| entry.frs),
+        Some(3),
+        "the new entry follows the recovered ones"
+    );
+}
+
+#[test]
+fn rotation_and_size_retention_open_a_reported_gap() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    // Each batch fills a segment; the budget holds about two of them.
+    let line_len = serde_json::to_string(&entry(1)).expect("json").len() + 1;
+    let segment = uffs_mft::usize_to_u64(line_len * 2);
+    let log = tiny_log(tmp.path(), segment, segment * 2 + 10);
+    for batch in 0..5_u64 {
+        log.append(DriveLetter::C.into(), vec![
+            entry(batch * 2),
+            entry(batch * 2 + 1),
+        ])
+        .expect("append");
+    }
+
+    let segments = list_segments(&tmp.path().join("C")).expect("list");
+    assert_eq!(
+        segments.iter().map(|&(first, _)| first).collect::<Vec<_>>(),
+        [7, 9],
+        "oldest segments pruned down to the budget"
+    );
+
+    let behind = log.tail(DriveLetter::C.into(), 2, 100).expect("tail");
+    assert!(!behind.complete, "entries 3..=6 were pruned unseen");
+    assert_eq!(behind.oldest_seq, 7);
+    assert_eq!(seqs(&behind), [7, 8, 9, 10]);
+
+    let caught_up = log.tail(DriveLetter::C.into(), 8, 100).expect("tail");
+    assert!(caught_up.complete);
+    assert_eq!(seqs(&caught_up), [9, 10]);
+}
+
+#[test]
+fn a_torn_final_line_is_skipped_by_tail_and_cut_on_reopen() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    tiny_log(tmp.path(), MIB, MIB)
+        .append(DriveLetter::C.into(), vec![entry(1), entry(2)])
+        .expect("append");
+    let segment = segment_path(&tmp.path().join("C"), 1);
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&segment)
+        .and_then(|mut file| file.write_all(br#"{"seq":3,"at_ms":1,"ki"#))
+        .expect("tear the last line");
+
+    let reopened = tiny_log(tmp.path(), MIB, MIB);
+    let before = reopened.tail(DriveLetter::C.into(), 0, 10).expect("tail");
+    assert_eq!(seqs(&before), [1, 2], "the torn line is not served");
+
+    reopened
+        .append(DriveLetter::C.into(), vec![entry(3)])
+        .expect("append");
+    let after = reopened.tail(DriveLetter::C.into(), 0, 10).expect("tail");
+    assert_eq!(seqs(&after), [1, 2, 3]);
+    let text = std::fs::read_to_string(&segment).expect("read segment");
+    assert!(
+        text.lines()
+            .all(|line| serde_json::from_str::<ChangeLogEntry>(line).is_ok()),
+        "the torn bytes were cut before the append: {text}"
+    );
+}
+
+#[test]
+fn replayed_usns_are_logged_once_across_a_restart() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let log = tiny_log(tmp.path(), MIB, MIB);
+    log.append(DriveLetter::C.into(), vec![
+        journal_entry(11, 200),
+        journal_entry(10, 100),
+    ])
+    .expect("append");
+    log.append(DriveLetter::C.into(), vec![
+        journal_entry(10, 100),
+        journal_entry(12, 300),
+    ])
+    .expect("append");
+
+    let reopened = tiny_log(tmp.path(), MIB, MIB);
+    reopened
+        .append(DriveLetter::C.into(), vec![
+            journal_entry(11, 200),
+            journal_entry(12, 300),
+        ])
+        .expect("append");
+    let all = reopened.tail(DriveLetter::C.into(), 0, 10).expect("tail");
+    let logged: Vec<(u64, u64, i64)> = all
+        .entries
+        .iter()
+        .map(|entry| (entry.seq, entry.frs, entry.usn))
+        .collect();
+    assert_eq!(
+        logged,
+        [(1, 10, 100), (2, 11, 200), (3, 12, 300)],
+        "stamped in USN order, replays dropped"
+    );
+
+    reopened
+        .journal_wrapped(DriveLetter::C.into())
+        .expect("reset");
+    reopened
+        .append(DriveLetter::C.into(), vec![journal_entry(13, 5)])
+        .expect("append");
+    let wrapped = reopened.tail(DriveLetter::C.into(), 3, 10).expect("tail");
+    assert_eq!(
+        seqs(&wrapped),
+        [4],
+        "a recreated journal's low USNs are new changes"
+    );
+}
+
+#[test]
+fn a_failed_append_is_cut_back_off_the_segment() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let log = tiny_log(tmp.path(), MIB, MIB);
+    log.append(DriveLetter::C.into(), vec![journal_entry(1, 10)])
+        .expect("append");
+    let segment = segment_path(&tmp.path().join("C"), 1);
+    let size = std::fs::metadata(&segment).expect("segment").len();
+
+    tear_next_write(7);
+    let err = log
+        .append(DriveLetter::C.into(), vec![journal_entry(2, 20)])
+        .expect_err("injected failure");
+    assert_eq!(err.to_string(), "injected write failure");
+    assert_eq!(
+        std::fs::metadata(&segment).expect("segment").len(),
+        size,
+        "the torn batch is truncated away"
+    );
+
+    log.append(DriveLetter::C.into(), vec![journal_entry(3, 30)])
+        .expect("append after the failure");
+    let all = log.tail(DriveLetter::C.into(), 0, 10).expect("tail");
+    let logged: Vec<(u64, u64)> = all
+        .entries
+        .iter()
+        .map(|entry| (entry.seq, entry.frs))
+        .collect();
+    assert_eq!(
+        logged,
+        [(1, 1), (2, 3)],
+        "the failed batch took no sequence"
+    );
+    let text = std::fs::read_to_string(&segment).expect("read segment");
+    assert!(
+        text.lines()
+            .all(|line| serde_json::from_str::<ChangeLogEntry>(line).is_ok()),
+        "no torn bytes before the next batch: {text}"
+    );
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Persistent content-hash cache: one
+//! [`uffs_core::aggregate::HashCache`] per volume, loaded on first use and
+//! written back after every verification pass that added digests.
+//!
+//! When `[hash_cache]` is enabled (the default) duplicate verification
+//! ([`crate::index::IndexManager::run_aggregations`]) looks every member
+//! digest up here before reading the file, the journal apply path drops
+//! the digests of changed files ([`ContentHashStore::invalidate`]), and the
+//! `hash` search column renders the strongest full-content digest on file.
+//!
+//! ## Layout
+//!
+//! ```text
+//! <root>/C.hashes         HashCache sidecar of drive C:
+//! <root>/srv01@C.hashes   sidecar of srv01's C:
+//! ```
+//!
+//! A sidecar that fails to parse is treated as empty and overwritten on
+//! the next save — the cache only ever saves work, it never decides a
+//! result.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use uffs_core::aggregate::hash_cache::{ContentKey, Digest, HashAlgo, HashCache, HashKind};
+use uffs_mft::platform::VolumeId;
+
+use super::volume_store::VolumeRoot;
+use crate::config::HashCacheConfig;
+
+/// File extension of a volume's sidecar.
+const SIDECAR_EXTENSION: &str = "hashes";
+
+/// The on-disk content-hash cache for every volume.
+#[derive(Debug)]
+pub(crate) struct ContentHashStore {
+    /// Directory holding one sidecar per volume.
+    root: VolumeRoot,
+    /// Tables loaded so far.
+    tables: Mutex<HashMap<VolumeId, HashCache>>,
+}
+
+impl ContentHashStore {
+    /// Build the store described by `config`, or `None` when the cache is
+    /// disabled.  The directory defaults to `<cache dir>/hashes` and is
+    /// created on the first save.
+    #[must_use]
+    pub(crate) fn from_config(config: &HashCacheConfig) -> Option<Self> {
+        config
+            .enabled
+            .then(|| Self::new(VolumeRoot::configured(config.dir.as_deref(), "hashes")))
+    }
+
+    /// Store rooted at `root`.
+    #[must_use]
+    pub(crate) fn new(root: VolumeRoot) -> Self {
+        Self {
+            root,
+            tables: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached digest of `kind` for `key` on `volume`.
+    pub(crate) fn lookup(
+        &self,
+        volume: VolumeId,
+        key: ContentKey,
+        kind: HashKind,
+    ) -> Option<Digest> {
+        self.with_table(volume, |table| table.get(key, kind))
+    }
+
+    /// Cache `digest` as `key`'s digest of `kind` on `volume`.
+    pub(crate) fn record(&self, volume: VolumeId, key: ContentKey, kind: HashKind, digest: Digest) {
+        self.with_table(volume, |table| table.insert(key, kind, digest));
+    }
+
+    /// Strongest full-content digest cached for `key` on `volume`.
+    pub(crate) fn full_digest(
+        &self,
+        volume: VolumeId,
+        key: ContentKey,
+    ) -> Option<(HashAlgo, Digest)> {
+        self.with_table(volume, |table| table.full_digest(key))
+    }
+
+    /// Drop the digests of every file on `volume` whose FRS is in `frs`.
+    /// Returns how many digests were removed.
+    pub(crate) fn invalidate(&self, volume: VolumeId, frs: &[u64]) -> usize {
+        if frs.is_empty() {
+            return 0;
+        }
+        self.with_table(volume, |table| table.invalidate(frs))
+    }
+
+    /// Write every table changed since it was loaded or last saved.
+    ///
+    /// # Errors
+    ///
+    /// The first write failure; the failed table stays dirty and is
+    /// retried on the next save.
+    pub(crate) fn save(&self) -> std::io::Result<()> {
+        let mut tables = self.lock();
+        for (&volume, table) in tables.iter_mut().filter(|(_, table)| table.is_dirty()) {
+            std::fs::create_dir_all(self.root.path())?;
+            write_sidecar(&self.root.file(volume, SIDECAR_EXTENSION), table)?;
+            table.mark_clean();
+        }
+        drop(tables);
+        Ok(())
+    }
+
+    /// Run `op` on `volume`'s table, loading the sidecar on first use.
+    fn with_table<T>(&self, volume: VolumeId, op: impl FnOnce(&mut HashCache) -> T) -> T {
+        let mut tables = self.lock();
+        let table = tables
+            .entry(volume)
+            .or_insert_with(|| load_sidecar(&self.root.file(volume, SIDECAR_EXTENSION)));
+        let outcome = op(table);
+        drop(tables);
+        outcome
+    }
+
+    /// Lock the table map, recovering from poison: a panic mid-insert
+    /// leaves at worst one missing digest.
+    fn lock(&self) -> MutexGuard<'_, HashMap<VolumeId, HashCache>> {
+        self.tables.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Load a sidecar; missing or unreadable files give an empty table.
+fn load_sidecar(path: &Path) -> HashCache {
+    let Ok(file) = File::open(path) else {
+        return HashCache::new();
+    };
+    HashCache::read_from(BufReader::new(file)).unwrap_or_else(|err| {
+        tracing::warn!(
+            path = %path.display(),
+            error = %err,
+            "Content-hash sidecar unreadable; starting empty",
+        );
+        HashCache::new()
+    })
+}
+
+/// Write `table` to `path` through a temporary file, so a crash mid-write
+/// never leaves a truncated sidecar behind.
+fn write_sidecar(path: &Path, table: &HashCache) -> std::io::Result<()> {
+    let temp = path.with_extension("hashes.tmp");
+    table.write_to(BufWriter::new(File::create(&temp)?))?;
+    std::fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use uffs_core::aggregate::hash_cache::HashSpan;
+
+    use super::*;
+
+    #[test]
+    fn digests_survive_a_reload_and_invalidation_persists() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let volume = VolumeId::from(uffs_mft::platform::DriveLetter::C);
+        let key = ContentKey {
+            file_ref: uffs_core::compact::CompactRecord::pack_file_reference(42, 1),
+            size: 5,
+            modified: 1,
+        };
+        let kind = HashKind::new(HashAlgo::Blake3, HashSpan::Full);
+
+        let store = ContentHashStore::new(VolumeRoot::new(dir.path().to_path_buf()));
+        store.record(volume, key, kind, HashAlgo::Blake3.digest(b"hello"));
+        store.save().expect("save");
+
+        let reloaded = ContentHashStore::new(VolumeRoot::new(dir.path().to_path_buf()));
+        assert_eq!(
+            reloaded.lookup(volume, key, kind),
+            Some(HashAlgo::Blake3.digest(b"hello"))
+        );
+        assert_eq!(
+            reloaded.full_digest(volume, key).map(|(algo, _)| algo),
+            Some(HashAlgo::Blake3)
+        );
+
+        assert_eq!(reloaded.invalidate(volume, &[42]), 1);
+        reloaded.save().expect("save");
+        let after = ContentHashStore::new(VolumeRoot::new(dir.path().to_path_buf()));
+        assert_eq!(after.lookup(volume, key, kind), None);
+    }
+}
@@ -0,0 +1,430 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Point-in-time index history: periodic compact-index checkpoints plus
+//! the journal batches applied between them, under one directory per
+//! volume (its [`VolumeId::file_stem`]: `C`, `srv01@C`).
+//!
+//! When `[history]` is enabled the journal apply path
+//! ([`crate::index::IndexManager::handle_journal_apply`] /
+//! `handle_journal_save`) hands every applied batch to
+//! [`History::record`]. Once a drive's newest checkpoint is older than
+//! `checkpoint_hours`, the *pre-batch* body is written as a fresh
+//! checkpoint first; the batch then goes to that checkpoint's delta file.
+//! [`History::materialise`] rebuilds the body at any retained moment by
+//! loading the newest checkpoint at or before it and replaying its
+//! batches up to that moment through the same
+//! [`uffs_core::compact_loader::apply_usn_patch`] the live path uses.
+//!
+//! ## Layout
+//!
+//! ```text
+//! <root>/C/00000001760000000000.checkpoint     encrypted compact body
+//! <root>/C/00000001760000000000.deltas.ndjson  batches applied after it
+//! <root>/srv01@C/...                           a hosted volume's history
+//! ```
+//!
+//! Both are named after the checkpoint time (Unix ms). Checkpoints reuse
+//! the compact cache's zstd + AES-256-GCM format
+//! ([`uffs_core::compact_cache::write_compact_checkpoint`]); each delta
+//! line is one [`DeltaBatch`].
+//!
+//! ## Retention
+//!
+//! After each checkpoint, checkpoints older than `retention_days` are
+//! deleted together with their delta files. The newest checkpoint is
+//! never pruned, so a drive always keeps one restorable point.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uffs_core::compact::DriveCompactIndex;
+use uffs_mft::platform::VolumeId;
+use uffs_mft::usn::{FileChange, RecordMeta, Usn};
+
+use super::volume_store::{VolumeRoot, list_numbered, numbered_path};
+use crate::config::HistoryConfig;
+
+/// File extension of a checkpoint body.
+const CHECKPOINT_EXTENSION: &str = "checkpoint";
+
+/// Suffix of a checkpoint's delta file.
+const DELTAS_SUFFIX: &str = "deltas.ndjson";
+
+/// Why a historical state could not be rebuilt.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum HistoryError {
+    /// `[history]` (or, for an `@<time>` diff baseline, `[snapshots]`)
+    /// is not enabled in `daemon.toml`.
+    #[error(
+        "index history is disabled; set `[history] enabled = true` (or `[snapshots]` for \
+         `@<time>` diff baselines) in daemon.toml"
+    )]
+    Disabled,
+    /// The requested moment did not parse.
+    #[error("cannot read '{0}' as a point in time (try `yesterday`, `2026-10-01`, `7d`)")]
+    BadTime(String),
+    /// No retained checkpoint is at or before the requested moment.
+    #[error("no retained index state for {drive}: at or before {at_ms} ms (oldest: {oldest})")]
+    NoPoint {
+        /// Volume asked about.
+        drive: VolumeId,
+        /// Requested moment (Unix ms).
+        at_ms: u64,
+        /// Oldest retained checkpoint, rendered for the message.
+        oldest: String,
+    },
+    /// Reading a checkpoint or delta file failed.
+    #[error("index history read failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The rebuild task aborted.
+    #[error("index history worker failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+/// A rebuildable moment: the checkpoint a state starts from and the
+/// newest batch replayed on top of it.  Every moment between two batches
+/// resolves to the same point, and so to the same body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HistoryPoint {
+    /// Checkpoint time (Unix ms).
+    pub anchor: u64,
+    /// Time of the newest batch replayed (Unix ms); `anchor` when none is.
+    pub replayed_to: u64,
+}
+
+/// The time of a recorded batch, read without its changes.
+#[derive(Deserialize)]
+struct BatchTime {
+    /// [`DeltaBatch::at_ms`].
+    at_ms: u64,
+}
+
+/// One journal batch as recorded in a delta file.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DeltaBatch {
+    /// When the daemon applied the batch (Unix ms).
+    pub at_ms: u64,
+    /// The batch, in apply order.
+    pub changes: Vec<RecordedChange>,
+}
+
+/// Serialisable mirror of [`FileChange`].
+#[derive(Debug, Serialize, Deserialize)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "mirrors the independent change flags of `FileChange`"
+)]
+pub(crate) struct RecordedChange {
+    /// Raw FRS.
+    frs: u64,
+    /// Raw parent FRS.
+    parent_frs: u64,
+    /// Latest filename.
+    filename: String,
+    /// `FileChange::created`.
+    created: bool,
+    /// `FileChange::deleted`.
+    deleted: bool,
+    /// `FileChange::renamed`.
+    renamed: bool,
+    /// `FileChange::size_changed`.
+    size_changed: bool,
+    /// `FileChange::metadata_changed`.
+    metadata_changed: bool,
+    /// `(size, allocated, created, modified, accessed, flags)` when the
+    /// change carried backfilled metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    meta: Option<(u64, u64, i64, i64, i64, u32)>,
+}
+
+impl From<&FileChange> for RecordedChange {
+    fn from(change: &FileChange) -> Self {
+        Self {
+            frs: change.frs.raw(),
+            parent_frs: change.parent_frs.raw(),
+            filename: change.filename.clone(),
+            created: change.created,
+            deleted: change.deleted,
+            renamed: change.renamed,
+            size_changed: change.size_changed,
+            metadata_changed: change.metadata_changed,
+            meta: change.meta.map(|meta| {
+                (
+                    meta.size,
+                    meta.allocated,
+                    meta.created,
+                    meta.modified,
+                    meta.accessed,
+                    meta.flags,
+                )
+            }),
+        }
+    }
+}
+
+impl From<RecordedChange> for FileChange {
+    fn from(recorded: RecordedChange) -> Self {
+        Self {
+            frs: recorded.frs.into(),
+            parent_frs: recorded.parent_frs.into(),
+            filename: recorded.filename,
+            created: recorded.created,
+            deleted: recorded.deleted,
+            renamed: recorded.renamed,
+            size_changed: recorded.size_changed,
+            metadata_changed: recorded.metadata_changed,
+            meta: recorded
+                .meta
+                .map(
+                    |(size, allocated, created, modified, accessed, flags)| RecordMeta {
+                        size,
+                        allocated,
+                        created,
+                        modified,
+                        accessed,
+                        flags,
+                    },
+                ),
+            // Replayed into a rebuilt index only, never the change log.
+            usn: Usn::ZERO,
+        }
+    }
+}
+
+/// The on-disk history for every volume.
+#[derive(Debug)]
+pub(crate) struct History {
+    /// Directory holding one sub-directory per volume.
+    root: VolumeRoot,
+    /// Minimum spacing between two checkpoints of one volume.
+    checkpoint_every: Duration,
+    /// Age past which a non-newest checkpoint is pruned.
+    retention: Duration,
+    /// Newest checkpoint time per volume, recovered lazily from disk.
+    /// Held for the whole of [`Self::record`], so a batch never lands in
+    /// a delta file that another writer is pruning.
+    newest: Mutex<HashMap<VolumeId, u64>>,
+}
+
+impl History {
+    /// Build the history described by `config`, or `None` when it is
+    /// disabled.  The directory defaults to `<cache dir>/history` and is
+    /// created on the first checkpoint.
+    #[must_use]
+    pub(crate) fn from_config(config: &HistoryConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            Self::new(
+                VolumeRoot::configured(config.dir.as_deref(), "history"),
+                config,
+            )
+        })
+    }
+
+    /// History rooted at `root` with `config`'s cadence and retention.
+    #[must_use]
+    pub(crate) fn new(root: VolumeRoot, config: &HistoryConfig) -> Self {
+        Self {
+            root,
+            checkpoint_every: Duration::from_hours(config.checkpoint_hours),
+            retention: Duration::from_hours(config.retention_days.saturating_mul(24)),
+            newest: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record one applied batch for `volume` at `at_ms`.  `old_body` is
+    /// the body the batch was applied to; it becomes a new checkpoint
+    /// when the volume has none or its newest is due for renewal.
+    ///
+    /// # Errors
+    ///
+    /// Propagates checkpoint-write, delta-append and pruning failures.
+    pub(crate) fn record(
+        &self,
+        volume: VolumeId,
+        at_ms: u64,
+        old_body: &DriveCompactIndex,
+        changes: &[FileChange],
+    ) -> std::io::Result<()> {
+        let dir = self.root.dir(volume);
+        let mut newest = self.newest.lock().unwrap_or_else(PoisonError::into_inner);
+        let known = match newest.get(&volume) {
+            Some(&known) => Some(known),
+            None => list_checkpoints(&dir)?.last().copied(),
+        };
+        let checkpoint_every = u64::try_from(self.checkpoint_every.as_millis()).unwrap_or(u64::MAX);
+        let anchor = match known {
+            Some(at) if at_ms.saturating_sub(at) < checkpoint_every => at,
+            Some(_) | None => {
+                uffs_core::compact_cache::write_compact_checkpoint(
+                    old_body,
+                    &checkpoint_path(&dir, at_ms),
+                )?;
+                self.prune(&dir, at_ms)?;
+                at_ms
+            }
+        };
+        newest.insert(volume, anchor);
+
+        let batch = DeltaBatch {
+            at_ms,
+            changes: changes.iter().map(RecordedChange::from).collect(),
+        };
+        let mut line = serde_json::to_string(&batch)?;
+        line.push('\n');
+        let appended = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(deltas_path(&dir, anchor))
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        drop(newest);
+        appended
+    }
+
+    /// Retained checkpoint times of `volume` (Unix ms), oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Propagates directory-listing failures.  A volume with no history
+    /// has none.
+    pub(crate) fn points(&self, volume: VolumeId) -> std::io::Result<Vec<u64>> {
+        list_checkpoints(&self.root.dir(volume))
+    }
+
+    /// The point `volume`'s state at `at_ms` is rebuilt from.  Reads only
+    /// the batch times, so it is cheap next to [`Self::rebuild`].
+    ///
+    /// # Errors
+    ///
+    /// [`HistoryError::NoPoint`] when nothing is retained that early,
+    /// otherwise listing and read failures.
+    pub(crate) fn point(&self, volume: VolumeId, at_ms: u64) -> Result<HistoryPoint, HistoryError> {
+        let dir = self.root.dir(volume);
+        let points = list_checkpoints(&dir)?;
+        let Some(&anchor) = points.iter().rev().find(|&&point| point <= at_ms) else {
+            return Err(HistoryError::NoPoint {
+                drive: volume,
+                at_ms,
+                oldest: points
+                    .first()
+                    .map_or_else(|| "none".to_owned(), |oldest| format!("{oldest} ms")),
+            });
+        };
+        let mut replayed_to = anchor;
+        for line in delta_lines(&dir, anchor)? {
+            let Ok(batch) = serde_json::from_str::<BatchTime>(&line?) else {
+                continue;
+            };
+            if batch.at_ms > at_ms {
+                break;
+            }
+            replayed_to = batch.at_ms;
+        }
+        Ok(HistoryPoint {
+            anchor,
+            replayed_to,
+        })
+    }
+
+    /// Load `point`'s checkpoint of `volume` and replay its batches up to
+    /// [`HistoryPoint::replayed_to`].
+    ///
+    /// # Errors
+    ///
+    /// Read / decrypt / parse failures.
+    pub(crate) fn rebuild(
+        &self,
+        volume: VolumeId,
+        point: HistoryPoint,
+    ) -> std::io::Result<DriveCompactIndex> {
+        let dir = self.root.dir(volume);
+        let mut body = uffs_core::compact_cache::read_compact_checkpoint(
+            &checkpoint_path(&dir, point.anchor),
+            volume,
+        )?;
+        for line in delta_lines(&dir, point.anchor)? {
+            let Ok(batch) = serde_json::from_str::<DeltaBatch>(&line?) else {
+                continue;
+            };
+            if batch.at_ms > point.replayed_to {
+                break;
+            }
+            let changes: Vec<FileChange> =
+                batch.changes.into_iter().map(FileChange::from).collect();
+            let _stats = uffs_core::compact_loader::apply_usn_patch(&mut body, &changes);
+        }
+        Ok(body)
+    }
+
+    /// Rebuild `volume`'s body as it stood at `at_ms`.
+    ///
+    /// # Errors
+    ///
+    /// [`HistoryError::NoPoint`] when nothing is retained that early,
+    /// otherwise read / decrypt / parse failures.
+    pub(crate) fn materialise(
+        &self,
+        volume: VolumeId,
+        at_ms: u64,
+    ) -> Result<DriveCompactIndex, HistoryError> {
+        let point = self.point(volume, at_ms)?;
+        Ok(self.rebuild(volume, point)?)
+    }
+
+    /// Delete checkpoints of `dir` (and their delta files) older than the
+    /// retention window, never the newest one.
+    fn prune(&self, dir: &Path, now_ms: u64) -> std::io::Result<()> {
+        let retention = u64::try_from(self.retention.as_millis()).unwrap_or(u64::MAX);
+        let mut points = list_checkpoints(dir)?;
+        let _newest = points.pop();
+        for point in points {
+            if now_ms.saturating_sub(point) <= retention {
+                break;
+            }
+            std::fs::remove_file(checkpoint_path(dir, point))?;
+            match std::fs::remove_file(deltas_path(dir, point)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+                Ok(()) | Err(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Path of the checkpoint taken at `at_ms`.
+fn checkpoint_path(dir: &Path, at_ms: u64) -> PathBuf {
+    numbered_path(dir, at_ms, CHECKPOINT_EXTENSION)
+}
+
+/// Path of the delta file following the checkpoint taken at `at_ms`.
+fn deltas_path(dir: &Path, at_ms: u64) -> PathBuf {
+    numbered_path(dir, at_ms, DELTAS_SUFFIX)
+}
+
+/// Lines of the delta file following the checkpoint taken at `anchor`;
+/// none when the checkpoint has no batches yet.
+fn delta_lines(
+    dir: &Path,
+    anchor: u64,
+) -> std::io::Result<impl Iterator<Item = std::io::Result<String>>> {
+    let file = match File::open(deltas_path(dir, anchor)) {
+        Ok(file) => Some(BufReader::new(file)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+    Ok(file.into_iter().flat_map(BufRead::lines))
+}
+
+/// Checkpoint times in `dir`, oldest first.  A missing directory has
+/// none.
+fn list_checkpoints(dir: &Path) -> std::io::Result<Vec<u64>> {
+    Ok(list_numbered(dir, CHECKPOINT_EXTENSION)?
+        .into_iter()
+        .map(|(at_ms, _)| at_ms)
+        .collect())
+}
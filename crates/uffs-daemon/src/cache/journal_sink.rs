@@ -492,7 +492,9 @@ async fn dispatch_msg(idx: &Arc<IndexManager>, cursor_store: &dyn CursorStore, m
             // head reset, so cloning + patching is wasted work — the
             // cleanest option is `load_drive_with_usn_refresh` which
             // re-reads the MFT and replays the new journal from
-            // its current head.
+            // its current head.  The new journal numbers its records
+            // from the start, so the change log's replay mark goes too.
+            idx.change_feed_journal_wrapped(letter).await;
             let _applied = idx.handle_journal_refresh(letter, "journal-wrapped").await;
         }
     }
@@ -32,8 +32,11 @@
 pub(crate) mod background_io;
 pub(crate) mod body_loader;
 pub(crate) mod cache_cleaner;
+pub(crate) mod change_log;
 pub(crate) mod cursor_store;
 pub(crate) mod guarded_load;
+pub(crate) mod hash_store;
+pub(crate) mod history;
 pub(crate) mod journal_loop;
 pub(crate) mod journal_sink;
 pub(crate) mod policy;
@@ -41,6 +44,8 @@ pub(crate) mod prefetch;
 pub(crate) mod pressure;
 pub(crate) mod registry;
 pub(crate) mod shard;
+pub(crate) mod snapshots;
+pub(crate) mod volume_store;
 pub(crate) mod working_set;
 
 // Phase 1 surface: only `ShardRegistry` and `ShardState` are referenced
@@ -174,17 +174,17 @@ impl ShardRegistry {
         }
     }
 
-    /// Insert a fresh `Warm` shard for `body.letter` and return the
+    /// Insert a fresh `Warm` shard for `body.volume()` and return the
     /// rebuilt registry.  The previous registry is left untouched.
     ///
-    /// The shard's identity is `body.letter` so callers don't have to
+    /// The shard's identity is `body.volume()` so callers don't have to
     /// thread the letter separately and can't accidentally store a
     /// shard whose letter disagrees with its body.
     #[must_use]
     pub(crate) fn add(&self, body: Arc<DriveCompactIndex>) -> Self {
-        let letter = body.letter;
+        let volume = body.volume();
         let mut shards = self.shards.clone();
-        shards.push(Arc::new(ShardEntry::new_warm(letter, body)));
+        shards.push(Arc::new(ShardEntry::new_warm(volume, body)));
         Self::from_shards(shards)
     }
 
@@ -192,7 +192,7 @@ impl ShardRegistry {
     /// matches `match_letter` (if any) with a fresh `Warm` entry, and
     /// return the rebuilt registry.
     ///
-    /// The new shard's identity is `body.letter` (canonical case from
+    /// The new shard's identity is `body.volume()` (canonical case from
     /// the index payload), preserving the pre-Phase-1 contract where
     /// `DriveIndex { drives: vec![Arc::new(new_drive)] }` always
     /// identified the new entry by `new_drive.letter`.  When no
@@ -204,16 +204,17 @@ impl ShardRegistry {
     /// `eq_ignore_ascii_case` to handle drive letters that flow
     /// through the daemon in either case.
     #[must_use]
-    pub(crate) fn replace(
+    pub(crate) fn replace<V: Into<uffs_mft::platform::VolumeId>>(
         &self,
-        match_letter: uffs_mft::platform::DriveLetter,
+        match_letter: V,
         body: Arc<DriveCompactIndex>,
     ) -> Self {
-        let new_letter = body.letter;
+        let match_volume = match_letter.into();
+        let new_letter = body.volume();
         let mut shards: Vec<Arc<ShardEntry>> = self
             .shards
             .iter()
-            .filter(|shard| shard.drive != match_letter)
+            .filter(|shard| shard.drive != match_volume)
             .cloned()
             .collect();
         shards.push(Arc::new(ShardEntry::new_warm(new_letter, body)));
@@ -230,11 +231,12 @@ impl ShardRegistry {
     /// [`crate::index::IndexManager::forget_drives`] calls `remove`
     /// after the eviction guard checks pass.
     #[must_use]
-    pub(crate) fn remove(&self, drive: uffs_mft::platform::DriveLetter) -> Self {
+    pub(crate) fn remove<V: Into<uffs_mft::platform::VolumeId>>(&self, drive: V) -> Self {
+        let volume = drive.into();
         let shards: Vec<Arc<ShardEntry>> = self
             .shards
             .iter()
-            .filter(|shard| shard.drive != drive)
+            .filter(|shard| shard.drive != volume)
             .cloned()
             .collect();
         Self::from_shards(shards)
@@ -282,21 +284,21 @@ impl ShardRegistry {
     /// carry `reason="pressure-cascade"` instead of the default
     /// `reason="demote"`.
     #[must_use]
-    pub(crate) fn demote_letter(
+    pub(crate) fn demote_letter<V: Into<uffs_mft::platform::VolumeId>>(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        letter: V,
         target: ShardState,
     ) -> Option<Self> {
         self.demote_letter_with_reason(letter, target, DemoteReason::IdleTtl)
     }
 
-    /// Demote the shard for `letter` to `target` (`Parked` or
+    /// Demote the shard for `drive` to `target` (`Parked` or
     /// `Cold`), dropping the body and emitting a single
     /// `shard.transition` `INFO` event with the supplied `reason`
     /// in its `reason` field.  Returns the rebuilt registry, or
     /// `None` when:
     ///
-    /// * `letter` is not registered;
+    /// * `drive` is not registered;
     /// * `target` is not a demote-legal tier (must be `Parked` or `Cold`);
     /// * the existing shard's state is not a legal "from" for the requested
     ///   demote (see [`is_legal_demote_target`]).
@@ -326,12 +328,13 @@ impl ShardRegistry {
     /// `last_query_at_ms` (previously cascade-only) is included for
     /// every demote so operator runbooks get a uniform schema.
     #[must_use]
-    pub(crate) fn demote_letter_with_reason(
+    pub(crate) fn demote_letter_with_reason<V: Into<uffs_mft::platform::VolumeId>>(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        drive: V,
         target: ShardState,
         reason: DemoteReason,
     ) -> Option<Self> {
+        let volume = drive.into();
         // Locate the matching shard by enumerating once: returns
         // `(pos, &Arc<ShardEntry>)` so we never index into
         // `self.shards` (clippy::indexing_slicing).
@@ -339,7 +342,7 @@ impl ShardRegistry {
             .shards
             .iter()
             .enumerate()
-            .find(|(_, shard)| shard.drive == letter)?;
+            .find(|(_, shard)| shard.drive == volume)?;
         let from_state = old_arc.state();
         if !is_legal_demote_target(from_state, target) {
             return None;
@@ -355,7 +358,6 @@ impl ShardRegistry {
         // second event of their own just to log this field.
         let last_query_at_ms = old_arc.stats.last_query_at_ms();
         let stats = Arc::clone(&old_arc.stats);
-        let drive = old_arc.drive;
         let new_entry = match target {
             ShardState::Parked => {
                 // Phase 4 Commit F — extract the bloom + trie from the
@@ -369,7 +371,7 @@ impl ShardRegistry {
                 let Some(body) = old_arc.body() else {
                     tracing::error!(
                         target: "shard.transition",
-                        letter = %letter,
+                        letter = %volume,
                         from = %from_state,
                         to = %target,
                         reason = reason.as_str(),
@@ -378,9 +380,9 @@ impl ShardRegistry {
                     return None;
                 };
                 let parked_body = Arc::new(body.to_parked_body());
-                ShardEntry::new_parked(drive, stats, parked_body)
+                ShardEntry::new_parked(volume, stats, parked_body)
             }
-            ShardState::Cold => ShardEntry::new_cold(drive, stats),
+            ShardState::Cold => ShardEntry::new_cold(volume, stats),
             // Filtered out by `is_legal_demote_target` above; this
             // arm is unreachable in practice, exists only so the
             // match is exhaustive without an `unreachable!`.
@@ -408,7 +410,7 @@ impl ShardRegistry {
             .collect();
         tracing::info!(
             target: "shard.transition",
-            letter = %letter,
+            letter = %volume,
             from = %from_state,
             to = %target,
             freed_mb,
@@ -418,12 +420,12 @@ impl ShardRegistry {
         Some(Self::from_shards(shards))
     }
 
-    /// Promote the shard for `letter` from `Parked` / `Cold` back
+    /// Promote the shard for `drive` from `Parked` / `Cold` back
     /// to `Warm`, attaching `body` and emitting a single
     /// `shard.transition` tracing event.  Returns the rebuilt
     /// registry, or `None` when:
     ///
-    /// * `letter` is not registered;
+    /// * `drive` is not registered;
     /// * the existing shard's state is not `Parked` / `Cold` (a request to
     ///   promote an already-warm shard is a caller bug).
     ///
@@ -435,24 +437,24 @@ impl ShardRegistry {
     /// [`crate::index::IndexManager::ensure_warm_for_dispatch`]
     /// (Phase 3 Commit C).
     #[must_use]
-    pub(crate) fn promote_letter(
+    pub(crate) fn promote_letter<V: Into<uffs_mft::platform::VolumeId>>(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        drive: V,
         body: Arc<DriveCompactIndex>,
     ) -> Option<Self> {
+        let volume = drive.into();
         let (pos, old_arc) = self
             .shards
             .iter()
             .enumerate()
-            .find(|(_, shard)| shard.drive == letter)?;
+            .find(|(_, shard)| shard.drive == volume)?;
         let from_state = old_arc.state();
         if !matches!(from_state, ShardState::Parked | ShardState::Cold) {
             return None;
         }
         let restored_mb = (body.heap_size_bytes().total / 1_048_576) as u64;
         let stats = Arc::clone(&old_arc.stats);
-        let drive = old_arc.drive;
-        let new_arc = Arc::new(ShardEntry::new_warm_with_stats(drive, body, stats));
+        let new_arc = Arc::new(ShardEntry::new_warm_with_stats(volume, body, stats));
         let shards: Vec<Arc<ShardEntry>> = self
             .shards
             .iter()
@@ -467,7 +469,7 @@ impl ShardRegistry {
             .collect();
         tracing::info!(
             target: "shard.transition",
-            letter = %letter,
+            letter = %volume,
             from = %from_state,
             to = %ShardState::Warm,
             restored_mb,
@@ -476,11 +478,11 @@ impl ShardRegistry {
         Some(Self::from_shards(shards))
     }
 
-    /// Promote the shard for `letter` to `Hot`, attaching `body`
+    /// Promote the shard for `drive` to `Hot`, attaching `body`
     /// and emitting a single `shard.transition` tracing event.
     /// Returns the rebuilt registry, or `None` when:
     ///
-    /// * `letter` is not registered;
+    /// * `drive` is not registered;
     /// * the existing shard's state is `Hot` (caller must extend the pin via
     ///   [`crate::cache::shard::ShardEntry::pin_until`] on the live
     ///   `Arc<ShardEntry>` instead of rebuilding);
@@ -502,16 +504,17 @@ impl ShardRegistry {
     /// Cold/Parked/Warm → Hot preserves query counters and
     /// `last_query_at_ms`.
     #[must_use]
-    pub(crate) fn promote_letter_to_hot(
+    pub(crate) fn promote_letter_to_hot<V: Into<uffs_mft::platform::VolumeId>>(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        drive: V,
         body: Arc<DriveCompactIndex>,
     ) -> Option<Self> {
+        let volume = drive.into();
         let (pos, old_arc) = self
             .shards
             .iter()
             .enumerate()
-            .find(|(_, shard)| shard.drive == letter)?;
+            .find(|(_, shard)| shard.drive == volume)?;
         let from_state = old_arc.state();
         if !matches!(
             from_state,
@@ -521,7 +524,6 @@ impl ShardRegistry {
         }
         let restored_mb = (body.heap_size_bytes().total / 1_048_576) as u64;
         let stats = Arc::clone(&old_arc.stats);
-        let drive = old_arc.drive;
         // Phase 9: bump the Cold → Hot promotion counter only when
         // the source tier was actually Cold.  Already-Warm preload
         // calls (where the body is in RAM and only the tier marker
@@ -535,7 +537,7 @@ impl ShardRegistry {
         if from_state == ShardState::Cold {
             stats.record_cold_to_hot_promote();
         }
-        let new_arc = Arc::new(ShardEntry::new_hot_with_stats(drive, body, stats));
+        let new_arc = Arc::new(ShardEntry::new_hot_with_stats(volume, body, stats));
         let shards: Vec<Arc<ShardEntry>> = self
             .shards
             .iter()
@@ -550,7 +552,7 @@ impl ShardRegistry {
             .collect();
         tracing::info!(
             target: "shard.transition",
-            letter = %letter,
+            letter = %volume,
             from = %from_state,
             to = %ShardState::Hot,
             restored_mb,
@@ -564,7 +566,7 @@ impl ShardRegistry {
     /// [`uffs_core::compact_loader::load_drive_with_usn_refresh`]).
     /// Returns the rebuilt registry on success, or `None` when:
     ///
-    /// * `letter` is not registered;
+    /// * `drive` is not registered;
     /// * the existing shard's state is `Parked` / `Cold` / `Unknown` /
     ///   `Evicting` — these tiers don't have an in-memory body to refresh.  A
     ///   `Parked` shard gets a USN-refreshed body via the normal
@@ -581,24 +583,24 @@ impl ShardRegistry {
     /// Wired into the production refresh path by
     /// [`crate::spawn_journal_loops_for_warm_shards`].
     #[must_use]
-    pub(crate) fn replace_warm_body(
+    pub(crate) fn replace_warm_body<V: Into<uffs_mft::platform::VolumeId>>(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        drive: V,
         body: Arc<DriveCompactIndex>,
     ) -> Option<Self> {
+        let volume = drive.into();
         let (pos, old_arc) = self
             .shards
             .iter()
             .enumerate()
-            .find(|(_, shard)| shard.drive == letter)?;
+            .find(|(_, shard)| shard.drive == volume)?;
         let from_state = old_arc.state();
         if !matches!(from_state, ShardState::Warm | ShardState::Hot) {
             return None;
         }
         let refreshed_mb = (body.heap_size_bytes().total / 1_048_576) as u64;
         let stats = Arc::clone(&old_arc.stats);
-        let drive = old_arc.drive;
-        let new_arc = Arc::new(ShardEntry::new_warm_with_stats(drive, body, stats));
+        let new_arc = Arc::new(ShardEntry::new_warm_with_stats(volume, body, stats));
         let shards: Vec<Arc<ShardEntry>> = self
             .shards
             .iter()
@@ -613,7 +615,7 @@ impl ShardRegistry {
             .collect();
         tracing::info!(
             target: "shard.transition",
-            letter = %letter,
+            letter = %volume,
             from = %from_state,
             to = %ShardState::Warm,
             refreshed_mb,
@@ -630,13 +632,14 @@ impl ShardRegistry {
 
     /// `true` iff any shard exists for `drive` (regardless of tier).
     #[must_use]
-    pub(crate) fn contains(&self, drive: uffs_mft::platform::DriveLetter) -> bool {
-        self.shards.iter().any(|shard| shard.drive == drive)
+    pub(crate) fn contains<V: Into<uffs_mft::platform::VolumeId>>(&self, drive: V) -> bool {
+        let volume = drive.into();
+        self.shards.iter().any(|shard| shard.drive == volume)
     }
 
-    /// Drive letters of every loaded shard in load order.
+    /// Volumes of every loaded shard in load order.
     #[must_use]
-    pub(crate) fn loaded_letters(&self) -> Vec<uffs_mft::platform::DriveLetter> {
+    pub(crate) fn loaded_letters(&self) -> Vec<uffs_mft::platform::VolumeId> {
         self.shards.iter().map(|shard| shard.drive).collect()
     }
 }
@@ -666,7 +669,7 @@ mod tests {
         assert_eq!(reg.active_index().drives.len(), 0);
         assert_eq!(
             reg.loaded_letters(),
-            Vec::<uffs_mft::platform::DriveLetter>::new()
+            Vec::<uffs_mft::platform::VolumeId>::new()
         );
         assert!(!reg.contains(uffs_mft::platform::DriveLetter::C));
     }
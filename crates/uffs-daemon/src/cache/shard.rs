@@ -198,9 +198,10 @@ impl StdError for IllegalTransition {}
 /// concurrent `mark_query_at` writes from in-flight searches still
 /// land on the canonical counters.
 pub(crate) struct ShardEntry {
-    /// Drive letter (`'C'`, `'D'`, …). Capital ASCII per existing
-    /// daemon convention.
-    pub(crate) drive: uffs_mft::platform::DriveLetter,
+    /// Volume identity: the drive letter (`'C'`, `'D'`, …), qualified
+    /// by a host label for shards loaded from another machine's
+    /// capture.
+    pub(crate) drive: uffs_mft::platform::VolumeId,
     /// Tier state. Read on every search via [`Self::state`]; mutated
     /// only by [`Self::try_transition`] (test-only) or by the
     /// registry's tier-transition rebuilds (production path).
@@ -252,12 +253,12 @@ impl ShardEntry {
     /// constructor.  Phase 3 adds [`Self::new_parked`] /
     /// [`Self::new_cold`] for tier-transition rebuilds.
     #[must_use]
-    pub(crate) fn new_warm(
-        drive: uffs_mft::platform::DriveLetter,
+    pub(crate) fn new_warm<V: Into<uffs_mft::platform::VolumeId>>(
+        drive: V,
         body: Arc<DriveCompactIndex>,
     ) -> Self {
         Self {
-            drive,
+            drive: drive.into(),
             state: AtomicU8::new(ShardState::Warm as u8),
             stats: Arc::new(DriveStats::new()),
             body: Some(body),
@@ -272,13 +273,13 @@ impl ShardEntry {
     /// is lifted into the new `Warm` `ShardEntry` so the per-drive
     /// query counters survive the round-trip through demote-and-back.
     #[must_use]
-    pub(crate) const fn new_warm_with_stats(
-        drive: uffs_mft::platform::DriveLetter,
+    pub(crate) fn new_warm_with_stats<V: Into<uffs_mft::platform::VolumeId>>(
+        drive: V,
         body: Arc<DriveCompactIndex>,
         stats: Arc<DriveStats>,
     ) -> Self {
         Self {
-            drive,
+            drive: drive.into(),
             state: AtomicU8::new(ShardState::Warm as u8),
             stats,
             body: Some(body),
@@ -301,13 +302,13 @@ impl ShardEntry {
     /// query counters and `last_query_at_ms` survive the round-trip
     /// through Cold/Parked → Warm → Hot.
     #[must_use]
-    pub(crate) const fn new_hot_with_stats(
-        drive: uffs_mft::platform::DriveLetter,
+    pub(crate) fn new_hot_with_stats<V: Into<uffs_mft::platform::VolumeId>>(
+        drive: V,
         body: Arc<DriveCompactIndex>,
         stats: Arc<DriveStats>,
     ) -> Self {
         Self {
-            drive,
+            drive: drive.into(),
             state: AtomicU8::new(ShardState::Hot as u8),
             stats,
             body: Some(body),
@@ -329,13 +330,13 @@ impl ShardEntry {
     /// [`crate::cache::ShardRegistry::demote_letter`] (Phase 3
     /// Commit D, extended in Phase 4 Commit F).
     #[must_use]
-    pub(crate) const fn new_parked(
-        drive: uffs_mft::platform::DriveLetter,
+    pub(crate) fn new_parked<V: Into<uffs_mft::platform::VolumeId>>(
+        drive: V,
         stats: Arc<DriveStats>,
         parked_body: Arc<ParkedBody>,
     ) -> Self {
         Self {
-            drive,
+            drive: drive.into(),
             state: AtomicU8::new(ShardState::Parked as u8),
             stats,
             body: None,
@@ -355,12 +356,12 @@ impl ShardEntry {
     /// Commit D, when a `Parked` shard's idle time exceeds
     /// `PARKED_TO_COLD_IDLE_SECS`).
     #[must_use]
-    pub(crate) const fn new_cold(
-        drive: uffs_mft::platform::DriveLetter,
+    pub(crate) fn new_cold<V: Into<uffs_mft::platform::VolumeId>>(
+        drive: V,
         stats: Arc<DriveStats>,
     ) -> Self {
         Self {
-            drive,
+            drive: drive.into(),
             state: AtomicU8::new(ShardState::Cold as u8),
             stats,
             body: None,
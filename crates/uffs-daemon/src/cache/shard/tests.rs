@@ -87,6 +87,8 @@ fn make_test_body(letter: uffs_mft::platform::DriveLetter) -> DriveCompactIndex
 
     DriveCompactIndex {
         letter,
+        host: None,
+        serial: None,
         records: ColumnStorage::from_vec(records),
         names: ColumnStorage::from_vec(names),
         trigram: Arc::new(trigram),
@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Scheduled baseline snapshots: one compact-index body per drive per
+//! day, kept on a daily + weekly retention ladder under one directory per
+//! volume (its [`VolumeId::file_stem`]: `C`, `srv01@C`).
+//!
+//! When `[snapshots]` is enabled the daemon's snapshot scheduler
+//! ([`crate::index::IndexManager::take_due_snapshots`]) asks
+//! [`SnapshotStore::is_due`] once a minute for every loaded drive. A drive
+//! is due once the most recent daily slot (`at`, local time) has passed
+//! and its newest snapshot predates that slot — so a daemon that was not
+//! running at 02:00 catches up on its next tick.
+//!
+//! A snapshot is named after its local calendar day (`2026-10-01`), which
+//! is what `--diff @2026-10-01` matches first; any other `@<time>` picks
+//! the newest snapshot at or before that moment
+//! ([`SnapshotStore::at_or_before`]).
+//!
+//! ## Layout
+//!
+//! ```text
+//! <root>/C/00000001760000000000.snapshot   encrypted compact body
+//! ```
+//!
+//! Files are named after the snapshot time (Unix ms) and use the compact
+//! cache's zstd + AES-256-GCM format
+//! ([`uffs_core::compact_cache::write_compact_checkpoint`]).
+//!
+//! ## Retention
+//!
+//! After each snapshot, a drive keeps the newest snapshot of each of the
+//! last `keep_daily` local days that have one, plus the newest of each of
+//! the last `keep_weekly` ISO weeks; everything else is deleted. The
+//! newest snapshot is always kept.
+
+use alloc::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike as _, NaiveDate, NaiveTime, TimeZone};
+use uffs_core::compact::DriveCompactIndex;
+use uffs_mft::platform::VolumeId;
+
+use super::history::HistoryError;
+use super::volume_store::{VolumeRoot, list_numbered, numbered_path};
+use crate::config::SnapshotsConfig;
+
+/// File extension of a snapshot body.
+const SNAPSHOT_EXTENSION: &str = "snapshot";
+
+/// Default slot (02:00) for a config whose `at` was never validated.
+const DEFAULT_MINUTE_OF_DAY: u32 = 120;
+
+/// The on-disk snapshot store for every drive.
+#[derive(Debug)]
+pub(crate) struct SnapshotStore {
+    /// Directory holding one sub-directory per volume.
+    root: VolumeRoot,
+    /// Daily slot, in minutes after local midnight.
+    minute_of_day: u32,
+    /// Days whose newest snapshot is kept.
+    keep_daily: usize,
+    /// ISO weeks whose newest snapshot is kept.
+    keep_weekly: usize,
+}
+
+impl SnapshotStore {
+    /// Build the store described by `config`, or `None` when snapshots
+    /// are disabled.  The directory defaults to `<cache dir>/snapshots`
+    /// and is created on the first snapshot.
+    #[must_use]
+    pub(crate) fn from_config(config: &SnapshotsConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            Self::new(
+                VolumeRoot::configured(config.dir.as_deref(), "snapshots"),
+                config,
+            )
+        })
+    }
+
+    /// Store rooted at `root` with `config`'s schedule and retention.
+    #[must_use]
+    pub(crate) fn new(root: VolumeRoot, config: &SnapshotsConfig) -> Self {
+        Self {
+            root,
+            minute_of_day: config.minute_of_day().unwrap_or(DEFAULT_MINUTE_OF_DAY),
+            keep_daily: usize::try_from(config.keep_daily).unwrap_or(usize::MAX),
+            keep_weekly: usize::try_from(config.keep_weekly).unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Retained snapshot times of `volume` (Unix ms), oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Propagates directory-listing failures.  A volume with no snapshots
+    /// has none.
+    pub(crate) fn list(&self, volume: VolumeId) -> std::io::Result<Vec<u64>> {
+        list_snapshots(&self.root.dir(volume))
+    }
+
+    /// On-disk size of `volume`'s snapshot taken at `at_ms`, `0` when it
+    /// cannot be read.
+    pub(crate) fn size(&self, volume: VolumeId, at_ms: u64) -> u64 {
+        std::fs::metadata(snapshot_path(&self.root.dir(volume), at_ms)).map_or(0, |meta| meta.len())
+    }
+
+    /// Whether `volume` needs a snapshot at `now_ms`: the latest daily
+    /// slot has passed and no snapshot was taken since.
+    ///
+    /// # Errors
+    ///
+    /// Propagates directory-listing failures.
+    pub(crate) fn is_due<Tz: TimeZone>(
+        &self,
+        volume: VolumeId,
+        now_ms: u64,
+        tz: &Tz,
+    ) -> std::io::Result<bool> {
+        let Some(slot) = last_slot_ms(now_ms, self.minute_of_day, tz) else {
+            return Ok(false);
+        };
+        Ok(self
+            .list(volume)?
+            .last()
+            .is_none_or(|&newest| newest < slot))
+    }
+
+    /// Write `body` as `volume`'s snapshot taken at `at_ms`, then apply
+    /// the retention ladder.
+    ///
+    /// # Errors
+    ///
+    /// Propagates snapshot-write and pruning failures.
+    pub(crate) fn take<Tz: TimeZone>(
+        &self,
+        volume: VolumeId,
+        at_ms: u64,
+        body: &DriveCompactIndex,
+        tz: &Tz,
+    ) -> std::io::Result<()> {
+        let dir = self.root.dir(volume);
+        uffs_core::compact_cache::write_compact_checkpoint(body, &snapshot_path(&dir, at_ms))?;
+        let points = list_snapshots(&dir)?;
+        let keep = retained(&points, self.keep_daily, self.keep_weekly, tz);
+        for point in points.into_iter().filter(|point| !keep.contains(point)) {
+            std::fs::remove_file(snapshot_path(&dir, point))?;
+        }
+        Ok(())
+    }
+
+    /// The newest of `volume`'s snapshots named `name` (its local day,
+    /// `YYYY-MM-DD`), if any.
+    ///
+    /// # Errors
+    ///
+    /// Propagates directory-listing failures.
+    pub(crate) fn named<Tz: TimeZone>(
+        &self,
+        volume: VolumeId,
+        name: &str,
+        tz: &Tz,
+    ) -> std::io::Result<Option<u64>> {
+        let Ok(day) = NaiveDate::parse_from_str(name.trim(), "%Y-%m-%d") else {
+            return Ok(None);
+        };
+        Ok(self
+            .list(volume)?
+            .into_iter()
+            .rev()
+            .find(|&point| local_day(point, tz) == Some(day)))
+    }
+
+    /// The newest of `volume`'s snapshots taken at or before `at_ms`.
+    ///
+    /// # Errors
+    ///
+    /// [`HistoryError::NoPoint`] when none is that old, otherwise listing
+    /// failures.
+    pub(crate) fn at_or_before<Tz: TimeZone>(
+        &self,
+        volume: VolumeId,
+        at_ms: u64,
+        tz: &Tz,
+    ) -> Result<u64, HistoryError> {
+        let points = self.list(volume)?;
+        points
+            .iter()
+            .rev()
+            .find(|&&point| point <= at_ms)
+            .copied()
+            .ok_or_else(|| HistoryError::NoPoint {
+                drive: volume,
+                at_ms,
+                oldest: points.first().map_or_else(
+                    || "none".to_owned(),
+                    |&oldest| format!("snapshot {}", snapshot_name(oldest, tz)),
+                ),
+            })
+    }
+
+    /// Load `volume`'s snapshot taken at `at_ms`.
+    ///
+    /// # Errors
+    ///
+    /// Read / decrypt / parse failures.
+    pub(crate) fn load(&self, volume: VolumeId, at_ms: u64) -> std::io::Result<DriveCompactIndex> {
+        uffs_core::compact_cache::read_compact_checkpoint(
+            &snapshot_path(&self.root.dir(volume), at_ms),
+            volume,
+        )
+    }
+}
+
+/// A snapshot's name: the local calendar day it was taken on.
+pub(crate) fn snapshot_name<Tz: TimeZone>(at_ms: u64, tz: &Tz) -> String {
+    local_day(at_ms, tz).map_or_else(|| at_ms.to_string(), |day| day.to_string())
+}
+
+/// The local calendar day of `at_ms`.
+fn local_day<Tz: TimeZone>(at_ms: u64, tz: &Tz) -> Option<NaiveDate> {
+    let utc = DateTime::from_timestamp_millis(i64::try_from(at_ms).ok()?)?;
+    Some(utc.with_timezone(tz).date_naive())
+}
+
+/// The most recent daily slot (`minute_of_day` local time) at or before
+/// `now_ms`, in Unix ms.  A slot skipped by a DST jump falls back to the
+/// previous day's.
+fn last_slot_ms<Tz: TimeZone>(now_ms: u64, minute_of_day: u32, tz: &Tz) -> Option<u64> {
+    let today = local_day(now_ms, tz)?;
+    let time = NaiveTime::from_hms_opt(minute_of_day / 60, minute_of_day % 60, 0)?;
+    [Some(today), today.pred_opt()]
+        .into_iter()
+        .flatten()
+        .filter_map(|day| tz.from_local_datetime(&day.and_time(time)).earliest())
+        .filter_map(|slot| u64::try_from(slot.timestamp_millis()).ok())
+        .find(|&slot| slot <= now_ms)
+}
+
+/// The snapshots the retention ladder keeps: the newest of each of the
+/// last `keep_daily` days and of each of the last `keep_weekly` ISO weeks
+/// (walking `points` newest first), plus the newest overall.
+fn retained<Tz: TimeZone>(
+    points: &[u64],
+    keep_daily: usize,
+    keep_weekly: usize,
+    tz: &Tz,
+) -> BTreeSet<u64> {
+    let mut keep: BTreeSet<u64> = points.last().copied().into_iter().collect();
+    let mut days = BTreeSet::new();
+    let mut weeks = BTreeSet::new();
+    for &point in points.iter().rev() {
+        let Some(day) = local_day(point, tz) else {
+            continue;
+        };
+        if days.len() < keep_daily && days.insert(day) {
+            keep.insert(point);
+        }
+        let week = day.iso_week();
+        if weeks.len() < keep_weekly && weeks.insert((week.year(), week.week())) {
+            keep.insert(point);
+        }
+    }
+    keep
+}
+
+/// Path of the snapshot taken at `at_ms`.
+fn snapshot_path(dir: &Path, at_ms: u64) -> PathBuf {
+    numbered_path(dir, at_ms, SNAPSHOT_EXTENSION)
+}
+
+/// Snapshot times in `dir`, oldest first.  A missing directory has none.
+fn list_snapshots(dir: &Path) -> std::io::Result<Vec<u64>> {
+    Ok(list_numbered(dir, SNAPSHOT_EXTENSION)?
+        .into_iter()
+        .map(|(at_ms, _)| at_ms)
+        .collect())
+}
+
+#[cfg(test)]
+#[path = "snapshots_tests.rs"]
+mod tests;
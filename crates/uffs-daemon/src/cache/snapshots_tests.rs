@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for the scheduled snapshot store: the daily slot, the
+//! daily + weekly retention ladder, and name / time resolution.
+
+use chrono::{FixedOffset, Utc};
+use uffs_mft::platform::DriveLetter;
+
+use super::*;
+
+/// 2026-10-01T00:00:00Z (a Thursday) in Unix ms.
+const OCT_1: u64 = 1_790_812_800_000;
+const HOUR: u64 = 3_600_000;
+const DAY: u64 = 24 * HOUR;
+
+fn config(keep_daily: u32, keep_weekly: u32) -> SnapshotsConfig {
+    SnapshotsConfig {
+        enabled: true,
+        keep_daily,
+        keep_weekly,
+        ..SnapshotsConfig::default()
+    }
+}
+
+fn touch(root: &Path, volume: VolumeId, at_ms: u64) {
+    let dir = root.join(volume.file_stem());
+    std::fs::create_dir_all(&dir).expect("drive dir");
+    std::fs::write(snapshot_path(&dir, at_ms), b"").expect("snapshot file");
+}
+
+#[test]
+fn slot_is_the_latest_local_at_time_not_after_now() {
+    let two_am = 120;
+    assert_eq!(
+        last_slot_ms(OCT_1 + 3 * HOUR, two_am, &Utc),
+        Some(OCT_1 + 2 * HOUR)
+    );
+    assert_eq!(
+        last_slot_ms(OCT_1 + HOUR, two_am, &Utc),
+        Some(OCT_1 - DAY + 2 * HOUR)
+    );
+    // 02:00 at UTC+02:00 is midnight UTC.
+    let plus_two = FixedOffset::east_opt(2 * 3600).expect("offset");
+    assert_eq!(last_slot_ms(OCT_1 + HOUR, two_am, &plus_two), Some(OCT_1));
+}
+
+#[test]
+fn due_once_per_slot_and_catches_up_after_downtime() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = SnapshotStore::new(VolumeRoot::new(tmp.path().to_path_buf()), &config(14, 8));
+    let volume = VolumeId::from(DriveLetter::C);
+    assert!(store.is_due(volume, OCT_1 + 3 * HOUR, &Utc).expect("due"));
+
+    touch(tmp.path(), volume, OCT_1 + 2 * HOUR + 5);
+    assert!(
+        !store
+            .is_due(volume, OCT_1 + 23 * HOUR, &Utc)
+            .expect("not due")
+    );
+    // The daemon was down at the next 02:00: due as soon as it is back.
+    assert!(
+        store
+            .is_due(volume, OCT_1 + DAY + 9 * HOUR, &Utc)
+            .expect("catch up")
+    );
+}
+
+#[test]
+fn retention_keeps_newest_per_day_and_per_week() {
+    // Two snapshots a day for 30 days.
+    let points: Vec<u64> = (0..30)
+        .flat_map(|day| [OCT_1 + day * DAY + 2 * HOUR, OCT_1 + day * DAY + 14 * HOUR])
+        .collect();
+    let keep = retained(&points, 3, 2, &Utc);
+
+    let newest_of_day = |day: u64| OCT_1 + day * DAY + 14 * HOUR;
+    // Last three days, newest of each.
+    for day in 27..30 {
+        assert!(keep.contains(&newest_of_day(day)), "day {day}");
+        assert!(
+            !keep.contains(&(OCT_1 + day * DAY + 2 * HOUR)),
+            "older twin of day {day}"
+        );
+    }
+    // Oct 30 (Fri) is the newest of ISO week 44, Oct 25 (Sun) of week 43.
+    assert!(keep.contains(&newest_of_day(24)), "previous week's newest");
+    assert_eq!(keep.len(), 4);
+    assert!(
+        retained(&points, 0, 0, &Utc).contains(&newest_of_day(29)),
+        "newest always kept"
+    );
+}
+
+#[test]
+fn names_and_times_resolve_to_snapshots() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = SnapshotStore::new(VolumeRoot::new(tmp.path().to_path_buf()), &config(14, 8));
+    let volume = VolumeId::hosted("srv01".parse().expect("host"), DriveLetter::D);
+    touch(tmp.path(), volume, OCT_1 + 2 * HOUR);
+    touch(tmp.path(), volume, OCT_1 + DAY + 2 * HOUR);
+    assert!(
+        store
+            .list(VolumeId::from(DriveLetter::D))
+            .expect("list")
+            .is_empty(),
+        "a local D: does not see srv01's snapshots"
+    );
+
+    assert_eq!(snapshot_name(OCT_1 + 2 * HOUR, &Utc), "2026-10-01");
+    assert_eq!(
+        store.named(volume, "2026-10-01", &Utc).expect("list"),
+        Some(OCT_1 + 2 * HOUR)
+    );
+    assert_eq!(store.named(volume, "2026-10-05", &Utc).expect("list"), None);
+    assert_eq!(store.named(volume, "yesterday", &Utc).expect("list"), None);
+
+    let found = store
+        .at_or_before(volume, OCT_1 + DAY + 3 * HOUR, &Utc)
+        .expect("a snapshot that old");
+    assert_eq!(found, OCT_1 + DAY + 2 * HOUR);
+    assert!(matches!(
+        store.at_or_before(volume, OCT_1, &Utc),
+        Err(HistoryError::NoPoint { oldest, .. }) if oldest == "snapshot 2026-10-01"
+    ));
+}
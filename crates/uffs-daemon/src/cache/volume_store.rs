@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Plumbing shared by the per-volume stores under the cache directory:
+//! the change feed ([`super::change_log`]), the index history
+//! ([`super::history`]), the baseline snapshots ([`super::snapshots`]) and
+//! the content-hash sidecars ([`super::hash_store`]).
+//!
+//! Each store keeps its files under a [`VolumeRoot`], one directory (or
+//! one sidecar) per volume named after [`VolumeId::file_stem`] — `C` for
+//! a local drive, `srv01@C` for a hosted capture — so two hosts' `C:`
+//! never share a file.  Inside a volume's directory, files are named
+//! after a zero-padded number (a time or a sequence number) and listed
+//! in that order by [`list_numbered`].
+
+use std::path::{Path, PathBuf};
+
+use uffs_mft::platform::VolumeId;
+
+/// Directory a per-volume store lives in.
+#[derive(Debug, Clone)]
+pub(crate) struct VolumeRoot(PathBuf);
+
+impl VolumeRoot {
+    /// `dir` when the config sets one, otherwise `<cache dir>/<default>`.
+    #[must_use]
+    pub(crate) fn configured(dir: Option<&Path>, default: &str) -> Self {
+        Self::new(dir.map_or_else(
+            || uffs_mft::cache::cache_dir().join(default),
+            Path::to_path_buf,
+        ))
+    }
+
+    /// Root at `path`.
+    #[must_use]
+    pub(crate) const fn new(path: PathBuf) -> Self {
+        Self(path)
+    }
+
+    /// The root directory itself.
+    #[must_use]
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Directory holding `volume`'s files.
+    #[must_use]
+    pub(crate) fn dir(&self, volume: VolumeId) -> PathBuf {
+        self.0.join(volume.file_stem())
+    }
+
+    /// `volume`'s single file with `extension`, directly under the root.
+    #[must_use]
+    pub(crate) fn file(&self, volume: VolumeId, extension: &str) -> PathBuf {
+        self.0.join(format!("{}.{extension}", volume.file_stem()))
+    }
+}
+
+/// Path of the file numbered `number` in `dir`: `<number>.<suffix>`.
+#[must_use]
+pub(crate) fn numbered_path(dir: &Path, number: u64, suffix: &str) -> PathBuf {
+    dir.join(format!("{number:020}.{suffix}"))
+}
+
+/// `(number, path)` of every `<number>.<extension>` file in `dir`, lowest
+/// first.  A missing directory has none.
+///
+/// # Errors
+///
+/// Directory-listing failures other than the directory not existing.
+pub(crate) fn list_numbered(dir: &Path, extension: &str) -> std::io::Result<Vec<(u64, PathBuf)>> {
+    let read_dir = match std::fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut files: Vec<(u64, PathBuf)> = read_dir
+        .filter_map(|dirent| {
+            let path = dirent.ok()?.path();
+            if path.extension()? != extension {
+                return None;
+            }
+            let number = path.file_stem()?.to_str()?.parse::<u64>().ok()?;
+            Some((number, path))
+        })
+        .collect();
+    files.sort_unstable_by_key(|&(number, _)| number);
+    Ok(files)
+}
+
+/// Run a store operation for `volume` on the blocking pool.  Best-effort:
+/// a failure, or a worker that panicked, is warn-logged as `failure` and
+/// comes back as `None`.
+pub(crate) async fn run_logged<T, F>(volume: VolumeId, failure: &'static str, op: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+{
+    let error = match tokio::task::spawn_blocking(op).await {
+        Ok(Ok(value)) => return Some(value),
+        Ok(Err(err)) => err.to_string(),
+        Err(join_err) => join_err.to_string(),
+    };
+    tracing::warn!(target: "cache.store", drive = %volume, error, "{failure}");
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use uffs_mft::platform::DriveLetter;
+
+    use super::*;
+
+    #[test]
+    fn volumes_sharing_a_letter_get_their_own_files() {
+        let root = VolumeRoot::new(PathBuf::from("store"));
+        let local = VolumeId::from(DriveLetter::C);
+        let hosted = VolumeId::hosted("srv01".parse().expect("host"), DriveLetter::C);
+        assert_eq!(root.dir(local), Path::new("store").join("C"));
+        assert_eq!(root.dir(hosted), Path::new("store").join("srv01@C"));
+        assert_eq!(
+            root.file(hosted, "hashes"),
+            Path::new("store").join("srv01@C.hashes")
+        );
+    }
+
+    #[test]
+    fn numbered_files_list_lowest_first_and_skip_strangers() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        for (number, suffix) in [(20, "snapshot"), (3, "snapshot"), (7, "deltas.ndjson")] {
+            std::fs::write(numbered_path(tmp.path(), number, suffix), b"").expect("write");
+        }
+        std::fs::write(tmp.path().join("notes.snapshot"), b"").expect("write");
+
+        let listed: Vec<u64> = list_numbered(tmp.path(), "snapshot")
+            .expect("list")
+            .into_iter()
+            .map(|(number, _)| number)
+            .collect();
+        assert_eq!(listed, [3, 20]);
+        assert!(
+            list_numbered(&tmp.path().join("missing"), "snapshot")
+                .expect("missing dir")
+                .is_empty()
+        );
+    }
+}
@@ -34,6 +34,38 @@
 //! [shards.per_drive]
 //! "C:" = { min_tier = "WARM", max_tier = "HOT" }
 //! "Z:" = { max_tier = "PARKED" }
+//!
+//! [change_log]
+//! enabled                      = true
+//! max_mb_per_drive             = 512
+//! max_age_days                 = 30
+//!
+//! [history]
+//! enabled                      = true
+//! checkpoint_hours             = 24
+//! retention_days               = 14
+//!
+//! [snapshots]
+//! enabled                      = true
+//! at                           = "02:00"
+//! keep_daily                   = 14
+//! keep_weekly                  = 8
+//!
+//! [hash_cache]
+//! enabled                      = true
+//!
+//! [fields]
+//! avgsize                      = "treesize / descendants"
+//! slack                        = "allocated - size"
+//!
+//! [[alerts]]
+//! name                         = "projects-over-500g"
+//! metric                       = "treesize"
+//! path                         = "D:\\Projects"
+//! above                        = 500_000_000_000
+//! rearm                        = 450_000_000_000
+//! cooldown_secs                = 3600
+//! sink                         = "C:/ProgramData/uffs/alerts.jsonl"
 //! ```
 //!
 //! ## Layering
@@ -70,7 +102,9 @@ use alloc::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use uffs_core::search::computed::{ComputedError, ComputedFields};
 
+pub(crate) use self::alerts::{AlertMetric, AlertRule};
 use crate::cache::policy::{
     hot_to_warm_idle_secs, parked_to_cold_idle_secs, usn_refresh_interval_secs,
     warm_to_parked_idle_secs,
@@ -93,6 +127,23 @@ pub(crate) struct Config {
     pub tiers: TiersConfig,
     /// Cache and runtime root paths plus per-drive overrides.
     pub shards: ShardsConfig,
+    /// How paths of host-qualified volumes are rendered.
+    pub display: DisplayConfig,
+    /// Durable per-drive change feed (`tail_changes`).
+    pub change_log: ChangeLogConfig,
+    /// Point-in-time checkpoints behind `as_of` searches.
+    pub history: HistoryConfig,
+    /// Scheduled baseline snapshots behind `@<date>` diffs.
+    pub snapshots: SnapshotsConfig,
+    /// Persistent content-hash cache behind duplicate verification.
+    pub hash_cache: HashCacheConfig,
+    /// Saved computed fields, `name = "expression"`, usable by name in
+    /// every search (see [`uffs_core::search::computed`]).  A request's
+    /// own `computed_fields` override these.
+    pub fields: BTreeMap<String, String>,
+    /// Threshold alert rules, evaluated after every journal apply and
+    /// drive refresh (see [`crate::index::alerts`]).
+    pub alerts: Vec<AlertRule>,
 }
 
 // ── [memory] ─────────────────────────────────────────────────────
@@ -315,6 +366,167 @@ pub(crate) struct PerDriveConfig {
     pub max_tier: Option<TierLevel>,
 }
 
+// ── [display] ────────────────────────────────────────────────────
+
+/// `[display]` — rendering of volumes loaded from other machines'
+/// captures (`<data-dir>/<host>/drive_<x>/`).
+///
+/// ```toml
+/// [display]
+/// path_template = "/evidence/{host}/{letter}"
+/// ```
+///
+/// `None` keeps [`uffs_mft::platform::PathTemplate::DEFAULT`]
+/// (`srv01\C:\…`).  Local volumes always render as `C:\…`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct DisplayConfig {
+    /// Root template with `{host}` and `{letter}` placeholders.
+    pub path_template: Option<String>,
+}
+
+// ── [change_log] ─────────────────────────────────────────────────
+
+/// `[change_log]` — the durable change feed behind `tail_changes`
+/// ([`crate::cache::change_log`]).
+///
+/// Off by default: when enabled, every journal delta the daemon applies
+/// is appended, paths resolved, to `<dir>/<letter>/*.ndjson`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ChangeLogConfig {
+    /// Record applied changes at all.
+    pub enabled: bool,
+    /// Log directory.  `None` ⇒ `changes/` under the cache directory.
+    pub dir: Option<PathBuf>,
+    /// Size at which a segment file rolls over.
+    pub segment_mb: u64,
+    /// Per-drive size budget; the oldest segments go first.
+    pub max_mb_per_drive: u64,
+    /// Segments whose newest entry is older than this are pruned.
+    pub max_age_days: u64,
+}
+
+impl Default for ChangeLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            segment_mb: 16,
+            max_mb_per_drive: 512,
+            max_age_days: 30,
+        }
+    }
+}
+
+// ── [history] ────────────────────────────────────────────────────
+
+/// `[history]` — retained index states behind `as_of` searches and
+/// `@<time>` diff baselines ([`crate::cache::history`]).
+///
+/// Off by default: when enabled, the daemon writes a compact-index
+/// checkpoint of each drive every `checkpoint_hours` and records the
+/// journal batches applied between checkpoints, so any moment in the
+/// last `retention_days` can be rebuilt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct HistoryConfig {
+    /// Keep history at all.
+    pub enabled: bool,
+    /// History directory.  `None` ⇒ `history/` under the cache directory.
+    pub dir: Option<PathBuf>,
+    /// Minimum spacing between two checkpoints of one drive.
+    pub checkpoint_hours: u64,
+    /// Checkpoints older than this are pruned (the newest is always
+    /// kept).
+    pub retention_days: u64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            checkpoint_hours: 24,
+            retention_days: 14,
+        }
+    }
+}
+
+// ── [snapshots] ──────────────────────────────────────────────────
+
+/// `[snapshots]` — scheduled baseline snapshots for `--diff @<date>`
+/// ([`crate::cache::snapshots`]).
+///
+/// Off by default: when enabled, the daemon writes one compact-index
+/// snapshot of every loaded drive per day at the local time `at`, then
+/// keeps the newest snapshot of each of the last `keep_daily` days and
+/// of each of the last `keep_weekly` ISO weeks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SnapshotsConfig {
+    /// Take snapshots at all.
+    pub enabled: bool,
+    /// Snapshot directory.  `None` ⇒ `snapshots/` under the cache
+    /// directory.
+    pub dir: Option<PathBuf>,
+    /// Local time of day (`HH:MM`, 24-hour) the daily snapshot is due.
+    pub at: String,
+    /// Days whose newest snapshot is kept.
+    pub keep_daily: u32,
+    /// ISO weeks whose newest snapshot is kept.
+    pub keep_weekly: u32,
+}
+
+impl Default for SnapshotsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            at: "02:00".to_owned(),
+            keep_daily: 14,
+            keep_weekly: 8,
+        }
+    }
+}
+
+impl SnapshotsConfig {
+    /// `at` as minutes after local midnight, or `None` when it is not a
+    /// valid `HH:MM`.
+    pub(crate) fn minute_of_day(&self) -> Option<u32> {
+        let (hours_text, minutes_text) = self.at.trim().split_once(':')?;
+        let hours: u32 = hours_text.parse().ok()?;
+        let minutes: u32 = minutes_text.parse().ok()?;
+        (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+    }
+}
+
+// ── [hash_cache] ─────────────────────────────────────────────────
+
+/// `[hash_cache]` — per-drive sidecar of the content digests computed by
+/// duplicate verification ([`crate::cache::hash_store`]).
+///
+/// On by default: a digest is keyed by `(file_ref, size, modified)`, so an
+/// unchanged file is never hashed twice across scans or restarts, and the
+/// journal apply path drops the digests of changed files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct HashCacheConfig {
+    /// Keep digests at all.
+    pub enabled: bool,
+    /// Sidecar directory.  `None` ⇒ `hashes/` under the cache directory.
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for HashCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+        }
+    }
+}
+
 // ── TierLevel ────────────────────────────────────────────────────
 
 /// User-facing tier-level enum that round-trips through TOML.
@@ -365,9 +577,31 @@ impl Config {
     ///
     /// Returns the structured config or a [`ConfigError::Parse`]
     /// describing the parse failure (line / column included via
-    /// `toml::de::Error`'s `Display`).
+    /// `toml::de::Error`'s `Display`).  An `[[alerts]]` rule that parses
+    /// but cannot be evaluated is a [`ConfigError::Alert`]; an enabled
+    /// `[snapshots]` section with a malformed `at` is a
+    /// [`ConfigError::SnapshotTime`].
     pub(crate) fn from_toml(body: &str) -> Result<Self, ConfigError> {
-        toml::from_str(body).map_err(ConfigError::Parse)
+        let config: Self = toml::from_str(body).map_err(ConfigError::Parse)?;
+        if let Some((rule, reason)) = config
+            .alerts
+            .iter()
+            .find_map(|rule| rule.problem().map(|reason| (rule.name.clone(), reason)))
+        {
+            return Err(ConfigError::Alert { rule, reason });
+        }
+        if config.snapshots.enabled && config.snapshots.minute_of_day().is_none() {
+            return Err(ConfigError::SnapshotTime(config.snapshots.at));
+        }
+        ComputedFields::parse(config.field_definitions()).map_err(ConfigError::Field)?;
+        Ok(config)
+    }
+
+    /// `[fields]` as `name=expression` definitions, in name order.
+    pub(crate) fn field_definitions(&self) -> impl Iterator<Item = String> + '_ {
+        self.fields
+            .iter()
+            .map(|(name, expr)| format!("{name}={expr}"))
     }
 
     /// Read and parse `daemon.toml` from disk.
@@ -463,314 +697,25 @@ pub(crate) enum ConfigError {
     /// `std::io::Error` from a non-`NotFound` filesystem failure.
     #[error("daemon.toml read: {0}")]
     Io(#[from] std::io::Error),
+    /// An `[[alerts]]` rule that parses but cannot be evaluated.
+    #[error("daemon.toml alert {rule:?}: {reason}")]
+    Alert {
+        /// The rule's `name`.
+        rule: String,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// `[snapshots] at` is not a 24-hour `HH:MM`.
+    #[error("daemon.toml [snapshots] at = {0:?}: expected a local 24-hour HH:MM")]
+    SnapshotTime(String),
+    /// A `[fields]` entry that does not compile.
+    #[error("daemon.toml [fields]: {0}")]
+    Field(ComputedError),
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // ── Defaults ──────────────────────────────────────────────────
-
-    /// Plan task 6.8: missing `daemon.toml` ⇒ defaults match Phase
-    /// 3 static behavior.  Pin the exact tier / USN / cap values so
-    /// a future tweak to `cache::policy` constants cannot silently
-    /// drift the defaultable config away from the controller's
-    /// statics.
-    #[test]
-    fn defaults_match_phase3_static_behavior() {
-        // Without the env-var override, the getter must collapse
-        // to the documented static default — pin the constant
-        // mapping at the policy layer so a future tweak to the
-        // const cannot silently drift the default config without
-        // also failing this test.  Done via a const-pin so the
-        // value is fixed at build time and the assertion holds
-        // regardless of `OnceLock` cache state.  Hoisted above the
-        // first `let` so `clippy::items_after_statements` doesn't
-        // fire — the const block is build-time, so its position is
-        // purely stylistic relative to the runtime asserts below.
-        const _: () = {
-            assert!(crate::cache::policy::HOT_TO_WARM_IDLE_SECS == 600);
-            assert!(crate::cache::policy::WARM_TO_PARKED_IDLE_SECS == 1_800);
-            assert!(crate::cache::policy::PARKED_TO_COLD_IDLE_SECS == 86_400);
-            assert!(crate::cache::policy::USN_REFRESH_INTERVAL_SECS == 300);
-        };
-
-        let cfg = Config::default();
-
-        // Tier defaults track the env-var-aware Phase-3 getters
-        // (`hot_to_warm_idle_secs()` etc.), which themselves
-        // resolve to the `HOT_TO_WARM_IDLE_SECS` constants when
-        // the corresponding `UFFS_*_IDLE_SECS` env vars are
-        // unset.  Asserting against the getters preserves the
-        // env-var-override contract end-to-end: a developer who
-        // sets `UFFS_HOT_TO_WARM_IDLE_SECS=10` for a benchmark
-        // gets `cfg.tiers.hot_ttl_base_secs == 10` and the test
-        // still passes.
-        assert_eq!(cfg.tiers.hot_ttl_base_secs, hot_to_warm_idle_secs());
-        assert_eq!(cfg.tiers.warm_ttl_base_secs, warm_to_parked_idle_secs());
-        assert_eq!(cfg.tiers.parked_ttl_secs, parked_to_cold_idle_secs());
-        assert_eq!(
-            cfg.shards.usn_refresh_interval_secs,
-            usn_refresh_interval_secs()
-        );
-
-        // No per-drive overrides by default — ladder is uniform.
-        assert!(cfg.shards.per_drive.is_empty());
-
-        // Memory budget tracks plan §11 (2 GiB; respect OS;
-        // working-set trim ON; large-pages OFF; unencrypted
-        // sidecar OFF).
-        assert_eq!(cfg.memory.max_total_resident_mb, 2048);
-        assert!(cfg.memory.respect_os_low_memory);
-        assert!(cfg.memory.enable_working_set_trim);
-        assert!(!cfg.memory.enable_large_pages);
-        assert!(!cfg.memory.unencrypted_sidecar);
-
-        // Auto-promotion defaults: rate-driven hot upgrade ON at
-        // 3 q/min sustained.
-        assert!(cfg.tiers.heavy_query_auto_hot);
-        assert_eq!(cfg.tiers.sustained_rate_auto_hot_qpm, 3);
-    }
+#[path = "config_alerts.rs"]
+mod alerts;
 
-    /// Plan task 6.8 continued: an empty `daemon.toml` body parses
-    /// to the same value as `Config::default()`.  Distinct
-    /// from the missing-file case (which is exercised by
-    /// `load_from_path_missing_file_returns_defaults`) — this pins
-    /// the `#[serde(default)]` plumbing on every nested struct.
-    #[test]
-    fn empty_toml_body_parses_to_defaults() {
-        let cfg = Config::from_toml("").expect("empty body must parse");
-        assert_eq!(cfg, Config::default());
-    }
-
-    /// Plan task 6.8 final clause: a missing `daemon.toml` file
-    /// ⇒ defaults, no error.  Uses `tempfile::tempdir` to get a
-    /// guaranteed-empty directory; the missing path inside it is
-    /// the missing-file case the loader has to tolerate.
-    #[test]
-    fn load_from_path_missing_file_returns_defaults() {
-        let dir = tempfile::tempdir().expect("tempdir create");
-        let missing = dir.path().join("daemon.toml");
-        let cfg = Config::load_from_path(&missing)
-            .expect("missing daemon.toml must yield defaults, not error");
-        assert_eq!(cfg, Config::default());
-    }
-
-    /// Loader returns the parsed body when the file exists.  Uses
-    /// a single-section overlay (just `[tiers]`) to also pin the
-    /// "missing section ⇒ default" behavior at the file boundary.
-    #[test]
-    fn load_from_path_with_partial_file_overlays_defaults() {
-        let dir = tempfile::tempdir().expect("tempdir create");
-        let path = dir.path().join("daemon.toml");
-        std::fs::write(
-            &path,
-            "[tiers]\n\
-             hot_ttl_base_secs = 999\n",
-        )
-        .expect("write fixture");
-
-        let cfg = Config::load_from_path(&path).expect("parse fixture");
-        // Overridden field landed.
-        assert_eq!(cfg.tiers.hot_ttl_base_secs, 999);
-        // Sibling fields fell through to defaults — same env-var-aware
-        // contract as `defaults_match_phase3_static_behavior`.
-        assert_eq!(cfg.tiers.warm_ttl_base_secs, warm_to_parked_idle_secs());
-        // Sibling sections fell through to defaults.
-        assert_eq!(cfg.memory, MemoryConfig::default());
-        assert_eq!(cfg.shards, ShardsConfig::default());
-    }
-
-    // ── Round-trip ────────────────────────────────────────────────
-
-    /// Plan task 6.5: TOML round-trip on the full default config
-    /// (serialize → deserialize → equality).  Belt-and-braces
-    /// regression: a future schema change that adds a non-default
-    /// field without a `#[serde(default)]` will fail this test
-    /// before it can ship.
-    #[test]
-    fn default_config_round_trips_through_toml() {
-        let original = Config::default();
-        let body = original.to_toml().expect("serialize default");
-        let parsed = Config::from_toml(&body).expect("parse round-trip");
-        assert_eq!(parsed, original);
-    }
-
-    /// Round-trip with a non-trivial `[shards.per_drive]` map.
-    /// Pins the `BTreeMap` deterministic ordering — a `HashMap`
-    /// substitution would make this test flaky.
-    #[test]
-    fn full_config_with_per_drive_round_trips() {
-        let mut original = Config::default();
-        original
-            .shards
-            .per_drive
-            .insert("C:".to_owned(), PerDriveConfig {
-                min_tier: Some(TierLevel::Warm),
-                max_tier: Some(TierLevel::Hot),
-            });
-        original
-            .shards
-            .per_drive
-            .insert("Z:".to_owned(), PerDriveConfig {
-                min_tier: None,
-                max_tier: Some(TierLevel::Parked),
-            });
-        original.memory.enable_large_pages = true;
-        original.tiers.sustained_rate_auto_hot_qpm = 5;
-
-        let body = original.to_toml().expect("serialize full");
-        let parsed = Config::from_toml(&body).expect("parse full round-trip");
-        assert_eq!(parsed, original);
-    }
-
-    // ── Per-drive overrides (task 6.6 partial — parse side) ───────
-
-    /// Plan task 6.6 (partial — parser side; the demote-ladder
-    /// enforcement is Commit C): a `[shards.per_drive."C:"]`
-    /// section with `min_tier = "WARM"` parses into
-    /// `Some(TierLevel::Warm)` so the controller can clamp the
-    /// ladder at Commit C wiring time.
-    #[test]
-    fn per_drive_min_tier_warm_parses() {
-        let body = r#"
-[shards.per_drive."C:"]
-min_tier = "WARM"
-"#;
-        let cfg = Config::from_toml(body).expect("parse per-drive override");
-        let entry = cfg
-            .shards
-            .per_drive
-            .get("C:")
-            .expect("C: override must be present");
-        assert_eq!(entry.min_tier, Some(TierLevel::Warm));
-        assert_eq!(entry.max_tier, None);
-    }
-
-    /// Inline-table form (the plan §11 canonical example shape) of
-    /// the same override.  Both shapes must produce identical
-    /// parsed output — TOML's inline-table sugar is purely
-    /// surface-level.
-    #[test]
-    fn per_drive_inline_table_form_matches_section_form() {
-        let inline = r#"
-[shards.per_drive]
-"C:" = { min_tier = "WARM", max_tier = "HOT" }
-"Z:" = { max_tier = "PARKED" }
-"#;
-        let cfg = Config::from_toml(inline).expect("parse inline form");
-        let c_entry = cfg.shards.per_drive.get("C:").expect("C: present");
-        assert_eq!(c_entry.min_tier, Some(TierLevel::Warm));
-        assert_eq!(c_entry.max_tier, Some(TierLevel::Hot));
-        let z_entry = cfg.shards.per_drive.get("Z:").expect("Z: present");
-        assert_eq!(z_entry.min_tier, None);
-        assert_eq!(z_entry.max_tier, Some(TierLevel::Parked));
-    }
-
-    // ── TierLevel wire format ─────────────────────────────────────
-
-    /// Pin the wire format: serialize emits `UPPERCASE`, matching
-    /// the plan §11 example and the `shard.transition` tracing
-    /// vocabulary.  Uses [`TierLevelWrapper`] because TOML requires
-    /// a table at the document root — a bare `TierLevel` is not a
-    /// valid TOML document on its own.
-    #[test]
-    fn tier_level_serializes_uppercase() {
-        for (variant, expected) in [
-            (TierLevel::Hot, "value = \"HOT\"\n"),
-            (TierLevel::Warm, "value = \"WARM\"\n"),
-            (TierLevel::Parked, "value = \"PARKED\"\n"),
-        ] {
-            let body =
-                toml::to_string(&TierLevelWrapper { value: variant }).expect("serialize wrapper");
-            assert_eq!(body, expected, "unexpected wire form for {variant:?}");
-        }
-    }
-
-    /// Pin the deserialize side: `"HOT"` / `"WARM"` / `"PARKED"`
-    /// parse cleanly.  Lowercase / mixed case is **not** accepted
-    /// — opinionated to keep the wire format unambiguous in logs
-    /// and CLI output.
-    #[test]
-    fn tier_level_deserializes_uppercase_only() {
-        for (raw, expected) in [
-            ("\"HOT\"", TierLevel::Hot),
-            ("\"WARM\"", TierLevel::Warm),
-            ("\"PARKED\"", TierLevel::Parked),
-        ] {
-            let parsed: TierLevel = toml::from_str(&format!("value = {raw}")).map_or_else(
-                |_| panic!("parse {raw}"),
-                |wrapper: TierLevelWrapper| wrapper.value,
-            );
-            assert_eq!(parsed, expected);
-        }
-
-        // Lowercase rejected — pins the rename_all = "UPPERCASE"
-        // contract.  A future relaxation to case-insensitive
-        // would have to update this test deliberately.
-        let lower: Result<TierLevelWrapper, _> = toml::from_str("value = \"warm\"");
-        assert!(
-            lower.is_err(),
-            "lowercase tier level must be rejected to keep wire format unambiguous",
-        );
-    }
-
-    /// Helper for the round-trip `TierLevel` pins above.
-    /// `toml::{from_str, to_string}` both require a containing
-    /// table at the document root because `TierLevel` itself
-    /// (a bare enum value) isn't a valid TOML document.
-    #[derive(Serialize, Deserialize)]
-    struct TierLevelWrapper {
-        /// Wrapped value — single-field shim so a bare
-        /// `TierLevel` can deserialize from `value = "WARM"` in
-        /// a TOML root context.
-        value: TierLevel,
-    }
-
-    /// `TierLevel::to_state` lifts each variant to the matching
-    /// `ShardState`.  Pin so a future addition (e.g. a hypothetical
-    /// `Frozen` tier) has to update this test deliberately.
-    #[test]
-    fn tier_level_to_state_pin() {
-        assert_eq!(TierLevel::Hot.to_state(), ShardState::Hot);
-        assert_eq!(TierLevel::Warm.to_state(), ShardState::Warm);
-        assert_eq!(TierLevel::Parked.to_state(), ShardState::Parked);
-    }
-
-    // ── Strictness ────────────────────────────────────────────────
-
-    /// `#[serde(deny_unknown_fields)]` makes typos fail the parse
-    /// rather than silently no-op.  Exercise on a plausible typo
-    /// (`hot_tt1_base_secs` instead of `hot_ttl_base_secs`) so a
-    /// future relaxation to permissive parsing has to update this
-    /// test.
-    #[test]
-    fn unknown_field_in_tiers_section_rejected() {
-        let body = "
-[tiers]
-hot_tt1_base_secs = 999
-";
-        let err = Config::from_toml(body).expect_err("typo'd field must produce a parse error");
-        let msg = format!("{err}");
-        assert!(
-            msg.contains("hot_tt1_base_secs") || msg.contains("unknown field"),
-            "error should mention the unknown field: got {msg:?}",
-        );
-    }
-
-    /// Same strictness pin at the top-level section boundary.
-    #[test]
-    fn unknown_top_level_section_rejected() {
-        let body = "
-[bogus_section]
-foo = 42
-";
-        let err = Config::from_toml(body)
-            .expect_err("unknown top-level section must produce a parse error");
-        let msg = format!("{err}");
-        assert!(
-            msg.contains("bogus_section") || msg.contains("unknown field"),
-            "error should mention the unknown section: got {msg:?}",
-        );
-    }
-}
+#[cfg(test)]
+#[path = "config_tests.rs"]
+mod tests;
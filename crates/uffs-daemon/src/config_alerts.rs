@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `[[alerts]]` — threshold rules on folder and drive metrics, evaluated by
+//! [`crate::index::alerts`].  Split out of `config.rs` so the parser file
+//! stays under the 800 LOC policy ceiling.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Default [`AlertRule::cooldown_secs`]: one firing per rule per hour.
+const DEFAULT_ALERT_COOLDOWN_SECS: u64 = 3600;
+
+/// Default [`AlertRule::command_timeout_secs`].
+const DEFAULT_ALERT_COMMAND_TIMEOUT_SECS: u64 = 60;
+
+/// One `[[alerts]]` rule — a threshold on a folder or drive metric.
+///
+/// ```toml
+/// [[alerts]]
+/// name    = "c-low-space"
+/// metric  = "free_percent"
+/// path    = "C:"
+/// below   = 10
+/// rearm   = 15
+/// command = ["powershell", "-File", "C:/scripts/notify.ps1"]
+/// ```
+///
+/// Exactly one of `above` / `below` is set.  After a firing the rule is
+/// disarmed until the metric crosses back over `rearm` (hysteresis), and
+/// never fires twice within `cooldown_secs`.  Checked at load time by
+/// [`super::Config::from_toml`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct AlertRule {
+    /// Rule name, carried on every firing.
+    pub name: String,
+    /// What to measure.
+    pub metric: AlertMetric,
+    /// Folder for `treesize` / `growth_per_hour` (`D:\Projects`), drive
+    /// for `free_percent` (`C:`).
+    pub path: String,
+    /// Fire when the metric rises above this value.
+    pub above: Option<u64>,
+    /// Fire when the metric falls below this value.
+    pub below: Option<u64>,
+    /// Re-arm level after a firing.  `None` ⇒ the threshold itself
+    /// (no hysteresis band).
+    pub rearm: Option<u64>,
+    /// Minimum seconds between two firings of this rule.
+    pub cooldown_secs: u64,
+    /// Program and arguments to run on every firing; the alert JSON is
+    /// passed in the `UFFS_ALERT` environment variable.  Empty ⇒ none.
+    pub command: Vec<String>,
+    /// Seconds `command` may run before it is killed.
+    pub command_timeout_secs: u64,
+    /// JSONL file every firing is appended to.
+    pub sink: Option<PathBuf>,
+}
+
+impl Default for AlertRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            metric: AlertMetric::default(),
+            path: String::new(),
+            above: None,
+            below: None,
+            rearm: None,
+            cooldown_secs: DEFAULT_ALERT_COOLDOWN_SECS,
+            command: Vec::new(),
+            command_timeout_secs: DEFAULT_ALERT_COMMAND_TIMEOUT_SECS,
+            sink: None,
+        }
+    }
+}
+
+impl AlertRule {
+    /// `(threshold, rising)` — `rising` is `true` for an `above` rule.
+    /// `None` when the rule sets neither or both bounds (rejected at load).
+    #[must_use]
+    pub(crate) const fn trigger(&self) -> Option<(u64, bool)> {
+        match (self.above, self.below) {
+            (Some(above), None) => Some((above, true)),
+            (None, Some(below)) => Some((below, false)),
+            (Some(_), Some(_)) | (None, None) => None,
+        }
+    }
+
+    /// Volume the rule's `path` lives on, from its `X:` / `host\X:`
+    /// prefix.
+    #[must_use]
+    pub(crate) fn volume(&self) -> Option<uffs_mft::platform::VolumeId> {
+        let (qualified, _) = self.path.split_once(':')?;
+        uffs_mft::platform::VolumeId::parse(qualified).ok()
+    }
+
+    /// Reason the rule cannot be evaluated, if any.
+    pub(super) fn problem(&self) -> Option<&'static str> {
+        let Some((threshold, rising)) = self.trigger() else {
+            return Some("set exactly one of `above` / `below`");
+        };
+        if self.name.is_empty() {
+            return Some("`name` is required");
+        }
+        if self.volume().is_none() {
+            return Some("`path` must start with a volume (`C:` or `srv01\\C:`)");
+        }
+        let rearm = self.rearm.unwrap_or(threshold);
+        if (rising && rearm > threshold) || (!rising && rearm < threshold) {
+            return Some("`rearm` must sit on the safe side of the threshold");
+        }
+        None
+    }
+}
+
+/// Metric an [`AlertRule`] watches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AlertMetric {
+    /// Bytes under the folder (`treesize`).
+    #[default]
+    Treesize,
+    /// Free space of the drive, in whole percent of its capacity.
+    FreePercent,
+    /// Folder `treesize` growth, in bytes per hour.
+    GrowthPerHour,
+}
@@ -0,0 +1,477 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for the `daemon.toml` parser.
+
+use super::*;
+
+// ── Defaults ──────────────────────────────────────────────────
+
+/// Plan task 6.8: missing `daemon.toml` ⇒ defaults match Phase
+/// 3 static behavior.  Pin the exact tier / USN / cap values so
+/// a future tweak to `cache::policy` constants cannot silently
+/// drift the defaultable config away from the controller's
+/// statics.
+#[test]
+fn defaults_match_phase3_static_behavior() {
+    // Without the env-var override, the getter must collapse
+    // to the documented static default — pin the constant
+    // mapping at the policy layer so a future tweak to the
+    // const cannot silently drift the default config without
+    // also failing this test.  Done via a const-pin so the
+    // value is fixed at build time and the assertion holds
+    // regardless of `OnceLock` cache state.  Hoisted above the
+    // first `let` so `clippy::items_after_statements` doesn't
+    // fire — the const block is build-time, so its position is
+    // purely stylistic relative to the runtime asserts below.
+    const _: () = {
+        assert!(crate::cache::policy::HOT_TO_WARM_IDLE_SECS == 600);
+        assert!(crate::cache::policy::WARM_TO_PARKED_IDLE_SECS == 1_800);
+        assert!(crate::cache::policy::PARKED_TO_COLD_IDLE_SECS == 86_400);
+        assert!(crate::cache::policy::USN_REFRESH_INTERVAL_SECS == 300);
+    };
+
+    let cfg = Config::default();
+
+    // Tier defaults track the env-var-aware Phase-3 getters
+    // (`hot_to_warm_idle_secs()` etc.), which themselves
+    // resolve to the `HOT_TO_WARM_IDLE_SECS` constants when
+    // the corresponding `UFFS_*_IDLE_SECS` env vars are
+    // unset.  Asserting against the getters preserves the
+    // env-var-override contract end-to-end: a developer who
+    // sets `UFFS_HOT_TO_WARM_IDLE_SECS=10` for a benchmark
+    // gets `cfg.tiers.hot_ttl_base_secs == 10` and the test
+    // still passes.
+    assert_eq!(cfg.tiers.hot_ttl_base_secs, hot_to_warm_idle_secs());
+    assert_eq!(cfg.tiers.warm_ttl_base_secs, warm_to_parked_idle_secs());
+    assert_eq!(cfg.tiers.parked_ttl_secs, parked_to_cold_idle_secs());
+    assert_eq!(
+        cfg.shards.usn_refresh_interval_secs,
+        usn_refresh_interval_secs()
+    );
+
+    // No per-drive overrides by default — ladder is uniform.
+    assert!(cfg.shards.per_drive.is_empty());
+
+    // Memory budget tracks plan §11 (2 GiB; respect OS;
+    // working-set trim ON; large-pages OFF; unencrypted
+    // sidecar OFF).
+    assert_eq!(cfg.memory.max_total_resident_mb, 2048);
+    assert!(cfg.memory.respect_os_low_memory);
+    assert!(cfg.memory.enable_working_set_trim);
+    assert!(!cfg.memory.enable_large_pages);
+    assert!(!cfg.memory.unencrypted_sidecar);
+
+    // Auto-promotion defaults: rate-driven hot upgrade ON at
+    // 3 q/min sustained.
+    assert!(cfg.tiers.heavy_query_auto_hot);
+    assert_eq!(cfg.tiers.sustained_rate_auto_hot_qpm, 3);
+}
+
+/// Plan task 6.8 continued: an empty `daemon.toml` body parses
+/// to the same value as `Config::default()`.  Distinct
+/// from the missing-file case (which is exercised by
+/// `load_from_path_missing_file_returns_defaults`) — this pins
+/// the `#[serde(default)]` plumbing on every nested struct.
+#[test]
+fn empty_toml_body_parses_to_defaults() {
+    let cfg = Config::from_toml("").expect("empty body must parse");
+    assert_eq!(cfg, Config::default());
+}
+
+/// Plan task 6.8 final clause: a missing `daemon.toml` file
+/// ⇒ defaults, no error.  Uses `tempfile::tempdir` to get a
+/// guaranteed-empty directory; the missing path inside it is
+/// the missing-file case the loader has to tolerate.
+#[test]
+fn load_from_path_missing_file_returns_defaults() {
+    let dir = tempfile::tempdir().expect("tempdir create");
+    let missing = dir.path().join("daemon.toml");
+    let cfg = Config::load_from_path(&missing)
+        .expect("missing daemon.toml must yield defaults, not error");
+    assert_eq!(cfg, Config::default());
+}
+
+/// Loader returns the parsed body when the file exists.  Uses
+/// a single-section overlay (just `[tiers]`) to also pin the
+/// "missing section ⇒ default" behavior at the file boundary.
+#[test]
+fn load_from_path_with_partial_file_overlays_defaults() {
+    let dir = tempfile::tempdir().expect("tempdir create");
+    let path = dir.path().join("daemon.toml");
+    std::fs::write(
+        &path,
+        "[tiers]\n\
+         hot_ttl_base_secs = 999\n",
+    )
+    .expect("write fixture");
+
+    let cfg = Config::load_from_path(&path).expect("parse fixture");
+    // Overridden field landed.
+    assert_eq!(cfg.tiers.hot_ttl_base_secs, 999);
+    // Sibling fields fell through to defaults — same env-var-aware
+    // contract as `defaults_match_phase3_static_behavior`.
+    assert_eq!(cfg.tiers.warm_ttl_base_secs, warm_to_parked_idle_secs());
+    // Sibling sections fell through to defaults.
+    assert_eq!(cfg.memory, MemoryConfig::default());
+    assert_eq!(cfg.shards, ShardsConfig::default());
+}
+
+// ── Round-trip ────────────────────────────────────────────────
+
+/// Plan task 6.5: TOML round-trip on the full default config
+/// (serialize → deserialize → equality).  Belt-and-braces
+/// regression: a future schema change that adds a non-default
+/// field without a `#[serde(default)]` will fail this test
+/// before it can ship.
+#[test]
+fn default_config_round_trips_through_toml() {
+    let original = Config::default();
+    let body = original.to_toml().expect("serialize default");
+    let parsed = Config::from_toml(&body).expect("parse round-trip");
+    assert_eq!(parsed, original);
+}
+
+/// Round-trip with a non-trivial `[shards.per_drive]` map.
+/// Pins the `BTreeMap` deterministic ordering — a `HashMap`
+/// substitution would make this test flaky.
+#[test]
+fn full_config_with_per_drive_round_trips() {
+    let mut original = Config::default();
+    original
+        .shards
+        .per_drive
+        .insert("C:".to_owned(), PerDriveConfig {
+            min_tier: Some(TierLevel::Warm),
+            max_tier: Some(TierLevel::Hot),
+        });
+    original
+        .shards
+        .per_drive
+        .insert("Z:".to_owned(), PerDriveConfig {
+            min_tier: None,
+            max_tier: Some(TierLevel::Parked),
+        });
+    original.memory.enable_large_pages = true;
+    original.tiers.sustained_rate_auto_hot_qpm = 5;
+
+    let body = original.to_toml().expect("serialize full");
+    let parsed = Config::from_toml(&body).expect("parse full round-trip");
+    assert_eq!(parsed, original);
+}
+
+// ── Per-drive overrides (task 6.6 partial — parse side) ───────
+
+/// Plan task 6.6 (partial — parser side; the demote-ladder
+/// enforcement is Commit C): a `[shards.per_drive."C:"]`
+/// section with `min_tier = "WARM"` parses into
+/// `Some(TierLevel::Warm)` so the controller can clamp the
+/// ladder at Commit C wiring time.
+#[test]
+fn per_drive_min_tier_warm_parses() {
+    let body = r#"
+[shards.per_drive."C:"]
+min_tier = "WARM"
+"#;
+    let cfg = Config::from_toml(body).expect("parse per-drive override");
+    let entry = cfg
+        .shards
+        .per_drive
+        .get("C:")
+        .expect("C: override must be present");
+    assert_eq!(entry.min_tier, Some(TierLevel::Warm));
+    assert_eq!(entry.max_tier, None);
+}
+
+/// Inline-table form (the plan §11 canonical example shape) of
+/// the same override.  Both shapes must produce identical
+/// parsed output — TOML's inline-table sugar is purely
+/// surface-level.
+#[test]
+fn per_drive_inline_table_form_matches_section_form() {
+    let inline = r#"
+[shards.per_drive]
+"C:" = { min_tier = "WARM", max_tier = "HOT" }
+"Z:" = { max_tier = "PARKED" }
+"#;
+    let cfg = Config::from_toml(inline).expect("parse inline form");
+    let c_entry = cfg.shards.per_drive.get("C:").expect("C: present");
+    assert_eq!(c_entry.min_tier, Some(TierLevel::Warm));
+    assert_eq!(c_entry.max_tier, Some(TierLevel::Hot));
+    let z_entry = cfg.shards.per_drive.get("Z:").expect("Z: present");
+    assert_eq!(z_entry.min_tier, None);
+    assert_eq!(z_entry.max_tier, Some(TierLevel::Parked));
+}
+
+// ── TierLevel wire format ─────────────────────────────────────
+
+/// Pin the wire format: serialize emits `UPPERCASE`, matching
+/// the plan §11 example and the `shard.transition` tracing
+/// vocabulary.  Uses [`TierLevelWrapper`] because TOML requires
+/// a table at the document root — a bare `TierLevel` is not a
+/// valid TOML document on its own.
+#[test]
+fn tier_level_serializes_uppercase() {
+    for (variant, expected) in [
+        (TierLevel::Hot, "value = \"HOT\"\n"),
+        (TierLevel::Warm, "value = \"WARM\"\n"),
+        (TierLevel::Parked, "value = \"PARKED\"\n"),
+    ] {
+        let body =
+            toml::to_string(&TierLevelWrapper { value: variant }).expect("serialize wrapper");
+        assert_eq!(body, expected, "unexpected wire form for {variant:?}");
+    }
+}
+
+/// Pin the deserialize side: `"HOT"` / `"WARM"` / `"PARKED"`
+/// parse cleanly.  Lowercase / mixed case is **not** accepted
+/// — opinionated to keep the wire format unambiguous in logs
+/// and CLI output.
+#[test]
+fn tier_level_deserializes_uppercase_only() {
+    for (raw, expected) in [
+        ("\"HOT\"", TierLevel::Hot),
+        ("\"WARM\"", TierLevel::Warm),
+        ("\"PARKED\"", TierLevel::Parked),
+    ] {
+        let parsed: TierLevel = toml::from_str(&format!("value = {raw}")).map_or_else(
+            |_| panic!("parse {raw}"),
+            |wrapper: TierLevelWrapper| wrapper.value,
+        );
+        assert_eq!(parsed, expected);
+    }
+
+    // Lowercase rejected — pins the rename_all = "UPPERCASE"
+    // contract.  A future relaxation to case-insensitive
+    // would have to update this test deliberately.
+    let lower: Result<TierLevelWrapper, _> = toml::from_str("value = \"warm\"");
+    assert!(
+        lower.is_err(),
+        "lowercase tier level must be rejected to keep wire format unambiguous",
+    );
+}
+
+/// Helper for the round-trip `TierLevel` pins above.
+/// `toml::{from_str, to_string}` both require a containing
+/// table at the document root because `TierLevel` itself
+/// (a bare enum value) isn't a valid TOML document.
+#[derive(Serialize, Deserialize)]
+struct TierLevelWrapper {
+    /// Wrapped value — single-field shim so a bare
+    /// `TierLevel` can deserialize from `value = "WARM"` in
+    /// a TOML root context.
+    value: TierLevel,
+}
+
+/// `TierLevel::to_state` lifts each variant to the matching
+/// `ShardState`.  Pin so a future addition (e.g. a hypothetical
+/// `Frozen` tier) has to update this test deliberately.
+#[test]
+fn tier_level_to_state_pin() {
+    assert_eq!(TierLevel::Hot.to_state(), ShardState::Hot);
+    assert_eq!(TierLevel::Warm.to_state(), ShardState::Warm);
+    assert_eq!(TierLevel::Parked.to_state(), ShardState::Parked);
+}
+
+// ── Strictness ────────────────────────────────────────────────
+
+/// `#[serde(deny_unknown_fields)]` makes typos fail the parse
+/// rather than silently no-op.  Exercise on a plausible typo
+/// (`hot_tt1_base_secs` instead of `hot_ttl_base_secs`) so a
+/// future relaxation to permissive parsing has to update this
+/// test.
+#[test]
+fn unknown_field_in_tiers_section_rejected() {
+    let body = "
+[tiers]
+hot_tt1_base_secs = 999
+";
+    let err = Config::from_toml(body).expect_err("typo'd field must produce a parse error");
+    let msg = format!("{err}");
+    assert!(
+        msg.contains("hot_tt1_base_secs") || msg.contains("unknown field"),
+        "error should mention the unknown field: got {msg:?}",
+    );
+}
+
+/// `[display] path_template` parses and survives a round trip.
+#[test]
+fn display_path_template_round_trips() {
+    let body = r#"
+[display]
+path_template = "/evidence/{host}/{letter}"
+"#;
+    let cfg = Config::from_toml(body).expect("display section must parse");
+    assert_eq!(
+        cfg.display.path_template.as_deref(),
+        Some("/evidence/{host}/{letter}")
+    );
+    let round =
+        Config::from_toml(&cfg.to_toml().expect("serialize")).expect("round trip must parse");
+    assert_eq!(round, cfg);
+}
+
+/// Same strictness pin at the top-level section boundary.
+#[test]
+fn unknown_top_level_section_rejected() {
+    let body = "
+[bogus_section]
+foo = 42
+";
+    let err =
+        Config::from_toml(body).expect_err("unknown top-level section must produce a parse error");
+    let msg = format!("{err}");
+    assert!(
+        msg.contains("bogus_section") || msg.contains("unknown field"),
+        "error should mention the unknown section: got {msg:?}",
+    );
+}
+
+// ── [[alerts]] ───────────────────────────────────────────────
+
+/// `[[alerts]]` rules parse with their defaults filled in and survive
+/// a round trip.
+#[test]
+fn alert_rules_parse_and_round_trip() {
+    let body = r#"
+[[alerts]]
+name  = "projects-over-500g"
+path  = "D:\\Projects"
+above = 500_000_000_000
+rearm = 450_000_000_000
+sink  = "/var/log/uffs-alerts.jsonl"
+
+[[alerts]]
+name    = "c-low-space"
+metric  = "free_percent"
+path    = "C:"
+below   = 10
+command = ["notify", "--urgent"]
+"#;
+    let cfg = Config::from_toml(body).expect("alerts must parse");
+    let [treesize, free] = cfg.alerts.as_slice() else {
+        panic!("expected two rules, got {:?}", cfg.alerts);
+    };
+    assert_eq!(treesize.metric, AlertMetric::Treesize);
+    assert_eq!(treesize.trigger(), Some((500_000_000_000, true)));
+    assert_eq!(
+        treesize.volume(),
+        Some(uffs_mft::platform::DriveLetter::D.into())
+    );
+    assert_eq!(treesize.cooldown_secs, 3600);
+    assert_eq!(free.metric, AlertMetric::FreePercent);
+    assert_eq!(free.trigger(), Some((10, false)));
+    assert_eq!(free.command, ["notify", "--urgent"]);
+
+    let round =
+        Config::from_toml(&cfg.to_toml().expect("serialize")).expect("round trip must parse");
+    assert_eq!(round, cfg);
+}
+
+/// Rules that parse but cannot be evaluated are load errors naming the
+/// rule.
+#[test]
+fn unevaluable_alert_rules_rejected() {
+    for (body, reason) in [
+        (
+            r#"name = "a"
+path = "C:""#,
+            "above",
+        ),
+        (
+            r#"name = "a"
+path = "C:"
+above = 1
+below = 2"#,
+            "above",
+        ),
+        (
+            r#"name = "a"
+path = "Projects"
+above = 1"#,
+            "volume",
+        ),
+        (
+            r#"name = "a"
+path = "C:"
+above = 10
+rearm = 20"#,
+            "rearm",
+        ),
+        (
+            r#"path = "C:"
+below = 10"#,
+            "name",
+        ),
+    ] {
+        let err =
+            Config::from_toml(&format!("[[alerts]]\n{body}\n")).expect_err("rule must be rejected");
+        assert!(
+            matches!(&err, ConfigError::Alert { reason: got, .. } if got.contains(reason)),
+            "{body:?}: expected a {reason:?} complaint, got {err}",
+        );
+    }
+}
+
+// ── [snapshots] ──────────────────────────────────────────────
+
+/// `at` parses as local minutes after midnight; an enabled section with a
+/// malformed `at` is a load error, a disabled one is left alone.
+#[test]
+fn snapshot_time_of_day_is_validated_when_enabled() {
+    let cfg = Config::from_toml("[snapshots]\nenabled = true\nat = \"23:45\"\n")
+        .expect("valid snapshots section");
+    assert_eq!(cfg.snapshots.minute_of_day(), Some(23 * 60 + 45));
+    assert_eq!(cfg.snapshots.keep_daily, 14);
+
+    for at in ["24:00", "2am", "02:60", ""] {
+        let err = Config::from_toml(&format!("[snapshots]\nenabled = true\nat = {at:?}\n"))
+            .expect_err("malformed `at` must be rejected");
+        assert!(
+            matches!(&err, ConfigError::SnapshotTime(got) if got == at),
+            "{at:?}: {err}"
+        );
+    }
+    Config::from_toml("[snapshots]\nat = \"2am\"\n").expect("disabled section is not validated");
+}
+
+// ── [hash_cache] ─────────────────────────────────────────────
+
+/// The content-hash cache is on by default, can be switched off, and
+/// rejects unknown keys like every other section.
+#[test]
+fn hash_cache_defaults_on_and_parses_overrides() {
+    let defaults = Config::from_toml("").expect("empty config");
+    assert!(defaults.hash_cache.enabled);
+    assert!(defaults.hash_cache.dir.is_none());
+
+    let custom = Config::from_toml("[hash_cache]\nenabled = false\ndir = \"/tmp/uffs-hashes\"\n")
+        .expect("valid hash_cache section");
+    assert!(!custom.hash_cache.enabled);
+    assert_eq!(
+        custom.hash_cache.dir.as_deref(),
+        Some(Path::new("/tmp/uffs-hashes"))
+    );
+
+    Config::from_toml("[hash_cache]\nalgo = \"md5\"\n").expect_err("unknown key must be rejected");
+}
+
+// ── [fields] ─────────────────────────────────────────────────
+
+/// Saved computed fields are compiled at load time, so a typo stops the
+/// daemon from starting instead of failing every search.
+#[test]
+fn computed_fields_are_validated() {
+    let cfg = Config::from_toml(
+        "[fields]\navgsize = \"treesize / descendants\"\nslack = \"allocated - size\"\n",
+    )
+    .expect("valid fields section");
+    assert_eq!(cfg.field_definitions().collect::<Vec<_>>(), [
+        "avgsize=treesize / descendants",
+        "slack=allocated - size"
+    ]);
+
+    let err = Config::from_toml("[fields]\nbroken = \"size +\"\n").expect_err("bad expression");
+    assert!(matches!(err, ConfigError::Field(_)), "{err}");
+    Config::from_toml("[fields]\nsize = \"allocated\"\n").expect_err("shadowing a built-in");
+}
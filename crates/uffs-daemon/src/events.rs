@@ -4,9 +4,12 @@
 //! Daemon event broadcasting — push notifications to connected clients.
 //!
 //! The daemon emits `DaemonEvent`s at lifecycle milestones (drive loaded,
-//! ready, refresh, shutdown) and periodic stats heartbeats. Events are
-//! serialized as JSON-RPC 2.0 notifications (no `id` field) and pushed to
-//! all connected clients via `tokio::sync::broadcast`.
+//! ready, refresh, shutdown), periodic stats heartbeats, threshold alerts,
+//! and standing-query matches. Events are serialized as JSON-RPC 2.0
+//! notifications (no `id` field) and pushed to all connected clients via
+//! `tokio::sync::broadcast` — except query matches, which each connection's
+//! notification loop filters down to the subscriptions it owns (see
+//! [`DaemonEvent::is_for`]).
 //!
 //! Clients that don't read fast enough simply miss events (broadcast
 //! channel lag) — this is fire-and-forget, never blocks the daemon.
@@ -58,13 +61,13 @@ pub(crate) enum DaemonEvent {
     },
     /// A drive refresh has started.
     RefreshStarted {
-        /// Drives being refreshed.
-        drives: Vec<uffs_mft::platform::DriveLetter>,
+        /// Volumes being refreshed.
+        drives: Vec<uffs_mft::platform::VolumeId>,
     },
     /// A single drive finished refreshing.
     DriveRefreshed {
-        /// Drive letter.
-        drive: uffs_mft::platform::DriveLetter,
+        /// The refreshed volume.
+        drive: uffs_mft::platform::VolumeId,
         /// Updated record count.
         records: usize,
         /// MFT parse time in milliseconds.
@@ -100,6 +103,45 @@ pub(crate) enum DaemonEvent {
         /// Reason for shutdown.
         reason: String,
     },
+    /// A journal-applied change matched a standing query
+    /// (`subscribe_query`).  Delivered only to the owning connection.
+    QueryMatch {
+        /// IPC connection that owns the subscription (routing only, not
+        /// serialised).
+        #[serde(skip)]
+        connection: u64,
+        /// Subscription the row matched.
+        subscription_id: u64,
+        /// What happened to the record.
+        change: uffs_client::protocol::response::QueryChangeKind,
+        /// The matching row, as the `search` method returns it.
+        row: uffs_client::protocol::response::SearchRow,
+    },
+    /// A `daemon.toml` `[[alerts]]` rule crossed its threshold.
+    AlertFired {
+        /// The rule's `name`.
+        rule: String,
+        /// Metric the rule watches.
+        metric: crate::config::AlertMetric,
+        /// Folder or drive the rule watches.
+        path: String,
+        /// Metric value that tripped the rule.
+        value: u64,
+        /// The rule's `above` / `below` threshold.
+        threshold: u64,
+        /// When the rule fired, in Unix milliseconds.
+        fired_at_ms: u64,
+    },
+}
+
+impl DaemonEvent {
+    /// Whether connection `connection` should receive this event: every
+    /// event is broadcast except a [`Self::QueryMatch`], which goes only
+    /// to the connection that owns the subscription.
+    #[must_use]
+    pub(crate) const fn is_for(&self, connection: u64) -> bool {
+        !matches!(self, Self::QueryMatch { connection: owner, .. } if *owner != connection)
+    }
 }
 
 /// Create a new event broadcast channel.
@@ -155,6 +197,8 @@ pub(crate) fn event_to_json_line(event: &DaemonEvent) -> Option<String> {
         DaemonEvent::StatsHeartbeat { .. } => "daemon.stats",
         DaemonEvent::ConnectionChanged { .. } => "daemon.connection_changed",
         DaemonEvent::ShuttingDown { .. } => "daemon.shutting_down",
+        DaemonEvent::QueryMatch { .. } => uffs_client::protocol::response::QUERY_MATCH_METHOD,
+        DaemonEvent::AlertFired { .. } => "daemon.alert_fired",
     };
     let params = serde_json::to_value(event).ok()?;
     let notification = serde_json::json!({
@@ -282,6 +326,36 @@ mod tests {
         assert_eq!(parsed["params"]["reason"], "idle timeout (300s, tier 0)");
     }
 
+    #[test]
+    fn query_match_routes_to_owner_and_hides_connection() {
+        let row: uffs_client::protocol::response::SearchRow =
+            serde_json::from_value(serde_json::json!({
+                "drive": "C", "path": "C:\\Users\\a\\Downloads\\x.ps1", "name": "x.ps1",
+                "size": 10, "is_directory": false, "modified": 0, "created": 0,
+                "accessed": 0, "flags": 32, "allocated": 0, "descendants": 0,
+                "treesize": 0, "file_reference": 42,
+            }))
+            .unwrap();
+        let event = DaemonEvent::QueryMatch {
+            connection: 7,
+            subscription_id: 3,
+            change: uffs_client::protocol::response::QueryChangeKind::Created,
+            row,
+        };
+        assert!(event.is_for(7));
+        assert!(!event.is_for(8));
+        assert!(DaemonEvent::ConnectionChanged { active: 1 }.is_for(8));
+
+        let line = event_to_json_line(&event).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(parsed["method"], "daemon.query_match");
+        assert!(parsed["params"].get("connection").is_none());
+        let notification: uffs_client::protocol::response::QueryMatch =
+            serde_json::from_value(parsed["params"].clone()).unwrap();
+        assert_eq!(notification.subscription_id, 3);
+        assert_eq!(notification.row.name, "x.ps1");
+    }
+
     #[test]
     fn event_to_json_line_all_event_types_serialize() {
         // Verify every variant produces valid JSON — no panics, no None.
@@ -306,12 +380,12 @@ mod tests {
             },
             DaemonEvent::RefreshStarted {
                 drives: vec![
-                    uffs_mft::platform::DriveLetter::C,
-                    uffs_mft::platform::DriveLetter::D,
+                    uffs_mft::platform::DriveLetter::C.into(),
+                    uffs_mft::platform::DriveLetter::D.into(),
                 ],
             },
             DaemonEvent::DriveRefreshed {
-                drive: uffs_mft::platform::DriveLetter::C,
+                drive: uffs_mft::platform::DriveLetter::C.into(),
                 records: 200,
                 mft_ms: 20,
                 compact_ms: 10,
@@ -39,18 +39,21 @@ mod blob;
 mod parse_search_params;
 use parse_search_params::ParseSearchParamsError;
 
-// `diff_search_response` (the snapshot-diff error mapping) lives in a sibling
-// file for the same 800-LOC policy reason as `handler_blob.rs`; `#[path]` keeps
-// it an `impl RequestHandler` method the dispatcher calls as
-// `self.diff_search_response(...)`.
+// The snapshot-diff handlers (`diff_search_response`, `list_baselines`) live
+// in a sibling file for the same 800-LOC policy reason as `handler_blob.rs`;
+// `#[path]` keeps them `impl RequestHandler` methods.
 #[path = "handler_diff.rs"]
 mod diff_handler;
 
-// The USN-journal `changed_since` handler lives in a sibling file for the
-// same 800-LOC policy reason; `#[path]` keeps it an `impl RequestHandler`
-// method the dispatcher calls as `self.handle_changed_since(...)`.
+// The journal (`changed_since`, `tail_changes`), saved- and standing-query
+// handlers live in sibling files for the same 800-LOC policy reason; `#[path]`
+// keeps them `impl RequestHandler` methods called as `self.handle_*`.
 #[path = "handler_journal.rs"]
 mod journal_handler;
+#[path = "handler_queries.rs"]
+mod queries_handler;
+#[path = "handler_standing.rs"]
+mod standing_handler;
 
 /// Request handler holding shared daemon state.
 pub(crate) struct RequestHandler {
@@ -61,8 +64,8 @@ pub(crate) struct RequestHandler {
 }
 
 impl RequestHandler {
-    /// Handle a single JSON-RPC request and return a JSON response string.
-    pub(crate) async fn handle(&self, req: &RpcRequest) -> String {
+    /// Handle one JSON-RPC request from IPC connection `connection`.
+    pub(crate) async fn handle(&self, req: &RpcRequest, connection: u64) -> String {
         // Every incoming request — search, drives, status, keepalive, etc. —
         // extends the daemon's sliding-window idle deadline.  This is the
         // single authoritative call site; individual handlers do not need to
@@ -86,15 +89,16 @@ impl RequestHandler {
             "keepalive" => self.handle_keepalive(id, req),
             "shutdown" => self.handle_shutdown(id, req),
             // Phase 8-B … 8-E — operator-driven memory tiering.
-            // Phase 8-A scaffolded these arms with NotImplemented
-            // stubs; sub-phases 8-B / 8-C / 8-D / 8-E filled in
-            // `hibernate`, `preload`, `forget`, and `status_drives`
-            // respectively.
             "hibernate" => self.handle_hibernate(id, req).await,
             "preload" => self.handle_preload(id, req).await,
             "forget" => self.handle_forget(id, req).await,
             "status_drives" => self.handle_status_drives(id).await,
-            "changed_since" => self.handle_changed_since(id, req).await,
+            "changed_since" | "tail_changes" => self.handle_journal_rpc(id, req).await,
+            "list_baselines" => self.handle_list_baselines(id).await,
+            "save_query" | "list_queries" | "delete_query" => self.handle_query_rpc(id, req).await,
+            "subscribe_query" | "unsubscribe_query" => {
+                self.handle_standing_rpc(id, req, connection)
+            }
             _ => serde_json::to_string(&RpcErrorResponse::error(
                 Some(id),
                 ERR_METHOD_NOT_FOUND,
@@ -192,7 +196,9 @@ impl RequestHandler {
     /// [`SearchParams`]; returns
     /// [`ParseSearchParamsError::PatternTooLong`] when
     /// `search_params.pattern` exceeds [`MAX_PATTERN_LENGTH`] (S4.4.3
-    /// regex-DoS guard).  The wire bytes produced by
+    /// regex-DoS guard); returns
+    /// [`ParseSearchParamsError::BadSearchCursor`] when `search_cursor`
+    /// is not a token the daemon issued.  The wire bytes produced by
     /// [`ParseSearchParamsError::to_rpc_error_json`] are byte-identical
     /// with the pre-Phase-5d output so clients see no change.
     fn parse_and_validate_search_params(
@@ -204,13 +210,7 @@ impl RequestHandler {
             .and_then(|val| serde_json::from_value::<SearchParams>(val.clone()).ok())
             .ok_or(ParseSearchParamsError::MissingOrInvalidParams)?;
 
-        // S4.4.3: Reject overly long patterns (regex DoS prevention).
-        if search_params.pattern.len() > MAX_PATTERN_LENGTH {
-            return Err(ParseSearchParamsError::PatternTooLong {
-                len: search_params.pattern.len(),
-            });
-        }
-
+        parse_search_params::validate(&search_params)?;
         Ok(search_params)
     }
 
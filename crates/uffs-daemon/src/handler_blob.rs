@@ -279,17 +279,17 @@ impl RequestHandler {
             return;
         };
 
-        // `uffs-format` is char-typed at its public API; convert
-        // the typed slice at the boundary so the format crate keeps
-        // its narrow no-`uffs-mft` dep (issue #216).
-        let drive_chars: Vec<char> = params
+        // `uffs-format` is string-typed at its public API; render the
+        // drive labels at the boundary so the format crate keeps its
+        // narrow no-`uffs-mft` dep (issue #216).
+        let drive_labels: Vec<String> = params
             .output_drive_targets
             .iter()
-            .map(|dl| dl.as_char())
+            .map(|volume| volume.drive_label())
             .collect();
         let footer_ctx =
             Self::wants_custom_footer(params).then(|| uffs_format::DriveFooterContext {
-                output_targets: &drive_chars,
+                output_targets: &drive_labels,
                 pattern: &params.pattern,
                 row_count: rows.len(),
             });
@@ -511,7 +511,7 @@ impl RequestHandler {
             header: cfg.header,
             pos: cfg.pos.clone(),
             neg: cfg.neg.clone(),
-            timezone_offset_secs: cfg.timezone_offset_secs,
+            time_zone: cfg.time_zone,
             parity_compat: cfg.parity_compat,
         }
     }
@@ -39,6 +39,8 @@ fn bare_response(rows: Vec<SearchRow>) -> SearchResponse {
         duration_ms: 0,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: Vec::new(),
         applied_projection: vec!["path".to_owned()],
@@ -60,7 +62,7 @@ fn sample_row(
     size: u64,
 ) -> SearchRow {
     SearchRow {
-        drive,
+        drive: drive.into(),
         path,
         name,
         size,
@@ -80,6 +82,8 @@ fn sample_row(
         malformed_path: false,
         name_hex: None,
         file_reference: 0,
+        content_hash: None,
+        matched_pattern: None,
     }
 }
 
@@ -507,7 +511,7 @@ fn try_pack_csv_blob_custom_appends_footer_when_drives_set() {
         projection: vec!["path".to_owned(), "name".to_owned()],
         output_columns: Some("path,name".to_owned()),
         output_format: Some("custom".to_owned()),
-        output_drive_targets: vec![uffs_mft::platform::DriveLetter::C],
+        output_drive_targets: vec![uffs_mft::platform::DriveLetter::C.into()],
         ..SearchParams::default()
     };
 
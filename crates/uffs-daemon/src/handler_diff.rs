@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (c) 2025-2026 SKY, LLC.
 
-//! Snapshot-diff error mapping for [`super::RequestHandler`], plus the
+//! Snapshot-diff and point-in-time (`as_of`) error mapping for
+//! [`super::RequestHandler`], the `list_baselines` method, plus the
 //! logged `search`-request helpers built on top of
 //! [`RequestHandler::search_or_diff`].
 //!
@@ -12,46 +13,98 @@
 //! / `self.run_search_or_diff(...)`.
 //!
 //! The diff itself lives in `IndexManager::diff_search` (`crate::index::diff`);
-//! this only maps its [`crate::index::diff::DiffError`] setup failures onto the
-//! JSON-RPC error envelope.
+//! this only maps its [`crate::index::diff::DiffError`] setup failures — and
+//! the [`HistoryError`]s of `IndexManager::as_of_search` — onto the JSON-RPC
+//! error envelope. A `--hash-in` list is loaded here too, before any search
+//! runs, so a missing or unparsable list is an invalid-params error rather
+//! than an empty result.
 
 use uffs_client::protocol::response::SearchResponse;
 use uffs_client::protocol::{
-    ERR_INTERNAL, ERR_INVALID_PARAMS, ERR_NOT_READY, RpcErrorResponse, SearchParams,
+    ERR_INTERNAL, ERR_INVALID_PARAMS, ERR_INVALID_REQUEST, ERR_NOT_READY, RpcErrorResponse,
+    RpcResponse, SearchParams,
 };
 
 use super::RequestHandler;
+use crate::cache::history::HistoryError;
 use crate::index::diff::DiffError;
 
 impl RequestHandler {
     /// Resolve a search request to its response: a snapshot diff when
     /// `params.diff_baseline` is set (via [`Self::diff_search_response`], which
-    /// may yield a JSON-RPC error string), or an ordinary live search.
+    /// may yield a JSON-RPC error string), a point-in-time search when
+    /// `params.as_of` is, or an ordinary live search.
     pub(super) async fn search_or_diff(
         &self,
         id: u64,
         params: &SearchParams,
     ) -> Result<SearchResponse, String> {
+        if let Some(path) = params.hash_in.as_deref()
+            && let Err(err) = self.index.hash_list(path).await
+        {
+            return Err(serde_json::to_string(&RpcErrorResponse::error(
+                Some(id),
+                ERR_INVALID_PARAMS,
+                &err.to_string(),
+            ))
+            .unwrap_or_default());
+        }
+        if let Err(err) = self.index.pattern_set(&params.patterns) {
+            return Err(serde_json::to_string(&RpcErrorResponse::error(
+                Some(id),
+                ERR_INVALID_PARAMS,
+                &format!("pattern list: {err}"),
+            ))
+            .unwrap_or_default());
+        }
+        if let Err(err) = self.index.check_computed_fields(params) {
+            return Err(serde_json::to_string(&RpcErrorResponse::error(
+                Some(id),
+                ERR_INVALID_PARAMS,
+                &format!("computed field: {err}"),
+            ))
+            .unwrap_or_default());
+        }
+        if let Some(zone) = params.time_zone.as_deref()
+            && uffs_time::TimeZone::parse(zone).is_none()
+        {
+            return Err(serde_json::to_string(&RpcErrorResponse::error(
+                Some(id),
+                ERR_INVALID_PARAMS,
+                &format!("unknown time zone `{zone}`"),
+            ))
+            .unwrap_or_default());
+        }
         if params.diff_baseline.is_some() {
             self.diff_search_response(id, params).await
+        } else if params.as_of.is_some() {
+            self.index
+                .as_of_search(params)
+                .await
+                .map_err(|err| history_error_json(id, &err))
         } else {
             Ok(self.index.search(params).await)
         }
     }
 
-    /// Auto-load `drives` from `data_dir` before a search, timing the
+    /// Auto-load the local drives among `volumes` from `data_dir` before
+    /// a search, timing the
     /// load and warn-logging any drive that couldn't be auto-loaded —
     /// split out of `handler.rs::handle_search` to keep that function's
     /// cognitive complexity down.
-    pub(super) async fn auto_load_missing_drives(
-        &self,
-        drives: &[uffs_mft::platform::DriveLetter],
-    ) {
+    pub(super) async fn auto_load_missing_drives(&self, volumes: &[uffs_mft::platform::VolumeId]) {
+        // Host-qualified selectors name offline captures, which are
+        // loaded at startup and never auto-discovered.
+        let drives: Vec<uffs_mft::platform::DriveLetter> = volumes
+            .iter()
+            .filter(|volume| volume.is_local())
+            .map(|volume| volume.letter())
+            .collect();
         if drives.is_empty() {
             return;
         }
         let load_started = std::time::Instant::now();
-        let missing = self.index.ensure_drives_loaded(drives, false).await;
+        let missing = self.index.ensure_drives_loaded(&drives, false).await;
         tracing::info!(
             ?drives,
             elapsed_ms = load_started.elapsed().as_millis(),
@@ -132,6 +185,36 @@ impl RequestHandler {
                 ))
                 .unwrap_or_default())
             }
+            Err(DiffError::History(err)) => Err(history_error_json(id, &err)),
         }
     }
+
+    /// Handle the `list_baselines` method: the scheduled snapshots and
+    /// history reach retained per drive.  Always succeeds — a disabled
+    /// store just contributes nothing.
+    pub(super) async fn handle_list_baselines(&self, id: u64) -> String {
+        let response = self.index.list_baselines().await;
+        serde_json::to_string(&RpcResponse::success(
+            id,
+            serde_json::to_value(&response).unwrap_or_default(),
+        ))
+        .unwrap_or_default()
+    }
+}
+
+/// Pre-serialized JSON-RPC error for a failed historical rebuild: a
+/// disabled history is a request the daemon is not set up for, a bad or
+/// too-old moment is the caller's parameter, anything else is internal.
+fn history_error_json(id: u64, err: &HistoryError) -> String {
+    let code = match err {
+        HistoryError::Disabled => ERR_INVALID_REQUEST,
+        HistoryError::BadTime(_) | HistoryError::NoPoint { .. } => ERR_INVALID_PARAMS,
+        HistoryError::Io(_) | HistoryError::Join(_) => ERR_INTERNAL,
+    };
+    serde_json::to_string(&RpcErrorResponse::error(
+        Some(id),
+        code,
+        &format!("history: {err}"),
+    ))
+    .unwrap_or_default()
 }
@@ -1,9 +1,11 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (c) 2025-2026 SKY, LLC.
 
-//! `changed_since` handler for [`super::RequestHandler`]: answers
-//! *"which files changed on this drive since my USN cursor?"* straight
-//! from the volume's NTFS USN journal.
+//! `changed_since` and `tail_changes` handlers for
+//! [`super::RequestHandler`]: answer *"which files changed on this drive
+//! since my cursor?"* — straight from the volume's NTFS USN journal, or
+//! from the daemon's own durable change feed
+//! ([`crate::cache::change_log`]) that outlives journal wrap.
 //!
 //! The daemon is the natural owner of this question — it already holds
 //! the volume access (broker-adopted handles) and runs the per-shard
@@ -22,12 +24,16 @@
 //! USN journals don't (macOS/Linux), which this handler surfaces as a
 //! JSON-RPC error rather than a panic or a silent empty delta.
 
-use uffs_client::protocol::response::{ChangedSinceParams, ChangedSinceResponse, JournalChange};
+use uffs_client::protocol::response::{
+    ChangedSinceParams, ChangedSinceResponse, JournalChange, TailChangesParams,
+};
 use uffs_client::protocol::{
-    ERR_INTERNAL, ERR_INVALID_PARAMS, RpcErrorResponse, RpcRequest, RpcResponse,
+    ERR_INTERNAL, ERR_INVALID_PARAMS, ERR_INVALID_REQUEST, RpcErrorResponse, RpcRequest,
+    RpcResponse,
 };
 
 use super::RequestHandler;
+use crate::index::change_feed::ChangeFeedError;
 
 /// Hard per-call cap on raw journal records read, and the default when
 /// the request carries no `max_records`. One 64 KiB FSCTL batch holds
@@ -39,6 +45,52 @@ use super::RequestHandler;
 const MAX_RECORDS_PER_CALL: usize = 262_144;
 
 impl RequestHandler {
+    /// Route the `changed_since` / `tail_changes` methods.
+    pub(super) async fn handle_journal_rpc(&self, id: u64, req: &RpcRequest) -> String {
+        if req.method == "tail_changes" {
+            self.handle_tail_changes(id, req).await
+        } else {
+            self.handle_changed_since(id, req).await
+        }
+    }
+
+    /// Handle the `tail_changes` method: read the drive's durable change
+    /// feed after the caller's sequence cursor.
+    ///
+    /// Malformed params are `ERR_INVALID_PARAMS`; a daemon with
+    /// `[change_log]` disabled answers `ERR_INVALID_REQUEST`, and a read
+    /// failure `ERR_INTERNAL`.
+    async fn handle_tail_changes(&self, id: u64, req: &RpcRequest) -> String {
+        let Some(params) = req
+            .params
+            .as_ref()
+            .and_then(|val| serde_json::from_value::<TailChangesParams>(val.clone()).ok())
+        else {
+            return serde_json::to_string(&RpcErrorResponse::error(
+                Some(id),
+                ERR_INVALID_PARAMS,
+                "tail_changes requires params {drive[, after_seq, max_entries]}",
+            ))
+            .unwrap_or_default();
+        };
+        match self.index.tail_changes(&params).await {
+            Ok(response) => serde_json::to_string(&RpcResponse::success(
+                id,
+                serde_json::to_value(&response).unwrap_or_default(),
+            ))
+            .unwrap_or_default(),
+            Err(err) => {
+                let code = if matches!(err, ChangeFeedError::Disabled) {
+                    ERR_INVALID_REQUEST
+                } else {
+                    ERR_INTERNAL
+                };
+                serde_json::to_string(&RpcErrorResponse::error(Some(id), code, &err.to_string()))
+                    .unwrap_or_default()
+            }
+        }
+    }
+
     /// Handle the `changed_since` method.
     ///
     /// Strict on params: a request that doesn't deserialise (above all,
@@ -49,7 +101,7 @@ impl RequestHandler {
     ///
     /// The journal FSCTLs are synchronous blocking I/O, so the read
     /// runs on the blocking pool, keeping the RPC loop responsive.
-    pub(super) async fn handle_changed_since(&self, id: u64, req: &RpcRequest) -> String {
+    async fn handle_changed_since(&self, id: u64, req: &RpcRequest) -> String {
         let parsed: Option<ChangedSinceParams> = req
             .params
             .as_ref()
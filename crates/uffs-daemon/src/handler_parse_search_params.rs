@@ -21,7 +21,8 @@
 
 use alloc::borrow::Cow;
 
-use uffs_client::protocol::{ERR_INVALID_PARAMS, RpcErrorResponse};
+use uffs_client::protocol::{ERR_INVALID_PARAMS, RpcErrorResponse, SearchParams};
+use uffs_core::search::cursor::SearchCursor;
 
 use super::MAX_PATTERN_LENGTH;
 
@@ -45,6 +46,28 @@ pub(super) enum ParseSearchParamsError {
         /// The offending pattern's length in bytes.
         len: usize,
     },
+    /// `search_params.search_cursor` is not a token this daemon issued
+    /// (see `uffs_core::search::cursor::SearchCursor`).
+    #[error("Invalid search_cursor")]
+    BadSearchCursor,
+}
+
+/// Field-level checks on freshly decoded params.
+pub(super) fn validate(params: &SearchParams) -> Result<(), ParseSearchParamsError> {
+    // S4.4.3: Reject overly long patterns (regex DoS prevention).
+    if params.pattern.len() > MAX_PATTERN_LENGTH {
+        return Err(ParseSearchParamsError::PatternTooLong {
+            len: params.pattern.len(),
+        });
+    }
+    if params
+        .search_cursor
+        .as_deref()
+        .is_some_and(|token| SearchCursor::decode(token).is_none())
+    {
+        return Err(ParseSearchParamsError::BadSearchCursor);
+    }
+    Ok(())
 }
 
 impl ParseSearchParamsError {
@@ -64,6 +87,7 @@ impl ParseSearchParamsError {
         // throw-away heap allocation for the static-text variant.
         let message: Cow<'_, str> = match *self {
             Self::MissingOrInvalidParams => "Missing or invalid search params".into(),
+            Self::BadSearchCursor => "Invalid search_cursor".into(),
             Self::PatternTooLong { len } => {
                 format!("Pattern too long ({len} chars, max {MAX_PATTERN_LENGTH})").into()
             }
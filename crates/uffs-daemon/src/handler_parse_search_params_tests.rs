@@ -150,3 +150,20 @@ fn parse_and_validate_accepts_valid_params() {
         .expect("valid params must parse");
     assert_eq!(params.pattern, "*.rs");
 }
+
+/// A `search_cursor` the daemon never issued (e.g. the MCP layer's old
+/// `offset:N` tokens) is rejected up front instead of silently
+/// restarting from the first page.
+#[test]
+fn parse_and_validate_rejects_foreign_search_cursor() {
+    let req = RpcRequest {
+        jsonrpc: "2.0".to_owned(),
+        id: Some(17),
+        method: "search".to_owned(),
+        params: Some(serde_json::json!({ "pattern": "*.rs", "search_cursor": "offset:50" })),
+    };
+    let err = super::RequestHandler::parse_and_validate_search_params(&req)
+        .expect_err("foreign cursor must error");
+    assert_eq!(err, ParseSearchParamsError::BadSearchCursor);
+    assert!(err.to_rpc_error_json(17).contains("Invalid search_cursor"));
+}
@@ -23,7 +23,7 @@ use super::RequestHandler;
 /// because the packing loop only reads `row.path`.
 fn path_only_row(path: String) -> SearchRow {
     SearchRow {
-        drive: uffs_mft::platform::DriveLetter::C,
+        drive: uffs_mft::platform::DriveLetter::C.into(),
         path,
         name: String::new(),
         size: 0,
@@ -40,6 +40,8 @@ fn path_only_row(path: String) -> SearchRow {
         malformed_path: false,
         name_hex: None,
         file_reference: 0,
+        content_hash: None,
+        matched_pattern: None,
     }
 }
 
@@ -58,6 +60,8 @@ fn bare_response(rows: Vec<SearchRow>) -> SearchResponse {
         duration_ms: 0,
         promotion_ms: None,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: Vec::new(),
         applied_projection: vec!["path".to_owned()],
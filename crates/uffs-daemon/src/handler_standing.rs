@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `subscribe_query` / `unsubscribe_query` handlers for
+//! [`super::RequestHandler`]: register and drop standing queries on the
+//! calling IPC connection.
+//!
+//! The registry and the per-tick evaluation live in
+//! [`crate::index::standing`]; matches travel back as
+//! `daemon.query_match` notifications on the same connection.
+//!
+//! Lifted into a sibling file to keep `handler.rs` under the 800-line
+//! policy ceiling — same `#[path]` re-attachment pattern as
+//! `handler_queries.rs`.
+
+use uffs_client::protocol::response::{
+    SubscribeQueryParams, SubscribeQueryResponse, UnsubscribeQueryParams, UnsubscribeQueryResponse,
+};
+use uffs_client::protocol::{ERR_INVALID_PARAMS, RpcErrorResponse, RpcRequest, RpcResponse};
+
+use super::{MAX_PATTERN_LENGTH, RequestHandler};
+
+impl RequestHandler {
+    /// Route the `subscribe_query` / `unsubscribe_query` methods; both
+    /// act on the calling `connection`.
+    pub(super) fn handle_standing_rpc(&self, id: u64, req: &RpcRequest, connection: u64) -> String {
+        if req.method == "subscribe_query" {
+            self.handle_subscribe_query(id, req, connection)
+        } else {
+            self.handle_unsubscribe_query(id, req, connection)
+        }
+    }
+
+    /// Handle `subscribe_query`: validate the standing search and register
+    /// it for `connection`.
+    ///
+    /// Malformed params, an over-long pattern, an unsupported response
+    /// shape, and a full per-connection quota are all
+    /// `ERR_INVALID_PARAMS`.
+    fn handle_subscribe_query(&self, id: u64, req: &RpcRequest, connection: u64) -> String {
+        let Some(params) = req
+            .params
+            .clone()
+            .and_then(|value| serde_json::from_value::<SubscribeQueryParams>(value).ok())
+        else {
+            return invalid_params(id, "subscribe_query requires params {search[, changes]}");
+        };
+        if params.search.pattern.len() > MAX_PATTERN_LENGTH {
+            return invalid_params(
+                id,
+                &format!("Pattern exceeds maximum length of {MAX_PATTERN_LENGTH} bytes"),
+            );
+        }
+        match self.index.subscribe_query(connection, params) {
+            Ok(subscription_id) => serde_json::to_string(&RpcResponse::success(
+                id,
+                serde_json::to_value(SubscribeQueryResponse { subscription_id })
+                    .unwrap_or_default(),
+            ))
+            .unwrap_or_default(),
+            Err(err) => invalid_params(id, &format!("subscribe_query failed: {err}")),
+        }
+    }
+
+    /// Handle `unsubscribe_query`: drop one of `connection`'s standing
+    /// queries (`removed == false` when it owns no such id).
+    fn handle_unsubscribe_query(&self, id: u64, req: &RpcRequest, connection: u64) -> String {
+        let Some(params) = req
+            .params
+            .clone()
+            .and_then(|value| serde_json::from_value::<UnsubscribeQueryParams>(value).ok())
+        else {
+            return invalid_params(id, "unsubscribe_query requires params {subscription_id}");
+        };
+        let removed = self
+            .index
+            .unsubscribe_query(connection, params.subscription_id);
+        serde_json::to_string(&RpcResponse::success(
+            id,
+            serde_json::to_value(UnsubscribeQueryResponse {
+                subscription_id: params.subscription_id,
+                removed,
+            })
+            .unwrap_or_default(),
+        ))
+        .unwrap_or_default()
+    }
+}
+
+/// Serialise an `ERR_INVALID_PARAMS` error response.
+fn invalid_params(id: u64, message: &str) -> String {
+    serde_json::to_string(&RpcErrorResponse::error(
+        Some(id),
+        ERR_INVALID_PARAMS,
+        message,
+    ))
+    .unwrap_or_default()
+}
@@ -24,73 +24,25 @@
 use uffs_client::protocol::{DrilldownWire, SampleRowWire};
 use uffs_core::aggregate::finalize::{DrilldownPredicate, DrilldownValue, SampleRow};
 use uffs_core::aggregate::spec::DuplicateVerify;
-use uffs_core::aggregate::verify::{DuplicateVerifier, FileReader, VerificationBudget};
+use uffs_core::aggregate::verify::{DuplicateVerifier, VerificationBudget};
 use uffs_core::search::backend::DriveIndex;
+use uffs_core::search::computed::ComputedFields;
 
 use super::IndexManager;
-
-// ── Daemon file reader for duplicate verification ───────────────────
-
-/// File reader that resolves `(record_idx, drive_ordinal)` to a file path
-/// via the compact index, then reads bytes from disk.
-///
-/// Only functional on Windows where the resolved paths (e.g. `C:\Users\...`)
-/// point to real files. On macOS/Linux (offline mode), reads always fail
-/// gracefully — verification is skipped and groups remain unverified.
-struct DaemonFileReader<'a> {
-    /// Loaded drive indices for path resolution.
-    drives: &'a [alloc::sync::Arc<uffs_core::compact::DriveCompactIndex>],
-}
-
-impl DaemonFileReader<'_> {
-    /// Resolve a record to its full file path.
-    fn resolve_path(&self, record_idx: usize, drive_ordinal: u8) -> Option<String> {
-        let drive = self.drives.get(usize::from(drive_ordinal))?;
-        let volume_prefix = format!("{}:\\", drive.letter);
-        Some(uffs_core::search::tree::resolve_path(
-            drive,
-            record_idx,
-            &volume_prefix,
-            uffs_core::compact::MalformedRender::Lossy,
-        ))
-    }
-}
-
-impl FileReader for DaemonFileReader<'_> {
-    fn read_first_bytes(
-        &self,
-        record_idx: usize,
-        drive_ordinal: u8,
-        count: u32,
-    ) -> std::io::Result<Vec<u8>> {
-        use std::io::Read as _;
-        let path = self
-            .resolve_path(record_idx, drive_ordinal)
-            .ok_or_else(|| {
-                std::io::Error::new(std::io::ErrorKind::NotFound, "drive ordinal out of range")
-            })?;
-        let mut file = std::fs::File::open(&path)?;
-        let mut buf = vec![0_u8; uffs_mft::u32_as_usize(count)];
-        let n = file.read(&mut buf)?;
-        buf.truncate(n);
-        Ok(buf)
-    }
-
-    fn read_all(&self, record_idx: usize, drive_ordinal: u8) -> std::io::Result<Vec<u8>> {
-        let path = self
-            .resolve_path(record_idx, drive_ordinal)
-            .ok_or_else(|| {
-                std::io::Error::new(std::io::ErrorKind::NotFound, "drive ordinal out of range")
-            })?;
-        std::fs::read(&path)
-    }
-}
+use super::content_hash::{DaemonFileReader, DriveHashes, save_content_hashes};
+use crate::cache::hash_store::ContentHashStore;
 
 /// Default verification budget: 256 MB total, 10 000 files max.
 const DEFAULT_VERIFY_BUDGET_BYTES: u64 = 256 * 1024 * 1024;
 /// Default max files for verification budget.
 const DEFAULT_VERIFY_BUDGET_FILES: u32 = 10_000;
 
+/// The read budget one request's content verification gets: duplicate
+/// verification and the `--hash-in` filter alike.
+pub(super) const fn verify_budget() -> VerificationBudget {
+    VerificationBudget::new(DEFAULT_VERIFY_BUDGET_BYTES, DEFAULT_VERIFY_BUDGET_FILES)
+}
+
 /// Convert a core [`SampleRow`] to a wire [`SampleRowWire`].
 fn sample_row_to_wire(sr: SampleRow) -> SampleRowWire {
     SampleRowWire {
@@ -188,6 +140,8 @@ struct AggregateCacheCtx<'a> {
     key_hash: Option<u64>,
     /// Shared cache instance, when one is configured.
     cache: Option<&'a uffs_core::aggregate::AggregateCache>,
+    /// Content-hash store duplicate verification reads and fills.
+    hashes: Option<&'a ContentHashStore>,
 }
 
 /// The scan-scope inputs [`IndexManager::compute_aggregate_output`]
@@ -241,8 +195,8 @@ pub(crate) struct AggregationRequest<'a> {
     /// Glob / regex name matcher applied during the scan.  `None`
     /// disables name matching.
     pub pattern: Option<&'a str>,
-    /// Subset of drive letters to include; empty = all drives.
-    pub drives_filter: &'a [uffs_mft::platform::DriveLetter],
+    /// Volume selectors to include; empty = all drives.
+    pub drives_filter: &'a [uffs_mft::platform::VolumeId],
     /// O(1)-per-record predicates: extension IDs, directory flag,
     /// size bounds.  Defaults to "no filter" via
     /// [`uffs_core::aggregate::AggregateFilter::default`].
@@ -255,6 +209,11 @@ pub(crate) struct AggregationRequest<'a> {
     /// with path-dependent queries must use
     /// [`IndexManager::run_aggregations_over_rows`] instead.
     pub search_filters: Option<uffs_core::search::filters::SearchFilters>,
+    /// Persistent content-hash store consulted by duplicate verification
+    /// (`[hash_cache]`).  `None` (the test default) hashes every member.
+    pub content_hashes: Option<&'a ContentHashStore>,
+    /// Computed fields `stats` / `histogram` / `range` specs may name.
+    pub computed: ComputedFields,
 }
 
 impl IndexManager {
@@ -287,12 +246,14 @@ impl IndexManager {
             drives_filter,
             record_filter,
             search_filters,
+            content_hashes,
+            computed,
         } = request;
 
         // Convert wire specs to core specs.
         let mut specs: Vec<AggregateSpec> = Vec::new();
         for ws in wire_specs {
-            match Self::convert_wire_spec(ws) {
+            match Self::convert_wire_spec_in(ws, &computed) {
                 Ok(converted) => specs.extend(converted),
                 Err(e) => {
                     tracing::warn!(kind = %ws.kind, "skipping malformed aggregate spec: {e}");
@@ -308,7 +269,10 @@ impl IndexManager {
         let drive_refs: Vec<&uffs_core::compact::DriveCompactIndex> = snapshot
             .drives
             .iter()
-            .filter(|arc| drives_filter.is_empty() || drives_filter.contains(&arc.letter))
+            .filter(|arc| {
+                drives_filter.is_empty()
+                    || drives_filter.iter().any(|sel| sel.selects(arc.volume()))
+            })
             .map(|arc| arc.as_ref())
             .collect();
         // ── Cache lookup ────────────────────────────────────────────
@@ -350,6 +314,7 @@ impl IndexManager {
             AggregateCacheCtx {
                 key_hash: cache_key_hash,
                 cache,
+                hashes: content_hashes,
             },
             &drive_refs,
             &specs,
@@ -397,20 +362,30 @@ impl IndexManager {
     /// source of truth for what matched; aggregation only folds the
     /// survivors.  No cache: the row set is the input, and hashing
     /// millions of `(drive, idx)` pairs would cost more than the fold.
+    ///
+    /// Of `request`, only the drill-down predicates, the paging knobs, the
+    /// content-hash store and the computed fields apply: the row set
+    /// already carries the scope.
     pub(crate) fn run_aggregations_over_rows(
         snapshot: &DriveIndex,
         wire_specs: &[uffs_client::protocol::AggregateSpecWire],
-        matched: &[(uffs_mft::platform::DriveLetter, u32)],
-        query_predicates: Vec<DrilldownPredicate>,
-        agg_cursor: Option<&str>,
-        agg_page_size: Option<u16>,
+        matched: &[(uffs_mft::platform::VolumeId, u32)],
+        request: AggregationRequest<'_>,
     ) -> (Vec<uffs_client::protocol::AggregateResultWire>, u64) {
         use uffs_core::aggregate::finalize::FinalizeOptions;
         use uffs_core::aggregate::spec::AggregateSpec;
 
+        let AggregationRequest {
+            query_predicates,
+            agg_cursor,
+            agg_page_size,
+            content_hashes,
+            computed,
+            ..
+        } = request;
         let mut specs: Vec<AggregateSpec> = Vec::new();
         for ws in wire_specs {
-            match Self::convert_wire_spec(ws) {
+            match Self::convert_wire_spec_in(ws, &computed) {
                 Ok(converted) => specs.extend(converted),
                 Err(e) => {
                     tracing::warn!(kind = %ws.kind, "skipping malformed aggregate spec: {e}");
@@ -435,7 +410,12 @@ impl IndexManager {
             matched,
         ) {
             Ok(mut output) => {
-                Self::run_duplicate_verification(&specs, &mut output, &snapshot.drives);
+                Self::run_duplicate_verification(
+                    &specs,
+                    &mut output,
+                    &snapshot.drives,
+                    content_hashes,
+                );
                 let records_matched = output.records_matched;
                 let wire_results = convert_aggregate_results_to_wire(
                     output.response.results,
@@ -466,7 +446,11 @@ impl IndexManager {
         scope: ScanScope<'_>,
         drives: &[alloc::sync::Arc<uffs_core::compact::DriveCompactIndex>],
     ) -> Option<uffs_core::aggregate::AggregateOutput> {
-        let AggregateCacheCtx { key_hash, cache } = cache_ctx;
+        let AggregateCacheCtx {
+            key_hash,
+            cache,
+            hashes,
+        } = cache_ctx;
         if let Some(hit) = key_hash.and_then(|k| cache.and_then(|c| c.get(k))) {
             tracing::debug!(
                 scanned = hit.records_scanned,
@@ -494,7 +478,7 @@ impl IndexManager {
                 // result: its effect lives in `fresh.response`, so
                 // run it *before* populating the cache so future
                 // hits include verification state.
-                Self::run_duplicate_verification(specs, &mut fresh, drives);
+                Self::run_duplicate_verification(specs, &mut fresh, drives, hashes);
                 if let (Some(k), Some(c)) = (key_hash, cache) {
                     c.put(k, fresh.clone());
                 }
@@ -516,7 +500,7 @@ impl IndexManager {
     ///   `label`, `top`, sample spec, and rollup field.
     /// - `pattern` — glob/regex name matcher (`None` vs. `Some("")` are
     ///   distinguished by `Option::hash`).
-    /// - `drives_filter` — the set of drive letters to scope the scan.
+    /// - `drives_filter` — the volume selectors scoping the scan.
     /// - `record_filter` — extensions, directory flag, size bounds.
     /// - `query_predicates` — drill-down predicates forwarded to
     ///   `FinalizeOptions` so bucket drilldowns reflect the original query
@@ -536,7 +520,7 @@ impl IndexManager {
     fn build_agg_cache_key(
         specs: &[uffs_core::aggregate::spec::AggregateSpec],
         pattern: Option<&str>,
-        drives_filter: &[uffs_mft::platform::DriveLetter],
+        drives_filter: &[uffs_mft::platform::VolumeId],
         record_filter: &uffs_core::aggregate::AggregateFilter,
         query_predicates: &[DrilldownPredicate],
         search_filters: Option<&uffs_core::search::filters::SearchFilters>,
@@ -569,12 +553,14 @@ impl IndexManager {
     /// `verify != None`.
     ///
     /// Extracts the verify mode from the original specs, builds a
-    /// [`DaemonFileReader`], and calls [`DuplicateVerifier::verify`].
-    /// Results are mutated in place.
+    /// [`DaemonFileReader`], and calls [`DuplicateVerifier::verify_cached`]
+    /// against `hashes` when the content-hash cache is enabled, saving
+    /// the digests the pass added.  Results are mutated in place.
     fn run_duplicate_verification(
         specs: &[uffs_core::aggregate::spec::AggregateSpec],
         output: &mut uffs_core::aggregate::AggregateOutput,
         drives: &[alloc::sync::Arc<uffs_core::compact::DriveCompactIndex>],
+        hashes: Option<&ContentHashStore>,
     ) {
         use uffs_core::aggregate::finalize::AggregateResultData;
         use uffs_core::aggregate::spec::AggregateKind;
@@ -587,12 +573,15 @@ impl IndexManager {
                 AggregateKind::Count
                 | AggregateKind::Stats { .. }
                 | AggregateKind::Terms { .. }
+                | AggregateKind::RegexTerms { .. }
                 | AggregateKind::Histogram { .. }
                 | AggregateKind::DateHistogram { .. }
                 | AggregateKind::Range { .. }
                 | AggregateKind::Missing { .. }
                 | AggregateKind::Distinct { .. }
-                | AggregateKind::Rollup { .. } => DuplicateVerify::None,
+                | AggregateKind::Rollup { .. }
+                | AggregateKind::Pivot { .. }
+                | AggregateKind::SimilarNames { .. } => DuplicateVerify::None,
             })
             .collect();
 
@@ -605,8 +594,8 @@ impl IndexManager {
         }
 
         let reader = DaemonFileReader { drives };
-        let budget =
-            VerificationBudget::new(DEFAULT_VERIFY_BUDGET_BYTES, DEFAULT_VERIFY_BUDGET_FILES);
+        let drive_hashes = hashes.map(|store| DriveHashes { store, drives });
+        let budget = verify_budget();
 
         // Walk results in parallel with specs. Each result corresponds to a
         // spec at the same index.
@@ -626,7 +615,13 @@ impl IndexManager {
                     verification_mode: DuplicateVerify::None,
                 };
                 let current = core::mem::replace(dup_result, placeholder);
-                let (vfy_result, summary) = verifier.verify(current, &reader);
+                let (vfy_result, summary) = verifier.verify_cached(
+                    current,
+                    &reader,
+                    drive_hashes
+                        .as_ref()
+                        .map(|bridge| bridge as &dyn uffs_core::aggregate::HashStore),
+                );
                 *dup_result = vfy_result;
 
                 tracing::info!(
@@ -636,11 +631,15 @@ impl IndexManager {
                     groups_skipped = summary.groups_skipped,
                     groups_errored = summary.groups_errored,
                     bytes_read = summary.bytes_read,
+                    cache_hits = summary.cache_hits,
                     budget_exhausted = summary.budget_exhausted,
                     "duplicate verification complete"
                 );
             }
         }
+        if let Some(store) = hashes {
+            save_content_hashes(store);
+        }
     }
 }
 
@@ -701,6 +700,7 @@ fn build_aggregate_result_wire(
     use uffs_core::aggregate::finalize::AggregateResultData;
 
     let label = result.label.clone();
+    let mut pivot = None;
     let (kind, field, value, stats, buckets, other_count, total_groups, exact, values_complete) =
         match result.data {
             AggregateResultData::Count { value } => wire_count(value),
@@ -716,6 +716,11 @@ fn build_aggregate_result_wire(
             AggregateResultData::Distinct { field, count } => wire_distinct(field, count),
             AggregateResultData::Rollup { mode, rows } => wire_rollup(mode, rows),
             AggregateResultData::Duplicates { result } => wire_duplicates(result, drives),
+            AggregateResultData::Pivot { result } => {
+                let (tuple, matrix) = wire_pivot(result);
+                pivot = Some(matrix);
+                tuple
+            }
         };
 
     // Apply pagination: replace full bucket list with the current page
@@ -741,6 +746,7 @@ fn build_aggregate_result_wire(
         next_cursor,
         exact,
         values_complete,
+        pivot,
     }
 }
 
@@ -797,6 +803,7 @@ fn wire_stats(
             avg: stats.avg,
             waste_bytes: stats.waste_bytes,
             waste_pct: stats.waste_pct,
+            percentiles: percentiles_to_wire(&stats.percentiles),
         }),
         vec![],
         None,
@@ -815,28 +822,7 @@ fn wire_buckets(
     total_groups: usize,
     exact: bool,
 ) -> AggregateWireTuple {
-    use uffs_client::protocol::BucketWire;
-
-    let buckets = rows
-        .into_iter()
-        .map(|r| {
-            let samples = r.sample_rows.into_iter().map(sample_row_to_wire).collect();
-            let drills = r.drilldown.into_iter().map(drilldown_to_wire).collect();
-            BucketWire {
-                key: r.key,
-                count: r.count,
-                total_bytes: r.total_bytes,
-                total_allocated: Some(r.total_allocated),
-                avg_size: Some(r.avg_size),
-                share_count: Some(r.share_of_total_count),
-                share_bytes: Some(r.share_of_total_bytes),
-                sample_rows: samples,
-                drilldown: drills,
-                sub_buckets: Vec::new(),
-                verified: false,
-            }
-        })
-        .collect();
+    let buckets = rows.into_iter().map(bucket_row_to_wire).collect();
     (
         "buckets".to_owned(),
         Some(field),
@@ -850,6 +836,92 @@ fn wire_buckets(
     )
 }
 
+/// Estimated percentiles as labelled wire pairs.
+fn percentiles_to_wire(
+    percentiles: &[(uffs_core::aggregate::PercentileRank, u64)],
+) -> Vec<uffs_client::protocol::PercentileWire> {
+    percentiles
+        .iter()
+        .map(|&(rank, value)| uffs_client::protocol::PercentileWire {
+            rank: rank.label(),
+            value,
+        })
+        .collect()
+}
+
+/// Wire builder for
+/// [`uffs_core::aggregate::finalize::AggregateResultData::Pivot`]: the
+/// matrix travels in [`uffs_client::protocol::PivotWire`], the row count
+/// in `total_groups`.
+fn wire_pivot(
+    result: uffs_core::aggregate::PivotResult,
+) -> (AggregateWireTuple, uffs_client::protocol::PivotWire) {
+    use uffs_client::protocol::{PivotRowWire, PivotWire};
+    let complete =
+        result.total_rows <= result.rows.len() && result.total_cols <= result.columns.len();
+    let tuple = (
+        "pivot".to_owned(),
+        Some(result.row_field.clone()),
+        None,
+        None,
+        vec![],
+        None,
+        Some(result.total_rows),
+        Some(true),
+        Some(complete),
+    );
+    let matrix = PivotWire {
+        row_field: result.row_field,
+        col_field: result.col_field,
+        metric: uffs_core::aggregate::pivot::metric_name(result.metric),
+        columns: result.columns,
+        rows: result
+            .rows
+            .into_iter()
+            .map(|row| PivotRowWire {
+                key: row.key,
+                values: row.values,
+                total: row.total,
+            })
+            .collect(),
+        column_totals: result.column_totals,
+        grand_total: result.grand_total,
+        total_cols: result.total_cols,
+        heatmap: result.heatmap,
+        time_zone: result.time_zone,
+    };
+    (tuple, matrix)
+}
+
+/// One bucket row, nested sub-buckets included.
+fn bucket_row_to_wire(
+    row: uffs_core::aggregate::finalize::BucketRow,
+) -> uffs_client::protocol::BucketWire {
+    uffs_client::protocol::BucketWire {
+        key: row.key,
+        count: row.count,
+        total_bytes: row.total_bytes,
+        total_allocated: Some(row.total_allocated),
+        avg_size: Some(row.avg_size),
+        share_count: Some(row.share_of_total_count),
+        share_bytes: Some(row.share_of_total_bytes),
+        percentiles: percentiles_to_wire(&row.size_percentiles),
+        sample_rows: row
+            .sample_rows
+            .into_iter()
+            .map(sample_row_to_wire)
+            .collect(),
+        drilldown: row.drilldown.into_iter().map(drilldown_to_wire).collect(),
+        sub_buckets: row
+            .sub_buckets
+            .into_iter()
+            .map(bucket_row_to_wire)
+            .collect(),
+        verified: false,
+        growth: None,
+    }
+}
+
 /// Wire builder for
 /// [`uffs_core::aggregate::finalize::AggregateResultData::Missing`].
 fn wire_missing(field: String, count: u64) -> AggregateWireTuple {
@@ -888,45 +960,7 @@ fn wire_rollup(
     mode: String,
     rows: Vec<uffs_core::aggregate::finalize::BucketRow>,
 ) -> AggregateWireTuple {
-    use uffs_client::protocol::BucketWire;
-
-    let buckets = rows
-        .into_iter()
-        .map(|r| {
-            let samples = r.sample_rows.into_iter().map(sample_row_to_wire).collect();
-            let drills = r.drilldown.into_iter().map(drilldown_to_wire).collect();
-            let subs = r
-                .sub_buckets
-                .into_iter()
-                .map(|sub| BucketWire {
-                    key: sub.key,
-                    count: sub.count,
-                    total_bytes: sub.total_bytes,
-                    total_allocated: Some(sub.total_allocated),
-                    avg_size: Some(sub.avg_size),
-                    share_count: Some(sub.share_of_total_count),
-                    share_bytes: Some(sub.share_of_total_bytes),
-                    sample_rows: Vec::new(),
-                    drilldown: Vec::new(),
-                    sub_buckets: Vec::new(),
-                    verified: false,
-                })
-                .collect();
-            BucketWire {
-                key: r.key,
-                count: r.count,
-                total_bytes: r.total_bytes,
-                total_allocated: Some(r.total_allocated),
-                avg_size: Some(r.avg_size),
-                share_count: Some(r.share_of_total_count),
-                share_bytes: Some(r.share_of_total_bytes),
-                sample_rows: samples,
-                drilldown: drills,
-                sub_buckets: subs,
-                verified: false,
-            }
-        })
-        .collect();
+    let buckets = rows.into_iter().map(bucket_row_to_wire).collect();
     (
         "rollup".to_owned(),
         Some(mode),
@@ -988,10 +1022,12 @@ fn wire_duplicates(
                 avg_size: Some(uffs_mft::u64_to_f64(g.file_size)),
                 share_count: None,
                 share_bytes: None,
+                percentiles: Vec::new(),
                 sample_rows: samples,
                 drilldown: Vec::new(),
                 sub_buckets: Vec::new(),
                 verified: g.verified,
+                growth: None,
             }
         })
         .collect();
@@ -1012,6 +1048,7 @@ fn wire_duplicates(
         } else {
             0.0
         },
+        percentiles: Vec::new(),
     };
 
     (
@@ -0,0 +1,466 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Threshold alerts: the `[[alerts]]` rules of `daemon.toml`
+//! ([`crate::config::AlertRule`]) evaluated after every journal apply
+//! ([`IndexManager::handle_journal_apply`] / `handle_journal_save`) and
+//! every drive refresh.
+//!
+//! Each evaluation samples the metrics of the rules on the touched volume
+//! — a folder's `treesize`, the drive's free percentage via
+//! [`uffs_mft::platform::physical_drives`], or the `treesize` growth rate
+//! against a rolling baseline — and feeds them to the rule's
+//! [`AlertState`].  A rule's `path` is parsed once and resolved to a
+//! record with the same tree walk as the `info` RPC (`C:` alone is the
+//! volume root); the record is reused until the volume's index is
+//! replaced, and then only re-resolved when its `O(depth)` parent chain
+//! no longer spells the path.  A firing is broadcast as
+//! [`DaemonEvent::AlertFired`] and hands the alert JSON to the rule's
+//! local actions (command, JSONL sink) in a background task, so a slow
+//! script never holds up the journal tick; a command still running after
+//! the rule's `command_timeout_secs` is killed.
+//!
+//! Rules do not flap: after firing, a rule is disarmed until the metric
+//! crosses back over its `rearm` level, and it never fires twice within
+//! `cooldown_secs`.  State is in-memory only; a daemon restart re-arms
+//! every rule.
+
+use alloc::sync::{Arc, Weak};
+use core::time::Duration;
+use std::io::Write as _;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use uffs_core::compact::DriveCompactIndex;
+use uffs_mft::platform::VolumeId;
+
+use super::IndexManager;
+use crate::config::{AlertMetric, AlertRule};
+use crate::events::DaemonEvent;
+
+/// Shortest span a `growth_per_hour` rate is extrapolated from — a
+/// single two-second apply tick says nothing about an hourly rate.
+const MIN_GROWTH_WINDOW: Duration = Duration::from_mins(1);
+
+/// Span after which the `growth_per_hour` baseline rolls forward, so
+/// the rate tracks roughly the last hour rather than the daemon's life.
+const GROWTH_WINDOW: Duration = Duration::from_hours(1);
+
+/// Environment variable carrying the alert JSON to a rule's `command`.
+const ALERT_ENV_VAR: &str = "UFFS_ALERT";
+
+/// Evaluation state of one [`AlertRule`].
+#[derive(Debug)]
+pub(crate) struct AlertState {
+    /// `false` between a firing and the metric crossing back over the
+    /// rule's `rearm` level.
+    armed: bool,
+    /// When the rule last fired, for the cooldown.
+    last_fired: Option<Instant>,
+    /// `(taken_at, treesize)` the `growth_per_hour` rate is measured
+    /// against.
+    baseline: Option<(Instant, u64)>,
+    /// The rule's parsed `path`; `None` when it names no loadable volume.
+    target: Option<AlertTarget>,
+}
+
+impl Default for AlertState {
+    fn default() -> Self {
+        Self {
+            armed: true,
+            last_fired: None,
+            baseline: None,
+            target: None,
+        }
+    }
+}
+
+/// A rule's `path` split into its volume and segments, plus the record
+/// it last resolved to.
+#[derive(Debug)]
+struct AlertTarget {
+    /// Volume the path lives on.
+    volume: VolumeId,
+    /// Path segments below the volume root; empty for the root itself.
+    segments: Vec<String>,
+    /// The index the path was last resolved in and the record found
+    /// there (`None`: the path did not resolve).
+    resolved: Option<(Weak<DriveCompactIndex>, Option<usize>)>,
+}
+
+impl AlertTarget {
+    /// Parse `path` (`D:\Projects`, `C:`, `srv01\C:\Users`).
+    fn parse(path: &str) -> Option<Self> {
+        let (volume, segments) = IndexManager::split_info_path(path)?;
+        Some(Self {
+            volume,
+            segments,
+            resolved: None,
+        })
+    }
+
+    /// Whether the path lives on `volume` (any serial when the path
+    /// names none).
+    fn is_on(&self, volume: VolumeId) -> bool {
+        volume.with_serial(self.volume.serial()) == self.volume
+    }
+
+    /// Position of the path's record in `drive`.
+    ///
+    /// Free while `drive` is the index last resolved in; against a newer
+    /// index the previous position is kept if its parent chain still
+    /// spells the path, and only otherwise is the path walked again.
+    fn record(&mut self, drive: &Arc<DriveCompactIndex>) -> Option<usize> {
+        if let Some((seen, found)) = &self.resolved {
+            if seen.as_ptr() == Arc::as_ptr(drive) {
+                return *found;
+            }
+            if let Some(idx) = *found
+                && spells_path(drive, idx, &self.segments)
+            {
+                self.resolved = Some((Arc::downgrade(drive), Some(idx)));
+                return Some(idx);
+            }
+        }
+        let found = IndexManager::resolve_segments(drive, &self.segments);
+        self.resolved = Some((Arc::downgrade(drive), found));
+        found
+    }
+}
+
+/// Whether the record at `idx` still sits at `segments` — walked up the
+/// parent chain, so `O(depth)`.
+fn spells_path(drive: &DriveCompactIndex, idx: usize, segments: &[String]) -> bool {
+    if segments.is_empty() {
+        return drive.records.get(idx).is_some_and(|root| {
+            root.parent_idx == u32::MAX && root.frs() == uffs_mft::index::ROOT_FRS
+        });
+    }
+    let mut cursor = uffs_mft::len_to_u32(idx);
+    for segment in segments.iter().rev() {
+        let Some(record) = drive.records.get(uffs_mft::u32_as_usize(cursor)) else {
+            return false;
+        };
+        if !record.name(&drive.names).eq_ignore_ascii_case(segment) {
+            return false;
+        }
+        cursor = record.parent_idx;
+    }
+    cursor == u32::MAX
+}
+
+impl AlertState {
+    /// Feed one sample of `rule`'s metric taken at `now`; `true` when
+    /// the rule fires.
+    ///
+    /// Fires when the value is past the threshold, the rule is armed,
+    /// and the cooldown has elapsed.  A firing disarms the rule until a
+    /// sample lands back on the safe side of `rearm`.
+    pub(crate) fn observe(&mut self, rule: &AlertRule, value: u64, now: Instant) -> bool {
+        let Some((threshold, rising)) = rule.trigger() else {
+            return false;
+        };
+        let rearm = rule.rearm.unwrap_or(threshold);
+        let (breached, recovered) = if rising {
+            (value > threshold, value <= rearm)
+        } else {
+            (value < threshold, value >= rearm)
+        };
+        if recovered {
+            self.armed = true;
+        }
+        if !breached || !self.armed {
+            return false;
+        }
+        let cooldown = Duration::from_secs(rule.cooldown_secs);
+        if self
+            .last_fired
+            .is_some_and(|last| now.saturating_duration_since(last) < cooldown)
+        {
+            return false;
+        }
+        self.armed = false;
+        self.last_fired = Some(now);
+        true
+    }
+
+    /// Turn a `treesize` sample into a bytes-per-hour growth rate
+    /// against the rolling baseline (shrinkage reads as `0`).
+    ///
+    /// `None` until the baseline is at least [`MIN_GROWTH_WINDOW`] old;
+    /// the baseline moves to `now` once it is [`GROWTH_WINDOW`] old.
+    pub(crate) fn growth_per_hour(&mut self, treesize: u64, now: Instant) -> Option<u64> {
+        let Some((since, base)) = self.baseline else {
+            self.baseline = Some((now, treesize));
+            return None;
+        };
+        let elapsed = now.saturating_duration_since(since);
+        if elapsed < MIN_GROWTH_WINDOW {
+            return None;
+        }
+        if elapsed >= GROWTH_WINDOW {
+            self.baseline = Some((now, treesize));
+        }
+        Some(
+            treesize
+                .saturating_sub(base)
+                .saturating_mul(GROWTH_WINDOW.as_secs())
+                / elapsed.as_secs(),
+        )
+    }
+
+    /// Whether the rule's path lives on `volume`.
+    fn watches(&self, volume: VolumeId) -> bool {
+        self.target
+            .as_ref()
+            .is_some_and(|target| target.is_on(volume))
+    }
+
+    /// `treesize` of the rule's path in `drive`, `None` when the path
+    /// does not resolve.
+    fn treesize(&mut self, drive: &Arc<DriveCompactIndex>) -> Option<u64> {
+        let idx = self.target.as_mut()?.record(drive)?;
+        Some(drive.records.get(idx)?.treesize)
+    }
+}
+
+/// Per-rule [`AlertState`]s, parallel to `Config::alerts`.
+#[derive(Debug, Default)]
+pub(crate) struct AlertMonitor {
+    /// One state per configured rule, in config order.
+    states: Mutex<Vec<AlertState>>,
+}
+
+impl AlertMonitor {
+    /// Fresh (armed) state for `rules`, each with its `path` parsed.
+    pub(crate) fn new(rules: &[AlertRule]) -> Self {
+        Self {
+            states: Mutex::new(
+                rules
+                    .iter()
+                    .map(|rule| AlertState {
+                        target: AlertTarget::parse(&rule.path),
+                        ..AlertState::default()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Lock the states, riding through poisoning.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<AlertState>> {
+        self.states
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl IndexManager {
+    /// Evaluate the `[[alerts]]` rules whose path lives on `volume`,
+    /// emitting [`DaemonEvent::AlertFired`] and starting the local
+    /// actions for every rule that fires.  Cheap no-op when no rule
+    /// targets the volume.
+    pub(crate) async fn evaluate_alerts(&self, volume: VolumeId) {
+        let rules = &self.config.alerts;
+        let metrics: Vec<AlertMetric> = rules
+            .iter()
+            .zip(self.alerts.lock().iter())
+            .filter(|(_, state)| state.watches(volume))
+            .map(|(rule, _)| rule.metric)
+            .collect();
+        if metrics.is_empty() {
+            return;
+        }
+        let snap = self.snapshot().await;
+        let drive = snap
+            .drives
+            .iter()
+            .find(|dr| dr.volume().with_serial(volume.serial()) == volume);
+        let free_percent = if metrics.contains(&AlertMetric::FreePercent) {
+            free_percent(volume).await
+        } else {
+            None
+        };
+        let now = Instant::now();
+
+        let fired: Vec<(&AlertRule, u64)> = {
+            let mut states = self.alerts.lock();
+            rules
+                .iter()
+                .zip(states.iter_mut())
+                .filter(|(_, state)| state.watches(volume))
+                .filter_map(|(rule, state)| {
+                    let value = match rule.metric {
+                        AlertMetric::FreePercent => free_percent?,
+                        AlertMetric::Treesize => state.treesize(drive?)?,
+                        AlertMetric::GrowthPerHour => {
+                            let treesize = state.treesize(drive?)?;
+                            state.growth_per_hour(treesize, now)?
+                        }
+                    };
+                    state.observe(rule, value, now).then_some((rule, value))
+                })
+                .collect()
+        };
+
+        for (rule, value) in fired {
+            let event = DaemonEvent::AlertFired {
+                rule: rule.name.clone(),
+                metric: rule.metric,
+                path: rule.path.clone(),
+                value,
+                threshold: rule.trigger().map_or(0, |(threshold, _)| threshold),
+                fired_at_ms: crate::cache::unix_now_ms(),
+            };
+            tracing::info!(
+                target: "daemon.alerts",
+                rule = %rule.name,
+                path = %rule.path,
+                value,
+                "Alert fired"
+            );
+            spawn_alert_actions(rule, &event);
+            self.events.emit(event);
+        }
+    }
+}
+
+/// Free space of `volume` in whole percent, or `None` when the volume
+/// size cannot be read (hosted volumes, and always on non-Windows hosts).
+async fn free_percent(volume: VolumeId) -> Option<u64> {
+    if !volume.is_local() {
+        return None;
+    }
+    let letter = volume.letter();
+    let drive = tokio::task::spawn_blocking(move || {
+        uffs_mft::platform::physical_drives()
+            .into_iter()
+            .find(|drive| drive.letter == letter)
+    })
+    .await
+    .ok()??;
+    drive
+        .free_bytes
+        .saturating_mul(100)
+        .checked_div(drive.total_bytes)
+}
+
+/// Run `rule`'s sink (on the blocking pool) and command for `event` in a
+/// background task.  Best-effort: failures are warn-logged, never
+/// retried.
+fn spawn_alert_actions(rule: &AlertRule, event: &DaemonEvent) {
+    if rule.command.is_empty() && rule.sink.is_none() {
+        return;
+    }
+    let Ok(json) = serde_json::to_string(event) else {
+        return;
+    };
+    let name = rule.name.clone();
+    let command = rule.command.clone();
+    let limit = Duration::from_secs(rule.command_timeout_secs);
+    let sink = rule.sink.clone();
+    let _actions_join = tokio::spawn(async move {
+        if let Some(path) = sink {
+            let line = json.clone();
+            let appended =
+                tokio::task::spawn_blocking(move || (append_jsonl(&path, &line), path)).await;
+            if let Ok((Err(err), sink_path)) = appended {
+                tracing::warn!(
+                    target: "daemon.alerts",
+                    rule = %name,
+                    sink = %sink_path.display(),
+                    error = %err,
+                    "Alert sink append failed"
+                );
+            }
+        }
+        if let Some((program, args)) = command.split_first() {
+            run_alert_command(&name, program, args, &json, limit).await;
+        }
+    });
+}
+
+/// How a rule's `command` ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommandEnd {
+    /// Exited on its own, successfully or not.
+    Exited(std::process::ExitStatus),
+    /// Could not be started or waited on.
+    Failed,
+    /// Still running at the timeout, and killed.
+    Killed,
+}
+
+/// Run one rule's `command` with the alert JSON in [`ALERT_ENV_VAR`],
+/// killing it if it outlives `limit`; a failed start, non-zero exit or
+/// timeout is warn-logged.
+pub(crate) async fn run_alert_command(
+    name: &str,
+    program: &str,
+    args: &[String],
+    json: &str,
+    limit: Duration,
+) -> CommandEnd {
+    match run_with_limit(program, args, json, limit).await {
+        Ok(Some(status)) => {
+            if !status.success() {
+                tracing::warn!(
+                    target: "daemon.alerts",
+                    rule = %name,
+                    %status,
+                    "Alert command exited unsuccessfully"
+                );
+            }
+            CommandEnd::Exited(status)
+        }
+        Ok(None) => {
+            tracing::warn!(
+                target: "daemon.alerts",
+                rule = %name,
+                timeout_secs = limit.as_secs(),
+                "Alert command timed out and was killed"
+            );
+            CommandEnd::Killed
+        }
+        Err(err) => {
+            tracing::warn!(
+                target: "daemon.alerts",
+                rule = %name,
+                error = %err,
+                "Alert command failed"
+            );
+            CommandEnd::Failed
+        }
+    }
+}
+
+/// Start `program` and wait for it up to `limit`; `None` once it had to
+/// be killed.
+async fn run_with_limit(
+    program: &str,
+    args: &[String],
+    json: &str,
+    limit: Duration,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .env(ALERT_ENV_VAR, json)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    match tokio::time::timeout(limit, child.wait()).await {
+        Ok(status) => status.map(Some),
+        Err(_elapsed) => child.kill().await.map(|()| None),
+    }
+}
+
+/// Append `line` plus a newline to the JSONL file at `path`, creating
+/// it if needed.
+fn append_jsonl(path: &Path, line: &str) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")
+}
@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Change-feed capture for [`IndexManager`]: turns each applied journal
+//! batch into [`ChangeLogEntry`] lines for the durable
+//! [`crate::cache::change_log::ChangeLog`], and serves them back through
+//! the `tail_changes` RPC.
+//!
+//! Capture is split around the body swap in
+//! [`IndexManager::handle_journal_apply`] / `handle_journal_save`:
+//! [`IndexManager::change_feed_batch`] classifies the batch and keeps the
+//! pre-patch body (the only place a deleted file's path, or a renamed
+//! file's old path, can still be resolved) before the changes are moved
+//! into the patch task; [`IndexManager::record_change_feed`] resolves
+//! the new paths against the patched body once the swap has landed and
+//! appends on the blocking pool.  Batches that fail to apply are never
+//! logged.
+
+use alloc::sync::Arc;
+
+use uffs_client::protocol::response::{
+    ChangeLogEntry, ChangeLogKind, TailChangesParams, TailChangesResponse,
+};
+use uffs_core::compact::{DriveCompactIndex, MalformedRender};
+use uffs_mft::platform::VolumeId;
+use uffs_mft::usn::{FileChange, Usn};
+
+use super::IndexManager;
+use crate::cache::change_log::MAX_TAIL_ENTRIES;
+use crate::cache::volume_store::run_logged;
+
+/// Why a `tail_changes` request could not be answered.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ChangeFeedError {
+    /// `[change_log]` is not enabled in `daemon.toml`.
+    #[error("the change log is disabled; set `[change_log] enabled = true` in daemon.toml")]
+    Disabled,
+    /// Reading the log failed.
+    #[error("change log read failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The read task aborted.
+    #[error("change log worker failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+/// A journal batch classified for the change log, plus the pre-patch
+/// body its deleted / old paths resolve against.
+pub(super) struct PendingFeed {
+    /// `(kind, frs, usn)` per logged change, in batch order.
+    changes: Vec<(ChangeLogKind, u64, Usn)>,
+    /// The body the batch is applied to.
+    old_body: Option<Arc<DriveCompactIndex>>,
+}
+
+impl PendingFeed {
+    /// Resolve every change's paths and build its entry (sequence
+    /// numbers are stamped by the log).
+    fn into_entries(self, new_body: &DriveCompactIndex) -> Vec<ChangeLogEntry> {
+        let at_ms = crate::cache::unix_now_ms();
+        let old_body = self.old_body.as_deref();
+        self.changes
+            .into_iter()
+            .map(|(kind, frs, usn)| {
+                let before = old_body.and_then(|body| resolve(body, frs));
+                let (path, old_path) = match kind {
+                    ChangeLogKind::Deleted => (before, None),
+                    ChangeLogKind::Renamed => (resolve(new_body, frs), before),
+                    ChangeLogKind::Created | ChangeLogKind::Modified => {
+                        (resolve(new_body, frs), None)
+                    }
+                };
+                ChangeLogEntry {
+                    seq: 0,
+                    at_ms,
+                    kind,
+                    frs,
+                    path: path.unwrap_or_default(),
+                    old_path,
+                    usn: usn.raw(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl IndexManager {
+    /// Classify `changes` for the change log before they are applied.
+    /// `None` when the log is disabled.
+    pub(super) fn change_feed_batch(
+        &self,
+        changes: &[FileChange],
+        shard: &crate::cache::shard::ShardEntry,
+    ) -> Option<PendingFeed> {
+        self.change_log.as_ref()?;
+        Some(PendingFeed {
+            changes: changes.iter().filter_map(classify).collect(),
+            old_body: shard.body(),
+        })
+    }
+
+    /// Append an applied batch to `volume`'s change log.  Best-effort:
+    /// a failed write is warn-logged and the feed skips the batch.
+    pub(super) async fn record_change_feed(
+        &self,
+        volume: VolumeId,
+        pending: Option<PendingFeed>,
+        new_body: &Arc<DriveCompactIndex>,
+    ) {
+        let (Some(change_log), Some(feed)) = (self.change_log.as_ref(), pending) else {
+            return;
+        };
+        let log = Arc::clone(change_log);
+        let body = Arc::clone(new_body);
+        let _appended = run_logged(
+            volume,
+            "Change-log append failed; batch missing from the change feed",
+            move || log.append(volume, feed.into_entries(&body)),
+        )
+        .await;
+    }
+
+    /// Tell the change log that `letter`'s journal was recreated, so the
+    /// new journal's low USNs are not dropped as replays.
+    pub(crate) async fn change_feed_journal_wrapped(
+        &self,
+        letter: uffs_mft::platform::DriveLetter,
+    ) {
+        let Some(change_log) = self.change_log.as_ref() else {
+            return;
+        };
+        let Some(shard) = self.snapshot_shard_for_letter(letter).await else {
+            return;
+        };
+        let (log, volume) = (Arc::clone(change_log), shard.drive);
+        let _reset = run_logged(
+            volume,
+            "Change-log USN reset failed; changes may be taken for replays",
+            move || log.journal_wrapped(volume),
+        )
+        .await;
+    }
+
+    /// Serve a `tail_changes` request from the change log.
+    ///
+    /// # Errors
+    ///
+    /// [`ChangeFeedError::Disabled`] when `[change_log]` is off, otherwise
+    /// read failures.
+    pub(crate) async fn tail_changes(
+        &self,
+        params: &TailChangesParams,
+    ) -> Result<TailChangesResponse, ChangeFeedError> {
+        let log = Arc::clone(self.change_log.as_ref().ok_or(ChangeFeedError::Disabled)?);
+        let (volume, after_seq) = (VolumeId::from(params.drive), params.after_seq);
+        let max_entries = params.max_entries.map_or(MAX_TAIL_ENTRIES, |requested| {
+            usize::try_from(requested).unwrap_or(MAX_TAIL_ENTRIES)
+        });
+        Ok(tokio::task::spawn_blocking(move || log.tail(volume, after_seq, max_entries)).await??)
+    }
+}
+
+/// Log kind of one journal change: deletes win, then creates, renames,
+/// and size / metadata updates.  `None` for a change with no flag set.
+const fn classify(change: &FileChange) -> Option<(ChangeLogKind, u64, Usn)> {
+    let kind = if change.deleted {
+        ChangeLogKind::Deleted
+    } else if change.created {
+        ChangeLogKind::Created
+    } else if change.renamed {
+        ChangeLogKind::Renamed
+    } else if change.size_changed || change.metadata_changed {
+        ChangeLogKind::Modified
+    } else {
+        return None;
+    };
+    Some((kind, change.frs.raw(), change.usn))
+}
+
+/// Full display path of record `frs` in `body`, if it has one.
+fn resolve(body: &DriveCompactIndex, frs: u64) -> Option<String> {
+    let idx = *body.frs_to_compact.get(uffs_mft::frs_to_usize(frs))?;
+    (idx != u32::MAX).then(|| {
+        body.display_path(uffs_core::search::tree::resolve_path(
+            body,
+            uffs_mft::u32_as_usize(idx),
+            &body.volume_prefix(),
+            MalformedRender::Lossy,
+        ))
+    })
+}
@@ -0,0 +1,543 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! User-defined computed fields for the search pipeline.
+//!
+//! Definitions come from `daemon.toml` `[fields]` and, per request, from
+//! `SearchParams::computed_fields` (`--field avgsize=treesize/descendants`),
+//! the request winning on a name clash.  [`RequestFields::prepare`] lifts
+//! every use of a computed name out of the canonical request, where the
+//! built-in machinery would drop it as an unknown field:
+//!
+//! - predicates become [`ComputedFilter`]s, run in the compact scan loop;
+//! - sort clauses order the matched rows, paged by their computed keys;
+//! - projected names are evaluated onto each projected JSON row.
+//!
+//! Aggregations take the compiled set as-is (see `wire_spec.rs`).  The
+//! handler compiles once up front ([`compile`] + [`RequestFields::prepare`])
+//! so a bad definition or predicate surfaces as a JSON-RPC error.
+
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+
+use uffs_client::protocol::response::SearchRow;
+use uffs_client::protocol::{
+    SearchParams, SearchPredicate, SearchPredicateOp, SearchPredicateValue, SearchSortDirection,
+    SearchSortSpec,
+};
+use uffs_core::search::backend::DisplayRow;
+use uffs_core::search::computed::{
+    ComputedCmp, ComputedError, ComputedField, ComputedFields, ComputedFilter, FieldSource,
+    path_length,
+};
+use uffs_core::search::cursor::{CursorKey, SearchCursor, SortKeys};
+use uffs_core::search::field::{FieldId, SortDirection};
+
+use super::IndexManager;
+use crate::config::Config;
+
+/// Why a request's computed fields were rejected.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum FieldError {
+    /// A definition does not compile.
+    #[error(transparent)]
+    Definition(#[from] ComputedError),
+    /// A predicate on a computed field is not a numeric comparison.
+    #[error("predicate on computed field `{field}` must compare it with an integer")]
+    Predicate {
+        /// The computed field named.
+        field: String,
+    },
+}
+
+/// The daemon's `[fields]` followed by the request's own definitions.
+///
+/// # Errors
+///
+/// A definition that does not compile.
+pub(crate) fn compile(
+    config: &Config,
+    params: &SearchParams,
+) -> Result<ComputedFields, FieldError> {
+    let defs = config
+        .field_definitions()
+        .chain(params.computed_fields.iter().cloned());
+    Ok(ComputedFields::parse(defs)?)
+}
+
+/// One effective sort clause.
+#[derive(Debug)]
+#[expect(
+    variant_size_differences,
+    reason = "a handful per request; boxing a one-byte field id buys nothing"
+)]
+enum SortKey {
+    /// A built-in sortable column.
+    Builtin(FieldId),
+    /// A computed field.
+    Computed(Arc<ComputedField>),
+}
+
+/// A request's computed fields, split out of its canonical parameters.
+#[derive(Debug, Default)]
+pub(crate) struct RequestFields {
+    /// The compiled set, for aggregations.
+    pub(crate) fields: ComputedFields,
+    /// Predicates on computed fields.
+    pub(crate) filters: Vec<ComputedFilter>,
+    /// Every sort clause, in order, when any names a computed field.
+    sorts: Vec<(SortKey, bool)>,
+    /// Projected computed fields, in request order.
+    projected: Vec<Arc<ComputedField>>,
+    /// Clock for sort and projection values — the cursor's on a later
+    /// page, so an age-like key means the same on every page.
+    now: i64,
+}
+
+impl RequestFields {
+    /// Compile the request's fields and take its computed predicates out
+    /// of `params.predicates`.  Call after `populate_canonical_fields`.
+    ///
+    /// # Errors
+    ///
+    /// A definition that does not compile, or a computed predicate that
+    /// is not `=`, `!=`, `<`, `<=`, `>`, `>=` against an integer.
+    pub(crate) fn prepare(config: &Config, params: &mut SearchParams) -> Result<Self, FieldError> {
+        let fields = compile(config, params)?;
+        if fields.is_empty() {
+            return Ok(Self::default());
+        }
+        // One clock for the whole request, and for every later page of it.
+        let now = params
+            .search_cursor
+            .as_deref()
+            .and_then(SearchCursor::decode)
+            .and_then(|cursor| cursor.now)
+            .unwrap_or_else(uffs_core::search::computed::now);
+        let mut filters = Vec::new();
+        let mut kept = Vec::with_capacity(params.predicates.len());
+        for predicate in params.predicates.drain(..) {
+            match fields.get(&predicate.field) {
+                Some(field) => {
+                    let mut filter = filter_for(field, &predicate)?;
+                    if field.uses_now() {
+                        filter.now = now;
+                    }
+                    filters.push(filter);
+                }
+                None => kept.push(predicate),
+            }
+        }
+        params.predicates = kept;
+
+        let resolved = params.resolved_sorts();
+        let sorts = if resolved
+            .iter()
+            .any(|spec| fields.get(&spec.field).is_some())
+        {
+            resolved
+                .iter()
+                .filter_map(|spec| sort_key(&fields, spec))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut projected: Vec<Arc<ComputedField>> = Vec::new();
+        for field in params.projection.iter().filter_map(|name| fields.get(name)) {
+            if !projected.contains(field) {
+                projected.push(Arc::clone(field));
+            }
+        }
+        Ok(Self {
+            fields,
+            filters,
+            sorts,
+            projected,
+            now,
+        })
+    }
+
+    /// Whether the rows are reordered after the scan, so the backend
+    /// order means nothing and the scan must return every match.
+    pub(crate) const fn reorders(&self) -> bool {
+        !self.sorts.is_empty()
+    }
+
+    /// The sort clauses to report, with computed clauses in their place;
+    /// `builtin` is the daemon's own resolution, kept when none applies.
+    pub(crate) fn applied_sorts(&self, builtin: Vec<SearchSortSpec>) -> Vec<SearchSortSpec> {
+        if self.sorts.is_empty() {
+            return builtin;
+        }
+        self.sorts
+            .iter()
+            .map(|(key, desc)| SearchSortSpec {
+                field: match key {
+                    SortKey::Builtin(field) => field.canonical_name().to_owned(),
+                    SortKey::Computed(field) => field.name().to_owned(),
+                },
+                direction: Some(if *desc {
+                    SearchSortDirection::Desc
+                } else {
+                    SearchSortDirection::Asc
+                }),
+            })
+            .collect()
+    }
+
+    /// Names of the projected computed fields.
+    pub(crate) fn projected_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.projected.iter().map(|field| field.name().to_owned())
+    }
+
+    /// Clock the computed values are taken at.
+    pub(crate) const fn now(&self) -> i64 {
+        self.now
+    }
+
+    /// Direction of each sort clause, `true` = descending.
+    pub(crate) fn directions(&self) -> Vec<bool> {
+        self.sorts.iter().map(|(_, desc)| *desc).collect()
+    }
+
+    /// `row`'s key under each sort clause; `None` where a computed value
+    /// is undefined.
+    pub(crate) fn sort_keys(&self, row: &DisplayRow) -> SortKeys {
+        self.sorts
+            .iter()
+            .map(|(key, _)| match key {
+                SortKey::Builtin(field) => Some(CursorKey::of(row, *field)),
+                SortKey::Computed(field) => field.eval(row, self.now).map(CursorKey::Int),
+            })
+            .collect()
+    }
+
+    /// Reorder `rows` by the request's sort clauses; rows whose computed
+    /// value is undefined sort last either way.
+    pub(crate) fn sort_rows(&self, rows: &mut Vec<DisplayRow>) {
+        if self.sorts.is_empty() {
+            return;
+        }
+        let mut keyed: Vec<(SortKeys, DisplayRow)> = rows
+            .drain(..)
+            .map(|row| (self.sort_keys(&row), row))
+            .collect();
+        keyed.sort_by(|(lhs, _), (rhs, _)| {
+            lhs.iter()
+                .zip(rhs)
+                .zip(&self.sorts)
+                .map(|((left, right), (_, desc))| match (left, right) {
+                    (Some(lkey), Some(rkey)) if *desc => rkey.cmp(lkey),
+                    (Some(lkey), Some(rkey)) => lkey.cmp(rkey),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                })
+                .find(|order| order.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        rows.extend(keyed.into_iter().map(|(_, row)| row));
+    }
+
+    /// Add the projected computed values to one projected JSON row; an
+    /// undefined value projects as `null`.
+    pub(crate) fn project(
+        &self,
+        row: &SearchRow,
+        mut projected: serde_json::Map<String, serde_json::Value>,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        for field in &self.projected {
+            let value = field
+                .eval(&WireRow(row), self.now)
+                .map_or(serde_json::Value::Null, serde_json::Value::from);
+            projected.insert(field.name().to_owned(), value);
+        }
+        projected
+    }
+}
+
+impl IndexManager {
+    /// Check `params`' computed fields and predicates against the
+    /// daemon's `[fields]` without running the search.
+    ///
+    /// # Errors
+    ///
+    /// As [`RequestFields::prepare`].
+    pub(crate) fn check_computed_fields(&self, params: &SearchParams) -> Result<(), FieldError> {
+        if params.computed_fields.is_empty() && self.config.fields.is_empty() {
+            return Ok(());
+        }
+        let mut canonical = params.clone();
+        canonical.populate_canonical_fields();
+        RequestFields::prepare(&self.config, &mut canonical).map(drop)
+    }
+}
+
+/// The filter for one predicate on a computed field.
+fn filter_for(
+    field: &Arc<ComputedField>,
+    predicate: &SearchPredicate,
+) -> Result<ComputedFilter, FieldError> {
+    let op = match predicate.op {
+        SearchPredicateOp::Eq => Some(ComputedCmp::Eq),
+        SearchPredicateOp::Ne => Some(ComputedCmp::Ne),
+        SearchPredicateOp::Lt => Some(ComputedCmp::Lt),
+        SearchPredicateOp::Lte => Some(ComputedCmp::Lte),
+        SearchPredicateOp::Gt => Some(ComputedCmp::Gt),
+        SearchPredicateOp::Gte => Some(ComputedCmp::Gte),
+        SearchPredicateOp::In
+        | SearchPredicateOp::NotIn
+        | SearchPredicateOp::HasAll
+        | SearchPredicateOp::HasAny
+        | SearchPredicateOp::HasNone
+        | SearchPredicateOp::Match
+        | SearchPredicateOp::NotMatch
+        | SearchPredicateOp::Contains
+        | SearchPredicateOp::StartsWith
+        | SearchPredicateOp::EndsWith => None,
+    };
+    let value = match &predicate.value {
+        SearchPredicateValue::U64(value) => i64::try_from(*value).ok(),
+        SearchPredicateValue::I64(value) => Some(*value),
+        SearchPredicateValue::String(_)
+        | SearchPredicateValue::StringList(_)
+        | SearchPredicateValue::Bool(_) => None,
+    };
+    op.zip(value)
+        .map(|(cmp, bound)| ComputedFilter::new(Arc::clone(field), cmp, bound))
+        .ok_or_else(|| FieldError::Predicate {
+            field: field.name().to_owned(),
+        })
+}
+
+/// One clause of a sort that involves a computed field.  A computed
+/// clause without a direction sorts descending (largest first).
+fn sort_key(fields: &ComputedFields, spec: &SearchSortSpec) -> Option<(SortKey, bool)> {
+    if let Some(field) = fields.get(&spec.field) {
+        let desc = spec.direction != Some(SearchSortDirection::Asc);
+        return Some((SortKey::Computed(Arc::clone(field)), desc));
+    }
+    let field = FieldId::parse(&spec.field).filter(|field| field.metadata().sortable)?;
+    let desc = spec.direction.map_or_else(
+        || field.default_sort_direction() == Some(SortDirection::Descending),
+        |dir| dir == SearchSortDirection::Desc,
+    );
+    Some((SortKey::Builtin(field), desc))
+}
+
+/// A wire row as a computed-field source.
+struct WireRow<'row>(&'row SearchRow);
+
+impl FieldSource for WireRow<'_> {
+    #[expect(
+        clippy::wildcard_enum_match_arm,
+        reason = "fields without a numeric value make the expression undefined"
+    )]
+    fn value(&self, field: FieldId) -> Option<i64> {
+        let row = self.0;
+        let bytes = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+        let chars = |text: &str| i64::try_from(text.chars().count()).ok();
+        match field {
+            FieldId::Size => Some(bytes(row.size)),
+            FieldId::SizeOnDisk => Some(bytes(row.allocated)),
+            FieldId::TreeSize => Some(bytes(row.treesize)),
+            FieldId::TreeAllocated => Some(bytes(row.tree_allocated)),
+            FieldId::Descendants => Some(i64::from(row.descendants)),
+            FieldId::Created => Some(row.created),
+            FieldId::Modified => Some(row.modified),
+            FieldId::Accessed => Some(row.accessed),
+            FieldId::Name => chars(&row.name),
+            // Same rule as the scan: no extension for `.dotfile` or `name.`.
+            FieldId::Extension => chars(
+                row.name
+                    .rsplit_once('.')
+                    .filter(|(stem, ext)| !stem.is_empty() && !ext.is_empty())
+                    .map_or("", |(_, ext)| ext),
+            ),
+            FieldId::Path => Some(path_length(row.drive, &row.path)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uffs_core::search::cursor::keyset_page;
+
+    use super::*;
+
+    /// A request defining `slack` and using it everywhere.
+    fn request() -> SearchParams {
+        let mut params = SearchParams {
+            computed_fields: vec!["slack=allocated-size".to_owned()],
+            sorts: vec![SearchSortSpec {
+                field: "slack".to_owned(),
+                direction: None,
+            }],
+            projection: vec!["name".to_owned(), "Slack".to_owned()],
+            predicates: vec![SearchPredicate {
+                field: "slack".to_owned(),
+                op: SearchPredicateOp::Gt,
+                value: SearchPredicateValue::U64(100),
+            }],
+            ..SearchParams::default()
+        };
+        params.populate_canonical_fields();
+        params
+    }
+
+    /// A file row of `size` bytes in `allocated` on disk.
+    fn row(name: &str, size: u64, allocated: u64) -> DisplayRow {
+        DisplayRow::new(
+            0,
+            uffs_mft::platform::DriveLetter::C,
+            format!("C:\\{name}"),
+            size,
+            false,
+            0,
+            0,
+            0,
+            0,
+            allocated,
+            0,
+            0,
+            0,
+        )
+    }
+
+    #[test]
+    fn lifts_predicates_sorts_and_projection() {
+        let mut params = request();
+        let fields = RequestFields::prepare(&Config::default(), &mut params).expect("compiles");
+        assert!(
+            params.predicates.is_empty(),
+            "computed predicate lifted out"
+        );
+        assert_eq!(fields.filters.len(), 1);
+        assert!(fields.reorders());
+        assert_eq!(fields.projected_names().collect::<Vec<_>>(), ["slack"]);
+        let applied = fields.applied_sorts(Vec::new());
+        let [sort] = applied.as_slice() else {
+            panic!("one applied sort, got {applied:?}");
+        };
+        assert_eq!(sort.field, "slack");
+        assert_eq!(sort.direction, Some(SearchSortDirection::Desc));
+
+        let mut rows = vec![row("a.txt", 4000, 4096), row("b.txt", 10, 4096)];
+        fields.sort_rows(&mut rows);
+        let names: Vec<_> = rows.iter().map(DisplayRow::name).collect();
+        assert_eq!(names, ["b.txt", "a.txt"], "largest slack first");
+
+        let wire = SearchRow {
+            drive: uffs_mft::platform::DriveLetter::C.into(),
+            path: "C:\\b.txt".to_owned(),
+            name: "b.txt".to_owned(),
+            size: 10,
+            is_directory: false,
+            modified: 0,
+            created: 0,
+            accessed: 0,
+            flags: 0,
+            allocated: 4096,
+            descendants: 0,
+            treesize: 0,
+            tree_allocated: 0,
+            malformed: false,
+            malformed_path: false,
+            name_hex: None,
+            file_reference: 0,
+            content_hash: None,
+            matched_pattern: None,
+        };
+        let projected = fields.project(&wire, serde_json::Map::new());
+        assert_eq!(projected.get("slack"), Some(&serde_json::json!(4086_u64)));
+    }
+
+    #[test]
+    fn pages_computed_ties_by_identity_at_the_pinned_clock() {
+        let mut params = request();
+        let fields = RequestFields::prepare(&Config::default(), &mut params).expect("compiles");
+        let rows: Vec<DisplayRow> = (1_u64..=5)
+            .map(|frs| row(&format!("f{frs}.txt"), 96, 4096).with_file_reference(frs))
+            .collect();
+        let mut seen = Vec::new();
+        let mut cursor: Option<SearchCursor> = None;
+        loop {
+            let mut page_rows = rows.clone();
+            let page = keyset_page(
+                &mut page_rows,
+                cursor.as_ref(),
+                &fields.directions(),
+                |row| fields.sort_keys(row),
+                2,
+                1,
+            );
+            assert!(!page_rows.is_empty(), "no empty page");
+            seen.extend(page_rows.iter().map(|row| row.file_reference));
+            let Some(next) = page.next else { break };
+            cursor = Some(next);
+        }
+        assert_eq!(seen, [5, 4, 3, 2, 1], "ties walk by file reference once");
+
+        let pinned = SearchCursor {
+            index_version: 1,
+            offset: 2,
+            drive: uffs_mft::platform::DriveLetter::C.into(),
+            file_reference: 4,
+            keys: vec![Some(CursorKey::Int(0))],
+            now: Some(42),
+        };
+        let mut aged = SearchParams {
+            computed_fields: vec!["age=now-modified".to_owned()],
+            predicates: vec![SearchPredicate {
+                field: "age".to_owned(),
+                op: SearchPredicateOp::Gt,
+                value: SearchPredicateValue::U64(1),
+            }],
+            search_cursor: Some(pinned.encode()),
+            ..SearchParams::default()
+        };
+        aged.populate_canonical_fields();
+        let later = RequestFields::prepare(&Config::default(), &mut aged).expect("compiles");
+        assert_eq!(
+            later.now(),
+            42,
+            "a later page reuses the first page's clock"
+        );
+        assert!(later.filters.iter().all(|filter| filter.now == 42));
+    }
+
+    #[test]
+    fn rejects_bad_definitions_and_predicates() {
+        let mut params = SearchParams {
+            computed_fields: vec!["slack=allocated-".to_owned()],
+            ..SearchParams::default()
+        };
+        RequestFields::prepare(&Config::default(), &mut params).expect_err("dangling operator");
+
+        let mut mistyped = request();
+        for predicate in &mut mistyped.predicates {
+            predicate.value = SearchPredicateValue::String("big".to_owned());
+        }
+        assert!(matches!(
+            RequestFields::prepare(&Config::default(), &mut mistyped),
+            Err(FieldError::Predicate { .. })
+        ));
+    }
+
+    #[test]
+    fn no_fields_leaves_the_request_alone() {
+        let mut params = SearchParams {
+            predicates: vec![SearchPredicate {
+                field: "size".to_owned(),
+                op: SearchPredicateOp::Gt,
+                value: SearchPredicateValue::U64(1),
+            }],
+            ..SearchParams::default()
+        };
+        let fields = RequestFields::prepare(&Config::default(), &mut params).expect("empty");
+        assert_eq!(params.predicates.len(), 1);
+        assert!(!fields.reorders());
+        assert_eq!(fields.applied_sorts(Vec::new()), Vec::new());
+    }
+}
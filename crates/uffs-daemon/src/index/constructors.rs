@@ -40,7 +40,13 @@ use uffs_client::protocol::response::DaemonStatus;
 use uffs_core::aggregate::AggregateCache;
 
 use super::IndexManager;
+use super::alerts::AlertMonitor;
+use super::standing::StandingQueries;
 use crate::cache::ShardRegistry;
+use crate::cache::change_log::ChangeLog;
+use crate::cache::hash_store::ContentHashStore;
+use crate::cache::history::History;
+use crate::cache::snapshots::SnapshotStore;
 use crate::events::EventSender;
 
 /// Bundle of trait-object lifecycle hooks injected into
@@ -150,6 +156,11 @@ impl IndexManager {
             background_io,
             cache_cleaner,
         } = hooks;
+        let alerts = AlertMonitor::new(&config.alerts);
+        let change_log = ChangeLog::from_config(&config.change_log).map(Arc::new);
+        let history = History::from_config(&config.history).map(Arc::new);
+        let snapshots = SnapshotStore::from_config(&config.snapshots).map(Arc::new);
+        let content_hashes = ContentHashStore::from_config(&config.hash_cache).map(Arc::new);
         let cpus = std::thread::available_parallelism().map_or(4, core::num::NonZeroUsize::get);
         Self {
             index: RwLock::new(Arc::new(ShardRegistry::new())),
@@ -178,6 +189,15 @@ impl IndexManager {
             in_flight_promotes: Arc::new(StdMutex::new(std::collections::HashMap::new())),
             journal_handles: Arc::new(StdMutex::new(std::collections::HashMap::new())),
             config,
+            standing_queries: StandingQueries::default(),
+            alerts,
+            change_log,
+            history,
+            snapshots,
+            content_hashes,
+            hash_list_cache: StdMutex::new(None),
+            pattern_set_cache: StdMutex::new(None),
+            as_of_cache: StdMutex::new(None),
         }
     }
 
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Duplicate-verification I/O for [`IndexManager`]: the file reader that
+//! resolves `(record_idx, drive_ordinal)` to real files, the bridge from
+//! the verifier's [`HashStore`] seam to the daemon's
+//! [`ContentHashStore`], and the store's two other consumers — journal
+//! invalidation and the `hash` search column.
+//!
+//! Invalidation mirrors the change feed ([`super::change_feed`]):
+//! [`IndexManager::content_hash_changes`] picks the changed FRS values
+//! before the batch is moved into the patch task, and
+//! [`IndexManager::invalidate_content_hashes`] drops their digests once
+//! the swap has landed.
+
+use alloc::sync::Arc;
+
+use uffs_core::aggregate::hash_cache::{ContentKey, Digest, HashKind, HashStore, render_digest};
+use uffs_core::aggregate::verify::FileReader;
+use uffs_core::compact::{CompactRecord, DriveCompactIndex};
+use uffs_core::search::backend::DisplayRow;
+use uffs_mft::platform::VolumeId;
+use uffs_mft::usn::FileChange;
+
+use super::IndexManager;
+use crate::cache::hash_store::ContentHashStore;
+use crate::cache::volume_store::run_logged;
+
+// ── Daemon file reader for duplicate verification ───────────────────
+
+/// File reader that resolves `(record_idx, drive_ordinal)` to a file path
+/// via the compact index, then reads bytes from disk.
+///
+/// Only functional on Windows where the resolved paths (e.g. `C:\Users\...`)
+/// point to real files. On macOS/Linux (offline mode), reads always fail
+/// gracefully — verification is skipped and groups remain unverified.
+/// Members on host-qualified volumes name another machine's files and are
+/// never opened, so a hosted `srv01\C:` cannot hash this host's `C:`.
+pub(super) struct DaemonFileReader<'a> {
+    /// Loaded drive indices for path resolution.
+    pub(super) drives: &'a [Arc<DriveCompactIndex>],
+}
+
+impl DaemonFileReader<'_> {
+    /// Resolve a record to its full file path; `None` for out-of-range
+    /// ordinals and hosted volumes.
+    fn resolve_path(&self, record_idx: usize, drive_ordinal: u8) -> Option<String> {
+        let drive = self
+            .drives
+            .get(usize::from(drive_ordinal))
+            .filter(|drive| drive.volume().is_local())?;
+        let volume_prefix = format!("{}:\\", drive.letter);
+        Some(uffs_core::search::tree::resolve_path(
+            drive,
+            record_idx,
+            &volume_prefix,
+            uffs_core::compact::MalformedRender::Lossy,
+        ))
+    }
+
+    /// Open the member's file.
+    fn open(&self, record_idx: usize, drive_ordinal: u8) -> std::io::Result<std::fs::File> {
+        let path = self
+            .resolve_path(record_idx, drive_ordinal)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    "drive ordinal out of range or volume not local",
+                )
+            })?;
+        std::fs::File::open(&path)
+    }
+}
+
+impl FileReader for DaemonFileReader<'_> {
+    fn read_first_bytes(
+        &self,
+        record_idx: usize,
+        drive_ordinal: u8,
+        count: u32,
+    ) -> std::io::Result<Vec<u8>> {
+        use std::io::Read as _;
+        let mut file = self.open(record_idx, drive_ordinal)?;
+        let mut buf = vec![0_u8; uffs_mft::u32_as_usize(count)];
+        let n = file.read(&mut buf)?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
+    fn read_all(&self, record_idx: usize, drive_ordinal: u8) -> std::io::Result<Vec<u8>> {
+        use std::io::Read as _;
+        let mut buf = Vec::new();
+        self.open(record_idx, drive_ordinal)?
+            .read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_head_tail(
+        &self,
+        record_idx: usize,
+        drive_ordinal: u8,
+        chunk: u32,
+    ) -> std::io::Result<Vec<u8>> {
+        use std::io::{Read as _, Seek as _, SeekFrom};
+        let mut file = self.open(record_idx, drive_ordinal)?;
+        let len = file.metadata()?.len();
+        let chunk_len = u64::from(chunk);
+        if len <= chunk_len.saturating_mul(2) {
+            return self.read_all(record_idx, drive_ordinal);
+        }
+        let mut buf = vec![0_u8; uffs_mft::u32_as_usize(chunk).saturating_mul(2)];
+        let (head, tail) = buf.split_at_mut(uffs_mft::u32_as_usize(chunk));
+        file.read_exact(head)?;
+        file.seek(SeekFrom::Start(len - chunk_len))?;
+        file.read_exact(tail)?;
+        Ok(buf)
+    }
+}
+
+// ── Hash-store bridge ───────────────────────────────────────────────
+
+/// [`HashStore`] over the daemon's [`ContentHashStore`]: resolves a
+/// member to its volume and [`ContentKey`] through the snapshot the
+/// verifier is walking.
+pub(super) struct DriveHashes<'a> {
+    /// The persistent store.
+    pub(super) store: &'a ContentHashStore,
+    /// Snapshot the duplicate groups index into.
+    pub(super) drives: &'a [Arc<DriveCompactIndex>],
+}
+
+impl DriveHashes<'_> {
+    /// Volume and content key of a member; `None` for synthetic records,
+    /// out-of-range indices and hosted volumes, whose content is never
+    /// read here.
+    fn resolve(&self, record_idx: usize, drive_ordinal: u8) -> Option<(VolumeId, ContentKey)> {
+        let drive = self
+            .drives
+            .get(usize::from(drive_ordinal))
+            .filter(|drive| drive.volume().is_local())?;
+        let record: &CompactRecord = drive.records.as_slice().get(record_idx)?;
+        Some((drive.volume(), ContentKey::of(record)?))
+    }
+}
+
+impl HashStore for DriveHashes<'_> {
+    fn lookup(&self, record_idx: usize, drive_ordinal: u8, kind: HashKind) -> Option<Digest> {
+        let (volume, key) = self.resolve(record_idx, drive_ordinal)?;
+        self.store.lookup(volume, key, kind)
+    }
+
+    fn record(&self, record_idx: usize, drive_ordinal: u8, kind: HashKind, digest: Digest) {
+        if let Some((volume, key)) = self.resolve(record_idx, drive_ordinal) {
+            self.store.record(volume, key, kind, digest);
+        }
+    }
+}
+
+/// Persist the digests a verification pass added.  Best-effort: a failed
+/// write is warn-logged and the digests are retried on the next save.
+pub(super) fn save_content_hashes(store: &ContentHashStore) {
+    if let Err(err) = store.save() {
+        tracing::warn!(
+            target: "aggregate.verify",
+            error = %err,
+            "Content-hash cache save failed; digests kept in memory",
+        );
+    }
+}
+
+impl IndexManager {
+    /// The content-hash store, `None` when `[hash_cache]` is disabled.
+    pub(crate) fn content_hashes(&self) -> Option<&ContentHashStore> {
+        self.content_hashes.as_deref()
+    }
+
+    /// FRS values whose cached digests `changes` makes stale: every
+    /// changed file except pure renames, which keep their content.  Empty
+    /// when `[hash_cache]` is disabled.
+    pub(super) fn content_hash_changes(&self, changes: &[FileChange]) -> Vec<u64> {
+        if self.content_hashes.is_none() {
+            return Vec::new();
+        }
+        changes
+            .iter()
+            .filter(|change| {
+                change.created
+                    || change.deleted
+                    || change.size_changed
+                    || change.metadata_changed
+                    || !change.renamed
+            })
+            .map(|change| change.frs.raw())
+            .collect()
+    }
+
+    /// Drop the digests of `frs` on `volume` and persist the table when
+    /// anything was removed.
+    pub(super) async fn invalidate_content_hashes(&self, volume: VolumeId, frs: Vec<u64>) {
+        let Some(store) = self.content_hashes.as_ref().map(Arc::clone) else {
+            return;
+        };
+        if frs.is_empty() {
+            return;
+        }
+        let invalidated = run_logged(volume, "Content-hash invalidation failed", move || {
+            let removed = store.invalidate(volume, &frs);
+            if removed > 0 {
+                save_content_hashes(&store);
+            }
+            Ok(removed)
+        })
+        .await;
+        if let Some(removed) = invalidated {
+            tracing::debug!(
+                target: "shard.journal",
+                drive = %volume,
+                removed,
+                "Content-hash cache invalidated",
+            );
+        }
+    }
+
+    /// Fill the `hash` column of `rows` from the store: the strongest
+    /// full-content digest on file, rendered `<algo>:<hex>`.  Rows never
+    /// hashed (or hashed before their last change) stay empty — the
+    /// column never reads file content.
+    pub(super) fn fill_content_hashes(&self, rows: &mut [DisplayRow]) {
+        let Some(store) = self.content_hashes.as_deref() else {
+            return;
+        };
+        for row in rows.iter_mut().filter(|row| !row.is_directory) {
+            let key = ContentKey {
+                file_ref: row.file_reference,
+                size: row.size,
+                modified: row.modified,
+            };
+            if key.file_ref == 0 {
+                continue;
+            }
+            row.content_hash = store
+                .full_digest(row.drive, key)
+                .map(|(algo, digest)| render_digest(algo, &digest));
+        }
+    }
+}
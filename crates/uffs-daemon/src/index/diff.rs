@@ -17,18 +17,32 @@
 //! 3. Mark those baseline rows with the `DELETED` flag.
 //! 4. Run the normal search pipeline ([`IndexManager::run_search_over`]) over
 //!    the marked baseline, with a forced `deleted-only` filter.
+//!
+//! With `[snapshots]` or `[history]` enabled either side can be a retained
+//! point in time instead: an `@<time>` baseline replaces the capture file
+//! ([`IndexManager::baseline_at`] — a scheduled snapshot or a rebuilt
+//! history state), and `params.as_of` replaces the live index as the
+//! current side ([`IndexManager::state_as_of`]) — so any two retained
+//! moments can be diffed.
+//!
+//! `growth` aggregates ([`super::growth`]) are the one aggregate that reads
+//! both sides: they are split off before the search and compare the
+//! baseline's folder tree metrics with the current side's.
 
 use alloc::sync::Arc;
 use std::path::PathBuf;
 
-use uffs_client::protocol::SearchParams;
 use uffs_client::protocol::response::SearchResponse;
+use uffs_client::protocol::{AggregateResultWire, SearchParams};
+use uffs_core::aggregate::compute_growth;
 use uffs_core::compact::MftSource;
 use uffs_core::diff::DELETED_TOMBSTONE_FLAG;
 use uffs_core::search::backend::DriveIndex;
-use uffs_mft::platform::DriveLetter;
+use uffs_mft::platform::VolumeId;
 
 use super::IndexManager;
+use super::growth::{growth_to_wire, split_growth_specs};
+use crate::cache::history::HistoryError;
 
 /// Why a `diff` request could not be served. Mapped to a JSON-RPC error by the
 /// handler; kept data-only here so this module stays free of wire concerns.
@@ -36,7 +50,7 @@ pub(crate) enum DiffError {
     /// No `--drive` was supplied, so there is no live index to diff against.
     NoDrive,
     /// The requested drive is not currently loaded in the live index.
-    DriveNotLoaded(DriveLetter),
+    DriveNotLoaded(VolumeId),
     /// The baseline snapshot at `path` could not be loaded into a compact
     /// index.
     BaselineLoad {
@@ -45,6 +59,9 @@ pub(crate) enum DiffError {
         /// The underlying load failure.
         source: anyhow::Error,
     },
+    /// An `@<time>` baseline or `as_of` current side could not be rebuilt
+    /// from the index history.
+    History(HistoryError),
 }
 
 impl IndexManager {
@@ -55,8 +72,10 @@ impl IndexManager {
     /// # Errors
     ///
     /// [`DiffError::NoDrive`] when no drive is given,
-    /// [`DiffError::DriveNotLoaded`] when it has no live index, or
-    /// [`DiffError::BaselineLoad`] when the baseline path cannot be loaded.
+    /// [`DiffError::DriveNotLoaded`] when it has no live index (and no
+    /// `as_of`), [`DiffError::BaselineLoad`] when the baseline path cannot
+    /// be loaded, or [`DiffError::History`] when a historical side cannot
+    /// be rebuilt.
     pub(crate) async fn diff_search(
         &self,
         params: &SearchParams,
@@ -64,24 +83,45 @@ impl IndexManager {
         let drive = *params.drives.first().ok_or(DiffError::NoDrive)?;
         let baseline_path = params.diff_baseline.clone().unwrap_or_default();
 
-        // Current side: the live, hot in-memory index for the drive.
-        let snapshot = self.snapshot().await;
-        let Some(current) = snapshot
-            .drives
-            .iter()
-            .find(|dr| dr.letter == drive)
-            .map(Arc::clone)
-        else {
-            return Err(DiffError::DriveNotLoaded(drive));
+        // Current side: the drive as of `params.as_of`, else the live, hot
+        // in-memory index.
+        let current = match params.as_of.as_deref() {
+            Some(spec) => Arc::new(
+                self.state_as_of(drive, spec)
+                    .await
+                    .map_err(DiffError::History)?,
+            ),
+            None => self.live_drive(drive).await?,
+        };
+
+        // Baseline side: a retained snapshot or history state for `@<time>`.
+        let historical = match baseline_path.strip_prefix('@') {
+            Some(spec) => Some(
+                self.baseline_at(drive, spec)
+                    .await
+                    .map_err(DiffError::History)?,
+            ),
+            None => None,
         };
-        drop(snapshot);
 
-        // Load the baseline, diff it against the live index, and mark the
+        let (growth_specs, rest) = split_growth_specs(&params.aggregations);
+
+        // Load the baseline, diff it against the current side, and mark the
         // vanished rows — all off the async runtime (MFT parse + a hash-diff).
+        // Growth compares the two sides here too, while both are at hand.
         let load_path = baseline_path.clone();
+        let volume = current.volume();
         let outcome = tokio::task::spawn_blocking(move || {
-            let source = MftSource::File(PathBuf::from(&load_path), Some(drive));
-            let (mut baseline, _timing) = uffs_core::compact::load_drive(&source, true)?;
+            let mut baseline = if let Some(index) = historical {
+                index
+            } else {
+                let source = MftSource::File(PathBuf::from(&load_path), Some(volume));
+                uffs_core::compact::load_drive(&source, true)?.0
+            };
+            let growth: Vec<AggregateResultWire> = growth_specs
+                .iter()
+                .map(|spec| growth_to_wire(spec, &compute_growth(&baseline, &current, spec)))
+                .collect();
             let report = uffs_core::diff::diff_indexes(&baseline, &current);
             let records = baseline.records.as_mut_slice();
             for &idx in &report.deleted {
@@ -89,12 +129,12 @@ impl IndexManager {
                     record.flags |= DELETED_TOMBSTONE_FLAG;
                 }
             }
-            anyhow::Ok(baseline)
+            anyhow::Ok((baseline, growth))
         })
         .await;
 
-        let baseline = match outcome {
-            Ok(Ok(index)) => index,
+        let (baseline, growth) = match outcome {
+            Ok(Ok(sides)) => sides,
             Ok(Err(source)) => {
                 return Err(DiffError::BaselineLoad {
                     path: baseline_path,
@@ -114,6 +154,29 @@ impl IndexManager {
         let index = Arc::new(DriveIndex {
             drives: vec![Arc::new(baseline)],
         });
-        Ok(self.run_search_over(params, Some(index)).await)
+        if growth.is_empty() {
+            return Ok(self.run_search_over(params, Some(index)).await);
+        }
+        let search_params = SearchParams {
+            aggregations: rest,
+            ..params.clone()
+        };
+        let mut response = self.run_search_over(&search_params, Some(index)).await;
+        response.aggregations.extend(growth);
+        Ok(response)
+    }
+
+    /// The live in-memory index for `drive`.
+    async fn live_drive(
+        &self,
+        drive: VolumeId,
+    ) -> Result<Arc<uffs_core::compact::DriveCompactIndex>, DiffError> {
+        self.snapshot()
+            .await
+            .drives
+            .iter()
+            .find(|dr| drive.selects(dr.volume()))
+            .map(Arc::clone)
+            .ok_or(DiffError::DriveNotLoaded(drive))
     }
 }
@@ -96,7 +96,7 @@ impl IndexManager {
     /// case, single read-lock acquisition only.
     pub(super) async fn ensure_warm_for_dispatch(
         &self,
-        params_drives: &[uffs_mft::platform::DriveLetter],
+        params_drives: &[uffs_mft::platform::VolumeId],
         ext_terms: &[String],
     ) {
         // ── Phase 1: read-lock detection (fast path) ───────────
@@ -111,11 +111,19 @@ impl IndexManager {
         // contract).  Cold shards drop their bloom on demote, so
         // they always promote.  Empty `ext_terms` short-circuits
         // to the Phase-3 always-promote behaviour.
+        //
+        // Host-qualified shards (offline captures) are never demoted,
+        // so only local shards can need a promote; the body loader and
+        // the single-flight map stay keyed on the bare letter.
         let needs_promote: Vec<uffs_mft::platform::DriveLetter> = {
             let guard = self.index.read().await;
             guard
                 .iter()
-                .filter(|shard| params_drives.is_empty() || params_drives.contains(&shard.drive))
+                .filter(|shard| shard.drive.is_local())
+                .filter(|shard| {
+                    params_drives.is_empty()
+                        || params_drives.iter().any(|sel| sel.selects(shard.drive))
+                })
                 .filter(|shard| {
                     matches!(
                         shard.state(),
@@ -123,7 +131,7 @@ impl IndexManager {
                     )
                 })
                 .filter(|shard| Self::bloom_pre_check_should_promote(shard, ext_terms))
-                .map(|shard| shard.drive)
+                .map(|shard| shard.drive.letter())
                 .collect()
         };
         if needs_promote.is_empty() {
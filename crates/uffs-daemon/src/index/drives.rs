@@ -124,12 +124,16 @@ impl IndexManager {
                         shard.stats.set_last_known_records(records as u64);
                     }
                     DriveInfo {
-                        letter: shard.drive,
+                        letter: shard.drive.letter(),
+                        host: shard.drive.host().map(|host| host.as_str().to_owned()),
+                        serial: shard.drive.serial().map(|serial| serial.to_string()),
                         records,
                         source,
                         tier: Some(tier),
                         records_when_warm: Some(shard.stats.last_known_records()),
-                        loading: Some(loading.contains(&shard.drive)),
+                        loading: Some(
+                            shard.drive.is_local() && loading.contains(&shard.drive.letter()),
+                        ),
                     }
                 })
                 .collect()
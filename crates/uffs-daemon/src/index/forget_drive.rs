@@ -123,7 +123,7 @@ impl IndexManager {
             let found = guard
                 .iter()
                 .find(|shard| shard.drive == requested)
-                .map(|shard| (shard.drive, shard.state()));
+                .map(|shard| (shard.drive.letter(), shard.state()));
             match found {
                 Some((drive, state)) => {
                     if !force && state != ShardState::Cold {
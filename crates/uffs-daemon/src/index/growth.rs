@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `growth` aggregates for the snapshot-diff path.
+//!
+//! A growth spec compares the diff baseline with the current side, so it
+//! cannot run in [`IndexManager::run_aggregations`]' single-index scan
+//! (where [`IndexManager::convert_wire_spec_in`] rejects it).
+//! [`split_growth_specs`] pulls the growth specs out of a diff request
+//! before the rest reach the normal pipeline; `diff_search` evaluates them
+//! with [`uffs_core::aggregate::compute_growth`] and appends the results
+//! via [`growth_to_wire`].
+//!
+//! [`IndexManager::run_aggregations`]: crate::index::IndexManager
+//! [`IndexManager::convert_wire_spec_in`]: crate::index::IndexManager
+
+use uffs_client::protocol::{AggregateResultWire, AggregateSpecWire, BucketWire, GrowthWire};
+use uffs_core::aggregate::growth::GrowthRow;
+use uffs_core::aggregate::{GrowthRank, GrowthResult, GrowthSpec, ParseAggSpecError};
+
+/// Split `specs` into the growth specs and the rest. Malformed growth
+/// specs are warn-logged and dropped, like any malformed aggregate spec.
+pub(crate) fn split_growth_specs(
+    specs: &[AggregateSpecWire],
+) -> (Vec<GrowthSpec>, Vec<AggregateSpecWire>) {
+    let mut growth = Vec::new();
+    let mut rest = Vec::new();
+    for ws in specs {
+        match growth_spec(ws) {
+            Some(Ok(spec)) => growth.push(spec),
+            Some(Err(err)) => {
+                tracing::warn!(kind = %ws.kind, "skipping malformed aggregate spec: {err}");
+            }
+            None => rest.push(ws.clone()),
+        }
+    }
+    (growth, rest)
+}
+
+/// Decode a wire spec as a growth spec, or `None` when it is another
+/// kind. The structured form carries the depth and rank in their own
+/// fields; `kind: "raw"` carries the `growth:` power syntax in `label`.
+fn growth_spec(ws: &AggregateSpecWire) -> Option<Result<GrowthSpec, ParseAggSpecError>> {
+    match ws.kind.as_str() {
+        "growth" => {
+            let mut spec = GrowthSpec {
+                label: ws.label.clone(),
+                ..GrowthSpec::default()
+            };
+            if let Some(depth) = ws.depth {
+                spec.depth = depth;
+            }
+            if let Some(top) = ws.top {
+                spec.top = top;
+            }
+            if let Some(rank) = ws.rank.as_deref() {
+                match GrowthRank::parse(rank) {
+                    Some(parsed) => spec.rank = parsed,
+                    None => {
+                        return Some(Err(ParseAggSpecError::UnknownGrowthRank {
+                            val: rank.to_owned(),
+                        }));
+                    }
+                }
+            }
+            Some(Ok(spec))
+        }
+        "raw" => uffs_core::aggregate::parse_growth_spec(ws.label.as_deref()?),
+        _ => None,
+    }
+}
+
+/// Render a growth result: one bucket per folder, keyed by path, with the
+/// current tree metrics in the usual bucket slots and the comparison in
+/// [`BucketWire::growth`].
+pub(crate) fn growth_to_wire(spec: &GrowthSpec, result: &GrowthResult) -> AggregateResultWire {
+    let shown = result.rows.len();
+    AggregateResultWire {
+        label: spec.label.clone(),
+        kind: "growth".to_owned(),
+        field: Some(spec.rank.as_str().to_owned()),
+        value: None,
+        stats: None,
+        buckets: result.rows.iter().map(growth_bucket).collect(),
+        other_count: None,
+        total_groups: Some(result.total_groups),
+        next_cursor: None,
+        exact: Some(true),
+        values_complete: Some(shown == result.total_groups),
+        pivot: None,
+    }
+}
+
+/// One folder's bucket.
+fn growth_bucket(row: &GrowthRow) -> BucketWire {
+    BucketWire {
+        key: row.path.clone(),
+        count: row.after.descendants,
+        total_bytes: row.after.treesize,
+        total_allocated: Some(row.after.tree_allocated),
+        growth: Some(GrowthWire {
+            before_bytes: row.before.treesize,
+            before_allocated: row.before.tree_allocated,
+            before_descendants: row.before.descendants,
+            delta_bytes: row.delta_bytes(),
+            delta_allocated: row.delta_allocated(),
+            delta_descendants: row.delta_descendants(),
+            growth_pct: row.growth_pct(),
+            matched: row.matched.as_str().to_owned(),
+        }),
+        ..BucketWire::default()
+    }
+}
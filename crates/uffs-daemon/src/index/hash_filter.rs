@@ -0,0 +1,426 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `--hash-in` known-hash filtering for [`IndexManager`].
+//!
+//! The handler loads the list before dispatching the search
+//! ([`IndexManager::hash_list`]), so a missing or unparsable file surfaces
+//! as a JSON-RPC error; the search pipeline then runs
+//! [`IndexManager::filter_by_hash_list`] over the matched rows, ahead of
+//! paging and aggregation.  Only regular files on local volumes whose size
+//! the list allows are hashed, with just the algorithms the list uses, and
+//! every digest goes through the `[hash_cache]` store
+//! ([`super::content_hash`]) — a repeat hunt reads only what changed.
+//! Reads share duplicate verification's budget
+//! ([`super::aggregation::verify_budget`]) and stop at the scan's
+//! [`super::search::SEARCH_TIMEOUT`]; a candidate left unread is treated
+//! like an unreadable one.
+//!
+//! The parsed list is kept for the next query while its file keeps the
+//! same size and modification time: NSRL subsets run to millions of lines.
+
+use alloc::sync::Arc;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
+
+use uffs_client::protocol::HashListMode;
+use uffs_core::aggregate::hash_cache::{
+    ContentKey, Digest, HashAlgo, HashKind, HashSpan, digest_reader,
+};
+use uffs_core::aggregate::verify::VerificationBudget;
+use uffs_core::search::backend::DisplayRow;
+use uffs_core::search::hash_list::HashList;
+
+use super::IndexManager;
+use super::aggregation::verify_budget;
+use super::content_hash::save_content_hashes;
+use super::search::SEARCH_TIMEOUT;
+use crate::cache::hash_store::ContentHashStore;
+
+/// A `--hash-in` list that could not be read or held no digest.
+#[derive(Debug, thiserror::Error)]
+#[error("hash list '{path}': {source}")]
+pub(crate) struct HashListError {
+    /// The list path as requested.
+    path: String,
+    /// The read or parse failure.
+    #[source]
+    source: std::io::Error,
+}
+
+/// The most recently loaded list, reused while its file is unchanged.
+#[derive(Debug)]
+pub(crate) struct CachedHashList {
+    /// File the list was read from.
+    path: PathBuf,
+    /// `(length, mtime)` of the file when it was read.
+    stamp: (u64, Option<SystemTime>),
+    /// The parsed list.
+    list: Arc<HashList>,
+}
+
+/// What one filter pass did, for the log.
+#[derive(Debug, Default)]
+struct HashFilterStats {
+    /// Rows whose size the list allows.
+    candidates: usize,
+    /// Digests answered by the content-hash cache.
+    cache_hits: usize,
+    /// Files read and hashed.
+    hashed: usize,
+    /// Candidates that could not be read (offline captures, access denied).
+    unreadable: usize,
+    /// Candidates left unread because the budget or the deadline ran out.
+    over_budget: usize,
+}
+
+/// How much one filter pass may read.
+#[derive(Debug)]
+struct ReadLimits {
+    /// Bytes and files left to read.
+    budget: VerificationBudget,
+    /// No file is opened after this instant.
+    deadline: Instant,
+}
+
+impl ReadLimits {
+    /// The request's limits: the verification budget, from now until the
+    /// search timeout.
+    fn for_request() -> Self {
+        Self {
+            budget: verify_budget(),
+            deadline: Instant::now() + SEARCH_TIMEOUT,
+        }
+    }
+}
+
+impl IndexManager {
+    /// The list at `path`, parsed on a blocking thread or reused from the
+    /// previous query when the file has not changed.
+    ///
+    /// # Errors
+    ///
+    /// The file cannot be read, or holds no SHA-256 / SHA-1 / MD5 digest.
+    pub(crate) async fn hash_list(&self, path: &str) -> Result<Arc<HashList>, HashListError> {
+        let fail = |source| HashListError {
+            path: path.to_owned(),
+            source,
+        };
+        let file = PathBuf::from(path);
+        let meta = std::fs::metadata(&file).map_err(fail)?;
+        let stamp = (meta.len(), meta.modified().ok());
+        if let Some(cached) = self.cached_hash_list(&file, stamp) {
+            return Ok(cached);
+        }
+        let reader_path = file.clone();
+        let parsed = tokio::task::spawn_blocking(move || {
+            let reader = std::io::BufReader::new(std::fs::File::open(&reader_path)?);
+            HashList::parse(reader)
+        })
+        .await
+        .map_err(|join_err| fail(std::io::Error::other(join_err)))?
+        .map_err(fail)?;
+        let list = Arc::new(parsed);
+        tracing::info!(
+            target: "search.hash_in",
+            path,
+            digests = list.len(),
+            skipped_lines = list.skipped_lines(),
+            "Hash list loaded",
+        );
+        if let Ok(mut slot) = self.hash_list_cache.lock() {
+            *slot = Some(CachedHashList {
+                path: file,
+                stamp,
+                list: Arc::clone(&list),
+            });
+        }
+        Ok(list)
+    }
+
+    /// The cached list for `file`, when it was read at `stamp`.
+    fn cached_hash_list(
+        &self,
+        file: &std::path::Path,
+        stamp: (u64, Option<SystemTime>),
+    ) -> Option<Arc<HashList>> {
+        let slot = self.hash_list_cache.lock().ok()?;
+        slot.as_ref()
+            .filter(|cached| cached.path == file && cached.stamp == stamp)
+            .map(|cached| Arc::clone(&cached.list))
+    }
+
+    /// Apply the `--hash-in` list at `path` to `rows`: keep the listed
+    /// files (`match`) or drop them (`known_good`).
+    ///
+    /// A candidate that cannot be hashed — unreadable, or past the read
+    /// budget or the deadline — is never proven to be on the list: it is
+    /// dropped by `match` and kept by `known_good`.  A list
+    /// that has become unreadable since the handler checked it filters
+    /// as if it were empty.
+    pub(super) async fn filter_by_hash_list(
+        &self,
+        rows: Vec<DisplayRow>,
+        path: &str,
+        mode: HashListMode,
+    ) -> Vec<DisplayRow> {
+        let list = match self.hash_list(path).await {
+            Ok(list) => list,
+            Err(err) => {
+                tracing::warn!(target: "search.hash_in", error = %err, "Hash list unavailable");
+                return match mode {
+                    HashListMode::Match => Vec::new(),
+                    HashListMode::KnownGood => rows,
+                };
+            }
+        };
+        let store = self.content_hashes.as_ref().map(Arc::clone);
+        let mut limits = ReadLimits::for_request();
+        let filtered = tokio::task::spawn_blocking(move || {
+            let (kept, stats) = filter_rows(rows, &list, mode, store.as_deref(), &mut limits);
+            if stats.hashed > 0
+                && let Some(hashes) = store.as_deref()
+            {
+                save_content_hashes(hashes);
+            }
+            tracing::info!(
+                target: "search.hash_in",
+                candidates = stats.candidates,
+                cache_hits = stats.cache_hits,
+                hashed = stats.hashed,
+                unreadable = stats.unreadable,
+                over_budget = stats.over_budget,
+                kept = kept.len(),
+                "Hash list applied",
+            );
+            if stats.over_budget > 0 {
+                tracing::warn!(
+                    target: "search.hash_in",
+                    over_budget = stats.over_budget,
+                    "Hash list read budget exhausted; remaining candidates were not hashed",
+                );
+            }
+            kept
+        })
+        .await;
+        filtered.unwrap_or_else(|join_err| {
+            tracing::warn!(target: "search.hash_in", error = %join_err, "Hash filter failed");
+            Vec::new()
+        })
+    }
+}
+
+/// Keep or drop every row by whether its content is on `list`.
+fn filter_rows(
+    rows: Vec<DisplayRow>,
+    list: &HashList,
+    mode: HashListMode,
+    store: Option<&ContentHashStore>,
+    limits: &mut ReadLimits,
+) -> (Vec<DisplayRow>, HashFilterStats) {
+    let algos = list.algos();
+    let mut stats = HashFilterStats::default();
+    let kept = rows
+        .into_iter()
+        .filter(|row| {
+            let listed = if row.is_directory || !list.is_candidate_size(row.size) {
+                Some(false)
+            } else {
+                stats.candidates = stats.candidates.saturating_add(1);
+                row_digests(row, &algos, store, limits, &mut stats).map(|digests| {
+                    algos
+                        .iter()
+                        .zip(&digests)
+                        .any(|(&algo, digest)| list.contains(algo, digest))
+                })
+            };
+            match (mode, listed) {
+                (HashListMode::Match, Some(on_list)) => on_list,
+                (HashListMode::KnownGood, Some(on_list)) => !on_list,
+                (HashListMode::Match, None) => false,
+                (HashListMode::KnownGood, None) => true,
+            }
+        })
+        .collect();
+    (kept, stats)
+}
+
+/// `row`'s full-content digest for each of `algos`, from the cache where
+/// possible and otherwise from one read of the file.  `None` when the
+/// file cannot be read, or `limits` no longer allow reading it.
+fn row_digests(
+    row: &DisplayRow,
+    algos: &[HashAlgo],
+    store: Option<&ContentHashStore>,
+    limits: &mut ReadLimits,
+    stats: &mut HashFilterStats,
+) -> Option<Vec<Digest>> {
+    // Host-qualified rows name another machine's files.
+    if !row.drive.is_local() {
+        stats.unreadable = stats.unreadable.saturating_add(1);
+        return None;
+    }
+    let volume = row.drive;
+    let key = (row.file_reference != 0).then_some(ContentKey {
+        file_ref: row.file_reference,
+        size: row.size,
+        modified: row.modified,
+    });
+    let kind = |algo| HashKind::new(algo, HashSpan::Full);
+    let cached: Vec<Option<Digest>> = algos
+        .iter()
+        .map(|&algo| {
+            store
+                .zip(key)
+                .and_then(|(hashes, content)| hashes.lookup(volume, content, kind(algo)))
+        })
+        .collect();
+    let missing: Vec<HashAlgo> = algos
+        .iter()
+        .zip(&cached)
+        .filter(|(_, digest)| digest.is_none())
+        .map(|(&algo, _)| algo)
+        .collect();
+    stats.cache_hits = stats
+        .cache_hits
+        .saturating_add(algos.len().saturating_sub(missing.len()));
+    if missing.is_empty() {
+        return cached.into_iter().collect();
+    }
+    if Instant::now() >= limits.deadline || !limits.budget.can_read(row.size) {
+        stats.over_budget = stats.over_budget.saturating_add(1);
+        return None;
+    }
+    limits.budget.record_read(row.size);
+    let Ok(fresh) = std::fs::File::open(&row.path).and_then(|file| digest_reader(file, &missing))
+    else {
+        stats.unreadable = stats.unreadable.saturating_add(1);
+        return None;
+    };
+    stats.hashed = stats.hashed.saturating_add(1);
+    if let Some((hashes, content)) = store.zip(key) {
+        for (&algo, &digest) in missing.iter().zip(&fresh) {
+            hashes.record(volume, content, kind(algo), digest);
+        }
+    }
+    let mut computed = fresh.into_iter();
+    cached
+        .into_iter()
+        .map(|digest| digest.or_else(|| computed.next()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use uffs_core::compact::CompactRecord;
+    use uffs_mft::platform::DriveLetter;
+
+    use super::*;
+    use crate::cache::volume_store::VolumeRoot;
+
+    /// A local file row for `path`, `size` bytes, FRS `frs`.
+    fn file_row(path: &std::path::Path, size: u64, frs: u64) -> DisplayRow {
+        let mut row = DisplayRow::new(
+            0,
+            DriveLetter::C,
+            path.display().to_string(),
+            size,
+            false,
+            1,
+            0,
+            0,
+            0x20,
+            size,
+            0,
+            0,
+            0,
+        );
+        row.file_reference = CompactRecord::pack_file_reference(frs, 1);
+        row
+    }
+
+    #[test]
+    fn match_keeps_listed_files_and_known_good_drops_them() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let evil = dir.path().join("evil.bin");
+        let benign = dir.path().join("benign.bin");
+        let odd_size = dir.path().join("other.bin");
+        std::fs::write(&evil, b"abc").expect("write");
+        std::fs::write(&benign, b"xyz").expect("write");
+        std::fs::write(&odd_size, b"abcd").expect("write");
+        let rows = vec![
+            file_row(&evil, 3, 10),
+            file_row(&benign, 3, 11),
+            file_row(&odd_size, 4, 12),
+            file_row(&dir.path().join("gone.bin"), 3, 13),
+        ];
+        let mut list = HashList::default();
+        list.insert(HashAlgo::Sha256, HashAlgo::Sha256.digest(b"abc"), Some(3));
+        let store = ContentHashStore::new(VolumeRoot::new(dir.path().join("hashes")));
+
+        let (hits, stats) = filter_rows(
+            rows.clone(),
+            &list,
+            HashListMode::Match,
+            Some(&store),
+            &mut ReadLimits::for_request(),
+        );
+        assert_eq!(hits.len(), 1);
+        assert!(hits.iter().all(|row| row.path.ends_with("evil.bin")));
+        // The 4-byte file is ruled out by size, never read.
+        assert_eq!(stats.candidates, 3);
+        assert_eq!(stats.hashed, 2);
+        assert_eq!(stats.unreadable, 1);
+
+        // Second pass: the digests come from the cache; the missing file
+        // is kept by `known_good`, which only drops what it can prove.
+        let (rest, again) = filter_rows(
+            rows,
+            &list,
+            HashListMode::KnownGood,
+            Some(&store),
+            &mut ReadLimits::for_request(),
+        );
+        assert_eq!(again.cache_hits, 2);
+        assert_eq!(again.hashed, 0);
+        assert_eq!(rest.len(), 3);
+        assert!(rest.iter().all(|row| !row.path.ends_with("evil.bin")));
+    }
+
+    #[test]
+    fn candidates_past_the_read_budget_are_left_unread() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let first = dir.path().join("first.bin");
+        let second = dir.path().join("second.bin");
+        std::fs::write(&first, b"abc").expect("write");
+        std::fs::write(&second, b"abc").expect("write");
+        let rows = vec![file_row(&first, 3, 10), file_row(&second, 3, 11)];
+        let mut list = HashList::default();
+        list.insert(HashAlgo::Sha256, HashAlgo::Sha256.digest(b"abc"), Some(3));
+        let mut one_file = ReadLimits {
+            budget: VerificationBudget::new(0, 1),
+            deadline: Instant::now() + SEARCH_TIMEOUT,
+        };
+
+        let (hits, stats) = filter_rows(
+            rows.clone(),
+            &list,
+            HashListMode::Match,
+            None,
+            &mut one_file,
+        );
+        assert_eq!(stats.hashed, 1);
+        assert_eq!(stats.over_budget, 1);
+        assert!(matches!(hits.as_slice(), [hit] if hit.path.ends_with("first.bin")));
+
+        // Past the deadline nothing is read, and `known_good` keeps it all.
+        let mut expired = ReadLimits {
+            budget: VerificationBudget::unlimited(),
+            deadline: Instant::now(),
+        };
+        let (kept, late) = filter_rows(rows, &list, HashListMode::KnownGood, None, &mut expired);
+        assert_eq!(late.hashed, 0);
+        assert_eq!(late.over_budget, 2);
+        assert_eq!(kept.len(), 2);
+    }
+}
@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Point-in-time queries for [`IndexManager`]: records applied journal
+//! batches into the on-disk [`crate::cache::history::History`] and
+//! serves `as_of` searches from the states it can rebuild.
+//!
+//! Capture mirrors the change feed ([`super::change_feed`]):
+//! [`IndexManager::history_batch`] copies the batch and keeps the
+//! pre-patch body before the changes are moved into the patch task;
+//! [`IndexManager::record_history`] writes them on the blocking pool once
+//! the swap has landed, so a batch that fails to apply is never recorded.
+//!
+//! An `as_of` search rebuilds every target drive at the requested moment
+//! and runs the normal search pipeline over the rebuilt bodies
+//! ([`IndexManager::run_search_over`]), exactly as a snapshot diff
+//! searches its marked baseline.  The last rebuild is kept
+//! ([`CachedAsOf`]), so paging through an `as_of` result or repeating it
+//! only re-reads the delta files' batch times.
+
+use alloc::sync::Arc;
+
+use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::response::SearchResponse;
+use uffs_core::compact::DriveCompactIndex;
+use uffs_core::search::backend::DriveIndex;
+use uffs_mft::platform::VolumeId;
+use uffs_mft::usn::FileChange;
+
+use super::IndexManager;
+use crate::cache::history::{HistoryError, HistoryPoint};
+use crate::cache::volume_store::run_logged;
+
+/// A journal batch waiting to be recorded, plus the body it applies to
+/// (the checkpoint candidate).
+pub(super) struct PendingHistory {
+    /// Volume the batch belongs to.
+    volume: VolumeId,
+    /// The batch, in apply order.
+    changes: Vec<FileChange>,
+    /// The pre-patch body.
+    old_body: Arc<DriveCompactIndex>,
+}
+
+impl IndexManager {
+    /// Copy `changes` for the history before they are applied.  `None`
+    /// when `[history]` is disabled or the shard has no body.
+    pub(super) fn history_batch(
+        &self,
+        changes: &[FileChange],
+        shard: &crate::cache::shard::ShardEntry,
+    ) -> Option<PendingHistory> {
+        self.history.as_ref()?;
+        Some(PendingHistory {
+            volume: shard.drive,
+            changes: changes.to_vec(),
+            old_body: shard.body()?,
+        })
+    }
+
+    /// Record an applied batch in its volume's history.  Best-effort: a
+    /// failed write is warn-logged and the history misses the batch.
+    pub(super) async fn record_history(&self, pending: Option<PendingHistory>) {
+        let (Some(history), Some(batch)) = (self.history.as_ref(), pending) else {
+            return;
+        };
+        let store = Arc::clone(history);
+        let volume = batch.volume;
+        let at_ms = crate::cache::unix_now_ms();
+        let _recorded = run_logged(
+            volume,
+            "History record failed; batch missing from the index history",
+            move || store.record(volume, at_ms, &batch.old_body, &batch.changes),
+        )
+        .await;
+    }
+
+    /// Rebuild `volume`'s index as it stood at `spec` (see
+    /// [`uffs_core::search::filters::parse_time_point_ms`]).
+    ///
+    /// # Errors
+    ///
+    /// [`HistoryError::Disabled`] when `[history]` is off,
+    /// [`HistoryError::BadTime`] for an unparseable `spec`, otherwise the
+    /// store's rebuild failures.
+    pub(crate) async fn state_as_of(
+        &self,
+        volume: VolumeId,
+        spec: &str,
+    ) -> Result<DriveCompactIndex, HistoryError> {
+        let store = Arc::clone(self.history.as_ref().ok_or(HistoryError::Disabled)?);
+        let at_ms = history_moment(spec)?;
+        tokio::task::spawn_blocking(move || store.materialise(volume, at_ms)).await?
+    }
+
+    /// Run a point-in-time search: rebuild every target drive (the
+    /// requested ones, or all loaded drives) at `params.as_of` and search
+    /// the rebuilt bodies with the full filter/sort/output pipeline.
+    ///
+    /// # Errors
+    ///
+    /// [`HistoryError::Disabled`] when `[history]` is off,
+    /// [`HistoryError::BadTime`] for an unparseable moment, otherwise the
+    /// first drive's resolve or rebuild failure.
+    pub(crate) async fn as_of_search(
+        &self,
+        params: &SearchParams,
+    ) -> Result<SearchResponse, HistoryError> {
+        let store = Arc::clone(self.history.as_ref().ok_or(HistoryError::Disabled)?);
+        let at_ms = history_moment(params.as_of.as_deref().unwrap_or_default())?;
+        let volumes: Vec<VolumeId> = if params.drives.is_empty() {
+            self.snapshot()
+                .await
+                .drives
+                .iter()
+                .map(|drive| drive.volume())
+                .collect()
+        } else {
+            params.drives.clone()
+        };
+        let resolver = Arc::clone(&store);
+        let points = tokio::task::spawn_blocking(move || {
+            volumes
+                .into_iter()
+                .map(|volume| Ok((volume, resolver.point(volume, at_ms)?)))
+                .collect::<Result<Vec<_>, HistoryError>>()
+        })
+        .await??;
+        let index = if let Some(index) = self.cached_as_of(&points) {
+            index
+        } else {
+            let targets = points.clone();
+            let drives = tokio::task::spawn_blocking(move || {
+                targets
+                    .into_iter()
+                    .map(|(volume, point)| Ok(Arc::new(store.rebuild(volume, point)?)))
+                    .collect::<Result<Vec<_>, HistoryError>>()
+            })
+            .await??;
+            let index = Arc::new(DriveIndex { drives });
+            if let Ok(mut slot) = self.as_of_cache.lock() {
+                *slot = Some(CachedAsOf {
+                    points,
+                    index: Arc::clone(&index),
+                });
+            }
+            index
+        };
+        Ok(self.run_search_over(params, Some(index)).await)
+    }
+
+    /// The cached rebuild, when it was made from exactly `points`.
+    pub(super) fn cached_as_of(
+        &self,
+        points: &[(VolumeId, HistoryPoint)],
+    ) -> Option<Arc<DriveIndex>> {
+        let slot = self.as_of_cache.lock().ok()?;
+        slot.as_ref()
+            .filter(|cached| cached.points == points)
+            .map(|cached| Arc::clone(&cached.index))
+    }
+}
+
+/// The most recent `as_of` rebuild, reused while every target volume
+/// still resolves to the same [`HistoryPoint`].
+pub(crate) struct CachedAsOf {
+    /// Target volumes, in search order, and the points they were rebuilt
+    /// from.
+    points: Vec<(VolumeId, HistoryPoint)>,
+    /// The rebuilt bodies.
+    index: Arc<DriveIndex>,
+}
+
+/// The moment `spec` names (see
+/// [`uffs_core::search::filters::parse_time_point_ms`]), in Unix ms.
+fn history_moment(spec: &str) -> Result<u64, HistoryError> {
+    let now = uffs_core::search::filters::now_unix_micros();
+    uffs_core::search::filters::parse_time_point_ms(spec, now)
+        .ok_or_else(|| HistoryError::BadTime(spec.to_owned()))
+}
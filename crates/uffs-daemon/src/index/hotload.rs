@@ -42,7 +42,7 @@ impl IndexManager {
         // Skip if already loaded.
         {
             let snap = self.snapshot().await;
-            if snap.drives.iter().any(|dr| dr.letter == letter) {
+            if snap.drives.iter().any(|dr| dr.volume() == letter) {
                 tracing::debug!(drive = %letter, "Drive already loaded, skipping");
                 return Ok(None);
             }
@@ -175,7 +175,7 @@ impl IndexManager {
         // Atomic swap: old drive (if any) is replaced in a single pointer
         // swap — in-flight queries on the old Arc finish undisturbed, new
         // queries see the fresh data immediately.
-        self.replace_drive(letter, drive_index).await;
+        self.replace_drive(letter.into(), drive_index).await;
 
         Ok(records)
     }
@@ -232,7 +232,10 @@ impl IndexManager {
                 uffs_mft::discovery::find_best_mft_file(&drive_subdir).ok_or_else(|| {
                     anyhow::anyhow!("No MFT file found in {}", drive_subdir.display())
                 })?;
-            Ok(uffs_core::compact::MftSource::File(mft_path, Some(letter)))
+            Ok(uffs_core::compact::MftSource::File(
+                mft_path,
+                Some(letter.into()),
+            ))
         }
     }
 
@@ -8,7 +8,7 @@
 //! corresponding record's MFT-derived metadata as
 //! [`uffs_client::protocol::response::InfoResponse`].
 //!
-//! Lookup is `O(path_depth)`: we parse the drive prefix, then
+//! Lookup is `O(path_depth)`: we parse the volume prefix, then
 //! walk the [`uffs_core::compact::DriveCompactIndex::children`]
 //! adjacency list one segment at a time, matching each segment
 //! case-insensitively.  This is asymptotically better than
@@ -17,23 +17,30 @@
 //! cost of failing for partial paths and case-sensitive
 //! filesystems (the latter not relevant for NTFS).
 //!
-//! The four functions in this module form one cohesive
-//! pipeline:
+//! The functions in this module form one cohesive pipeline:
 //!
 //! 1. [`IndexManager::info`] — the async public entry point.  Snapshots the
 //!    registry, hands the snapshot to the synchronous tree-walk, and wraps the
 //!    resulting `Option<Value>` in [`InfoResponse`].
-//! 2. [`IndexManager::info_tree_lookup`] — the synchronous walker.  Drives the
-//!    parse + segment-by-segment match.
-//! 3. [`IndexManager::parse_drive_prefix`] — helper that splits `"C:\\foo"`
-//!    into `(uffs_mft::platform::DriveLetter::C, "foo")`.
-//! 4. [`IndexManager::build_info_json`] — turns a matching
+//! 2. [`IndexManager::info_tree_lookup`] — resolves the path and describes the
+//!    record.
+//! 3. [`IndexManager::resolve_info_path`] — the synchronous walker.  Finds the
+//!    path's own volume, so `C:\…` and `srv01\C:\…` never answer for each
+//!    other, and hands the segments to [`IndexManager::resolve_segments`] for
+//!    the segment-by-segment match (`C:` alone is the volume root).
+//! 4. [`IndexManager::split_info_path`] / [`IndexManager::parse_volume_prefix`]
+//!    — helpers that split `"srv01\\C:\\foo"` into `(srv01\C, ["foo"])`.
+//! 5. [`IndexManager::build_info_json`] — turns a matching
 //!    [`uffs_core::compact::CompactRecord`] into the JSON payload the response
 //!    carries.
 //!
 //! [`InfoResponse`]: uffs_client::protocol::response::InfoResponse
 
+use alloc::sync::Arc;
+
+use uffs_core::compact::DriveCompactIndex;
 use uffs_core::search::backend::DriveIndex;
+use uffs_mft::platform::VolumeId;
 
 use super::IndexManager;
 
@@ -58,84 +65,91 @@ impl IndexManager {
         }
     }
 
-    /// Fast tree-walk lookup: parse path → drive letter + segments, then
-    /// walk `children` index matching each segment case-insensitively.
-    fn info_tree_lookup(snap: &DriveIndex, file_path: &str) -> Option<serde_json::Value> {
-        // Parse "C:\Windows\System32\notepad.exe" →
-        // (uffs_mft::platform::DriveLetter::C, ["Windows", "System32",
-        // "notepad.exe"])
-        let normalized = file_path.replace('/', "\\");
-        let (drive_letter, remainder) = Self::parse_drive_prefix(&normalized)?;
+    /// Fast tree-walk lookup: resolve the path with
+    /// [`Self::resolve_info_path`] and describe the record it names.
+    pub(super) fn info_tree_lookup(
+        snap: &DriveIndex,
+        file_path: &str,
+    ) -> Option<serde_json::Value> {
+        let (drive, idx) = Self::resolve_info_path(snap, file_path)?;
+        let rec = drive.records.get(idx)?;
+        let resolved = drive.display_path(uffs_core::search::tree::resolve_path(
+            drive,
+            idx,
+            &drive.volume_prefix(),
+            uffs_core::compact::MalformedRender::Lossy,
+        ));
+        Some(Self::build_info_json(drive, rec, &resolved))
+    }
 
-        let segments: Vec<&str> = remainder
+    /// Resolve `file_path` on its own volume: the volume's index and the
+    /// record's position in it.  `C:` and `C:\` name the volume root.
+    pub(super) fn resolve_info_path<'snap>(
+        snap: &'snap DriveIndex,
+        file_path: &str,
+    ) -> Option<(&'snap Arc<DriveCompactIndex>, usize)> {
+        let (volume, segments) = Self::split_info_path(file_path)?;
+
+        // Find the volume; a path carries no serial, so it names the
+        // volume of that letter on that host.
+        let drive = snap
+            .drives
+            .iter()
+            .find(|dr| dr.volume().with_serial(volume.serial()) == volume)?;
+        let idx = Self::resolve_segments(drive, &segments)?;
+        Some((drive, idx))
+    }
+
+    /// Parse `"srv01\\C:\\Windows\\notepad.exe"` (either separator)
+    /// into `(srv01\C, ["Windows", "notepad.exe"])`.
+    pub(super) fn split_info_path(file_path: &str) -> Option<(VolumeId, Vec<String>)> {
+        let normalized = file_path.replace('/', "\\");
+        let (volume, remainder) = Self::parse_volume_prefix(&normalized)?;
+        let segments = remainder
             .split('\\')
             .filter(|seg| !seg.is_empty())
+            .map(str::to_owned)
             .collect();
+        Some((volume, segments))
+    }
+
+    /// Walk `drive`'s `children` index top-down, matching each segment
+    /// case-insensitively; no segments at all name the volume root.
+    pub(super) fn resolve_segments(
+        drive: &DriveCompactIndex,
+        segments: &[String],
+    ) -> Option<usize> {
         if segments.is_empty() {
-            return None;
+            return drive.records.iter().position(|rec| {
+                rec.parent_idx == u32::MAX && rec.frs() == uffs_mft::index::ROOT_FRS
+            });
         }
 
-        // Find the matching drive.
-        let drive = snap.drives.iter().find(|dr| dr.letter == drive_letter)?;
-
-        // Find root entries (parent_idx == u32::MAX) as starting candidates.
-        let mut candidates: Vec<u32> = Vec::new();
-        for (idx, rec) in drive.records.iter().enumerate() {
-            if rec.parent_idx == u32::MAX && rec.name_len > 0 {
-                candidates.push(uffs_mft::len_to_u32(idx));
-            }
-        }
+        // Root entries (parent_idx == u32::MAX) are the first candidates.
+        let mut candidates: Vec<u32> = drive
+            .records
+            .iter()
+            .enumerate()
+            .filter(|(_, rec)| rec.parent_idx == u32::MAX && rec.name_len > 0)
+            .map(|(idx, _)| uffs_mft::len_to_u32(idx))
+            .collect();
 
         // Walk segments top-down through the children index.
-        for (seg_idx, &segment) in segments.iter().enumerate() {
-            let seg_lower = segment.to_ascii_lowercase();
-            let is_last = seg_idx == segments.len() - 1;
-
+        for (seg_idx, segment) in segments.iter().enumerate() {
+            let is_last = seg_idx + 1 == segments.len();
             let mut next_candidates: Vec<u32> = Vec::new();
-
-            if seg_idx == 0 {
-                // First segment: match against root entries.
-                for &root_idx in &candidates {
-                    if let Some(rec) = drive.records.get(uffs_mft::u32_as_usize(root_idx)) {
-                        let name = rec.name(&drive.names);
-                        if name.to_ascii_lowercase() == seg_lower {
-                            if is_last {
-                                let volume_prefix = format!("{}:\\", drive.letter);
-                                let resolved = uffs_core::search::tree::resolve_path(
-                                    drive,
-                                    uffs_mft::u32_as_usize(root_idx),
-                                    &volume_prefix,
-                                    uffs_core::compact::MalformedRender::Lossy,
-                                );
-                                return Some(Self::build_info_json(drive, rec, &resolved));
-                            }
-                            // Collect children for next segment.
-                            next_candidates.extend_from_slice(&drive.children_of(root_idx));
-                        }
-                    }
+            for &idx in &candidates {
+                let Some(rec) = drive.records.get(uffs_mft::u32_as_usize(idx)) else {
+                    continue;
+                };
+                if !rec.name(&drive.names).eq_ignore_ascii_case(segment) {
+                    continue;
                 }
-            } else {
-                // Subsequent segments: match against children of previous matches.
-                for &child_idx in &candidates {
-                    if let Some(rec) = drive.records.get(uffs_mft::u32_as_usize(child_idx)) {
-                        let name = rec.name(&drive.names);
-                        if name.to_ascii_lowercase() == seg_lower {
-                            if is_last {
-                                let volume_prefix = format!("{}:\\", drive.letter);
-                                let resolved = uffs_core::search::tree::resolve_path(
-                                    drive,
-                                    uffs_mft::u32_as_usize(child_idx),
-                                    &volume_prefix,
-                                    uffs_core::compact::MalformedRender::Lossy,
-                                );
-                                return Some(Self::build_info_json(drive, rec, &resolved));
-                            }
-                            next_candidates.extend_from_slice(&drive.children_of(child_idx));
-                        }
-                    }
+                if is_last {
+                    return Some(uffs_mft::u32_as_usize(idx));
                 }
+                next_candidates.extend_from_slice(&drive.children_of(idx));
             }
-
             if next_candidates.is_empty() {
                 return None;
             }
@@ -145,32 +159,29 @@ impl IndexManager {
         None
     }
 
-    /// Parse `C:\...` or `c:/...` into `(drive_letter, remainder)`.
-    fn parse_drive_prefix(path: &str) -> Option<(uffs_mft::platform::DriveLetter, &str)> {
-        let mut chars = path.chars();
-        let letter_ch = chars.next()?;
-        let letter = uffs_mft::platform::DriveLetter::parse(letter_ch).ok()?;
-        if chars.next()? != ':' {
-            return None;
-        }
+    /// Parse `C:\...`, `c:/...` or `srv01\C:\...` into
+    /// `(volume, remainder)`.  A host the daemon has not loaded does not
+    /// parse.
+    fn parse_volume_prefix(path: &str) -> Option<(VolumeId, &str)> {
+        let (qualified, after_colon) = path.split_once(':')?;
+        let volume = VolumeId::parse(qualified).ok()?;
         // Skip optional separator after ':'
-        let after_colon = path.get(2..)?;
         let remainder = after_colon
             .strip_prefix('\\')
             .or_else(|| after_colon.strip_prefix('/'))
             .unwrap_or(after_colon);
-        Some((letter, remainder))
+        Some((volume, remainder))
     }
 
     /// Build the JSON value for an info response record.
     fn build_info_json(
-        drive: &uffs_core::compact::DriveCompactIndex,
+        drive: &DriveCompactIndex,
         rec: &uffs_core::compact::CompactRecord,
         resolved_path: &str,
     ) -> serde_json::Value {
         let name = rec.name(&drive.names);
         serde_json::json!({
-            "drive": drive.letter.to_string(),
+            "drive": drive.volume().to_string(),
             "path": resolved_path,
             "name": name,
             "size": rec.size,
@@ -208,8 +208,20 @@ impl IndexManager {
     /// apply the buffered batch, and Arc-swap the result into the
     /// registry.  Returns the patched body on success so the save-tick
     /// caller can hand it to the background disk-save task; the
-    /// apply-tick caller discards it.  Stops short of any disk I/O —
-    /// disk persistence is the save tick's responsibility alone.
+    /// apply-tick caller discards it.  Never writes the live compact
+    /// cache — that is the save tick's responsibility alone.
+    ///
+    /// Once the swap lands, the batch is appended to the durable change
+    /// feed when `[change_log]` is enabled
+    /// ([`IndexManager::record_change_feed`]) and to the point-in-time
+    /// history when `[history]` is ([`IndexManager::record_history`]),
+    /// the cached content digests of its changed files are dropped
+    /// ([`IndexManager::invalidate_content_hashes`]), its changed records
+    /// are handed to a background check against the standing queries
+    /// ([`IndexManager::spawn_standing_query_matches`]) so subscribers hear
+    /// about matches on the same tick that makes them searchable, and
+    /// the `daemon.toml` threshold alerts on the drive are re-evaluated
+    /// ([`IndexManager::evaluate_alerts`]).
     async fn apply_to_body(
         &self,
         letter: uffs_mft::platform::DriveLetter,
@@ -226,6 +238,10 @@ impl IndexManager {
             log_save_no_shard(letter, reason, change_count);
             return BodyApplyOutcome::Failed;
         };
+        let feed = self.change_feed_batch(&changes, &shard);
+        let history = self.history_batch(&changes, &shard);
+        let standing = self.standing_query_batch(letter, &changes, &shard);
+        let stale_hashes = self.content_hash_changes(&changes);
 
         let (new_body, stats) = match self
             .run_surgical_patch_task(&shard, letter, reason, changes)
@@ -248,6 +264,12 @@ impl IndexManager {
             return BodyApplyOutcome::Failed;
         }
 
+        self.record_change_feed(shard.drive, feed, &new_body).await;
+        self.record_history(history).await;
+        self.invalidate_content_hashes(shard.drive, stale_hashes)
+            .await;
+        self.spawn_standing_query_matches(standing, &new_body);
+        self.evaluate_alerts(letter.into()).await;
         BodyApplyOutcome::Applied(new_body)
     }
 
@@ -255,7 +277,7 @@ impl IndexManager {
     /// cloning the `Arc<ShardEntry>` so subsequent registry
     /// mutations (demote, promote, `replace_warm_body`) leave this
     /// snapshot observing the pre-tick state.
-    async fn snapshot_shard_for_letter(
+    pub(super) async fn snapshot_shard_for_letter(
         &self,
         letter: uffs_mft::platform::DriveLetter,
     ) -> Option<Arc<crate::cache::shard::ShardEntry>> {
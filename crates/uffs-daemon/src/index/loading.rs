@@ -39,7 +39,14 @@ impl IndexManager {
     ///
     /// Each MFT file is loaded on its own blocking thread via `JoinSet`.
     /// Results are collected as they complete (fastest first).
-    pub(crate) async fn load_from_data_dir(&self, mft_files: &[PathBuf], no_cache: bool) {
+    ///
+    /// A `Some` volume pins the file's identity (host-qualified captures);
+    /// `None` infers the letter from the file name.
+    pub(crate) async fn load_from_data_dir(
+        &self,
+        mft_files: &[(PathBuf, Option<uffs_mft::platform::VolumeId>)],
+        no_cache: bool,
+    ) {
         let total = mft_files.len();
         *self.status.write().await = DaemonStatus::Loading {
             drives_loaded: 0,
@@ -68,7 +75,7 @@ impl IndexManager {
     /// Spawn one blocking task per MFT file, returning the `JoinSet`
     /// the caller drains for incremental progress.
     fn spawn_data_dir_loaders(
-        mft_files: &[PathBuf],
+        mft_files: &[(PathBuf, Option<uffs_mft::platform::VolumeId>)],
         no_cache: bool,
     ) -> tokio::task::JoinSet<(
         PathBuf,
@@ -78,11 +85,10 @@ impl IndexManager {
         )>,
     )> {
         let mut join_set = tokio::task::JoinSet::new();
-        for mft_path in mft_files {
-            let path = mft_path.clone();
+        for (path, volume) in mft_files.iter().cloned() {
             tracing::info!(path = %path.display(), "Loading MFT file (parallel)");
             join_set.spawn_blocking(move || {
-                let source = uffs_core::compact::MftSource::File(path.clone(), None);
+                let source = uffs_core::compact::MftSource::File(path.clone(), volume);
                 let result = uffs_core::compact::load_drive(&source, no_cache);
                 (path, result)
             });
@@ -147,7 +153,7 @@ impl IndexManager {
         let letter = drive_index.letter;
         let records = drive_index.records.len();
         tracing::info!(
-            drive = %letter,
+            drive = %drive_index.volume(),
             records,
             mft_ms = timing.mft,
             compact_ms = timing.compact,
@@ -630,10 +636,10 @@ impl IndexManager {
     /// new one.
     pub(super) async fn add_drive(&self, drive: uffs_core::compact::DriveCompactIndex) {
         let body = Arc::new(drive);
-        let letter = body.letter;
+        let volume = body.volume();
         let now_ms = unix_now_ms();
         let mut guard = self.index.write().await;
-        // ShardRegistry::add identifies the new shard by `body.letter`
+        // ShardRegistry::add identifies the new shard by `body.volume()`
         // (its canonical case from the index payload) — callers don't
         // thread the letter separately so it can't drift.
         let new_registry = guard.add(body);
@@ -641,7 +647,7 @@ impl IndexManager {
         // mounted shard so the demote-controller's idle clock starts
         // ticking from now, not from epoch zero.  See
         // `DriveStats::mark_loaded_at` doc.
-        if let Some(shard) = new_registry.iter().find(|shard| shard.drive == letter) {
+        if let Some(shard) = new_registry.iter().find(|shard| shard.drive == volume) {
             shard.stats.mark_loaded_at(now_ms);
         }
         *guard = Arc::new(new_registry);
@@ -649,7 +655,7 @@ impl IndexManager {
         self.bump_index_version();
     }
 
-    /// Replace a drive by letter (for refresh) via atomic pointer swap.
+    /// Replace the shard of `volume` (for refresh) via atomic pointer swap.
     ///
     /// Builds a new snapshot with the old drive removed and the new one
     /// appended.  Write lock held for < 1 μs (pointer swap only).
@@ -657,16 +663,16 @@ impl IndexManager {
     /// computed against the pre-refresh snapshot.
     pub(super) async fn replace_drive(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        volume: uffs_mft::platform::VolumeId,
         new_drive: uffs_core::compact::DriveCompactIndex,
     ) {
         let body = Arc::new(new_drive);
-        let canonical = body.letter;
+        let canonical = body.volume();
         let now_ms = unix_now_ms();
         let mut guard = self.index.write().await;
         // `ShardRegistry::replace` matches case-insensitively, mirroring
         // the previous `eq_ignore_ascii_case` filter on `DriveIndex`.
-        let new_registry = guard.replace(letter, body);
+        let new_registry = guard.replace(volume, body);
         // Phase 3 Commit D — same load-timestamp seeding as add_drive.
         // The replaced shard gets a fresh `Arc<DriveStats>` (replace
         // builds a new ShardEntry), so we don't need to preserve any
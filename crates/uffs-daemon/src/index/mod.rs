@@ -11,20 +11,30 @@
 //! `mod` declarations below.
 
 mod aggregation;
+pub(crate) mod alerts;
+pub(crate) mod change_feed;
+pub(crate) mod computed;
 mod constructors;
+pub(crate) mod content_hash;
 pub(crate) mod diff;
 mod dispatch;
 mod drives;
 pub(crate) mod forget_drive;
+pub(crate) mod growth;
+pub(crate) mod hash_filter;
+pub(crate) mod history;
 mod hotload;
 mod info;
 mod journal;
 mod loading;
+pub(crate) mod name_patterns;
 mod physical_order;
 mod predicates;
 mod projection;
 mod refresh;
 pub(crate) mod search;
+pub(crate) mod snapshots;
+pub(crate) mod standing;
 mod stats;
 mod status_drives;
 mod test_helpers;
@@ -347,6 +357,34 @@ pub(crate) struct IndexManager {
     ///
     /// [`TierThresholds`]: crate::cache::policy::TierThresholds
     config: Arc<crate::config::Config>,
+    /// Standing queries (`subscribe_query`) evaluated after every
+    /// journal apply tick — see [`standing`].
+    standing_queries: standing::StandingQueries,
+    /// Hysteresis / cooldown state of the `daemon.toml` `[[alerts]]`
+    /// rules, evaluated after every journal apply and refresh — see
+    /// [`alerts`].
+    alerts: alerts::AlertMonitor,
+    /// Durable change feed every applied journal batch is appended to
+    /// (`[change_log]`); `None` when disabled — see [`change_feed`].
+    change_log: Option<Arc<crate::cache::change_log::ChangeLog>>,
+    /// Point-in-time checkpoints + journal deltas (`[history]`); `None`
+    /// when disabled — see [`history`].
+    history: Option<Arc<crate::cache::history::History>>,
+    /// Scheduled baseline snapshots (`[snapshots]`); `None` when
+    /// disabled — see [`snapshots`].
+    snapshots: Option<Arc<crate::cache::snapshots::SnapshotStore>>,
+    /// Content digests from duplicate verification (`[hash_cache]`);
+    /// `None` when disabled — see [`content_hash`].
+    content_hashes: Option<Arc<crate::cache::hash_store::ContentHashStore>>,
+    /// Last `--hash-in` list parsed, reused while its file is unchanged —
+    /// see [`hash_filter`].
+    hash_list_cache: StdMutex<Option<hash_filter::CachedHashList>>,
+    /// Last `--patterns-from` list compiled, shared by the handler's check
+    /// and the search — see [`name_patterns`].
+    pattern_set_cache: StdMutex<Option<name_patterns::CachedPatternSet>>,
+    /// Last `as_of` search's rebuilt drives, reused while they still
+    /// match the history — see [`history`].
+    as_of_cache: StdMutex<Option<history::CachedAsOf>>,
 }
 
 impl IndexManager {
@@ -551,7 +589,23 @@ impl IndexManager {
     /// Cold).  Pre-Phase-3 this matched the active-index drive list
     /// exactly; post-Phase-3 it can include shards whose body has been
     /// dropped.
+    ///
+    /// Only local volumes are listed: host-qualified shards come from
+    /// offline captures and have no live volume behind their letter.
+    /// See [`Self::loaded_volumes`] for every shard.
     pub(crate) async fn loaded_drive_letters(&self) -> Vec<uffs_mft::platform::DriveLetter> {
+        let guard = self.index.read().await;
+        guard
+            .loaded_letters()
+            .into_iter()
+            .filter(|volume| volume.is_local())
+            .map(uffs_mft::platform::VolumeId::letter)
+            .collect()
+    }
+
+    /// Return the volume of every loaded shard, local and
+    /// host-qualified, in load order.
+    pub(crate) async fn loaded_volumes(&self) -> Vec<uffs_mft::platform::VolumeId> {
         let guard = self.index.read().await;
         guard.loaded_letters()
     }
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `--patterns-from` multi-pattern name matching for the search pipeline.
+//!
+//! The client ships the list as `SearchParams::patterns`; the handler
+//! compiles it up front ([`IndexManager::pattern_set`]) so a bad entry
+//! surfaces as a JSON-RPC error, and the search reuses that compiled set
+//! for its filters ([`apply_to_filters`]) — a feed of hundreds of globs
+//! builds its automaton and `RegexSet` once per request, not twice.  An
+//! extension-only list rides the extension index like `--ext`; anything
+//! else is checked per record against the folded name.  The
+//! `matched_pattern` column is filled for the shown page only
+//! ([`fill_matched_patterns`]).
+
+use alloc::sync::Arc;
+
+use uffs_core::CoreError;
+use uffs_core::search::backend::DisplayRow;
+use uffs_core::search::filters::SearchFilters;
+use uffs_core::search::pattern_set::PatternSet;
+
+use super::IndexManager;
+
+/// The most recently compiled list, reused while a request repeats it.
+#[derive(Debug)]
+pub(crate) struct CachedPatternSet {
+    /// The entries as the request sent them.
+    patterns: Vec<String>,
+    /// The compiled set.
+    set: Arc<PatternSet>,
+}
+
+/// The compiled list, or `None` when the request carries none.
+///
+/// # Errors
+///
+/// An entry that does not compile, or a list of only blanks and comments.
+fn compile(patterns: &[String]) -> Result<Option<Arc<PatternSet>>, CoreError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    PatternSet::new(patterns).map(|set| Some(Arc::new(set)))
+}
+
+impl IndexManager {
+    /// The request's compiled list, or `None` when it carries none —
+    /// compiled on first use and reused by the next call with the same
+    /// entries (the handler's check and the search that follows it).
+    ///
+    /// # Errors
+    ///
+    /// As [`compile`].
+    pub(crate) fn pattern_set(
+        &self,
+        patterns: &[String],
+    ) -> Result<Option<Arc<PatternSet>>, CoreError> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        if let Ok(slot) = self.pattern_set_cache.lock()
+            && let Some(cached) = slot.as_ref().filter(|cached| cached.patterns == patterns)
+        {
+            return Ok(Some(Arc::clone(&cached.set)));
+        }
+        let compiled = compile(patterns)?;
+        if let Some(set) = &compiled
+            && let Ok(mut slot) = self.pattern_set_cache.lock()
+        {
+            *slot = Some(CachedPatternSet {
+                patterns: patterns.to_vec(),
+                set: Arc::clone(set),
+            });
+        }
+        Ok(compiled)
+    }
+}
+
+/// Narrow `filters` to names on `set`.
+///
+/// A list of pure `*.<ext>` globs becomes the extension filter when no
+/// other extension filter is set, so the scan stays on the extension
+/// index; otherwise it intersects with the existing filters.
+pub(super) fn apply_to_filters(filters: &mut SearchFilters, set: &Arc<PatternSet>) {
+    match set.extensions() {
+        Some(exts) if filters.extensions.is_empty() => filters.extensions = exts.to_vec(),
+        Some(_) | None => filters.name_patterns = Some(Arc::clone(set)),
+    }
+}
+
+/// Name the entry each row's file name matched.
+pub(super) fn fill_matched_patterns(rows: &mut [DisplayRow], set: &PatternSet) {
+    for row in rows {
+        row.matched_pattern = set.matching_pattern(row.name()).map(str::to_owned);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_only_lists_use_the_extension_filter() {
+        let mut filters = SearchFilters::default();
+        let exts = compile(&["*.ps1".to_owned(), "*.HTA".to_owned()])
+            .expect("valid")
+            .expect("non-empty");
+        apply_to_filters(&mut filters, &exts);
+        assert_eq!(filters.extensions, ["ps1", "hta"]);
+        assert!(filters.name_patterns.is_none());
+
+        let mixed = compile(&["*.ps1".to_owned(), "mimikatz.exe".to_owned()])
+            .expect("valid")
+            .expect("non-empty");
+        apply_to_filters(&mut filters, &mixed);
+        assert!(filters.name_patterns.is_some());
+
+        assert!(compile(&[]).expect("empty is fine").is_none());
+        compile(&[">(".to_owned()]).expect_err("bad regex");
+    }
+}
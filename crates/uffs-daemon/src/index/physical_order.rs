@@ -19,7 +19,9 @@ use uffs_client::protocol::SearchParams;
 use uffs_client::protocol::response::SearchRow;
 #[cfg(windows)]
 use uffs_core::compact::CompactRecord;
+#[cfg(windows)]
 use uffs_mft::platform::DriveLetter;
+use uffs_mft::platform::VolumeId;
 
 use super::IndexManager;
 
@@ -70,7 +72,7 @@ impl IndexManager {
     async fn reorder_impl(&self, rows: Vec<SearchRow>) -> Vec<SearchRow> {
         let drive_order = first_appearance_order(&rows);
 
-        let mut by_drive: HashMap<DriveLetter, Vec<SearchRow>> = HashMap::new();
+        let mut by_drive: HashMap<VolumeId, Vec<SearchRow>> = HashMap::new();
         for row in rows {
             by_drive.entry(row.drive).or_default().push(row);
         }
@@ -81,10 +83,16 @@ impl IndexManager {
         let device_paths: HashMap<DriveLetter, String> = self.device_paths.read().await.clone();
         let mut output = Vec::with_capacity(by_drive.values().map(Vec::len).sum());
 
-        for drive in drive_order {
-            let Some(mut group) = by_drive.remove(&drive) else {
+        for volume_id in drive_order {
+            let Some(mut group) = by_drive.remove(&volume_id) else {
                 continue;
             };
+            // Rows from an offline capture have no live volume to ask.
+            if !volume_id.is_local() {
+                output.extend(group);
+                continue;
+            }
+            let drive = volume_id.letter();
 
             let opened = device_paths.get(&drive).map_or_else(
                 || uffs_mft::VolumeHandle::open(drive),
@@ -142,7 +150,7 @@ impl IndexManager {
     not(windows),
     expect(dead_code, reason = "only consumed by the Windows reorder_impl")
 )]
-fn first_appearance_order(rows: &[SearchRow]) -> Vec<DriveLetter> {
+fn first_appearance_order(rows: &[SearchRow]) -> Vec<VolumeId> {
     let mut seen = Vec::new();
     for row in rows {
         if !seen.contains(&row.drive) {
@@ -67,6 +67,7 @@ impl IndexManager {
                 // Name: NotMatch compiled into exclude_lower glob.
                 FieldId::Name => predicate.op == SearchPredicateOp::NotMatch,
                 FieldId::Drive
+                | FieldId::Host
                 | FieldId::Path
                 | FieldId::PathOnly
                 | FieldId::SizeOnDisk
@@ -99,7 +100,10 @@ impl IndexManager {
                 // parent chain) → always post-filter; `name_hex` is
                 // projection-only and never appears as a predicate.
                 | FieldId::MalformedPath
-                | FieldId::NameHex => false,
+                | FieldId::NameHex
+                // `hash` and `matched_pattern` are projection-only as well.
+                | FieldId::Hash
+                | FieldId::MatchedPattern => false,
                 // Length predicates are compiled into hot-path min/max filters.
                 FieldId::NameLength | FieldId::PathLength => {
                     matches!(
@@ -228,9 +232,12 @@ impl IndexManager {
                         let now_us = uffs_core::search::filters::now_unix_micros();
                         let is_newer =
                             matches!(predicate.op, SearchPredicateOp::Gte | SearchPredicateOp::Gt);
-                        if let Some(bound) =
-                            uffs_core::search::filters::parse_time_bound(spec, now_us, is_newer)
-                        {
+                        if let Some(bound) = uffs_core::search::filters::parse_time_bound_in(
+                            spec,
+                            now_us,
+                            is_newer,
+                            filters.time_zone,
+                        ) {
                             match (field, &predicate.op) {
                                 (FieldId::Modified, SearchPredicateOp::Gte) => {
                                     let merged =
@@ -461,7 +468,8 @@ impl IndexManager {
             FieldId::PathOnly => Self::match_string(row.path_dir(), predicate),
             FieldId::Path => Self::match_string(&row.path, predicate),
             FieldId::Name => Self::match_string(row.name(), predicate),
-            FieldId::Drive => Self::match_string(&row.drive.to_string(), predicate),
+            FieldId::Drive => Self::match_string(row.drive.letter().as_str(), predicate),
+            FieldId::Host => Self::match_string(row.drive.host_str(), predicate),
             FieldId::Extension => Self::match_string(
                 row.name().rsplit_once('.').map_or("", |(_, ext)| ext),
                 predicate,
@@ -517,7 +525,9 @@ impl IndexManager {
             // filterable, so any predicate on it is a no-op (matches all).
             FieldId::Malformed => Self::match_bool(row.malformed, predicate),
             FieldId::MalformedPath => Self::match_bool(row.malformed_path, predicate),
-            FieldId::NameHex => true,
+            // `hash` and `matched_pattern` are projection-only for the same
+            // reason.
+            FieldId::NameHex | FieldId::Hash | FieldId::MatchedPattern => true,
         }
     }
 
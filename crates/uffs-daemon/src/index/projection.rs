@@ -14,10 +14,6 @@ impl IndexManager {
     // ── Private helpers ─────────────────────────────────────────────
 
     /// Convert a [`DisplayRow`] to a protocol [`SearchRow`].
-    #[expect(
-        clippy::single_call_fn,
-        reason = "type-conversion helper — clarity over inlining"
-    )]
     pub(crate) fn display_row_to_search_row(row: &DisplayRow) -> SearchRow {
         SearchRow {
             drive: row.drive,
@@ -39,6 +35,8 @@ impl IndexManager {
             malformed_path: row.malformed_path,
             name_hex: row.name_hex.clone(),
             file_reference: row.file_reference,
+            content_hash: row.content_hash.clone(),
+            matched_pattern: row.matched_pattern.clone(),
         }
     }
 
@@ -142,7 +140,8 @@ impl IndexManager {
     )]
     pub(crate) fn projected_value(row: &SearchRow, field: FieldId) -> serde_json::Value {
         match field {
-            FieldId::Drive => serde_json::Value::String(row.drive.to_string()),
+            FieldId::Drive => serde_json::Value::String(row.drive.letter().to_string()),
+            FieldId::Host => serde_json::Value::String(row.drive.host_str().to_owned()),
             FieldId::Path => serde_json::Value::String(row.path.clone()),
             FieldId::Name => serde_json::Value::String(row.name.clone()),
             FieldId::PathOnly => serde_json::Value::String(
@@ -199,6 +198,14 @@ impl IndexManager {
                 .name_hex
                 .clone()
                 .map_or(serde_json::Value::Null, serde_json::Value::String),
+            FieldId::Hash => row
+                .content_hash
+                .clone()
+                .map_or(serde_json::Value::Null, serde_json::Value::String),
+            FieldId::MatchedPattern => row
+                .matched_pattern
+                .clone()
+                .map_or(serde_json::Value::Null, serde_json::Value::String),
         }
     }
 }
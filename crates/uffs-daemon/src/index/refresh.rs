@@ -3,9 +3,10 @@
 
 //! Drive-refresh path for [`IndexManager`].
 //!
-//! On a `refresh` RPC the daemon walks the requested drive list
-//! sequentially, reloads each drive's MFT (live on Windows or the
-//! original `.mft` snapshot on Mac/Linux) on a blocking thread,
+//! On a `refresh` RPC the daemon walks the loaded volumes the request
+//! selects (a bare letter selects that letter on every host, see
+//! [`VolumeId::selects`]) sequentially, reloads each drive's MFT (live on
+//! Windows or the original `.mft` snapshot on Mac/Linux) on a blocking thread,
 //! and atomically swaps the new compact index into the registry
 //! via [`IndexManager::replace_drive`].
 //!
@@ -27,19 +28,33 @@
 //! on-disk `.mft` snapshot reloadable from disk on any platform.
 
 use uffs_client::protocol::response::DaemonStatus;
+use uffs_mft::platform::VolumeId;
 
 use super::{IndexManager, release_allocator_pages};
 use crate::events::DaemonEvent;
 
 impl IndexManager {
-    /// Refresh specific drives (or all if empty).
-    pub(crate) async fn refresh(&self, drives: &[uffs_mft::platform::DriveLetter]) {
-        let drives_to_refresh: Vec<uffs_mft::platform::DriveLetter> = if drives.is_empty() {
+    /// Refresh the loaded volumes `selectors` select (or all if empty).
+    pub(crate) async fn refresh(&self, selectors: &[VolumeId]) {
+        let drives_to_refresh: Vec<VolumeId> = {
             let snap = self.snapshot().await;
-            snap.drives.iter().map(|dr| dr.letter).collect()
-        } else {
-            drives.to_vec()
+            snap.drives
+                .iter()
+                .map(|dr| dr.volume())
+                .filter(|&volume| {
+                    selectors.is_empty()
+                        || selectors.iter().any(|selector| selector.selects(volume))
+                })
+                .collect()
         };
+        for selector in selectors {
+            if !drives_to_refresh
+                .iter()
+                .any(|&volume| selector.selects(volume))
+            {
+                tracing::warn!(drive = %selector, "Drive not found for refresh");
+            }
+        }
 
         self.events.emit(DaemonEvent::RefreshStarted {
             drives: drives_to_refresh.clone(),
@@ -55,8 +70,8 @@ impl IndexManager {
         // happens inside the helper after every per-drive cycle so a
         // long refresh list doesn't accumulate freed-but-not-decommitted
         // pages.
-        for &letter in &drives_to_refresh {
-            self.refresh_one_drive(letter).await;
+        for &volume in &drives_to_refresh {
+            self.refresh_one_drive(volume).await;
         }
 
         self.set_ready().await;
@@ -72,9 +87,9 @@ impl IndexManager {
     /// shared index on success, and traces the outcome of every arm
     /// of the resulting `Result<Result<_, _>, JoinError>`.  Caller
     /// holds no locks across the await points.
-    async fn refresh_one_drive(&self, letter: uffs_mft::platform::DriveLetter) {
-        let Some(source) = self.lookup_drive_source(letter).await else {
-            tracing::warn!(drive = %letter, "Drive not found for refresh");
+    async fn refresh_one_drive(&self, volume: VolumeId) {
+        let Some(source) = self.lookup_drive_source(volume).await else {
+            tracing::warn!(drive = %volume, "Drive not found for refresh");
             return;
         };
 
@@ -83,13 +98,13 @@ impl IndexManager {
                 if Self::is_live_drive_marker(mft_path) && !Self::live_refresh_supported() {
                     return Err(anyhow::anyhow!("Cannot refresh live drive on non-Windows"));
                 }
-                let mft_source = Self::resolve_refresh_mft_source(mft_path, letter);
+                let mft_source = Self::resolve_refresh_mft_source(mft_path, volume);
                 uffs_core::compact::load_drive(&mft_source, false)
             }
         })
         .await;
 
-        self.apply_refresh_result(letter, result).await;
+        self.apply_refresh_result(volume, result).await;
 
         // Reclaim pages freed by the old drive index and MftIndex temporaries.
         release_allocator_pages();
@@ -102,7 +117,7 @@ impl IndexManager {
     /// error arm emits the matching error trace.
     async fn apply_refresh_result(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        volume: VolumeId,
         result: Result<
             anyhow::Result<(
                 uffs_core::compact::DriveCompactIndex,
@@ -113,13 +128,13 @@ impl IndexManager {
     ) {
         match result {
             Ok(Ok((new_drive, timing))) => {
-                self.apply_refresh_success(letter, new_drive, &timing).await;
+                self.apply_refresh_success(volume, new_drive, &timing).await;
             }
             Ok(Err(refresh_err)) => {
-                tracing::error!(drive = %letter, error = %refresh_err, "Failed to refresh drive");
+                tracing::error!(drive = %volume, error = %refresh_err, "Failed to refresh drive");
             }
             Err(join_err) => {
-                tracing::error!(drive = %letter, error = %join_err, "Task panicked during refresh");
+                tracing::error!(drive = %volume, error = %join_err, "Task panicked during refresh");
             }
         }
     }
@@ -131,29 +146,30 @@ impl IndexManager {
     /// the await.
     async fn lookup_drive_source(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        volume: VolumeId,
     ) -> Option<uffs_core::compact::IndexSource> {
         let snap = self.snapshot().await;
         snap.drives
             .iter()
-            .find(|dr| dr.letter == letter)
+            .find(|dr| dr.volume() == volume)
             .map(|dr| dr.source.clone())
     }
 
     /// Successful-refresh fanout: hot-swap the drive in the shared
     /// snapshot, trace the new record count + per-stage timings, and
     /// emit `DriveRefreshed` so subscribers (TUI, daemon-events RPC)
-    /// stay in lockstep with the index.
+    /// stay in lockstep with the index, then re-evaluate the drive's
+    /// threshold alerts.
     async fn apply_refresh_success(
         &self,
-        letter: uffs_mft::platform::DriveLetter,
+        volume: VolumeId,
         new_drive: uffs_core::compact::DriveCompactIndex,
         timing: &uffs_core::compact::LoadTiming,
     ) {
         let records = new_drive.records.len();
-        self.replace_drive(letter, new_drive).await;
+        self.replace_drive(volume, new_drive).await;
         tracing::info!(
-            drive = %letter,
+            drive = %volume,
             records,
             mft_ms = timing.mft,
             compact_ms = timing.compact,
@@ -161,12 +177,13 @@ impl IndexManager {
             "Drive refreshed"
         );
         self.events.emit(DaemonEvent::DriveRefreshed {
-            drive: letter,
+            drive: volume,
             records,
             mft_ms: timing.mft,
             compact_ms: timing.compact,
             trigram_ms: timing.trigram,
         });
+        self.evaluate_alerts(volume).await;
     }
 
     /// Map a cached drive's recorded MFT source path back to a
@@ -181,12 +198,12 @@ impl IndexManager {
     /// [`MftSource`]: uffs_core::compact::MftSource
     fn resolve_refresh_mft_source(
         mft_path: &std::path::Path,
-        letter: uffs_mft::platform::DriveLetter,
+        volume: VolumeId,
     ) -> uffs_core::compact::MftSource {
         if Self::is_live_drive_marker(mft_path) {
             #[cfg(windows)]
             {
-                uffs_core::compact::MftSource::Live(letter)
+                uffs_core::compact::MftSource::Live(volume.letter())
             }
             #[cfg(not(windows))]
             {
@@ -194,10 +211,10 @@ impl IndexManager {
                 // we only reach it on Windows; the non-Windows
                 // construction here is unreachable but kept so the
                 // function remains total without a `Result` wrapper.
-                uffs_core::compact::MftSource::File(mft_path.to_path_buf(), Some(letter))
+                uffs_core::compact::MftSource::File(mft_path.to_path_buf(), Some(volume))
             }
         } else {
-            uffs_core::compact::MftSource::File(mft_path.to_path_buf(), Some(letter))
+            uffs_core::compact::MftSource::File(mft_path.to_path_buf(), Some(volume))
         }
     }
 
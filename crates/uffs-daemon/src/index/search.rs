@@ -9,39 +9,41 @@
     reason = "search handler: Arc clones for task boundaries, readable conditionals"
 )]
 
-//! Search execution: query dispatch, profile construction, and drive info.
+//! Search execution: query dispatch and drive info (profile construction
+//! lives in `search_profile.rs`).
 
 use alloc::sync::Arc;
 use core::sync::atomic::Ordering;
 use std::time::Instant;
 
-use uffs_client::protocol::response::{
-    DriveProfile, SearchPayload, SearchProfile, SearchResponse, SearchRow,
-};
-use uffs_client::protocol::{SearchFilterMode, SearchParams, SearchResponseMode};
-use uffs_core::search::backend::{
-    DriveIndex, FilterMode, PhaseTimings, SearchRequest, SortSpec, search_index,
-};
+use uffs_client::protocol::response::{SearchPayload, SearchResponse, SearchRow};
+use uffs_client::protocol::{SearchParams, SearchResponseMode};
+use uffs_core::search::backend::{DriveIndex, FilterMode, SearchRequest, SortSpec, search_index};
 use uffs_core::search::field::FieldId;
-use uffs_core::search::filters::{SearchFilterParams, SearchFilters};
 
-use super::IndexManager;
+use super::computed::RequestFields;
+use super::{IndexManager, name_patterns};
+
+/// How long one request's scan — and its `--hash-in` reads — may run.
+pub(super) const SEARCH_TIMEOUT: core::time::Duration = core::time::Duration::from_secs(30);
 
 impl IndexManager {
     /// Execute a live search query over the registry snapshot (updates perf
-    /// counters). Snapshot-diff searches (`params.diff_baseline`) are routed by
-    /// the handler to [`Self::diff_search`] instead, so they can surface setup
-    /// errors (missing baseline / unloaded drive) as JSON-RPC errors.
+    /// counters). Snapshot-diff (`params.diff_baseline`) and point-in-time
+    /// (`params.as_of`) searches are routed by the handler to
+    /// [`Self::diff_search`] / [`Self::as_of_search`] instead, so they can
+    /// surface setup errors (missing baseline / unloaded drive / no retained
+    /// state) as JSON-RPC errors.
     pub(crate) async fn search(&self, params: &SearchParams) -> SearchResponse {
         self.run_search_over(params, None).await
     }
 
     /// Run the search pipeline over either the live registry snapshot
     /// (`snapshot_override == None`) or a caller-supplied index — the marked
-    /// baseline built by [`Self::diff_search`]. When an override is present the
-    /// query is a snapshot diff: the `deleted-only` filter is forced on and the
-    /// registry-specific warm-up / dispatch-accounting is skipped (the baseline
-    /// is not a registry shard).
+    /// baseline built by [`Self::diff_search`], or the historical bodies built
+    /// by [`Self::as_of_search`]. With an override the registry-specific
+    /// warm-up / dispatch-accounting is skipped (the index is not a registry
+    /// shard), and for a snapshot diff the `deleted-only` filter is forced on.
     ///
     /// When `params.profile` is `true`, populates `SearchResponse::profile`
     /// with a per-phase timing breakdown so the CLI can print it.
@@ -58,7 +60,7 @@ impl IndexManager {
         params: &SearchParams,
         snapshot_override: Option<Arc<DriveIndex>>,
     ) -> SearchResponse {
-        let is_diff = snapshot_override.is_some();
+        let is_override = snapshot_override.is_some();
         // Acquire a concurrency permit — blocks if too many searches
         // are already in flight.  The effective cap is
         // `max(2, (cpus × 26) / (drives × 10))` by default (see
@@ -80,11 +82,21 @@ impl IndexManager {
         let profiling = params.profile;
         let mut effective_params = params.clone();
         effective_params.populate_canonical_fields();
-        let applied_sorts = Self::resolve_applied_sorts(&effective_params);
+        // Computed-field predicates, sorts and columns are lifted out before
+        // the built-in resolution below would drop them as unknown fields.
+        let computed = match RequestFields::prepare(&self.config, &mut effective_params) {
+            Ok(computed) => computed,
+            Err(err) => {
+                tracing::warn!(error = %err, "computed fields rejected");
+                return empty_response(0, None);
+            }
+        };
+        let applied_sorts = computed.applied_sorts(Self::resolve_applied_sorts(&effective_params));
         let projection_fields = Self::resolve_projection_fields(&effective_params.projection);
         let applied_projection: Vec<String> = projection_fields
             .iter()
             .map(|field| field.canonical_name().to_owned())
+            .chain(computed.projected_names())
             .collect();
         let response_mode = effective_params.resolved_response_mode();
         let requires_post_filter =
@@ -111,57 +123,20 @@ impl IndexManager {
                     (column, descending, extra)
                 });
 
-        let filter_mode = match effective_params.resolved_filter_mode() {
-            SearchFilterMode::Files => FilterMode::FilesOnly,
-            SearchFilterMode::Dirs => FilterMode::DirsOnly,
-            SearchFilterMode::All => FilterMode::All,
+        let filter_mode = request_filter_mode(&effective_params);
+        // `--patterns-from`: the handler has already rejected a list that
+        // does not compile, so a failure here means no name can match.
+        let (mut filters, name_patterns) = match self.record_filters(&effective_params, &computed) {
+            Ok(built) => built,
+            Err(err) => {
+                tracing::warn!(error = %err, "pattern list rejected");
+                return empty_response(0, None);
+            }
         };
 
-        let ep = &effective_params;
-        let mut filters = SearchFilters::from_params(&SearchFilterParams {
-            hide_system: ep.hide_system,
-            hide_ads: ep.hide_ads,
-            min_size: ep.min_size,
-            max_size: ep.max_size,
-            min_descendants: ep.min_descendants,
-            max_descendants: ep.max_descendants,
-            newer: ep.newer.as_deref(),
-            older: ep.older.as_deref(),
-            newer_created: ep.newer_created.as_deref(),
-            older_created: ep.older_created.as_deref(),
-            newer_accessed: ep.newer_accessed.as_deref(),
-            older_accessed: ep.older_accessed.as_deref(),
-            attr_filter: ep.attr.as_deref(),
-            ext_filter: ep.ext.as_deref(),
-            exclude: ep.exclude.as_deref(),
-            path_contains: ep.path_contains.as_deref(),
-            path_excludes: ep.path_excludes.as_deref(),
-            type_filter: ep.type_filter.as_deref(),
-            min_bulkiness: ep.min_bulkiness,
-            max_bulkiness: ep.max_bulkiness,
-            min_name_len: ep.min_name_len,
-            max_name_len: ep.max_name_len,
-            min_path_len: ep.min_path_len,
-            max_path_len: ep.max_path_len,
-            min_allocated: ep.min_allocated,
-            max_allocated: ep.max_allocated,
-            min_treesize: ep.min_treesize,
-            max_treesize: ep.max_treesize,
-            min_tree_allocated: ep.min_tree_allocated,
-            max_tree_allocated: ep.max_tree_allocated,
-            allowed_months: &ep.allowed_months,
-        });
-        // Display-only: select the malformed-name render mode for resolved
-        // paths + the name column (`--normalize-malformed`).
-        filters.normalize_malformed = ep.normalize_malformed;
-
-        // Overlay canonical predicates that can be compiled into the hot
-        // path (size / descendant bounds).
-        Self::compile_predicates_into_filters(&mut filters, &effective_params.predicates);
-
         // Snapshot-diff: the override index carries the baseline with its
         // vanished rows pre-marked `DELETED`; restrict the search to those.
-        if is_diff {
+        if is_override && effective_params.diff_baseline.is_some() {
             filters.deleted = Some(true);
         }
 
@@ -180,13 +155,13 @@ impl IndexManager {
         // skip the promote (zero-RAM-touch contract).  Empty
         // `ext_terms` short-circuits to the Phase-3 always-promote
         // behaviour.
-        // Registry warm-up only applies to live shards; a diff searches the
-        // caller's baseline index, which is not in the registry.
+        // Registry warm-up only applies to live shards; a diff or as-of search
+        // runs over the caller's index, which is not in the registry.
         // Timed unconditionally, not just under `--profile`: paging a
         // cold index back in is the largest cost a query can incur, and
         // a client that cannot see it reads `duration_ms: 1` and
         // concludes the search was instant.
-        let promotion_ms = if is_diff {
+        let promotion_ms = if is_override {
             0
         } else {
             let t_promote = Instant::now();
@@ -205,8 +180,8 @@ impl IndexManager {
         // active shard so `DriveStats::decay_ema` (consumed by Phase 6
         // adaptive-TTL) accumulates a real signal.  See
         // `crate::cache::DriveStats` and the `record_search_dispatch`
-        // doc comment.  Skipped for a diff (the baseline is not a shard).
-        if !is_diff {
+        // doc comment.  Skipped for an override (it is not a shard).
+        if !is_override {
             self.record_search_dispatch().await;
         }
         let lock_us = t_lock.map_or(0, |ts| ts.elapsed().as_micros());
@@ -253,14 +228,27 @@ impl IndexManager {
         // before `filters` moves into the search closure.
         let agg_search_filters = filters.clone();
 
+        let paging = CursorPaging::new(
+            &effective_params,
+            sort_column,
+            sort_desc,
+            &extra_sort_tiers,
+            self.index_version.load(Ordering::Relaxed),
+        )
+        .by_computed(computed.reorders());
+        paging.bind(&mut filters, agg_over_rows);
         let search_limit = resolve_search_limit(
             // Aggregating over the row set needs EVERY matching row, not
             // the display limit's worth — a truncated set would silently
-            // undercount.
-            requires_post_filter || agg_over_rows,
+            // undercount.  A `--hash-in` list drops rows after the scan
+            // just as a post-filter does.
+            requires_post_filter
+                || agg_over_rows
+                || effective_params.hash_in.is_some()
+                || computed.reorders(),
             filters.needs_display_row_filter(),
             filters.malformed == Some(true),
-            effective_params.limit,
+            paging.scan_limit(effective_params.limit),
         );
 
         // ── Execute search on a blocking thread with timeout ────────
@@ -271,6 +259,12 @@ impl IndexManager {
         let case_sensitive = effective_params.case_sensitive;
         let whole_word = effective_params.whole_word;
         let match_path = effective_params.match_path;
+        let fuzzy = effective_params.fuzzy.then(|| {
+            effective_params
+                .fuzzy_threshold
+                .unwrap_or(uffs_core::search::fuzzy::DEFAULT_THRESHOLD)
+        });
+        let rank = effective_params.rank;
         let drives = effective_params.drives.clone();
         let agg_snapshot = snapshot.clone();
         let search_handle = tokio::task::spawn_blocking(move || {
@@ -285,6 +279,8 @@ impl IndexManager {
                     filter_mode,
                     search_filters: &mut filters,
                     drives_filter: &drives,
+                    fuzzy,
+                    rank,
                 },
                 sort_column,
                 sort_desc,
@@ -292,8 +288,7 @@ impl IndexManager {
             )
         });
 
-        let search_outcome =
-            tokio::time::timeout(core::time::Duration::from_secs(30), search_handle).await;
+        let search_outcome = tokio::time::timeout(SEARCH_TIMEOUT, search_handle).await;
 
         let result = match search_outcome {
             Ok(Ok(res)) => res,
@@ -329,12 +324,16 @@ impl IndexManager {
         if requires_post_filter {
             filtered_rows.retain(|row| Self::matches_predicates(row, &effective_params.predicates));
         }
+        if let Some(path) = effective_params.hash_in.as_deref() {
+            let mode = effective_params.hash_mode.unwrap_or_default();
+            filtered_rows = self.filter_by_hash_list(filtered_rows, path, mode).await;
+        }
 
         let mut total_count = filtered_rows.len() as u64;
         // Snapshot the matched set for row-fed aggregation BEFORE the
         // display truncation below — the display limit bounds what the
         // user sees, never what an aggregation counts.
-        let agg_row_set: Vec<(uffs_mft::platform::DriveLetter, u32)> = if agg_over_rows {
+        let agg_row_set: Vec<(uffs_mft::platform::VolumeId, u32)> = if agg_over_rows {
             filtered_rows
                 .iter()
                 .map(|row| (row.drive, row.record_index))
@@ -342,8 +341,19 @@ impl IndexManager {
         } else {
             Vec::new()
         };
-        if let Some(limit) = effective_params.limit {
-            filtered_rows.truncate(limit as usize);
+        let (next_search_cursor, cursor_index_changed) =
+            paging.apply(&mut filtered_rows, effective_params.limit, &computed);
+        // `hash` is read from the content-hash cache, once per shown row
+        // and only when projected — before either output path below.
+        if projection_fields.contains(&FieldId::Hash) {
+            self.fill_content_hashes(&mut filtered_rows);
+        }
+        // `matched_pattern` rides every full row, and a projected one.
+        if let Some(set) = &name_patterns
+            && (projection_fields.is_empty()
+                || projection_fields.contains(&FieldId::MatchedPattern))
+        {
+            name_patterns::fill_matched_patterns(&mut filtered_rows, set);
         }
 
         // Per-drive match counts for `--profile`.  Computed once here
@@ -363,7 +373,7 @@ impl IndexManager {
             let mut tally: std::collections::HashMap<uffs_mft::platform::DriveLetter, usize> =
                 std::collections::HashMap::with_capacity(drive_info.len().max(1));
             for row in &filtered_rows {
-                *tally.entry(row.drive).or_insert(0) += 1;
+                *tally.entry(row.drive.letter()).or_insert(0) += 1;
             }
             // Project back to the `drive_info` ordering so callers see
             // an entry for every mounted drive (0 counts included),
@@ -450,6 +460,8 @@ impl IndexManager {
                         duration_ms,
                         promotion_ms: Some(promotion_ms),
                         truncated: false,
+                        next_search_cursor: None,
+                        cursor_index_changed: false,
                         profile,
                         applied_sorts: Vec::new(),
                         applied_projection: Vec::new(),
@@ -531,10 +543,10 @@ impl IndexManager {
         };
 
         let projected_rows = (matches!(response_mode, SearchResponseMode::Json)
-            && !projection_fields.is_empty())
+            && !applied_projection.is_empty())
         .then(|| {
             rows.iter()
-                .map(|row| Self::project_search_row(row, &projection_fields))
+                .map(|row| computed.project(row, Self::project_search_row(row, &projection_fields)))
                 .collect()
         });
 
@@ -547,9 +559,14 @@ impl IndexManager {
                 &agg_snapshot,
                 &effective_params.aggregations,
                 &agg_row_set,
-                build_query_predicates(&effective_params),
-                effective_params.agg_cursor.as_deref(),
-                effective_params.agg_page_size,
+                crate::index::aggregation::AggregationRequest {
+                    query_predicates: build_query_predicates(&effective_params),
+                    agg_cursor: effective_params.agg_cursor.as_deref(),
+                    agg_page_size: effective_params.agg_page_size,
+                    content_hashes: self.content_hashes(),
+                    computed: computed.fields.clone(),
+                    ..Default::default()
+                },
             )
         } else if agg_requested {
             let predicates = build_query_predicates(&effective_params);
@@ -574,6 +591,8 @@ impl IndexManager {
                     drives_filter: &effective_params.drives,
                     record_filter: agg_record_filter,
                     search_filters: Some(agg_search_filters),
+                    content_hashes: self.content_hashes(),
+                    computed: computed.fields.clone(),
                 },
             )
         } else {
@@ -609,6 +628,8 @@ impl IndexManager {
             duration_ms,
             promotion_ms: Some(promotion_ms),
             truncated,
+            next_search_cursor,
+            cursor_index_changed,
             profile,
             applied_sorts,
             applied_projection,
@@ -617,100 +638,6 @@ impl IndexManager {
             aggregations: agg_results,
         }
     }
-
-    /// Build the `SearchProfile` for `--profile` output.
-    ///
-    /// `drive_match_counts` is pre-computed by the caller so the profile
-    /// stays accurate even when row materialisation was skipped (e.g.
-    /// under `--no-output`).  Pairs with `drive_info`: both slices are
-    /// keyed by drive letter and have identical lengths.
-    #[expect(
-        clippy::too_many_arguments,
-        reason = "per-phase instrumentation payload: lock/search/row_build/write + sub-phase timings + drive slices"
-    )]
-    async fn build_search_profile(
-        &self,
-        lock_us: u128,
-        search_us: u128,
-        row_build_us: u128,
-        write_us: u128,
-        phase_timings: Option<PhaseTimings>,
-        drive_info: &[(uffs_mft::platform::DriveLetter, usize)],
-        drive_match_counts: &[(uffs_mft::platform::DriveLetter, usize)],
-    ) -> SearchProfile {
-        let timings = self.drive_timings.read().await;
-        let startup_us = self.startup_duration_us.load(Ordering::Relaxed);
-
-        let us_to_ms = |us: u128| u64::try_from(us / 1000).unwrap_or(u64::MAX);
-        let ms_clamp = |val: u128| u64::try_from(val).unwrap_or(u64::MAX);
-
-        let mut drive_profiles: Vec<DriveProfile> = drive_info
-            .iter()
-            .map(|&(drive, records)| {
-                let matches = drive_match_counts
-                    .iter()
-                    .find_map(|&(letter, count)| (letter == drive).then_some(count))
-                    .unwrap_or(0);
-                let (cache_ms, mft_ms, compact_ms, trigram_ms) =
-                    timings.get(&drive).map_or((0, 0, 0, 0), |ts| {
-                        (
-                            ms_clamp(ts.cache),
-                            ms_clamp(ts.mft),
-                            ms_clamp(ts.compact),
-                            ms_clamp(ts.trigram),
-                        )
-                    });
-                DriveProfile {
-                    drive,
-                    records,
-                    matches,
-                    cache_ms,
-                    mft_ms,
-                    compact_ms,
-                    trigram_ms,
-                }
-            })
-            .collect();
-        drive_profiles.sort_by_key(|dp| dp.drive);
-
-        let (
-            scan_ms,
-            sort_ms,
-            path_resolve_ms,
-            path_candidates,
-            path_cache_entries,
-            path_resolve_fn_ns,
-            path_build_row_ns,
-        ) = phase_timings.map_or((0, 0, 0, 0, 0, 0, 0), |pt| {
-            (
-                pt.scan_ms,
-                pt.sort_ms,
-                pt.path_resolve_ms,
-                pt.path_candidates,
-                pt.path_cache_entries,
-                pt.path_resolve_fn_ns,
-                pt.path_build_row_ns,
-            )
-        });
-
-        SearchProfile {
-            uptime_ms: us_to_ms(self.start_time.elapsed().as_micros()),
-            startup_ms: startup_us / 1000,
-            lock_ms: us_to_ms(lock_us),
-            search_ms: us_to_ms(search_us),
-            row_build_ms: us_to_ms(row_build_us),
-            serialize_ms: 0, // filled in by handler after shmem write
-            scan_ms,
-            sort_ms,
-            path_resolve_ms,
-            write_ms: us_to_ms(write_us),
-            path_candidates,
-            path_cache_entries,
-            path_resolve_fn_ns,
-            path_build_row_ns,
-            drives: drive_profiles,
-        }
-    }
 }
 
 /// A response carrying no rows — the shape every early-out path returns
@@ -725,6 +652,8 @@ const fn empty_response(duration_ms: u64, promotion_ms: Option<u64>) -> SearchRe
         duration_ms,
         promotion_ms,
         truncated: false,
+        next_search_cursor: None,
+        cursor_index_changed: false,
         profile: None,
         applied_sorts: Vec::new(),
         applied_projection: Vec::new(),
@@ -789,6 +718,23 @@ use predicates::{aggregation_needs_row_set, build_query_predicates};
 mod file_sink;
 use file_sink::write_rows_to_file;
 
+// `IndexManager::build_search_profile` (the `--profile` payload) lives
+// in a sibling file to keep `search.rs` under the 800-line policy ceiling.
+#[path = "search_profile.rs"]
+mod profile;
+
+// Request → core filter compilation lives in a sibling file, shared with
+// the standing-query matcher.
+#[path = "search_filters.rs"]
+mod filters;
+pub(crate) use filters::request_filter_mode;
+
+// `search_cursor` keyset paging lives in a sibling file to keep `search.rs`
+// under the 800-line policy ceiling.
+#[path = "search_cursor.rs"]
+mod cursor;
+use cursor::CursorPaging;
+
 // The inline `tests` module lives in a sibling file to keep `search.rs`
 // under the 800-line policy ceiling.  `#[path]` keeps the test module
 // path identical (`crate::index::search::tests`), so `super::*` inside
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Compile a search request's filters into the core filter set.
+//!
+//! Lifted out of `search.rs` so the standing-query matcher
+//! ([`crate::index::standing`]) builds its filters exactly the way the
+//! `search` RPC does.  Re-attached via
+//! `#[path = "search_filters.rs"] mod filters;` in `search.rs`.
+
+use alloc::sync::Arc;
+
+use uffs_client::protocol::{SearchFilterMode, SearchParams};
+use uffs_core::CoreError;
+use uffs_core::search::backend::FilterMode;
+use uffs_core::search::filters::{SearchFilterParams, SearchFilters};
+use uffs_core::search::pattern_set::PatternSet;
+
+use super::output_config;
+use crate::index::computed::RequestFields;
+use crate::index::{IndexManager, name_patterns};
+
+/// The core filter mode for `params`' `--files` / `--dirs` choice.
+pub(crate) fn request_filter_mode(params: &SearchParams) -> FilterMode {
+    match params.resolved_filter_mode() {
+        SearchFilterMode::Files => FilterMode::FilesOnly,
+        SearchFilterMode::Dirs => FilterMode::DirsOnly,
+        SearchFilterMode::All => FilterMode::All,
+    }
+}
+
+impl IndexManager {
+    /// Build the record-level filters of `ep` (already canonicalised, with
+    /// its computed fields lifted into `computed`): the flag filters, the
+    /// computed-field comparisons, the predicates that compile into the
+    /// hot path, and the `--patterns-from` list, which is also returned so
+    /// the caller can fill `matched_pattern`.
+    ///
+    /// # Errors
+    ///
+    /// A `--patterns-from` list that does not compile (see
+    /// [`Self::pattern_set`]).
+    pub(crate) fn record_filters(
+        &self,
+        ep: &SearchParams,
+        computed: &RequestFields,
+    ) -> Result<(SearchFilters, Option<Arc<PatternSet>>), CoreError> {
+        let mut filters = SearchFilters::from_params(&SearchFilterParams {
+            hide_system: ep.hide_system,
+            hide_ads: ep.hide_ads,
+            min_size: ep.min_size,
+            max_size: ep.max_size,
+            min_descendants: ep.min_descendants,
+            max_descendants: ep.max_descendants,
+            newer: ep.newer.as_deref(),
+            older: ep.older.as_deref(),
+            newer_created: ep.newer_created.as_deref(),
+            older_created: ep.older_created.as_deref(),
+            newer_accessed: ep.newer_accessed.as_deref(),
+            older_accessed: ep.older_accessed.as_deref(),
+            attr_filter: ep.attr.as_deref(),
+            ext_filter: ep.ext.as_deref(),
+            exclude: ep.exclude.as_deref(),
+            path_contains: ep.path_contains.as_deref(),
+            path_excludes: ep.path_excludes.as_deref(),
+            type_filter: ep.type_filter.as_deref(),
+            min_bulkiness: ep.min_bulkiness,
+            max_bulkiness: ep.max_bulkiness,
+            min_name_len: ep.min_name_len,
+            max_name_len: ep.max_name_len,
+            min_path_len: ep.min_path_len,
+            max_path_len: ep.max_path_len,
+            min_allocated: ep.min_allocated,
+            max_allocated: ep.max_allocated,
+            min_treesize: ep.min_treesize,
+            max_treesize: ep.max_treesize,
+            min_tree_allocated: ep.min_tree_allocated,
+            max_tree_allocated: ep.max_tree_allocated,
+            allowed_months: &ep.allowed_months,
+            time_zone: output_config::request_time_zone(ep).unwrap_or_default(),
+        });
+        // Display-only: select the malformed-name render mode for resolved
+        // paths + the name column (`--normalize-malformed`).
+        filters.normalize_malformed = ep.normalize_malformed;
+        filters.computed.clone_from(&computed.filters);
+
+        // Overlay canonical predicates that can be compiled into the hot
+        // path (size / descendant bounds).
+        Self::compile_predicates_into_filters(&mut filters, &ep.predicates);
+
+        let name_patterns = self.pattern_set(&ep.patterns)?;
+        if let Some(set) = &name_patterns {
+            name_patterns::apply_to_filters(&mut filters, set);
+        }
+        Ok((filters, name_patterns))
+    }
+}
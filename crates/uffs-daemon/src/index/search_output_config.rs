@@ -15,6 +15,7 @@
 //! in `handler::RequestHandler::try_pack_csv_blob`).
 
 use uffs_client::protocol::SearchParams;
+use uffs_time::TimeZone;
 
 /// Reconstruct an [`uffs_core::output::OutputConfig`] from protocol
 /// fields in [`SearchParams`].
@@ -61,8 +62,23 @@ pub(crate) fn build_output_config(params: &SearchParams) -> uffs_core::output::O
     if parity_columns || params.output_parity_compat == Some(true) {
         cfg = cfg.with_parity_compat(true);
     }
-    if let Some(tz_hours) = params.output_tz_offset_hours {
-        cfg = cfg.with_tz_offset_hours(tz_hours);
+    if let Some(zone) = request_time_zone(params) {
+        cfg = cfg.with_time_zone(zone);
     }
     cfg
 }
+
+/// The zone the request reads and shows dates in: `--tz`, else
+/// `--tz-offset`; `None` when it names neither (or an unknown zone,
+/// which the handler has already rejected).
+pub(crate) fn request_time_zone(params: &SearchParams) -> Option<TimeZone> {
+    params
+        .time_zone
+        .as_deref()
+        .and_then(TimeZone::parse)
+        .or_else(|| {
+            params
+                .output_tz_offset_hours
+                .map(|hours| TimeZone::fixed(hours.saturating_mul(3_600)))
+        })
+}
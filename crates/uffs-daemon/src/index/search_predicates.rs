@@ -31,7 +31,8 @@ use uffs_core::aggregate::finalize::{DrilldownPredicate, DrilldownValue};
 /// directory still counted 3.8 M files, and a path glob counted 0.
 /// For such queries the aggregation folds the row search's matched set,
 /// so path semantics are applied exactly once, by the engine that owns
-/// them.
+/// them.  The same goes for a `--hash-in` list, which only the row
+/// pass can apply.
 pub(super) fn aggregation_needs_row_set(
     params: &SearchParams,
     filters: &uffs_core::search::filters::SearchFilters,
@@ -40,7 +41,8 @@ pub(super) fn aggregation_needs_row_set(
         || params.pattern.starts_with('>')
         || params.pattern.contains('\\')
         || params.pattern.contains('/');
-    !params.aggregations.is_empty() && (filters.needs_display_row_filter() || pattern_needs_paths)
+    !params.aggregations.is_empty()
+        && (filters.needs_display_row_filter() || pattern_needs_paths || params.hash_in.is_some())
 }
 
 /// Build the drill-down-predicate list that prefixes every
@@ -85,7 +85,21 @@ fn build_output_config_explicit_values_applied() {
     assert!(!cfg.header);
     assert!(cfg.columns.is_some(), "parity columns must be set");
     assert!(cfg.parity_compat, "parity_compat must be true");
-    assert_eq!(cfg.timezone_offset_secs, -7_i32 * 3_600_i32);
+    assert_eq!(
+        cfg.time_zone,
+        uffs_time::TimeZone::fixed(-7_i32 * 3_600_i32)
+    );
+
+    // `--tz` wins over `--tz-offset`.
+    let named = SearchParams {
+        output_tz_offset_hours: Some(-7_i32),
+        time_zone: Some("America/Denver".to_owned()),
+        ..Default::default()
+    };
+    assert_eq!(
+        build_output_config(&named).time_zone.name(),
+        Some("America/Denver")
+    );
 }
 
 /// `--parity-compat` without explicit sep/quote must produce a valid
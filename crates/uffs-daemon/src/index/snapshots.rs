@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Scheduled baseline snapshots for [`IndexManager`]: takes the daily
+//! snapshots, resolves `@<time>` diff baselines, and lists what is
+//! retained.
+//!
+//! [`IndexManager::take_due_snapshots`] is driven by the daemon's
+//! once-a-minute snapshot scheduler; every resident local drive whose
+//! daily slot has passed is written to the
+//! [`crate::cache::snapshots::SnapshotStore`] on the blocking pool.
+//! Parked and cold drives have no body in memory and are snapshotted on
+//! the first tick after they are warm again.
+//!
+//! An `@<spec>` baseline ([`IndexManager::baseline_at`]) resolves, in
+//! order, to:
+//!
+//! 1. the snapshot *named* `spec` (its local day, `@2026-10-01`);
+//! 2. the exact state rebuilt from `[history]`, when enabled and retained that
+//!    far back;
+//! 3. the newest snapshot taken at or before the moment.
+
+use alloc::sync::Arc;
+
+use uffs_client::protocol::response::{DriveBaselines, ListBaselinesResponse, SnapshotInfo};
+use uffs_core::compact::DriveCompactIndex;
+use uffs_mft::platform::VolumeId;
+
+use super::IndexManager;
+use crate::cache::history::HistoryError;
+use crate::cache::snapshots::{SnapshotStore, snapshot_name};
+use crate::cache::volume_store::run_logged;
+
+impl IndexManager {
+    /// Snapshot every resident local drive whose daily slot has passed
+    /// since its newest snapshot.  Best-effort: a failed write is
+    /// warn-logged and retried on the next tick.
+    pub(crate) async fn take_due_snapshots(&self, now_ms: u64) {
+        let Some(store) = self.snapshots.as_ref() else {
+            return;
+        };
+        let bodies: Vec<(VolumeId, Arc<DriveCompactIndex>)> = {
+            let guard = self.index.read().await;
+            guard
+                .iter()
+                .filter(|shard| shard.drive.is_local())
+                .filter_map(|shard| Some((shard.drive, shard.body()?)))
+                .collect()
+        };
+        for (volume, body) in bodies {
+            let worker = Arc::clone(store);
+            let taken = run_logged(
+                volume,
+                "Scheduled baseline snapshot failed; retrying next tick",
+                move || {
+                    if !worker.is_due(volume, now_ms, &chrono::Local)? {
+                        return Ok(false);
+                    }
+                    worker
+                        .take(volume, now_ms, &body, &chrono::Local)
+                        .map(|()| true)
+                },
+            )
+            .await;
+            if taken == Some(true) {
+                tracing::info!(
+                    target: "shard.snapshot",
+                    drive = %volume,
+                    name = %snapshot_name(now_ms, &chrono::Local),
+                    "Scheduled baseline snapshot written",
+                );
+            }
+        }
+    }
+
+    /// Resolve the `@<spec>` diff baseline for `volume` (see the module
+    /// docs for the resolution order).
+    ///
+    /// # Errors
+    ///
+    /// [`HistoryError::Disabled`] when neither `[snapshots]` nor
+    /// `[history]` is enabled, [`HistoryError::BadTime`] for an
+    /// unparseable `spec`, [`HistoryError::NoPoint`] when nothing is
+    /// retained that early, otherwise read failures.
+    pub(crate) async fn baseline_at(
+        &self,
+        volume: VolumeId,
+        spec: &str,
+    ) -> Result<DriveCompactIndex, HistoryError> {
+        let store = self.snapshots.as_ref().map(Arc::clone);
+        if let Some(snapshots) = &store
+            && let Some(at_ms) = snapshots.named(volume, spec, &chrono::Local)?
+        {
+            return load_snapshot(Arc::clone(snapshots), volume, at_ms).await;
+        }
+        if self.history.is_some() {
+            match self.state_as_of(volume, spec).await {
+                Err(HistoryError::NoPoint { .. }) if store.is_some() => {}
+                rebuilt => return rebuilt,
+            }
+        }
+        let snapshots = store.ok_or(HistoryError::Disabled)?;
+        let now = uffs_core::search::filters::now_unix_micros();
+        let at_ms = uffs_core::search::filters::parse_time_point_ms(spec, now)
+            .ok_or_else(|| HistoryError::BadTime(spec.to_owned()))?;
+        let taken = snapshots.at_or_before(volume, at_ms, &chrono::Local)?;
+        load_snapshot(snapshots, volume, taken).await
+    }
+
+    /// The `list_baselines` RPC: every registered local drive with its
+    /// retained snapshots and the reach of its history.
+    pub(crate) async fn list_baselines(&self) -> ListBaselinesResponse {
+        let mut volumes: Vec<VolumeId> = {
+            let guard = self.index.read().await;
+            guard
+                .iter()
+                .filter(|shard| shard.drive.is_local())
+                .map(|shard| shard.drive)
+                .collect()
+        };
+        volumes.sort_unstable();
+        volumes.dedup();
+        let snapshots = self.snapshots.as_ref().map(Arc::clone);
+        let history = self.history.as_ref().map(Arc::clone);
+        let snapshots_enabled = snapshots.is_some();
+        let history_enabled = history.is_some();
+        let drives = tokio::task::spawn_blocking(move || {
+            volumes
+                .into_iter()
+                .map(|volume| DriveBaselines {
+                    drive: volume.letter(),
+                    snapshots: snapshots
+                        .as_deref()
+                        .map(|store| snapshot_infos(store, volume))
+                        .unwrap_or_default(),
+                    history_since_ms: history
+                        .as_ref()
+                        .and_then(|store| store.points(volume).ok()?.first().copied()),
+                })
+                .collect()
+        })
+        .await
+        .unwrap_or_default();
+        ListBaselinesResponse {
+            snapshots_enabled,
+            history_enabled,
+            drives,
+        }
+    }
+}
+
+/// Load `volume`'s snapshot taken at `at_ms` on the blocking pool.
+async fn load_snapshot(
+    store: Arc<SnapshotStore>,
+    volume: VolumeId,
+    at_ms: u64,
+) -> Result<DriveCompactIndex, HistoryError> {
+    Ok(tokio::task::spawn_blocking(move || store.load(volume, at_ms)).await??)
+}
+
+/// `volume`'s retained snapshots, oldest first (none when unreadable).
+fn snapshot_infos(store: &SnapshotStore, volume: VolumeId) -> Vec<SnapshotInfo> {
+    store
+        .list(volume)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|at_ms| SnapshotInfo {
+            name: snapshot_name(at_ms, &chrono::Local),
+            at_ms,
+            bytes: store.size(volume, at_ms),
+        })
+        .collect()
+}
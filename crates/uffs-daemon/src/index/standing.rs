@@ -0,0 +1,470 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Standing queries: searches registered once and re-evaluated on every
+//! USN-journal apply tick (`subscribe_query` / `unsubscribe_query`).
+//!
+//! A subscription is compiled at subscribe time into a [`RecordMatcher`]
+//! — the same filters and name test the `search` RPC would run — plus the
+//! predicates that only a materialised row can answer.  Before a batch is
+//! patched, [`IndexManager::standing_query_batch`] captures the covering
+//! subscriptions, the batch's created / renamed / modified / deleted File
+//! References and the pre-patch body; once the patched body is swapped
+//! in, [`IndexManager::spawn_standing_query_matches`] tests just those
+//! records on the blocking pool and pushes each match as a
+//! [`DaemonEvent::QueryMatch`].  Deleted records are tested against the
+//! pre-patch body, where they still exist; the rest against the patched
+//! one.  Neither the drive scan nor the journal apply path waits on it.
+//!
+//! Subscriptions are owned by an IPC connection: [`crate::ipc`] drops a
+//! connection's subscriptions when it closes, and the notification loop
+//! forwards a match only to its owner.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use uffs_client::protocol::response::{QueryChangeKind, SubscribeQueryParams};
+use uffs_client::protocol::{SearchParams, SearchPredicate};
+use uffs_core::compact::DriveCompactIndex;
+use uffs_core::search::backend::SearchRequest;
+use uffs_core::search::pattern_set::PatternSet;
+use uffs_core::search::query::RecordMatcher;
+use uffs_mft::platform::{DriveLetter, VolumeId};
+use uffs_mft::usn::FileChange;
+
+use super::computed::RequestFields;
+use super::search::request_filter_mode;
+use super::{IndexManager, name_patterns};
+use crate::events::{DaemonEvent, EventSender};
+
+/// Per-connection cap on active subscriptions.  Each one is tested
+/// against every changed record of every journal apply tick.
+pub(crate) const MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 16;
+
+/// Mask selecting the 48-bit record number of an NTFS File Reference
+/// (the sequence number lives in the top 16 bits).
+const FRS_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+/// Why a `subscribe_query` request was refused.  Mapped to
+/// `ERR_INVALID_PARAMS` by the handler.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum StandingQueryError {
+    /// The search asks for a response shape a row notification cannot
+    /// carry.
+    #[error("standing queries do not support {feature}")]
+    Unsupported {
+        /// The offending feature, as named in the error message.
+        feature: &'static str,
+    },
+    /// The search does not compile (bad regex, unknown computed field,
+    /// unreadable pattern list).
+    #[error("invalid standing query: {0}")]
+    Invalid(String),
+    /// The connection already holds the maximum number of subscriptions.
+    #[error(
+        "too many standing queries on this connection (limit {MAX_SUBSCRIPTIONS_PER_CONNECTION})"
+    )]
+    TooMany,
+}
+
+/// A subscription's search, compiled once at subscribe time.
+struct CompiledQuery {
+    /// Record-level filters and name test.
+    records: RecordMatcher,
+    /// Predicates checked on the materialised row (empty when they all
+    /// compiled into `records`).
+    predicates: Vec<SearchPredicate>,
+    /// The `--patterns-from` list, to fill `matched_pattern`.
+    name_patterns: Option<Arc<PatternSet>>,
+}
+
+impl CompiledQuery {
+    /// The matching rows among `records` (compact indices into `body`).
+    fn matching_rows(
+        &self,
+        body: &DriveCompactIndex,
+        records: &[u32],
+    ) -> Vec<uffs_core::search::backend::DisplayRow> {
+        let mut rows = self.records.matching_rows(body, records);
+        if !self.predicates.is_empty() {
+            rows.retain(|row| IndexManager::matches_predicates(row, &self.predicates));
+        }
+        if let Some(set) = &self.name_patterns {
+            name_patterns::fill_matched_patterns(&mut rows, set);
+        }
+        rows
+    }
+}
+
+/// One registered standing query.
+#[derive(Clone)]
+struct StandingQuery {
+    /// IPC connection that owns the subscription.
+    connection: u64,
+    /// The compiled search (see [`IndexManager::compile_standing_query`]).
+    compiled: Arc<CompiledQuery>,
+    /// Change kinds to report; empty = every kind.
+    changes: Vec<QueryChangeKind>,
+}
+
+impl StandingQuery {
+    /// Whether this subscription reports `change`.
+    fn reports(&self, change: QueryChangeKind) -> bool {
+        self.changes.is_empty() || self.changes.contains(&change)
+    }
+}
+
+/// Registry of active standing queries, keyed by subscription id.
+///
+/// Wrapped in a [`std::sync::Mutex`] for the same reason as
+/// `IndexManager::journal_handles`: the critical sections are map
+/// lookups and inserts with no async work under the lock.  Poison is
+/// recovered via [`std::sync::PoisonError::into_inner`] — the map holds
+/// no cross-entry invariants.
+#[derive(Default)]
+pub(crate) struct StandingQueries {
+    /// Next subscription id (ids are never reused within a daemon run).
+    next_id: AtomicU64,
+    /// Active subscriptions.
+    subscriptions: Mutex<BTreeMap<u64, StandingQuery>>,
+}
+
+impl StandingQueries {
+    /// Lock the subscription map, recovering from poison.
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, StandingQuery>> {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Register `compiled` for `connection` and return the new id.
+    fn insert(
+        &self,
+        connection: u64,
+        compiled: CompiledQuery,
+        changes: Vec<QueryChangeKind>,
+    ) -> Result<u64, StandingQueryError> {
+        let mut guard = self.lock();
+        let owned = guard
+            .values()
+            .filter(|query| query.connection == connection)
+            .count();
+        if owned >= MAX_SUBSCRIPTIONS_PER_CONNECTION {
+            return Err(StandingQueryError::TooMany);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        guard.insert(id, StandingQuery {
+            connection,
+            compiled: Arc::new(compiled),
+            changes,
+        });
+        drop(guard);
+        Ok(id)
+    }
+
+    /// Remove subscription `id` if `connection` owns it.
+    fn remove(&self, connection: u64, id: u64) -> bool {
+        let mut guard = self.lock();
+        let owned = guard
+            .get(&id)
+            .is_some_and(|query| query.connection == connection);
+        if owned {
+            guard.remove(&id);
+        }
+        owned
+    }
+
+    /// Remove every subscription owned by `connection`; returns how many.
+    fn remove_connection(&self, connection: u64) -> usize {
+        let mut guard = self.lock();
+        let before = guard.len();
+        guard.retain(|_, query| query.connection != connection);
+        before - guard.len()
+    }
+
+    /// Whether `connection` owns at least one subscription.
+    fn has_connection(&self, connection: u64) -> bool {
+        self.lock()
+            .values()
+            .any(|query| query.connection == connection)
+    }
+
+    /// Clone out the subscriptions covering `volume`, so evaluation runs
+    /// without the lock held.
+    fn covering(&self, volume: VolumeId) -> Vec<(u64, StandingQuery)> {
+        self.lock()
+            .iter()
+            .filter(|(_, query)| query.compiled.records.covers(volume))
+            .map(|(id, query)| (*id, query.clone()))
+            .collect()
+    }
+}
+
+/// A journal batch's standing-query work, captured before the patch.
+pub(crate) struct PendingStandingMatches {
+    /// Subscriptions covering the patched drive.
+    subscriptions: Vec<(u64, StandingQuery)>,
+    /// Masked FRS → change kind (see [`changed_records`]).
+    changed: BTreeMap<u64, QueryChangeKind>,
+    /// The body before the patch, where deleted records still resolve.
+    old_body: Arc<DriveCompactIndex>,
+}
+
+impl PendingStandingMatches {
+    /// Test the changed records against every captured subscription and
+    /// emit one [`DaemonEvent::QueryMatch`] per match.  Deletes resolve
+    /// in the pre-patch body, every other change in `new_body`.
+    pub(crate) fn emit(&self, new_body: &DriveCompactIndex, events: &EventSender) {
+        let mut live: BTreeMap<u32, QueryChangeKind> = BTreeMap::new();
+        let mut deleted: BTreeMap<u32, QueryChangeKind> = BTreeMap::new();
+        for (&frs, &change) in &self.changed {
+            let (body, records) = if change == QueryChangeKind::Deleted {
+                (&*self.old_body, &mut deleted)
+            } else {
+                (new_body, &mut live)
+            };
+            if let Some(idx) = compact_index(body, frs) {
+                records.insert(idx, change);
+            }
+        }
+        for (subscription_id, query) in &self.subscriptions {
+            for (body, records) in [(new_body, &live), (&*self.old_body, &deleted)] {
+                let wanted: Vec<u32> = records
+                    .iter()
+                    .filter(|&(_, &change)| query.reports(change))
+                    .map(|(&idx, _)| idx)
+                    .collect();
+                for row in query.compiled.matching_rows(body, &wanted) {
+                    let Some(&change) = records.get(&row.record_index) else {
+                        continue;
+                    };
+                    events.emit(DaemonEvent::QueryMatch {
+                        connection: query.connection,
+                        subscription_id: *subscription_id,
+                        change,
+                        row: IndexManager::display_row_to_search_row(&row),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl IndexManager {
+    /// Register a standing query for IPC connection `connection`.
+    ///
+    /// # Errors
+    ///
+    /// [`StandingQueryError::Unsupported`] for aggregations, snapshot
+    /// diffs, point-in-time searches, `hash_in` lists or direct file
+    /// output; [`StandingQueryError::Invalid`] for a search that does not
+    /// compile; [`StandingQueryError::TooMany`] when the connection
+    /// already holds [`MAX_SUBSCRIPTIONS_PER_CONNECTION`].
+    pub(crate) fn subscribe_query(
+        &self,
+        connection: u64,
+        params: SubscribeQueryParams,
+    ) -> Result<u64, StandingQueryError> {
+        let compiled = self.compile_standing_query(params.search)?;
+        let id = self
+            .standing_queries
+            .insert(connection, compiled, params.changes)?;
+        tracing::debug!(
+            connection,
+            subscription_id = id,
+            "Standing query registered"
+        );
+        Ok(id)
+    }
+
+    /// Compile `search` the way the search pipeline would run it.
+    fn compile_standing_query(
+        &self,
+        search: SearchParams,
+    ) -> Result<CompiledQuery, StandingQueryError> {
+        let invalid = |err: &dyn core::fmt::Display| StandingQueryError::Invalid(err.to_string());
+        let mut params = normalise_search(search)?;
+        params.populate_canonical_fields();
+        let computed =
+            RequestFields::prepare(&self.config, &mut params).map_err(|err| invalid(&err))?;
+        let (mut filters, name_patterns) = self
+            .record_filters(&params, &computed)
+            .map_err(|err| invalid(&err))?;
+        let records = RecordMatcher::new(SearchRequest {
+            pattern: &params.pattern,
+            case_sensitive: params.case_sensitive,
+            whole_word: params.whole_word,
+            match_path: params.match_path,
+            result_limit: None,
+            filter_mode: request_filter_mode(&params),
+            search_filters: &mut filters,
+            drives_filter: &params.drives,
+            fuzzy: params.fuzzy.then(|| {
+                params
+                    .fuzzy_threshold
+                    .unwrap_or(uffs_core::search::fuzzy::DEFAULT_THRESHOLD)
+            }),
+            rank: false,
+        })
+        .map_err(|err| invalid(&err))?;
+        let predicates = if Self::predicates_require_post_filter(&params.predicates) {
+            params.predicates
+        } else {
+            Vec::new()
+        };
+        Ok(CompiledQuery {
+            records,
+            predicates,
+            name_patterns,
+        })
+    }
+
+    /// Drop subscription `id`; `false` when `connection` does not own it.
+    pub(crate) fn unsubscribe_query(&self, connection: u64, id: u64) -> bool {
+        self.standing_queries.remove(connection, id)
+    }
+
+    /// Drop every subscription owned by a closed connection.
+    pub(crate) fn drop_connection_queries(&self, connection: u64) {
+        let removed = self.standing_queries.remove_connection(connection);
+        if removed > 0 {
+            tracing::debug!(
+                connection,
+                removed,
+                "Dropped standing queries of closed connection"
+            );
+        }
+    }
+
+    /// Whether `connection` holds any standing query — such a connection
+    /// is a listener and is exempt from the IPC idle disconnect.
+    pub(crate) fn has_standing_queries(&self, connection: u64) -> bool {
+        self.standing_queries.has_connection(connection)
+    }
+
+    /// Capture what the standing queries need from `changes` before the
+    /// batch is patched into `shard`.  `None` — and free — while no
+    /// subscription covers `letter` or the batch changes nothing they
+    /// can report.
+    pub(super) fn standing_query_batch(
+        &self,
+        letter: DriveLetter,
+        changes: &[FileChange],
+        shard: &crate::cache::shard::ShardEntry,
+    ) -> Option<PendingStandingMatches> {
+        self.pending_standing_matches(letter, changes, shard.body()?)
+    }
+
+    /// [`Self::standing_query_batch`] over an explicit pre-patch body.
+    pub(crate) fn pending_standing_matches(
+        &self,
+        letter: DriveLetter,
+        changes: &[FileChange],
+        old_body: Arc<DriveCompactIndex>,
+    ) -> Option<PendingStandingMatches> {
+        let subscriptions = self.standing_queries.covering(VolumeId::local(letter));
+        if subscriptions.is_empty() {
+            return None;
+        }
+        let records = changed_records(changes);
+        (!records.is_empty()).then_some(PendingStandingMatches {
+            subscriptions,
+            changed: records,
+            old_body,
+        })
+    }
+
+    /// Evaluate a captured batch against the freshly swapped-in
+    /// `new_body` on the blocking pool, without waiting for it: matching
+    /// is a handful of record lookups per subscription, but it must never
+    /// hold up the next journal tick.
+    pub(super) fn spawn_standing_query_matches(
+        &self,
+        pending: Option<PendingStandingMatches>,
+        new_body: &Arc<DriveCompactIndex>,
+    ) {
+        let Some(batch) = pending else {
+            return;
+        };
+        let events = self.events.clone();
+        let body = Arc::clone(new_body);
+        drop(tokio::task::spawn_blocking(move || {
+            batch.emit(&body, &events);
+        }));
+    }
+}
+
+/// Strip the fields that shape a one-shot response (paging, projection,
+/// ranking, profiling) so the search always yields full rows, and refuse
+/// the ones a row notification cannot express.
+fn normalise_search(search: SearchParams) -> Result<SearchParams, StandingQueryError> {
+    if !search.aggregations.is_empty() {
+        return Err(StandingQueryError::Unsupported {
+            feature: "aggregations",
+        });
+    }
+    if search.diff_baseline.is_some() {
+        return Err(StandingQueryError::Unsupported {
+            feature: "snapshot diffs",
+        });
+    }
+    if search.as_of.is_some() {
+        return Err(StandingQueryError::Unsupported {
+            feature: "point-in-time searches",
+        });
+    }
+    if search.output_file.is_some() {
+        return Err(StandingQueryError::Unsupported {
+            feature: "direct file output",
+        });
+    }
+    if search.hash_in.is_some() {
+        return Err(StandingQueryError::Unsupported {
+            feature: "hash lists",
+        });
+    }
+    Ok(SearchParams {
+        limit: None,
+        search_cursor: None,
+        rank: false,
+        projection: Vec::new(),
+        response_mode: None,
+        include_rows: true,
+        profile: false,
+        resolve_lcn_order: false,
+        agg_cursor: None,
+        agg_page_size: None,
+        output_format: None,
+        ..search
+    })
+}
+
+/// Map a journal batch to masked FRS → change kind, skipping records
+/// whose only change is one the index does not track.
+fn changed_records(changes: &[FileChange]) -> BTreeMap<u64, QueryChangeKind> {
+    changes
+        .iter()
+        .filter_map(|change| {
+            let kind = if change.deleted {
+                QueryChangeKind::Deleted
+            } else if change.created {
+                QueryChangeKind::Created
+            } else if change.renamed {
+                QueryChangeKind::Renamed
+            } else if change.size_changed || change.metadata_changed {
+                QueryChangeKind::Modified
+            } else {
+                return None;
+            };
+            Some((change.frs.raw() & FRS_MASK, kind))
+        })
+        .collect()
+}
+
+/// Compact index of record `frs` in `body`, if it is there.
+fn compact_index(body: &DriveCompactIndex, frs: u64) -> Option<u32> {
+    let idx = *body.frs_to_compact.get(uffs_mft::frs_to_usize(frs))?;
+    (idx != u32::MAX).then_some(idx)
+}
@@ -84,7 +84,7 @@ fn build_row(shard: &ShardEntry, now_ms: u64) -> DriveTierStatus {
     let query_rate_per_min = shard.stats.decay_ema_qpm(now_ms);
 
     DriveTierStatus {
-        letter: shard.drive,
+        letter: shard.drive.letter(),
         tier,
         resident_bytes,
         query_rate_per_min,
@@ -45,7 +45,7 @@ impl IndexManager {
         let guard = self.index.read().await;
         guard
             .iter()
-            .map(|shard| (shard.drive, shard.stats.queries_total()))
+            .map(|shard| (shard.drive.letter(), shard.stats.queries_total()))
             .collect()
     }
 
@@ -115,7 +115,7 @@ impl IndexManager {
         let guard = self.index.read().await;
         guard
             .iter()
-            .map(|shard| (shard.drive, shard.state()))
+            .map(|shard| (shard.drive.letter(), shard.state()))
             .collect()
     }
 
@@ -205,6 +205,74 @@ fn duplicates_returns_result() {
     assert_eq!(results.len(), 1);
 }
 
+#[test]
+fn hosted_duplicate_members_stay_unverified_and_uncached() {
+    use uffs_core::aggregate::duplicates::{DuplicateGroup, DuplicateResult};
+    use uffs_core::aggregate::hash_cache::{ContentKey, HashAlgo, HashKind, HashSpan};
+    use uffs_core::aggregate::spec::DuplicateVerify;
+    use uffs_core::aggregate::verify::{DuplicateVerifier, FileReader as _, VerificationBudget};
+
+    use crate::cache::hash_store::ContentHashStore;
+    use crate::cache::volume_store::VolumeRoot;
+    use crate::index::content_hash::{DaemonFileReader, DriveHashes};
+
+    let mut drive = super::build_test_drive();
+    drive.host = Some("srv01".parse().expect("host"));
+    let volume = drive.volume();
+    let files: Vec<(usize, ContentKey)> = drive
+        .records
+        .as_slice()
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| !record.is_directory())
+        .filter_map(|(idx, record)| Some((idx, ContentKey::of(record)?)))
+        .collect();
+    let [(first, first_key), (second, _), ..] = files.as_slice() else {
+        panic!("fixture needs two files, got {files:?}");
+    };
+    let drives = [std::sync::Arc::new(drive)];
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = ContentHashStore::new(VolumeRoot::new(tmp.path().to_path_buf()));
+    // A digest already filed under the hosted volume is not trusted either.
+    let kind = HashKind::new(HashAlgo::Sha256, HashSpan::Full);
+    store.record(volume, *first_key, kind, HashAlgo::Sha256.digest(b"stale"));
+
+    let reader = DaemonFileReader { drives: &drives };
+    let err = reader
+        .read_all(*first, 0)
+        .expect_err("hosted file must not be opened");
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+
+    let group = DuplicateGroup {
+        count: 2,
+        total_bytes: 0,
+        file_size: 0,
+        reclaimable_bytes: 0,
+        member_indices: vec![(*first, 0), (*second, 0)],
+        sample_rows: Vec::new(),
+        verified: false,
+    };
+    let result = DuplicateResult {
+        candidate_groups: 1,
+        candidate_files: 2,
+        total_duplicate_bytes: 0,
+        total_reclaimable_bytes: 0,
+        groups: vec![group],
+        verification_mode: DuplicateVerify::None,
+    };
+    let hashes = DriveHashes {
+        store: &store,
+        drives: &drives,
+    };
+    let (verified, summary) =
+        DuplicateVerifier::new(DuplicateVerify::Sha256, VerificationBudget::unlimited())
+            .verify_cached(result, &reader, Some(&hashes));
+    assert!(verified.groups.iter().all(|checked| !checked.verified));
+    assert_eq!(summary.groups_verified, 0);
+    assert_eq!(summary.cache_hits, 0);
+    assert_eq!(summary.files_read, 0);
+}
+
 // ── Raw power syntax ─────────────────────────────────────────────
 
 #[test]
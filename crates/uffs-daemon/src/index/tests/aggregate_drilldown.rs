@@ -161,6 +161,151 @@ fn raw_power_syntax_stats_size() {
     assert!(stats.is_some(), "should have a stats result");
 }
 
+// ── Nested sub-aggregations ───────────────────────────────────────
+
+#[test]
+fn nested_sub_via_wire_renders_sub_buckets() {
+    let index = test_index();
+    let specs = [AggregateSpecWire {
+        field: Some("drive".to_owned()),
+        sub: Some(Box::new(AggregateSpecWire {
+            field: Some("extension".to_owned()),
+            top: Some(3),
+            ..spec("terms")
+        })),
+        ..spec("terms")
+    }];
+    // A page of one top-level bucket still carries its whole subtree.
+    let (results, _matched) =
+        IndexManager::run_aggregations(&index, None, &specs, AggregationRequest {
+            agg_page_size: Some(1),
+            ..AggregationRequest::default()
+        });
+    let terms = results.iter().find(|r| r.kind == "buckets").unwrap();
+    assert_eq!(terms.buckets.len(), 1);
+    let drive = &terms.buckets[0];
+    assert_eq!(drive.key, "C:");
+    assert_eq!(drive.sub_buckets.len(), 3, "top=3 under the drive");
+    let rs = drive
+        .sub_buckets
+        .iter()
+        .find(|b| b.key == "rs")
+        .expect("rs under C:");
+    assert_eq!(rs.count, 2);
+    let drill: Vec<(&str, &str)> = rs
+        .drilldown
+        .iter()
+        .map(|d| (d.field.as_str(), d.value.as_str().unwrap_or_default()))
+        .collect();
+    assert_eq!(drill, [("drive", "C:"), ("extension", "rs")]);
+}
+
+#[test]
+fn wire_percentile_metrics_render_per_bucket_and_stats() {
+    let index = test_index();
+    let specs = [
+        AggregateSpecWire {
+            field: Some("extension".to_owned()),
+            metrics: vec!["count".to_owned(), "median".to_owned(), "p100".to_owned()],
+            ..spec("terms")
+        },
+        AggregateSpecWire {
+            field: Some("size".to_owned()),
+            metrics: vec!["sum".to_owned(), "p99".to_owned()],
+            ..spec("stats")
+        },
+    ];
+    let (results, _matched) =
+        IndexManager::run_aggregations(&index, None, &specs, AggregationRequest::default());
+    let terms = results.iter().find(|r| r.kind == "buckets").unwrap();
+    let rs = terms
+        .buckets
+        .iter()
+        .find(|b| b.key == "rs")
+        .expect("rs bucket");
+    let ranks: Vec<&str> = rs.percentiles.iter().map(|p| p.rank.as_str()).collect();
+    assert_eq!(ranks, ["p50", "p100"]);
+    let stats = results
+        .iter()
+        .find_map(|r| r.stats.as_ref())
+        .expect("stats result");
+    assert_eq!(stats.percentiles.len(), 1);
+    assert_eq!(stats.percentiles[0].rank, "p99");
+}
+
+#[test]
+fn wire_pivot_carries_matrix_and_totals() {
+    let index = test_index();
+    let specs = [AggregateSpecWire {
+        label: Some("pivot:extension,cols=drive,metric=total_bytes".to_owned()),
+        ..spec("raw")
+    }];
+    let (results, _matched) =
+        IndexManager::run_aggregations(&index, None, &specs, AggregationRequest::default());
+    let result = results.iter().find(|r| r.kind == "pivot").expect("pivot");
+    assert_eq!(result.field.as_deref(), Some("extension"));
+    let pivot = result.pivot.as_ref().expect("pivot matrix");
+    assert_eq!(pivot.col_field, "drive");
+    assert_eq!(pivot.metric, "total_bytes");
+    assert_eq!(pivot.column_totals.len(), pivot.columns.len());
+    let row_sum: f64 = pivot.rows.iter().map(|row| row.total).sum();
+    assert!((row_sum - pivot.grand_total).abs() < 0.5_f64);
+    assert!(
+        pivot
+            .rows
+            .iter()
+            .all(|row| row.values.len() == pivot.columns.len())
+    );
+}
+
+#[test]
+fn convert_wire_spec_nests_sub_and_raw_chains() {
+    // Structured `sub` under a raw chain nests at the deepest level.
+    let ws = AggregateSpecWire {
+        label: Some("terms:drive>hist:size".to_owned()),
+        sub: Some(Box::new(AggregateSpecWire {
+            field: Some("extension".to_owned()),
+            ..spec("terms")
+        })),
+        ..spec("raw")
+    };
+    let converted = IndexManager::convert_wire_spec(&ws).unwrap();
+    assert_eq!(converted.len(), 1);
+    let hist = converted[0].sub.as_deref().expect("hist level");
+    assert!(matches!(hist.kind, AggregateKind::Histogram { .. }));
+    let leaf = hist.sub.as_deref().expect("terms level");
+    assert!(matches!(leaf.kind, AggregateKind::Terms {
+        field: FieldId::Extension,
+        ..
+    }));
+
+    for (parent, child) in [
+        (spec("count"), AggregateSpecWire {
+            field: Some("extension".to_owned()),
+            ..spec("terms")
+        }),
+        (
+            AggregateSpecWire {
+                field: Some("drive".to_owned()),
+                ..spec("terms")
+            },
+            AggregateSpecWire {
+                preset: Some("overview".to_owned()),
+                ..spec("preset")
+            },
+        ),
+    ] {
+        let nested = AggregateSpecWire {
+            sub: Some(Box::new(child)),
+            ..parent
+        };
+        assert!(matches!(
+            IndexManager::convert_wire_spec(&nested),
+            Err(crate::index::wire_spec::WireSpecError::NestedNotBucketed { .. })
+        ));
+    }
+}
+
 // ── Cursor pagination ─────────────────────────────────────────────
 
 #[test]
@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Threshold-alert tests for [`super::IndexManager`].
+//!
+//! Pins the anti-flap state machine — a rule fires once per excursion
+//! (hysteresis via `rearm`) and never inside its cooldown — the
+//! `growth_per_hour` rate window, the end-to-end path from a `treesize`
+//! rule to an `AlertFired` event plus a JSONL sink line, and how a rule's
+//! path is resolved: `C:` as the volume root, the host respected, and
+//! the cached record re-checked when the index is swapped — plus the
+//! command timeout.
+
+#![expect(
+    clippy::std_instead_of_alloc,
+    reason = "test fixtures — `std::sync::Arc` matches the rest of the daemon's \
+              test fixtures, no need to switch to `alloc::sync::Arc` for tests"
+)]
+
+use core::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+use uffs_core::compact::build_compact_index;
+use uffs_mft::index::{IndexNameRef, MftIndex, ROOT_FRS, SizeInfo};
+use uffs_mft::platform::DriveLetter;
+
+use super::IndexManager;
+use crate::config::{AlertMetric, AlertRule, Config};
+use crate::events::DaemonEvent;
+use crate::index::alerts::{AlertState, CommandEnd, run_alert_command};
+
+/// Drive C with `C:\Projects` holding one 5 000-byte file, tree
+/// metrics computed (the shared fixture leaves `treesize` at zero).
+fn drive_with_treesize() -> uffs_core::compact::DriveCompactIndex {
+    drive_of(&[
+        ("Projects", 100, ROOT_FRS, None),
+        ("big.iso", 101, 100, Some(5_000)),
+    ])
+}
+
+/// Drive C built from `(name, frs, parent_frs, file size)` entries; a
+/// `None` size makes a directory.
+fn drive_of(entries: &[(&str, u64, u64, Option<u64>)]) -> uffs_core::compact::DriveCompactIndex {
+    let mut idx = MftIndex::new(DriveLetter::C);
+    let root_off = idx.add_name(".");
+    let root = idx.get_or_create(ROOT_FRS.into());
+    root.stdinfo.set_directory(true);
+    root.first_name.name = IndexNameRef::new(root_off, 1, true, IndexNameRef::NO_EXTENSION);
+    root.first_name.parent_frs = Into::into(ROOT_FRS);
+
+    for &(name, frs, parent, size) in entries {
+        let off = idx.add_name(name);
+        let ext = idx.intern_extension(name);
+        let rec = idx.get_or_create(frs.into());
+        rec.first_name.name = IndexNameRef::new(off, uffs_mft::len_to_u16(name.len()), true, ext);
+        rec.first_name.parent_frs = Into::into(parent);
+        if let Some(length) = size {
+            rec.first_stream.size = SizeInfo {
+                length,
+                allocated: length,
+            };
+        } else {
+            rec.stdinfo.set_directory(true);
+        }
+    }
+    idx.compute_tree_metrics();
+
+    let (drive, _, _) = build_compact_index(DriveLetter::C, &idx);
+    drive
+}
+
+fn rule_above(threshold: u64, rearm: Option<u64>, cooldown_secs: u64) -> AlertRule {
+    AlertRule {
+        name: "big".to_owned(),
+        path: "C:\\Projects".to_owned(),
+        above: Some(threshold),
+        rearm,
+        cooldown_secs,
+        ..AlertRule::default()
+    }
+}
+
+#[test]
+fn alert_fires_once_per_excursion_past_rearm() {
+    let rule = rule_above(100, Some(80), 0);
+    let mut state = AlertState::default();
+    let now = Instant::now();
+
+    let fired: Vec<bool> = [50, 120, 130, 90, 110, 80, 101]
+        .into_iter()
+        .map(|value| state.observe(&rule, value, now))
+        .collect();
+    // 90 is below the threshold but above `rearm`: still disarmed, so
+    // 110 stays quiet; 80 re-arms and 101 fires again.
+    assert_eq!(fired, [false, true, false, false, false, false, true]);
+}
+
+#[test]
+fn alert_cooldown_holds_back_a_rearmed_rule() {
+    let rule = AlertRule {
+        above: None,
+        below: Some(10),
+        ..rule_above(0, None, 600)
+    };
+    let mut state = AlertState::default();
+    let start = Instant::now();
+
+    assert!(state.observe(&rule, 5, start));
+    assert!(!state.observe(&rule, 20, start + Duration::from_secs(60)));
+    assert!(
+        !state.observe(&rule, 5, start + Duration::from_secs(120)),
+        "re-armed but still cooling down"
+    );
+    assert!(state.observe(&rule, 5, start + Duration::from_secs(601)));
+}
+
+#[test]
+fn growth_rate_needs_a_minute_and_rolls_hourly() {
+    let mut state = AlertState::default();
+    let start = Instant::now();
+
+    assert_eq!(state.growth_per_hour(1_000, start), None, "first sample");
+    assert_eq!(
+        state.growth_per_hour(9_000, start + Duration::from_secs(30)),
+        None,
+        "window too short"
+    );
+    // +3 000 bytes over 15 minutes ⇒ 12 000 bytes per hour.
+    assert_eq!(
+        state.growth_per_hour(4_000, start + Duration::from_secs(900)),
+        Some(12_000)
+    );
+    // An hour in, the baseline rolls forward to this sample …
+    assert_eq!(
+        state.growth_per_hour(2_000, start + Duration::from_secs(3600)),
+        Some(1_000)
+    );
+    // … so shrinkage since then reads as no growth.
+    assert_eq!(
+        state.growth_per_hour(1_500, start + Duration::from_secs(3700)),
+        Some(0)
+    );
+}
+
+#[tokio::test]
+async fn treesize_rule_emits_alert_and_appends_sink() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let sink = dir.path().join("alerts.jsonl");
+    let config = Config {
+        alerts: vec![AlertRule {
+            sink: Some(sink.clone()),
+            ..rule_above(1_000, None, 0)
+        }],
+        ..Config::default()
+    };
+    let (tx, _rx) = crate::events::event_channel();
+    let mut events = tx.subscribe();
+    let mgr = IndexManager::new(None, tx, Arc::new(config));
+    mgr.add_drive(drive_with_treesize()).await;
+    while events.try_recv().is_ok() {}
+
+    mgr.evaluate_alerts(DriveLetter::D.into()).await;
+    assert!(events.try_recv().is_err(), "rule is on C:, not D:");
+
+    mgr.evaluate_alerts(DriveLetter::C.into()).await;
+    let Ok(DaemonEvent::AlertFired {
+        rule,
+        metric,
+        value,
+        threshold,
+        ..
+    }) = events.try_recv()
+    else {
+        panic!("expected an AlertFired event");
+    };
+    assert_eq!(
+        (rule.as_str(), metric, threshold),
+        ("big", AlertMetric::Treesize, 1_000)
+    );
+    assert_eq!(value, 5_000);
+
+    mgr.evaluate_alerts(DriveLetter::C.into()).await;
+    assert!(events.try_recv().is_err(), "still breached, not re-armed");
+
+    // The sink is written on the blocking pool.
+    let mut lines = String::new();
+    for _ in 0_u32..100 {
+        lines = std::fs::read_to_string(&sink).unwrap_or_default();
+        if !lines.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let entry: serde_json::Value = serde_json::from_str(lines.trim_end()).expect("one JSON line");
+    assert_eq!(
+        entry.get("event").and_then(serde_json::Value::as_str),
+        Some("alert_fired")
+    );
+    assert_eq!(
+        entry.get("value").and_then(serde_json::Value::as_u64),
+        Some(value)
+    );
+}
+
+/// Manager with `rules` configured and [`drive_with_treesize`] loaded,
+/// plus a receiver drained of the load events.
+async fn manager_with_rules(rules: Vec<AlertRule>) -> (IndexManager, crate::events::EventReceiver) {
+    let config = Config {
+        alerts: rules,
+        ..Config::default()
+    };
+    let (tx, _rx) = crate::events::event_channel();
+    let mut events = tx.subscribe();
+    let mgr = IndexManager::new(None, tx, Arc::new(config));
+    mgr.add_drive(drive_with_treesize()).await;
+    while events.try_recv().is_ok() {}
+    (mgr, events)
+}
+
+/// The value of the next event if it is an `AlertFired`.
+fn fired_value(events: &mut crate::events::EventReceiver) -> Option<u64> {
+    match events.try_recv() {
+        Ok(DaemonEvent::AlertFired { value, .. }) => Some(value),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn bare_drive_rule_watches_the_volume_root() {
+    for path in ["C:", "C:\\"] {
+        let (mgr, mut events) = manager_with_rules(vec![AlertRule {
+            path: path.to_owned(),
+            ..rule_above(1_000, None, 0)
+        }])
+        .await;
+        mgr.evaluate_alerts(DriveLetter::C.into()).await;
+        assert_eq!(fired_value(&mut events), Some(5_000), "{path}");
+    }
+}
+
+#[tokio::test]
+async fn hosted_rule_ignores_the_local_volume_with_its_letter() {
+    let (mgr, mut events) = manager_with_rules(vec![AlertRule {
+        path: "srv01\\C:\\Projects".to_owned(),
+        ..rule_above(1_000, None, 0)
+    }])
+    .await;
+    mgr.evaluate_alerts(DriveLetter::C.into()).await;
+    assert_eq!(fired_value(&mut events), None);
+}
+
+/// The resolved record is reused across index swaps only while its
+/// parent chain still spells the rule's path.
+#[tokio::test]
+async fn resolved_record_follows_renames_across_index_swaps() {
+    let (mgr, mut events) = manager_with_rules(vec![rule_above(1_000, Some(100), 0)]).await;
+    mgr.evaluate_alerts(DriveLetter::C.into()).await;
+    assert_eq!(fired_value(&mut events), Some(5_000));
+
+    // `Projects` is renamed away and an empty one takes its name: the
+    // cached position now holds `Archive`, so the path is walked again
+    // and the empty folder re-arms the rule.
+    mgr.replace_drive(
+        DriveLetter::C.into(),
+        drive_of(&[
+            ("Archive", 100, ROOT_FRS, None),
+            ("big.iso", 101, 100, Some(5_000)),
+            ("Projects", 102, ROOT_FRS, None),
+        ]),
+    )
+    .await;
+    mgr.evaluate_alerts(DriveLetter::C.into()).await;
+    assert_eq!(fired_value(&mut events), None);
+
+    mgr.replace_drive(DriveLetter::C.into(), drive_with_treesize())
+        .await;
+    mgr.evaluate_alerts(DriveLetter::C.into()).await;
+    assert_eq!(
+        fired_value(&mut events),
+        Some(5_000),
+        "re-armed by the rename"
+    );
+}
+
+/// A command that outlives its timeout is killed rather than holding a
+/// task forever; one that finishes in time reports its exit.
+#[cfg(unix)]
+#[tokio::test]
+async fn alert_command_is_killed_at_its_timeout() {
+    let started = Instant::now();
+    let slow = run_alert_command(
+        "slow",
+        "sleep",
+        &["30".to_owned()],
+        "{}",
+        Duration::from_millis(200),
+    )
+    .await;
+    assert_eq!(slow, CommandEnd::Killed);
+    assert!(
+        started.elapsed() < Duration::from_secs(10),
+        "did not wait the sleep out"
+    );
+
+    let quick = run_alert_command("quick", "true", &[], "{}", Duration::from_secs(10)).await;
+    assert!(matches!(quick, CommandEnd::Exited(status) if status.success()));
+}
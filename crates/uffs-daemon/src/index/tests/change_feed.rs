@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Change-feed tests for [`super::IndexManager`]: an applied journal
+//! batch lands in the change log with paths resolved against the right
+//! body (pre-patch for deletes and rename sources, post-patch for
+//! everything else), and `tail_changes` reads it back.
+
+#![expect(
+    clippy::std_instead_of_alloc,
+    reason = "test fixtures — `std::sync::Arc` matches the rest of the daemon's \
+              test fixtures, no need to switch to `alloc::sync::Arc` for tests"
+)]
+
+use std::sync::Arc;
+
+use uffs_client::protocol::response::{ChangeLogKind, TailChangesParams};
+use uffs_mft::platform::DriveLetter;
+use uffs_mft::usn::FileChange;
+
+use super::{IndexManager, build_test_drive};
+use crate::config::{ChangeLogConfig, Config};
+use crate::index::change_feed::ChangeFeedError;
+
+fn tail(drive: DriveLetter, after_seq: u64) -> TailChangesParams {
+    TailChangesParams {
+        drive,
+        after_seq,
+        max_entries: None,
+    }
+}
+
+#[tokio::test]
+async fn applied_batch_is_logged_with_resolved_paths() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let config = Config {
+        change_log: ChangeLogConfig {
+            enabled: true,
+            dir: Some(tmp.path().to_path_buf()),
+            ..ChangeLogConfig::default()
+        },
+        ..Config::default()
+    };
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(config));
+    mgr.add_drive(build_test_drive()).await;
+
+    let batch = vec![
+        FileChange {
+            frs: 103.into(),
+            deleted: true,
+            ..FileChange::default()
+        },
+        FileChange {
+            frs: 104.into(),
+            parent_frs: 100.into(),
+            filename: "settings.toml".to_owned(),
+            renamed: true,
+            ..FileChange::default()
+        },
+        FileChange {
+            frs: 102.into(),
+            size_changed: true,
+            ..FileChange::default()
+        },
+        FileChange {
+            frs: 106.into(),
+            parent_frs: 100.into(),
+            filename: "new.txt".to_owned(),
+            created: true,
+            ..FileChange::default()
+        },
+    ];
+    assert!(
+        mgr.handle_journal_apply(DriveLetter::C, "test", batch)
+            .await
+    );
+
+    let feed = mgr
+        .tail_changes(&tail(DriveLetter::C, 0))
+        .await
+        .expect("tail");
+    let got: Vec<(u64, ChangeLogKind, &str, Option<&str>)> = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.seq,
+                entry.kind,
+                entry.path.as_str(),
+                entry.old_path.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(got, [
+        (1, ChangeLogKind::Deleted, "C:\\Projects\\lib.rs", None),
+        (
+            2,
+            ChangeLogKind::Renamed,
+            "C:\\Projects\\settings.toml",
+            Some("C:\\Projects\\config.toml")
+        ),
+        (3, ChangeLogKind::Modified, "C:\\Projects\\main.rs", None),
+        (4, ChangeLogKind::Created, "C:\\Projects\\new.txt", None),
+    ]);
+    assert!(feed.complete && !feed.truncated);
+    assert_eq!(feed.next_seq, 4);
+
+    let caught_up = mgr
+        .tail_changes(&tail(DriveLetter::C, 4))
+        .await
+        .expect("tail");
+    assert!(caught_up.entries.is_empty());
+}
+
+#[tokio::test]
+async fn tail_changes_reports_a_disabled_log() {
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(Config::default()));
+    assert!(matches!(
+        mgr.tail_changes(&tail(DriveLetter::C, 0)).await,
+        Err(ChangeFeedError::Disabled)
+    ));
+}
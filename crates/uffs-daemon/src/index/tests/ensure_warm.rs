@@ -63,11 +63,11 @@ async fn ensure_warm_for_dispatch_no_op_when_all_warm() {
     // Empty filter → all touched.  Non-empty filter → subset.
     // Either way, no shard is Parked/Cold so this is a no-op.
     mgr.ensure_warm_for_dispatch(&[], &[]).await;
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await; // case-insensitive
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::Z], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::Z.into()], &[])
         .await; // unknown letter
 
     let states_after = mgr.shard_states_for_test().await;
@@ -99,7 +99,7 @@ async fn ensure_warm_for_dispatch_skips_parked_shard_outside_filter() {
 
     // Search targets only D — C must stay Parked.  The on-disk
     // cache lookup for D would no-op because D is already Warm.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::D], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::D.into()], &[])
         .await;
 
     let states_post = mgr.shard_states_for_test().await;
@@ -142,7 +142,7 @@ async fn ensure_warm_for_dispatch_promotes_with_fixed_body_loader() {
     );
 
     // Promote via ensure_warm_for_dispatch.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
 
     // Shard is Warm again AND the heap-bytes metric is back to its
@@ -185,7 +185,7 @@ async fn ensure_warm_for_dispatch_handles_missing_cache_gracefully() {
     )]);
 
     // Loader returns None → graceful failure path.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
 
     let states_post = mgr.shard_states_for_test().await;
@@ -213,7 +213,7 @@ async fn ensure_warm_for_dispatch_handles_panicking_body_loader_gracefully() {
     );
 
     // Loader panics → JoinError arm runs → shard stays Parked.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
 
     let states = mgr.shard_states_for_test().await;
@@ -225,7 +225,7 @@ async fn ensure_warm_for_dispatch_handles_panicking_body_loader_gracefully() {
 
     // Subsequent ensure_warm_for_dispatch on the same manager
     // still works (no global daemon state corruption).
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
     let states_again = mgr.shard_states_for_test().await;
     assert_eq!(
@@ -293,9 +293,9 @@ async fn ensure_warm_for_dispatch_promotes_in_parallel() {
 
     mgr.ensure_warm_for_dispatch(
         &[
-            uffs_mft::platform::DriveLetter::C,
-            uffs_mft::platform::DriveLetter::D,
-            uffs_mft::platform::DriveLetter::E,
+            uffs_mft::platform::DriveLetter::C.into(),
+            uffs_mft::platform::DriveLetter::D.into(),
+            uffs_mft::platform::DriveLetter::E.into(),
         ],
         &[],
     )
@@ -436,8 +436,10 @@ async fn ensure_warm_for_dispatch_keeps_parked_when_bloom_misses() {
     // existing `ensure_warm_for_dispatch_keeps_parked_on_panicking_loader`
     // test which establishes the catch_unwind contract; here we rely
     // on it as a known-good infrastructure.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &["csv".to_owned()])
-        .await;
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[
+        "csv".to_owned()
+    ])
+    .await;
 
     let states_post = mgr.shard_states_for_test().await;
     assert_eq!(
@@ -481,8 +483,10 @@ async fn ensure_warm_for_dispatch_promotes_parked_when_bloom_hits() {
     // `rs` IS in the drive (`main.rs`, `lib.rs`).  Bloom hits →
     // bloom-pre-check returns true → loader is called → returns the
     // fresh body → shard transitions to Warm.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &["rs".to_owned()])
-        .await;
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[
+        "rs".to_owned()
+    ])
+    .await;
 
     let states_post = mgr.shard_states_for_test().await;
     assert_eq!(
@@ -565,7 +569,7 @@ async fn ensure_warm_for_dispatch_dedupes_concurrent_promotes_for_same_letter()
         let mgr_clone = Arc::clone(&mgr);
         handles.push(tokio::spawn(async move {
             mgr_clone
-                .ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+                .ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
                 .await;
         }));
     }
@@ -629,7 +633,7 @@ async fn ensure_warm_for_dispatch_slot_clears_so_re_promote_after_demote_loads_f
         mgr.demote_letter_for_test(uffs_mft::platform::DriveLetter::C, ShardState::Parked)
             .await
     );
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
     assert_eq!(
         loader.call_count(),
@@ -651,7 +655,7 @@ async fn ensure_warm_for_dispatch_slot_clears_so_re_promote_after_demote_loads_f
         mgr.demote_letter_for_test(uffs_mft::platform::DriveLetter::C, ShardState::Parked)
             .await
     );
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
 
     // The fresh load contract: re-promote after the cleanup task
@@ -705,7 +709,7 @@ async fn ensure_warm_for_dispatch_dedupes_concurrent_failures_for_same_letter()
         let mgr_clone = Arc::clone(&mgr);
         handles.push(tokio::spawn(async move {
             mgr_clone
-                .ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+                .ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
                 .await;
         }));
     }
@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `growth` aggregate tests: a snapshot diff compares folder tree metrics
+//! against the `@<name>` baseline, and outside a diff the spec is skipped.
+
+#![expect(
+    clippy::std_instead_of_alloc,
+    reason = "test fixtures — `std::sync::Arc` matches the rest of the daemon's \
+              test fixtures, no need to switch to `alloc::sync::Arc` for tests"
+)]
+
+use std::sync::Arc;
+
+use uffs_client::protocol::{AggregateSpecWire, SearchParams};
+use uffs_core::aggregate::GrowthRank;
+use uffs_core::compact::build_compact_index;
+use uffs_mft::index::{IndexNameRef, MftIndex, ROOT_FRS, SizeInfo};
+use uffs_mft::platform::{DriveLetter, VolumeId};
+
+use super::IndexManager;
+use crate::cache::snapshots::snapshot_name;
+use crate::config::{Config, SnapshotsConfig};
+use crate::index::growth::split_growth_specs;
+
+/// Drive C with `C:\Projects` and `C:\Media`, each holding one file of
+/// the given size, tree metrics computed.
+fn drive(projects: u64, media: u64) -> uffs_core::compact::DriveCompactIndex {
+    let mut idx = MftIndex::new(DriveLetter::C);
+    let root_off = idx.add_name(".");
+    let root = idx.get_or_create(ROOT_FRS.into());
+    root.stdinfo.set_directory(true);
+    root.first_name.name = IndexNameRef::new(root_off, 1, true, IndexNameRef::NO_EXTENSION);
+    root.first_name.parent_frs = Into::into(ROOT_FRS);
+
+    for (name, frs, parent, size) in [
+        ("Projects", 100, ROOT_FRS, None),
+        ("Media", 200, ROOT_FRS, None),
+        ("build.log", 101, 100, Some(projects)),
+        ("song.flac", 201, 200, Some(media)),
+    ] {
+        let off = idx.add_name(name);
+        let ext = idx.intern_extension(name);
+        let rec = idx.get_or_create(frs.into());
+        rec.first_name.name = IndexNameRef::new(off, uffs_mft::len_to_u16(name.len()), true, ext);
+        rec.first_name.parent_frs = Into::into(parent);
+        if let Some(length) = size {
+            rec.first_stream.size = SizeInfo {
+                length,
+                allocated: length,
+            };
+        } else {
+            rec.stdinfo.set_directory(true);
+        }
+    }
+    idx.compute_tree_metrics();
+
+    let (drive, _, _) = build_compact_index(DriveLetter::C, &idx);
+    drive
+}
+
+fn growth_search(baseline: Option<String>) -> SearchParams {
+    SearchParams {
+        pattern: "*".to_owned(),
+        drives: vec![VolumeId::from(DriveLetter::C)],
+        diff_baseline: baseline,
+        aggregations: vec![
+            AggregateSpecWire {
+                kind: "raw".to_owned(),
+                label: Some("growth:path,depth=1,rank=rel".to_owned()),
+                ..AggregateSpecWire::default()
+            },
+            AggregateSpecWire {
+                kind: "count".to_owned(),
+                ..AggregateSpecWire::default()
+            },
+        ],
+        include_rows: false,
+        ..SearchParams::default()
+    }
+}
+
+#[tokio::test]
+async fn diff_growth_ranks_folders_against_the_snapshot() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let config = Config {
+        snapshots: SnapshotsConfig {
+            enabled: true,
+            dir: Some(tmp.path().to_path_buf()),
+            ..SnapshotsConfig::default()
+        },
+        ..Config::default()
+    };
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(config));
+    mgr.add_drive(drive(1_000, 4_000)).await;
+
+    let now = crate::cache::unix_now_ms();
+    mgr.take_due_snapshots(now).await;
+    mgr.replace_drive(DriveLetter::C.into(), drive(3_000, 5_000))
+        .await;
+
+    let name = snapshot_name(now, &chrono::Local);
+    let Ok(response) = mgr
+        .diff_search(&growth_search(Some(format!("@{name}"))))
+        .await
+    else {
+        panic!("snapshot diff");
+    };
+    let kinds: Vec<&str> = response
+        .aggregations
+        .iter()
+        .map(|result| result.kind.as_str())
+        .collect();
+    assert_eq!(kinds, ["count", "growth"], "growth appended after the rest");
+
+    let growth = response.aggregations.last().expect("growth result");
+    assert_eq!(growth.field.as_deref(), Some("relative"));
+    assert_eq!(growth.total_groups, Some(2));
+    let rows: Vec<(&str, u64, i64, &str)> = growth
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let delta = bucket.growth.as_ref().expect("growth detail");
+            (
+                bucket.key.as_str(),
+                bucket.total_bytes,
+                delta.delta_bytes,
+                delta.matched.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(rows, [
+        ("C:\\Projects", 3_000, 2_000, "file_ref"),
+        ("C:\\Media", 5_000, 1_000, "file_ref"),
+    ]);
+}
+
+#[tokio::test]
+async fn growth_outside_a_diff_is_skipped() {
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(Config::default()));
+    mgr.add_drive(drive(1_000, 4_000)).await;
+
+    let response = mgr.search(&growth_search(None)).await;
+    let kinds: Vec<&str> = response
+        .aggregations
+        .iter()
+        .map(|result| result.kind.as_str())
+        .collect();
+    assert_eq!(kinds, ["count"]);
+}
+
+#[test]
+fn structured_growth_specs_carry_depth_and_rank_in_their_own_fields() {
+    let specs = [
+        AggregateSpecWire {
+            kind: "growth".to_owned(),
+            depth: Some(3),
+            rank: Some("rel".to_owned()),
+            top: Some(5),
+            ..AggregateSpecWire::default()
+        },
+        AggregateSpecWire {
+            kind: "growth".to_owned(),
+            rank: Some("sideways".to_owned()),
+            ..AggregateSpecWire::default()
+        },
+        AggregateSpecWire {
+            kind: "histogram".to_owned(),
+            field: Some("size".to_owned()),
+            interval: Some(1_024),
+            ..AggregateSpecWire::default()
+        },
+    ];
+    let (growth, rest) = split_growth_specs(&specs);
+
+    let [spec] = growth.as_slice() else {
+        panic!("the unknown rank is dropped: {growth:?}");
+    };
+    assert_eq!(
+        (spec.depth, spec.top, spec.rank),
+        (3, 5, GrowthRank::Relative)
+    );
+    let intervals: Vec<Option<u64>> = rest.iter().map(|ws| ws.interval).collect();
+    assert_eq!(intervals, [Some(1_024)]);
+}
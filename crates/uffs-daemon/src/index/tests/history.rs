@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Point-in-time history tests: checkpoints plus replayed journal
+//! batches rebuild the right state, retention prunes old checkpoints,
+//! and `as_of` / `@<time>` searches run over the rebuilt bodies.
+
+#![expect(
+    clippy::std_instead_of_alloc,
+    reason = "test fixtures — `std::sync::Arc` matches the rest of the daemon's \
+              test fixtures, no need to switch to `alloc::sync::Arc` for tests"
+)]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::response::{SearchPayload, SearchResponse};
+use uffs_core::compact::DriveCompactIndex;
+use uffs_core::search::backend::DriveIndex;
+use uffs_mft::platform::{DriveLetter, VolumeId};
+use uffs_mft::usn::FileChange;
+
+use super::{IndexManager, build_test_drive};
+use crate::cache::history::{History, HistoryError};
+use crate::cache::volume_store::VolumeRoot;
+use crate::config::{Config, HistoryConfig};
+
+fn history_config(dir: &Path) -> HistoryConfig {
+    HistoryConfig {
+        enabled: true,
+        dir: Some(dir.to_path_buf()),
+        ..HistoryConfig::default()
+    }
+}
+
+fn delete_main_rs() -> Vec<FileChange> {
+    vec![FileChange {
+        frs: 102.into(),
+        deleted: true,
+        ..FileChange::default()
+    }]
+}
+
+fn has_name(body: &DriveCompactIndex, name: &str) -> bool {
+    body.records
+        .as_slice()
+        .iter()
+        .any(|record| record.name(&body.names) == name)
+}
+
+fn names(response: SearchResponse) -> Vec<String> {
+    match response.payload {
+        SearchPayload::Empty => Vec::new(),
+        SearchPayload::InlineRows(rows) => rows.into_iter().map(|row| row.name).collect(),
+        other @ (SearchPayload::ShmemRows { .. }
+        | SearchPayload::InlineBlob(_)
+        | SearchPayload::ShmemBlob(_)) => {
+            panic!("small fixture must deliver inline rows, got {other:?}")
+        }
+    }
+}
+
+fn cached_as_of_index(mgr: &IndexManager, at_ms: u64) -> Arc<DriveIndex> {
+    let volume = VolumeId::from(DriveLetter::C);
+    let history = mgr.history.as_ref().expect("history enabled");
+    let point = history.point(volume, at_ms).expect("point");
+    mgr.cached_as_of(&[(volume, point)])
+        .expect("an as_of search fills the cache")
+}
+
+fn search(pattern: &str) -> SearchParams {
+    SearchParams {
+        pattern: pattern.to_owned(),
+        drives: vec![VolumeId::from(DriveLetter::C)],
+        ..SearchParams::default()
+    }
+}
+
+#[test]
+fn materialise_replays_batches_up_to_the_requested_moment() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let history = History::new(
+        VolumeRoot::new(tmp.path().to_path_buf()),
+        &history_config(tmp.path()),
+    );
+    let body = build_test_drive();
+    let volume = VolumeId::from(DriveLetter::C);
+    history
+        .record(volume, 1_000, &body, &[])
+        .expect("first batch checkpoints");
+    history
+        .record(volume, 2_000, &body, &delete_main_rs())
+        .expect("second batch is a delta");
+    assert_eq!(history.points(volume).expect("points"), [1_000]);
+
+    let before = history.materialise(volume, 1_500).expect("before delete");
+    assert!(has_name(&before, "main.rs"));
+    let after = history.materialise(volume, 2_500).expect("after delete");
+    assert!(!has_name(&after, "main.rs"));
+    assert!(has_name(&after, "lib.rs"));
+
+    assert!(matches!(
+        history.materialise(volume, 500),
+        Err(HistoryError::NoPoint { at_ms: 500, .. })
+    ));
+}
+
+#[test]
+fn moments_between_two_batches_resolve_to_one_point() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let history = History::new(
+        VolumeRoot::new(tmp.path().to_path_buf()),
+        &history_config(tmp.path()),
+    );
+    let body = build_test_drive();
+    let volume = VolumeId::from(DriveLetter::C);
+    history
+        .record(volume, 1_000, &body, &[])
+        .expect("checkpoint");
+    history
+        .record(volume, 2_000, &body, &delete_main_rs())
+        .expect("delta");
+
+    let point = |at_ms| history.point(volume, at_ms).expect("point");
+    assert_eq!(point(1_500), point(1_999));
+    assert_eq!(point(2_000), point(9_000));
+    assert_ne!(point(1_500), point(2_000));
+    assert_eq!(point(9_000).replayed_to, 2_000);
+}
+
+#[test]
+fn hosted_volumes_keep_their_own_history() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let history = History::new(
+        VolumeRoot::new(tmp.path().to_path_buf()),
+        &history_config(tmp.path()),
+    );
+    let body = build_test_drive();
+    let local = VolumeId::from(DriveLetter::C);
+    let hosted = VolumeId::hosted("srv01".parse().expect("host"), DriveLetter::C);
+    history.record(local, 1_000, &body, &[]).expect("local");
+    history.record(hosted, 3_000, &body, &[]).expect("hosted");
+
+    assert_eq!(history.points(local).expect("points"), [1_000]);
+    assert_eq!(history.points(hosted).expect("points"), [3_000]);
+    assert!(tmp.path().join("srv01@C").is_dir());
+    assert!(matches!(
+        history.materialise(hosted, 2_000),
+        Err(HistoryError::NoPoint { drive, .. }) if drive == hosted
+    ));
+}
+
+#[test]
+fn retention_prunes_old_checkpoints_but_keeps_the_newest() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let config = HistoryConfig {
+        checkpoint_hours: 1,
+        retention_days: 0,
+        ..history_config(tmp.path())
+    };
+    let history = History::new(VolumeRoot::new(tmp.path().to_path_buf()), &config);
+    let body = build_test_drive();
+    let hour_ms = 3_600_000;
+    let volume = VolumeId::from(DriveLetter::C);
+    history
+        .record(volume, 1_000, &body, &delete_main_rs())
+        .expect("record");
+    history
+        .record(volume, 1_000 + hour_ms, &body, &[])
+        .expect("record");
+
+    assert_eq!(history.points(volume).expect("points"), [1_000 + hour_ms]);
+    let leftover = std::fs::read_dir(tmp.path().join("C"))
+        .expect("drive dir")
+        .count();
+    assert_eq!(leftover, 2, "the pruned checkpoint's deltas go with it");
+}
+
+#[tokio::test]
+async fn as_of_and_history_diff_search_the_rebuilt_state() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let config = Config {
+        history: history_config(tmp.path()),
+        ..Config::default()
+    };
+    // Seed a checkpoint an hour ago, before the manager applies a delete;
+    // the delete then lands in that checkpoint's deltas.
+    let seeded = crate::cache::unix_now_ms() - 3_600_000;
+    History::new(VolumeRoot::new(tmp.path().to_path_buf()), &config.history)
+        .record(DriveLetter::C.into(), seeded, &build_test_drive(), &[])
+        .expect("seed checkpoint");
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(config));
+    mgr.add_drive(build_test_drive()).await;
+    assert!(
+        mgr.handle_journal_apply(DriveLetter::C, "test", delete_main_rs())
+            .await
+    );
+
+    assert!(names(mgr.search(&search("main.rs")).await).is_empty());
+    let then = SearchParams {
+        as_of: Some(seeded.to_string()),
+        ..search("main.rs")
+    };
+    let found = names(mgr.as_of_search(&then).await.expect("as_of"));
+    assert_eq!(found, ["main.rs"]);
+    let rebuilt = cached_as_of_index(&mgr, seeded);
+    let again = names(mgr.as_of_search(&then).await.expect("as_of again"));
+    assert_eq!(again, ["main.rs"]);
+    assert!(
+        Arc::ptr_eq(&rebuilt, &cached_as_of_index(&mgr, seeded)),
+        "a repeated as_of search reuses the rebuilt bodies"
+    );
+
+    let diff = SearchParams {
+        diff_baseline: Some(format!("@{seeded}")),
+        ..search("*")
+    };
+    let Ok(response) = mgr.diff_search(&diff).await else {
+        panic!("history diff");
+    };
+    assert_eq!(
+        names(response),
+        ["main.rs"],
+        "only the delete since the checkpoint"
+    );
+}
+
+#[tokio::test]
+async fn as_of_reports_disabled_history_and_bad_times() {
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(Config::default()));
+    let volume = VolumeId::from(DriveLetter::C);
+    assert!(matches!(
+        mgr.state_as_of(volume, "yesterday").await,
+        Err(HistoryError::Disabled)
+    ));
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let config = Config {
+        history: history_config(tmp.path()),
+        ..Config::default()
+    };
+    let (enabled_tx, _enabled_rx) = crate::events::event_channel();
+    let enabled = IndexManager::new(None, enabled_tx, Arc::new(config));
+    assert!(matches!(
+        enabled.state_as_of(volume, "someday").await,
+        Err(HistoryError::BadTime(_))
+    ));
+    assert!(matches!(
+        enabled.state_as_of(volume, "yesterday").await,
+        Err(HistoryError::NoPoint { .. })
+    ));
+}
@@ -102,7 +102,7 @@ async fn shard_transition_events_emitted_on_demote_and_promote() {
             .await
     );
     // Promote via ensure_warm_for_dispatch → expect one promote event.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
 
     let events = log.events();
@@ -398,7 +398,7 @@ async fn promote_resets_idle_clock_against_thrash() {
 
     // ── Step 3: Promote.  PR-f bumps `last_query_at_ms` to
     // ~`unix_now_ms()` inside the registry write-swap.
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
     assert_eq!(
         mgr.shard_states_for_test().await,
@@ -276,7 +276,7 @@ async fn ensure_warm_for_dispatch_invokes_prefetch_with_records_and_names_region
         Vec::<Vec<(usize, usize)>>::new()
     );
 
-    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C], &[])
+    mgr.ensure_warm_for_dispatch(&[uffs_mft::platform::DriveLetter::C.into()], &[])
         .await;
 
     // Shard promoted (the Phase-3 contract this test depends on).
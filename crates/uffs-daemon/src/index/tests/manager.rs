@@ -8,6 +8,7 @@
 //! * Phase 3.1 NUL fast path (`include_rows = false` suppression).
 //! * Zero-drive shutdown guard's reliance on `loaded_drive_letters`.
 //! * Drive-letter inference + live-drive marker classification.
+//! * `info` path lookup keyed by the full volume id, not the letter.
 //! * Phase 0 telemetry: `status` populates `rss_bytes` +
 //!   `mimalloc_committed_bytes`; `total_index_heap_bytes` matches the per-drive
 //!   breakdown.
@@ -283,3 +284,37 @@ async fn total_index_heap_bytes_matches_status_breakdown() {
         "status.index_heap_bytes must equal total_index_heap_bytes",
     );
 }
+
+/// A local `C:` and a hosted `srv01\C:` share a letter: `info` must
+/// resolve each path on its own volume, never on the first `C` found.
+#[test]
+fn info_lookup_tells_a_local_volume_from_a_hosted_one_with_the_same_letter() {
+    let mut hosted = build_test_drive();
+    hosted.host = Some("srv01".parse().expect("host"));
+    let snap = uffs_core::search::backend::DriveIndex {
+        drives: vec![Arc::new(hosted), Arc::new(build_test_drive())],
+    };
+
+    let local = IndexManager::info_tree_lookup(&snap, r"C:\Projects\main.rs").expect("local");
+    assert_eq!(
+        local.get("drive").and_then(serde_json::Value::as_str),
+        Some("C")
+    );
+    assert_eq!(
+        local.get("path").and_then(serde_json::Value::as_str),
+        Some(r"C:\Projects\main.rs")
+    );
+
+    let remote =
+        IndexManager::info_tree_lookup(&snap, r"srv01\C:\Projects\main.rs").expect("hosted");
+    assert_eq!(
+        remote.get("drive").and_then(serde_json::Value::as_str),
+        Some(r"srv01\C")
+    );
+    assert_eq!(
+        remote.get("path").and_then(serde_json::Value::as_str),
+        Some(r"srv01\C:\Projects\main.rs")
+    );
+
+    assert!(IndexManager::info_tree_lookup(&snap, r"srv02\C:\Projects\main.rs").is_none());
+}
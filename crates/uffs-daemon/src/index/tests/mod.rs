@@ -10,6 +10,12 @@
 //! * [`aggregate`] / [`aggregate_drilldown`] — `run_aggregations` handler tests
 //!   covering presets, terms+sample drilldown, pagination, query-cache
 //!   invalidation, and auto-concurrency.
+//! * [`alerts`] — `daemon.toml` threshold alerts: hysteresis, cooldown, growth
+//!   rate, and the evaluate → event → sink path.
+//! * [`change_feed`] — applied journal batches reaching the durable change log
+//!   with resolved paths, and `tail_changes`.
+//! * [`history`] — point-in-time checkpoints + replayed batches, retention, and
+//!   `as_of` / `@<time>` searches over the rebuilt state.
 //! * [`manager`] — search RPC, status (RSS / mimalloc), drive-letter inference,
 //!   and the live-marker check.
 //! * [`registry`] — `ShardRegistry` add/replace/remove, the legal transition
@@ -25,6 +31,10 @@
 //! * [`lifecycle_hooks`] — Phase 5 task 5.8 / 5.9 / 5.10 `WorkingSetTrim` +
 //!   `Prefetch` + `PressureSignal` injection tests, plus the `drives` RPC
 //!   tier-marker enumeration.
+//! * [`snapshots`] — the `[snapshots]` scheduler tick, `list_baselines`, and
+//!   `@<name>` diffs against a scheduled snapshot.
+//! * [`standing`] — standing-query (`subscribe_query`) evaluation and the
+//!   connection-scoped subscription registry.
 //! * [`tracing_capture`] — shared `tracing::Subscriber` scaffold (`EventLog` /
 //!   `CapturedEvent`) used by [`idle_demote_tracing`] and other
 //!   observability-contract tests.
@@ -47,15 +57,21 @@ use super::aggregation::AggregationRequest;
 
 mod aggregate;
 mod aggregate_drilldown;
+mod alerts;
 mod body_loader_fakes;
+mod change_feed;
 mod ensure_warm;
 mod forget_status;
+mod growth;
+mod history;
 mod idle_demote;
 mod idle_demote_tracing;
 mod lifecycle_hooks;
 mod manager;
 mod registry;
 mod shard_ttl_events;
+mod snapshots;
+mod standing;
 mod tiering_ops;
 // Exposed at `pub(crate)` so the shared `EventLog` / `CapturedEvent`
 // scaffold (already `pub(crate)`) can be imported from sibling test
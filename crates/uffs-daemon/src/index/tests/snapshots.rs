@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Scheduled snapshot tests: the scheduler tick writes one snapshot per
+//! slot, `list_baselines` reports it, and `@<name>` diffs resolve to it.
+
+#![expect(
+    clippy::std_instead_of_alloc,
+    reason = "test fixtures — `std::sync::Arc` matches the rest of the daemon's \
+              test fixtures, no need to switch to `alloc::sync::Arc` for tests"
+)]
+
+use std::sync::Arc;
+
+use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::response::SearchPayload;
+use uffs_mft::platform::{DriveLetter, VolumeId};
+use uffs_mft::usn::FileChange;
+
+use super::{IndexManager, build_test_drive};
+use crate::cache::history::HistoryError;
+use crate::cache::snapshots::snapshot_name;
+use crate::config::{Config, SnapshotsConfig};
+use crate::index::diff::DiffError;
+
+fn diff_since(baseline: &str) -> SearchParams {
+    SearchParams {
+        pattern: "*".to_owned(),
+        drives: vec![VolumeId::from(DriveLetter::C)],
+        diff_baseline: Some(baseline.to_owned()),
+        ..SearchParams::default()
+    }
+}
+
+#[tokio::test]
+async fn scheduled_snapshot_is_listed_and_diffable_by_name() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let config = Config {
+        snapshots: SnapshotsConfig {
+            enabled: true,
+            dir: Some(tmp.path().to_path_buf()),
+            ..SnapshotsConfig::default()
+        },
+        ..Config::default()
+    };
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(config));
+    mgr.add_drive(build_test_drive()).await;
+
+    let now = crate::cache::unix_now_ms();
+    mgr.take_due_snapshots(now).await;
+    mgr.take_due_snapshots(now + 1).await;
+    let listing = mgr.list_baselines().await;
+    assert!(listing.snapshots_enabled && !listing.history_enabled);
+    let [drive] = listing.drives.as_slice() else {
+        panic!("one registered drive, got {:?}", listing.drives);
+    };
+    let name = snapshot_name(now, &chrono::Local);
+    assert_eq!(drive.drive, DriveLetter::C);
+    assert_eq!(drive.snapshots.len(), 1, "one snapshot per daily slot");
+    assert_eq!(
+        drive.snapshots.first().map(|snap| snap.name.as_str()),
+        Some(name.as_str())
+    );
+
+    let delete = FileChange {
+        frs: 102.into(),
+        deleted: true,
+        ..FileChange::default()
+    };
+    assert!(
+        mgr.handle_journal_apply(DriveLetter::C, "test", vec![delete])
+            .await
+    );
+    let Ok(response) = mgr.diff_search(&diff_since(&format!("@{name}"))).await else {
+        panic!("snapshot diff");
+    };
+    let SearchPayload::InlineRows(rows) = response.payload else {
+        panic!("small fixture must deliver inline rows");
+    };
+    let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+    assert_eq!(names, ["main.rs"]);
+}
+
+#[tokio::test]
+async fn at_baseline_needs_snapshots_or_history() {
+    let (tx, _rx) = crate::events::event_channel();
+    let mgr = IndexManager::new(None, tx, Arc::new(Config::default()));
+    mgr.add_drive(build_test_drive()).await;
+    assert!(matches!(
+        mgr.diff_search(&diff_since("@yesterday")).await,
+        Err(DiffError::History(HistoryError::Disabled))
+    ));
+    let listing = mgr.list_baselines().await;
+    assert!(!listing.snapshots_enabled && !listing.history_enabled);
+}
@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Standing-query (`subscribe_query`) tests for [`super::IndexManager`].
+//!
+//! Pins the per-tick evaluation contract — only changed records that
+//! match are pushed, deletes match as they stood before the patch, the
+//! `changes` filter is honoured and each match names its owning
+//! connection — plus the registry rules: unsupported response shapes,
+//! the per-connection cap, and connection-scoped unsubscribe / teardown.
+
+#![expect(
+    clippy::std_instead_of_alloc,
+    reason = "test fixtures — `std::sync::Arc` matches the rest of the daemon's \
+              test fixtures, no need to switch to `alloc::sync::Arc` for tests"
+)]
+
+use std::sync::Arc;
+
+use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::response::{QueryChangeKind, SubscribeQueryParams};
+use uffs_core::compact::DriveCompactIndex;
+use uffs_mft::platform::DriveLetter;
+use uffs_mft::usn::FileChange;
+
+use super::{IndexManager, build_test_drive};
+use crate::events::{DaemonEvent, EventReceiver};
+use crate::index::standing::{MAX_SUBSCRIPTIONS_PER_CONNECTION, StandingQueryError};
+
+/// Manager with the synthetic drive C loaded, plus a receiver subscribed
+/// before any event fires.
+async fn manager_with_events() -> (IndexManager, EventReceiver) {
+    let (tx, _rx) = crate::events::event_channel();
+    let events = tx.subscribe();
+    let mgr = IndexManager::new(None, tx, Arc::new(crate::config::Config::default()));
+    mgr.add_drive(build_test_drive()).await;
+    (mgr, events)
+}
+
+fn subscription(pattern: &str, changes: Vec<QueryChangeKind>) -> SubscribeQueryParams {
+    SubscribeQueryParams {
+        search: SearchParams {
+            pattern: pattern.to_owned(),
+            ..SearchParams::default()
+        },
+        changes,
+    }
+}
+
+/// The loaded body of drive C, standing in for both the pre- and the
+/// post-patch body.
+async fn drive_c_body(mgr: &IndexManager) -> Arc<DriveCompactIndex> {
+    Arc::clone(mgr.snapshot().await.drives.first().expect("drive C loaded"))
+}
+
+/// Drain the `QueryMatch` events as `(connection, subscription, change, name)`.
+fn query_matches(events: &mut EventReceiver) -> Vec<(u64, u64, QueryChangeKind, String)> {
+    let mut matches = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let DaemonEvent::QueryMatch {
+            connection,
+            subscription_id,
+            change,
+            row,
+        } = event
+        {
+            matches.push((connection, subscription_id, change, row.name));
+        }
+    }
+    matches
+}
+
+#[tokio::test]
+async fn standing_query_pushes_only_matching_changed_rows() {
+    let (mgr, mut events) = manager_with_events().await;
+    let any_change = mgr
+        .subscribe_query(7, subscription("*.rs", Vec::new()))
+        .expect("subscribe");
+    let renames_only = mgr
+        .subscribe_query(8, subscription("*.rs", vec![QueryChangeKind::Renamed]))
+        .expect("subscribe");
+    assert_ne!(any_change, renames_only);
+
+    // main.rs created, readme.md created (no match), lib.rs deleted
+    // (matched in the pre-patch body), config.toml touched (no match).
+    let batch = [
+        FileChange {
+            frs: 102.into(),
+            created: true,
+            ..FileChange::default()
+        },
+        FileChange {
+            frs: 101.into(),
+            created: true,
+            ..FileChange::default()
+        },
+        FileChange {
+            frs: 103.into(),
+            deleted: true,
+            ..FileChange::default()
+        },
+        FileChange {
+            frs: 104.into(),
+            metadata_changed: true,
+            ..FileChange::default()
+        },
+    ];
+    let body = drive_c_body(&mgr).await;
+    mgr.pending_standing_matches(DriveLetter::C, &batch, Arc::clone(&body))
+        .expect("drive C is covered")
+        .emit(&body, mgr.event_sender());
+    assert_eq!(query_matches(&mut events), [
+        (
+            7,
+            any_change,
+            QueryChangeKind::Created,
+            "main.rs".to_owned()
+        ),
+        (7, any_change, QueryChangeKind::Deleted, "lib.rs".to_owned()),
+    ]);
+
+    // A drive the subscriptions do not cover captures nothing.
+    let narrowed = SubscribeQueryParams {
+        search: SearchParams {
+            drives: vec![uffs_mft::platform::VolumeId::local(DriveLetter::D)],
+            ..subscription("*.rs", Vec::new()).search
+        },
+        changes: Vec::new(),
+    };
+    mgr.unsubscribe_query(7, any_change);
+    mgr.subscribe_query(7, narrowed).expect("subscribe");
+    mgr.unsubscribe_query(8, renames_only);
+    assert!(
+        mgr.pending_standing_matches(DriveLetter::C, &batch, body)
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn standing_query_batch_is_empty_without_subscribers() {
+    let (mgr, _events) = manager_with_events().await;
+    let batch = [FileChange {
+        frs: 102.into(),
+        created: true,
+        ..FileChange::default()
+    }];
+    let body = drive_c_body(&mgr).await;
+    assert!(
+        mgr.pending_standing_matches(DriveLetter::C, &batch, body)
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn standing_query_registry_is_scoped_to_connection() {
+    let (mgr, _events) = manager_with_events().await;
+
+    let diff = SubscribeQueryParams {
+        search: SearchParams {
+            diff_baseline: Some("C.mft".to_owned()),
+            ..subscription("*", Vec::new()).search
+        },
+        changes: Vec::new(),
+    };
+    assert!(matches!(
+        mgr.subscribe_query(1, diff),
+        Err(StandingQueryError::Unsupported { .. })
+    ));
+    assert!(matches!(
+        mgr.subscribe_query(1, subscription(">(", Vec::new())),
+        Err(StandingQueryError::Invalid(_))
+    ));
+
+    let ids: Vec<u64> = (0..MAX_SUBSCRIPTIONS_PER_CONNECTION)
+        .map(|_| {
+            mgr.subscribe_query(1, subscription("*", Vec::new()))
+                .expect("under the cap")
+        })
+        .collect();
+    assert!(matches!(
+        mgr.subscribe_query(1, subscription("*", Vec::new())),
+        Err(StandingQueryError::TooMany)
+    ));
+    let other = mgr
+        .subscribe_query(2, subscription("*", Vec::new()))
+        .expect("cap is per connection");
+
+    let first = *ids.first().expect("ids");
+    assert!(!mgr.unsubscribe_query(2, first), "not the owner");
+    assert!(mgr.unsubscribe_query(1, first));
+    assert!(!mgr.unsubscribe_query(1, first), "already removed");
+
+    assert!(mgr.has_standing_queries(1));
+    mgr.drop_connection_queries(1);
+    assert!(!mgr.has_standing_queries(1));
+    assert!(mgr.has_standing_queries(2));
+    assert!(mgr.unsubscribe_query(2, other));
+}
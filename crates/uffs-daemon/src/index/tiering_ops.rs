@@ -137,8 +137,10 @@ impl IndexManager {
         let demotes: Vec<(uffs_mft::platform::DriveLetter, ShardState)> = {
             let guard = self.index.read().await;
             let mut to_demote: Vec<(uffs_mft::platform::DriveLetter, ShardState)> = Vec::new();
-            for shard in guard.iter() {
-                let drive = shard.drive;
+            // Host-qualified shards (offline captures) have no
+            // re-warm path and are never demoted.
+            for shard in guard.iter().filter(|shard| shard.drive.is_local()) {
+                let drive = shard.drive.letter();
                 if !drives.is_empty() && !drives.contains(&drive) {
                     continue;
                 }
@@ -259,7 +261,14 @@ impl IndexManager {
             guard
                 .iter()
                 .find(|shard| shard.drive == letter)
-                .map(|shard| (shard.drive, shard.state(), shard.body(), Arc::clone(shard)))
+                .map(|shard| {
+                    (
+                        shard.drive.letter(),
+                        shard.state(),
+                        shard.body(),
+                        Arc::clone(shard),
+                    )
+                })
         };
         let Some((drive, from_state, current_body, current_arc)) = snapshot else {
             return PreloadOutcome::UnknownDrive;
@@ -105,11 +105,15 @@ impl IndexManager {
         let config = Arc::clone(&self.config);
         let demotes: Vec<(uffs_mft::platform::DriveLetter, ShardState)> = {
             let guard = self.index.read().await;
+            // Host-qualified shards (offline captures) have no re-warm
+            // path, so they stay resident.
             guard
                 .iter()
+                .filter(|shard| shard.drive.is_local())
                 .filter(|shard| !shard.is_pinned(now_ms))
                 .filter_map(|shard| {
-                    evaluate_idle_demote(shard, now_ms, &config).map(|target| (shard.drive, target))
+                    evaluate_idle_demote(shard, now_ms, &config)
+                        .map(|target| (shard.drive.letter(), target))
                 })
                 .collect()
         };
@@ -212,9 +216,10 @@ impl IndexManager {
             let guard = self.index.read().await;
             guard
                 .iter()
+                .filter(|shard| shard.drive.is_local())
                 .filter(|shard| shard.state() == ShardState::Warm)
                 .filter(|shard| !shard.is_pinned(now_ms))
-                .map(|shard| (shard.drive, shard.stats.last_query_at_ms()))
+                .map(|shard| (shard.drive.letter(), shard.stats.last_query_at_ms()))
                 .min_by_key(|&(_, ts)| ts)
         };
         let (letter, _last_query_at_ms) = pick?;
@@ -389,7 +394,7 @@ fn evaluate_idle_demote(shard: &ShardEntry, now_ms: u64, config: &Config) -> Opt
     let warm_ttl_sec = thresholds.warm_to_parked_secs;
     let parked_ttl_sec = thresholds.parked_to_cold_secs;
 
-    match (target, min_tier_for_drive(shard.drive, config)) {
+    match (target, min_tier_for_drive(shard.drive.letter(), config)) {
         (Some(proposed), Some(floor)) if tier_rank(proposed) < tier_rank(floor) => {
             tracing::debug!(
                 target: "shard.ttl",
@@ -11,14 +11,18 @@
 //! [`uffs_core::aggregate::AggregateSpec`] mapping — so isolating it makes the
 //! transport contract obvious without changing any call site.
 //!
-//! Public surface is unchanged: callers still write
-//! `IndexManager::convert_wire_spec(ws)`.
+//! Public surface is unchanged apart from the computed-field scope:
+//! callers write `IndexManager::convert_wire_spec_in(ws, fields)`.
+
+use alloc::sync::Arc;
 
 use uffs_core::aggregate::TopHitsSpec;
+use uffs_core::search::computed::ComputedFields;
+use uffs_time::TimeZone;
 
 use crate::index::IndexManager;
 
-/// Typed errors produced by [`IndexManager::convert_wire_spec`] and
+/// Typed errors produced by [`IndexManager::convert_wire_spec_in`] and
 /// its helpers.
 ///
 /// Phase 5d migration of the previous `Result<_, String>` return type:
@@ -88,6 +92,13 @@ pub(crate) enum WireSpecError {
     /// [`parse_agg_spec`]: uffs_core::aggregate::parser::parse_agg_spec
     #[error("{0}")]
     RawSyntax(#[source] uffs_core::aggregate::ParseAggSpecError),
+    /// `tz` was neither an IANA zone (`Europe/Berlin`) nor an offset from
+    /// UTC (`UTC`, `+2`, `-05:30`).
+    #[error("invalid time zone: `{tz}`")]
+    InvalidTimeZone {
+        /// The offending zone as supplied on the wire.
+        tz: String,
+    },
     /// `kind` did not match any of the supported aggregate kinds.
     #[error("unknown aggregate kind: `{kind}`")]
     UnknownKind {
@@ -99,6 +110,20 @@ pub(crate) enum WireSpecError {
     /// drilldown must name the drive it belongs to.
     #[error("ancestor rollup requires a valid 'drive' letter")]
     AncestorDriveMissing,
+    /// `kind == "growth"` outside a snapshot diff. Growth compares a
+    /// baseline with the current index, so only `diff_search` runs it
+    /// (see [`crate::index::growth`]).
+    #[error("growth compares two indexes and needs a diff baseline")]
+    GrowthNeedsBaseline,
+    /// A `sub` nested a spec under (or below) one that is not a single
+    /// bucketed kind (terms, histogram, date histogram, range, rollup).
+    #[error("nested aggregations need bucketed kinds at every level (got `{parent}` > `{child}`)")]
+    NestedNotBucketed {
+        /// The parent's wire kind.
+        parent: String,
+        /// The child's wire kind.
+        child: String,
+    },
 }
 
 impl IndexManager {
@@ -107,27 +132,78 @@ impl IndexManager {
     /// more core [`uffs_core::aggregate::AggregateSpec`]s.
     ///
     /// Presets expand to multiple specs; all other kinds produce
-    /// exactly one.
+    /// exactly one. A wire `sub` converts recursively and nests under
+    /// the deepest level of this spec (so it composes with a raw
+    /// `parent>child` chain).
     ///
     /// # Errors
     ///
     /// Returns [`WireSpecError`] when the wire spec is missing a
-    /// required field, names an unknown preset / calendar / kind, or
-    /// fails the inner `parse_agg_spec` (for `kind: "raw"`).  The
+    /// required field, names an unknown preset / calendar / kind / zone, fails
+    /// the inner `parse_agg_spec` (for `kind: "raw"`), or nests a kind
+    /// without buckets.  The
     /// [`core::fmt::Display`] string stays byte-identical with the
     /// pre-Phase-5d `String` payload so operator-facing log lines are
     /// unchanged.
     ///
     /// [`uffs_core::aggregate::AggregateSpec`]: uffs_core::aggregate::spec::AggregateSpec
     /// [`uffs_client::protocol::aggregate_wire::AggregateSpecWire`]: uffs_client::protocol::AggregateSpecWire
+    ///
+    /// `fields` are the request's computed fields: a `stats` / `histogram`
+    /// / `range` level (or a raw spec) may name one where it would take a
+    /// numeric field.
+    pub(crate) fn convert_wire_spec_in(
+        ws: &uffs_client::protocol::AggregateSpecWire,
+        fields: &ComputedFields,
+    ) -> Result<Vec<uffs_core::aggregate::spec::AggregateSpec>, WireSpecError> {
+        let specs = Self::convert_wire_level(ws, fields)?;
+        let mut nested = match ws.sub.as_deref() {
+            None => specs,
+            Some(child) => {
+                let subs = Self::convert_wire_spec_in(child, fields)?;
+                match (<[_; 1]>::try_from(specs), <[_; 1]>::try_from(subs)) {
+                    (Ok([parent]), Ok([sub]))
+                        if parent.kind.is_bucketed() && sub.kind.is_bucketed() =>
+                    {
+                        vec![parent.with_sub(sub)]
+                    }
+                    _ => {
+                        return Err(WireSpecError::NestedNotBucketed {
+                            parent: ws.kind.clone(),
+                            child: child.kind.clone(),
+                        });
+                    }
+                }
+            }
+        };
+        if let Some(tz) = ws.tz.as_deref() {
+            let zone = TimeZone::parse(tz)
+                .ok_or_else(|| WireSpecError::InvalidTimeZone { tz: tz.to_owned() })?;
+            for spec in &mut nested {
+                spec.apply_default_tz(zone);
+            }
+        }
+        Ok(nested)
+    }
+
+    /// [`Self::convert_wire_spec_in`] with no computed fields in scope.
+    #[cfg(test)]
+    pub(crate) fn convert_wire_spec(
+        ws: &uffs_client::protocol::AggregateSpecWire,
+    ) -> Result<Vec<uffs_core::aggregate::spec::AggregateSpec>, WireSpecError> {
+        Self::convert_wire_spec_in(ws, &ComputedFields::default())
+    }
+
+    /// Convert one wire level, ignoring its `sub`.
     #[expect(
         clippy::too_many_lines,
         reason = "straightforward match arms — one per wire kind"
     )]
-    pub(crate) fn convert_wire_spec(
+    fn convert_wire_level(
         ws: &uffs_client::protocol::AggregateSpecWire,
+        fields: &ComputedFields,
     ) -> Result<Vec<uffs_core::aggregate::spec::AggregateSpec>, WireSpecError> {
-        use uffs_core::aggregate::parser::parse_agg_spec;
+        use uffs_core::aggregate::parse_agg_spec_with;
         use uffs_core::aggregate::presets::AggregatePreset;
         use uffs_core::aggregate::spec::{
             AggregateKind, AggregateSpec, CalendarInterval, DuplicateVerify, RollupMode,
@@ -140,6 +216,19 @@ impl IndexManager {
             vec![spec]
         };
 
+        // A computed field is measured in place of a numeric one: convert
+        // the level as if it measured `size`, then bind the field.
+        if let Some(computed) = ws.field.as_deref().and_then(|name| fields.get(name))
+            && matches!(ws.kind.as_str(), "stats" | "histogram" | "hist" | "range")
+        {
+            let mut measured = ws.clone();
+            measured.field = Some("size".to_owned());
+            return Ok(Self::convert_wire_level(&measured, fields)?
+                .into_iter()
+                .map(|spec| spec.with_computed(Arc::clone(computed)))
+                .collect());
+        }
+
         match ws.kind.as_str() {
             "preset" => {
                 let name = ws
@@ -192,6 +281,7 @@ impl IndexManager {
                     field,
                     calendar,
                     metrics,
+                    time_zone: None,
                 }))
             }
             "range" => {
@@ -239,7 +329,6 @@ impl IndexManager {
                     top,
                     metrics,
                     sample: build_sample(ws),
-                    sub: None, // TODO: wire sub-agg from wire type
                 }))
             }
             "duplicates" | "dups" => {
@@ -259,6 +348,8 @@ impl IndexManager {
                         count: ws.verify_bytes.unwrap_or(4096),
                     },
                     Some("sha256") => DuplicateVerify::Sha256,
+                    Some("xxh3") => DuplicateVerify::Xxh3,
+                    Some("blake3") => DuplicateVerify::Blake3,
                     _ => DuplicateVerify::None,
                 };
                 Ok(make(AggregateKind::Duplicates {
@@ -271,9 +362,10 @@ impl IndexManager {
             }
             "raw" => {
                 let syntax = ws.label.as_deref().ok_or(WireSpecError::RawSyntaxMissing)?;
-                let spec = parse_agg_spec(syntax).map_err(WireSpecError::RawSyntax)?;
+                let spec = parse_agg_spec_with(syntax, fields).map_err(WireSpecError::RawSyntax)?;
                 Ok(vec![spec])
             }
+            "growth" => Err(WireSpecError::GrowthNeedsBaseline),
             other => Err(WireSpecError::UnknownKind {
                 kind: other.to_owned(),
             }),
@@ -300,8 +392,9 @@ fn require_field(
 /// Empty input falls back to the default `[Count, TotalBytes]` pair so
 /// `terms` / `histogram` / `range` / `rollup` / `date_histogram`
 /// always emit at least the two metrics every UFFS dashboard relies on.
+/// `pNN` / `median` request per-bucket size percentiles.
 fn parse_bucket_metrics(wire: &[String]) -> Vec<uffs_core::aggregate::spec::BucketMetric> {
-    use uffs_core::aggregate::spec::BucketMetric;
+    use uffs_core::aggregate::spec::{BucketMetric, PercentileRank};
     if wire.is_empty() {
         return vec![BucketMetric::Count, BucketMetric::TotalBytes];
     }
@@ -317,7 +410,7 @@ fn parse_bucket_metrics(wire: &[String]) -> Vec<uffs_core::aggregate::spec::Buck
             "max_size" | "max" => Some(BucketMetric::MaxSize),
             "share_count" | "share_of_count" => Some(BucketMetric::ShareOfTotalCount),
             "share_bytes" | "share_of_bytes" => Some(BucketMetric::ShareOfTotalBytes),
-            _ => None,
+            other => PercentileRank::parse(other).map(BucketMetric::SizePercentile),
         })
         .collect()
 }
@@ -326,8 +419,9 @@ fn parse_bucket_metrics(wire: &[String]) -> Vec<uffs_core::aggregate::spec::Buck
 ///
 /// Empty input expands to `[Sum, Min, Max, Avg]`, matching the
 /// behaviour every `stats` aggregation has shipped with since v0.5.
+/// `pNN` / `median` request percentiles of the field.
 fn parse_scalar_metrics(wire: &[String]) -> Vec<uffs_core::aggregate::spec::ScalarMetric> {
-    use uffs_core::aggregate::spec::ScalarMetric;
+    use uffs_core::aggregate::spec::{PercentileRank, ScalarMetric};
     if wire.is_empty() {
         return vec![
             ScalarMetric::Sum,
@@ -344,7 +438,7 @@ fn parse_scalar_metrics(wire: &[String]) -> Vec<uffs_core::aggregate::spec::Scal
             "avg" | "mean" => Some(ScalarMetric::Avg),
             "value_count" | "count" => Some(ScalarMetric::ValueCount),
             "missing_count" | "missing" => Some(ScalarMetric::MissingCount),
-            _ => None,
+            other => PercentileRank::parse(other).map(ScalarMetric::Percentile),
         })
         .collect()
 }
@@ -379,7 +473,7 @@ mod tests {
 
     use uffs_client::protocol::AggregateSpecWire;
 
-    use super::{IndexManager, WireSpecError};
+    use super::{IndexManager, TimeZone, WireSpecError};
 
     /// Make an empty wire spec with the given `kind` for happy-path
     /// rejection tests.
@@ -517,4 +611,31 @@ mod tests {
         let specs = IndexManager::convert_wire_spec(&spec).expect("valid ancestor rollup");
         assert_eq!(specs.len(), 1);
     }
+
+    #[test]
+    fn wire_tz_reaches_every_calendar_level_without_its_own() {
+        use uffs_core::aggregate::spec::AggregateKind;
+
+        let mut spec = ws("raw");
+        spec.label =
+            Some("datehist:modified,calendar=hour_of_day>datehist:created,tz=-5".to_owned());
+        spec.tz = Some("+2".to_owned());
+        let [converted] =
+            <[_; 1]>::try_from(IndexManager::convert_wire_spec(&spec).expect("valid spec"))
+                .expect("one spec");
+        let zone = |kind: &AggregateKind| {
+            if let AggregateKind::DateHistogram { time_zone, .. } = *kind {
+                time_zone
+            } else {
+                None
+            }
+        };
+        assert_eq!(zone(&converted.kind), Some(TimeZone::fixed(7200)));
+        let sub = converted.sub.as_deref().expect("nested level");
+        assert_eq!(zone(&sub.kind), Some(TimeZone::fixed(-18_000)));
+
+        spec.tz = Some("Mars/Olympus".to_owned());
+        let err = IndexManager::convert_wire_spec(&spec).expect_err("bad zone");
+        assert_eq!(err.to_string(), "invalid time zone: `Mars/Olympus`");
+    }
 }
@@ -7,6 +7,7 @@
 //! request handler, and writes responses back.
 
 use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
 use std::path::PathBuf;
 
 use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
@@ -32,6 +33,11 @@ const IDLE_CONNECTION_SECS: u64 = 300; // 5 minutes
 /// Per-connection rate limit: max queries per second (S4.4.6).
 const MAX_QUERIES_PER_SEC: u32 = 100;
 
+/// Source of per-connection ids.  Standing queries (`subscribe_query`) are
+/// owned by the connection that registered them; the id keys that
+/// ownership and routes `daemon.query_match` notifications back to it.
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
 /// IPC server for daemon-client communication.
 pub(crate) struct IpcServer;
 
@@ -174,14 +180,20 @@ impl IpcServer {
     ///   (single writer, no concurrent writes).
     ///
     /// Enforces:
-    /// - S4.4.8: 5-minute idle connection timeout
+    /// - S4.4.8: 5-minute idle connection timeout (waived while the connection
+    ///   holds standing queries — it is a listener)
     /// - S4.4.6: Per-connection rate limit (100 queries/sec)
+    ///
+    /// Each connection gets a fresh id from [`NEXT_CONNECTION_ID`]; its
+    /// standing queries are dropped when the reader exits.
     async fn handle_connection(
         reader: impl tokio::io::AsyncRead + Unpin + Send + 'static,
         writer: impl tokio::io::AsyncWrite + Unpin + Send + 'static,
         handler: Arc<RequestHandler>,
         event_rx: EventReceiver,
     ) -> anyhow::Result<()> {
+        let connection = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+
         // Outbound channel — both responses and notifications funnel here.
         let (out_tx, out_rx) = tokio::sync::mpsc::channel::<String>(128);
 
@@ -190,12 +202,14 @@ impl IpcServer {
 
         // ── Notification task: broadcast events → outbound channel ───
         let notif_tx = out_tx.clone();
-        let notif_task = tokio::spawn(Self::notification_loop(event_rx, notif_tx));
+        let notif_task = tokio::spawn(Self::notification_loop(event_rx, notif_tx, connection));
 
         // ── Reader task: reads requests → handler → outbound channel ─
-        let reader_result = Self::reader_loop(reader, handler, out_tx).await;
+        let reader_result = Self::reader_loop(reader, &handler, out_tx, connection).await;
 
-        // Reader done (client disconnected or error) — cancel helpers.
+        // Reader done (client disconnected or error) — drop its standing
+        // queries and cancel helpers.
+        handler.index.drop_connection_queries(connection);
         notif_task.abort();
         writer_task.abort();
 
@@ -210,8 +224,9 @@ impl IpcServer {
     )]
     async fn reader_loop(
         reader: impl tokio::io::AsyncRead + Unpin,
-        handler: Arc<RequestHandler>,
+        handler: &RequestHandler,
         out_tx: tokio::sync::mpsc::Sender<String>,
+        connection: u64,
     ) -> anyhow::Result<()> {
         let mut buf_reader = BufReader::new(reader);
         let mut line = String::new();
@@ -230,6 +245,7 @@ impl IpcServer {
             let bytes_read = match read_result {
                 Ok(Ok(count)) => count,
                 Ok(Err(io_err)) => return Err(io_err.into()),
+                Err(_) if handler.index.has_standing_queries(connection) => continue,
                 Err(_) => {
                     tracing::debug!(
                         "Idle connection timeout ({}s), disconnecting",
@@ -289,7 +305,7 @@ impl IpcServer {
                 }
             };
 
-            let response = handler.handle(&req).await;
+            let response = handler.handle(&req, connection).await;
             let mut msg = response;
             msg.push('\n');
             if out_tx.send(msg).await.is_err() {
@@ -300,7 +316,9 @@ impl IpcServer {
     }
 
     /// Subscribes to daemon events and forwards them as JSON-RPC
-    /// notifications to the outbound channel.
+    /// notifications to the outbound channel — every event except the
+    /// standing-query matches of other connections
+    /// ([`crate::events::DaemonEvent::is_for`]).
     #[expect(
         clippy::single_call_fn,
         reason = "structural separation — reader/writer/notifier split"
@@ -308,11 +326,13 @@ impl IpcServer {
     async fn notification_loop(
         mut event_rx: EventReceiver,
         out_tx: tokio::sync::mpsc::Sender<String>,
+        connection: u64,
     ) {
         loop {
             match event_rx.recv().await {
                 Ok(event) => {
-                    if let Some(json_line) = event_to_json_line(&event)
+                    if event.is_for(connection)
+                        && let Some(json_line) = event_to_json_line(&event)
                         && out_tx.send(json_line).await.is_err()
                     {
                         // Client disconnected.
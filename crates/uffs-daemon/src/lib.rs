@@ -48,6 +48,7 @@
 //!   clients.
 //! * `spawn_idle_demote_controller` — memory-pressure-driven shard-demote
 //!   signal source.
+//! * `spawn_snapshot_scheduler` — once-a-minute `[snapshots]` baseline check.
 //! * `spawn_journal_loops_for_warm_shards` — per-shard USN journal loops, each
 //!   cooperatively cancelled via a dedicated `watch::Sender<bool>`.
 //! * `spawn_pressure_subscriber` — listens to OS memory-pressure events and
@@ -114,6 +115,8 @@ mod lifecycle;
 mod live_update;
 /// JSON-RPC protocol types.
 mod protocol;
+/// Saved-query library (`queries.toml`) behind the `*_query` RPCs.
+mod queries;
 /// Phase 2b memory-tiering: runtime-tempfile orphan cleanup at boot.
 mod runtime_orphans;
 /// Process-level memory and runtime telemetry.
@@ -211,6 +214,7 @@ pub async fn run_daemon(config: DaemonConfig) -> anyhow::Result<()> {
     // `run_daemon`'s cognitive complexity under the workspace's
     // strict-clippy ceiling.
     let daemon_config = startup::load_daemon_config()?;
+    startup::install_path_template(&daemon_config)?;
 
     // Create index manager — uses the user-supplied --data-dir for offline MFT
     // discovery and hot-loading (not the lifecycle directory).
@@ -222,6 +226,9 @@ pub async fn run_daemon(config: DaemonConfig) -> anyhow::Result<()> {
     tracing::debug!(index_data_dir = ?idx.data_dir(), "Index manager created");
 
     let mft_files = startup::gather_mft_files(&config);
+    // Every host the daemon serves is registered by now; requests naming
+    // any other are rejected instead of interning their labels.
+    uffs_mft::platform::HostLabel::seal();
     let drives = startup::resolve_drive_list(&config);
     tracing::info!(mft_files = mft_files.len(), drives = ?drives, "Final data sources");
 
@@ -247,6 +254,7 @@ pub async fn run_daemon(config: DaemonConfig) -> anyhow::Result<()> {
     );
     // Phase 3 Commit D — periodic shard idle-demote sweep.
     let _idle_demote_task = spawn_idle_demote_controller(Arc::clone(&idx));
+    let _snapshot_task = spawn_snapshot_scheduler(Arc::clone(&idx));
 
     // Phase 7 activation: per-shard journal loops are spawned
     // inside `spawn_load_task` after `record_load_complete()`.  They
@@ -272,7 +280,7 @@ pub async fn run_daemon(config: DaemonConfig) -> anyhow::Result<()> {
 /// fails every load doesn't linger in `Ready` with an empty index.
 fn spawn_load_task(
     load_index: Arc<index::IndexManager>,
-    mft_files: Vec<PathBuf>,
+    mft_files: Vec<(PathBuf, Option<uffs_mft::platform::VolumeId>)>,
     drives: Vec<uffs_mft::platform::DriveLetter>,
     device_sources: Vec<(String, uffs_mft::platform::DriveLetter)>,
     no_cache: bool,
@@ -366,7 +374,7 @@ async fn zero_drive_shutdown_guard(
     load_index: &Arc<index::IndexManager>,
     load_lifecycle: &lifecycle::LifecycleHandle,
 ) {
-    let loaded_drives = load_index.loaded_drive_letters().await;
+    let loaded_drives = load_index.loaded_volumes().await;
     if loaded_drives.is_empty() {
         tracing::error!(
             "Daemon loaded zero drives even though data sources were provided — every \
@@ -540,6 +548,19 @@ fn spawn_idle_demote_controller(idx: Arc<index::IndexManager>) -> tokio::task::J
     })
 }
 
+/// Spawn the `[snapshots]` scheduler — once a minute, write a baseline
+/// snapshot of every resident drive whose daily slot has passed
+/// (`IndexManager::take_due_snapshots`; a no-op when disabled).
+fn spawn_snapshot_scheduler(idx: Arc<index::IndexManager>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(core::time::Duration::from_mins(1));
+        loop {
+            interval.tick().await;
+            idx.take_due_snapshots(cache::unix_now_ms()).await;
+        }
+    })
+}
+
 /// Phase 7 activation: spawn one [`crate::cache::journal_loop::JournalLoop`]
 /// per loaded drive letter.
 ///
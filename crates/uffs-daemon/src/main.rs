@@ -35,6 +35,7 @@ static GLOBAL: MiMalloc = MiMalloc;
 // Suppress unused-crate-dependency warnings for deps consumed by the
 // library crate (lib.rs) rather than the binary.
 use anyhow as _;
+use chrono as _;
 use clap::Parser;
 use dirs_next as _;
 use futures as _;
@@ -73,6 +74,7 @@ use uffs_core as _;
 use uffs_format as _;
 use uffs_mft as _;
 use uffs_security as _;
+use uffs_time as _;
 #[cfg(windows)]
 use windows as _;
 
@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `queries.toml` — the daemon-side saved-query library.
+//!
+//! Lives next to `daemon.toml` (see [`crate::config::Config::default_path`])
+//! and maps each query name to a
+//! [`SavedQuery`](uffs_client::protocol::response::SavedQuery):
+//!
+//! ```toml
+//! [stale-vms]
+//! description = "VM images untouched for a while"
+//! args = ["*", "--ext", "vmdk,vhdx,vdi", "--older", "{age}", "--sort", "size"]
+//!
+//! [stale-vms.defaults]
+//! age = "90d"
+//! ```
+//!
+//! The file is re-read on every RPC, so hand edits take effect without a
+//! daemon restart; writes go through [`uffs_mft::cache::atomic_write`]
+//! under a process-wide lock so two concurrent `save_query` calls cannot
+//! lose each other's entry.  A missing file is an empty library.
+
+use alloc::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::response::{
+    DeleteQueryResponse, QueryError, SaveQueryParams, SaveQueryResponse, SavedQuery,
+    validate_query_name,
+};
+
+use crate::config::Config;
+
+/// File name of the library, in the same directory as `daemon.toml`.
+const QUERIES_FILE: &str = "queries.toml";
+
+/// Serialises read-modify-write cycles on the library file.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// The whole library, keyed and sorted by name.
+pub(crate) type QueryLibrary = BTreeMap<String, SavedQuery>;
+
+/// Handle on one `queries.toml` file.
+#[derive(Debug, Clone)]
+pub(crate) struct QueryStore {
+    /// Path of the library file.
+    path: PathBuf,
+}
+
+impl QueryStore {
+    /// Store backed by `path`.
+    pub(crate) const fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Store at the platform-default location, next to `daemon.toml`.
+    ///
+    /// # Errors
+    ///
+    /// [`QueryStoreError::NoLocation`] when the platform data directory
+    /// cannot be resolved.
+    pub(crate) fn default_location() -> Result<Self, QueryStoreError> {
+        Config::default_path()
+            .as_deref()
+            .and_then(Path::parent)
+            .map(|dir| Self::at(dir.join(QUERIES_FILE)))
+            .ok_or(QueryStoreError::NoLocation)
+    }
+
+    /// Read the library; a missing file is an empty library.
+    ///
+    /// # Errors
+    ///
+    /// [`QueryStoreError::Parse`] for a malformed file and
+    /// [`QueryStoreError::Io`] for any read failure other than not-found.
+    pub(crate) fn load(&self) -> Result<QueryLibrary, QueryStoreError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(body) => toml::from_str(&body).map_err(QueryStoreError::Parse),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(QueryLibrary::new()),
+            Err(err) => Err(QueryStoreError::Io(err)),
+        }
+    }
+
+    /// Validate and store `params.query` under `params.name`.
+    ///
+    /// # Errors
+    ///
+    /// Validation failures ([`QueryStoreError::is_invalid_request`]) plus
+    /// the I/O and (de)serialisation errors of [`Self::load`] and the
+    /// write.
+    pub(crate) fn save(
+        &self,
+        params: &SaveQueryParams,
+    ) -> Result<SaveQueryResponse, QueryStoreError> {
+        validate_query(&params.name, &params.query)?;
+        let _guard = WRITE_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut library = self.load()?;
+        if library.contains_key(&params.name) && !params.replace {
+            return Err(QueryStoreError::Exists {
+                name: params.name.clone(),
+            });
+        }
+        let replaced = library
+            .insert(params.name.clone(), params.query.clone())
+            .is_some();
+        self.write(&library)?;
+        Ok(SaveQueryResponse {
+            name: params.name.clone(),
+            replaced,
+        })
+    }
+
+    /// Remove `name` from the library (idempotent).
+    ///
+    /// # Errors
+    ///
+    /// The I/O and (de)serialisation errors of [`Self::load`] and the
+    /// write.
+    pub(crate) fn delete(&self, name: &str) -> Result<DeleteQueryResponse, QueryStoreError> {
+        let _guard = WRITE_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut library = self.load()?;
+        let deleted = library.remove(name).is_some();
+        if deleted {
+            self.write(&library)?;
+        }
+        Ok(DeleteQueryResponse {
+            name: name.to_owned(),
+            deleted,
+        })
+    }
+
+    /// Atomically replace the file with `library`.
+    fn write(&self, library: &QueryLibrary) -> Result<(), QueryStoreError> {
+        let body = toml::to_string_pretty(library).map_err(QueryStoreError::Serialize)?;
+        if let Some(dir) = self.path.parent() {
+            uffs_mft::cache::create_secure_dir(dir)?;
+        }
+        uffs_mft::cache::atomic_write(&self.path, body.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Reject queries that could never run.
+///
+/// When every placeholder has a default, the expanded arguments must also
+/// parse as a search — a flag typo is caught at save time instead of on
+/// every later run.  Queries with required placeholders are parsed when
+/// they are run.
+fn validate_query(name: &str, query: &SavedQuery) -> Result<(), QueryStoreError> {
+    validate_query_name(name)?;
+    let Some(first) = query.args.first() else {
+        return Err(QueryStoreError::EmptyArgs);
+    };
+    if first.starts_with('@') {
+        return Err(QueryStoreError::InvalidArgs {
+            reason: "a saved query cannot invoke another saved query".to_owned(),
+        });
+    }
+    if query
+        .args
+        .iter()
+        .any(|arg| arg == "--param" || arg.starts_with("--param="))
+    {
+        return Err(QueryStoreError::InvalidArgs {
+            reason: "--param belongs on the invocation, not in the saved arguments".to_owned(),
+        });
+    }
+    if let Ok(expanded) = query.expand(&BTreeMap::new()) {
+        SearchParams::from_cli_args(&expanded).map_err(|err| QueryStoreError::InvalidArgs {
+            reason: err.to_string(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Errors from [`QueryStore`].
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum QueryStoreError {
+    /// Bad name (or another [`QueryError`]).
+    #[error(transparent)]
+    Query(#[from] QueryError),
+    /// The name is taken and the request did not ask to replace it.
+    #[error("a saved query named `{name}` already exists (pass replace to overwrite)")]
+    Exists {
+        /// The taken name.
+        name: String,
+    },
+    /// The query has no arguments at all.
+    #[error("a saved query needs at least a pattern")]
+    EmptyArgs,
+    /// The arguments cannot run as a search.
+    #[error("saved query arguments rejected: {reason}")]
+    InvalidArgs {
+        /// Why they were rejected.
+        reason: String,
+    },
+    /// No platform data directory to put `queries.toml` in.
+    #[error("cannot locate the daemon data directory for queries.toml")]
+    NoLocation,
+    /// `queries.toml` is malformed.
+    #[error("queries.toml parse: {0}")]
+    Parse(toml::de::Error),
+    /// The library could not be serialised.
+    #[error("queries.toml serialize: {0}")]
+    Serialize(toml::ser::Error),
+    /// Filesystem failure reading or writing the library.
+    #[error("queries.toml I/O: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl QueryStoreError {
+    /// `true` when the caller sent a request that can never succeed as-is
+    /// (reported as invalid params rather than an internal error).
+    pub(crate) const fn is_invalid_request(&self) -> bool {
+        matches!(
+            self,
+            Self::Query(_) | Self::Exists { .. } | Self::EmptyArgs | Self::InvalidArgs { .. }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (tempfile::TempDir, QueryStore) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(QUERIES_FILE);
+        (dir, QueryStore::at(path))
+    }
+
+    fn save(name: &str, args: &[&str], replace: bool) -> SaveQueryParams {
+        SaveQueryParams {
+            name: name.to_owned(),
+            query: SavedQuery {
+                description: Some("test".to_owned()),
+                args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+                defaults: BTreeMap::new(),
+            },
+            replace,
+        }
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_library() {
+        let (_dir, queries) = store();
+        assert!(queries.load().expect("load").is_empty());
+    }
+
+    #[test]
+    fn save_list_delete_round_trip_through_toml() {
+        let (_dir, queries) = store();
+        let saved = queries
+            .save(&save("big-psts", &["*.pst", "--min-size", "2GB"], false))
+            .expect("save");
+        assert!(!saved.replaced);
+        let body = std::fs::read_to_string(&queries.path).expect("written");
+        assert!(body.contains("[big-psts]"), "{body}");
+
+        let library = queries.load().expect("load");
+        assert_eq!(
+            library.get("big-psts").map(|query| query.args.len()),
+            Some(3)
+        );
+
+        assert!(matches!(
+            queries.save(&save("big-psts", &["*.pst"], false)),
+            Err(QueryStoreError::Exists { .. })
+        ));
+        assert!(
+            queries
+                .save(&save("big-psts", &["*.pst"], true))
+                .expect("replace")
+                .replaced
+        );
+
+        assert!(queries.delete("big-psts").expect("delete").deleted);
+        assert!(!queries.delete("big-psts").expect("idempotent").deleted);
+        assert!(queries.load().expect("load").is_empty());
+    }
+
+    #[test]
+    fn save_rejects_queries_that_cannot_run() {
+        let (_dir, queries) = store();
+        for (name, args) in [
+            ("bad name", &["*"][..]),
+            ("empty", &[][..]),
+            ("nested", &["@other"][..]),
+            ("typo", &["*", "--min-sise", "1GB"][..]),
+            ("param", &["*", "--param", "a=b"][..]),
+        ] {
+            let err = queries
+                .save(&save(name, args, false))
+                .expect_err("must be rejected");
+            assert!(err.is_invalid_request(), "{name}: {err}");
+        }
+        // A required placeholder defers parsing to run time.
+        queries
+            .save(&save("sized", &["*", "--min-size", "{size}"], false))
+            .expect("placeholder without default is accepted");
+    }
+}
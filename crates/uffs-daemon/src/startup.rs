@@ -18,7 +18,7 @@ use crate::{DaemonConfig, config, events, lifecycle};
 
 /// Bail if the daemon has nothing to serve.
 pub(crate) fn validate_data_sources(
-    mft_files: &[PathBuf],
+    mft_files: &[(PathBuf, Option<uffs_mft::platform::VolumeId>)],
     drives: &[uffs_mft::platform::DriveLetter],
     device_sources: &[(String, uffs_mft::platform::DriveLetter)],
     lifecycle_mgr: &lifecycle::LifecycleManager,
@@ -131,6 +131,25 @@ pub(crate) fn load_daemon_config() -> anyhow::Result<Arc<config::Config>> {
     Ok(Arc::new(cfg))
 }
 
+/// Install `[display] path_template` as the process-wide root template
+/// for host-qualified volumes, before any capture loads.
+///
+/// # Errors
+///
+/// Fails when the template lacks a `{letter}` placeholder.
+pub(crate) fn install_path_template(cfg: &config::Config) -> anyhow::Result<()> {
+    let Some(template) = cfg.display.path_template.as_deref() else {
+        return Ok(());
+    };
+    let parsed = uffs_mft::platform::PathTemplate::parse(template)
+        .map_err(|err| anyhow::anyhow!("daemon.toml [display] path_template: {err}"))?;
+    tracing::info!(path_template = %parsed, "Host volume path template installed");
+    if !parsed.install() {
+        tracing::warn!("Host volume path template was already installed; keeping the first");
+    }
+    Ok(())
+}
+
 /// Build the [`lifecycle::LifecycleManager`], gate against another
 /// running instance via the PID file, and write a fresh PID file.
 ///
@@ -176,8 +195,19 @@ pub(crate) fn bootstrap_lifecycle_manager(
 
 /// Merge `--mft-file` arguments with files discovered under
 /// `--data-dir`, applying the `--drive` filter when present.
-pub(crate) fn gather_mft_files(config: &DaemonConfig) -> Vec<PathBuf> {
-    let mut mft_files = config.mft_files.clone();
+///
+/// Captures under `<data-dir>/<host>/drive_<x>/` carry their
+/// host-qualified volume; everything else (`None`) infers its letter
+/// from the file name at load time.  A `--drive` letter keeps that
+/// letter on every host.
+pub(crate) fn gather_mft_files(
+    config: &DaemonConfig,
+) -> Vec<(PathBuf, Option<uffs_mft::platform::VolumeId>)> {
+    let mut mft_files: Vec<(PathBuf, Option<uffs_mft::platform::VolumeId>)> = config
+        .mft_files
+        .iter()
+        .map(|path| (path.clone(), None))
+        .collect();
     let Some(dir) = config.data_dir.as_ref() else {
         return mft_files;
     };
@@ -191,13 +221,26 @@ pub(crate) fn gather_mft_files(config: &DaemonConfig) -> Vec<PathBuf> {
             .filter(|path| drive_letter_matches(path, &config.drives))
             .collect()
     };
+    let hosted: Vec<(uffs_mft::platform::VolumeId, PathBuf)> =
+        uffs_mft::discovery::discover_host_captures(dir)
+            .into_iter()
+            .filter(|(volume, _)| {
+                config.drives.is_empty() || config.drives.contains(&volume.letter())
+            })
+            .collect();
     tracing::info!(
         data_dir = %dir.display(),
         count = filtered.len(),
+        hosted = hosted.len(),
         filter = ?config.drives,
         "Discovered MFT files from --data-dir"
     );
-    mft_files.extend(filtered);
+    mft_files.extend(filtered.into_iter().map(|path| (path, None)));
+    mft_files.extend(
+        hosted
+            .into_iter()
+            .map(|(volume, path)| (path, Some(volume))),
+    );
     mft_files
 }
 
@@ -24,6 +24,7 @@
 // Linked via the crate's `[dependencies]` (its binaries call
 // `uffs_version::handle_version!`); this target does not reference it.
 use anyhow as _;
+use chrono as _;
 use clap as _;
 use dirs_next as _;
 use futures as _;
@@ -56,6 +57,7 @@ use uffs_daemon as _;
 use uffs_format as _;
 use uffs_mft as _;
 use uffs_security as _;
+use uffs_time as _;
 use uffs_version as _;
 #[cfg(windows)]
 use windows as _;
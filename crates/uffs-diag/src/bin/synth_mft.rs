@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Generate a synthetic UFFS-MFT capture for scale and regression testing.
+//!
+//! Offline, cross-platform: writes valid NTFS `FILE` records for a seeded
+//! volume profile (see `uffs_diag::synth`). The capture loads anywhere a real
+//! one does (`uffs-mft load`, `--mft-file`), so benchmark runs and parser
+//! regressions reproduce on Linux CI without a disk. `--corpus` instead
+//! writes one file per record, for seeding the `uffs-mft` fuzz targets.
+//!
+//! Usage:
+//! ```text
+//!   uffs-synth-mft <output.bin> [--profile workstation|file-server|photo-archive]
+//!                  [--seed N] [--records N[k|M]] [--record-size 1024|4096]
+//!                  [--no-compress]
+//!   uffs-synth-mft --corpus <dir> [--profile ...] [--seed N] [--records N]
+//! ```
+
+#![expect(
+    unused_crate_dependencies,
+    reason = "shared Cargo.toml dependencies not used by all binaries"
+)]
+#![expect(
+    clippy::print_stdout,
+    clippy::print_stderr,
+    reason = "diagnostic tool — stdout/stderr output is intentional"
+)]
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use anyhow::{Context as _, Result, bail};
+use uffs_diag::synth::{Profile, SynthConfig, SynthMft, SynthStats, write_capture};
+
+/// Where the records go.
+enum Target {
+    /// One UFFS-MFT capture file.
+    Capture(PathBuf),
+    /// One file per record in a directory (fuzz corpus).
+    Corpus(PathBuf),
+}
+
+/// Parsed command line.
+struct Args {
+    /// Output target.
+    target: Target,
+    /// Generator configuration.
+    config: SynthConfig,
+    /// Whether to zstd-compress the capture.
+    compress: bool,
+}
+
+/// Print usage to stderr.
+fn usage() {
+    eprintln!(
+        "Usage: uffs-synth-mft <output.bin> [--profile P] [--seed N] [--records N[k|M]] \
+         [--record-size 1024|4096] [--no-compress]"
+    );
+    eprintln!("       uffs-synth-mft --corpus <dir> [--profile P] [--seed N] [--records N]");
+    let names: Vec<&str> = Profile::ALL.iter().map(|profile| profile.name()).collect();
+    eprintln!("Profiles: {}", names.join(", "));
+}
+
+/// Parse a count with an optional `k` / `M` suffix.
+fn parse_count(value: &str) -> Result<u64> {
+    let (digits, scale) = value.strip_suffix(['k', 'K']).map_or_else(
+        || {
+            value
+                .strip_suffix(['m', 'M'])
+                .map_or((value, 1), |digits| (digits, 1_000_000))
+        },
+        |digits| (digits, 1_000),
+    );
+    let count: u64 = digits
+        .replace('_', "")
+        .parse()
+        .with_context(|| format!("invalid count '{value}'"))?;
+    count
+        .checked_mul(scale)
+        .with_context(|| format!("count '{value}' overflows"))
+}
+
+/// Parse the command line; `None` when `--help` was answered.
+fn parse_args() -> Result<Option<Args>> {
+    let mut target = None;
+    let mut config = SynthConfig::default();
+    let mut compress = true;
+    let mut corpus = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().with_context(|| format!("{flag} needs a value"));
+        match arg.as_str() {
+            "--profile" => {
+                config.profile = value("--profile")?.parse().map_err(anyhow::Error::msg)?;
+            }
+            "--seed" => {
+                config.seed = value("--seed")?.parse().context("invalid --seed")?;
+            }
+            "--records" => config.records = parse_count(&value("--records")?)?,
+            "--record-size" => {
+                config.record_size = value("--record-size")?
+                    .parse()
+                    .context("invalid --record-size")?;
+            }
+            "--no-compress" => compress = false,
+            "--corpus" => {
+                corpus = true;
+                target = Some(Target::Corpus(PathBuf::from(value("--corpus")?)));
+            }
+            "-h" | "--help" => {
+                usage();
+                return Ok(None);
+            }
+            other if other.starts_with('-') => bail!("unknown flag '{other}'"),
+            other if !corpus => target = Some(Target::Capture(PathBuf::from(other))),
+            other => bail!("unexpected argument '{other}'"),
+        }
+    }
+    let Some(output) = target else {
+        usage();
+        bail!("missing output path");
+    };
+    config.validate()?;
+    Ok(Some(Args {
+        target: output,
+        config,
+        compress,
+    }))
+}
+
+/// Write every record as `<dir>/frs_<n>.bin`, skipping byte-identical
+/// reserved slots.
+fn write_corpus(dir: &Path, config: SynthConfig) -> Result<SynthStats> {
+    std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    let mut generator = SynthMft::new(config)?;
+    let mut record = vec![0_u8; generator.record_size()];
+    while let Some(frs) = generator.next_record(&mut record) {
+        let path = dir.join(format!("frs_{frs:06}.bin"));
+        std::fs::write(&path, &record).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(generator.stats())
+}
+
+/// Print the generation summary.
+fn print_stats(config: &SynthConfig, stats: &SynthStats, elapsed_ms: u128) {
+    println!(
+        "Profile {} · seed {} · {} records × {} B in {elapsed_ms} ms",
+        config.profile, config.seed, stats.records, config.record_size
+    );
+    println!(
+        "  in use {} (dirs {}, files {}), deleted {}, extension records {}",
+        stats.in_use, stats.directories, stats.files, stats.deleted, stats.extension_records
+    );
+    println!(
+        "  hard links {}, DOS names {}, named streams {}, reparse points {}",
+        stats.hard_links, stats.dos_names, stats.streams, stats.reparse_points
+    );
+}
+
+fn main() -> Result<ExitCode> {
+    uffs_version::handle_version!("uffs-synth-mft");
+
+    let Some(args) = parse_args()? else {
+        return Ok(ExitCode::SUCCESS);
+    };
+    let started = Instant::now();
+    let stats = match &args.target {
+        Target::Capture(path) => {
+            let stats = write_capture(path, args.config, args.compress)?;
+            println!("Wrote {}", path.display());
+            stats
+        }
+        Target::Corpus(dir) => {
+            let stats = write_corpus(dir, args.config)?;
+            println!("Wrote fuzz corpus to {}", dir.display());
+            stats
+        }
+    };
+    print_stats(&args.config, &stats, started.elapsed().as_millis());
+    Ok(ExitCode::SUCCESS)
+}
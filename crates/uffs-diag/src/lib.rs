@@ -15,7 +15,6 @@ use chrono as _;
 use hex as _;
 use rayon as _;
 use sha2 as _;
-use uffs_mft as _;
 use uffs_polars as _;
 use uffs_version as _;
 
@@ -23,6 +22,10 @@ use uffs_version as _;
 /// Rust implementations.
 pub mod parity;
 
+/// Seeded synthetic MFT generator (valid `FILE` records → UFFS-MFT
+/// captures) for scale benchmarks and parser fuzz seeds.
+pub mod synth;
+
 /// Windows-only helpers for inspecting the full uffs-mft raw->fixup->parse
 /// pipeline for a single FRS.
 #[cfg(windows)]
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Generator building blocks: the frozen PRNG, deterministic file
+//! references, DOS short names and the per-record [`Entry`] description.
+//!
+//! Split out of `mod.rs` to keep it under the 800-LOC file-size policy.
+
+use super::record::{CLUSTER_SIZE, FileNameFields, Times, file_name, file_ref};
+use super::{FILE_NAME_DIRECTORY, NAMESPACE_DOS, RESIDENT_LIMIT};
+
+/// `SplitMix64`: tiny, fast and — unlike `rand` — frozen forever.
+#[derive(Debug, Clone)]
+pub(super) struct SplitMix64(pub(super) u64);
+
+impl SplitMix64 {
+    /// Next 64 random bits.
+    pub(super) const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut mixed = self.0;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        mixed ^ (mixed >> 31)
+    }
+
+    /// Uniform value in `0..bound` (`0` when `bound` is `0`).
+    pub(super) fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64().checked_rem(bound).unwrap_or(0)
+    }
+
+    /// Uniform index into a slice of length `len`.
+    pub(super) fn index(&mut self, len: usize) -> usize {
+        usize::try_from(self.below(uffs_mft::usize_to_u64(len))).unwrap_or(0)
+    }
+
+    /// `true` with probability `per_mille / 1000`.
+    pub(super) fn chance(&mut self, per_mille: u32) -> bool {
+        self.below(1000) < u64::from(per_mille)
+    }
+
+    /// Pick one element of a non-empty slice.
+    pub(super) fn pick<'item>(&mut self, items: &[&'item str]) -> &'item str {
+        items
+            .get(self.index(items.len()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Sequence number for `frs` (deterministic, so parent references can be
+/// built without storing the parent's record).
+pub(super) const fn sequence_of(frs: u64) -> u16 {
+    (frs % 5) as u16 + 1
+}
+
+/// File reference for `frs` with its sequence number.
+pub(super) const fn reference(frs: u64) -> u64 {
+    file_ref(frs, sequence_of(frs))
+}
+
+/// Whether `name` is already a valid upper-case DOS 8.3 name.
+pub(super) fn is_short_name(name: &str) -> bool {
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    let valid = |part: &str| {
+        part.bytes()
+            .all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit() || byte == b'_')
+    };
+    (1..=8).contains(&stem.len()) && ext.len() <= 3 && valid(stem) && valid(ext)
+}
+
+/// DOS short name `STEM~1.EXT` derived from a long name.
+pub(super) fn short_name(name: &str, frs: u64) -> String {
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    let mut short: String = stem
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(6)
+        .map(|ch| ch.to_ascii_uppercase())
+        .collect();
+    if short.is_empty() {
+        short = format!("{:04X}", frs & 0xFFFF);
+    }
+    short.push_str("~1");
+    let short_ext: String = ext
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(3)
+        .map(|ch| ch.to_ascii_uppercase())
+        .collect();
+    if !short_ext.is_empty() {
+        short.push('.');
+        short.push_str(&short_ext);
+    }
+    short
+}
+
+/// One `$FILE_NAME` to emit.
+#[derive(Debug, Clone)]
+pub(super) struct Link {
+    /// Parent directory FRS.
+    pub(super) parent: u64,
+    /// Name as UTF-16.
+    pub(super) name: Vec<u16>,
+    /// `$FILE_NAME` namespace.
+    pub(super) namespace: u8,
+}
+
+/// Everything needed to encode one file or directory.
+#[derive(Debug, Clone)]
+pub(super) struct Entry {
+    /// Names (hard links and DOS names).
+    pub(super) links: Vec<Link>,
+    /// Timestamps.
+    pub(super) times: Times,
+    /// Unnamed `$DATA` size (0 for directories).
+    pub(super) size: u64,
+    /// File attribute flags for `$STANDARD_INFORMATION`.
+    pub(super) attributes: u32,
+    /// Reparse tag and target, if a reparse point.
+    pub(super) reparse: Option<(u32, Vec<u16>)>,
+    /// Named stream (name, size), if any.
+    pub(super) stream: Option<(&'static str, u64)>,
+    /// Whether this is a directory.
+    pub(super) directory: bool,
+}
+
+impl Entry {
+    /// `$FILE_NAME` flags for this entry.
+    pub(super) const fn file_name_flags(&self) -> u32 {
+        let mut flags = self.attributes;
+        if self.directory {
+            flags |= FILE_NAME_DIRECTORY;
+        }
+        flags
+    }
+
+    /// Hard-link count stored in the record header (DOS names share their
+    /// Win32 twin's link).
+    pub(super) fn link_count(&self) -> u16 {
+        let links = self
+            .links
+            .iter()
+            .filter(|link| link.namespace != NAMESPACE_DOS)
+            .count();
+        u16::try_from(links).unwrap_or(u16::MAX)
+    }
+
+    /// Encoded `$FILE_NAME` value for `link`.
+    pub(super) fn file_name_value(&self, link: &Link) -> Vec<u8> {
+        let allocated = if self.size > RESIDENT_LIMIT {
+            self.size.div_ceil(CLUSTER_SIZE) * CLUSTER_SIZE
+        } else {
+            0
+        };
+        file_name(&FileNameFields {
+            parent: reference(link.parent),
+            times: self.times,
+            allocated,
+            size: self.size,
+            flags: self.file_name_flags(),
+            reparse_tag: self.reparse.as_ref().map_or(0, |(tag, _)| *tag),
+            namespace: link.namespace,
+            name: &link.name,
+        })
+    }
+}
+
+/// An extension record owed to the previous base record.
+#[derive(Debug, Clone)]
+pub(super) struct PendingExtension {
+    /// Base record FRS.
+    pub(super) base: u64,
+    /// The base record's entry, whose names live in the extension.
+    pub(super) entry: Entry,
+}
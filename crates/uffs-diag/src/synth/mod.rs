@@ -0,0 +1,740 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Synthetic MFT generator for scale and regression testing.
+//!
+//! Produces valid, USA-protected NTFS `FILE` records — the 16 system
+//! metafiles, `$Extend` children, directories with `$I30` roots, files with
+//! resident or non-resident `$DATA`, hard links, DOS 8.3 names, alternate
+//! data streams, junctions and symlinks, base + extension records joined by
+//! an `$ATTRIBUTE_LIST`, and deleted (not-in-use) slots — and streams them
+//! into a UFFS-MFT capture that loads through `--mft-file` like a real one.
+//!
+//! Output is a pure function of [`SynthConfig`]: the same seed, profile,
+//! record count and record size always yield byte-identical records, so a
+//! scale run or a parser regression can be reproduced on any machine
+//! (including Linux CI) without the original disk. Records are generated one
+//! at a time; memory stays at one `u64` per directory, so 100M-record
+//! captures are practical.
+//!
+//! The PRNG is an in-crate SplitMix64 rather than `rand`, so the stream —
+//! and every capture derived from it — stays stable across dependency
+//! upgrades.
+
+mod entry;
+mod profile;
+mod record;
+#[cfg(test)]
+mod tests;
+
+use std::path::Path;
+
+use anyhow::{Context as _, Result, bail};
+use uffs_mft::raw::{SaveRawOptions, StreamingRawMftWriter};
+
+use self::entry::{
+    Entry, Link, PendingExtension, SplitMix64, is_short_name, reference, sequence_of, short_name,
+};
+pub use self::profile::Profile;
+use self::profile::ProfileParams;
+use self::record::{
+    ATTR_ATTRIBUTE_LIST, ATTR_DATA, ATTR_FILE_NAME, ATTR_INDEX_ROOT, ATTR_REPARSE_POINT,
+    ATTR_STANDARD_INFORMATION, ATTR_VOLUME_NAME, CLUSTER_SIZE, FLAG_DIRECTORY, FLAG_IN_USE,
+    RecordBuilder, TAG_MOUNT_POINT, TAG_SYMLINK, Times, attribute_list_entry, empty_index_root,
+    reparse_point, standard_information, utf16_bytes,
+};
+
+/// Largest record count the generator accepts (FRS must fit the 32-bit
+/// record-number header field).
+pub const MAX_RECORDS: u64 = u32::MAX as u64;
+
+/// First FRS after the reserved metafile range and the `$Extend` children.
+pub const FIRST_USER_FRS: u64 = 28;
+
+/// Root directory FRS.
+const ROOT_FRS: u64 = 5;
+
+/// `$Extend` FRS.
+const EXTEND_FRS: u64 = 11;
+
+/// Records per chunk handed to the capture writer.
+const CHUNK_RECORDS: usize = 4096;
+
+/// Files at or below this size keep their `$DATA` resident.
+const RESIDENT_LIMIT: u64 = 256;
+
+/// First LCN handed out to non-resident streams.
+const FIRST_LCN: u64 = 0x1_0000;
+
+/// 2026-01-01T00:00:00Z as FILETIME: upper bound for generated timestamps.
+const END_OF_TIME: u64 = 134_116_992_000_000_000;
+
+/// `FILE_ATTRIBUTE_ARCHIVE`.
+const ATTRIBUTE_ARCHIVE: u32 = 0x20;
+/// `FILE_ATTRIBUTE_REPARSE_POINT`.
+const ATTRIBUTE_REPARSE: u32 = 0x400;
+/// `FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM` (metafiles).
+const ATTRIBUTE_HIDDEN_SYSTEM: u32 = 0x06;
+/// `$FILE_NAME` flag marking a directory (`DUP_FILE_NAME_INDEX_PRESENT`).
+const FILE_NAME_DIRECTORY: u32 = 0x1000_0000;
+
+/// Namespaces of `$FILE_NAME`.
+const NAMESPACE_WIN32: u8 = 1;
+/// DOS 8.3 namespace.
+const NAMESPACE_DOS: u8 = 2;
+/// Name valid in both Win32 and DOS.
+const NAMESPACE_WIN32_DOS: u8 = 3;
+
+/// Named streams attached to files (`Zone.Identifier` first: most common).
+const STREAM_NAMES: [&str; 4] = [
+    "Zone.Identifier",
+    "Zone.Identifier",
+    "com.dropbox.attrs",
+    "SummaryInformation",
+];
+
+/// Generator input. Output is a pure function of these fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SynthConfig {
+    /// PRNG seed.
+    pub seed: u64,
+    /// Volume profile.
+    pub profile: Profile,
+    /// Total record slots (FRS `0..records`), including metafiles and
+    /// deleted slots.
+    pub records: u64,
+    /// Bytes per `FILE` record (1024 or 4096).
+    pub record_size: u32,
+}
+
+impl Default for SynthConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            profile: Profile::default(),
+            records: 100_000,
+            record_size: 1024,
+        }
+    }
+}
+
+impl SynthConfig {
+    /// Check the record size and count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for a record size other than 1024 / 4096 or a record
+    /// count above [`MAX_RECORDS`].
+    pub fn validate(&self) -> Result<()> {
+        if !matches!(self.record_size, 1024 | 4096) {
+            bail!(
+                "record size must be 1024 or 4096 bytes (got {})",
+                self.record_size
+            );
+        }
+        if self.records > MAX_RECORDS {
+            bail!(
+                "record count {} exceeds the maximum of {MAX_RECORDS}",
+                self.records
+            );
+        }
+        Ok(())
+    }
+}
+
+/// What the generator wrote.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SynthStats {
+    /// Record slots written (in use, deleted and extension).
+    pub records: u64,
+    /// In-use base records (metafiles, directories, files).
+    pub in_use: u64,
+    /// In-use directories, including the root and `$Extend`.
+    pub directories: u64,
+    /// In-use files, including metafiles.
+    pub files: u64,
+    /// Not-in-use slots with stale content.
+    pub deleted: u64,
+    /// Additional hard links (names beyond the first, DOS names excluded).
+    pub hard_links: u64,
+    /// DOS 8.3 short names.
+    pub dos_names: u64,
+    /// Named alternate data streams.
+    pub streams: u64,
+    /// Junctions and symlinks.
+    pub reparse_points: u64,
+    /// Extension records reached through an `$ATTRIBUTE_LIST`.
+    pub extension_records: u64,
+}
+
+/// Streaming record generator; yields records in FRS order.
+#[derive(Debug)]
+pub struct SynthMft {
+    /// Input configuration.
+    config: SynthConfig,
+    /// Profile parameters.
+    params: ProfileParams,
+    /// PRNG.
+    rng: SplitMix64,
+    /// FRS of the next record.
+    next_frs: u64,
+    /// In-use user directories, in creation order.
+    directories: Vec<u64>,
+    /// Next free LCN for non-resident streams.
+    next_lcn: u64,
+    /// Extension record to emit next, if the last base record needs one.
+    pending: Option<PendingExtension>,
+    /// Running counters.
+    stats: SynthStats,
+}
+
+impl SynthMft {
+    /// Start a generator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config` fails [`SynthConfig::validate`].
+    pub fn new(config: SynthConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            params: config.profile.params(),
+            rng: SplitMix64(config.seed),
+            next_frs: 0,
+            directories: Vec::new(),
+            next_lcn: FIRST_LCN,
+            pending: None,
+            stats: SynthStats::default(),
+        })
+    }
+
+    /// Counters for the records generated so far.
+    #[must_use]
+    pub const fn stats(&self) -> SynthStats {
+        self.stats
+    }
+
+    /// Bytes per record.
+    #[must_use]
+    pub const fn record_size(&self) -> usize {
+        uffs_mft::u32_as_usize(self.config.record_size)
+    }
+
+    /// Write the next record into `buf` (`record_size` bytes). Returns the
+    /// record's FRS, or `None` once all records have been generated.
+    pub fn next_record(&mut self, buf: &mut [u8]) -> Option<u64> {
+        let frs = self.next_frs;
+        if frs >= self.config.records || buf.len() != self.record_size() {
+            return None;
+        }
+        self.next_frs += 1;
+        self.stats.records += 1;
+
+        if frs < 16 {
+            self.metafile(frs, buf);
+        } else if frs < 24 {
+            // Reserved slots: formatted but never used.
+            RecordBuilder::new(buf, frs, sequence_of(frs), 0, 0, 0).finish();
+        } else if frs < FIRST_USER_FRS {
+            self.extend_child(frs, buf);
+        } else if let Some(pending) = self.pending.take() {
+            self.extension(frs, &pending, buf);
+        } else {
+            self.user_record(frs, buf);
+        }
+        Some(frs)
+    }
+
+    /// Random timestamps between the profile epoch and [`END_OF_TIME`].
+    fn times(&mut self) -> Times {
+        let epoch = self.params.epoch;
+        let created = epoch + self.rng.below(END_OF_TIME - epoch);
+        let modified = created + self.rng.below(END_OF_TIME - created);
+        let changed = modified + self.rng.below(10_000_000 * 3600);
+        let accessed = changed.max(modified + self.rng.below(END_OF_TIME - modified));
+        Times {
+            created,
+            modified,
+            changed,
+            accessed,
+        }
+    }
+
+    /// Log-uniform file size for the profile.
+    fn file_size(&mut self) -> u64 {
+        let (min, max) = self.params.size_log2;
+        let exponent = min + u32::try_from(self.rng.below(u64::from(max - min + 1))).unwrap_or(0);
+        let base = 1_u64 << exponent;
+        base + self.rng.below(base)
+    }
+
+    /// Hand out `size` bytes worth of clusters; returns the first LCN.
+    fn allocate(&mut self, size: u64) -> u64 {
+        let lcn = self.next_lcn;
+        self.next_lcn += size.div_ceil(CLUSTER_SIZE).max(1);
+        lcn
+    }
+
+    /// Parent for a new entry: usually a recent directory (deep trees),
+    /// sometimes any directory, rarely the root.
+    fn parent(&mut self) -> u64 {
+        let count = self.directories.len();
+        if count == 0 || self.rng.chance(5) {
+            return ROOT_FRS;
+        }
+        let idx = if self.rng.chance(self.params.locality_per_mille) {
+            count - 1 - self.rng.index(count.min(32))
+        } else {
+            self.rng.index(count)
+        };
+        self.directories.get(idx).copied().unwrap_or(ROOT_FRS)
+    }
+
+    /// Directory name for the profile.
+    fn directory_name(&mut self) -> String {
+        let stem = self.rng.pick(self.params.dir_names);
+        match self.rng.below(4) {
+            0 => format!("{stem} ({})", self.rng.below(20) + 2),
+            1 => format!("{stem}_{}", self.rng.below(1000)),
+            _ => stem.to_owned(),
+        }
+    }
+
+    /// File name for the profile.
+    fn file_name(&mut self) -> String {
+        let stem = self.rng.pick(self.params.file_stems);
+        let ext = self.rng.pick(self.params.extensions);
+        let base = match self.config.profile {
+            Profile::PhotoArchive => format!("{stem}_{:04}", self.rng.below(10_000)),
+            Profile::FileServer if self.rng.chance(400) => {
+                format!("{stem} {}", 2000 + self.rng.below(26))
+            }
+            Profile::Workstation | Profile::FileServer if self.rng.chance(300) => {
+                format!("{stem}-{:08x}", self.rng.next_u64() & 0xFFFF_FFFF)
+            }
+            Profile::Workstation | Profile::FileServer => stem.to_owned(),
+        };
+        if ext.is_empty() {
+            base
+        } else {
+            format!("{base}.{ext}")
+        }
+    }
+
+    /// Primary link for `name` under `parent`, plus a DOS twin when the
+    /// profile wants one and the name is not already 8.3.
+    fn links_for(&mut self, frs: u64, parent: u64, name: &str) -> Vec<Link> {
+        if is_short_name(name) {
+            return vec![Link {
+                parent,
+                name: name.encode_utf16().collect(),
+                namespace: NAMESPACE_WIN32_DOS,
+            }];
+        }
+        let mut links = vec![Link {
+            parent,
+            name: name.encode_utf16().collect(),
+            namespace: NAMESPACE_WIN32,
+        }];
+        if self.rng.chance(self.params.dos_name_per_mille) {
+            links.push(Link {
+                parent,
+                name: short_name(name, frs).encode_utf16().collect(),
+                namespace: NAMESPACE_DOS,
+            });
+        }
+        links
+    }
+
+    /// A user directory, file or deleted slot.
+    fn user_record(&mut self, frs: u64, buf: &mut [u8]) {
+        let deleted = self.rng.chance(self.params.deleted_per_mille);
+        let directory = self.rng.chance(self.params.dir_per_mille);
+        let parent = self.parent();
+        let entry = if directory {
+            self.directory_entry(frs, parent)
+        } else {
+            self.file_entry(frs, parent)
+        };
+
+        if deleted {
+            self.stats.deleted += 1;
+            self.encode(frs, &entry, false, buf);
+            return;
+        }
+
+        let split = !directory
+            && frs + 1 < self.config.records
+            && self.rng.chance(self.params.attr_list_per_mille);
+        self.count(&entry);
+        if directory {
+            self.directories.push(frs);
+        }
+        if split {
+            self.encode_base_with_list(frs, &entry, buf);
+            self.pending = Some(PendingExtension { base: frs, entry });
+        } else {
+            self.encode(frs, &entry, true, buf);
+        }
+    }
+
+    /// Update counters for an in-use entry.
+    fn count(&mut self, entry: &Entry) {
+        self.stats.in_use += 1;
+        if entry.directory {
+            self.stats.directories += 1;
+        } else {
+            self.stats.files += 1;
+        }
+        let dos = entry
+            .links
+            .iter()
+            .filter(|link| link.namespace == NAMESPACE_DOS)
+            .count();
+        self.stats.dos_names += uffs_mft::usize_to_u64(dos);
+        self.stats.hard_links += u64::from(entry.link_count().saturating_sub(1));
+        self.stats.streams += u64::from(entry.stream.is_some());
+        self.stats.reparse_points += u64::from(entry.reparse.is_some());
+    }
+
+    /// A user directory entry (junction with the profile's reparse rate).
+    fn directory_entry(&mut self, frs: u64, parent: u64) -> Entry {
+        let name = self.directory_name();
+        let links = self.links_for(frs, parent, &name);
+        let reparse = self.rng.chance(self.params.reparse_per_mille).then(|| {
+            let target = format!(r"D:\{}\{name}", self.rng.pick(self.params.dir_names));
+            (TAG_MOUNT_POINT, target.encode_utf16().collect())
+        });
+        Entry {
+            links,
+            times: self.times(),
+            size: 0,
+            attributes: if reparse.is_some() {
+                ATTRIBUTE_REPARSE
+            } else {
+                0
+            },
+            reparse,
+            stream: None,
+            directory: true,
+        }
+    }
+
+    /// A user file entry with the profile's hard-link, ADS and symlink rates.
+    fn file_entry(&mut self, frs: u64, parent: u64) -> Entry {
+        let name = self.file_name();
+        let mut links = self.links_for(frs, parent, &name);
+        if self.rng.chance(self.params.hard_link_per_mille) {
+            let other = self.parent();
+            links.push(Link {
+                parent: other,
+                name: name.encode_utf16().collect(),
+                namespace: NAMESPACE_WIN32,
+            });
+        }
+        let reparse = self.rng.chance(self.params.reparse_per_mille).then(|| {
+            let target = format!(r"C:\Users\Public\{}", self.file_name());
+            (TAG_SYMLINK, target.encode_utf16().collect())
+        });
+        let size = if reparse.is_some() {
+            0
+        } else {
+            self.file_size()
+        };
+        let stream = self
+            .rng
+            .chance(self.params.ads_per_mille)
+            .then(|| (self.rng.pick(&STREAM_NAMES), 26 + self.rng.below(200)));
+        let mut attributes = ATTRIBUTE_ARCHIVE;
+        if reparse.is_some() {
+            attributes |= ATTRIBUTE_REPARSE;
+        }
+        Entry {
+            links,
+            times: self.times(),
+            size,
+            attributes,
+            reparse,
+            stream,
+            directory: false,
+        }
+    }
+
+    /// Append the unnamed `$DATA` (and any named stream) for `entry`.
+    fn push_data(&mut self, builder: &mut RecordBuilder<'_>, entry: &Entry) {
+        if entry.directory {
+            return;
+        }
+        if entry.size > RESIDENT_LIMIT {
+            let lcn = self.allocate(entry.size);
+            builder.non_resident(ATTR_DATA, &[], entry.size, lcn);
+        } else {
+            let zeros = vec![0_u8; uffs_mft::frs_to_usize(entry.size)];
+            builder.resident(ATTR_DATA, &[], &zeros);
+        }
+        if let Some((name, size)) = entry.stream {
+            let units: Vec<u16> = name.encode_utf16().collect();
+            let value = vec![b'Z'; uffs_mft::frs_to_usize(size)];
+            builder.resident(ATTR_DATA, &units, &value);
+        }
+    }
+
+    /// Encode a complete single-record entry.
+    fn encode(&mut self, frs: u64, entry: &Entry, in_use: bool, buf: &mut [u8]) {
+        let mut flags = if in_use { FLAG_IN_USE } else { 0 };
+        if entry.directory {
+            flags |= FLAG_DIRECTORY;
+        }
+        let mut builder =
+            RecordBuilder::new(buf, frs, sequence_of(frs), flags, 0, entry.link_count());
+        builder.resident(
+            ATTR_STANDARD_INFORMATION,
+            &[],
+            &standard_information(entry.times, entry.attributes),
+        );
+        for link in &entry.links {
+            builder.resident(ATTR_FILE_NAME, &[], &entry.file_name_value(link));
+        }
+        self.push_data(&mut builder, entry);
+        if entry.directory {
+            let i30: Vec<u16> = "$I30".encode_utf16().collect();
+            builder.resident(ATTR_INDEX_ROOT, &i30, &empty_index_root());
+        }
+        if let Some((tag, target)) = &entry.reparse {
+            builder.resident(ATTR_REPARSE_POINT, &[], &reparse_point(*tag, target));
+        }
+        builder.finish();
+    }
+
+    /// Encode a base record whose names live in the next (extension) record.
+    fn encode_base_with_list(&mut self, frs: u64, entry: &Entry, buf: &mut [u8]) {
+        let ext_ref = reference(frs + 1);
+        let base_ref = reference(frs);
+        // Base holds SI (id 0), the list (id 1) and $DATA (id 2, 3 for an
+        // ADS); the extension holds the names (ids 0..n).
+        let mut list = attribute_list_entry(ATTR_STANDARD_INFORMATION, &[], base_ref, 0);
+        for (id, _) in entry.links.iter().enumerate() {
+            let attr_id = u16::try_from(id).unwrap_or(u16::MAX);
+            list.extend(attribute_list_entry(ATTR_FILE_NAME, &[], ext_ref, attr_id));
+        }
+        list.extend(attribute_list_entry(ATTR_DATA, &[], base_ref, 2));
+        if let Some((name, _)) = entry.stream {
+            let units: Vec<u16> = name.encode_utf16().collect();
+            list.extend(attribute_list_entry(ATTR_DATA, &units, base_ref, 3));
+        }
+
+        let mut builder = RecordBuilder::new(
+            buf,
+            frs,
+            sequence_of(frs),
+            FLAG_IN_USE,
+            0,
+            entry.link_count(),
+        );
+        builder.resident(
+            ATTR_STANDARD_INFORMATION,
+            &[],
+            &standard_information(entry.times, entry.attributes),
+        );
+        builder.resident(ATTR_ATTRIBUTE_LIST, &[], &list);
+        self.push_data(&mut builder, entry);
+        builder.finish();
+    }
+
+    /// Encode the extension record holding a split entry's names.
+    fn extension(&mut self, frs: u64, pending: &PendingExtension, buf: &mut [u8]) {
+        self.stats.extension_records += 1;
+        let mut builder = RecordBuilder::new(
+            buf,
+            frs,
+            sequence_of(frs),
+            FLAG_IN_USE,
+            reference(pending.base),
+            0,
+        );
+        for link in &pending.entry.links {
+            builder.resident(ATTR_FILE_NAME, &[], &pending.entry.file_name_value(link));
+        }
+        builder.finish();
+    }
+
+    /// One of the 16 reserved metafile records.
+    fn metafile(&mut self, frs: u64, buf: &mut [u8]) {
+        const NAMES: [&str; 12] = [
+            "$MFT", "$MFTMirr", "$LogFile", "$Volume", "$AttrDef", ".", "$Bitmap", "$Boot",
+            "$BadClus", "$Secure", "$UpCase", "$Extend",
+        ];
+        let Some(name) = NAMES.get(uffs_mft::frs_to_usize(frs)).copied() else {
+            // FRS 12–15: in use, reserved for future metafiles, no name.
+            RecordBuilder::new(buf, frs, sequence_of(frs), FLAG_IN_USE, 0, 0).finish();
+            return;
+        };
+        let directory = frs == ROOT_FRS || frs == EXTEND_FRS;
+        let size = match frs {
+            0 => u64::from(self.config.record_size) * self.config.records,
+            1 => 4 * u64::from(self.config.record_size),
+            2 => 64_u64 << 20_u32,
+            4 => 2560,
+            6 => self.config.records.div_ceil(8).max(8),
+            7 => 8192,
+            10 => 128_u64 << 10_u32,
+            _ => 0,
+        };
+        let stream = match frs {
+            8 => Some(("$Bad", 0)),
+            9 => Some(("$SDS", 256_u64 << 10_u32)),
+            _ => None,
+        };
+        let entry = Entry {
+            links: vec![Link {
+                parent: ROOT_FRS,
+                name: name.encode_utf16().collect(),
+                namespace: NAMESPACE_WIN32_DOS,
+            }],
+            times: Times {
+                created: self.params.epoch,
+                modified: self.params.epoch,
+                changed: self.params.epoch,
+                accessed: self.params.epoch,
+            },
+            size,
+            attributes: ATTRIBUTE_HIDDEN_SYSTEM,
+            reparse: None,
+            stream: None,
+            directory,
+        };
+        self.count(&entry);
+
+        let flags = if directory {
+            FLAG_IN_USE | FLAG_DIRECTORY
+        } else {
+            FLAG_IN_USE
+        };
+        let mut builder = RecordBuilder::new(buf, frs, sequence_of(frs), flags, 0, 1);
+        builder.resident(
+            ATTR_STANDARD_INFORMATION,
+            &[],
+            &standard_information(entry.times, entry.attributes),
+        );
+        for link in &entry.links {
+            builder.resident(ATTR_FILE_NAME, &[], &entry.file_name_value(link));
+        }
+        if frs == 3 {
+            let label: Vec<u16> = "SYNTH".encode_utf16().collect();
+            builder.resident(ATTR_VOLUME_NAME, &[], &utf16_bytes(&label));
+        }
+        self.push_data(&mut builder, &entry);
+        if let Some((stream_name, stream_size)) = stream {
+            let units: Vec<u16> = stream_name.encode_utf16().collect();
+            let lcn = self.allocate(stream_size);
+            builder.non_resident(ATTR_DATA, &units, stream_size, lcn);
+        }
+        if directory {
+            let i30: Vec<u16> = "$I30".encode_utf16().collect();
+            builder.resident(ATTR_INDEX_ROOT, &i30, &empty_index_root());
+        }
+        builder.finish();
+    }
+
+    /// `$Quota`, `$ObjId`, `$Reparse` and `$UsnJrnl` under `$Extend`.
+    fn extend_child(&mut self, frs: u64, buf: &mut [u8]) {
+        let (name, stream): (&str, Option<(&str, u64)>) = match frs {
+            24 => ("$Quota", None),
+            25 => ("$ObjId", None),
+            26 => ("$Reparse", None),
+            _ => ("$UsnJrnl", Some(("$J", 32_u64 << 20_u32))),
+        };
+        let entry = Entry {
+            links: vec![Link {
+                parent: EXTEND_FRS,
+                name: name.encode_utf16().collect(),
+                namespace: NAMESPACE_WIN32_DOS,
+            }],
+            times: Times {
+                created: self.params.epoch,
+                modified: self.params.epoch,
+                changed: self.params.epoch,
+                accessed: self.params.epoch,
+            },
+            size: 0,
+            attributes: ATTRIBUTE_HIDDEN_SYSTEM,
+            reparse: None,
+            stream: None,
+            directory: false,
+        };
+        self.count(&entry);
+        let mut builder = RecordBuilder::new(buf, frs, sequence_of(frs), FLAG_IN_USE, 0, 1);
+        builder.resident(
+            ATTR_STANDARD_INFORMATION,
+            &[],
+            &standard_information(entry.times, entry.attributes),
+        );
+        for link in &entry.links {
+            builder.resident(ATTR_FILE_NAME, &[], &entry.file_name_value(link));
+        }
+        if let Some((stream_name, stream_size)) = stream {
+            let units: Vec<u16> = stream_name.encode_utf16().collect();
+            let lcn = self.allocate(stream_size);
+            builder.non_resident(ATTR_DATA, &units, stream_size, lcn);
+        }
+        builder.finish();
+    }
+}
+
+/// Generate every record of `config` into one in-memory `$MFT` image.
+///
+/// Intended for tests and fuzz seeds; use [`write_capture`] for large runs.
+///
+/// # Errors
+///
+/// Returns an error if `config` fails [`SynthConfig::validate`].
+pub fn generate(config: SynthConfig) -> Result<(Vec<u8>, SynthStats)> {
+    let mut generator = SynthMft::new(config)?;
+    let record_size = generator.record_size();
+    let total = usize::try_from(config.records)
+        .ok()
+        .and_then(|records| records.checked_mul(record_size))
+        .context("record count too large for an in-memory image")?;
+    let mut data = vec![0_u8; total];
+    for record in data.chunks_exact_mut(record_size) {
+        let _frs = generator.next_record(record);
+    }
+    Ok((data, generator.stats()))
+}
+
+/// Stream a synthetic capture for `config` to `path` in UFFS-MFT format
+/// (zstd-compressed when `compress`).
+///
+/// # Errors
+///
+/// Returns an error if `config` is invalid or the capture cannot be written.
+pub fn write_capture(path: &Path, config: SynthConfig, compress: bool) -> Result<SynthStats> {
+    let mut generator = SynthMft::new(config)?;
+    let record_size = generator.record_size();
+    let options = SaveRawOptions {
+        compress,
+        ..SaveRawOptions::default()
+    };
+    let mut writer = StreamingRawMftWriter::new(path, config.record_size, &options)
+        .with_context(|| format!("creating {}", path.display()))?;
+    let mut chunk = vec![0_u8; CHUNK_RECORDS * record_size];
+    loop {
+        let mut filled = 0_usize;
+        for record in chunk.chunks_exact_mut(record_size) {
+            if generator.next_record(record).is_none() {
+                break;
+            }
+            filled += record_size;
+        }
+        if filled == 0 {
+            break;
+        }
+        writer
+            .write_chunk(chunk.get(..filled).unwrap_or_default())
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    writer
+        .finish()
+        .with_context(|| format!("finalizing {}", path.display()))?;
+    Ok(generator.stats())
+}
@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Volume profiles: the record mix, tree shape and name vocabulary of the
+//! volumes the generator imitates.
+
+use core::fmt;
+use core::str::FromStr;
+
+/// The kind of volume to synthesize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    /// Developer workstation: deep source/build trees, many small files,
+    /// `.git` objects, symlinks and junctions.
+    #[default]
+    Workstation,
+    /// Departmental file server: office documents, wide shallow shares, DOS
+    /// short names, hard links, `Zone.Identifier` streams, non-ASCII names.
+    FileServer,
+    /// Photo archive: dated folders of large camera files with sidecars.
+    PhotoArchive,
+}
+
+/// Per-mille rates and distributions for one [`Profile`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProfileParams {
+    /// Share of new records that are directories.
+    pub(crate) dir_per_mille: u32,
+    /// Share of slots left as deleted (not in use) records.
+    pub(crate) deleted_per_mille: u32,
+    /// Share of files with a second hard link.
+    pub(crate) hard_link_per_mille: u32,
+    /// Share of files with a named alternate data stream.
+    pub(crate) ads_per_mille: u32,
+    /// Share of records that are reparse points (junctions for directories,
+    /// symlinks for files).
+    pub(crate) reparse_per_mille: u32,
+    /// Share of files split across a base and an extension record through an
+    /// `$ATTRIBUTE_LIST`.
+    pub(crate) attr_list_per_mille: u32,
+    /// Share of long names that also get a DOS 8.3 name.
+    pub(crate) dos_name_per_mille: u32,
+    /// Share of new directories placed under a recently created directory
+    /// (deepens the tree) rather than a uniformly chosen one.
+    pub(crate) locality_per_mille: u32,
+    /// File sizes are drawn log-uniformly from `2^min..2^max` bytes.
+    pub(crate) size_log2: (u32, u32),
+    /// Volume creation time (FILETIME); timestamps spread forward from here.
+    pub(crate) epoch: u64,
+    /// Directory name stems.
+    pub(crate) dir_names: &'static [&'static str],
+    /// File name stems.
+    pub(crate) file_stems: &'static [&'static str],
+    /// Extensions; repeated entries weight the draw.
+    pub(crate) extensions: &'static [&'static str],
+}
+
+/// 2015-01-01T00:00:00Z as FILETIME.
+const EPOCH_2015: u64 = 130_645_440_000_000_000;
+/// 2019-01-01T00:00:00Z as FILETIME.
+const EPOCH_2019: u64 = 131_907_744_000_000_000;
+/// 2008-01-01T00:00:00Z as FILETIME.
+const EPOCH_2008: u64 = 128_436_192_000_000_000;
+
+impl Profile {
+    /// Every profile, in CLI listing order.
+    pub const ALL: [Self; 3] = [Self::Workstation, Self::FileServer, Self::PhotoArchive];
+
+    /// The CLI / report name.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Workstation => "workstation",
+            Self::FileServer => "file-server",
+            Self::PhotoArchive => "photo-archive",
+        }
+    }
+
+    /// Generation parameters.
+    pub(crate) const fn params(self) -> ProfileParams {
+        match self {
+            Self::Workstation => WORKSTATION,
+            Self::FileServer => FILE_SERVER,
+            Self::PhotoArchive => PHOTO_ARCHIVE,
+        }
+    }
+}
+
+/// [`Profile::Workstation`] parameters.
+const WORKSTATION: ProfileParams = ProfileParams {
+    dir_per_mille: 120,
+    deleted_per_mille: 60,
+    hard_link_per_mille: 8,
+    ads_per_mille: 10,
+    reparse_per_mille: 4,
+    attr_list_per_mille: 2,
+    dos_name_per_mille: 50,
+    locality_per_mille: 800,
+    size_log2: (6, 22),
+    epoch: EPOCH_2019,
+    dir_names: &[
+        "src",
+        "target",
+        "debug",
+        "release",
+        "deps",
+        "build",
+        "node_modules",
+        "lib",
+        "tests",
+        "docs",
+        ".git",
+        "objects",
+        "crates",
+        "packages",
+        "dist",
+        "examples",
+        "vendor",
+        "include",
+        "bin",
+        "obj",
+        "incremental",
+        "fixtures",
+    ],
+    file_stems: &[
+        "main",
+        "lib",
+        "mod",
+        "index",
+        "README",
+        "config",
+        "utils",
+        "types",
+        "error",
+        "parser",
+        "build",
+        "Cargo",
+        "package",
+        "tsconfig",
+        "test_helpers",
+        "server",
+        "client",
+        "schema",
+        "api",
+        "handler",
+        "LICENSE",
+        "CHANGELOG",
+    ],
+    extensions: &[
+        "rs", "rs", "rs", "js", "js", "ts", "json", "json", "md", "toml", "o", "o", "d", "rlib",
+        "rmeta", "lock", "h", "c", "cpp", "py", "pyc", "png", "svg", "yml", "txt", "pack", "idx",
+        "",
+    ],
+};
+
+/// [`Profile::FileServer`] parameters.
+const FILE_SERVER: ProfileParams = ProfileParams {
+    dir_per_mille: 70,
+    deleted_per_mille: 90,
+    hard_link_per_mille: 15,
+    ads_per_mille: 120,
+    reparse_per_mille: 1,
+    attr_list_per_mille: 6,
+    dos_name_per_mille: 900,
+    locality_per_mille: 500,
+    size_log2: (10, 26),
+    epoch: EPOCH_2008,
+    dir_names: &[
+        "Finance",
+        "HR",
+        "Projects",
+        "Marketing",
+        "Legal",
+        "Archive",
+        "Shared",
+        "Contracts",
+        "Invoices",
+        "2021",
+        "2022",
+        "2023",
+        "2024",
+        "Q1",
+        "Q2",
+        "Q3",
+        "Q4",
+        "Templates",
+        "Clients",
+        "Budget Planning",
+        "Präsentationen",
+        "資料",
+    ],
+    file_stems: &[
+        "Quarterly Report",
+        "Invoice",
+        "Meeting Notes",
+        "Budget",
+        "Contract",
+        "Proposal",
+        "Draft",
+        "Final",
+        "Copy of Budget",
+        "Org Chart",
+        "Policy",
+        "Handbook",
+        "Résumé",
+        "Übersicht",
+        "報告書",
+        "Timesheet",
+        "Roadmap",
+    ],
+    extensions: &[
+        "docx", "docx", "docx", "xlsx", "xlsx", "pdf", "pdf", "pdf", "pptx", "msg", "zip", "txt",
+        "csv", "vsdx", "jpg", "png", "tmp", "lnk",
+    ],
+};
+
+/// [`Profile::PhotoArchive`] parameters.
+const PHOTO_ARCHIVE: ProfileParams = ProfileParams {
+    dir_per_mille: 15,
+    deleted_per_mille: 20,
+    hard_link_per_mille: 2,
+    ads_per_mille: 5,
+    reparse_per_mille: 0,
+    attr_list_per_mille: 1,
+    dos_name_per_mille: 10,
+    locality_per_mille: 950,
+    size_log2: (18, 26),
+    epoch: EPOCH_2015,
+    dir_names: &[
+        "2015-06-14",
+        "2016-08-02",
+        "2017-12-24",
+        "2018-07-30",
+        "2019-03-11",
+        "2020-10-05",
+        "2021-05-22",
+        "2022-09-17",
+        "2023-01-01",
+        "Vacation",
+        "Wedding",
+        "Family",
+        "Edits",
+        "Exports",
+        "RAW",
+        "Selects",
+    ],
+    file_stems: &["IMG", "DSC", "DSCF", "_MG", "PXL", "GOPR", "P100"],
+    extensions: &[
+        "JPG", "JPG", "JPG", "CR2", "NEF", "ARW", "HEIC", "xmp", "xmp", "MP4", "MOV", "dng", "tif",
+    ],
+};
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().replace('_', "-").as_str() {
+            "workstation" | "dev" | "developer" => Ok(Self::Workstation),
+            "file-server" | "fileserver" | "server" => Ok(Self::FileServer),
+            "photo-archive" | "photos" | "photo" => Ok(Self::PhotoArchive),
+            other => Err(format!(
+                "unknown profile '{other}' (expected workstation, file-server or photo-archive)"
+            )),
+        }
+    }
+}
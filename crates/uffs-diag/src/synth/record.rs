@@ -0,0 +1,417 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Byte-level encoder for NTFS `FILE` records and the attributes the
+//! generator emits.
+//!
+//! Layout follows NTFS 3.1: a 56-byte header (USA at `0x30`), 8-byte aligned
+//! attributes, an `0xFFFFFFFF` end marker, and update-sequence protection
+//! applied last via [`uffs_mft::protect_usa`].
+
+/// Offset of the update sequence array in every generated record.
+const USA_OFFSET: u16 = 0x30;
+
+/// Header size of a resident attribute (value follows the name).
+const RESIDENT_HEADER: usize = 24;
+
+/// Header size of a non-resident attribute (mapping pairs follow the name).
+const NON_RESIDENT_HEADER: usize = 64;
+
+/// End-of-attributes marker.
+const ATTR_END: u32 = 0xFFFF_FFFF;
+
+/// `$STANDARD_INFORMATION`.
+pub(crate) const ATTR_STANDARD_INFORMATION: u32 = 0x10;
+/// `$ATTRIBUTE_LIST`.
+pub(crate) const ATTR_ATTRIBUTE_LIST: u32 = 0x20;
+/// `$FILE_NAME`.
+pub(crate) const ATTR_FILE_NAME: u32 = 0x30;
+/// `$VOLUME_NAME`.
+pub(crate) const ATTR_VOLUME_NAME: u32 = 0x60;
+/// `$DATA`.
+pub(crate) const ATTR_DATA: u32 = 0x80;
+/// `$INDEX_ROOT`.
+pub(crate) const ATTR_INDEX_ROOT: u32 = 0x90;
+/// `$REPARSE_POINT`.
+pub(crate) const ATTR_REPARSE_POINT: u32 = 0xC0;
+
+/// Record header flag: record is in use.
+pub(crate) const FLAG_IN_USE: u16 = 0x0001;
+/// Record header flag: record is a directory.
+pub(crate) const FLAG_DIRECTORY: u16 = 0x0002;
+
+/// `IO_REPARSE_TAG_MOUNT_POINT` (junction).
+pub(crate) const TAG_MOUNT_POINT: u32 = 0xA000_0003;
+/// `IO_REPARSE_TAG_SYMLINK`.
+pub(crate) const TAG_SYMLINK: u32 = 0xA000_000C;
+
+/// NTFS cluster size assumed for allocated sizes and run lists.
+pub(crate) const CLUSTER_SIZE: u64 = 4096;
+
+/// Round `len` up to the next multiple of 8.
+const fn align8(len: usize) -> usize {
+    (len + 7) & !7
+}
+
+/// Copy `bytes` into `buf` at `offset`; out-of-range writes are dropped
+/// (callers size-check first).
+pub(crate) fn put(buf: &mut [u8], offset: usize, bytes: &[u8]) {
+    if let Some(dest) = buf.get_mut(offset..offset + bytes.len()) {
+        dest.copy_from_slice(bytes);
+    }
+}
+
+/// Encode UTF-16 units as little-endian bytes.
+pub(crate) fn utf16_bytes(units: &[u16]) -> Vec<u8> {
+    units.iter().flat_map(|unit| unit.to_le_bytes()).collect()
+}
+
+/// An NTFS file reference: FRS in the low 48 bits, sequence in the high 16.
+pub(crate) const fn file_ref(frs: u64, sequence: u16) -> u64 {
+    (frs & 0x0000_FFFF_FFFF_FFFF) | ((sequence as u64) << 48)
+}
+
+/// Timestamps shared by `$STANDARD_INFORMATION` and `$FILE_NAME`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Times {
+    /// Creation time (FILETIME).
+    pub(crate) created: u64,
+    /// Last data modification (FILETIME).
+    pub(crate) modified: u64,
+    /// Last MFT record change (FILETIME).
+    pub(crate) changed: u64,
+    /// Last access (FILETIME).
+    pub(crate) accessed: u64,
+}
+
+impl Times {
+    /// The four timestamps in on-disk order.
+    fn bytes(self) -> [u8; 32] {
+        let mut out = [0_u8; 32];
+        put(&mut out, 0, &self.created.to_le_bytes());
+        put(&mut out, 8, &self.modified.to_le_bytes());
+        put(&mut out, 16, &self.changed.to_le_bytes());
+        put(&mut out, 24, &self.accessed.to_le_bytes());
+        out
+    }
+}
+
+/// A 72-byte (NTFS 3.x) `$STANDARD_INFORMATION` value.
+pub(crate) fn standard_information(times: Times, file_attributes: u32) -> [u8; 72] {
+    let mut out = [0_u8; 72];
+    put(&mut out, 0, &times.bytes());
+    put(&mut out, 32, &file_attributes.to_le_bytes());
+    out
+}
+
+/// Fields of a `$FILE_NAME` value.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FileNameFields<'name> {
+    /// Parent directory file reference.
+    pub(crate) parent: u64,
+    /// Timestamps copied from `$STANDARD_INFORMATION`.
+    pub(crate) times: Times,
+    /// Allocated size of the unnamed `$DATA` stream.
+    pub(crate) allocated: u64,
+    /// Real size of the unnamed `$DATA` stream.
+    pub(crate) size: u64,
+    /// File attribute flags.
+    pub(crate) flags: u32,
+    /// Reparse tag (when `FILE_ATTRIBUTE_REPARSE_POINT` is set).
+    pub(crate) reparse_tag: u32,
+    /// Namespace: 0 POSIX, 1 Win32, 2 DOS, 3 Win32+DOS.
+    pub(crate) namespace: u8,
+    /// The name in UTF-16 (at most 255 units).
+    pub(crate) name: &'name [u16],
+}
+
+/// Encode a `$FILE_NAME` value (also used as an `$I30` index key).
+pub(crate) fn file_name(fields: &FileNameFields<'_>) -> Vec<u8> {
+    let name_len = fields.name.len().min(255);
+    let mut out = vec![0_u8; 66 + name_len * 2];
+    put(&mut out, 0, &fields.parent.to_le_bytes());
+    put(&mut out, 8, &fields.times.bytes());
+    put(&mut out, 40, &fields.allocated.to_le_bytes());
+    put(&mut out, 48, &fields.size.to_le_bytes());
+    put(&mut out, 56, &fields.flags.to_le_bytes());
+    put(&mut out, 60, &fields.reparse_tag.to_le_bytes());
+    put(&mut out, 64, &[u8::try_from(name_len).unwrap_or(u8::MAX)]);
+    put(&mut out, 65, &[fields.namespace]);
+    put(
+        &mut out,
+        66,
+        &utf16_bytes(fields.name.get(..name_len).unwrap_or_default()),
+    );
+    out
+}
+
+/// A resident `$I30` `$INDEX_ROOT` value holding only the end entry (small
+/// directory whose children live in the parent-reference graph only).
+pub(crate) fn empty_index_root() -> [u8; 48] {
+    let mut out = [0_u8; 48];
+    put(&mut out, 0, &ATTR_FILE_NAME.to_le_bytes());
+    put(&mut out, 4, &1_u32.to_le_bytes()); // COLLATION_FILE_NAME
+    put(&mut out, 8, &4096_u32.to_le_bytes());
+    put(&mut out, 12, &1_u32.to_le_bytes());
+    // Index header: first entry at 16, 16 bytes in use / allocated.
+    put(&mut out, 16, &16_u32.to_le_bytes());
+    put(&mut out, 20, &32_u32.to_le_bytes());
+    put(&mut out, 24, &32_u32.to_le_bytes());
+    // End entry: length 16, flags LAST.
+    put(&mut out, 40, &16_u16.to_le_bytes());
+    put(&mut out, 44, &2_u16.to_le_bytes());
+    out
+}
+
+/// A mount-point or symlink `$REPARSE_POINT` value targeting `target`
+/// (a `C:\...` path).
+pub(crate) fn reparse_point(tag: u32, target: &[u16]) -> Vec<u8> {
+    let mut substitute: Vec<u16> = r"\??\".encode_utf16().collect();
+    substitute.extend_from_slice(target);
+    let subst_bytes = utf16_bytes(&substitute);
+    let print_bytes = utf16_bytes(target);
+    let header = if tag == TAG_SYMLINK { 20 } else { 16 };
+    // Mount points NUL-terminate both names; symlinks do not.
+    let terminator = if tag == TAG_SYMLINK { 0 } else { 2 };
+    let print_off = subst_bytes.len() + terminator;
+    let buffer_len = print_off + print_bytes.len() + terminator;
+    let mut out = vec![0_u8; header + buffer_len];
+    let data_len = out.len() - 8;
+    put(&mut out, 0, &tag.to_le_bytes());
+    put(
+        &mut out,
+        4,
+        &u16::try_from(data_len).unwrap_or_default().to_le_bytes(),
+    );
+    put(&mut out, 8, &0_u16.to_le_bytes());
+    put(
+        &mut out,
+        10,
+        &u16::try_from(subst_bytes.len())
+            .unwrap_or_default()
+            .to_le_bytes(),
+    );
+    put(
+        &mut out,
+        12,
+        &u16::try_from(print_off).unwrap_or_default().to_le_bytes(),
+    );
+    put(
+        &mut out,
+        14,
+        &u16::try_from(print_bytes.len())
+            .unwrap_or_default()
+            .to_le_bytes(),
+    );
+    put(&mut out, header, &subst_bytes);
+    put(&mut out, header + print_off, &print_bytes);
+    out
+}
+
+/// One `$ATTRIBUTE_LIST` entry: attribute `type_code` (named `name`) with
+/// instance `attr_id` lives in the record `record_ref`.
+pub(crate) fn attribute_list_entry(
+    type_code: u32,
+    name: &[u16],
+    record_ref: u64,
+    attr_id: u16,
+) -> Vec<u8> {
+    let len = align8(26 + name.len() * 2);
+    let mut out = vec![0_u8; len];
+    put(&mut out, 0, &type_code.to_le_bytes());
+    put(
+        &mut out,
+        4,
+        &u16::try_from(len).unwrap_or_default().to_le_bytes(),
+    );
+    put(&mut out, 6, &[
+        u8::try_from(name.len()).unwrap_or_default(),
+        26,
+    ]);
+    put(&mut out, 16, &record_ref.to_le_bytes());
+    put(&mut out, 24, &attr_id.to_le_bytes());
+    put(&mut out, 26, &utf16_bytes(name));
+    out
+}
+
+/// Incremental writer for one `FILE` record.
+pub(crate) struct RecordBuilder<'buf> {
+    /// The record buffer (`record_size` bytes).
+    buf: &'buf mut [u8],
+    /// Offset of the next attribute.
+    offset: usize,
+    /// Next attribute instance id.
+    next_id: u16,
+}
+
+impl<'buf> RecordBuilder<'buf> {
+    /// Start a record: zeroes `buf` and writes the header.
+    pub(crate) fn new(
+        buf: &'buf mut [u8],
+        frs: u64,
+        sequence: u16,
+        flags: u16,
+        base_ref: u64,
+        link_count: u16,
+    ) -> Self {
+        buf.fill(0);
+        let record_size = buf.len();
+        let usa_count = record_size / uffs_mft::SECTOR_SIZE + 1;
+        let first_attr = align8(usize::from(USA_OFFSET) + usa_count * 2);
+        put(buf, 0, b"FILE");
+        put(buf, 4, &USA_OFFSET.to_le_bytes());
+        put(
+            buf,
+            6,
+            &u16::try_from(usa_count).unwrap_or_default().to_le_bytes(),
+        );
+        put(buf, 8, &(frs << 8).to_le_bytes()); // plausible, monotonic LSN
+        put(buf, 16, &sequence.to_le_bytes());
+        put(buf, 18, &link_count.to_le_bytes());
+        put(
+            buf,
+            20,
+            &u16::try_from(first_attr).unwrap_or_default().to_le_bytes(),
+        );
+        put(buf, 22, &flags.to_le_bytes());
+        put(
+            buf,
+            28,
+            &u32::try_from(record_size).unwrap_or_default().to_le_bytes(),
+        );
+        put(buf, 32, &base_ref.to_le_bytes());
+        put(
+            buf,
+            44,
+            &u32::try_from(frs).unwrap_or_default().to_le_bytes(),
+        );
+        // Update sequence number; the array entries are filled by protect_usa.
+        put(buf, usize::from(USA_OFFSET), &sequence.max(1).to_le_bytes());
+        Self {
+            buf,
+            offset: first_attr,
+            next_id: 0,
+        }
+    }
+
+    /// Bytes still free for attributes (keeping room for the end marker).
+    pub(crate) const fn remaining(&self) -> usize {
+        self.buf.len().saturating_sub(self.offset + 8)
+    }
+
+    /// Write the common attribute header fields and return the attribute's
+    /// start offset, or `None` if `len` does not fit.
+    fn begin(
+        &mut self,
+        type_code: u32,
+        len: usize,
+        non_resident: bool,
+        name: &[u16],
+        name_off: usize,
+    ) -> Option<usize> {
+        if len > self.remaining() {
+            return None;
+        }
+        let start = self.offset;
+        put(self.buf, start, &type_code.to_le_bytes());
+        put(self.buf, start + 4, &u32::try_from(len).ok()?.to_le_bytes());
+        put(self.buf, start + 8, &[
+            u8::from(non_resident),
+            u8::try_from(name.len()).ok()?,
+        ]);
+        put(
+            self.buf,
+            start + 10,
+            &u16::try_from(name_off).ok()?.to_le_bytes(),
+        );
+        put(self.buf, start + 14, &self.next_id.to_le_bytes());
+        put(self.buf, start + name_off, &utf16_bytes(name));
+        self.next_id += 1;
+        self.offset += len;
+        Some(start)
+    }
+
+    /// Append a resident attribute; skipped if it does not fit (entries are
+    /// sized so that never happens).
+    pub(crate) fn resident(&mut self, type_code: u32, name: &[u16], value: &[u8]) {
+        let value_off = align8(RESIDENT_HEADER + name.len() * 2);
+        let len = align8(value_off + value.len());
+        let Some(start) = self.begin(type_code, len, false, name, RESIDENT_HEADER) else {
+            return;
+        };
+        put(
+            self.buf,
+            start + 16,
+            &u32::try_from(value.len()).unwrap_or_default().to_le_bytes(),
+        );
+        put(
+            self.buf,
+            start + 20,
+            &u16::try_from(value_off).unwrap_or_default().to_le_bytes(),
+        );
+        put(self.buf, start + value_off, value);
+    }
+
+    /// Append a non-resident attribute of `size` bytes stored in one run at
+    /// `lcn`; skipped if it does not fit.
+    pub(crate) fn non_resident(&mut self, type_code: u32, name: &[u16], size: u64, lcn: u64) {
+        let clusters = size.div_ceil(CLUSTER_SIZE).max(1);
+        let run = mapping_pairs(clusters, lcn);
+        let runs_off = align8(NON_RESIDENT_HEADER + name.len() * 2);
+        let len = align8(runs_off + run.len() + 1);
+        let Some(start) = self.begin(type_code, len, true, name, NON_RESIDENT_HEADER) else {
+            return;
+        };
+        put(self.buf, start + 24, &(clusters - 1).to_le_bytes());
+        put(
+            self.buf,
+            start + 32,
+            &u16::try_from(runs_off).unwrap_or_default().to_le_bytes(),
+        );
+        put(
+            self.buf,
+            start + 40,
+            &(clusters * CLUSTER_SIZE).to_le_bytes(),
+        );
+        put(self.buf, start + 48, &size.to_le_bytes());
+        put(self.buf, start + 56, &size.to_le_bytes());
+        put(self.buf, start + runs_off, &run);
+    }
+
+    /// Write the end marker, `bytes_in_use` and the next attribute id, then
+    /// apply update-sequence protection.
+    pub(crate) fn finish(self) {
+        let Self {
+            buf,
+            offset,
+            next_id,
+        } = self;
+        put(buf, offset, &ATTR_END.to_le_bytes());
+        put(
+            buf,
+            24,
+            &u32::try_from(offset + 8).unwrap_or_default().to_le_bytes(),
+        );
+        put(buf, 40, &next_id.to_le_bytes());
+        let usa_count = u16::try_from(buf.len() / uffs_mft::SECTOR_SIZE + 1).unwrap_or_default();
+        let _protected = uffs_mft::protect_usa(buf, USA_OFFSET, usa_count);
+    }
+}
+
+/// Minimal little-endian byte width of `value` (at least one byte).
+const fn byte_width(value: u64) -> usize {
+    let bits = 64 - uffs_mft::u32_as_usize(value.leading_zeros());
+    // +1 bit so the signed LCN delta never reads back negative.
+    (bits + 8_usize) / 8
+}
+
+/// A single-run mapping-pairs array (without the terminating zero).
+fn mapping_pairs(clusters: u64, lcn: u64) -> Vec<u8> {
+    let len_width = byte_width(clusters);
+    let off_width = byte_width(lcn);
+    let mut out = Vec::with_capacity(1 + len_width + off_width);
+    out.push(u8::try_from(len_width | (off_width << 4_u32)).unwrap_or_default());
+    out.extend_from_slice(clusters.to_le_bytes().get(..len_width).unwrap_or_default());
+    out.extend_from_slice(lcn.to_le_bytes().get(..off_width).unwrap_or_default());
+    out
+}
@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for the synthetic MFT generator.
+
+use uffs_mft::parse::{ParseResult, parse_record_full};
+
+use super::{Profile, SynthConfig, generate, write_capture};
+
+/// A small config for `profile`.
+fn config(profile: Profile, seed: u64) -> SynthConfig {
+    SynthConfig {
+        seed,
+        profile,
+        records: 4000,
+        record_size: 1024,
+    }
+}
+
+#[test]
+fn output_is_a_pure_function_of_the_config() {
+    let (first, first_stats) = generate(config(Profile::Workstation, 7)).expect("generate");
+    let (second, second_stats) = generate(config(Profile::Workstation, 7)).expect("generate");
+    assert_eq!(first, second);
+    assert_eq!(first_stats, second_stats);
+
+    let (other, _) = generate(config(Profile::Workstation, 8)).expect("generate");
+    assert_ne!(first, other);
+}
+
+#[test]
+fn every_record_fixes_up_and_parses() {
+    for profile in Profile::ALL {
+        let (data, stats) = generate(config(profile, 1)).expect("generate");
+        let mut bases = 0_u64;
+        let mut extensions = 0_u64;
+        let mut split_bases = 0_u64;
+        for (frs, record) in data.chunks_exact(1024).enumerate() {
+            let mut fixed = record.to_vec();
+            assert!(
+                uffs_mft::fixup_file_record(&mut fixed),
+                "{profile} FRS {frs}"
+            );
+            match parse_record_full(&fixed, uffs_mft::usize_to_u64(frs)) {
+                // FRS 12–15 are reserved: in use, unnamed and not counted.
+                ParseResult::Base(_) if (12..16).contains(&frs) => {}
+                ParseResult::Base(parsed) => {
+                    // Split entries keep their names in the extension record.
+                    if parsed.name.is_empty() {
+                        split_bases += 1;
+                    }
+                    bases += 1;
+                }
+                ParseResult::Extension(_) => extensions += 1,
+                ParseResult::Skip => {}
+            }
+        }
+        assert_eq!(extensions, stats.extension_records, "{profile}");
+        assert_eq!(split_bases, stats.extension_records, "{profile}");
+        assert_eq!(bases, stats.in_use, "{profile}");
+        assert!(
+            stats.deleted > 0 && stats.directories > 2,
+            "{profile}: {stats:?}"
+        );
+    }
+}
+
+#[test]
+fn file_server_profile_exercises_links_streams_and_short_names() {
+    let (_, stats) = generate(config(Profile::FileServer, 3)).expect("generate");
+    assert!(stats.hard_links > 0, "{stats:?}");
+    assert!(stats.streams > 100, "{stats:?}");
+    assert!(stats.dos_names > 1000, "{stats:?}");
+    assert!(stats.extension_records > 0, "{stats:?}");
+}
+
+#[test]
+fn capture_loads_into_an_index() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("synth_mft.bin");
+    let config = config(Profile::FileServer, 11);
+
+    let stats = write_capture(&path, config, true).expect("write capture");
+    assert_eq!(stats.records, config.records);
+    let index = uffs_mft::MftReader::load_raw_to_index(&path).expect("load capture");
+
+    let user_records = index
+        .records
+        .iter()
+        .filter(|record| record.frs.raw() >= super::FIRST_USER_FRS)
+        .count();
+    assert_eq!(
+        uffs_mft::usize_to_u64(user_records),
+        stats.in_use - (super::FIRST_USER_FRS - 12),
+        "{stats:?}"
+    );
+    let linked = index
+        .records
+        .iter()
+        .filter(|record| record.name_count > 1)
+        .count();
+    assert!(linked > 0);
+}
+
+#[test]
+fn profiles_parse_from_cli_names() {
+    for profile in Profile::ALL {
+        assert_eq!(profile.name().parse::<Profile>(), Ok(profile));
+    }
+    assert_eq!("photos".parse::<Profile>(), Ok(Profile::PhotoArchive));
+    "nas".parse::<Profile>().expect_err("not a profile");
+}
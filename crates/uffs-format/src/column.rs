@@ -117,6 +117,12 @@ pub enum OutputColumn {
     MalformedPath,
     /// Hex of the true (WTF-8) leaf-name bytes. WI-4.4 forensic evidence.
     NameHex,
+    /// Host label of the volume (empty for local volumes).
+    Host,
+    /// Cached full-content digest (`blake3:…`), empty when never hashed.
+    Hash,
+    /// The `--patterns-from` entry the name matched, empty without a list.
+    MatchedPattern,
 }
 
 impl OutputColumn {
@@ -164,6 +170,9 @@ impl OutputColumn {
         Self::Malformed,
         Self::MalformedPath,
         Self::NameHex,
+        Self::Host,
+        Self::Hash,
+        Self::MatchedPattern,
     ];
 
     /// Canonical wire / config name — matches `FieldMeta::canonical_name`
@@ -213,6 +222,9 @@ impl OutputColumn {
             Self::Malformed => "malformed",
             Self::MalformedPath => "malformed_path",
             Self::NameHex => "name_hex",
+            Self::Host => "host",
+            Self::Hash => "hash",
+            Self::MatchedPattern => "matched_pattern",
         }
     }
 
@@ -276,6 +288,9 @@ impl OutputColumn {
             Self::Malformed => "Malformed",
             Self::MalformedPath => "Malformed Path",
             Self::NameHex => "Name (hex)",
+            Self::Host => "Host",
+            Self::Hash => "Hash",
+            Self::MatchedPattern => "Matched Pattern",
         }
     }
 
@@ -319,6 +334,9 @@ impl OutputColumn {
             Self::Malformed => &["ill_formed", "illformed", "bad_name"],
             Self::MalformedPath => &["malformedpath", "ill_formed_path", "bad_path"],
             Self::NameHex => &["namehex", "name_bytes_hex"],
+            Self::Host => &["machine"],
+            Self::Hash => &["content_hash", "digest"],
+            Self::MatchedPattern => &["ioc", "matched"],
             // Variants with no aliases fall through to the empty slice.
             Self::Path
             | Self::Name
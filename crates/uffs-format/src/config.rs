@@ -8,6 +8,8 @@
 //! one `OutputConfig` regardless of which end of the pipeline they
 //! touch.
 
+use uffs_time::TimeZone;
+
 use crate::column::{OutputColumn, PARITY_COLUMN_ORDER};
 
 /// Configuration for columnar CSV output.
@@ -53,11 +55,11 @@ pub struct OutputConfig {
     pub pos: String,
     /// Text for a `false` boolean flag column (default: `"0"`).
     pub neg: String,
-    /// Fixed timezone offset (seconds from UTC) applied to all
-    /// timestamp columns.  Matches Windows'
-    /// `FileTimeToLocalFileTime()` behaviour: a single CURRENT offset
-    /// is applied to every row, ignoring historical DST transitions.
-    pub timezone_offset_secs: i32,
+    /// Zone applied to all timestamp columns.  Defaults to a fixed
+    /// offset matching Windows' `FileTimeToLocalFileTime()` behaviour: a
+    /// single CURRENT offset is applied to every row, ignoring historical
+    /// DST transitions.  A named zone (`--tz`) resolves DST per row.
+    pub time_zone: TimeZone,
     /// Parity-compat mode: directories get trailing `\` in `Path`,
     /// empty `Name`, self-path in `PathOnly`, and treesize for `Size`.
     ///
@@ -72,7 +74,7 @@ impl Default for OutputConfig {
         // Auto-detect the host's local offset once — matches the
         // legacy CLI behaviour and `uffs-core::output::OutputConfig`
         // before the v0.5.62 unification.
-        let timezone_offset_secs = chrono::Local::now().offset().local_minus_utc();
+        let time_zone = TimeZone::fixed(chrono::Local::now().offset().local_minus_utc());
 
         Self {
             columns: None,
@@ -81,7 +83,7 @@ impl Default for OutputConfig {
             header: true,
             pos: "1".to_owned(),
             neg: "0".to_owned(),
-            timezone_offset_secs,
+            time_zone,
             parity_compat: false,
         }
     }
@@ -189,7 +191,14 @@ impl OutputConfig {
     /// Override the timezone offset (hours from UTC).
     #[must_use]
     pub const fn with_tz_offset_hours(mut self, hours: i32) -> Self {
-        self.timezone_offset_secs = hours * 3_600_i32;
+        self.time_zone = TimeZone::fixed(hours * 3_600_i32);
+        self
+    }
+
+    /// Override the time zone (fixed offset or named IANA zone).
+    #[must_use]
+    pub const fn with_time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
         self
     }
 
@@ -5,22 +5,24 @@
 
 use core::fmt::Write as _;
 
+use uffs_time::TimeZone;
+
 /// Append `YYYY-MM-DD HH:MM:SS` from a raw FILETIME (100-ns ticks
-/// since 1601-01-01) with the supplied timezone offset.
+/// since 1601-01-01) in the supplied time zone.
 ///
 /// v13+ of the compact index stores timestamps as **raw FILETIME**
 /// (matching the C++ NTFS baseline), not Unix microseconds.  This
-/// function interprets its argument as FILETIME; the bias is applied
-/// in FILETIME ticks (matching C++ `FileTimeToLocalFileTime`),
-/// then the result is decomposed via the Hinnant civil calendar in
-/// [`uffs_time::filetime_to_calendar`].
+/// function interprets its argument as FILETIME; the zone's offset at
+/// that instant is applied in FILETIME ticks (a fixed offset matches
+/// C++ `FileTimeToLocalFileTime`), then the result is decomposed via the
+/// Hinnant civil calendar in [`uffs_time::filetime_to_calendar`].
 ///
 /// A `filetime` of 0 (the "unset / null" sentinel) formats as
 /// `"0000-00-00 00:00:00"` instead of decomposing to a 1601 date, so
 /// callers (and users reading CSV output) can recognise missing
 /// timestamps.
-pub(crate) fn append_datetime_native(buf: &mut String, filetime: i64, tz_offset_secs: i32) {
-    let local_ft = uffs_time::filetime_with_tz_bias(filetime, tz_offset_secs);
+pub(crate) fn append_datetime_native(buf: &mut String, filetime: i64, time_zone: TimeZone) {
+    let local_ft = time_zone.to_local(filetime);
     if let Some(uffs_time::CalendarParts {
         year,
         month,
@@ -45,6 +47,8 @@ pub(crate) fn append_datetime_native(buf: &mut String, filetime: i64, tz_offset_
 
 #[cfg(test)]
 mod tests {
+    use uffs_time::TimeZone;
+
     use super::append_datetime_native;
 
     /// Pin the FILETIME-as-FILETIME invariant — a 2024-01-01 anchor
@@ -54,7 +58,7 @@ mod tests {
     fn filetime_2024_utc_decomposes_correctly() {
         let ft_2024: i64 = 133_485_408_000_000_000;
         let mut buf = String::new();
-        append_datetime_native(&mut buf, ft_2024, 0);
+        append_datetime_native(&mut buf, ft_2024, TimeZone::UTC);
         assert_eq!(buf, "2024-01-01 00:00:00");
     }
 
@@ -62,7 +66,7 @@ mod tests {
     #[test]
     fn zero_filetime_is_zero_sentinel() {
         let mut buf = String::new();
-        append_datetime_native(&mut buf, 0, 0);
+        append_datetime_native(&mut buf, 0, TimeZone::UTC);
         assert_eq!(buf, "0000-00-00 00:00:00");
     }
 
@@ -73,7 +77,21 @@ mod tests {
         let ft_2024: i64 = 133_485_408_000_000_000;
         let pst_offset: i32 = -8 * 3600;
         let mut buf = String::new();
-        append_datetime_native(&mut buf, ft_2024, pst_offset);
+        append_datetime_native(&mut buf, ft_2024, TimeZone::fixed(pst_offset));
         assert_eq!(buf, "2023-12-31 16:00:00");
     }
+
+    /// A named zone applies the offset in force at each instant.
+    #[test]
+    fn named_zone_follows_daylight_saving() {
+        let berlin = TimeZone::named("Europe/Berlin").expect("IANA zone");
+        // 2024-07-01 00:00:00 UTC.
+        let ft_july: i64 = 133_642_656_000_000_000;
+        let mut buf = String::new();
+        append_datetime_native(&mut buf, ft_july, berlin);
+        assert_eq!(buf, "2024-07-01 02:00:00");
+        buf.clear();
+        append_datetime_native(&mut buf, 133_485_408_000_000_000, berlin);
+        assert_eq!(buf, "2024-01-01 01:00:00");
+    }
 }
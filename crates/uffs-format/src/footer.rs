@@ -17,7 +17,7 @@
 //! ```text
 //! <CSV body>
 //! \r\n\r\n
-//! Drives? \t<N>\t<letters>\r\n
+//! Drives? \t<N>\t<drive labels>\r\n
 //! \r\n
 //! MMMmmm that was FAST ...\t<pattern>\r\n   (full-scan + small)
 //! Search path. E.g. 'C:/' or 'C:\Prog**' \r\n
@@ -53,11 +53,11 @@ use std::io::{self, Write};
 /// and `uffs_daemon::handler_blob`) live in the same workspace.
 #[derive(Debug, Clone, Copy)]
 pub struct DriveFooterContext<'a> {
-    /// Drive letters the search targeted (e.g. `['C', 'D']`).  When
-    /// empty the footer is omitted entirely — matches the CLI's
-    /// behaviour for searches that did not specify an explicit
-    /// `--drive` / `--drives`.
-    pub output_targets: &'a [char],
+    /// Drive labels of the volumes the search targeted (e.g.
+    /// `["C:", "srv01\\D:"]`).  When empty the footer is omitted
+    /// entirely — matches the CLI's behaviour for searches that did not
+    /// specify an explicit `--drive` / `--drives`.
+    pub output_targets: &'a [String],
     /// Raw search pattern the user supplied (`"*"`, `"*.dll"`,
     /// `">ext.*"`, …).  Used both to label the fast-scan warning
     /// line and to drive the full-scan heuristic.
@@ -111,14 +111,10 @@ pub fn write_legacy_drive_footer<W: Write + ?Sized>(
     Ok(())
 }
 
-/// Format drive letters in the legacy `C:|D:|E:` shape — uppercase
-/// letters separated by `|`, each followed by `:`.
-fn format_legacy_drive_letters(output_targets: &[char]) -> String {
-    output_targets
-        .iter()
-        .map(|drive| format!("{}:", drive.to_ascii_uppercase()))
-        .collect::<Vec<_>>()
-        .join("|")
+/// Format drive labels in the legacy `C:|D:|E:` shape — labels
+/// separated by `|`.
+fn format_legacy_drive_letters(output_targets: &[String]) -> String {
+    output_targets.join("|")
 }
 
 /// Classify a pattern as a full-scan candidate for the fast-scan
@@ -169,7 +165,7 @@ mod tests {
     fn single_drive_full_scan_includes_fast_warning() {
         let mut buf = Vec::new();
         let ctx = DriveFooterContext {
-            output_targets: &['G'],
+            output_targets: &["G:".to_owned()],
             pattern: "*",
             row_count: 100,
         };
@@ -183,19 +179,20 @@ mod tests {
         );
     }
 
-    /// Multi-drive footer joins with `|` in the legacy style.
+    /// Multi-drive footer joins with `|` in the legacy style; hosted
+    /// volumes keep their host.
     #[test]
     fn multi_drive_letters_are_pipe_joined() {
         let mut buf = Vec::new();
         let ctx = DriveFooterContext {
-            output_targets: &['c', 'D'],
+            output_targets: &["C:".to_owned(), "srv01\\D:".to_owned()],
             pattern: ">.*\\.(jpg|png)",
             row_count: 5_000,
         };
         write_legacy_drive_footer(&mut buf, &ctx).expect("write");
         let text = String::from_utf8(buf).expect("utf8");
         assert!(
-            text.starts_with("\r\n\r\nDrives? \t2\tC:|D:\r\n\r\n"),
+            text.starts_with("\r\n\r\nDrives? \t2\tC:|srv01\\D:\r\n\r\n"),
             "unexpected header: {text:?}"
         );
         // Real regex (has alternation with a real suffix) — fast-scan
@@ -211,7 +208,7 @@ mod tests {
     fn fast_scan_suppressed_at_row_threshold() {
         let mut buf = Vec::new();
         let ctx = DriveFooterContext {
-            output_targets: &['G'],
+            output_targets: &["G:".to_owned()],
             pattern: "*",
             row_count: super::FAST_SCAN_ROW_LIMIT,
         };
@@ -90,4 +90,20 @@ pub trait FormatRow {
     fn name_hex(&self) -> Option<&str> {
         None
     }
+    /// Host label of the row's volume (`srv01` for an offline capture of
+    /// `srv01\C`).  Defaults to `""`, the local machine; labels are interned,
+    /// hence `'static`.
+    fn host(&self) -> &'static str {
+        ""
+    }
+    /// Cached full-content digest rendered `<algo>:<hex>`, or `None` when
+    /// the file was never hashed or the row type does not carry it.
+    fn content_hash(&self) -> Option<&str> {
+        None
+    }
+    /// The pattern-list entry the row's name matched, or `None` when no
+    /// list was given or the row type does not carry it.
+    fn matched_pattern(&self) -> Option<&str> {
+        None
+    }
 }
@@ -197,13 +197,13 @@ pub(crate) fn write_row<R: FormatRow>(
                 buf.push_str(itoa_buf.format(val));
             }
             OutputColumn::Created => {
-                append_datetime_native(buf, row.created(), cfg.timezone_offset_secs);
+                append_datetime_native(buf, row.created(), cfg.time_zone);
             }
             OutputColumn::Modified => {
-                append_datetime_native(buf, row.modified(), cfg.timezone_offset_secs);
+                append_datetime_native(buf, row.modified(), cfg.time_zone);
             }
             OutputColumn::Accessed => {
-                append_datetime_native(buf, row.accessed(), cfg.timezone_offset_secs);
+                append_datetime_native(buf, row.accessed(), cfg.time_zone);
             }
             OutputColumn::Descendants => {
                 buf.push_str(itoa_buf.format(row.descendants()));
@@ -253,6 +253,11 @@ pub(crate) fn write_row<R: FormatRow>(
             OutputColumn::Drive => {
                 buf.push(row.drive());
             }
+            OutputColumn::Host => {
+                buf.push_str(&cfg.quote);
+                buf.push_str(row.host());
+                buf.push_str(&cfg.quote);
+            }
             OutputColumn::Extension => {
                 buf.push_str(&cfg.quote);
                 // Dot-gated: dotfiles (`.bash_history`), dotless names
@@ -286,6 +291,20 @@ pub(crate) fn write_row<R: FormatRow>(
                 }
                 buf.push_str(&cfg.quote);
             }
+            OutputColumn::Hash => {
+                buf.push_str(&cfg.quote);
+                if let Some(hash) = row.content_hash() {
+                    buf.push_str(hash);
+                }
+                buf.push_str(&cfg.quote);
+            }
+            OutputColumn::MatchedPattern => {
+                buf.push_str(&cfg.quote);
+                if let Some(pattern) = row.matched_pattern() {
+                    buf.push_str(pattern);
+                }
+                buf.push_str(&cfg.quote);
+            }
         }
     }
 }
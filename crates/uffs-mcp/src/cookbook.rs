@@ -68,6 +68,9 @@ pub(crate) fn cookbook_json() -> String {
              uffs_search call. Example: 'terms:extension,top=10,sample=3' shows 3 files per extension.",
             "rollup with sub=kind:field adds a sub-aggregation inside each bucket. \
              Example: 'rollup:path,depth=1,top=10,sub=terms:type' shows type breakdown per folder.",
+            "'>' nests any bucketed spec (terms, hist, datehist, range, rollup) inside \
+             each bucket of the previous one: 'terms:drive>terms:extension,top=10' gives the \
+             top 10 extensions per drive in one call; chains can go deeper.",
             "Pagination: use page_size=N on uffs_aggregate or uffs_facet_values to get \
              paginated results. Response includes next_cursor — pass it back as cursor \
              to get the next page. Repeat until next_cursor is null.",
@@ -648,6 +651,64 @@ fn cookbook_aggregation() -> Value {
                 "explanation": "Sub-aggregation inside each rollup bucket. Shows the \
                     type breakdown WITHIN each top-level folder. Eliminates a follow-up query."
             },
+            {
+                "title": "Custom: nested breakdown — top extensions per drive",
+                "tool": "uffs_aggregate",
+                "arguments": {
+                    "aggregations": ["terms:drive>terms:extension,top=10"]
+                },
+                "explanation": "'>' runs the second spec inside every bucket of the first, \
+                    in the same scan. Each drive bucket carries sub_buckets with its own top \
+                    10 extensions; shares are relative to the drive. Chains can go deeper, \
+                    e.g. 'rollup:path,depth=1>datehist:modified,calendar=year'."
+            },
+            {
+                "title": "Custom: median and tail file sizes",
+                "tool": "uffs_aggregate",
+                "arguments": {
+                    "aggregations": [
+                        "stats:size,metrics=median+p90+p99+max",
+                        "terms:extension,top=10,metrics=count+total_bytes+p50+p99"
+                    ]
+                },
+                "explanation": "File sizes are skewed, so avg is dominated by a few huge \
+                    files. Percentiles (median, pNN) come back in a 'percentiles' array on \
+                    stats and on every bucket — typical vs worst-case size per extension."
+            },
+            {
+                "title": "Custom: pivot — bytes per extension on each drive",
+                "tool": "uffs_aggregate",
+                "arguments": {
+                    "aggregations": ["pivot:extension,cols=drive,metric=total_bytes,top=15"]
+                },
+                "explanation": "A two-dimensional cross-tab: one row per extension, one \
+                    column per drive, with row, column and grand totals. Axes take '@' \
+                    bucketing (size@sizes, modified@year, modified@age); rows/columns past \
+                    top=/cols_top= roll into '(other)'."
+            },
+            {
+                "title": "Custom: activity heatmap — when are files written?",
+                "tool": "uffs_aggregate",
+                "arguments": {
+                    "aggregations": ["heatmap:modified"],
+                    "tz": "-5"
+                },
+                "explanation": "A 7×24 grid: weekdays down the rows, hours of the day \
+                    across, every slot present. tz= (or a spec's own tz=) sets the zone the \
+                    hours are cut in; default UTC. calendar=hour_of_day / day_of_week (and \
+                    @hod / @dow on pivot axes) give the single-axis views."
+            },
+            {
+                "title": "Custom: near-duplicate names (copy clutter)",
+                "tool": "uffs_aggregate",
+                "arguments": {
+                    "aggregations": ["similar_names:scope=all,rank=bytes,top=20,sample=3"]
+                },
+                "explanation": "Clusters files whose names match once copy markers, \
+                    version tokens and numbering are stripped — 'report (1).docx', \
+                    'Copy of report.docx', 'report_final_v2.docx'. scope=dir (default) \
+                    clusters within each folder; each cluster carries sample files."
+            },
             {
                 "title": "Custom: terms with inline samples",
                 "tool": "uffs_aggregate",
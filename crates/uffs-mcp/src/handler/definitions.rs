@@ -103,6 +103,7 @@ pub(crate) const KNOWN_TOOLS: &[&str] = &[
     "uffs_info",
     "uffs_aggregate",
     "uffs_facet_values",
+    "uffs_baselines",
 ];
 
 /// Check if a tool name is known.
@@ -114,7 +115,7 @@ pub(crate) fn is_known_tool(name: &str) -> bool {
 /// Build the static list of tool definitions for `tools/list`.
 #[must_use]
 pub(crate) fn tool_definitions() -> Vec<Tool> {
-    use crate::schemas::{DrivesOutput, InfoOutput, StatusOutput};
+    use crate::schemas::{BaselinesOutput, DrivesOutput, InfoOutput, StatusOutput};
 
     let read_only = ToolAnnotations::from_raw(
         None,        // title
@@ -154,6 +155,14 @@ pub(crate) fn tool_definitions() -> Vec<Tool> {
             Arc::clone(&empty_schema),
         ))
         .with_annotations(read_only.clone()),
+        with_clean_output_schema::<BaselinesOutput>(Tool::new(
+            "uffs_baselines",
+            "List the daemon-side baselines a snapshot diff can use: scheduled \
+             daily snapshots per drive (by date) and how far back the index \
+             history reaches. Pass one to uffs_search as diff_baseline='@<date>'.",
+            Arc::clone(&empty_schema),
+        ))
+        .with_annotations(read_only.clone()),
         with_clean_output_schema::<StatusOutput>(Tool::new(
             "uffs_status",
             "Get the current health and loading progress of the UFFS daemon. Returns \
@@ -29,12 +29,15 @@ TOOLS (all read-only):
   extension, type, path, NTFS attributes, bulkiness, treesize, descendants.
 • uffs_aggregate — Server-side analytics.  Use presets for one-call answers: \
   overview, by_type, by_extension, by_drive, by_size, by_age, storage, \
-  activity, top_folders, duplicates, media, cleanup.
+  activity, top_folders, duplicates, media, cleanup, name_years.
 • uffs_facet_values — Discover distinct values of a field (extension, type, \
   drive) with counts and byte totals.  Use BEFORE searching to understand \
   what exists.
 • uffs_info     — Full metadata for a single file/directory by path.
 • uffs_drives   — List indexed drives with record counts.
+• uffs_baselines — List daemon-side diff baselines (daily snapshots, index \
+  history).  'What got deleted since <date>?' = uffs_search with \
+  diff_baseline='@<date>' and one drive.
 • uffs_status   — Daemon health, uptime, memory, loading progress, and the \
   running UFFS server version (see STAYING CURRENT).
 
@@ -51,6 +54,11 @@ KEY PARAMETERS for uffs_search:
   regex, NEVER N separate searches: '>(solar|energy|utility|pge|sunrun)'. \
   Regex is CASE-INSENSITIVE by default.
 • match_path: true → match `pattern` against the full path, not just the name
+• match_mode: 'fuzzy' → typo-tolerant name match ranked by similarity \
+  ('recieve_invoce.xlsx' finds 'receive_invoice.xlsx'); tune with \
+  fuzzy_threshold (0–1, default 0.6).  Use it when an exact search is empty.
+• rank: true → best-match-first for a name lookup ('the report I meant'): \
+  exact names first, system files and WinSxS-style noise last.
 • filter: 'files', 'dirs', or 'all'
 • ext: 'pdf' or collection aliases: pictures, documents, videos, music, \
   archives, code  (documents = pdf, doc/docx, xls/xlsx, ppt/pptx, csv, txt, …)
@@ -68,22 +76,62 @@ KEY PARAMETERS for uffs_search:
 • sort: 'modified', '-size', 'name', '-treesize', '-descendants', '-bulkiness'
 • limit: max results (default 50, cap 500)
 • projection: columns to return — name, ext, type, size, modified, path, drive, \
-  created, accessed, allocated, treesize, descendants, tree_allocated
+  created, accessed, allocated, treesize, descendants, tree_allocated, \
+  matched_pattern
 • whole_word: true for word-boundary matching
 • attr: NTFS attributes — 'hidden', 'system', 'compressed', 'encrypted', etc.
+• hash_in: daemon-side path of a SHA-256/SHA-1/MD5 list (IOC feed, NSRL CSV) \
+  → only files whose content is listed; hash_mode='known_good' excludes them \
+  instead.  Hashes only size-matching files and caches the digests.
+• patterns: array of file names / globs / '>regex' entries (an IOC list) \
+  matched in ONE pass — bare names must equal the whole name, use '*x*' for \
+  a substring; each row's matched_pattern names the entry that hit
 • min_descendants / max_descendants: filter dirs by child count
+• fields: computed fields for this query, e.g. \
+  ['avgsize=treesize/descendants', 'slack=allocated-size'] — integer \
+  arithmetic (+ - * / %, abs, min, max, len(name), age_days(modified)); \
+  sort by them ('-avgsize'), and measure them in uffs_aggregate \
+  (fields + 'stats:slack', 'hist:avgsize,interval=1048576')
 
 KEY PARAMETERS for uffs_aggregate:
 • preset: one-word shortcut — overview, by_type, by_extension, by_drive, \
-  by_size, by_age, storage, activity, top_folders, duplicates, media, cleanup
+  by_size, by_age, storage, activity, top_folders, duplicates, media, cleanup, \
+  name_years
 • aggregations: array of custom power-syntax specs for full control. \
   10 kinds: count, stats:FIELD, terms:FIELD, hist:FIELD, datehist:FIELD, \
   range:FIELD, missing:FIELD, distinct:FIELD, rollup:path, duplicates:KEY+KEY. \
   Options: top=N, sample=N, interval=N, calendar=day|week|month|quarter|year, \
   depth=N, bins=A..B+C..D, sub=kind:field.
+• nesting: '>' evaluates a bucketed spec inside every bucket of the one \
+  before it, in the same pass — 'terms:drive>terms:extension,top=10' = top \
+  10 extensions per drive. terms/hist/datehist/range/rollup only; chains \
+  can go deeper; child shares are relative to their parent bucket.
+• percentiles: add 'median' or 'pNN' (p90, p99.9) to metrics= — \
+  'stats:size,metrics=median+p90+p99' or per bucket \
+  'terms:extension,metrics=count+p50+p99'. Sizes are skewed, so prefer \
+  the median over avg. Sketch estimates within 1%; numeric fields only.
+• pivot: 'pivot:extension,cols=drive,metric=total_bytes' = one metric \
+  cross-tabulated by two fields, with row/column/grand totals. Axis \
+  bucketing via '@': size@sizes, modified@year, modified@age. top=/cols_top= \
+  cap rows/columns; the rest rolls into '(other)'.
+• heatmap: 'heatmap:modified' = weekday × hour-of-day activity grid (7×24, \
+  every slot present). calendar=hour_of_day|day_of_week (or @hod/@dow on a \
+  pivot axis) for one cycle. tz= on the tool or a spec ('UTC', '+2', \
+  '-05:30') sets the zone hours and days are cut in; default UTC.
+• similar_names: 'similar_names:scope=dir|all,rank=count|bytes' = \
+  near-duplicate filename clusters ('report (1).docx', 'report - Copy.docx', \
+  'report_final_v2.docx' → 'report.docx') with samples. Start cleanup \
+  reviews here; exact name+size matches are 'duplicates'.
+• regex keys: 'terms:regex(name, \"^(\\d{4})-\")' or \
+  'terms:regex(path, \"\\\\Clients\\\\([^\\\\]+)\\\\\")' = buckets keyed by \
+  the first capture group (project codes, years, client folders); \
+  unmatched=true adds an '(unmatched)' bucket. Drill-downs pin the group.
 • pattern / drives: scope aggregation to a subset (same as search).
 • page_size: enable paginated buckets. Response includes next_cursor.
 • cursor: opaque token from previous response to fetch the next page.
+• diff_baseline: '@<date>' / '@yesterday' (see uffs_baselines) with one \
+  drive enables 'growth:path,depth=N,top=N,rank=abs|rel' — per-folder size \
+  growth since that baseline, paired by File Reference then path.
 POWER MOVE: stack multiple specs in ONE call — \
   aggregations=['count','stats:size','terms:type,top=10'] runs all in one pass.
 AGGREGATABLE fields (stats/hist/range): size, allocated, modified, created, \
@@ -35,6 +35,7 @@ use crate::tools;
 pub mod definitions;
 pub(crate) mod instructions;
 pub mod prompts;
+pub(crate) mod saved_queries;
 
 use definitions::is_known_tool;
 use instructions::AGENT_INSTRUCTIONS;
@@ -286,6 +287,7 @@ impl UffsMcpServer {
                 tools::search::run(&mut client, parsed, &roots_state).await
             }
             "uffs_drives" => tools::drives::run(&mut client).await,
+            "uffs_baselines" => tools::baselines::run(&mut client).await,
             "uffs_status" => tools::status::run(&mut client).await,
             "uffs_info" => {
                 let parsed = serde_json::from_value(Value::Object(args)).map_err(|err| {
@@ -483,17 +485,14 @@ impl ServerHandler for UffsMcpServer {
             .map_err(McpError::from)
     }
 
-    #[expect(
-        clippy::unused_async_trait_impl,
-        reason = "rmcp ServerHandler trait mandates an async signature; this handler returns precomputed data and has no .await"
-    )]
     async fn list_resources(
         &self,
         _request: Option<PaginatedRequestParams>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
         self.touch();
-        Ok(ListResourcesResult::with_all_items(vec![
+        let saved = saved_queries::query_resources(&self.saved_queries_or_empty().await);
+        let mut resources = vec![
             Resource::new("uffs://schema/fields", "Field Catalog")
                 .with_description(
                     "Complete catalog of fields available for searching, filtering, \
@@ -530,7 +529,9 @@ impl ServerHandler for UffsMcpServer {
                          Read this first to learn how to compose effective UFFS queries.",
                 )
                 .with_mime_type("application/json"),
-        ]))
+        ];
+        resources.extend(saved);
+        Ok(ListResourcesResult::with_all_items(resources))
     }
 
     #[expect(
@@ -551,6 +552,9 @@ impl ServerHandler for UffsMcpServer {
                      with forward slashes (e.g. C:/Users/me/file.txt).",
                 )
                 .with_mime_type("application/json"),
+            ResourceTemplate::new("uffs://queries/{name}", "Saved Query")
+                .with_description("A saved query from the daemon's queries.toml library")
+                .with_mime_type("application/json"),
         ]))
     }
 
@@ -619,6 +623,13 @@ impl ServerHandler for UffsMcpServer {
                     .map_err(|err| McpError::internal_error(err.to_string(), None))?
             }
 
+            _ if uri_str.starts_with(saved_queries::QUERY_URI_PREFIX) => {
+                let name = uri_str
+                    .get(saved_queries::QUERY_URI_PREFIX.len()..)
+                    .unwrap_or_default();
+                saved_queries::query_resource_json(name, &self.saved_query(name).await?)
+            }
+
             _ => {
                 return Err(McpError::resource_not_found(
                     format!("Unknown resource: {uri_str}"),
@@ -630,25 +641,19 @@ impl ServerHandler for UffsMcpServer {
         Ok(ReadResourceResult::new(vec![ResourceContents::text(json, request.uri)]).into())
     }
 
-    #[expect(
-        clippy::unused_async_trait_impl,
-        reason = "rmcp ServerHandler trait mandates an async signature; this handler returns precomputed data and has no .await"
-    )]
     async fn list_prompts(
         &self,
         _request: Option<PaginatedRequestParams>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListPromptsResult, McpError> {
         self.touch();
-        Ok(ListPromptsResult::with_all_items(
-            definitions::prompt_definitions(),
-        ))
+        let mut all = definitions::prompt_definitions();
+        all.extend(saved_queries::query_prompts(
+            &self.saved_queries_or_empty().await,
+        ));
+        Ok(ListPromptsResult::with_all_items(all))
     }
 
-    #[expect(
-        clippy::unused_async_trait_impl,
-        reason = "rmcp ServerHandler trait mandates an async signature; this handler builds prompt messages synchronously with no .await"
-    )]
     async fn get_prompt(
         &self,
         request: GetPromptRequestParams,
@@ -657,7 +662,17 @@ impl ServerHandler for UffsMcpServer {
         self.stats.record_prompt_get();
         let prompt_args = request.arguments.unwrap_or_default();
 
-        let messages = prompts::build_prompt_messages(request.name.as_ref(), &prompt_args)?;
+        let messages = match request
+            .name
+            .strip_prefix(saved_queries::QUERY_PROMPT_PREFIX)
+        {
+            Some(name) => saved_queries::query_prompt_messages(
+                name,
+                &self.saved_query(name).await?,
+                &prompt_args,
+            )?,
+            None => prompts::build_prompt_messages(request.name.as_ref(), &prompt_args)?,
+        };
 
         Ok(GetPromptResult::new(messages)
             .with_description(format!("UFFS prompt: {}", request.name))
@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Saved queries as MCP resources and prompts.
+//!
+//! Each entry of the daemon's `queries.toml` library is published twice:
+//!
+//! * a **resource** `uffs://queries/{name}` — the stored definition (arguments,
+//!   placeholders, defaults) for an agent to inspect;
+//! * a **prompt** `@{name}` — one argument per placeholder; `prompts/get`
+//!   expands it and tells the agent to run the resulting search with
+//!   `uffs_search`.
+//!
+//! The library is fetched per request (one `list_queries` round trip), so
+//! a query saved from the CLI shows up without restarting the bridge.  A
+//! daemon that cannot be reached simply contributes no entries to the
+//! listings; reading or expanding a specific query surfaces the error.
+
+use alloc::collections::BTreeMap;
+
+use rmcp::ErrorData as McpError;
+use rmcp::model::{Prompt, PromptArgument, PromptMessage, Resource};
+use serde_json::Value;
+use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::response::{QueryError, SavedQuery};
+
+use super::UffsMcpServer;
+use super::prompts::str_arg;
+
+/// URI prefix of the per-query resources.
+pub(crate) const QUERY_URI_PREFIX: &str = "uffs://queries/";
+
+/// Prompt-name prefix marking a saved query (mirrors the CLI's `uffs @name`).
+pub(crate) const QUERY_PROMPT_PREFIX: char = '@';
+
+impl UffsMcpServer {
+    /// Fetch the daemon's saved-query library.
+    ///
+    /// # Errors
+    ///
+    /// `McpError::internal_error` when the daemon is unreachable or the
+    /// `list_queries` RPC fails.
+    pub(super) async fn saved_queries(&self) -> Result<BTreeMap<String, SavedQuery>, McpError> {
+        let mut client = self
+            .connect_daemon()
+            .await
+            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        let library = client
+            .list_queries()
+            .await
+            .map_err(|err| McpError::internal_error(format!("list_queries: {err}"), None))?;
+        Ok(library.queries)
+    }
+
+    /// [`Self::saved_queries`] for the listings: an unreachable daemon
+    /// yields an empty library rather than failing `resources/list` or
+    /// `prompts/list` for the built-in entries.
+    pub(super) async fn saved_queries_or_empty(&self) -> BTreeMap<String, SavedQuery> {
+        self.saved_queries().await.unwrap_or_default()
+    }
+
+    /// Look up one saved query by name.
+    ///
+    /// # Errors
+    ///
+    /// `McpError::resource_not_found` for an unknown name, plus the errors
+    /// of [`Self::saved_queries`].
+    pub(super) async fn saved_query(&self, name: &str) -> Result<SavedQuery, McpError> {
+        self.saved_queries().await?.remove(name).ok_or_else(|| {
+            McpError::resource_not_found(
+                QueryError::UnknownQuery {
+                    name: name.to_owned(),
+                }
+                .to_string(),
+                None,
+            )
+        })
+    }
+}
+
+/// One `uffs://queries/{name}` resource per saved query.
+pub(super) fn query_resources(library: &BTreeMap<String, SavedQuery>) -> Vec<Resource> {
+    library
+        .iter()
+        .map(|(name, query)| {
+            Resource::new(
+                format!("{QUERY_URI_PREFIX}{name}"),
+                format!("Saved query @{name}"),
+            )
+            .with_description(
+                query
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| format!("uffs {}", query.args.join(" "))),
+            )
+            .with_mime_type("application/json")
+        })
+        .collect()
+}
+
+/// JSON body of the `uffs://queries/{name}` resource.
+pub(super) fn query_resource_json(name: &str, query: &SavedQuery) -> String {
+    let body = serde_json::json!({
+        "name": name,
+        "description": query.description,
+        "args": query.args,
+        "defaults": query.defaults,
+        "placeholders": query.placeholders(),
+        "command": format!("uffs {QUERY_PROMPT_PREFIX}{name}"),
+    });
+    serde_json::to_string_pretty(&body).unwrap_or_default()
+}
+
+/// One `@{name}` prompt per saved query; each placeholder is an argument,
+/// required when it has no default.
+pub(super) fn query_prompts(library: &BTreeMap<String, SavedQuery>) -> Vec<Prompt> {
+    library
+        .iter()
+        .map(|(name, query)| {
+            let arguments = query
+                .placeholders()
+                .into_iter()
+                .map(|placeholder| {
+                    let default = query.defaults.get(&placeholder);
+                    let description = default.map_or_else(
+                        || format!("Value for {{{placeholder}}}"),
+                        |value| format!("Value for {{{placeholder}}} (default: {value})"),
+                    );
+                    PromptArgument::new(placeholder)
+                        .with_description(description)
+                        .with_required(default.is_none())
+                })
+                .collect::<Vec<_>>();
+            let description = query
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("Run the saved search `uffs {}`", query.args.join(" ")));
+            Prompt::new(
+                format!("{QUERY_PROMPT_PREFIX}{name}"),
+                Some(description),
+                (!arguments.is_empty()).then_some(arguments),
+            )
+        })
+        .collect()
+}
+
+/// Expand saved query `name` with the prompt `args` and build the message
+/// asking the agent to run it.
+///
+/// # Errors
+///
+/// `McpError::invalid_params` when a placeholder has neither an argument
+/// nor a default, or when the expanded arguments do not parse as a search.
+pub(super) fn query_prompt_messages(
+    name: &str,
+    query: &SavedQuery,
+    args: &serde_json::Map<String, Value>,
+) -> Result<Vec<PromptMessage>, McpError> {
+    let values: BTreeMap<String, String> = query
+        .placeholders()
+        .into_iter()
+        .filter_map(|placeholder| {
+            str_arg(args, &placeholder).map(|value| (placeholder.clone(), value.to_owned()))
+        })
+        .collect();
+    let expanded = query
+        .expand(&values)
+        .map_err(|err| McpError::invalid_params(err.to_string(), None))?;
+    let params = SearchParams::from_cli_args(&expanded)
+        .map_err(|err| McpError::invalid_params(format!("saved query @{name}: {err}"), None))?;
+    let params_json = serde_json::to_value(&params)
+        .map(compact)
+        .unwrap_or_default();
+    let intro = query
+        .description
+        .as_deref()
+        .map_or_else(String::new, |description| format!(" ({description})"));
+    Ok(vec![PromptMessage::new_text(
+        rmcp::model::Role::User,
+        format!(
+            "Run the saved query @{name}{intro} with the uffs_search tool.\n\n\
+             CLI equivalent: uffs {}\n\n\
+             Parsed search parameters (map these onto the uffs_search arguments):\n{}\n\n\
+             Show the results as a table and summarise anything notable.",
+            expanded.join(" "),
+            serde_json::to_string_pretty(&params_json).unwrap_or_default(),
+        ),
+    )])
+}
+
+/// Drop unset fields (`null`, `false`, empty arrays) so the prompt shows
+/// only what the saved query actually sets.
+fn compact(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .filter(|(_, field)| match field {
+                    Value::Null | Value::Bool(false) => false,
+                    Value::Array(items) => !items.is_empty(),
+                    Value::Bool(true) | Value::Number(_) | Value::String(_) | Value::Object(_) => {
+                        true
+                    }
+                })
+                .collect(),
+        ),
+        other @ (Value::Null
+        | Value::Bool(_)
+        | Value::Number(_)
+        | Value::String(_)
+        | Value::Array(_)) => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> BTreeMap<String, SavedQuery> {
+        let query = SavedQuery {
+            description: None,
+            args: ["*", "--ext", "{ext}", "--older", "{age}"]
+                .map(str::to_owned)
+                .to_vec(),
+            defaults: BTreeMap::from([("age".to_owned(), "90d".to_owned())]),
+        };
+        BTreeMap::from([("stale".to_owned(), query)])
+    }
+
+    #[test]
+    fn saved_query_prompt_requires_placeholders_without_defaults() {
+        let prompts = query_prompts(&library());
+        let prompt = prompts.first().expect("one prompt");
+        assert_eq!(prompt.name, "@stale");
+        let required: Vec<(&str, Option<bool>)> = prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|arg| (arg.name.as_str(), arg.required))
+            .collect();
+        assert_eq!(required, [("age", Some(false)), ("ext", Some(true))]);
+    }
+
+    #[test]
+    fn saved_query_prompt_expands_arguments() {
+        let lib = library();
+        let query = lib.get("stale").expect("fixture");
+        let mut args = serde_json::Map::new();
+        query_prompt_messages("stale", query, &args).expect_err("ext is required");
+
+        args.insert("ext".to_owned(), Value::from("vmdk"));
+        let messages = query_prompt_messages("stale", query, &args).expect("expands");
+        let text = serde_json::to_string(&messages).expect("serialise");
+        assert!(text.contains("uffs * --ext vmdk --older 90d"), "{text}");
+    }
+}
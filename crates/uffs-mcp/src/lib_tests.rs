@@ -64,7 +64,7 @@ mod text_tests {
 
     fn test_row(name: &str, size: u64, modified: i64, path: &str) -> SearchRow {
         SearchRow {
-            drive: uffs_mft::platform::DriveLetter::C,
+            drive: uffs_mft::platform::DriveLetter::C.into(),
             name: name.to_owned(),
             size,
             is_directory: false,
@@ -81,6 +81,8 @@ mod text_tests {
             malformed_path: false,
             name_hex: None,
             file_reference: 0,
+            content_hash: None,
+            matched_pattern: None,
         }
     }
 
@@ -258,9 +260,9 @@ mod tool_def_tests {
     use crate::handler::definitions::tool_definitions;
 
     #[test]
-    fn six_tools_defined() {
+    fn seven_tools_defined() {
         let tools = tool_definitions();
-        assert_eq!(tools.len(), 6, "expected 6 tools, got {}", tools.len());
+        assert_eq!(tools.len(), 7, "expected 7 tools, got {}", tools.len());
     }
 
     #[test]
@@ -286,6 +288,7 @@ mod tool_def_tests {
             "uffs_status",
             "uffs_aggregate",
             "uffs_facet_values",
+            "uffs_baselines",
         ] {
             assert!(names.contains(expected), "missing tool: {expected}");
         }
@@ -1,4 +1,4 @@
-use uffs_client::protocol::{AggregateResultWire, BucketWire, StatsWire};
+use uffs_client::protocol::{AggregateResultWire, BucketWire, PercentileWire, StatsWire};
 use uffs_mcp::text::format_aggregate_summary;
 
 #[test]
@@ -15,6 +15,7 @@ fn summary_count_result() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(summary.contains("total_files: 42000"), "got: {summary}");
@@ -35,6 +36,16 @@ fn summary_stats_result() {
             avg: 5000.0,
             waste_bytes: 100_000,
             waste_pct: 2.0,
+            percentiles: vec![
+                PercentileWire {
+                    rank: "p50".to_owned(),
+                    value: 4096,
+                },
+                PercentileWire {
+                    rank: "p99".to_owned(),
+                    value: 900_000,
+                },
+            ],
         }),
         buckets: vec![],
         other_count: None,
@@ -42,12 +53,14 @@ fn summary_stats_result() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(summary.contains("count=1000"), "got: {summary}");
     assert!(summary.contains("sum=5000000"), "got: {summary}");
     assert!(summary.contains("avg=5000.0"), "got: {summary}");
     assert!(summary.contains("waste: 100000 bytes"), "got: {summary}");
+    assert!(summary.contains("p50=4096 p99=900000"), "got: {summary}");
 }
 
 #[test]
@@ -67,10 +80,12 @@ fn summary_buckets_result() {
                 avg_size: None,
                 share_count: None,
                 share_bytes: None,
+                percentiles: Vec::new(),
                 sample_rows: Vec::new(),
                 drilldown: Vec::new(),
                 sub_buckets: Vec::new(),
                 verified: false,
+                growth: None,
             },
             BucketWire {
                 key: "toml".to_owned(),
@@ -80,10 +95,12 @@ fn summary_buckets_result() {
                 avg_size: None,
                 share_count: None,
                 share_bytes: None,
+                percentiles: Vec::new(),
                 sample_rows: Vec::new(),
                 drilldown: Vec::new(),
                 sub_buckets: Vec::new(),
                 verified: false,
+                growth: None,
             },
         ],
         other_count: Some(300),
@@ -91,6 +108,7 @@ fn summary_buckets_result() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(summary.contains("ext_terms (2 buckets)"), "got: {summary}");
@@ -113,6 +131,7 @@ fn summary_missing_result() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(
@@ -135,6 +154,7 @@ fn summary_distinct_result() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(summary.contains("4500 distinct values"), "got: {summary}");
@@ -161,6 +181,7 @@ fn summary_mixed_results() {
             next_cursor: None,
             exact: None,
             values_complete: None,
+            pivot: None,
         },
         AggregateResultWire {
             label: Some("by_type".to_owned()),
@@ -183,6 +204,7 @@ fn summary_mixed_results() {
             next_cursor: None,
             exact: None,
             values_complete: None,
+            pivot: None,
         },
     ];
     let summary = format_aggregate_summary(&results);
@@ -213,6 +235,7 @@ fn summary_buckets_truncated_at_10() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(summary.contains("ext_0"), "first bucket present");
@@ -296,6 +319,7 @@ fn summary_shows_next_cursor_when_present() {
         next_cursor: Some("0:1:1".to_owned()),
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(
@@ -325,6 +349,7 @@ fn summary_omits_cursor_when_none() {
         next_cursor: None,
         exact: None,
         values_complete: None,
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(
@@ -356,6 +381,12 @@ fn summary_renders_sub_buckets() {
                     key: "image".to_owned(),
                     count: 400,
                     total_bytes: 2_000_000,
+                    sub_buckets: vec![BucketWire {
+                        key: "jpg".to_owned(),
+                        count: 300,
+                        total_bytes: 1_500_000,
+                        ..BucketWire::default()
+                    }],
                     ..BucketWire::default()
                 },
             ],
@@ -366,6 +397,7 @@ fn summary_renders_sub_buckets() {
         next_cursor: None,
         exact: Some(true),
         values_complete: Some(true),
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(
@@ -380,6 +412,10 @@ fn summary_renders_sub_buckets() {
         summary.contains("├─"),
         "sub-buckets should be indented with ├─: {summary}"
     );
+    assert!(
+        summary.contains("        ├─ jpg"),
+        "second-level sub-buckets indent one step further: {summary}"
+    );
 }
 
 #[test]
@@ -396,6 +432,7 @@ fn summary_shows_values_complete_false() {
         next_cursor: None,
         exact: Some(true),
         values_complete: Some(false),
+        pivot: None,
     }];
     let summary = format_aggregate_summary(&results);
     assert!(
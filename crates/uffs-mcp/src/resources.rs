@@ -166,6 +166,10 @@ pub(crate) fn aggregate_presets_json() -> String {
             description: "Cleanup candidates: zero-byte files, temp files, no-extension files, \
                           cache directories",
         },
+        PresetEntry {
+            name: "name_years",
+            description: "Files grouped by a 19xx/20xx year in their name, plus the unmatched rest",
+        },
     ];
 
     serde_json::to_string_pretty(&presets).unwrap_or_else(|_| "[]".to_owned())
@@ -206,6 +206,7 @@ pub(crate) fn apply_roots_scope(state: &RootsState, params: &mut SearchParams) {
             .iter()
             .filter_map(|drv| drv.chars().next())
             .filter_map(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
+            .map(uffs_mft::platform::VolumeId::local)
             .collect();
     }
 
@@ -440,7 +441,7 @@ mod tests {
         update_roots_state(&mut state, &roots);
 
         let mut params = SearchParams {
-            drives: vec![uffs_mft::platform::DriveLetter::D], // User explicitly set drives.
+            drives: vec![uffs_mft::platform::DriveLetter::D.into()], // User explicitly set drives.
             ..Default::default()
         };
         apply_roots_scope(&state, &mut params);
@@ -55,6 +55,10 @@ pub(crate) struct SearchRowOutput {
     /// (no `schemars` dep).
     #[schemars(with = "char")]
     pub drive: uffs_mft::platform::DriveLetter,
+    /// Host label for rows from another machine's capture; omitted for
+    /// local volumes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
     /// Filename.
     pub name: String,
     /// File extension (lowercase, without leading dot). Empty for directories
@@ -99,6 +103,34 @@ pub(crate) struct InfoOutput {
     pub record: Option<serde_json::Value>,
 }
 
+// ── uffs_baselines ──────────────────────────────────────────────────
+
+/// Structured output for `uffs_baselines`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct BaselinesOutput {
+    /// `[snapshots]` is enabled in the daemon config.
+    pub snapshots_enabled: bool,
+    /// `[history]` is enabled in the daemon config.
+    pub history_enabled: bool,
+    /// Per-drive baselines.
+    pub drives: Vec<DriveBaselinesOutput>,
+}
+
+/// The baselines retained for one drive (structured).
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct DriveBaselinesOutput {
+    /// Drive letter, serialized as a single ASCII char (see
+    /// [`DriveOutput::letter`]).
+    #[schemars(with = "char")]
+    pub letter: uffs_mft::platform::DriveLetter,
+    /// Scheduled snapshot names (local days, oldest first) — pass one as
+    /// `diff_baseline: "@<name>"`.
+    pub snapshots: Vec<String>,
+    /// Oldest moment the index history can rebuild (Unix ms); `null`
+    /// when there is none.
+    pub history_since_ms: Option<u64>,
+}
+
 // ── uffs_drives ─────────────────────────────────────────────────────
 
 /// Structured output for `uffs_drives`.
@@ -44,6 +44,8 @@ pub struct McpStats {
     pub tool_aggregate: AtomicU64,
     /// `uffs_facet_values` call count.
     pub tool_facet_values: AtomicU64,
+    /// `uffs_baselines` call count.
+    pub tool_baselines: AtomicU64,
 }
 
 impl McpStats {
@@ -121,6 +123,7 @@ impl McpStats {
                 "info": self.tool_info.load(Ordering::Relaxed),
                 "aggregate": self.tool_aggregate.load(Ordering::Relaxed),
                 "facet_values": self.tool_facet_values.load(Ordering::Relaxed),
+                "baselines": self.tool_baselines.load(Ordering::Relaxed),
             }
         })
     }
@@ -134,6 +137,7 @@ impl McpStats {
             "uffs_info" => self.tool_info.fetch_add(1, Ordering::Relaxed),
             "uffs_aggregate" => self.tool_aggregate.fetch_add(1, Ordering::Relaxed),
             "uffs_facet_values" => self.tool_facet_values.fetch_add(1, Ordering::Relaxed),
+            "uffs_baselines" => self.tool_baselines.fetch_add(1, Ordering::Relaxed),
             _ => 0, // Unknown tool — don't crash, just skip.
         };
     }
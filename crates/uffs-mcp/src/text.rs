@@ -48,6 +48,9 @@ pub fn format_aggregate_summary(results: &[uffs_client::protocol::AggregateResul
                         "• {label}: count={} sum={} min={} max={} avg={:.1}",
                         stats.count, stats.sum, stats.min, stats.max, stats.avg
                     );
+                    if !stats.percentiles.is_empty() {
+                        _ = writeln!(out, "  {}", format_percentiles(&stats.percentiles));
+                    }
                     if stats.waste_bytes > 0 {
                         _ = writeln!(
                             out,
@@ -60,6 +63,8 @@ pub fn format_aggregate_summary(results: &[uffs_client::protocol::AggregateResul
             "buckets" | "terms" | "rollup" | "duplicates" => {
                 format_bucket_summary(&mut out, label, result);
             }
+            "growth" => format_growth_summary(&mut out, label, result),
+            "pivot" => format_pivot_summary(&mut out, label, result),
             _ => {
                 _ = writeln!(
                     out,
@@ -78,6 +83,108 @@ pub fn format_aggregate_summary(results: &[uffs_client::protocol::AggregateResul
     out
 }
 
+/// Format folder growth against a diff baseline into `out`.
+fn format_growth_summary(
+    out: &mut String,
+    label: &str,
+    result: &uffs_client::protocol::AggregateResultWire,
+) {
+    let total = result.total_groups.unwrap_or(result.buckets.len());
+    _ = writeln!(out, "• {label} ({total} folders compared):");
+    for bucket in result.buckets.iter().take(10) {
+        let Some(growth) = &bucket.growth else {
+            continue;
+        };
+        let pct = growth
+            .growth_pct
+            .map_or_else(|| "new".to_owned(), |pct| format!("{pct:+.1}%"));
+        _ = writeln!(
+            out,
+            "    {:<30} {} → {} bytes ({:+}, {pct}, {})",
+            bucket.key, growth.before_bytes, bucket.total_bytes, growth.delta_bytes, growth.matched
+        );
+    }
+    if result.buckets.len() > 10 {
+        _ = writeln!(out, "    ... and {} more", result.buckets.len() - 10);
+    }
+}
+
+/// Format a pivot into `out`: one line per row (max 10) listing its
+/// non-empty cells, then the column totals.
+fn format_pivot_summary(
+    out: &mut String,
+    label: &str,
+    result: &uffs_client::protocol::AggregateResultWire,
+) {
+    let Some(pivot) = &result.pivot else {
+        return;
+    };
+    let zone = pivot
+        .time_zone
+        .as_deref()
+        .map_or_else(String::new, |zone| format!(", {zone}"));
+    _ = writeln!(
+        out,
+        "• {label} ({} × {}, {}{zone}):",
+        pivot.row_field, pivot.col_field, pivot.metric
+    );
+    let precision = usize::from(matches!(
+        pivot.metric.as_str(),
+        "waste_pct" | "avg_size" | "share_count" | "share_bytes"
+    ));
+    let cells = |values: &[Option<f64>], total: f64| {
+        let mut line = String::new();
+        for (column, value) in pivot.columns.iter().zip(values) {
+            if let Some(val) = value {
+                _ = write!(line, "{column}={val:.precision$} ");
+            }
+        }
+        _ = write!(line, "total={total:.precision$}");
+        line
+    };
+    for row in pivot.rows.iter().take(10) {
+        _ = writeln!(out, "    {:<24} {}", row.key, cells(&row.values, row.total));
+    }
+    if pivot.rows.len() > 10 {
+        _ = writeln!(out, "    ... and {} more rows", pivot.rows.len() - 10);
+    }
+    let totals: Vec<Option<f64>> = pivot.column_totals.iter().copied().map(Some).collect();
+    _ = writeln!(
+        out,
+        "    {:<24} {}",
+        "(all rows)",
+        cells(&totals, pivot.grand_total)
+    );
+}
+
+/// Format estimated percentiles as `p50=4096 p99=1048576`.
+fn format_percentiles(percentiles: &[uffs_client::protocol::PercentileWire]) -> String {
+    percentiles
+        .iter()
+        .map(|pct| format!("{}={}", pct.rank, pct.value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Format nested sub-aggregation buckets (max 5 per level), indented
+/// two more columns per level.
+fn format_sub_buckets(out: &mut String, subs: &[uffs_client::protocol::BucketWire], depth: usize) {
+    let indent = "  ".repeat(depth);
+    let width = 28_usize.saturating_sub(2 * depth);
+    for sub in subs.iter().take(5) {
+        _ = writeln!(
+            out,
+            "    {indent}├─ {:<width$} count={:<8} bytes={}",
+            sub.key, sub.count, sub.total_bytes
+        );
+        format_sub_buckets(out, &sub.sub_buckets, depth + 1);
+    }
+    let sub_rest = subs.len().saturating_sub(5);
+    if sub_rest > 0 {
+        _ = writeln!(out, "    {indent}... and {sub_rest} more sub-buckets");
+    }
+}
+
 /// Format bucket-style results (terms, rollup, duplicates) into `out`.
 fn format_bucket_summary(
     out: &mut String,
@@ -91,6 +198,9 @@ fn format_bucket_summary(
             "    {:<30} count={:<8} bytes={}",
             bucket.key, bucket.count, bucket.total_bytes
         );
+        if !bucket.percentiles.is_empty() {
+            _ = writeln!(out, "      {}", format_percentiles(&bucket.percentiles));
+        }
         // Sample rows (top-hits), max 3 per bucket.
         let max_samples = 3;
         for sr in bucket.sample_rows.iter().take(max_samples) {
@@ -106,18 +216,7 @@ fn format_bucket_summary(
         if remaining > 0 {
             _ = writeln!(out, "      ... and {remaining} more");
         }
-        // Nested sub-aggregation buckets.
-        for sub in bucket.sub_buckets.iter().take(5) {
-            _ = writeln!(
-                out,
-                "      ├─ {:<26} count={:<8} bytes={}",
-                sub.key, sub.count, sub.total_bytes
-            );
-        }
-        let sub_rest = bucket.sub_buckets.len().saturating_sub(5);
-        if sub_rest > 0 {
-            _ = writeln!(out, "      ... and {sub_rest} more sub-buckets");
-        }
+        format_sub_buckets(out, &bucket.sub_buckets, 1);
     }
     if result.buckets.len() > 10 {
         _ = writeln!(out, "    ... and {} more", result.buckets.len() - 10);
@@ -30,7 +30,7 @@ pub(crate) struct AggregateArgs {
     pub pattern: String,
     /// Named preset (`overview`, `by_type`, `by_extension`, `by_drive`,
     /// `by_size`, `by_age`, `storage`, `activity`, `top_folders`,
-    /// `duplicates`, `media`, `cleanup`).
+    /// `duplicates`, `media`, `cleanup`, `name_years`).
     #[serde(default)]
     pub preset: Option<String>,
     /// Custom aggregate specs in power syntax (e.g. `terms:extension,top=50`).
@@ -54,6 +54,21 @@ pub(crate) struct AggregateArgs {
     /// responses with a `next_cursor` in the output.
     #[serde(default)]
     pub page_size: Option<u16>,
+    /// Diff baseline for `growth` specs, which compare folder sizes
+    /// against it — `"@<date>"` / `"@yesterday"` for a daemon-side
+    /// baseline (see `uffs_baselines`) or a capture-file path.  Needs
+    /// exactly one drive.
+    #[serde(default)]
+    pub diff_baseline: Option<String>,
+    /// Offset from UTC that date, hour-of-day and weekday buckets are
+    /// cut in (`"UTC"`, `"+2"`, `"-05:30"`); default UTC.  A spec's own
+    /// `tz=` wins.
+    #[serde(default)]
+    pub tz: Option<String>,
+    /// Computed fields (`avgsize=treesize/descendants`) that `stats:`,
+    /// `hist:` and `range:` specs may measure.
+    #[serde(default)]
+    pub fields: Vec<String>,
 }
 
 /// Default pattern.
@@ -61,27 +76,22 @@ fn default_pattern() -> String {
     "*".to_owned()
 }
 
-/// Parse the tool's drive-name strings (`"C"`, `"C:"`, …) into typed
-/// letters, silently dropping anything unparseable — a bad drive arg
-/// scopes to nothing rather than failing the whole aggregation.
-fn parse_drive_letters(drives: &[String]) -> Vec<uffs_mft::platform::DriveLetter> {
+/// Parse the tool's drive-name strings (`"C"`, `"C:"`, `"C:\\"`,
+/// `"srv01\\C"`, …) into typed volume selectors, silently dropping
+/// anything unparseable — a bad drive arg scopes to nothing rather than
+/// failing the whole aggregation.
+pub(crate) fn parse_drive_selectors(drives: &[String]) -> Vec<uffs_mft::platform::VolumeId> {
     drives
         .iter()
-        .filter_map(|drv| drv.chars().next())
-        .filter_map(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
+        .filter_map(|drv| {
+            uffs_mft::platform::VolumeId::parse(drv.trim_end_matches(['\\', '/'])).ok()
+        })
         .collect()
 }
 
-/// Execute the aggregate tool.
-///
-/// # Errors
-///
-/// Returns [`BridgeError`] if the daemon call fails.
-pub(crate) async fn run(
-    client: &mut UffsClient,
-    args: AggregateArgs,
-    roots_state: &RootsState,
-) -> Result<CallToolResult, BridgeError> {
+/// The wire specs for `args`: its preset, then its raw specs, or the
+/// `overview` preset when it names neither.
+fn aggregate_specs(args: &AggregateArgs) -> Vec<AggregateSpecWire> {
     let mut agg_specs: Vec<AggregateSpecWire> = Vec::new();
 
     // Handle preset parameter.
@@ -111,7 +121,26 @@ pub(crate) async fn run(
         });
     }
 
-    let drives = parse_drive_letters(&args.drives);
+    if args.tz.is_some() {
+        for spec in &mut agg_specs {
+            spec.tz.clone_from(&args.tz);
+        }
+    }
+    agg_specs
+}
+
+/// Execute the aggregate tool.
+///
+/// # Errors
+///
+/// Returns [`BridgeError`] if the daemon call fails.
+pub(crate) async fn run(
+    client: &mut UffsClient,
+    args: AggregateArgs,
+    roots_state: &RootsState,
+) -> Result<CallToolResult, BridgeError> {
+    let agg_specs = aggregate_specs(&args);
+    let drives = parse_drive_selectors(&args.drives);
 
     let mut params = SearchParams {
         pattern: args.pattern,
@@ -128,6 +157,8 @@ pub(crate) async fn run(
         type_filter: args.type_filter,
         agg_cursor: args.cursor,
         agg_page_size: args.page_size,
+        diff_baseline: args.diff_baseline,
+        computed_fields: args.fields,
         ..Default::default()
     };
 
@@ -235,6 +266,10 @@ fn default_agg_spec() -> AggregateSpecWire {
         sample_desc: None,
         verify: None,
         verify_bytes: None,
+        depth: None,
+        rank: None,
         drive: None,
+        tz: None,
+        sub: None,
     }
 }
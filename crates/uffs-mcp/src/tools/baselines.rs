@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `uffs_baselines` tool — list the daemon-side diff baselines.
+
+use core::fmt::Write as _;
+
+use rmcp::model::{CallToolResult, ContentBlock};
+use uffs_client::connect::UffsClient;
+
+use crate::error::BridgeError;
+use crate::schemas::{BaselinesOutput, DriveBaselinesOutput};
+
+/// Execute the baselines tool (no arguments).
+///
+/// # Errors
+///
+/// Returns [`BridgeError`] if the daemon call fails.
+pub(crate) async fn run(client: &mut UffsClient) -> Result<CallToolResult, BridgeError> {
+    let response = client
+        .list_baselines()
+        .await
+        .map_err(|err| BridgeError::Daemon(format!("Failed to list baselines: {err}")))?;
+
+    let mut output = String::new();
+    if !response.snapshots_enabled && !response.history_enabled {
+        output.push_str(
+            "No daemon-side baselines: neither [snapshots] nor [history] is enabled in \
+             daemon.toml.\n",
+        );
+    }
+    for drive in &response.drives {
+        _ = writeln!(output, "{}:", drive.drive);
+        let names: Vec<&str> = drive
+            .snapshots
+            .iter()
+            .map(|snap| snap.name.as_str())
+            .collect();
+        if names.is_empty() {
+            output.push_str("  snapshots: none\n");
+        } else {
+            _ = writeln!(output, "  snapshots: {}", names.join(", "));
+        }
+        if let Some(since) = drive.history_since_ms {
+            _ = writeln!(output, "  history: any moment since {since} (Unix ms)");
+        }
+    }
+    output.push_str(
+        "\nDiff against one with uffs_search diff_baseline='@<name or time>' and drives=['<letter>'].",
+    );
+
+    let structured = BaselinesOutput {
+        snapshots_enabled: response.snapshots_enabled,
+        history_enabled: response.history_enabled,
+        drives: response
+            .drives
+            .into_iter()
+            .map(|drive| DriveBaselinesOutput {
+                letter: drive.drive,
+                snapshots: drive.snapshots.into_iter().map(|snap| snap.name).collect(),
+                history_since_ms: drive.history_since_ms,
+            })
+            .collect(),
+    };
+
+    let mut result = CallToolResult::success(vec![ContentBlock::text(output)]);
+    result.structured_content = Some(serde_json::to_value(structured)?);
+    Ok(result)
+}
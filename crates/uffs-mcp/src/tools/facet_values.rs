@@ -85,7 +85,11 @@ pub(crate) async fn run(
         sample_desc: None,
         verify: None,
         verify_bytes: None,
+        depth: None,
+        rank: None,
         drive: None,
+        tz: None,
+        sub: None,
     };
 
     let mut params = SearchParams {
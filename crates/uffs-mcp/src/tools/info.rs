@@ -32,11 +32,12 @@ pub(crate) async fn run(
 
     // Cold-index contract, scoped to the path's own drive: an info
     // lookup on a parked drive must not block for the re-warm either.
-    let scope: Vec<uffs_mft::platform::DriveLetter> = args
+    let scope: Vec<uffs_mft::platform::VolumeId> = args
         .path
         .chars()
         .next()
         .and_then(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
+        .map(uffs_mft::platform::VolumeId::local)
         .into_iter()
         .collect();
     super::warm::warm_gate(client, &scope).await?;
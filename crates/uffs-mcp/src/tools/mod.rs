@@ -10,6 +10,8 @@
 // Phase 3: parent `tools` is `pub(crate)`; siblings stay private to it.
 /// `uffs_aggregate` — server-side aggregation summaries.
 pub(crate) mod aggregate;
+/// `uffs_baselines` — list daemon-side diff baselines.
+pub(crate) mod baselines;
 /// `uffs_drives` — list indexed NTFS drives.
 pub(crate) mod drives;
 /// `uffs_facet_values` — search within facet values for a field.
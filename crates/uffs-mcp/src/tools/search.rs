@@ -9,7 +9,7 @@ use rmcp::model::{CallToolResult, ContentBlock, Resource};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use uffs_client::connect::UffsClient;
-use uffs_client::protocol::SearchParams;
+use uffs_client::protocol::{HashListMode, SearchParams};
 
 use crate::error::BridgeError;
 use crate::roots::{self, RootsState};
@@ -35,6 +35,28 @@ const MAX_RESOURCE_LINKS: usize = 20;
 /// suggesting the agent use `uffs_aggregate` instead of paging.
 const AGGREGATE_HINT_FACTOR: u64 = 5;
 
+/// How `uffs_search` interprets `pattern`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MatchMode {
+    /// Glob / substring / `>`-regex — the exact modes.
+    #[default]
+    Pattern,
+    /// Typo-tolerant, similarity-ranked filename match.
+    Fuzzy,
+}
+
+/// How `uffs_search` applies a `hash_in` list.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HashMode {
+    /// Keep only files whose content is on the list (IOC hunt).
+    #[default]
+    Match,
+    /// Drop files whose content is on the list (NSRL known-good).
+    KnownGood,
+}
+
 /// Input parameters for the `uffs_search` tool.
 ///
 /// Exposes the full `SearchParams` surface so agents can use any combination
@@ -57,6 +79,14 @@ pub(crate) struct SearchArgs {
     /// Match pattern against the full path instead of the file name.
     #[serde(default)]
     pub match_path: bool,
+    /// `pattern` (default) matches exactly; `fuzzy` tolerates typos and
+    /// returns names ranked by similarity, best first (ignores `sort`).
+    #[serde(default)]
+    pub match_mode: MatchMode,
+    /// Minimum fuzzy similarity in `(0, 1]` (default 0.6).  Lower finds
+    /// more distant spellings; only used with `match_mode: fuzzy`.
+    #[serde(default)]
+    pub fuzzy_threshold: Option<f32>,
 
     // ── Sorting ───────────────────────────────────────────────────
     /// Sort field(s): `name`, `size`, `modified`, `created`, `path`,
@@ -70,6 +100,16 @@ pub(crate) struct SearchArgs {
     /// used as secondary sort columns.
     #[serde(default)]
     pub sort_desc: bool,
+    /// Computed fields for this query, `name=expression` over numeric
+    /// fields (`avgsize=treesize/descendants`, `age=age_days(modified)`);
+    /// `sort` may name them.
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Order hits by relevance instead of `sort`: exact name before prefix
+    /// before substring, shallow before deep, recent before old, system
+    /// files and noise directories (`WinSxS`, …) last.  Ignored for `*`.
+    #[serde(default)]
+    pub rank: bool,
 
     // ── Paging ────────────────────────────────────────────────────
     /// Maximum results to return per page (default: 50, hard cap: 100).
@@ -83,7 +123,8 @@ pub(crate) struct SearchArgs {
     /// Filter: `all`, `files`, `dirs`.
     #[serde(default = "default_filter")]
     pub filter: String,
-    /// Limit to specific drive letters (e.g. `["C", "D"]`).
+    /// Limit to specific drive letters (e.g. `["C", "D"]`), optionally
+    /// host-qualified (`"srv01\\C"`).
     #[serde(default)]
     pub drives: Vec<String>,
     /// Hide system/metafiles (names starting with `$`).
@@ -139,6 +180,27 @@ pub(crate) struct SearchArgs {
     /// Accessed before.
     #[serde(default)]
     pub older_accessed: Option<String>,
+    /// Search the index as it stood at this moment (`"yesterday"`,
+    /// `"2026-10-01"`, Unix ms).  Needs `[history]` enabled in the
+    /// daemon config.
+    #[serde(default)]
+    pub as_of: Option<String>,
+    /// Snapshot diff: return only the files deleted since this baseline —
+    /// `"@<date>"` / `"@yesterday"` for a daemon-side baseline (see
+    /// `uffs_baselines`) or a capture-file path.  Needs exactly one drive.
+    #[serde(default)]
+    pub diff_baseline: Option<String>,
+    /// File names / globs (`*dump*`, `>regex`) matched as one set, e.g. an
+    /// IOC list; each row's `matched_pattern` names the entry that hit.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Daemon-side path of a SHA-256 / SHA-1 / MD5 list (one per line, or
+    /// NSRL-style CSV): only files whose content is listed match.
+    #[serde(default)]
+    pub hash_in: Option<String>,
+    /// `"known_good"` inverts `hash_in`: listed files are excluded.
+    #[serde(default)]
+    pub hash_mode: HashMode,
 
     // ── Attribute filters ─────────────────────────────────────────
     /// NTFS attribute filter (e.g. `"hidden"`, `"system,!hidden"`,
@@ -196,7 +258,7 @@ pub(crate) struct SearchArgs {
     /// Columns for the human-readable text table.
     /// Choose from: `name`, `ext`, `type`, `size`, `modified`, `path`, `drive`,
     /// `allocated`, `created`, `accessed`, `flags`, `descendants`, `treesize`,
-    /// `tree_allocated`.
+    /// `tree_allocated`, `matched_pattern`.
     /// Default: `name,ext,type,size,modified,path`.
     /// `structuredContent` always includes all fields regardless.
     #[serde(default)]
@@ -239,17 +301,16 @@ fn extract_ext(name: &str) -> String {
         .map_or_else(String::new, str::to_ascii_lowercase)
 }
 
-/// Decode a cursor string into an offset.  Returns 0 for invalid cursors.
-fn decode_cursor(cursor: &str) -> u32 {
-    cursor
-        .strip_prefix("offset:")
-        .and_then(|raw| raw.parse().ok())
-        .unwrap_or(0)
+/// Split an MCP cursor into the page's starting offset (for the
+/// "Showing N-M" line) and the daemon's keyset `search_cursor`.
+fn decode_cursor(cursor: &str) -> Option<(u32, &str)> {
+    let (offset, token) = cursor.strip_prefix('p')?.split_once(':')?;
+    Some((offset.parse().ok()?, token))
 }
 
-/// Encode an offset into an opaque cursor string.
-fn encode_cursor(offset: u32) -> String {
-    format!("offset:{offset}")
+/// Wrap the daemon's keyset `search_cursor` with the next page's offset.
+fn encode_cursor(offset: u32, token: &str) -> String {
+    format!("p{offset}:{token}")
 }
 
 /// Execute the search tool.
@@ -286,31 +347,33 @@ pub(crate) async fn run(
         ));
     }
 
-    // Resolve cursor offset.
-    let offset = args.cursor.as_deref().map_or(0, decode_cursor);
-
-    // Convert explicit drive strings to typed `DriveLetter` values.
-    // Malformed entries (non-ASCII, multi-char) are silently dropped
-    // — the prior `char` path took only the first character and
-    // forwarded it as-is, so this is a stricter version of the same
-    // observable behaviour.
-    let explicit_drives: Vec<uffs_mft::platform::DriveLetter> = args
-        .drives
-        .iter()
-        .filter_map(|drv| drv.chars().next())
-        .filter_map(|ch| uffs_mft::platform::DriveLetter::parse(ch).ok())
-        .collect();
-
-    // Ask the daemon for offset + limit rows so we can skip the first `offset`.
-    let daemon_limit = offset.saturating_add(effective_limit);
+    // Resolve the cursor; an unrecognised one restarts from the first page.
+    let resume = args.cursor.as_deref().and_then(|cursor| {
+        let decoded = decode_cursor(cursor);
+        if decoded.is_none() {
+            warnings.push("Unrecognised cursor; showing the first page.".to_owned());
+        }
+        decoded
+    });
+    let offset = resume.map_or(0, |(start, _)| start);
+
+    // Convert explicit drive strings to typed `VolumeId` values.
+    // Malformed entries are silently dropped — see
+    // `aggregate::parse_drive_selectors`.
+    let explicit_drives = super::aggregate::parse_drive_selectors(&args.drives);
+
     let mut search_params = SearchParams {
         pattern: args.pattern,
         case_sensitive: args.case_sensitive,
         whole_word: args.whole_word,
         match_path: args.match_path,
+        fuzzy: args.match_mode == MatchMode::Fuzzy,
+        fuzzy_threshold: args.fuzzy_threshold,
         sort: Some(args.sort),
         sort_desc: args.sort_desc,
-        limit: Some(daemon_limit),
+        rank: args.rank,
+        limit: Some(effective_limit),
+        search_cursor: resume.map(|(_, token)| token.to_owned()),
         filter: Some(args.filter),
         drives: explicit_drives,
         // Extension / exclude / path.
@@ -331,6 +394,15 @@ pub(crate) async fn run(
         older_created: args.older_created,
         newer_accessed: args.newer_accessed,
         older_accessed: args.older_accessed,
+        as_of: args.as_of,
+        diff_baseline: args.diff_baseline,
+        hash_mode: args.hash_in.is_some().then_some(match args.hash_mode {
+            HashMode::Match => HashListMode::Match,
+            HashMode::KnownGood => HashListMode::KnownGood,
+        }),
+        hash_in: args.hash_in,
+        patterns: args.patterns,
+        computed_fields: args.fields,
         // Attributes.
         attr: args.attr,
         // Type category.
@@ -404,18 +476,23 @@ pub(crate) async fn run(
         )
     })?;
 
-    // Slice to the current page (skip `offset` rows).
-    let page_rows: Vec<_> = rows
-        .into_iter()
-        .skip(offset as usize) // u32→usize lossless on 64-bit
-        .take(effective_limit as usize) // u32→usize lossless on 64-bit
-        .collect();
+    // The daemon already cut the page after the keyset cursor.
+    let page_rows = rows;
     // page_rows.len() is bounded by HARD_CAP (100) which fits in u32.
     let page_len = u32::try_from(page_rows.len()).unwrap_or(u32::MAX);
     let end_offset = offset.saturating_add(page_len);
-    let has_more = u64::from(end_offset) < total_count;
-
-    let next_cursor = has_more.then(|| encode_cursor(end_offset));
+    let next_cursor = response
+        .next_search_cursor
+        .as_deref()
+        .map(|token| encode_cursor(end_offset, token));
+    let has_more = next_cursor.is_some();
+    if response.cursor_index_changed {
+        warnings.push(
+            "The index changed since the previous page; this page resumes after the last \
+             row you saw, but files added or removed meanwhile may shift the boundary."
+                .to_owned(),
+        );
+    }
 
     let output = format_text_output(&FormatContext {
         page_rows: &page_rows,
@@ -461,7 +538,8 @@ pub(crate) async fn run(
                 let ext = extract_ext(&row.name);
                 let r#type = if row.is_directory { "dir" } else { "file" }.to_owned();
                 SearchRowOutput {
-                    drive: row.drive,
+                    drive: row.drive.letter(),
+                    host: row.drive.host().map(|host| host.as_str().to_owned()),
                     name: row.name.clone(),
                     ext,
                     r#type,
@@ -616,6 +694,7 @@ fn col_header(col: &str) -> &'static str {
         "descendants" => "Descendants",
         "treesize" => "Tree Size",
         "tree_allocated" => "Tree Allocated",
+        "matched_pattern" => "Matched Pattern",
         _ => "?",
     }
 }
@@ -643,6 +722,7 @@ fn col_value(col: &str, row: &uffs_client::protocol::response::SearchRow) -> Str
         "descendants" => row.descendants.to_string(),
         "treesize" => uffs_client::protocol::response::format_size(row.treesize),
         "tree_allocated" => uffs_client::protocol::response::format_size(row.tree_allocated),
+        "matched_pattern" => row.matched_pattern.clone().unwrap_or_default(),
         _ => String::new(),
     }
 }
@@ -677,49 +757,5 @@ pub(crate) fn percent_encode_path(path: &str) -> String {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::extract_ext;
-
-    /// Regression: dot-gated extraction so the MCP-emitted `ext` field
-    /// (in both the markdown table and `structuredContent.rows[*].ext`)
-    /// matches the daemon's sort key
-    /// (`uffs_core::search::sorting::build_row_sort_key` →
-    /// `extract_extension_after_dot`).  Pre-fix, naive `rsplit_once('.')`
-    /// produced `ext = "bash_history"` for `.bash_history`, but the sort
-    /// engine placed the row in the empty-extension bucket — the
-    /// resulting disagreement broke Windows MCP T62 `--sort extension`
-    /// (`scripts/tests/definitions/03-sort.toml`) because the validator
-    /// reads `ext` and finds it non-monotonic.
-    #[test]
-    fn extract_ext_returns_empty_for_dotfiles() {
-        assert_eq!(extract_ext(".bash_history"), "");
-        assert_eq!(extract_ext(".gitignore"), "");
-        assert_eq!(extract_ext(".env"), "");
-    }
-
-    #[test]
-    fn extract_ext_returns_empty_for_dotless_names() {
-        assert_eq!(extract_ext("README"), "");
-        assert_eq!(extract_ext("Makefile"), "");
-        assert_eq!(extract_ext(""), "");
-    }
-
-    #[test]
-    fn extract_ext_returns_empty_for_trailing_dot() {
-        assert_eq!(extract_ext("foo."), "");
-        assert_eq!(extract_ext("archive.tar."), "");
-    }
-
-    #[test]
-    fn extract_ext_returns_lowercase_segment_after_last_dot() {
-        assert_eq!(extract_ext("report.txt"), "txt");
-        assert_eq!(extract_ext("archive.tar.gz"), "gz");
-        assert_eq!(extract_ext("$RECYCLE.BIN"), "bin");
-        assert_eq!(
-            extract_ext(
-                "amd64_microsoft-windows-mdmappinstaller_31bf3856ad364e35_10.0.26100.8115_none_3591783d4bfd6e96"
-            ),
-            "8115_none_3591783d4bfd6e96"
-        );
-    }
-}
+#[path = "search_tests.rs"]
+mod tests;
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Unit tests for `tools::search`.
+//!
+//! Extracted from `search.rs` via `#[path]` so the tool file stays under
+//! the workspace 800-LOC ceiling.
+
+use super::extract_ext;
+
+/// Regression: dot-gated extraction so the MCP-emitted `ext` field
+/// (in both the markdown table and `structuredContent.rows[*].ext`)
+/// matches the daemon's sort key
+/// (`uffs_core::search::sorting::build_row_sort_key` →
+/// `extract_extension_after_dot`).  Pre-fix, naive `rsplit_once('.')`
+/// produced `ext = "bash_history"` for `.bash_history`, but the sort
+/// engine placed the row in the empty-extension bucket — the
+/// resulting disagreement broke Windows MCP T62 `--sort extension`
+/// (`scripts/tests/definitions/03-sort.toml`) because the validator
+/// reads `ext` and finds it non-monotonic.
+#[test]
+fn extract_ext_returns_empty_for_dotfiles() {
+    assert_eq!(extract_ext(".bash_history"), "");
+    assert_eq!(extract_ext(".gitignore"), "");
+    assert_eq!(extract_ext(".env"), "");
+}
+
+#[test]
+fn extract_ext_returns_empty_for_dotless_names() {
+    assert_eq!(extract_ext("README"), "");
+    assert_eq!(extract_ext("Makefile"), "");
+    assert_eq!(extract_ext(""), "");
+}
+
+#[test]
+fn extract_ext_returns_empty_for_trailing_dot() {
+    assert_eq!(extract_ext("foo."), "");
+    assert_eq!(extract_ext("archive.tar."), "");
+}
+
+#[test]
+fn extract_ext_returns_lowercase_segment_after_last_dot() {
+    assert_eq!(extract_ext("report.txt"), "txt");
+    assert_eq!(extract_ext("archive.tar.gz"), "gz");
+    assert_eq!(extract_ext("$RECYCLE.BIN"), "bin");
+    assert_eq!(
+        extract_ext(
+            "amd64_microsoft-windows-mdmappinstaller_31bf3856ad364e35_10.0.26100.8115_none_3591783d4bfd6e96"
+        ),
+        "8115_none_3591783d4bfd6e96"
+    );
+}
@@ -60,18 +60,18 @@ const WARM_TRIGGER_PATTERN: &str = "zzqzzqzzq";
 /// construction.
 fn not_ready(
     drives: &[DriveInfo],
-    scope: &[uffs_mft::platform::DriveLetter],
-) -> Vec<uffs_mft::platform::DriveLetter> {
+    scope: &[uffs_mft::platform::VolumeId],
+) -> Vec<uffs_mft::platform::VolumeId> {
     drives
         .iter()
-        .filter(|info| scope.is_empty() || scope.contains(&info.letter))
+        .filter(|info| scope.is_empty() || scope.iter().any(|sel| sel.selects(info.volume())))
         .filter(|info| {
             matches!(
                 info.tier,
                 Some(ShardTier::Parked | ShardTier::Cold | ShardTier::Unknown)
             )
         })
-        .map(|info| info.letter)
+        .map(DriveInfo::volume)
         .collect()
 }
 
@@ -85,7 +85,7 @@ fn not_ready(
 /// (the retry contract) or when the tier probe itself fails.
 pub(crate) async fn warm_gate(
     client: &mut UffsClient,
-    scope: &[uffs_mft::platform::DriveLetter],
+    scope: &[uffs_mft::platform::VolumeId],
 ) -> Result<(), BridgeError> {
     let drives = client
         .drives()
@@ -137,7 +137,7 @@ pub(crate) async fn warm_gate(
 #[cfg(test)]
 mod tests {
     use uffs_client::protocol::response::{DriveInfo, ShardTier};
-    use uffs_mft::platform::DriveLetter;
+    use uffs_mft::platform::{DriveLetter, VolumeId};
 
     use super::not_ready;
 
@@ -145,6 +145,8 @@ mod tests {
     fn info(letter: char, tier: Option<ShardTier>) -> DriveInfo {
         DriveInfo {
             letter: DriveLetter::parse(letter).unwrap_or(DriveLetter::C),
+            host: None,
+            serial: None,
             records: 0,
             source: "test".to_owned(),
             tier,
@@ -163,8 +165,8 @@ mod tests {
         ];
         let cold = not_ready(&drives, &[]);
         assert_eq!(cold, vec![
-            DriveLetter::parse('D').unwrap_or(DriveLetter::C),
-            DriveLetter::parse('S').unwrap_or(DriveLetter::C),
+            VolumeId::local(DriveLetter::parse('D').unwrap_or(DriveLetter::C)),
+            VolumeId::local(DriveLetter::parse('S').unwrap_or(DriveLetter::C)),
         ]);
     }
 
@@ -176,10 +178,10 @@ mod tests {
             info('C', Some(ShardTier::Warm)),
             info('D', Some(ShardTier::Cold)),
         ];
-        let scope = vec![DriveLetter::parse('C').unwrap_or(DriveLetter::C)];
+        let scope = vec![VolumeId::local(DriveLetter::C)];
         assert_eq!(
             not_ready(&drives, &scope),
-            Vec::<DriveLetter>::new(),
+            Vec::<VolumeId>::new(),
             "warm-scoped query must pass while another drive is cold"
         );
     }
@@ -192,9 +194,9 @@ mod tests {
             info('E', Some(ShardTier::Unknown)),
         ];
         let cold = not_ready(&drives, &[]);
-        assert_eq!(cold, vec![
+        assert_eq!(cold, vec![VolumeId::local(
             DriveLetter::parse('E').unwrap_or(DriveLetter::C)
-        ]);
+        )]);
     }
 
     /// A pre-tiering daemon (no tier field) never demotes — treat its
@@ -202,6 +204,6 @@ mod tests {
     #[test]
     fn missing_tier_reads_as_ready() {
         let drives = vec![info('C', None)];
-        assert_eq!(not_ready(&drives, &[]), Vec::<DriveLetter>::new());
+        assert_eq!(not_ready(&drives, &[]), Vec::<VolumeId>::new());
     }
 }
@@ -69,7 +69,7 @@ async fn mcp_tools_list() {
     let client = setup_client().await;
     let tools = client.list_tools(None).await.unwrap();
 
-    assert_eq!(tools.tools.len(), 6, "expected 6 tools");
+    assert_eq!(tools.tools.len(), 7, "expected 7 tools");
 
     let names: Vec<_> = tools.tools.iter().map(|t| t.name.as_ref()).collect();
     assert!(names.contains(&"uffs_search"));
@@ -41,8 +41,8 @@ use rustc_hash as _;
 // `serde` is a library-only dep (DriveLetter Serialize/Deserialize);
 // acknowledge it to keep `unused-crate-dependencies` quiet here too.
 use serde as _;
-// `serde_json` is a Windows-only bin dep (`info`/`drives --format json`).
-#[cfg(windows)]
+// `serde_json` is a library dep (capture-bundle manifest) and the bin's
+// `info`/`drives --format json`.
 use serde_json as _;
 use sha2 as _;
 use smallvec as _;
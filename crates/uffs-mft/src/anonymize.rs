@@ -0,0 +1,581 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Privacy-preserving rewrite of MFT captures for bug reports.
+//!
+//! Replaces every user-visible name in a raw `$MFT` image with a keyed-hash
+//! pseudonym while leaving everything the parser and the search engine key on
+//! untouched: record layout, tree shape (parent references), sizes,
+//! timestamps, flags, namespaces and name lengths. The result is still a valid
+//! capture — it loads through the normal `load` / `--mft-file` paths — so a
+//! performance or parity bug reproduces on another machine with the same
+//! record distribution but none of the original names.
+//!
+//! What is rewritten, per `FILE` record:
+//!
+//! - every `$FILE_NAME` (Win32, DOS and POSIX namespaces; DOS pseudonyms stay
+//!   upper-case);
+//! - the `$FILE_NAME` keys of resident `$INDEX_ROOT` (`$I30`) entries, which
+//!   are copies of the children's names;
+//! - named-attribute names (ADS such as `Zone.Identifier`), both in attribute
+//!   headers and in resident `$ATTRIBUTE_LIST` entries;
+//! - mount-point and symlink reparse targets (substitute and print names);
+//! - extended-attribute names in resident `$EA` values;
+//! - the `$VOLUME_NAME` label.
+//!
+//! Every byte from `bytes_in_use` to the end of the record is zeroed, in-use
+//! or not: that slack keeps the tail of whatever the record held before it
+//! shrank or was reused, names included.
+//!
+//! Pseudonyms are deterministic for a given key, so hard links, index keys and
+//! reparse-target components that named the same file still agree after the
+//! rewrite. ASCII punctuation is kept in place and short extensions (up to
+//! [`MAX_KEPT_EXTENSION`] UTF-16 units) are kept verbatim, so extension
+//! statistics and glob behaviour survive. NTFS metafile names (FRS 0–15 and
+//! `$`-prefixed children of `$Extend`) and `$`-prefixed system stream names
+//! (`$I30`, `$J`, `$SDS`, …) are not sensitive and are left alone.
+//!
+//! Records are fixed up before editing and USA-protected again afterwards, so
+//! a name that straddles a sector end — or slack that covers one — is
+//! handled correctly. A record whose
+//! fixup did not validate (torn write) is still scrubbed, then has its
+//! original mismatching sector ends restored so it stays torn exactly as
+//! captured.
+
+use sha2::{Digest as _, Sha256};
+
+use crate::ntfs::{SECTOR_SIZE, apply_usa_fixup, protect_usa};
+
+/// Extensions longer than this many UTF-16 units are pseudonymized with the
+/// stem instead of kept: a long "extension" is usually part of a name.
+pub const MAX_KEPT_EXTENSION: usize = 10;
+
+/// Highest reserved NTFS metafile FRS (`$MFT` … `$Extend` and the reserved
+/// slots after it).
+const SYSTEM_METAFILE_MAX_FRS: u64 = 15;
+
+/// FRS of `$Extend`, whose `$`-prefixed children (`$UsnJrnl`, `$ObjId`, …) are
+/// metafiles too.
+const EXTEND_FRS: u64 = 11;
+
+/// Mask for the FRS half of an NTFS file reference.
+const FRS_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+/// Attribute type codes the anonymizer edits.
+const ATTR_ATTRIBUTE_LIST: u32 = 0x20;
+/// `$FILE_NAME`.
+const ATTR_FILE_NAME: u32 = 0x30;
+/// `$VOLUME_NAME`.
+const ATTR_VOLUME_NAME: u32 = 0x60;
+/// `$INDEX_ROOT`.
+const ATTR_INDEX_ROOT: u32 = 0x90;
+/// `$REPARSE_POINT`.
+const ATTR_REPARSE_POINT: u32 = 0xC0;
+/// `$EA`.
+const ATTR_EA: u32 = 0xE0;
+/// End-of-attributes marker.
+const ATTR_END: u32 = 0xFFFF_FFFF;
+
+/// `IO_REPARSE_TAG_MOUNT_POINT`.
+const TAG_MOUNT_POINT: u32 = 0xA000_0003;
+/// `IO_REPARSE_TAG_SYMLINK`.
+const TAG_SYMLINK: u32 = 0xA000_000C;
+
+/// `$FILE_NAME` namespace value for DOS 8.3 names.
+const NAMESPACE_DOS: u8 = 2;
+
+/// UTF-16 code units for `.`, `$`, `\` and `:`.
+const DOT: u16 = 0x2E;
+/// `$`.
+const DOLLAR: u16 = 0x24;
+/// `\`.
+const BACKSLASH: u16 = 0x5C;
+/// `:`.
+const COLON: u16 = 0x3A;
+
+/// Pseudonym alphabets (lower-case for long names, upper-case for DOS names).
+const LOWER: &[u8; 36] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+/// Upper-case alphabet for DOS 8.3 names.
+const UPPER: &[u8; 36] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Counters reported after anonymizing a capture.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnonymizeStats {
+    /// `FILE` records visited.
+    pub records: u64,
+    /// Records with at least one rewritten name.
+    pub rewritten: u64,
+    /// `$FILE_NAME` attributes rewritten.
+    pub file_names: u64,
+    /// `$INDEX_ROOT` entry keys rewritten.
+    pub index_entries: u64,
+    /// Attribute (ADS) names rewritten, in headers and attribute lists.
+    pub stream_names: u64,
+    /// Reparse substitute/print names rewritten.
+    pub reparse_targets: u64,
+    /// Volume labels rewritten.
+    pub volume_labels: u64,
+    /// Extended-attribute names rewritten.
+    pub ea_names: u64,
+    /// Records with non-zero slack after `bytes_in_use` (zeroed).
+    pub slack_scrubbed: u64,
+    /// Records whose USA fixup did not validate (kept torn after rewrite).
+    pub torn_records: u64,
+}
+
+/// Keyed-hash pseudonymizer for raw MFT records.
+#[derive(Debug, Clone)]
+pub struct Anonymizer {
+    /// Secret key mixed into every pseudonym.
+    key: Vec<u8>,
+}
+
+/// Little-endian `u16` at `offset`.
+fn read_u16(buf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        buf.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+/// Little-endian `u32` at `offset`.
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        buf.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Little-endian `u64` at `offset`.
+fn read_u64(buf: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        buf.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+/// Decode `len` UTF-16 units starting at `offset`.
+fn read_units(buf: &[u8], offset: usize, len: usize) -> Option<Vec<u16>> {
+    let bytes = buf.get(offset..offset + len * 2)?;
+    Some(
+        bytes
+            .chunks_exact(2)
+            .filter_map(|pair| pair.try_into().ok().map(u16::from_le_bytes))
+            .collect(),
+    )
+}
+
+/// Encode `units` back over the bytes at `offset` (same length as read).
+fn write_units(buf: &mut [u8], offset: usize, units: &[u16]) {
+    if let Some(dest) = buf.get_mut(offset..offset + units.len() * 2) {
+        for (pair, unit) in dest.chunks_exact_mut(2).zip(units) {
+            pair.copy_from_slice(&unit.to_le_bytes());
+        }
+    }
+}
+
+/// Whether a name is a `$`-prefixed system name.
+fn is_system_name(units: &[u16]) -> bool {
+    units.first() == Some(&DOLLAR)
+}
+
+impl Anonymizer {
+    /// Create an anonymizer for `key`. The same key always produces the same
+    /// pseudonyms, so two captures anonymized with one key stay comparable.
+    #[must_use]
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.to_vec() }
+    }
+
+    /// Keyed pseudonym for `name`, with the same UTF-16 length.
+    ///
+    /// ASCII punctuation and a short extension are kept; every other unit
+    /// (letters, digits, non-ASCII, surrogate halves) becomes a character from
+    /// the pseudonym alphabet (`upper` selects the DOS upper-case alphabet).
+    #[must_use]
+    pub fn pseudonym(&self, name: &[u16], upper: bool) -> Vec<u16> {
+        let stem_len = name
+            .iter()
+            .rposition(|&unit| unit == DOT)
+            .filter(|&dot| dot > 0 && name.len() - dot - 1 <= MAX_KEPT_EXTENSION)
+            .unwrap_or(name.len());
+        let alphabet = if upper { UPPER } else { LOWER };
+
+        let mut out = Vec::with_capacity(name.len());
+        let mut stream: Vec<u8> = Vec::new();
+        let mut block = 0_u32;
+        for (idx, &unit) in name.iter().enumerate() {
+            let keep = idx >= stem_len
+                || u8::try_from(unit)
+                    .is_ok_and(|byte| byte.is_ascii() && !byte.is_ascii_alphanumeric());
+            if keep {
+                out.push(unit);
+                continue;
+            }
+            if stream.is_empty() {
+                stream = self.block(name, block);
+                block += 1;
+            }
+            let byte = stream.pop().unwrap_or_default();
+            let pick = alphabet
+                .get(usize::from(byte) % alphabet.len())
+                .copied()
+                .unwrap_or(b'x');
+            out.push(u16::from(pick));
+        }
+        out
+    }
+
+    /// One 32-byte keystream block for `name`.
+    fn block(&self, name: &[u16], counter: u32) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(crate::usize_to_u64(self.key.len()).to_le_bytes());
+        hasher.update(&self.key);
+        for unit in name {
+            hasher.update(unit.to_le_bytes());
+        }
+        hasher.update(counter.to_le_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Pseudonymize a backslash-separated path component by component,
+    /// keeping drive (`C:`), `??` and dot components.
+    fn pseudonym_path(&self, path: &[u16]) -> Vec<u16> {
+        let mut out = Vec::with_capacity(path.len());
+        for (idx, component) in path.split(|&unit| unit == BACKSLASH).enumerate() {
+            if idx > 0 {
+                out.push(BACKSLASH);
+            }
+            let keep = component.contains(&COLON)
+                || component.iter().all(|&unit| unit == DOT || unit == 0x3F);
+            if keep {
+                out.extend_from_slice(component);
+            } else {
+                out.extend(self.pseudonym(component, false));
+            }
+        }
+        out
+    }
+
+    /// Rewrite a `$FILE_NAME` value (attribute value or index key) owned by
+    /// `owner`. Returns whether the name changed.
+    fn scrub_file_name(&self, value: &mut [u8], owner: u64) -> bool {
+        let (Some(parent), Some(&len), Some(&namespace)) =
+            (read_u64(value, 0), value.get(64), value.get(65))
+        else {
+            return false;
+        };
+        let Some(name) = read_units(value, 66, usize::from(len)) else {
+            return false;
+        };
+        let parent_frs = parent & FRS_MASK;
+        if owner <= SYSTEM_METAFILE_MAX_FRS || (parent_frs == EXTEND_FRS && is_system_name(&name)) {
+            return false;
+        }
+        let pseudonym = self.pseudonym(&name, namespace == NAMESPACE_DOS);
+        write_units(value, 66, &pseudonym);
+        pseudonym != name
+    }
+
+    /// Rewrite the `$FILE_NAME` keys of a resident `$I30` index root.
+    fn scrub_index_root(&self, value: &mut [u8], stats: &mut AnonymizeStats) -> bool {
+        if read_u32(value, 0) != Some(ATTR_FILE_NAME) {
+            return false;
+        }
+        let (Some(first), Some(in_use)) = (read_u32(value, 16), read_u32(value, 20)) else {
+            return false;
+        };
+        let mut pos = 16 + first as usize;
+        let end = (16 + in_use as usize).min(value.len());
+        let mut changed = false;
+        while pos + 16 <= end {
+            let (Some(file_ref), Some(entry_len), Some(key_len), Some(flags)) = (
+                read_u64(value, pos),
+                read_u16(value, pos + 8),
+                read_u16(value, pos + 10),
+                read_u16(value, pos + 12),
+            ) else {
+                break;
+            };
+            if entry_len < 16 {
+                break;
+            }
+            let key_start = pos + 16;
+            let key_end = key_start + usize::from(key_len);
+            if key_len > 0
+                && key_end <= end
+                && let Some(key) = value.get_mut(key_start..key_end)
+                && self.scrub_file_name(key, file_ref & FRS_MASK)
+            {
+                stats.index_entries += 1;
+                changed = true;
+            }
+            if flags & 0x02 != 0 {
+                break;
+            }
+            pos += usize::from(entry_len);
+        }
+        changed
+    }
+
+    /// Rewrite attribute names in a resident `$ATTRIBUTE_LIST`.
+    fn scrub_attribute_list(
+        &self,
+        value: &mut [u8],
+        owner: u64,
+        stats: &mut AnonymizeStats,
+    ) -> bool {
+        let mut pos = 0_usize;
+        let mut changed = false;
+        while pos + 26 <= value.len() {
+            let (Some(entry_len), Some(&name_len), Some(&name_off)) = (
+                read_u16(value, pos + 4),
+                value.get(pos + 6),
+                value.get(pos + 7),
+            ) else {
+                break;
+            };
+            if entry_len == 0 {
+                break;
+            }
+            let name_start = pos + usize::from(name_off);
+            if name_len > 0
+                && owner > SYSTEM_METAFILE_MAX_FRS
+                && let Some(name) = read_units(value, name_start, usize::from(name_len))
+                && !is_system_name(&name)
+            {
+                write_units(value, name_start, &self.pseudonym(&name, false));
+                stats.stream_names += 1;
+                changed = true;
+            }
+            pos += usize::from(entry_len);
+        }
+        changed
+    }
+
+    /// Rewrite the substitute and print names of a mount-point or symlink.
+    fn scrub_reparse(&self, value: &mut [u8], stats: &mut AnonymizeStats) -> bool {
+        let path_base = match read_u32(value, 0) {
+            Some(TAG_MOUNT_POINT) => 16,
+            Some(TAG_SYMLINK) => 20,
+            _ => return false,
+        };
+        let mut changed = false;
+        for field in [8_usize, 12] {
+            let (Some(offset), Some(len)) = (read_u16(value, field), read_u16(value, field + 2))
+            else {
+                continue;
+            };
+            let start = path_base + usize::from(offset);
+            if let Some(path) = read_units(value, start, usize::from(len) / 2)
+                && !path.is_empty()
+            {
+                write_units(value, start, &self.pseudonym_path(&path));
+                stats.reparse_targets += 1;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Rewrite the names of a resident `$EA` value (a chain of
+    /// `FILE_FULL_EA_INFORMATION` entries with ASCII names).
+    fn scrub_ea(&self, value: &mut [u8], stats: &mut AnonymizeStats) -> bool {
+        let mut pos = 0_usize;
+        let mut changed = false;
+        while pos + 8 <= value.len() {
+            let (Some(next), Some(&name_len)) = (read_u32(value, pos), value.get(pos + 5)) else {
+                break;
+            };
+            let name_start = pos + 8;
+            if let Some(name) = value.get_mut(name_start..name_start + usize::from(name_len))
+                && name.first().is_some_and(|&byte| byte != b'$')
+            {
+                let units: Vec<u16> = name.iter().map(|&byte| u16::from(byte)).collect();
+                let pseudonym = self.pseudonym(&units, true);
+                for (byte, unit) in name.iter_mut().zip(pseudonym) {
+                    *byte = u8::try_from(unit).unwrap_or(b'X');
+                }
+                stats.ea_names += 1;
+                changed = true;
+            }
+            if next == 0 {
+                break;
+            }
+            pos += next as usize;
+        }
+        changed
+    }
+
+    /// Rewrite every name in one raw (USA-protected) `FILE` record in place
+    /// and zero its slack.
+    ///
+    /// `frs` is the record's position in the MFT; extension records use their
+    /// base record's FRS to decide whether names are metafile names. Deleted
+    /// records are scrubbed like live ones. Buffers that are not `FILE`
+    /// records (zeroed slots, `BAAD`) are left untouched. Returns whether any
+    /// name changed.
+    pub fn anonymize_record(
+        &self,
+        record: &mut [u8],
+        frs: u64,
+        stats: &mut AnonymizeStats,
+    ) -> bool {
+        if record.get(0..4) != Some(b"FILE".as_slice()) {
+            return false;
+        }
+        let (Some(usa_offset), Some(usa_count)) = (read_u16(record, 4), read_u16(record, 6)) else {
+            return false;
+        };
+        if usa_count == 0 || usize::from(usa_count - 1) * SECTOR_SIZE > record.len() {
+            return false;
+        }
+        stats.records += 1;
+
+        let original = record.to_vec();
+        let valid = apply_usa_fixup(record, usa_offset, usa_count);
+        let base = read_u64(record, 32).unwrap_or(0) & FRS_MASK;
+        let owner = if base == 0 { frs } else { base };
+
+        let mut changed = false;
+        let first = usize::from(read_u16(record, 20).unwrap_or(0));
+        let in_use = read_u32(record, 24).unwrap_or(0) as usize;
+        let limit = in_use.min(record.len());
+        let mut off = first;
+        while off + 16 <= limit {
+            let (Some(type_code), Some(attr_len)) =
+                (read_u32(record, off), read_u32(record, off + 4))
+            else {
+                break;
+            };
+            let len = attr_len as usize;
+            if type_code == ATTR_END || len < 16 || off + len > limit {
+                break;
+            }
+            changed |= self.scrub_attribute(record, off, len, type_code, owner, stats);
+            off += len;
+        }
+        // A `bytes_in_use` that reaches back into the header or the USA is
+        // corrupt; zeroing from it would break the fixup.
+        let usa_end = usize::from(usa_offset) + usize::from(usa_count) * 2;
+        if in_use >= first.max(usa_end)
+            && let Some(slack) = record.get_mut(in_use..)
+            && slack.iter().any(|&byte| byte != 0)
+        {
+            slack.fill(0);
+            stats.slack_scrubbed += 1;
+        }
+
+        if changed {
+            stats.rewritten += 1;
+        }
+        // `protect_usa` only fails when the USA lies outside the buffer, which
+        // `apply_usa_fixup` has already ruled out.
+        let _protected = protect_usa(record, usa_offset, usa_count);
+        if !valid {
+            stats.torn_records += 1;
+            restore_torn_sectors(record, &original, usa_offset, usa_count);
+        }
+        changed
+    }
+
+    /// Rewrite the names in one attribute at `off..off + len`.
+    fn scrub_attribute(
+        &self,
+        record: &mut [u8],
+        off: usize,
+        len: usize,
+        type_code: u32,
+        owner: u64,
+        stats: &mut AnonymizeStats,
+    ) -> bool {
+        let mut changed = false;
+        let non_resident = record.get(off + 8).copied().unwrap_or(0) != 0;
+        let name_len = usize::from(record.get(off + 9).copied().unwrap_or(0));
+        let name_start = off + usize::from(read_u16(record, off + 10).unwrap_or(0));
+        if name_len > 0
+            && owner > SYSTEM_METAFILE_MAX_FRS
+            && name_start + name_len * 2 <= off + len
+            && let Some(name) = read_units(record, name_start, name_len)
+            && !is_system_name(&name)
+        {
+            write_units(record, name_start, &self.pseudonym(&name, false));
+            stats.stream_names += 1;
+            changed = true;
+        }
+        if non_resident {
+            return changed;
+        }
+
+        let (Some(value_len), Some(value_off)) =
+            (read_u32(record, off + 16), read_u16(record, off + 20))
+        else {
+            return changed;
+        };
+        let start = off + usize::from(value_off);
+        let end = start + value_len as usize;
+        if end > off + len {
+            return changed;
+        }
+        let Some(value) = record.get_mut(start..end) else {
+            return changed;
+        };
+        match type_code {
+            ATTR_FILE_NAME => {
+                if self.scrub_file_name(value, owner) {
+                    stats.file_names += 1;
+                    changed = true;
+                }
+            }
+            ATTR_INDEX_ROOT => changed |= self.scrub_index_root(value, stats),
+            ATTR_ATTRIBUTE_LIST => changed |= self.scrub_attribute_list(value, owner, stats),
+            ATTR_REPARSE_POINT => changed |= self.scrub_reparse(value, stats),
+            ATTR_EA if owner > SYSTEM_METAFILE_MAX_FRS => {
+                changed |= self.scrub_ea(value, stats);
+            }
+            ATTR_VOLUME_NAME => {
+                if let Some(label) = read_units(value, 0, value.len() / 2)
+                    && !label.is_empty()
+                {
+                    write_units(value, 0, &self.pseudonym(&label, true));
+                    stats.volume_labels += 1;
+                    changed = true;
+                }
+            }
+            _ => {}
+        }
+        changed
+    }
+
+    /// Anonymize every record of a raw `$MFT` image (`record_size`-byte
+    /// records, FRS = position) in place.
+    #[must_use]
+    pub fn anonymize_mft(&self, data: &mut [u8], record_size: usize) -> AnonymizeStats {
+        let mut stats = AnonymizeStats::default();
+        if record_size == 0 {
+            return stats;
+        }
+        for (frs, record) in data.chunks_exact_mut(record_size).enumerate() {
+            let _changed = self.anonymize_record(record, crate::usize_to_u64(frs), &mut stats);
+        }
+        stats
+    }
+}
+
+/// Put back the original sector-end bytes of sectors whose USA check did not
+/// match, so a torn record stays torn after re-protection.
+fn restore_torn_sectors(record: &mut [u8], original: &[u8], usa_offset: u16, usa_count: u16) {
+    let Some(check) = read_u16(original, usize::from(usa_offset)) else {
+        return;
+    };
+    for sector_idx in 1..usize::from(usa_count) {
+        let end = sector_idx * SECTOR_SIZE - 2;
+        if let Some(before) = read_u16(original, end)
+            && before != check
+            && let Some(dest) = record.get_mut(end..end + 2)
+        {
+            dest.copy_from_slice(&before.to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "anonymize_tests.rs"]
+mod tests;
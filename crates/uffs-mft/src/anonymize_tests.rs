@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Tests for [`super`]: name rewriting, slack scrubbing and USA handling on
+//! synthetic `FILE` records.
+
+#![expect(
+    clippy::indexing_slicing,
+    reason = "test code builds records at fixed NTFS offsets"
+)]
+
+use super::{AnonymizeStats, Anonymizer, read_units};
+use crate::ntfs::{SECTOR_SIZE, fixup_file_record, protect_usa};
+
+/// Build a USA-protected 1 KiB `FILE` record holding `attrs`.
+fn record(base: u64, attrs: &[Vec<u8>]) -> Vec<u8> {
+    let mut rec = vec![0_u8; 1024];
+    rec[0..4].copy_from_slice(b"FILE");
+    rec[4..6].copy_from_slice(&0x30_u16.to_le_bytes());
+    rec[6..8].copy_from_slice(&3_u16.to_le_bytes());
+    rec[20..22].copy_from_slice(&0x38_u16.to_le_bytes());
+    rec[22..24].copy_from_slice(&1_u16.to_le_bytes());
+    rec[28..32].copy_from_slice(&1024_u32.to_le_bytes());
+    rec[32..40].copy_from_slice(&base.to_le_bytes());
+    rec[0x30..0x32].copy_from_slice(&0x0007_u16.to_le_bytes());
+    let mut off = 0x38;
+    for attr in attrs {
+        rec[off..off + attr.len()].copy_from_slice(attr);
+        off += attr.len();
+    }
+    rec[off..off + 4].copy_from_slice(&0xFFFF_FFFF_u32.to_le_bytes());
+    rec[24..28].copy_from_slice(&crate::len_to_u32(off + 8).to_le_bytes());
+    assert!(protect_usa(&mut rec, 0x30, 3));
+    rec
+}
+
+/// A resident attribute of `type_code` named `name` holding `value`.
+fn attr(type_code: u32, name: &str, value: &[u8]) -> Vec<u8> {
+    let name_units: Vec<u16> = name.encode_utf16().collect();
+    let value_off = (24 + name_units.len() * 2 + 7) & !7;
+    let len = (value_off + value.len() + 7) & !7;
+    let mut out = vec![0_u8; len];
+    out[0..4].copy_from_slice(&type_code.to_le_bytes());
+    out[4..8].copy_from_slice(&crate::len_to_u32(len).to_le_bytes());
+    out[9] = u8::try_from(name_units.len()).expect("short name");
+    out[10..12].copy_from_slice(&24_u16.to_le_bytes());
+    for (idx, unit) in name_units.iter().enumerate() {
+        out[24 + idx * 2..26 + idx * 2].copy_from_slice(&unit.to_le_bytes());
+    }
+    out[16..20].copy_from_slice(&crate::len_to_u32(value.len()).to_le_bytes());
+    out[20..22].copy_from_slice(&crate::len_to_u16(value_off).to_le_bytes());
+    out[value_off..value_off + value.len()].copy_from_slice(value);
+    out
+}
+
+/// A `$FILE_NAME` value for `name` under `parent`.
+fn file_name(parent: u64, name: &str, namespace: u8) -> Vec<u8> {
+    let units: Vec<u16> = name.encode_utf16().collect();
+    let mut value = vec![0_u8; 66 + units.len() * 2];
+    value[0..8].copy_from_slice(&parent.to_le_bytes());
+    value[48..56].copy_from_slice(&4242_u64.to_le_bytes());
+    value[64] = u8::try_from(units.len()).expect("short name");
+    value[65] = namespace;
+    for (idx, unit) in units.iter().enumerate() {
+        value[66 + idx * 2..68 + idx * 2].copy_from_slice(&unit.to_le_bytes());
+    }
+    value
+}
+
+/// Name stored in the first `$FILE_NAME` of a fixed-up record.
+fn first_name(rec: &[u8]) -> String {
+    let mut copy = rec.to_vec();
+    assert!(fixup_file_record(&mut copy));
+    let value_off = 0x38 + usize::from(u16::from_le_bytes([copy[0x38 + 20], copy[0x38 + 21]]));
+    let len = usize::from(copy[value_off + 64]);
+    String::from_utf16(&read_units(&copy, value_off + 66, len).expect("in bounds"))
+        .expect("pseudonyms are valid UTF-16")
+}
+
+#[test]
+fn pseudonym_keeps_length_punctuation_extension_and_is_keyed() {
+    let anon = Anonymizer::new(b"k1");
+    let name: Vec<u16> = "Q3 report (final).xlsx".encode_utf16().collect();
+    let out = String::from_utf16(&anon.pseudonym(&name, false)).expect("ascii");
+    assert_eq!(out.len(), 22);
+    assert!(out.ends_with(").xlsx"), "{out}");
+    assert_eq!(out.as_bytes()[2], b' ');
+    assert_ne!(out, "Q3 report (final).xlsx");
+    assert_eq!(anon.pseudonym(&name, false), anon.pseudonym(&name, false));
+    assert_ne!(
+        Anonymizer::new(b"k2").pseudonym(&name, false),
+        anon.pseudonym(&name, false)
+    );
+
+    let emoji: Vec<u16> = "😀😀.txt".encode_utf16().collect();
+    assert_eq!(anon.pseudonym(&emoji, false).len(), emoji.len());
+}
+
+#[test]
+fn rewrites_names_streams_and_keeps_sizes_and_metafiles() {
+    let anon = Anonymizer::new(b"secret");
+    let mut user = record(0, &[
+        attr(0x30, "", &file_name(5, "payroll.docx", 1)),
+        attr(0x30, "", &file_name(5, "PAYROL~1.DOC", 2)),
+        attr(0x80, "Zone.Identifier", b"[ZoneTransfer]"),
+        attr(0x80, "$J", b""),
+    ]);
+    let mut meta = record(0, &[attr(0x30, "", &file_name(5, "$MFT", 3))]);
+    let meta_before = meta.clone();
+
+    let mut stats = AnonymizeStats::default();
+    assert!(anon.anonymize_record(&mut user, 40, &mut stats));
+    assert!(!anon.anonymize_record(&mut meta, 0, &mut stats));
+    assert_eq!(meta, meta_before);
+    assert_eq!(stats.records, 2);
+    assert_eq!(stats.file_names, 2);
+    assert_eq!(stats.stream_names, 1);
+
+    // The record still fixes up, the name keeps its length and extension,
+    // and the size field in the `$FILE_NAME` is untouched.
+    let renamed = first_name(&user);
+    assert_eq!(renamed.len(), "payroll.docx".len());
+    assert_eq!(renamed.get(7..), Some(".docx"));
+    assert!(!renamed.starts_with("payroll"));
+    let mut fixed = user.clone();
+    assert!(fixup_file_record(&mut fixed));
+    let value_off = 0x38 + 24;
+    assert_eq!(
+        &fixed[value_off + 48..value_off + 56],
+        &4242_u64.to_le_bytes()
+    );
+    // AUDIT-OK(bytes): scans a whole record (headers included) for
+    // leftover plaintext; the lossy decode only feeds a substring check.
+    let text =
+        String::from_utf16_lossy(&read_units(&fixed, 0, fixed.len() / 2).expect("whole record"));
+    assert!(!text.contains("payroll") && !text.contains("Zone"));
+    assert!(text.contains("$J"));
+}
+
+/// Fix up `rec`, write `text` (UTF-16) at `offset`, and re-protect it.
+fn plant(rec: &mut [u8], offset: usize, text: &str) {
+    assert!(fixup_file_record(rec));
+    for (idx, unit) in text.encode_utf16().enumerate() {
+        rec[offset + idx * 2..offset + idx * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+    }
+    assert!(protect_usa(rec, 0x30, 3));
+}
+
+/// An `$EA` value with one entry per name (value `v`).
+fn ea(names: &[&str]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (idx, name) in names.iter().enumerate() {
+        let entry_len = (8 + name.len() + 1 + 1 + 3) & !3;
+        let start = out.len();
+        out.resize(start + entry_len, 0);
+        if idx + 1 < names.len() {
+            out[start..start + 4].copy_from_slice(&crate::len_to_u32(entry_len).to_le_bytes());
+        }
+        out[start + 5] = u8::try_from(name.len()).expect("short name");
+        out[start + 6..start + 8].copy_from_slice(&1_u16.to_le_bytes());
+        out[start + 8..start + 8 + name.len()].copy_from_slice(name.as_bytes());
+        out[start + 8 + name.len() + 1] = b'v';
+    }
+    out
+}
+
+#[test]
+fn slack_after_bytes_in_use_is_zeroed_in_live_and_deleted_records() {
+    let anon = Anonymizer::new(b"secret");
+    let mut stats = AnonymizeStats::default();
+    for flags in [0x0001_u16, 0x0000] {
+        let mut rec = record(0, &[attr(0x30, "", &file_name(5, "notes.txt", 1))]);
+        rec[22..24].copy_from_slice(&flags.to_le_bytes());
+        // The marker straddles the first sector end.
+        plant(&mut rec, SECTOR_SIZE - 6, "payroll");
+        plant(&mut rec, 0x300, "payroll");
+        let in_use = usize::try_from(u32::from_le_bytes([rec[24], rec[25], rec[26], rec[27]]))
+            .expect("fits");
+
+        assert!(anon.anonymize_record(&mut rec, 40, &mut stats));
+        let mut fixed = rec.clone();
+        assert!(fixup_file_record(&mut fixed));
+        assert!(fixed[in_use..].iter().all(|&byte| byte == 0));
+        // AUDIT-OK(bytes): lossy decode only feeds a substring check.
+        let text = String::from_utf16_lossy(
+            &read_units(&fixed, 0, fixed.len() / 2).expect("whole record"),
+        );
+        assert!(!text.contains("payroll"));
+    }
+    assert_eq!(stats.slack_scrubbed, 2);
+}
+
+#[test]
+fn rewrites_ea_names_but_keeps_system_ones() {
+    let anon = Anonymizer::new(b"secret");
+    let mut rec = record(0, &[
+        attr(0x30, "", &file_name(5, "build.sh", 1)),
+        attr(0xE0, "", &ea(&["PROJECTX.TAG", "$LXUID"])),
+    ]);
+    let mut stats = AnonymizeStats::default();
+    assert!(anon.anonymize_record(&mut rec, 40, &mut stats));
+    assert_eq!(stats.ea_names, 1);
+
+    assert!(fixup_file_record(&mut rec));
+    // AUDIT-OK(bytes): lossy decode only feeds a substring check.
+    let bytes = String::from_utf8_lossy(&rec);
+    assert!(!bytes.contains("PROJECTX"));
+    assert!(bytes.contains(".TAG") && bytes.contains("$LXUID"));
+}
+
+#[test]
+fn torn_record_is_scrubbed_but_stays_torn() {
+    let anon = Anonymizer::new(b"secret");
+    let mut rec = record(0, &[attr(0x30, "", &file_name(5, "diary.txt", 1))]);
+    rec[SECTOR_SIZE - 2..SECTOR_SIZE].copy_from_slice(&0xDEAD_u16.to_le_bytes());
+
+    let mut stats = AnonymizeStats::default();
+    assert!(anon.anonymize_record(&mut rec, 99, &mut stats));
+    assert_eq!(stats.torn_records, 1);
+    assert_eq!(
+        &rec[SECTOR_SIZE - 2..SECTOR_SIZE],
+        &0xDEAD_u16.to_le_bytes()
+    );
+    assert!(!fixup_file_record(&mut rec.clone()));
+}
+
+#[test]
+fn anonymize_mft_keeps_hard_links_consistent() {
+    let anon = Anonymizer::new(b"secret");
+    let mut data = record(0, &[attr(0x30, "", &file_name(30, "budget.xlsx", 1))]);
+    data.extend(record(0, &[attr(
+        0x30,
+        "",
+        &file_name(31, "budget.xlsx", 1),
+    )]));
+    data.extend(vec![0_u8; 1024]);
+
+    let stats = anon.anonymize_mft(&mut data, 1024);
+    assert_eq!(stats.records, 2);
+    assert_eq!(first_name(&data[..1024]), first_name(&data[1024..2048]));
+    assert!(data[2048..].iter().all(|&byte| byte == 0));
+}
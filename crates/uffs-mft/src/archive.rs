@@ -1,14 +1,17 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (c) 2025-2026 SKY, LLC.
 
-//! Minimal `ustar` (tar) archive writer and byte splitter for capture bundles.
+//! Minimal `ustar` (tar) archive writer, reader and byte splitter for capture
+//! bundles.
 //!
 //! No third-party archive dependency: a capture bundle is packed as a plain
 //! `ustar` archive (readable by any `tar`), which the caller compresses with
 //! the existing `zstd` dependency into a `.tar.zst` and optionally splits into
 //! fixed-size parts for transfer. Pure and cross-platform, so it is unit-tested
-//! on the build host and the offline (macOS/Linux) side can reason about it
-//! too.
+//! on the build host and the offline (macOS/Linux) side streams the same
+//! bytes back through a [`TarReader`] (see [`crate::bundle`]).
+
+use std::io::{ErrorKind, Read};
 
 use crate::error::{MftError, Result};
 use crate::usize_to_u64;
@@ -16,12 +19,41 @@ use crate::usize_to_u64;
 /// tar block size in bytes.
 const BLOCK: usize = 512;
 
+/// [`BLOCK`] as a stream offset.
+const BLOCK_U64: u64 = 512;
+
 /// Maximum file name length in the `ustar` name field.
 const NAME_MAX: usize = 100;
 
 /// Offset of the 8-byte checksum field within a `ustar` header.
 const CHKSUM_OFFSET: usize = 148;
 
+/// Offset of the 12-byte octal size field within a `ustar` header.
+const SIZE_OFFSET: usize = 124;
+
+/// Offset of the 12-byte octal mtime field within a `ustar` header.
+const MTIME_OFFSET: usize = 136;
+
+/// Offset of the 1-byte type flag within a `ustar` header.
+const TYPEFLAG_OFFSET: usize = 156;
+
+/// Offset of the 6-byte magic (`ustar\0`) within a `ustar` header.
+const MAGIC_OFFSET: usize = 257;
+
+/// Offset of the 155-byte name prefix within a `ustar` header.
+const PREFIX_OFFSET: usize = 345;
+
+/// Header of one regular-file member, as [`TarReader`] reaches it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarMember {
+    /// Member name (prefix and name fields joined with `/`).
+    pub name: String,
+    /// Modification time, Unix seconds.
+    pub mtime: u64,
+    /// Size of the member's data in bytes.
+    pub size: u64,
+}
+
 /// Push `content` into `buf`, truncated or zero-padded to exactly `width`
 /// bytes.
 fn push_field(buf: &mut Vec<u8>, content: &[u8], width: usize) {
@@ -99,6 +131,210 @@ pub fn finish(buf: &mut Vec<u8>) {
     buf.resize(buf.len() + BLOCK * 2, 0);
 }
 
+/// Bytes of a NUL-terminated header field, without the terminator.
+fn field_bytes(header: &[u8], offset: usize, width: usize) -> &[u8] {
+    let field = header.get(offset..offset + width).unwrap_or_default();
+    let end = field
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(field.len());
+    field.get(..end).unwrap_or_default()
+}
+
+/// A header name field as UTF-8.  Member names are matched against the
+/// manifest, so they are parsed strictly: a non-UTF-8 name is corruption,
+/// not something to repair.
+fn name_str(bytes: &[u8], pos: u64) -> Result<&str> {
+    core::str::from_utf8(bytes)
+        .map_err(|_err| MftError::InvalidData(format!("tar member at {pos} has a non-UTF-8 name")))
+}
+
+/// Parse a space/NUL-padded octal header field.
+fn parse_octal(header: &[u8], offset: usize, width: usize, what: &str) -> Result<u64> {
+    let raw = field_bytes(header, offset, width);
+    let text = core::str::from_utf8(raw)
+        .map_err(|_err| MftError::InvalidData(format!("tar {what} field is not ASCII")))?
+        .trim();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8)
+        .map_err(|err| MftError::InvalidData(format!("tar {what} field {text:?}: {err}")))
+}
+
+/// Parse and checksum-verify the header block found at archive offset
+/// `pos`, returning the member it describes and its type flag.
+fn parse_header(header: &[u8], pos: u64) -> Result<(TarMember, u8)> {
+    if header.get(MAGIC_OFFSET..MAGIC_OFFSET + 5) != Some(b"ustar".as_slice()) {
+        return Err(MftError::InvalidData(format!(
+            "tar header at offset {pos} has no ustar magic"
+        )));
+    }
+    let stored = parse_octal(header, CHKSUM_OFFSET, 8, "checksum")?;
+    let computed: u64 = header
+        .iter()
+        .enumerate()
+        .map(|(idx, &byte)| {
+            if (CHKSUM_OFFSET..CHKSUM_OFFSET + 8).contains(&idx) {
+                u64::from(b' ')
+            } else {
+                u64::from(byte)
+            }
+        })
+        .sum();
+    if stored != computed {
+        return Err(MftError::InvalidData(format!(
+            "tar header at offset {pos}: checksum {stored:o} != computed {computed:o}"
+        )));
+    }
+
+    let size = parse_octal(header, SIZE_OFFSET, 12, "size")?;
+    let mtime = parse_octal(header, MTIME_OFFSET, 12, "mtime")?;
+    let name_bytes = field_bytes(header, 0, NAME_MAX);
+    let prefix_bytes = field_bytes(header, PREFIX_OFFSET, 155);
+    let name = if prefix_bytes.is_empty() {
+        name_str(name_bytes, pos)?.to_owned()
+    } else {
+        format!(
+            "{}/{}",
+            name_str(prefix_bytes, pos)?,
+            name_str(name_bytes, pos)?
+        )
+    };
+    let typeflag = header.get(TYPEFLAG_OFFSET).copied().unwrap_or(0);
+    Ok((TarMember { name, mtime, size }, typeflag))
+}
+
+/// Streaming reader for a `ustar` archive: the inverse of [`push_entry`] +
+/// [`finish`].
+///
+/// [`Self::next_member`] moves to the next regular-file member — headers are
+/// checksum-verified, non-regular members (directories, links) are skipped,
+/// and the first all-zero block ends the archive — and the reader itself
+/// then yields that member's data.  Only one header block is buffered, so a
+/// multi-gigabyte `$MFT` member never has to fit in memory.  A member whose
+/// data runs past the end of the stream is an error rather than a silently
+/// short member.
+#[derive(Debug)]
+pub struct TarReader<R> {
+    /// The archive stream.
+    inner: R,
+    /// Archive offset of the next unread byte.
+    pos: u64,
+    /// Data bytes of the current member not read yet.
+    remaining: u64,
+    /// Zero padding after the current member's data.
+    padding: u64,
+    /// Name of the current member, for truncation errors.
+    current: String,
+    /// The end of the archive has been reached.
+    finished: bool,
+}
+
+impl<R: Read> TarReader<R> {
+    /// Read the archive from `inner`.
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pos: 0,
+            remaining: 0,
+            padding: 0,
+            current: String::new(),
+            finished: false,
+        }
+    }
+
+    /// Skip whatever is left of the current member and move to the next
+    /// regular-file member; `None` at the end of the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MftError::InvalidData`] on a bad magic, checksum mismatch,
+    /// malformed octal field or truncated member, and [`MftError::Io`] when
+    /// the stream cannot be read.
+    pub fn next_member(&mut self) -> Result<Option<TarMember>> {
+        while !self.finished {
+            self.skip_rest()?;
+            let mut header = [0_u8; BLOCK];
+            let header_pos = self.pos;
+            if !self.read_block(&mut header)? || header.iter().all(|&byte| byte == 0) {
+                self.finished = true;
+                break;
+            }
+            let (member, typeflag) = parse_header(&header, header_pos)?;
+            self.remaining = member.size;
+            self.padding = member.size.next_multiple_of(BLOCK_U64) - member.size;
+            self.current.clone_from(&member.name);
+            if matches!(typeflag, b'0' | 0) {
+                return Ok(Some(member));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fill `block` from the stream.  `false` when the stream ends first; a
+    /// partial trailing block ends the archive like a missing one.
+    fn read_block(&mut self, block: &mut [u8; BLOCK]) -> Result<bool> {
+        let mut filled = 0_usize;
+        while let Some(rest) = block.get_mut(filled..).filter(|rest| !rest.is_empty()) {
+            match self.inner.read(rest) {
+                Ok(0) => return Ok(false),
+                Ok(read) => filled += read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        self.pos += BLOCK_U64;
+        Ok(true)
+    }
+
+    /// Discard the current member's unread data and padding.  Missing
+    /// padding after the last member ends the archive.
+    fn skip_rest(&mut self) -> Result<()> {
+        let data = self.remaining;
+        let skipped = std::io::copy(&mut (&mut self.inner).take(data), &mut std::io::sink())?;
+        self.pos += skipped;
+        self.remaining = 0;
+        if skipped < data {
+            return Err(MftError::InvalidData(format!(
+                "tar member {:?} truncated ({data} more bytes declared)",
+                self.current
+            )));
+        }
+        let padding = core::mem::take(&mut self.padding);
+        let skipped_padding =
+            std::io::copy(&mut (&mut self.inner).take(padding), &mut std::io::sink())?;
+        self.pos += skipped_padding;
+        if skipped_padding < padding {
+            self.finished = true;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the current member's data; `Ok(0)` at its end.
+impl<R: Read> Read for TarReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let want = usize::try_from(self.remaining).map_or(buf.len(), |left| left.min(buf.len()));
+        let Some(window) = buf.get_mut(..want).filter(|window| !window.is_empty()) else {
+            return Ok(0);
+        };
+        let read = self.inner.read(window)?;
+        if read == 0 {
+            return Err(std::io::Error::new(
+                ErrorKind::UnexpectedEof,
+                format!(
+                    "tar member {:?} truncated ({} more bytes declared)",
+                    self.current, self.remaining
+                ),
+            ));
+        }
+        self.remaining -= usize_to_u64(read);
+        self.pos += usize_to_u64(read);
+        Ok(read)
+    }
+}
+
 /// Split `data` into consecutive parts of at most `part_size` bytes (the last
 /// part may be smaller). A `part_size` of `0` returns the data as one part.
 #[must_use]
@@ -111,7 +347,9 @@ pub fn split(data: &[u8], part_size: usize) -> Vec<&[u8]> {
 
 #[cfg(test)]
 mod tests {
-    use super::{BLOCK, CHKSUM_OFFSET, finish, push_entry, split};
+    use std::io::Read as _;
+
+    use super::{BLOCK, CHKSUM_OFFSET, TarMember, TarReader, finish, push_entry, split};
 
     #[test]
     #[expect(
@@ -157,6 +395,76 @@ mod tests {
         push_entry(&mut buf, &"a".repeat(101), b"x", 0).unwrap_err();
     }
 
+    /// Every member of `buf` with its data, read through a [`TarReader`].
+    fn read_all(buf: &[u8]) -> crate::error::Result<Vec<(TarMember, Vec<u8>)>> {
+        let mut tar = TarReader::new(buf);
+        let mut members = Vec::new();
+        while let Some(member) = tar.next_member()? {
+            let mut data = Vec::new();
+            tar.read_to_end(&mut data)?;
+            members.push((member, data));
+        }
+        Ok(members)
+    }
+
+    #[test]
+    fn tar_reader_round_trips_push_entry() {
+        let mut buf = Vec::new();
+        push_entry(&mut buf, "manifest.json", b"{}", 7).expect("valid name");
+        push_entry(&mut buf, "C_mft.bin", &[0xAB; 1300], 9).expect("valid name");
+        finish(&mut buf);
+
+        let members = read_all(&buf).expect("well-formed archive");
+        let [(manifest, manifest_data), (mft, mft_data)] = members.as_slice() else {
+            panic!("expected two members, got {members:?}");
+        };
+        assert_eq!(manifest.name, "manifest.json");
+        assert_eq!(manifest.mtime, 7);
+        assert_eq!(manifest_data, b"{}");
+        assert_eq!(mft.name, "C_mft.bin");
+        assert_eq!(mft.size, 1300);
+        assert_eq!(mft_data, &vec![0xAB; 1300]);
+    }
+
+    #[test]
+    fn tar_reader_skips_the_unread_rest_of_a_member() {
+        let mut buf = Vec::new();
+        push_entry(&mut buf, "big.bin", &[1; 1300], 0).expect("valid name");
+        push_entry(&mut buf, "small.bin", b"xy", 0).expect("valid name");
+        finish(&mut buf);
+
+        let mut tar = TarReader::new(buf.as_slice());
+        assert!(tar.next_member().expect("first").is_some());
+        let mut head = [0_u8; 10];
+        tar.read_exact(&mut head).expect("partial read");
+        let second = tar.next_member().expect("second").expect("a member");
+        assert_eq!(second.name, "small.bin");
+        let mut data = Vec::new();
+        tar.read_to_end(&mut data).expect("data");
+        assert_eq!(data, b"xy");
+        assert!(tar.next_member().expect("end").is_none());
+    }
+
+    #[test]
+    fn tar_reader_rejects_corrupt_header_and_truncation() {
+        let mut buf = Vec::new();
+        push_entry(&mut buf, "c_boot.bin", &[1; 600], 0).expect("valid name");
+        finish(&mut buf);
+
+        let mut corrupt = buf.clone();
+        if let Some(byte) = corrupt.get_mut(3) {
+            *byte ^= 0x20;
+        }
+        read_all(&corrupt).unwrap_err();
+
+        let truncated = buf.get(..BLOCK + 100).expect("in bounds");
+        read_all(truncated).unwrap_err();
+        // Skipping a truncated member fails too.
+        let mut tar = TarReader::new(truncated);
+        assert!(tar.next_member().expect("header").is_some());
+        tar.next_member().unwrap_err();
+    }
+
     #[test]
     fn split_chunks_data() {
         let data = [1_u8, 2, 3, 4, 5];
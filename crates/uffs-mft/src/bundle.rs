@@ -0,0 +1,657 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Offline side of `capture` bundles: reassemble, verify and unpack.
+//!
+//! The Windows `capture --zip` command writes `drive_<x>.tar.zst` (or split
+//! `drive_<x>.tar.zst.NNN` parts) holding the `$MFT`, the NTFS metafiles, a
+//! `manifest.json` with a SHA-256 per artifact and a `SHA256SUMS` file. This
+//! module is the matching reader on the transfer target (macOS/Linux): it
+//! stitches split parts back together, checks every manifest member against
+//! its recorded size and hash, and lays the members out as
+//! `<data-dir>/drive_<x>/`, the tree [`crate::discovery`] scans.
+//!
+//! A bundle is streamed, never held whole: the parts are read one after the
+//! other through a zstd decoder and a [`TarReader`], hashing each member as
+//! it passes, and [`Bundle::unpack`] streams it a second time straight into
+//! the target files.  Only the manifest is kept in memory.
+
+use std::fs::File;
+use std::io::{Read, Write as _};
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest as _, Sha256};
+
+use crate::archive::TarReader;
+use crate::error::{MftError, Result};
+
+/// Name of the manifest member inside a bundle.
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// Bundle members written by `capture` that the manifest does not list.
+const SIDECAR_NAMES: [&str; 3] = [MANIFEST_NAME, "SHA256SUMS", "reserved_allocated.txt"];
+
+/// One artifact as recorded in a bundle's `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ManifestArtifact {
+    /// File name within the bundle.
+    pub file: String,
+    /// NTFS metafile name (e.g. `$Boot`, `$MFT`).
+    pub kind: String,
+    /// Source MFT FRS number.
+    pub frs: u8,
+    /// File size in bytes.
+    pub bytes: u64,
+    /// SHA-256 of the file, lowercase hex.
+    pub sha256: String,
+}
+
+/// The subset of a bundle's `manifest.json` the offline side relies on.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct BundleManifest {
+    /// Manifest schema version.
+    pub schema: u32,
+    /// Captured drive letter.
+    pub drive: String,
+    /// Capture timestamp (RFC 3339, UTC).
+    pub captured_at: String,
+    /// `uffs-mft` version that wrote the bundle.
+    pub tool_version: String,
+    /// Captured artifacts.
+    pub artifacts: Vec<ManifestArtifact>,
+}
+
+/// Outcome of checking one bundle member against the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberStatus {
+    /// Present with the recorded size and SHA-256.
+    Ok,
+    /// Listed in the manifest but absent from the archive.
+    Missing,
+    /// Present, but its size differs from the manifest.
+    SizeMismatch {
+        /// Size recorded in the manifest.
+        expected: u64,
+        /// Size of the archived member.
+        actual: u64,
+    },
+    /// Present with the right size, but its SHA-256 differs.
+    HashMismatch {
+        /// Hash recorded in the manifest.
+        expected: String,
+        /// Hash of the archived member.
+        actual: String,
+    },
+    /// In the archive but not listed in the manifest (and not a known
+    /// sidecar).
+    Unlisted,
+}
+
+/// Verification result for one member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberReport {
+    /// Member file name.
+    pub file: String,
+    /// Manifest kind (`$MFT`, `$Boot`, ...), empty for unlisted members.
+    pub kind: String,
+    /// What the check found.
+    pub status: MemberStatus,
+}
+
+impl MemberReport {
+    /// Whether this member makes the bundle unusable (missing or corrupt).
+    /// Unlisted extras are reported but not fatal.
+    #[must_use]
+    pub const fn is_failure(&self) -> bool {
+        matches!(
+            self.status,
+            MemberStatus::Missing
+                | MemberStatus::SizeMismatch { .. }
+                | MemberStatus::HashMismatch { .. }
+        )
+    }
+}
+
+/// One archived member, as read while the bundle was opened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleMember {
+    /// Member file name.
+    pub name: String,
+    /// Modification time, Unix seconds.
+    pub mtime: u64,
+    /// Size in bytes.
+    pub size: u64,
+    /// SHA-256 of the member, lowercase hex.
+    pub sha256: String,
+}
+
+/// A capture bundle: its parsed manifest plus every archived member's size
+/// and hash.  The member data stays in the bundle's files.
+#[derive(Debug, Clone)]
+#[expect(
+    clippy::partial_pub_fields,
+    reason = "the source is re-read by unpack; only the manifest and member list are API"
+)]
+pub struct Bundle {
+    /// The parsed `manifest.json`.
+    pub manifest: BundleManifest,
+    /// Every regular-file member, in archive order.
+    pub entries: Vec<BundleMember>,
+    /// Where the compressed bytes are read from.
+    source: Source,
+}
+
+/// Where a bundle's compressed bytes live; read once to open the bundle and
+/// again to unpack it.
+#[derive(Debug, Clone)]
+enum Source {
+    /// The unsplit file or the split parts, in order.
+    Parts(Vec<PathBuf>),
+    /// Bytes handed to [`Bundle::from_compressed`].
+    Memory(Vec<u8>),
+}
+
+impl Source {
+    /// A tar reader over the decompressed bundle.
+    fn tar(&self) -> Result<TarReader<Box<dyn Read + '_>>> {
+        let compressed: Box<dyn Read + '_> = match self {
+            Self::Parts(paths) => Box::new(PartsReader {
+                parts: paths.iter(),
+                current: None,
+            }),
+            Self::Memory(bytes) => Box::new(bytes.as_slice()),
+        };
+        let decoder = zstd::stream::read::Decoder::new(compressed)
+            .map_err(|err| MftError::InvalidData(format!("bundle is not valid zstd: {err}")))?;
+        Ok(TarReader::new(Box::new(decoder)))
+    }
+}
+
+/// The concatenation of a bundle's parts, each opened when the previous one
+/// is used up.
+#[derive(Debug)]
+struct PartsReader<'parts> {
+    /// Parts not opened yet.
+    parts: core::slice::Iter<'parts, PathBuf>,
+    /// The part being read.
+    current: Option<File>,
+}
+
+impl Read for PartsReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if let Some(file) = &mut self.current {
+                let read = file.read(buf)?;
+                if read > 0 || buf.is_empty() {
+                    return Ok(read);
+                }
+            }
+            let Some(next) = self.parts.next() else {
+                return Ok(0);
+            };
+            self.current = Some(File::open(next)?);
+        }
+    }
+}
+
+/// Read the current member of `tar` to its end, handing each chunk to
+/// `sink`, and return its SHA-256 as lowercase hex.
+fn drain_member(
+    tar: &mut impl Read,
+    mut sink: impl FnMut(&[u8]) -> std::io::Result<()>,
+) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut chunk = vec![0_u8; 1 << 20];
+    loop {
+        let read = tar
+            .read(&mut chunk)
+            .map_err(|err| MftError::InvalidData(format!("bundle stream is corrupt: {err}")))?;
+        let Some(data) = chunk.get(..read).filter(|data| !data.is_empty()) else {
+            break;
+        };
+        hasher.update(data);
+        sink(data)?;
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Resolve the ordered list of files that make up a bundle.
+///
+/// `path` may name the unsplit `<dir>.tar.zst`, its first part
+/// `<dir>.tar.zst.000`, or the split base `<dir>.tar.zst` when only the
+/// numbered parts exist on disk. Parts are collected from `.000` upwards; a
+/// gap in the numbering is an error naming the missing part rather than a
+/// silently short archive.
+///
+/// # Errors
+///
+/// Returns [`MftError::InvalidInput`] if neither the file nor a `.000` part
+/// exists, or if a part in the middle of the sequence is missing.
+pub fn part_paths(path: &Path) -> Result<Vec<PathBuf>> {
+    let text = path.to_string_lossy();
+    let base = match text.rsplit_once('.') {
+        Some((stem, suffix))
+            if suffix.len() == 3 && suffix.bytes().all(|byte| byte.is_ascii_digit()) =>
+        {
+            stem.to_owned()
+        }
+        _ if path.is_file() => return Ok(vec![path.to_path_buf()]),
+        _ => text.into_owned(),
+    };
+
+    let part = |idx: usize| PathBuf::from(format!("{base}.{idx:03}"));
+    if !part(0).is_file() {
+        return Err(MftError::InvalidInput(format!(
+            "no bundle at {} (expected the file or a .000 part)",
+            path.display()
+        )));
+    }
+
+    // Collect consecutive parts, then make sure nothing numbered higher is
+    // stranded behind a gap.
+    let mut parts = Vec::new();
+    while part(parts.len()).is_file() {
+        parts.push(part(parts.len()));
+    }
+    let parent = Path::new(&base)
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let base_name = Path::new(&base)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if let Ok(dir) = std::fs::read_dir(parent) {
+        let highest_part = dir
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let suffix = name.strip_prefix(&base_name)?.strip_prefix('.')?;
+                (suffix.len() == 3)
+                    .then(|| suffix.parse::<usize>().ok())
+                    .flatten()
+            })
+            .max();
+        if let Some(highest) = highest_part
+            && highest >= parts.len()
+        {
+            return Err(MftError::InvalidInput(format!(
+                "bundle part {} is missing ({} of {} parts present)",
+                part(parts.len()).display(),
+                parts.len(),
+                highest + 1
+            )));
+        }
+    }
+    Ok(parts)
+}
+
+impl Bundle {
+    /// Reassemble, decompress and untar the bundle at `path` (see
+    /// [`part_paths`] for the accepted forms), then parse its manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a part is missing or unreadable, the zstd stream
+    /// or tar framing is corrupt, or the archive has no valid
+    /// `manifest.json`.
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::scan(Source::Parts(part_paths(path)?))
+    }
+
+    /// Decode a bundle from its reassembled `.tar.zst` bytes, keeping a copy
+    /// of them for [`Self::unpack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MftError::InvalidData`] if the zstd stream or tar framing is
+    /// corrupt or `manifest.json` is absent or malformed.
+    pub fn from_compressed(compressed: &[u8]) -> Result<Self> {
+        Self::scan(Source::Memory(compressed.to_vec()))
+    }
+
+    /// Stream `source` once: hash every member and parse the manifest.
+    fn scan(source: Source) -> Result<Self> {
+        let mut entries = Vec::new();
+        let mut manifest_json: Option<Vec<u8>> = None;
+        let mut tar = source.tar()?;
+        while let Some(member) = tar.next_member()? {
+            let mut json = (member.name == MANIFEST_NAME && manifest_json.is_none()).then(Vec::new);
+            let sha256 = drain_member(&mut tar, |data| {
+                if let Some(buf) = &mut json {
+                    buf.extend_from_slice(data);
+                }
+                Ok(())
+            })?;
+            if json.is_some() {
+                manifest_json = json;
+            }
+            entries.push(BundleMember {
+                name: member.name,
+                mtime: member.mtime,
+                size: member.size,
+                sha256,
+            });
+        }
+        drop(tar);
+        let json = manifest_json
+            .ok_or_else(|| MftError::InvalidData(format!("bundle has no {MANIFEST_NAME}")))?;
+        let manifest: BundleManifest = serde_json::from_slice(&json)
+            .map_err(|err| MftError::InvalidData(format!("{MANIFEST_NAME}: {err}")))?;
+        Ok(Self {
+            manifest,
+            entries,
+            source,
+        })
+    }
+
+    /// Check every manifest artifact against the archived members.
+    ///
+    /// One report per manifest artifact (in manifest order), followed by one
+    /// [`MemberStatus::Unlisted`] report for each archived member that is
+    /// neither in the manifest nor a known sidecar.
+    #[must_use]
+    pub fn verify(&self) -> Vec<MemberReport> {
+        let mut reports = Vec::with_capacity(self.manifest.artifacts.len());
+        for artifact in &self.manifest.artifacts {
+            let status = match self.entry(&artifact.file) {
+                None => MemberStatus::Missing,
+                Some(entry) if entry.size != artifact.bytes => MemberStatus::SizeMismatch {
+                    expected: artifact.bytes,
+                    actual: entry.size,
+                },
+                Some(entry) if entry.sha256.eq_ignore_ascii_case(&artifact.sha256) => {
+                    MemberStatus::Ok
+                }
+                Some(entry) => MemberStatus::HashMismatch {
+                    expected: artifact.sha256.clone(),
+                    actual: entry.sha256.clone(),
+                },
+            };
+            reports.push(MemberReport {
+                file: artifact.file.clone(),
+                kind: artifact.kind.clone(),
+                status,
+            });
+        }
+        for entry in &self.entries {
+            let listed = self
+                .manifest
+                .artifacts
+                .iter()
+                .any(|artifact| artifact.file == entry.name);
+            if !listed && !SIDECAR_NAMES.contains(&entry.name.as_str()) {
+                reports.push(MemberReport {
+                    file: entry.name.clone(),
+                    kind: String::new(),
+                    status: MemberStatus::Unlisted,
+                });
+            }
+        }
+        reports
+    }
+
+    /// The archived member named `name`, if present.
+    #[must_use]
+    pub fn entry(&self, name: &str) -> Option<&BundleMember> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// The `drive_<x>` directory name this bundle unpacks into.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MftError::InvalidData`] if the manifest's `drive` is not a
+    /// single ASCII letter.
+    pub fn drive_dir_name(&self) -> Result<String> {
+        let drive = self.manifest.drive.trim_end_matches(':');
+        let mut chars = drive.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) if letter.is_ascii_alphabetic() => {
+                Ok(format!("drive_{}", letter.to_ascii_lowercase()))
+            }
+            _ => Err(MftError::InvalidData(format!(
+                "manifest drive {:?} is not a drive letter",
+                self.manifest.drive
+            ))),
+        }
+    }
+
+    /// Write every member into `<data_dir>/drive_<x>/`, restoring each
+    /// member's mtime, and return that directory.
+    ///
+    /// Refuses to overwrite existing files unless `force` is set, so a second
+    /// unpack cannot silently replace a capture already in the data dir.
+    /// Member names containing path separators or `..` are rejected.  The
+    /// bundle is streamed again, and each member is written to a
+    /// `.<name>.partial` file, checked against the hash taken when it was
+    /// opened, and only then renamed into place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target exists (without `force`), a member name
+    /// is unsafe, the bundle changed since it was opened, or any write fails.
+    pub fn unpack(&self, data_dir: &Path, force: bool) -> Result<PathBuf> {
+        let dir = data_dir.join(self.drive_dir_name()?);
+        for entry in &self.entries {
+            if entry.name.is_empty()
+                || entry.name.contains(['/', '\\'])
+                || entry.name == ".."
+                || entry.name == "."
+            {
+                return Err(MftError::InvalidData(format!(
+                    "refusing to unpack unsafe member name {:?}",
+                    entry.name
+                )));
+            }
+            if !force && dir.join(&entry.name).exists() {
+                return Err(MftError::InvalidInput(format!(
+                    "{} already exists (use --force to overwrite)",
+                    dir.join(&entry.name).display()
+                )));
+            }
+        }
+
+        std::fs::create_dir_all(&dir)?;
+        let mut tar = self.source.tar()?;
+        for entry in &self.entries {
+            // Written beside the target and renamed over it only once the
+            // hash matches, so a failed unpack never leaves a torn file.
+            let partial = dir.join(format!(".{}.partial", entry.name));
+            if let Err(err) = unpack_member(&mut tar, entry, &partial) {
+                let _rm_partial = std::fs::remove_file(&partial);
+                return Err(err);
+            }
+            std::fs::rename(&partial, dir.join(&entry.name))?;
+        }
+        Ok(dir)
+    }
+}
+
+/// Stream the next member of `tar` into a new file at `path`, checking
+/// it is `entry` as hashed when the bundle was opened.
+fn unpack_member<R: Read>(tar: &mut TarReader<R>, entry: &BundleMember, path: &Path) -> Result<()> {
+    let changed = || {
+        MftError::InvalidData(format!(
+            "bundle changed since it was opened (at {:?})",
+            entry.name
+        ))
+    };
+    tar.next_member()?
+        .filter(|member| member.name == entry.name)
+        .ok_or_else(changed)?;
+    let mut file = File::create(path)?;
+    let sha256 = drain_member(tar, |data| file.write_all(data))?;
+    if sha256 != entry.sha256 {
+        return Err(changed());
+    }
+    file.sync_all()?;
+    let mtime = std::time::UNIX_EPOCH + core::time::Duration::from_secs(entry.mtime);
+    file.set_modified(mtime)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest as _, Sha256};
+
+    use super::{Bundle, MemberStatus, part_paths};
+    use crate::archive;
+
+    /// SHA-256 of a byte slice, lowercase hex.
+    fn sha256_hex(bytes: &[u8]) -> String {
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    /// Build a `.tar.zst` bundle with a manifest listing `listed` members and
+    /// archiving `archived` ones.
+    fn make_bundle(listed: &[(&str, &[u8])], archived: &[(&str, &[u8])]) -> Vec<u8> {
+        let artifacts: Vec<serde_json::Value> = listed
+            .iter()
+            .map(|(name, data)| {
+                serde_json::json!({
+                    "file": name,
+                    "kind": "$Boot",
+                    "frs": 7_u8,
+                    "bytes": data.len(),
+                    "sha256": sha256_hex(data),
+                })
+            })
+            .collect();
+        let manifest = serde_json::json!({
+            "schema": 1_u32,
+            "drive": "C",
+            "captured_at": "2026-01-01T00:00:00Z",
+            "tool_version": "0.0.0",
+            "volume": { "serial": "0x0" },
+            "artifacts": artifacts,
+        });
+        let mut tar = Vec::new();
+        archive::push_entry(
+            &mut tar,
+            "manifest.json",
+            manifest.to_string().as_bytes(),
+            1,
+        )
+        .expect("valid name");
+        for (name, data) in archived {
+            archive::push_entry(&mut tar, name, data, 1_700_000_000).expect("valid name");
+        }
+        archive::finish(&mut tar);
+        zstd::encode_all(tar.as_slice(), 3).expect("zstd encode")
+    }
+
+    #[test]
+    fn verify_reports_ok_missing_corrupt_and_unlisted() {
+        let compressed = make_bundle(
+            &[
+                ("C_mft.bin", b"mft"),
+                ("c_boot.bin", b"boot"),
+                ("c_bitmap.bin", b"bitmap"),
+                ("c_secure.bin", b"secure"),
+            ],
+            &[
+                ("C_mft.bin", b"mft"),
+                ("c_boot.bin", b"BOOT"),
+                ("c_bitmap.bin", b"bit"),
+                ("SHA256SUMS", b""),
+                ("stray.txt", b"x"),
+            ],
+        );
+        let bundle = Bundle::from_compressed(&compressed).expect("valid bundle");
+        let statuses: Vec<_> = bundle
+            .verify()
+            .into_iter()
+            .map(|report| (report.file, report.status))
+            .collect();
+
+        assert_eq!(statuses.len(), 5);
+        assert_eq!(
+            statuses.first(),
+            Some(&("C_mft.bin".to_owned(), MemberStatus::Ok))
+        );
+        assert!(matches!(
+            statuses.get(1),
+            Some((_, MemberStatus::HashMismatch { .. }))
+        ));
+        assert_eq!(
+            statuses.get(2).map(|(_, status)| status),
+            Some(&MemberStatus::SizeMismatch {
+                expected: 6,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            statuses.get(3).map(|(_, status)| status),
+            Some(&MemberStatus::Missing)
+        );
+        assert_eq!(
+            statuses.get(4),
+            Some(&("stray.txt".to_owned(), MemberStatus::Unlisted))
+        );
+    }
+
+    #[test]
+    fn open_reassembles_split_parts_and_flags_gaps() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let compressed = make_bundle(&[("C_mft.bin", b"mft")], &[("C_mft.bin", b"mft")]);
+        let base = tmp.path().join("drive_c.tar.zst");
+        for (idx, part) in archive::split(&compressed, 16).iter().enumerate() {
+            std::fs::write(format!("{}.{idx:03}", base.display()), part).expect("write part");
+        }
+        let part_count = compressed.len().div_ceil(16);
+
+        let bundle = Bundle::open(&base).expect("reassembled from base name");
+        assert!(bundle.verify().iter().all(|report| !report.is_failure()));
+        assert_eq!(part_paths(&base).expect("parts").len(), part_count);
+
+        std::fs::remove_file(format!("{}.001", base.display())).expect("remove part");
+        let err = Bundle::open(&tmp.path().join("drive_c.tar.zst.000")).unwrap_err();
+        assert!(err.to_string().contains(".001"), "{err}");
+    }
+
+    #[test]
+    fn unpack_lays_out_drive_dir_and_refuses_overwrite() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let compressed = make_bundle(&[("C_mft.bin", b"mft")], &[("C_mft.bin", b"mft")]);
+        let bundle = Bundle::from_compressed(&compressed).expect("valid bundle");
+
+        let dir = bundle.unpack(tmp.path(), false).expect("unpack");
+        assert_eq!(dir, tmp.path().join("drive_c"));
+        assert_eq!(std::fs::read(dir.join("C_mft.bin")).expect("read"), b"mft");
+        assert_eq!(crate::discovery::discover_mft_files(tmp.path()), vec![
+            dir.join("C_mft.bin")
+        ]);
+
+        bundle.unpack(tmp.path(), false).unwrap_err();
+        bundle.unpack(tmp.path(), true).expect("forced unpack");
+    }
+
+    #[test]
+    fn unpack_refuses_a_bundle_that_changed_since_open() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("drive_c.tar.zst");
+        std::fs::write(
+            &path,
+            make_bundle(&[("C_mft.bin", b"mft")], &[("C_mft.bin", b"mft")]),
+        )
+        .expect("write bundle");
+        let bundle = Bundle::open(&path).expect("valid bundle");
+        assert_eq!(bundle.entry("C_mft.bin").map(|member| member.size), Some(3));
+
+        std::fs::write(
+            &path,
+            make_bundle(&[("C_mft.bin", b"mft")], &[("C_mft.bin", b"MFT")]),
+        )
+        .expect("rewrite bundle");
+        let data = tmp.path().join("data");
+        let err = bundle.unpack(&data, false).unwrap_err();
+        assert!(err.to_string().contains("changed"), "{err}");
+        // The intact manifest landed; the changed member left nothing.
+        let left: Vec<_> = std::fs::read_dir(data.join("drive_c"))
+            .expect("drive dir")
+            .map(|dirent| dirent.expect("dirent").file_name())
+            .collect();
+        assert_eq!(left, ["manifest.json"], "no partial file may be left");
+    }
+}
@@ -231,12 +231,14 @@ pub fn cache_dir() -> PathBuf {
     dir
 }
 
-/// Gets the cache file path for a specific drive.
+/// Gets the cache file path for a specific volume.
 ///
-/// Returns `{SECURE_CACHE_DIR}/{DRIVE}_index.uffs`.
+/// Returns `{SECURE_CACHE_DIR}/{STEM}_index.uffs`, where the stem is
+/// [`VolumeId::file_stem`](crate::platform::VolumeId::file_stem) — `C` for
+/// a local drive, `srv01@C` for a hosted capture.
 #[must_use]
-pub fn cache_file_path(drive: crate::platform::DriveLetter) -> PathBuf {
-    cache_dir().join(format!("{}_index.uffs", drive.as_char()))
+pub fn cache_file_path<V: Into<crate::platform::VolumeId>>(drive: V) -> PathBuf {
+    cache_dir().join(format!("{}_index.uffs", drive.into().file_stem()))
 }
 
 /// Checks if a cached index file exists and is fresh (within TTL).
@@ -250,7 +252,7 @@ pub fn cache_file_path(drive: crate::platform::DriveLetter) -> PathBuf {
 ///
 /// `true` if the cache file exists and was modified within the TTL window.
 #[must_use]
-pub fn is_cache_fresh(drive: crate::platform::DriveLetter, ttl_seconds: u64) -> bool {
+pub fn is_cache_fresh<V: Into<crate::platform::VolumeId>>(drive: V, ttl_seconds: u64) -> bool {
     let path = cache_file_path(drive);
     std::fs::metadata(&path).is_ok_and(|meta| {
         meta.modified().is_ok_and(|modified| {
@@ -266,7 +268,7 @@ pub fn is_cache_fresh(drive: crate::platform::DriveLetter, ttl_seconds: u64) ->
 ///
 /// Returns `None` if the file doesn't exist or age cannot be determined.
 #[must_use]
-pub fn cache_age_seconds(drive: crate::platform::DriveLetter) -> Option<u64> {
+pub fn cache_age_seconds<V: Into<crate::platform::VolumeId>>(drive: V) -> Option<u64> {
     let path = cache_file_path(drive);
     let meta = std::fs::metadata(&path).ok()?;
     let modified = meta.modified().ok()?;
@@ -287,17 +289,18 @@ pub fn cache_age_seconds(drive: crate::platform::DriveLetter) -> Option<u64> {
 ///
 /// `Some((index, header))` if cache is fresh, `None` otherwise.
 #[must_use]
-pub fn load_cached_index(
-    drive: crate::platform::DriveLetter,
+pub fn load_cached_index<V: Into<crate::platform::VolumeId>>(
+    drive: V,
     ttl_seconds: u64,
 ) -> Option<(MftIndex, IndexHeader)> {
-    if !is_cache_fresh(drive, ttl_seconds) {
+    let volume = drive.into();
+    if !is_cache_fresh(volume, ttl_seconds) {
         return None;
     }
 
-    let lock_path = cache_lock_path(drive);
+    let lock_path = cache_lock_path(volume);
     let result = with_file_lock(&lock_path, LockKind::Shared, CACHE_LOCK_TIMEOUT, || {
-        let path = cache_file_path(drive);
+        let path = cache_file_path(volume);
         match MftIndex::load_from_file(&path) {
             Ok((index, header)) => Ok(Some((index, header))),
             Err(_) => Ok(None),
@@ -307,7 +310,7 @@ pub fn load_cached_index(
     match result {
         Ok(Some((index, header))) => {
             // Verify the volume matches
-            (header.volume == drive).then_some((index, header))
+            (header.volume == volume.letter()).then_some((index, header))
         }
         _ => None,
     }
@@ -322,26 +325,27 @@ pub fn load_cached_index(
 /// # Errors
 ///
 /// Returns an error if directory creation, locking, or file writing fails.
-pub fn save_to_cache(
+pub fn save_to_cache<V: Into<crate::platform::VolumeId>>(
     index: &MftIndex,
-    drive: crate::platform::DriveLetter,
+    drive: V,
     volume_serial: u64,
     usn_journal_id: u64,
     next_usn: crate::usn::Usn,
 ) -> std::io::Result<PathBuf> {
+    let volume = drive.into();
     let dir = cache_dir();
     create_secure_dir(&dir)?;
 
-    let lock_path = cache_lock_path(drive);
+    let lock_path = cache_lock_path(volume);
     let result = with_file_lock(&lock_path, LockKind::Exclusive, CACHE_LOCK_TIMEOUT, || {
-        let path = cache_file_path(drive);
+        let path = cache_file_path(volume);
         index.save_to_file(&path, volume_serial, usn_journal_id, next_usn)?;
         Ok(path)
     });
 
     // Invalidate the companion compact cache so it gets rebuilt from the
     // fresh MftIndex on next access.
-    invalidate_compact_cache(drive);
+    invalidate_compact_cache(volume);
 
     result
 }
@@ -362,13 +366,14 @@ pub fn save_to_cache(
 /// Returns an error only if serialization or directory creation fails.
 /// Background compression/encryption/write errors are logged but not
 /// propagated (best-effort save).
-pub fn save_to_cache_background(
+pub fn save_to_cache_background<V: Into<crate::platform::VolumeId>>(
     index: &MftIndex,
-    drive: crate::platform::DriveLetter,
+    drive: V,
     volume_serial: u64,
     usn_journal_id: u64,
     next_usn: crate::usn::Usn,
 ) -> std::io::Result<()> {
+    let volume = drive.into();
     let profile = std::env::var_os("UFFS_CACHE_PROFILE").is_some();
 
     let dir = cache_dir();
@@ -393,19 +398,19 @@ pub fn save_to_cache_background(
     }
 
     // Invalidate compact cache before writing new MftIndex.
-    invalidate_compact_cache(drive);
+    invalidate_compact_cache(volume);
 
     // Spawn background thread for compress → encrypt → write.
-    let path = cache_file_path(drive);
+    let path = cache_file_path(volume);
     std::thread::Builder::new()
-        .name(format!("mft-save-{drive}"))
+        .name(format!("mft-save-{volume}"))
         .spawn(move || {
             if let Err(err) = compress_encrypt_write(
                 serialized, &path, 3, // ZSTD_LEVEL
                 profile, "mft",
             ) {
                 tracing::warn!(
-                    drive = %drive,
+                    drive = %volume,
                     error = %err,
                     "Background MFT cache save failed"
                 );
@@ -420,18 +425,19 @@ pub fn save_to_cache_background(
 ///
 /// Called automatically by [`save_to_cache`] to ensure the compact index
 /// is rebuilt from the updated `MftIndex`.
-fn invalidate_compact_cache(drive: crate::platform::DriveLetter) {
-    let compact_path = cache_dir().join(format!("{}_compact.uffs", drive.as_char()));
+fn invalidate_compact_cache<V: Into<crate::platform::VolumeId>>(drive: V) {
+    let volume = drive.into();
+    let compact_path = cache_dir().join(format!("{}_compact.uffs", volume.file_stem()));
     if compact_path.exists() {
         if let Err(err) = std::fs::remove_file(&compact_path) {
             tracing::warn!(
-                drive = %drive,
+                drive = %volume,
                 error = %err,
                 "⚠️ Failed to invalidate compact cache"
             );
         } else {
             tracing::debug!(
-                drive = %drive,
+                drive = %volume,
                 "🗑️ Compact cache invalidated (MftIndex updated)"
             );
         }
@@ -442,11 +448,12 @@ fn invalidate_compact_cache(drive: crate::platform::DriveLetter) {
 ///
 /// Overwrites the file with zeros before deleting. Does nothing if the
 /// file doesn't exist.
-pub fn remove_cached_index(drive: crate::platform::DriveLetter) {
-    let path = cache_file_path(drive);
+pub fn remove_cached_index<V: Into<crate::platform::VolumeId>>(drive: V) {
+    let volume = drive.into();
+    let path = cache_file_path(volume);
     let _rm_cache = secure_remove(&path);
     // Also clean up the lock file
-    let lock = cache_lock_path(drive);
+    let lock = cache_lock_path(volume);
     let _rm_lock = std::fs::remove_file(lock);
 }
 
@@ -573,8 +580,8 @@ const CACHE_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
 ///
 /// E.g. `{SECURE_CACHE_DIR}/C_index.lock`
 #[must_use]
-pub fn cache_lock_path(drive: crate::platform::DriveLetter) -> PathBuf {
-    cache_dir().join(format!("{}_index.lock", drive.as_char()))
+pub fn cache_lock_path<V: Into<crate::platform::VolumeId>>(drive: V) -> PathBuf {
+    cache_dir().join(format!("{}_index.lock", drive.into().file_stem()))
 }
 
 /// Result of a cache check operation.
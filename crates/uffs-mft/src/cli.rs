@@ -5,46 +5,11 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand, ValueEnum};
-
-/// Output format for the `info` and `drives` commands.
-///
-/// `Human` is the default rich view (unchanged behaviour). `Table` is a compact
-/// aligned table; `Json` is a machine-readable object for tooling (e.g. the
-/// benchmark report embeds `drives --format json`).
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
-pub(crate) enum OutputFormat {
-    /// Rich, human-readable view (the default).
-    #[default]
-    Human,
-    /// Compact aligned table.
-    Table,
-    /// Machine-readable JSON.
-    Json,
-}
+use clap::{Parser, Subcommand};
 
-/// NTFS metafile selectable via `uffs-mft metafile --kind`.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
-pub(crate) enum MetafileKind {
-    /// `$Boot` — volume boot record + BPB (geometry, volume serial).
-    Boot,
-    /// `$Bitmap` — volume cluster-allocation bitmap (free space).
-    Bitmap,
-    /// `$Secure:$SDS` — security-descriptor store (ACLs / owner).
-    Secure,
-    /// `$AttrDef` — NTFS attribute-type definitions.
-    AttrDef,
-    /// `$MFTMirr` — backup of the first four `$MFT` records.
-    MftMirr,
-    /// `$Volume` — the MFT record (volume name / version / flags).
-    Volume,
-    /// `$BadClus` — the MFT record (bad-cluster run list).
-    BadClus,
-    /// `$LogFile` — the NTFS metadata transaction log.
-    LogFile,
-    /// `$UsnJrnl:$J` — the change journal (resolved via `$Extend`).
-    UsnJrnl,
-}
+mod options;
+
+pub(crate) use options::{BundleAction, MetafileKind, OutputFormat};
 
 /// `uffs-mft`: Low-level NTFS MFT reading tool.
 #[derive(Parser)]
@@ -191,6 +156,51 @@ pub(crate) enum Commands {
         split_gib: u64,
     },
 
+    /// Verify, list or unpack a `capture --zip` bundle offline (reassembles
+    /// split `.tar.zst.NNN` parts). Cross-platform.
+    ///
+    /// # Examples
+    ///
+    /// ```text
+    /// uffs-mft bundle verify drive_c.tar.zst
+    /// uffs-mft bundle ls drive_c.tar.zst.000
+    /// uffs-mft bundle unpack drive_c.tar.zst --data-dir ~/uffs_data
+    /// ```
+    Bundle {
+        /// Bundle action.
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+
+    /// Rewrite a saved MFT so it can be shared in a bug report: every file,
+    /// stream, extended-attribute, reparse-target and volume name becomes a
+    /// keyed pseudonym and record slack is zeroed, while record layout, tree
+    /// shape, sizes and timestamps are kept. Cross-platform.
+    ///
+    /// # Examples
+    ///
+    /// ```text
+    /// uffs-mft anonymize --input C_mft.bin --output C_anon.bin
+    /// uffs-mft anonymize --input C_mft.bin --output C_anon.bin --key-file ~/.uffs/anon.key
+    /// ```
+    Anonymize {
+        /// Path to the saved MFT (any format `load` accepts)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output path for the anonymized MFT (same format as the input)
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// File holding the pseudonym key (a trailing newline is ignored);
+        /// without it the `UFFS_ANON_KEY` environment variable is used. Reusing
+        /// a key keeps pseudonyms stable across captures; a random key is used
+        /// when neither is set. There is deliberately no `--key <secret>`: a
+        /// key on the command line leaks into shell history and process lists.
+        #[arg(long, value_name = "PATH")]
+        key_file: Option<PathBuf>,
+    },
+
     /// Inspect a captured NTFS metafile offline (header + $Boot geometry, ...)
     MetafileInfo {
         /// Path to a captured metafile (from `metafile` or `capture`)
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `anonymize` command — strip names from a saved MFT before sharing it.
+//!
+//! Cross-platform: loads any capture `load` accepts, rewrites every name with
+//! [`uffs_mft::anonymize::Anonymizer`], and saves the result in the input's
+//! format (compressed UFFS-MFT stays compressed, a raw dump stays raw). The
+//! output reproduces tree shape, sizes and timestamps, so it can be attached
+//! to a bug report and loaded with `--mft-file` on the maintainer's side.
+//!
+//! The pseudonym key comes from `--key-file` or [`KEY_ENV_VAR`], never from
+//! the command line, where it would be visible to other users of the machine.
+#![expect(
+    clippy::print_stdout,
+    reason = "intentional user-facing CLI anonymize summary"
+)]
+
+use std::path::Path;
+
+use anyhow::{Context as _, Result, bail};
+use uffs_mft::anonymize::Anonymizer;
+use uffs_mft::raw::{LoadRawOptions, SaveRawOptions, load_raw_mft, save_raw_mft};
+
+/// Environment variable holding the pseudonym key when no `--key-file` is
+/// given.
+const KEY_ENV_VAR: &str = "UFFS_ANON_KEY";
+
+/// Anonymize `input` into `output`, keyed by the contents of `key_file`, else
+/// [`KEY_ENV_VAR`], else a random key.
+///
+/// # Errors
+///
+/// Returns an error if the key file cannot be read or is empty, the input
+/// cannot be loaded, or the output cannot be written.
+pub(crate) fn cmd_anonymize(input: &Path, output: &Path, key_file: Option<&Path>) -> Result<()> {
+    let key = match key_file {
+        Some(path) => Some(read_key_file(path)?),
+        None => std::env::var_os(KEY_ENV_VAR)
+            .filter(|value| !value.is_empty())
+            .map(std::ffi::OsString::into_encoded_bytes),
+    };
+    let mut raw = load_raw_mft(input, &LoadRawOptions::default())
+        .with_context(|| format!("loading {}", input.display()))?;
+    let header = raw.header;
+
+    let anonymizer = key.as_deref().map_or_else(
+        || Anonymizer::new(&rand::random::<[u8; 32]>()),
+        Anonymizer::new,
+    );
+    let record_size = uffs_mft::u32_as_usize(header.record_size);
+    let stats = anonymizer.anonymize_mft(&mut raw.data, record_size);
+
+    let options = SaveRawOptions {
+        compress: header.is_compressed(),
+        volume_letter: header.volume_letter,
+        raw_compat: header.version == 0,
+        reserved_allocated_bytes: header.reserved_allocated_bytes,
+        ..SaveRawOptions::default()
+    };
+    save_raw_mft(output, &raw.data, header.record_size, &options)
+        .with_context(|| format!("writing {}", output.display()))?;
+
+    println!("✅ Anonymized MFT");
+    println!("  Input:   {}", input.display());
+    println!(
+        "  Output:  {} ({} records × {} B)",
+        output.display(),
+        header.record_count,
+        header.record_size
+    );
+    println!(
+        "  Records: {} visited, {} rewritten, {} torn (kept torn), {} with slack zeroed",
+        stats.records, stats.rewritten, stats.torn_records, stats.slack_scrubbed
+    );
+    println!(
+        "  Names:   {} file names, {} index keys, {} stream names, {} reparse targets, {} EA names, {} volume labels",
+        stats.file_names,
+        stats.index_entries,
+        stats.stream_names,
+        stats.reparse_targets,
+        stats.ea_names,
+        stats.volume_labels
+    );
+    if key.is_none() {
+        println!(
+            "  Key:     random (use --key-file or {KEY_ENV_VAR} to keep pseudonyms stable across captures)"
+        );
+    }
+    Ok(())
+}
+
+/// The key stored in `path`, without the trailing newline an editor or
+/// `echo` leaves behind.
+fn read_key_file(path: &Path) -> Result<Vec<u8>> {
+    let mut key =
+        std::fs::read(path).with_context(|| format!("reading key file {}", path.display()))?;
+    while key
+        .last()
+        .is_some_and(|&byte| byte == b'\n' || byte == b'\r')
+    {
+        key.pop();
+    }
+    if key.is_empty() {
+        bail!("key file {} is empty", path.display());
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_key_file;
+
+    #[test]
+    fn key_file_drops_the_trailing_newline_only() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("anon.key");
+        std::fs::write(&path, b" team secret \r\n").expect("write key");
+        assert_eq!(read_key_file(&path).expect("key"), b" team secret ");
+
+        std::fs::write(&path, b"\n").expect("write key");
+        let err = read_key_file(&path).expect_err("empty key");
+        assert!(err.to_string().contains("is empty"), "{err}");
+    }
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! `bundle` command — offline handling of `capture --zip` archives.
+//!
+//! Cross-platform: the transfer-target side of the capture flow. `ls` lists a
+//! bundle's members, `verify` reassembles split parts and checks every member
+//! against the manifest SHA-256, and `unpack` verifies and then lays the
+//! members out as `<data-dir>/drive_<x>/`, ready for `--data-dir` discovery.
+//! `verify` and `unpack` exit non-zero on any missing or corrupt member, so
+//! they drop into transfer scripts.
+#![expect(
+    clippy::print_stdout,
+    reason = "intentional user-facing CLI bundle report"
+)]
+
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use uffs_mft::bundle::{Bundle, MemberReport, MemberStatus};
+
+use crate::cli::BundleAction;
+
+/// Open a bundle (any accepted part form), with the path in the error context.
+fn open(input: &Path) -> Result<Bundle> {
+    Bundle::open(input).with_context(|| format!("opening bundle {}", input.display()))
+}
+
+/// Print the bundle header shared by every action.
+fn print_header(input: &Path, bundle: &Bundle) {
+    let manifest = &bundle.manifest;
+    println!("═══ UFFS bundle — drive {}: ═══", manifest.drive);
+    println!("  Bundle:      {}", input.display());
+    println!(
+        "  Captured:    {} (uffs-mft {}, schema {})",
+        manifest.captured_at, manifest.tool_version, manifest.schema
+    );
+}
+
+/// Print one verification line.
+fn print_member(report: &MemberReport) {
+    let detail = match &report.status {
+        MemberStatus::Ok => "ok".to_owned(),
+        MemberStatus::Missing => "MISSING from archive".to_owned(),
+        MemberStatus::SizeMismatch { expected, actual } => {
+            format!("SIZE MISMATCH (manifest {expected}, archive {actual})")
+        }
+        MemberStatus::HashMismatch { expected, actual } => {
+            format!("SHA-256 MISMATCH (manifest {expected}, archive {actual})")
+        }
+        MemberStatus::Unlisted => "not in manifest".to_owned(),
+    };
+    let glyph = match report.status {
+        MemberStatus::Ok => "✅",
+        MemberStatus::Unlisted => "⚠️ ",
+        MemberStatus::Missing
+        | MemberStatus::SizeMismatch { .. }
+        | MemberStatus::HashMismatch { .. } => "❌",
+    };
+    println!("  {glyph} {:<9} {:<24} {detail}", report.kind, report.file);
+}
+
+/// Verify the bundle, print per-member results, and fail on any missing or
+/// corrupt member.
+fn verify(input: &Path, bundle: &Bundle) -> Result<()> {
+    let reports = bundle.verify();
+    for report in &reports {
+        print_member(report);
+    }
+    let failures = reports.iter().filter(|report| report.is_failure()).count();
+    if failures == 0 {
+        println!(
+            "  ✅ OK — {} manifest member(s) verified.",
+            bundle.manifest.artifacts.len()
+        );
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "bundle {} failed verification: {failures} missing or corrupt member(s)",
+            input.display()
+        )
+    }
+}
+
+/// `bundle` command — dispatch `ls` / `verify` / `unpack`.
+///
+/// # Errors
+///
+/// Returns an error if the bundle cannot be reassembled or decoded, any
+/// manifest member is missing or corrupt (`verify` / `unpack`), or the
+/// unpack target cannot be written.
+pub(crate) fn cmd_bundle(action: &BundleAction) -> Result<()> {
+    match action {
+        BundleAction::Ls { input } => {
+            let bundle = open(input)?;
+            print_header(input, &bundle);
+            for entry in &bundle.entries {
+                println!("  {:>14}  {}", entry.size, entry.name);
+            }
+            println!("  {} member(s).", bundle.entries.len());
+            Ok(())
+        }
+        BundleAction::Verify { input } => {
+            let bundle = open(input)?;
+            print_header(input, &bundle);
+            verify(input, &bundle)
+        }
+        BundleAction::Unpack {
+            input,
+            data_dir,
+            force,
+        } => {
+            let bundle = open(input)?;
+            print_header(input, &bundle);
+            verify(input, &bundle)?;
+            let dir = bundle
+                .unpack(data_dir, *force)
+                .with_context(|| format!("unpacking into {}", data_dir.display()))?;
+            println!(
+                "  📂 unpacked {} member(s) → {}",
+                bundle.entries.len(),
+                dir.display()
+            );
+            println!("     load with: --data-dir {}", data_dir.display());
+            Ok(())
+        }
+    }
+}
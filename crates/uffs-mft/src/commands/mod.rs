@@ -7,6 +7,8 @@ use anyhow::Result;
 
 use crate::cli::Commands;
 
+mod anonymize;
+mod bundle;
 mod extract_mft;
 mod load;
 mod metafile_info;
@@ -53,6 +55,12 @@ pub(crate) async fn dispatch_command(command: Commands) -> Result<()> {
             zip,
             split_gib,
         } => windows::cmd_capture(drive, &out, all_drives, zip, split_gib).await,
+        Commands::Bundle { action } => bundle::cmd_bundle(&action),
+        Commands::Anonymize {
+            input,
+            output,
+            key_file,
+        } => anonymize::cmd_anonymize(&input, &output, key_file.as_deref()),
         Commands::MetafileInfo { input } => metafile_info::cmd_metafile_info(&input),
         Commands::ExtractMft { input, output } => extract_mft::cmd_extract_mft(&input, &output),
         Commands::Verify {
@@ -185,7 +193,8 @@ pub(crate) async fn dispatch_command(command: Commands) -> Result<()> {
 
 /// Command dispatcher for non-Windows platforms (limited functionality).
 ///
-/// Only the `load` command works on non-Windows platforms.
+/// Only the offline commands (`load`, `bundle`, `verify`, ...) work on
+/// non-Windows platforms.
 #[cfg(not(windows))]
 #[expect(
     clippy::unused_async,
@@ -215,6 +224,12 @@ pub(crate) async fn dispatch_command(command: Commands) -> Result<()> {
             forensic,
         ),
         Commands::Sysinfo { out, json } => sysinfo::run(out.as_deref(), json),
+        Commands::Bundle { action } => bundle::cmd_bundle(&action),
+        Commands::Anonymize {
+            input,
+            output,
+            key_file,
+        } => anonymize::cmd_anonymize(&input, &output, key_file.as_deref()),
         Commands::MetafileInfo { input } => metafile_info::cmd_metafile_info(&input),
         Commands::ExtractMft { input, output } => extract_mft::cmd_extract_mft(&input, &output),
         Commands::Verify {
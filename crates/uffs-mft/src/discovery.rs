@@ -15,10 +15,18 @@
 //! │   └── C_mft.iocp
 //! ├── drive_d/
 //! │   └── D_mft.bin
-//! └── drive_e/
-//!     └── E.mft
+//! ├── drive_e/
+//! │   └── E.mft
+//! └── srv01/            ← captures from another machine
+//!     └── drive_c/
+//!         └── C_mft.bin
 //! ```
 //!
+//! Host directories are picked up by [`discover_host_captures`]; their
+//! volumes load as host-qualified [`VolumeId`]s (`srv01\C`), qualified by
+//! the volume serial (`srv01\C#1A2B-3C4D`) when the capture directory also
+//! holds a `$Boot` capture (`c_boot.bin`, as an unpacked bundle does).
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -30,13 +38,17 @@
 //! assert!(!files.is_empty());
 //! ```
 
+use std::io::Read as _;
 use std::path::{Path, PathBuf};
 
+use crate::platform::metafile::{HEADER_SIZE, MetafileHeader};
+use crate::platform::{DriveLetter, HostLabel, VolumeId, VolumeSerial};
+
 /// Scan a data directory for MFT files in `drive_*` subdirectories.
 ///
 /// Looks for subdirectories named `drive_c`, `drive_d`, etc. (single ASCII
 /// letter after `drive_`). Within each, selects the best MFT file by format
-/// priority: `.iocp` > `.bin` > `.mft`.
+/// priority: `.iocp` > `*_mft.bin` > `.bin` > `.mft`.
 ///
 /// Returns a sorted list of discovered MFT file paths.
 #[must_use]
@@ -71,17 +83,97 @@ pub fn discover_mft_files(data_dir: &Path) -> Vec<PathBuf> {
     mft_files
 }
 
+/// Scan a data directory for captures taken on other machines.
+///
+/// Any subdirectory that is not itself a `drive_*` directory and whose
+/// name is a valid [`HostLabel`] is treated as a host: its `drive_*`
+/// subdirectories are resolved exactly like [`discover_mft_files`] does
+/// for the top level.  The letter comes from the `drive_<x>` directory
+/// name, not the file name, so `srv01/drive_d/mft.iocp` is `srv01\D`; the
+/// serial comes from a `*_boot.bin` capture beside it, when there is one.
+///
+/// Returns `(volume, path)` pairs sorted by volume.
+#[must_use]
+pub fn discover_host_captures(data_dir: &Path) -> Vec<(VolumeId, PathBuf)> {
+    let mut captures = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(data_dir) else {
+        return captures;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|os_name| os_name.to_str()) else {
+            continue;
+        };
+        if name.starts_with("drive_") {
+            continue;
+        }
+        let Ok(host) = HostLabel::register(name) else {
+            continue;
+        };
+        for mft_path in discover_mft_files(&path) {
+            if let Some(letter) = drive_dir_letter(&mft_path) {
+                let volume = VolumeId::hosted(host, letter).with_serial(sibling_serial(&mft_path));
+                captures.push((volume, mft_path));
+            }
+        }
+    }
+
+    captures.sort();
+    captures
+}
+
+/// Letter of the `drive_<x>` directory holding `mft_path`.
+fn drive_dir_letter(mft_path: &Path) -> Option<DriveLetter> {
+    mft_path
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("drive_"))
+        .and_then(|suffix| suffix.chars().next())
+        .and_then(|ch| DriveLetter::parse(ch).ok())
+}
+
+/// Volume serial recorded in the header of a `*_boot.bin` metafile capture
+/// in the same directory as `mft_path`.
+fn sibling_serial(mft_path: &Path) -> Option<VolumeSerial> {
+    let files = std::fs::read_dir(mft_path.parent()?).ok()?;
+    files
+        .flatten()
+        .map(|file| file.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.to_ascii_lowercase().ends_with("_boot.bin"))
+        })
+        .find_map(|path| {
+            let mut bytes = [0_u8; HEADER_SIZE];
+            std::fs::File::open(&path)
+                .and_then(|mut file| file.read_exact(&mut bytes))
+                .ok()?;
+            let header = MetafileHeader::from_bytes(&bytes).ok()?;
+            (header.volume_serial != 0).then(|| VolumeSerial::from_boot(header.volume_serial))
+        })
+}
+
 /// Find the best MFT file in a directory by format priority.
 ///
 /// Prefers `.iocp` (IOCP capture) over `.bin` (raw MFT) over `.mft`
-/// (legacy format). Returns `None` if no recognised MFT file is found.
+/// (legacy format). Among `.bin` files, the `capture` naming `<X>_mft.bin`
+/// wins, so an unpacked capture bundle (which also holds `c_boot.bin`,
+/// `c_bitmap.bin`, ...) resolves to its `$MFT`. Returns `None` if no
+/// recognised MFT file is found.
 #[must_use]
 pub fn find_best_mft_file(dir: &Path) -> Option<PathBuf> {
     let Ok(files) = std::fs::read_dir(dir) else {
         return None;
     };
 
-    let mut best: Option<(PathBuf, u8)> = None; // (path, priority: 0=iocp, 1=bin, 2=mft)
+    let mut best: Option<(PathBuf, u8)> = None; // (path, priority: 0=iocp, 1=_mft.bin, 2=bin, 3=mft)
 
     for file in files.flatten() {
         let file_path = file.path();
@@ -91,10 +183,15 @@ pub fn find_best_mft_file(dir: &Path) -> Option<PathBuf> {
         let Some(ext) = file_path.extension().and_then(|ext_os| ext_os.to_str()) else {
             continue;
         };
+        let is_capture_mft = file_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.to_ascii_lowercase().ends_with("_mft"));
         let priority = match ext {
             "iocp" => 0_u8, // best
-            "bin" => 1,
-            "mft" => 2,
+            "bin" if is_capture_mft => 1,
+            "bin" => 2,
+            "mft" => 3,
             _ => continue,
         };
         if best.as_ref().is_none_or(|(_, bp)| priority < *bp) {
@@ -124,6 +221,16 @@ mod tests {
         assert_eq!(best.extension().unwrap(), "iocp");
     }
 
+    #[test]
+    fn find_best_mft_file_prefers_capture_mft_over_metafiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in ["c_bitmap.bin", "c_boot.bin", "C_mft.bin", "c_volume.bin"] {
+            std::fs::write(tmp.path().join(name), b"").unwrap();
+        }
+        let best = find_best_mft_file(tmp.path()).unwrap();
+        assert_eq!(best.file_name().unwrap(), "C_mft.bin");
+    }
+
     #[test]
     fn discover_mft_files_finds_drive_subdirs() {
         let tmp = tempfile::tempdir().unwrap();
@@ -152,4 +259,53 @@ mod tests {
         let files = discover_mft_files(tmp.path());
         assert_eq!(files, Vec::<PathBuf>::new());
     }
+
+    #[test]
+    fn discover_host_captures_qualifies_volumes_by_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        for (dir, file) in [
+            ("drive_c", "C.iocp"),
+            ("srv01/drive_c", "C_mft.bin"),
+            ("srv01/drive_d", "mft.iocp"),
+            ("WS-0042/drive_c", "C.iocp"),
+            ("not a host/drive_c", "C.iocp"),
+        ] {
+            let sub = tmp.path().join(dir);
+            std::fs::create_dir_all(&sub).unwrap();
+            std::fs::write(sub.join(file), b"").unwrap();
+        }
+
+        let volumes: Vec<String> = discover_host_captures(tmp.path())
+            .into_iter()
+            .map(|(volume, _)| volume.to_string())
+            .collect();
+        assert_eq!(volumes, ["srv01\\C", "srv01\\D", "ws-0042\\C"]);
+        // The top-level local capture is still `discover_mft_files`' job.
+        assert_eq!(discover_mft_files(tmp.path()).len(), 1);
+    }
+
+    #[test]
+    fn discover_host_captures_reads_the_serial_from_a_boot_capture() {
+        use crate::platform::metafile::{MetafileKind, save_metafile_to_file};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let sub = tmp.path().join("srv01/drive_c");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(sub.join("C_mft.bin"), b"").unwrap();
+        let header = MetafileHeader {
+            kind: MetafileKind::Boot,
+            drive: DriveLetter::C,
+            volume_serial: 0x1122_3344_1A2B_3C4D,
+            timestamp: 0,
+            data_size: 0,
+        };
+        save_metafile_to_file(&sub.join("c_boot.bin"), &header, &[]).unwrap();
+
+        let captures = discover_host_captures(tmp.path());
+        let [(volume, path)] = captures.as_slice() else {
+            panic!("one capture expected, got {captures:?}");
+        };
+        assert_eq!(volume.to_string(), "srv01\\C#1A2B-3C4D");
+        assert_eq!(path.file_name().unwrap(), "C_mft.bin");
+    }
 }
@@ -174,10 +174,6 @@ use clap as _;
 #[cfg(test)]
 use criterion as _;
 use dirs_next as _;
-#[cfg(test)]
-use hex as _;
-#[cfg(windows)]
-use hex as _;
 use hostname as _;
 use indicatif as _;
 #[cfg(test)]
@@ -192,14 +188,6 @@ use rayon as _;
 // FxHash for fast hashing (used in io.rs on Windows)
 #[cfg(not(windows))]
 use rustc_hash as _;
-// `serde_json` powers `--format json` for the Windows-only `info` / `drives`
-// commands (src/commands/windows/info.rs); silence the library's view of it.
-#[cfg(windows)]
-use serde_json as _;
-// `sha2` + `hex` power the Windows-only `capture` command's manifest / SHA256SUMS
-// (src/commands/windows/capture.rs) and the chaos tests; silence the library's view.
-#[cfg(any(test, windows))]
-use sha2 as _;
 use smallvec as _;
 #[cfg(test)]
 use tempfile as _;
@@ -261,6 +249,12 @@ pub mod cache;
 /// Minimal `ustar` archive writer + byte splitter for capture bundles.
 pub mod archive;
 
+/// Offline reassemble / verify / unpack for `capture` bundles.
+pub mod bundle;
+
+/// Keyed-pseudonym rewrite of MFT captures for shareable bug reports.
+pub mod anonymize;
+
 /// Canonical CSV parity comparison for the capture verification flow.
 pub mod parity;
 
@@ -324,6 +318,7 @@ pub use ntfs::{
     ReparseMountPointBuffer, ReparsePointHeader, ReparseTag, ResidentAttributeData,
     StandardInformation, StreamInfo, apply_usa_fixup, data_runs_iter_from_attribute,
     extract_data_runs_from_attribute, fixup_file_record, parse_data_runs, parse_data_runs_iter,
+    protect_usa,
 };
 // Caller's effective uid (Unix-only) — daemon-management uses it to decide
 // whether managing the *running* daemon needs elevation (owner comparison).
@@ -52,7 +52,10 @@ use crossbeam_channel as _;
 // the binary's logging now routes through `uffs_security::log_dir`, so
 // acknowledge the dep here to keep `unused-crate-dependencies` quiet.
 use dirs_next as _;
-#[cfg(test)]
+// `hex`, `serde_json` and `sha2` back the library's capture-bundle manifest
+// (`uffs_mft::bundle`); the binary only names them in the Windows `capture`
+// and `info` commands, so acknowledge them here for the other builds.
+#[cfg(not(windows))]
 use hex as _;
 // Platform-gated dependencies (used on Windows only)
 #[cfg(not(windows))]
@@ -72,7 +75,9 @@ use rustc_hash as _;
 // the binary doesn't reference it directly.  Acknowledge to keep
 // `unused-crate-dependencies` quiet.
 use serde as _;
-#[cfg(test)]
+#[cfg(not(windows))]
+use serde_json as _;
+#[cfg(not(windows))]
 use sha2 as _;
 // SmallVec for path chain building (used in index.rs PathResolver)
 use smallvec as _;
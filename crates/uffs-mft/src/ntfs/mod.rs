@@ -60,7 +60,7 @@ pub(crate) use self::records::SECTOR_SIZE_U64;
 pub use self::records::{
     AttributeIterator, AttributeRecordHeader, AttributeRef, AttributeType, FileRecordSegmentHeader,
     MultiSectorHeader, NonResidentAttributeData, ResidentAttributeData, SECTOR_SIZE,
-    apply_usa_fixup, fixup_file_record,
+    apply_usa_fixup, fixup_file_record, protect_usa,
 };
 
 /// Extracts the File Record Segment number from a file reference.
@@ -151,6 +151,40 @@ pub fn apply_usa_fixup(buffer: &mut [u8], usa_offset: u16, usa_count: u16) -> bo
     result
 }
 
+/// Re-applies Update Sequence Array protection to a fixed-up record buffer —
+/// the inverse of [`apply_usa_fixup`].
+///
+/// For each sector, the current last 2 bytes are saved into the USA and
+/// replaced with the check value already stored at `usa_offset`. Writers that
+/// edit a record in memory (anonymizer, synthetic generator) call this before
+/// emitting it so readers' fixup validation succeeds on the new contents.
+///
+/// Returns `false` if the USA or a sector end lies outside `buffer`.
+#[must_use]
+pub fn protect_usa(buffer: &mut [u8], usa_offset: u16, usa_count: u16) -> bool {
+    let usa_offset_usize = usize::from(usa_offset);
+    let Some(check_value) = read_u16_le(buffer, usa_offset_usize) else {
+        return false;
+    };
+
+    for sector_idx in 1..usize::from(usa_count) {
+        let sector_end_offset = sector_idx * SECTOR_SIZE - 2;
+        let usa_entry_offset = usa_offset_usize + sector_idx * 2;
+        let Some(original) = read_u16_le(buffer, sector_end_offset) else {
+            return false;
+        };
+        let Some(usa_entry) = buffer.get_mut(usa_entry_offset..usa_entry_offset + 2) else {
+            return false;
+        };
+        usa_entry.copy_from_slice(&original.to_le_bytes());
+        if let Some(sector_end) = buffer.get_mut(sector_end_offset..sector_end_offset + 2) {
+            sector_end.copy_from_slice(&check_value.to_le_bytes());
+        }
+    }
+
+    true
+}
+
 /// Applies USA fixup to a file record buffer in-place.
 #[must_use]
 pub fn fixup_file_record(buffer: &mut [u8]) -> bool {
@@ -109,6 +109,37 @@ fn fixup_file_record_applies_usa_from_safe_header_decode() {
     );
 }
 
+#[test]
+fn protect_usa_round_trips_through_fixup() {
+    let mut record = vec![0_u8; 1024];
+    let usa_offset = 0x30;
+    record[0..4].copy_from_slice(b"FILE");
+    write_u16_le(&mut record, 4, crate::len_to_u16(usa_offset));
+    write_u16_le(&mut record, 6, 3);
+    write_u16_le(&mut record, usa_offset, 0x0042);
+    write_u16_le(&mut record, SECTOR_SIZE - 2, 0x1111);
+    write_u16_le(&mut record, SECTOR_SIZE * 2 - 2, 0x2222);
+    assert!(protect_usa(&mut record, 0x30, 3));
+    assert_eq!(
+        &record[SECTOR_SIZE - 2..SECTOR_SIZE],
+        &0x0042_u16.to_le_bytes()
+    );
+    assert_eq!(
+        &record[usa_offset + 2..usa_offset + 4],
+        &0x1111_u16.to_le_bytes()
+    );
+
+    assert!(fixup_file_record(&mut record));
+    assert_eq!(
+        &record[SECTOR_SIZE - 2..SECTOR_SIZE],
+        &0x1111_u16.to_le_bytes()
+    );
+    assert_eq!(
+        &record[SECTOR_SIZE * 2 - 2..SECTOR_SIZE * 2],
+        &0x2222_u16.to_le_bytes()
+    );
+}
+
 #[test]
 fn attribute_iterator_reads_resident_attribute_value() {
     let mut record = vec![0_u8; 96];
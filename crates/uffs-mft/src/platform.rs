@@ -55,6 +55,9 @@ mod system;
 pub mod upcase;
 #[cfg(windows)]
 mod volume;
+/// Volume identity beyond drive letters — host label + letter, for
+/// daemons serving captures from many machines.
+pub mod volume_id;
 
 pub use bitmap::MftBitmap;
 pub use drive_letter::{DriveLetter, DriveLetterError};
@@ -114,6 +117,7 @@ pub(crate) use volume::{
 // must be at least as public as the former.
 #[cfg(windows)]
 pub use volume::{NtfsVolumeData, VolumeHandle, register_broker_handle};
+pub use volume_id::{HostLabel, PathTemplate, VolumeId, VolumeIdError, VolumeSerial};
 
 /// Non-Windows: there is no Access Broker, so no adopted handles.
 #[cfg(not(windows))]
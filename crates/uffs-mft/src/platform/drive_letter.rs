@@ -199,6 +199,17 @@ impl DriveLetter {
     }
 }
 
+impl DriveLetter {
+    /// The letter as a one-character `&'static str` (`"C"`), for string
+    /// building without a `char` → `String` round trip.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let index = self.alphabet_index();
+        LETTERS.get(index..=index).unwrap_or("?")
+    }
+}
+
 impl fmt::Debug for DriveLetter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Matches the existing `tracing` log convention (`letter='C'`
@@ -33,7 +33,7 @@ const METAFILE_MAGIC: &[u8; 8] = b"UFFSMETA";
 const METAFILE_VERSION: u32 = 1;
 
 /// Fixed header size in bytes (payload starts at this offset).
-const HEADER_SIZE: usize = 64;
+pub const HEADER_SIZE: usize = 64;
 
 /// An NTFS metafile that can be captured.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,774 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Volume identity for multi-machine (offline estate) indexes.
+//!
+//! A [`DriveLetter`] alone identifies a volume only on the machine that
+//! mounted it: two servers' `C:` captures collide, and a daemon tops out
+//! at 26 shards.  [`VolumeId`] pairs the letter with an optional
+//! [`HostLabel`] naming the machine a capture came from, and optionally
+//! the [`VolumeSerial`] of the volume itself:
+//!
+//! | Form                  | Meaning                                     |
+//! |-----------------------|---------------------------------------------|
+//! | `C`                   | the daemon's own `C:` (live or captured)    |
+//! | `srv01\C`             | `C:` captured on host `srv01`               |
+//! | `srv01\C#1A2B-3C4D`   | the volume with serial `1A2B-3C4D` that was |
+//! |                       | mounted at `C:` on `srv01`                  |
+//!
+//! The serial is what survives a letter reassignment or a disk swap: two
+//! captures of `srv01\C` taken from different disks are different volumes.
+//!
+//! The string form is the wire / config / cache-key representation.  A
+//! local id serialises exactly like the [`DriveLetter`] it wraps (`"C"`),
+//! so protocol payloads from single-machine daemons are unchanged.
+//!
+//! # Selectors
+//!
+//! Search filters use [`VolumeId::selects`]: a bare letter selects that
+//! letter on *every* host (`--drives C` searches each machine's system
+//! volume), a host-qualified id selects that letter on one host, and a
+//! serial-qualified id selects exactly one volume.
+//!
+//! # Display roots
+//!
+//! Paths of local volumes resolve below `C:\`.  For hosted volumes the
+//! installed [`PathTemplate`] renders the root instead (`srv01\C:\…` by
+//! default, or e.g. `/evidence/srv01/C/…`), see [`VolumeId::display_root`].
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use super::{DriveLetter, DriveLetterError};
+
+/// Longest accepted host label (one DNS label).
+pub const MAX_HOST_LABEL_LEN: usize = 63;
+
+/// Interned host labels.  Labels are leaked once so [`HostLabel`] can be
+/// `Copy`; an estate has tens to hundreds of hosts, so the table stays tiny
+/// — as long as untrusted input cannot add to it, see [`HostLabel::seal`].
+static HOST_LABELS: OnceLock<Mutex<BTreeSet<&'static str>>> = OnceLock::new();
+
+/// Set by [`HostLabel::seal`]: [`HostLabel::parse`] no longer interns.
+static HOST_LABELS_SEALED: AtomicBool = AtomicBool::new(false);
+
+/// A validated, case-folded machine label (`srv01`, `ws-0042`, …).
+///
+/// `Copy` and pointer-sized: the label text is interned on first parse, so
+/// shards, rows and registry keys carry it without allocation.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HostLabel(&'static str);
+
+/// Error returned when a host label or volume id fails validation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum VolumeIdError {
+    /// The host label is empty, too long or contains characters outside
+    /// `[A-Za-z0-9._-]`.
+    Host(String),
+    /// The volume part is not a drive letter.
+    Letter(DriveLetterError),
+    /// The path template lacks a `{letter}` placeholder.
+    Template(String),
+    /// The volume serial is not `XXXX-XXXX` or eight hex digits.
+    Serial(String),
+    /// A well-formed host label this process has no volume for (the
+    /// label table is sealed, see [`HostLabel::seal`]).
+    UnknownHost(String),
+}
+
+impl fmt::Display for VolumeIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Host(host) => write!(
+                f,
+                "host label must be 1..={MAX_HOST_LABEL_LEN} characters of [A-Za-z0-9._-]; got \
+                 '{}'",
+                host.escape_default()
+            ),
+            Self::Letter(err) => fmt::Display::fmt(err, f),
+            Self::Template(template) => write!(
+                f,
+                "path template must contain '{{letter}}'; got '{}'",
+                template.escape_default()
+            ),
+            Self::Serial(serial) => write!(
+                f,
+                "volume serial must be XXXX-XXXX or eight hex digits; got '{}'",
+                serial.escape_default()
+            ),
+            Self::UnknownHost(host) => {
+                write!(
+                    f,
+                    "no such volume: host '{}' is not loaded",
+                    host.escape_default()
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for VolumeIdError {}
+
+impl From<DriveLetterError> for VolumeIdError {
+    fn from(err: DriveLetterError) -> Self {
+        Self::Letter(err)
+    }
+}
+
+impl HostLabel {
+    /// Validate `label` (ASCII case-folded to lowercase) and intern it —
+    /// or, once the table is [sealed](Self::seal), only look it up.
+    ///
+    /// # Errors
+    ///
+    /// [`VolumeIdError::Host`] when `label` is empty, longer than
+    /// [`MAX_HOST_LABEL_LEN`] or contains characters other than ASCII
+    /// letters, digits, `.`, `_` and `-`; [`VolumeIdError::UnknownHost`]
+    /// for a label never registered before the table was sealed.
+    pub fn parse(label: &str) -> Result<Self, VolumeIdError> {
+        Self::intern(label, !HOST_LABELS_SEALED.load(Ordering::Acquire))
+    }
+
+    /// Validate and intern `label` even when the table is sealed.  For
+    /// the hosts a process actually loads (`<data-dir>/<host>/` captures),
+    /// never for labels taken from requests.
+    ///
+    /// # Errors
+    ///
+    /// [`VolumeIdError::Host`] as for [`Self::parse`].
+    pub fn register(label: &str) -> Result<Self, VolumeIdError> {
+        Self::intern(label, true)
+    }
+
+    /// Stop [`Self::parse`] from interning: from now on it only finds
+    /// labels already registered, and rejects any other with
+    /// [`VolumeIdError::UnknownHost`].  A long-running server calls this
+    /// once its volumes are discovered, so client input — request
+    /// selectors, cursors — cannot grow the leaked table.
+    pub fn seal() {
+        HOST_LABELS_SEALED.store(true, Ordering::Release);
+    }
+
+    /// Validate `label`, then look it up, interning it when `insert`.
+    fn intern(label: &str, insert: bool) -> Result<Self, VolumeIdError> {
+        let valid = (1..=MAX_HOST_LABEL_LEN).contains(&label.len())
+            && label
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_' | b'-'));
+        if !valid {
+            return Err(VolumeIdError::Host(label.to_owned()));
+        }
+        let folded = label.to_ascii_lowercase();
+        let table = HOST_LABELS.get_or_init(|| Mutex::new(BTreeSet::new()));
+        let mut guard = table
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(&interned) = guard.get(folded.as_str()) {
+            return Ok(Self(interned));
+        }
+        if !insert {
+            return Err(VolumeIdError::UnknownHost(folded));
+        }
+        let interned: &'static str = Box::leak(folded.into_boxed_str());
+        guard.insert(interned);
+        drop(guard);
+        Ok(Self(interned))
+    }
+
+    /// The label text.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Debug for HostLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HostLabel({})", self.0)
+    }
+}
+
+impl fmt::Display for HostLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl core::str::FromStr for HostLabel {
+    type Err = VolumeIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The 32-bit NTFS volume serial as Windows reports it (`vol C:` prints
+/// `1A2B-3C4D`): the low half of the boot sector's 64-bit serial.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VolumeSerial(u32);
+
+impl VolumeSerial {
+    /// The serial Windows reports for a volume whose boot sector holds
+    /// `boot_serial`.
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Windows reports the low 32 bits of the serial"
+    )]
+    pub const fn from_boot(boot_serial: u64) -> Self {
+        Self(boot_serial as u32)
+    }
+
+    /// Parse `1A2B-3C4D` or `1a2b3c4d`.
+    ///
+    /// # Errors
+    ///
+    /// [`VolumeIdError::Serial`] when `text` is not eight hex digits with an
+    /// optional `-` after the fourth.
+    pub fn parse(text: &str) -> Result<Self, VolumeIdError> {
+        let digits = match text.split_once('-') {
+            Some((high, low)) if high.len() == 4 && low.len() == 4 => [high, low].concat(),
+            Some(_) => return Err(VolumeIdError::Serial(text.to_owned())),
+            None => text.to_owned(),
+        };
+        if digits.len() != 8 || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(VolumeIdError::Serial(text.to_owned()));
+        }
+        u32::from_str_radix(&digits, 16)
+            .map(Self)
+            .map_err(|_err| VolumeIdError::Serial(text.to_owned()))
+    }
+
+    /// The serial as a number.
+    #[inline]
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Debug for VolumeSerial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VolumeSerial({self})")
+    }
+}
+
+impl fmt::Display for VolumeSerial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04X}-{:04X}", self.0 >> 16_u32, self.0 & 0xFFFF)
+    }
+}
+
+/// A volume's identity: drive letter plus the host it was captured on,
+/// and the volume serial when the capture recorded one.
+///
+/// `None` host means the daemon's own machine.  Ordering sorts local
+/// volumes first, then by host label, then by letter, then by serial —
+/// the natural listing order for an estate.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VolumeId {
+    /// Host label; `None` for local volumes.
+    host: Option<HostLabel>,
+    /// Drive letter on that host.
+    letter: DriveLetter,
+    /// Serial of the volume; `None` when unknown.
+    serial: Option<VolumeSerial>,
+}
+
+impl VolumeId {
+    /// The local volume mounted at `letter`.
+    #[inline]
+    #[must_use]
+    pub const fn local(letter: DriveLetter) -> Self {
+        Self {
+            host: None,
+            letter,
+            serial: None,
+        }
+    }
+
+    /// Volume `letter` captured on `host`.
+    #[inline]
+    #[must_use]
+    pub const fn hosted(host: HostLabel, letter: DriveLetter) -> Self {
+        Self {
+            host: Some(host),
+            letter,
+            serial: None,
+        }
+    }
+
+    /// Build from an optional host.
+    #[inline]
+    #[must_use]
+    pub const fn new(host: Option<HostLabel>, letter: DriveLetter) -> Self {
+        Self {
+            host,
+            letter,
+            serial: None,
+        }
+    }
+
+    /// The same volume, identified by `serial` as well.
+    #[inline]
+    #[must_use]
+    pub const fn with_serial(self, serial: Option<VolumeSerial>) -> Self {
+        Self { serial, ..self }
+    }
+
+    /// The drive letter.
+    #[inline]
+    #[must_use]
+    pub const fn letter(self) -> DriveLetter {
+        self.letter
+    }
+
+    /// The host label, `None` for local volumes.
+    #[inline]
+    #[must_use]
+    pub const fn host(self) -> Option<HostLabel> {
+        self.host
+    }
+
+    /// The volume serial, `None` when the capture did not record one.
+    #[inline]
+    #[must_use]
+    pub const fn serial(self) -> Option<VolumeSerial> {
+        self.serial
+    }
+
+    /// The host label text, `""` for local volumes.
+    #[inline]
+    #[must_use]
+    pub fn host_str(self) -> &'static str {
+        self.host.map_or("", HostLabel::as_str)
+    }
+
+    /// Whether this is one of the daemon's own volumes.
+    #[inline]
+    #[must_use]
+    pub const fn is_local(self) -> bool {
+        self.host.is_none()
+    }
+
+    /// Whether `self`, used as a search selector, selects `volume`.
+    ///
+    /// A bare letter selects that letter on every host; a host-qualified
+    /// id selects that letter on its host, whatever the serial; a
+    /// serial-qualified id selects only the identical volume.
+    #[inline]
+    #[must_use]
+    pub fn selects(self, volume: Self) -> bool {
+        self.letter == volume.letter
+            && (self.host.is_none() || self.host == volume.host)
+            && (self.serial.is_none() || self.serial == volume.serial)
+    }
+
+    /// Parse `C`, `C:`, `srv01\C`, `srv01\C:` or `srv01/C`, each with an
+    /// optional `#1A2B-3C4D` serial suffix.
+    ///
+    /// # Errors
+    ///
+    /// [`VolumeIdError`] when the host label, the letter or the serial is
+    /// invalid.
+    pub fn parse(text: &str) -> Result<Self, VolumeIdError> {
+        let (qualified, serial) = match text.split_once('#') {
+            Some((volume, serial)) => (volume, Some(VolumeSerial::parse(serial)?)),
+            None => (text, None),
+        };
+        let (host, volume) = match qualified.rsplit_once(['\\', '/']) {
+            Some((host, volume)) => (Some(HostLabel::parse(host)?), volume),
+            None => (None, qualified),
+        };
+        let letter = volume.strip_suffix(':').unwrap_or(volume).parse()?;
+        Ok(Self {
+            host,
+            letter,
+            serial,
+        })
+    }
+
+    /// Display root of this volume's paths under the installed
+    /// [`PathTemplate`] (`C:\`, `srv01\C:\`, `/evidence/srv01/C/`).
+    #[must_use]
+    pub fn display_root(self) -> Cow<'static, str> {
+        PathTemplate::current().root(self)
+    }
+
+    /// Separator between path components below [`Self::display_root`].
+    #[must_use]
+    pub fn display_separator(self) -> char {
+        if self.is_local() {
+            '\\'
+        } else {
+            PathTemplate::current().separator()
+        }
+    }
+
+    /// Drive column label: `C:`, `srv01\C:`, or `srv01\C:#1A2B-3C4D` when
+    /// the serial is known.  Parses back with [`Self::parse`].
+    #[must_use]
+    pub fn drive_label(self) -> String {
+        let mut label = self
+            .host
+            .map_or_else(String::new, |host| format!("{host}\\"));
+        label.push(self.letter.as_char());
+        label.push(':');
+        if let Some(serial) = self.serial {
+            label.push('#');
+            label.push_str(&serial.to_string());
+        }
+        label
+    }
+
+    /// Filename-safe stem for per-volume files (caches, captures):
+    /// `C` for local volumes, `srv01@C` for hosted ones, with `@1A2B-3C4D`
+    /// appended when the serial is known.
+    #[must_use]
+    pub fn file_stem(self) -> String {
+        let mut stem = self.host.map_or_else(
+            || self.letter.to_string(),
+            |host| format!("{host}@{}", self.letter),
+        );
+        if let Some(serial) = self.serial {
+            stem.push('@');
+            stem.push_str(&serial.to_string());
+        }
+        stem
+    }
+}
+
+impl From<DriveLetter> for VolumeId {
+    #[inline]
+    fn from(letter: DriveLetter) -> Self {
+        Self::local(letter)
+    }
+}
+
+/// A bare letter equals only the local volume with that letter.
+impl PartialEq<DriveLetter> for VolumeId {
+    #[inline]
+    fn eq(&self, other: &DriveLetter) -> bool {
+        self.host.is_none() && self.serial.is_none() && self.letter == *other
+    }
+}
+
+impl fmt::Debug for VolumeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VolumeId({self})")
+    }
+}
+
+impl fmt::Display for VolumeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.host {
+            Some(host) => write!(f, "{host}\\{}", self.letter)?,
+            None => fmt::Display::fmt(&self.letter, f)?,
+        }
+        self.serial.map_or(Ok(()), |serial| write!(f, "#{serial}"))
+    }
+}
+
+impl core::str::FromStr for VolumeId {
+    type Err = VolumeIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl serde::Serialize for VolumeId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.host.is_none() && self.serial.is_none() {
+            // Byte-identical to `DriveLetter`'s encoding for local volumes.
+            serializer.serialize_char(self.letter.as_char())
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for VolumeId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VolumeIdVisitor;
+
+        impl serde::de::Visitor<'_> for VolumeIdVisitor {
+            type Value = VolumeId;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter
+                    .write_str("a drive letter or a host-qualified volume such as \"srv01\\\\C\"")
+            }
+
+            fn visit_char<E: serde::de::Error>(self, v: char) -> Result<Self::Value, E> {
+                DriveLetter::parse(v)
+                    .map(VolumeId::local)
+                    .map_err(serde::de::Error::custom)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                VolumeId::parse(v).map_err(serde::de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_str(VolumeIdVisitor)
+    }
+}
+
+/// Display template for hosted volumes' path roots.
+///
+/// Placeholders: `{host}`, `{letter}` (required) and `{serial}` (the
+/// volume serial, empty when unknown).  The separator used
+/// for the rest of the path follows the template: a template containing
+/// `/` and no `\` renders `/`-separated paths, anything else keeps `\`.
+///
+/// | Template                  | `srv01\C` file `Users\a.txt`     |
+/// |---------------------------|----------------------------------|
+/// | `{host}\{letter}:` (default) | `srv01\C:\Users\a.txt`        |
+/// | `/evidence/{host}/{letter}`  | `/evidence/srv01/C/Users/a.txt` |
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PathTemplate(Box<str>);
+
+impl PathTemplate {
+    /// The default template: `srv01\C:\…`.
+    pub const DEFAULT: &'static str = "{host}\\{letter}:";
+
+    /// Validate `template`.
+    ///
+    /// # Errors
+    ///
+    /// [`VolumeIdError::Template`] when `{letter}` is missing.
+    pub fn parse(template: &str) -> Result<Self, VolumeIdError> {
+        if template.contains("{letter}") {
+            Ok(Self(template.into()))
+        } else {
+            Err(VolumeIdError::Template(template.to_owned()))
+        }
+    }
+
+    /// Path separator implied by the template.
+    #[must_use]
+    pub fn separator(&self) -> char {
+        if self.0.contains('/') && !self.0.contains('\\') {
+            '/'
+        } else {
+            '\\'
+        }
+    }
+
+    /// Display root for `volume`, including the trailing separator.
+    ///
+    /// Local volumes always render as `C:\`; the template only shapes
+    /// hosted volumes.
+    #[must_use]
+    #[expect(
+        clippy::literal_string_with_formatting_args,
+        reason = "template placeholders, not format arguments"
+    )]
+    pub fn root(&self, volume: VolumeId) -> Cow<'static, str> {
+        let Some(host) = volume.host else {
+            return Cow::Borrowed(local_root(volume.letter));
+        };
+        let separator = self.separator();
+        let serial = volume
+            .serial
+            .map_or_else(String::new, |serial| serial.to_string());
+        let mut root = self
+            .0
+            .replace("{host}", host.as_str())
+            .replace("{letter}", volume.letter.as_str())
+            .replace("{serial}", &serial);
+        if !root.ends_with(separator) {
+            root.push(separator);
+        }
+        Cow::Owned(root)
+    }
+
+    /// Make `self` the process-wide template returned by [`Self::current`].
+    ///
+    /// Set once at daemon startup, before any volume loads.  Returns
+    /// `false` (and keeps the first template) when one was already set.
+    pub fn install(self) -> bool {
+        INSTALLED_TEMPLATE.set(self).is_ok()
+    }
+
+    /// The installed template, or the default when none was installed.
+    #[must_use]
+    pub fn current() -> &'static Self {
+        INSTALLED_TEMPLATE.get_or_init(Self::default)
+    }
+}
+
+/// Process-wide display template (see [`PathTemplate::install`]).
+static INSTALLED_TEMPLATE: OnceLock<PathTemplate> = OnceLock::new();
+
+/// `X:\` for a local volume without allocating.
+fn local_root(letter: DriveLetter) -> &'static str {
+    const ROOTS: [&str; 26] = [
+        "A:\\", "B:\\", "C:\\", "D:\\", "E:\\", "F:\\", "G:\\", "H:\\", "I:\\", "J:\\", "K:\\",
+        "L:\\", "M:\\", "N:\\", "O:\\", "P:\\", "Q:\\", "R:\\", "S:\\", "T:\\", "U:\\", "V:\\",
+        "W:\\", "X:\\", "Y:\\", "Z:\\",
+    ];
+    ROOTS
+        .get(letter.alphabet_index())
+        .copied()
+        .unwrap_or("?:\\")
+}
+
+impl Default for PathTemplate {
+    fn default() -> Self {
+        Self(Self::DEFAULT.into())
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DriveLetter, HostLabel, PathTemplate, VolumeId, VolumeIdError, VolumeSerial};
+
+    #[test]
+    fn parse_round_trips_local_and_hosted_forms() {
+        for (text, display) in [
+            ("C", "C"),
+            ("c:", "C"),
+            ("srv01\\C", "srv01\\C"),
+            ("SRV01\\d:", "srv01\\D"),
+            ("srv01/E", "srv01\\E"),
+        ] {
+            let volume = VolumeId::parse(text).unwrap();
+            assert_eq!(volume.to_string(), display);
+            assert_eq!(VolumeId::parse(display).unwrap(), volume);
+        }
+        assert!(matches!(
+            VolumeId::parse("srv 01\\C"),
+            Err(VolumeIdError::Host(_))
+        ));
+        assert!(matches!(
+            VolumeId::parse("srv01\\CD"),
+            Err(VolumeIdError::Letter(_))
+        ));
+    }
+
+    #[test]
+    fn host_labels_are_interned_case_insensitively() {
+        let lower = HostLabel::parse("ws-0042").unwrap();
+        let upper = HostLabel::parse("WS-0042").unwrap();
+        assert_eq!(lower, upper);
+        assert!(core::ptr::eq(lower.as_str(), upper.as_str()));
+    }
+
+    #[test]
+    fn a_sealed_table_only_finds_registered_hosts() {
+        // `intern(_, false)` is what `parse` does once the table is sealed;
+        // sealing itself is process-wide, so it is not flipped here.
+        assert_eq!(
+            HostLabel::intern("Never-Loaded-7f3a", false),
+            Err(VolumeIdError::UnknownHost("never-loaded-7f3a".to_owned()))
+        );
+        let registered = HostLabel::register("never-loaded-7f3a").unwrap();
+        assert_eq!(
+            HostLabel::intern("NEVER-LOADED-7F3A", false),
+            Ok(registered)
+        );
+        assert!(matches!(
+            HostLabel::intern("bad label", false),
+            Err(VolumeIdError::Host(_))
+        ));
+    }
+
+    #[test]
+    fn bare_letters_select_every_host() {
+        let srv01 = HostLabel::parse("srv01").unwrap();
+        let srv02 = HostLabel::parse("srv02").unwrap();
+        let bare = VolumeId::local(DriveLetter::C);
+        let exact = VolumeId::hosted(srv01, DriveLetter::C);
+        assert!(bare.selects(VolumeId::hosted(srv02, DriveLetter::C)));
+        assert!(bare.selects(bare));
+        assert!(exact.selects(exact));
+        assert!(!exact.selects(VolumeId::hosted(srv02, DriveLetter::C)));
+        assert!(!exact.selects(bare));
+        assert!(!bare.selects(VolumeId::local(DriveLetter::D)));
+    }
+
+    #[test]
+    fn serde_keeps_the_drive_letter_wire_form_for_local_volumes() {
+        let local = VolumeId::local(DriveLetter::C);
+        assert_eq!(serde_json::to_string(&local).unwrap(), "\"C\"");
+        let hosted = VolumeId::parse("srv01\\C").unwrap();
+        let json = serde_json::to_string(&hosted).unwrap();
+        assert_eq!(json, "\"srv01\\\\C\"");
+        assert_eq!(serde_json::from_str::<VolumeId>(&json).unwrap(), hosted);
+        assert_eq!(
+            serde_json::from_str::<VolumeId>("\"d\"").unwrap().letter(),
+            DriveLetter::D
+        );
+    }
+
+    #[test]
+    fn templates_render_display_roots() {
+        let hosted = VolumeId::parse("srv01\\C").unwrap();
+        let local = VolumeId::local(DriveLetter::C);
+        let default = PathTemplate::default();
+        assert_eq!(default.root(hosted), "srv01\\C:\\");
+        assert_eq!(default.root(local), "C:\\");
+        assert_eq!(default.separator(), '\\');
+
+        let evidence = PathTemplate::parse("/evidence/{host}/{letter}").unwrap();
+        assert_eq!(evidence.root(hosted), "/evidence/srv01/C/");
+        assert_eq!(evidence.root(local), "C:\\");
+        assert_eq!(evidence.separator(), '/');
+
+        assert!(matches!(
+            PathTemplate::parse("/evidence/{host}"),
+            Err(VolumeIdError::Template(_))
+        ));
+    }
+
+    #[test]
+    fn file_stems_are_filename_safe() {
+        assert_eq!(VolumeId::local(DriveLetter::C).file_stem(), "C");
+        assert_eq!(VolumeId::parse("srv01\\C").unwrap().file_stem(), "srv01@C");
+        assert_eq!(
+            VolumeId::parse("srv01\\C#1a2b3c4d").unwrap().file_stem(),
+            "srv01@C@1A2B-3C4D"
+        );
+    }
+
+    #[test]
+    fn serials_tell_apart_volumes_sharing_a_host_and_letter() {
+        let old_disk = VolumeId::parse("srv01\\C#1A2B-3C4D").unwrap();
+        let new_disk = VolumeId::parse("srv01\\C:#5E6F7A8B").unwrap();
+        assert_ne!(old_disk, new_disk);
+        assert_eq!(old_disk.to_string(), "srv01\\C#1A2B-3C4D");
+        assert_eq!(old_disk.drive_label(), "srv01\\C:#1A2B-3C4D");
+        assert_eq!(VolumeId::parse(&old_disk.drive_label()).unwrap(), old_disk);
+        assert_eq!(VolumeId::local(DriveLetter::C).drive_label(), "C:");
+        assert_eq!(VolumeId::parse(&new_disk.to_string()).unwrap(), new_disk);
+        assert_eq!(
+            old_disk.serial(),
+            Some(VolumeSerial::from_boot(0x9999_0000_1A2B_3C4D))
+        );
+
+        let host_only = VolumeId::parse("srv01\\C").unwrap();
+        assert!(host_only.selects(old_disk) && host_only.selects(new_disk));
+        assert!(old_disk.selects(old_disk) && !old_disk.selects(new_disk));
+
+        let json = serde_json::to_string(&old_disk).unwrap();
+        assert_eq!(serde_json::from_str::<VolumeId>(&json).unwrap(), old_disk);
+        assert!(matches!(
+            VolumeId::parse("srv01\\C#1A2B-3C"),
+            Err(VolumeIdError::Serial(_))
+        ));
+    }
+}
@@ -65,8 +65,9 @@ use crate::frs::{Frs, ParentFrs};
 ///
 /// [`Usn::ZERO`] mirrors the Win32 convention that `0` means *"no prior
 /// checkpoint — read from journal head"*.  Use [`Self::is_zero`] for
-/// readability at the wrap-detection / first-run call sites.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// readability at the wrap-detection / first-run call sites.  It is also
+/// the [`Default`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Usn(i64);
 
 impl Usn {
@@ -319,6 +320,9 @@ pub struct FileChange {
     /// backfilled it (USN records carry none). `None` → the applier leaves
     /// the record's metrics zeroed for a later re-warm to fill.
     pub meta: Option<RecordMeta>,
+    /// USN of the newest record aggregated into this change
+    /// ([`Usn::ZERO`] for a change that did not come from the journal).
+    pub usn: Usn,
 }
 
 /// Aggregates multiple USN records into per-file changes.
@@ -335,6 +339,7 @@ pub fn aggregate_changes(records: &[UsnRecord]) -> HashMap<Frs, FileChange> {
             ..Default::default()
         });
         entry.parent_frs = record.parent_frs;
+        entry.usn = entry.usn.max(record.usn);
         if !record.filename.is_empty() {
             entry.filename.clone_from(&record.filename);
         }
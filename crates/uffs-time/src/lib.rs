@@ -1,12 +1,14 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (c) 2025-2026 SKY, LLC.
 
-//! NTFS FILETIME arithmetic — pure `const fn`, zero dependencies.
+//! NTFS FILETIME arithmetic — pure `const fn` — and IANA time zones.
 //!
 //! Windows stores timestamps as FILETIME: 100-nanosecond ticks since
 //! 1601-01-01 UTC.  This crate provides the minimum helpers needed to:
 //!
 //! - Apply a timezone bias to a raw FILETIME ([`filetime_with_tz_bias`])
+//! - Resolve a named zone's offset per instant, daylight saving included
+//!   ([`TimeZone`])
 //! - Decompose a FILETIME into calendar fields ([`filetime_to_calendar`])
 //! - Convert to Unix microseconds for legacy callers
 //!   ([`filetime_to_unix_micros`])
@@ -48,7 +50,10 @@
 // merged into `doc_cfg` in Rust 1.92 (rust-lang/rust#138907); the unified
 // `doc_cfg` feature preserves the automatic cfg-badge inference behaviour.
 #![cfg_attr(docsrs, feature(doc_cfg))]
-#![no_std]
+
+mod tz;
+
+pub use tz::TimeZone;
 
 /// Number of 100-nanosecond intervals per second.
 pub const FILETIME_TICKS_PER_SECOND: i64 = 10_000_000;
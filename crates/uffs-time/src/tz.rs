@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (c) 2025-2026 SKY, LLC.
+
+//! Time zones: a fixed offset from UTC, or a named IANA zone whose offset
+//! follows its full history of standard-time and daylight-saving changes.
+//!
+//! A fixed offset is right for half the year at best: `+1` shows Berlin's
+//! summer timestamps an hour early.  [`TimeZone`] resolves the offset per
+//! instant instead.  Named zones come from the IANA Time Zone Database as
+//! compiled into [`chrono_tz`] — every zone and link, with its recorded
+//! transitions through 2099 — so no tz database is read from the host and
+//! the same zone name gives the same answer on every machine.
+
+use core::fmt;
+
+use chrono::{DateTime, LocalResult, Offset as _, TimeZone as _};
+use chrono_tz::{GapInfo, TZ_VARIANTS, Tz};
+
+use crate::{FILETIME_TICKS_PER_SECOND, FILETIME_UNIX_DIFF};
+
+/// Largest fixed offset accepted, in hours either side of UTC (UTC+14 is
+/// the easternmost zone in use).
+const MAX_OFFSET_HOURS: i32 = 14;
+
+/// What a [`TimeZone`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Repr {
+    /// A fixed offset, seconds east of UTC.
+    Fixed(i32),
+    /// An IANA zone.
+    Named(Tz),
+}
+
+/// The zone timestamps are shown and calendar dates are read in.
+///
+/// Either a fixed offset from UTC ([`TimeZone::fixed`]) or a named IANA
+/// zone ([`TimeZone::named`]) with its recorded offset changes.  Parse
+/// either form from user input with [`TimeZone::parse`]; `Display`
+/// prints the zone name, or `UTC` / `UTC+02:00` for an offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeZone(Repr);
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+impl TimeZone {
+    /// Coordinated Universal Time.
+    pub const UTC: Self = Self(Repr::Fixed(0));
+
+    /// A fixed offset of `offset_secs` seconds east of UTC.
+    #[inline]
+    #[must_use]
+    pub const fn fixed(offset_secs: i32) -> Self {
+        Self(Repr::Fixed(offset_secs))
+    }
+
+    /// The IANA zone or link called `name`, matched case-insensitively
+    /// (`Europe/Berlin`, `asia/calcutta`, `US/Eastern`).  `None` for a
+    /// name the database does not know.
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        name.parse::<Tz>()
+            .ok()
+            .or_else(|| {
+                TZ_VARIANTS
+                    .iter()
+                    .copied()
+                    .find(|zone| zone.name().eq_ignore_ascii_case(name))
+            })
+            .map(|zone| Self(Repr::Named(zone)))
+    }
+
+    /// Parse user input: an IANA zone name (`Europe/Berlin`), or a fixed
+    /// offset — `UTC` / `Z`, `+2`, `-5`, `+05:30`, `+0530`, optionally
+    /// after a `UTC` / `GMT` prefix (`UTC+2`).
+    ///
+    /// `None` for an unknown name or an offset beyond ±14 hours.
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        let trimmed = input.trim();
+        parse_offset(trimmed)
+            .map(Self::fixed)
+            .or_else(|| Self::named(trimmed))
+    }
+
+    /// The IANA name of a named zone; `None` for a fixed offset.
+    #[must_use]
+    pub fn name(self) -> Option<&'static str> {
+        match self.0 {
+            Repr::Fixed(_) => None,
+            Repr::Named(zone) => Some(zone.name()),
+        }
+    }
+
+    /// The offset of a fixed-offset zone; `None` for a named zone.
+    #[must_use]
+    pub const fn fixed_offset(self) -> Option<i32> {
+        match self.0 {
+            Repr::Fixed(offset_secs) => Some(offset_secs),
+            Repr::Named(_) => None,
+        }
+    }
+
+    /// Offset from UTC in force at the UTC instant `filetime`, in seconds
+    /// east.
+    #[must_use]
+    pub fn offset_at(self, filetime: i64) -> i32 {
+        match self.0 {
+            Repr::Fixed(offset_secs) => offset_secs,
+            Repr::Named(zone) => {
+                DateTime::from_timestamp(unix_secs(filetime), 0_u32).map_or(0_i32, |instant| {
+                    zone.offset_from_utc_datetime(&instant.naive_utc())
+                        .fix()
+                        .local_minus_utc()
+                })
+            }
+        }
+    }
+
+    /// Shift the UTC instant `filetime` to local wall-clock ticks, ready
+    /// for [`crate::filetime_to_calendar`].
+    #[must_use]
+    pub fn to_local(self, filetime: i64) -> i64 {
+        filetime + secs_to_ticks(self.offset_at(filetime))
+    }
+
+    /// The UTC instant at which local wall-clock ticks `local` occur.
+    ///
+    /// In the hour skipped when clocks go forward the time is read with
+    /// the offset before the switch, landing just after it; in the hour
+    /// repeated when clocks go back the earlier instant wins.
+    #[must_use]
+    pub fn local_to_utc(self, local: i64) -> i64 {
+        let offset_secs = match self.0 {
+            Repr::Fixed(offset_secs) => offset_secs,
+            Repr::Named(zone) => local_offset(zone, local),
+        };
+        local - secs_to_ticks(offset_secs)
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Named(zone) => f.write_str(zone.name()),
+            Repr::Fixed(0_i32) => f.write_str("UTC"),
+            Repr::Fixed(offset_secs) => {
+                let sign = if offset_secs < 0_i32 { '-' } else { '+' };
+                let minutes = offset_secs.unsigned_abs() / 60_u32;
+                write!(
+                    f,
+                    "UTC{sign}{:02}:{:02}",
+                    minutes / 60_u32,
+                    minutes % 60_u32
+                )
+            }
+        }
+    }
+}
+
+/// Offset `zone` reads the local wall-clock ticks `local` with: see
+/// [`TimeZone::local_to_utc`].
+fn local_offset(zone: Tz, local: i64) -> i32 {
+    let Some(wall) = DateTime::from_timestamp(unix_secs(local), 0_u32).map(|dt| dt.naive_utc())
+    else {
+        return 0_i32;
+    };
+    match zone.offset_from_local_datetime(&wall) {
+        LocalResult::Single(offset) | LocalResult::Ambiguous(offset, _) => {
+            offset.fix().local_minus_utc()
+        }
+        LocalResult::None => GapInfo::new(&wall, &zone)
+            .and_then(|gap| gap.begin)
+            .map_or(0_i32, |(_, offset)| offset.fix().local_minus_utc()),
+    }
+}
+
+/// `secs` as FILETIME ticks.
+const fn secs_to_ticks(secs: i32) -> i64 {
+    crate::filetime_with_tz_bias(0, secs)
+}
+
+/// Whole Unix seconds of `filetime`, rounded towards the past.
+const fn unix_secs(filetime: i64) -> i64 {
+    (filetime - FILETIME_UNIX_DIFF).div_euclid(FILETIME_TICKS_PER_SECOND)
+}
+
+/// `input` without a case-insensitive `prefix`.
+fn strip_prefix_ignore_case<'input>(input: &'input str, prefix: &str) -> Option<&'input str> {
+    input
+        .get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .and_then(|_| input.get(prefix.len()..))
+}
+
+/// A fixed offset, seconds east: see [`TimeZone::parse`].
+fn parse_offset(input: &str) -> Option<i32> {
+    let rest = strip_prefix_ignore_case(input, "UTC")
+        .or_else(|| strip_prefix_ignore_case(input, "GMT"))
+        .unwrap_or(input);
+    if rest.is_empty() || rest.eq_ignore_ascii_case("Z") {
+        return Some(0_i32);
+    }
+    let (sign, digits) = match rest.split_at_checked(1)? {
+        ("+", digits) => (1_i32, digits),
+        ("-", digits) => (-1_i32, digits),
+        _ => return None,
+    };
+    let (hour_digits, minute_digits) = match digits.split_once(':') {
+        Some(parts) => parts,
+        None if digits.len() == 4 => digits.split_at_checked(2)?,
+        None => (digits, "0"),
+    };
+    let is_number = |text: &str| !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit());
+    if !is_number(hour_digits) || !is_number(minute_digits) {
+        return None;
+    }
+    let hours: i32 = hour_digits.parse().ok()?;
+    let minutes: i32 = minute_digits.parse().ok()?;
+    if !(0_i32..60_i32).contains(&minutes) || hours > MAX_OFFSET_HOURS {
+        return None;
+    }
+    let secs = hours * 3600_i32 + minutes * 60_i32;
+    (secs <= MAX_OFFSET_HOURS * 3600_i32).then_some(sign * secs)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::ToString as _;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::{CalendarParts, filetime_to_calendar};
+
+    /// FILETIME of a UTC wall-clock time.
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+        let secs = NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .expect("valid date")
+            .and_utc()
+            .timestamp();
+        secs * FILETIME_TICKS_PER_SECOND + FILETIME_UNIX_DIFF
+    }
+
+    fn zone(name: &str) -> TimeZone {
+        TimeZone::named(name).expect("IANA zone")
+    }
+
+    #[test]
+    fn berlin_follows_summer_time() {
+        let berlin = zone("europe/berlin");
+        assert_eq!(berlin.offset_at(utc(2024, 1, 15, 12, 0)), 3600_i32);
+        assert_eq!(berlin.offset_at(utc(2024, 7, 15, 12, 0)), 7200_i32);
+        // 2025-03-30 01:00 UTC: clocks go forward.
+        assert_eq!(berlin.offset_at(utc(2025, 3, 30, 0, 59)), 3600_i32);
+        assert_eq!(berlin.offset_at(utc(2025, 3, 30, 1, 0)), 7200_i32);
+        // 2025-10-26 01:00 UTC: clocks go back.
+        assert_eq!(berlin.offset_at(utc(2025, 10, 26, 0, 59)), 7200_i32);
+        assert_eq!(berlin.offset_at(utc(2025, 10, 26, 1, 0)), 3600_i32);
+        // 1990: summer time still ended in September.
+        assert_eq!(berlin.offset_at(utc(1990, 10, 10, 12, 0)), 3600_i32);
+        assert_eq!(
+            filetime_to_calendar(berlin.to_local(utc(2024, 7, 15, 12, 0))),
+            Some(CalendarParts {
+                year: 2024,
+                month: 7,
+                day: 15,
+                hour: 14,
+                minute: 0,
+                second: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn historical_offset_changes_are_honoured() {
+        let moscow = zone("Europe/Moscow");
+        // MSK+DST until 2011, permanent +4 from 2011 to 2014, +3 since.
+        assert_eq!(moscow.offset_at(utc(2005, 1, 15, 12, 0)), 10_800_i32);
+        assert_eq!(moscow.offset_at(utc(2005, 7, 15, 12, 0)), 14_400_i32);
+        assert_eq!(moscow.offset_at(utc(2013, 1, 15, 12, 0)), 14_400_i32);
+        assert_eq!(moscow.offset_at(utc(2015, 7, 15, 12, 0)), 10_800_i32);
+        // Minsk: EET with summer time until 2011, then +3 year-round.
+        let minsk = zone("Europe/Minsk");
+        assert_eq!(minsk.offset_at(utc(2005, 1, 15, 12, 0)), 7200_i32);
+        assert_eq!(minsk.offset_at(utc(2012, 1, 15, 12, 0)), 10_800_i32);
+        // Istanbul kept EET/EEST until 2016.
+        let istanbul = zone("Europe/Istanbul");
+        assert_eq!(istanbul.offset_at(utc(2010, 1, 15, 12, 0)), 7200_i32);
+        assert_eq!(istanbul.offset_at(utc(2010, 7, 15, 12, 0)), 10_800_i32);
+        assert_eq!(istanbul.offset_at(utc(2018, 1, 15, 12, 0)), 10_800_i32);
+        // Sydney's summer time ended on 2007-03-25, a week before the
+        // April rule that started in 2008.
+        let sydney = zone("Australia/Sydney");
+        assert_eq!(sydney.offset_at(utc(2007, 3, 30, 0, 0)), 36_000_i32);
+        assert_eq!(sydney.offset_at(utc(2008, 3, 30, 0, 0)), 39_600_i32);
+        // Cairo observed summer time in 2010 but not in 2012.
+        let cairo = zone("Africa/Cairo");
+        assert_eq!(cairo.offset_at(utc(2010, 7, 1, 12, 0)), 10_800_i32);
+        assert_eq!(cairo.offset_at(utc(2012, 7, 1, 12, 0)), 7200_i32);
+        // Mid-March is daylight time in New York since 2007 only.
+        let new_york = zone("America/New_York");
+        assert_eq!(new_york.offset_at(utc(2006, 3, 15, 12, 0)), -18_000_i32);
+        assert_eq!(new_york.offset_at(utc(2007, 3, 15, 12, 0)), -14_400_i32);
+        // 2024-03-10 02:00 EST = 07:00 UTC.
+        assert_eq!(new_york.offset_at(utc(2024, 3, 10, 6, 59)), -18_000_i32);
+        assert_eq!(new_york.offset_at(utc(2024, 3, 10, 7, 0)), -14_400_i32);
+    }
+
+    #[test]
+    fn every_database_zone_resolves() {
+        let chatham = zone("Pacific/Chatham");
+        assert_eq!(chatham.offset_at(utc(2024, 7, 1, 0, 0)), 45_900_i32);
+        assert_eq!(chatham.offset_at(utc(2024, 1, 1, 0, 0)), 49_500_i32);
+        assert_eq!(
+            zone("America/Argentina/Ushuaia").offset_at(utc(2024, 7, 1, 0, 0)),
+            -10_800_i32
+        );
+        assert_eq!(
+            zone("America/Phoenix").offset_at(utc(2024, 7, 1, 0, 0)),
+            -25_200_i32
+        );
+        let eastern = zone("US/Eastern");
+        let new_york = zone("America/New_York");
+        for instant in [utc(1975, 1, 15, 0, 0), utc(2024, 7, 1, 0, 0)] {
+            assert_eq!(eastern.offset_at(instant), new_york.offset_at(instant));
+        }
+    }
+
+    #[test]
+    fn local_times_map_back_through_gaps_and_overlaps() {
+        let berlin = zone("Europe/Berlin");
+        let noon = utc(2024, 7, 15, 12, 0);
+        assert_eq!(berlin.local_to_utc(berlin.to_local(noon)), noon);
+        // 02:30 on 2025-03-30 does not exist; it reads as 03:30 CEST.
+        assert_eq!(
+            berlin.local_to_utc(utc(2025, 3, 30, 2, 30)),
+            utc(2025, 3, 30, 1, 30)
+        );
+        // 02:30 on 2025-10-26 happens twice; the first (CEST) wins.
+        assert_eq!(
+            berlin.local_to_utc(utc(2025, 10, 26, 2, 30)),
+            utc(2025, 10, 26, 0, 30)
+        );
+        assert_eq!(
+            TimeZone::fixed(-18_000).local_to_utc(0),
+            180_000_000_000_i64
+        );
+    }
+
+    #[test]
+    fn names_and_offsets_parse_and_display() {
+        assert_eq!(TimeZone::parse("UTC"), Some(TimeZone::UTC));
+        assert_eq!(TimeZone::parse("z"), Some(TimeZone::UTC));
+        assert_eq!(TimeZone::parse("+2"), Some(TimeZone::fixed(7200)));
+        assert_eq!(TimeZone::parse("UTC-5"), Some(TimeZone::fixed(-18_000)));
+        assert_eq!(TimeZone::parse("+05:30"), Some(TimeZone::fixed(19_800)));
+        assert_eq!(TimeZone::parse("gmt+0545"), Some(TimeZone::fixed(20_700)));
+        assert_eq!(TimeZone::parse("+15"), None);
+        assert_eq!(TimeZone::parse("+2:75"), None);
+        assert_eq!(TimeZone::parse("++2"), None);
+        assert_eq!(TimeZone::parse("Mars/Olympus_Mons"), None);
+
+        let calcutta = TimeZone::parse(" asia/calcutta ").expect("link");
+        assert_eq!(calcutta.name(), Some("Asia/Calcutta"));
+        assert_eq!(calcutta.to_string(), "Asia/Calcutta");
+        assert_eq!(TimeZone::UTC.to_string(), "UTC");
+        assert_eq!(TimeZone::fixed(7200).to_string(), "UTC+02:00");
+        assert_eq!(TimeZone::fixed(-19_800).to_string(), "UTC-05:30");
+        assert_eq!(
+            TimeZone::parse(&TimeZone::fixed(-19_800).to_string()),
+            Some(TimeZone::fixed(-19_800))
+        );
+    }
+}